@@ -33,17 +33,26 @@ use databend_query::servers::HttpHandlerKind;
 use databend_query::servers::MySQLHandler;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
+use databend_query::AutoCompactor;
+use databend_query::DataRetentionPurger;
 use databend_query::GlobalServices;
 use tracing::info;
 
 #[databend_main]
-async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<()> {
+async fn main(global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<()> {
     let conf: Config = Config::load()?;
 
     if run_cmd(&conf) {
         return Ok(());
     }
 
+    // The memory trackers of the queries are descendants of the global tracker, the query which
+    // allocates the memory over the limit is killed.
+    global_tracker.get_memory_tracker().set_limit(
+        "max_server_memory_usage",
+        conf.query.max_server_memory_usage as i64,
+    );
+
     init_default_metrics_recorder();
     set_panic_hook();
 
@@ -169,6 +178,14 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         );
     }
 
+    // Background purge of the data out of the retention period.
+    let mut data_retention_purger = DataRetentionPurger::create(&conf);
+    data_retention_purger.start();
+
+    // Background compaction and reclustering of tables.
+    let mut auto_compactor = AutoCompactor::create(&conf);
+    auto_compactor.start();
+
     // Print information to users.
     println!("Databend Query");
     println!();
@@ -234,6 +251,8 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
 
     info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
+    data_retention_purger.shutdown().await?;
+    auto_compactor.shutdown().await?;
     info!("Shutdown server.");
     Ok(())
 }
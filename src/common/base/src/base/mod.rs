@@ -38,6 +38,8 @@ pub use progress::ProgressValues;
 pub use runtime::Dropper;
 pub use runtime::Runtime;
 pub use runtime::TrySpawn;
+pub use runtime_tracker::MemoryTracker;
+pub use runtime_tracker::OutOfLimit;
 pub use runtime_tracker::RuntimeTracker;
 pub use runtime_tracker::ThreadTracker;
 pub use select::select3;
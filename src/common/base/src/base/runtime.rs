@@ -156,9 +156,17 @@ impl Runtime {
         Self::create(None, tracker, &mut runtime_builder)
     }
 
+    pub fn with_worker_threads(workers: usize, thread_name: Option<String>) -> Result<Self> {
+        Self::with_worker_threads_and_tracker(workers, thread_name, RuntimeTracker::create())
+    }
+
+    /// Like `with_worker_threads`, but the threads of the runtime are tracked by `tracker`.
     #[allow(unused_mut)]
-    pub fn with_worker_threads(workers: usize, mut thread_name: Option<String>) -> Result<Self> {
-        let tracker = RuntimeTracker::create();
+    pub fn with_worker_threads_and_tracker(
+        workers: usize,
+        mut thread_name: Option<String>,
+        tracker: Arc<RuntimeTracker>,
+    ) -> Result<Self> {
         let mut runtime_builder = Self::tracker_builder(tracker.clone());
 
         #[cfg(debug_assertions)]
@@ -14,10 +14,15 @@
 
 use std::alloc::GlobalAlloc;
 use std::alloc::Layout;
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
 use std::sync::atomic::AtomicI64;
+use std::sync::atomic::AtomicU8;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use once_cell::sync::OnceCell;
+
 use crate::mem_allocator::GlobalAllocator;
 
 #[thread_local]
@@ -64,11 +69,11 @@ impl ThreadTracker {
                 (*TRACKER).untracked_memory += size;
 
                 if (*TRACKER).untracked_memory > UNTRACKED_MEMORY_LIMIT {
-                    (*TRACKER)
-                        .rt_tracker
-                        .memory_tracker
-                        .alloc_memory((*TRACKER).untracked_memory);
-                    (*TRACKER).untracked_memory = 0;
+                    let size = std::mem::take(&mut (*TRACKER).untracked_memory);
+                    let rt_tracker = &(*TRACKER).rt_tracker;
+                    if let Err(out_of_limit) = rt_tracker.memory_tracker.alloc_memory(size) {
+                        rt_tracker.on_out_of_limit(out_of_limit);
+                    }
                 }
             }
         }
@@ -104,8 +109,19 @@ impl ThreadTracker {
     }
 }
 
+/// The memory usage of a tracker exceeds its limit.
+#[derive(Clone, Copy, Debug)]
+pub struct OutOfLimit {
+    /// The name of the limit exceeded, such as the name of the setting.
+    pub limit_name: &'static str,
+    pub memory_usage: i64,
+    pub limit: i64,
+}
+
 pub struct MemoryTracker {
     memory_usage: AtomicI64,
+    /// The name and the max memory usage of the limit, unlimited if not set.
+    limit: OnceCell<(&'static str, i64)>,
     parent_memory_tracker: Option<Arc<MemoryTracker>>,
 }
 
@@ -114,16 +130,37 @@ impl MemoryTracker {
         Arc::new(MemoryTracker {
             parent_memory_tracker,
             memory_usage: AtomicI64::new(0),
+            limit: OnceCell::new(),
         })
     }
 
+    /// Set the limit of the tracker once, the later calls are ignored. A limit of 0 means
+    /// unlimited, `limit_name` is reported when the limit is exceeded.
+    pub fn set_limit(&self, limit_name: &'static str, limit: i64) {
+        let _ = self.limit.set((limit_name, limit));
+    }
+
+    /// Track the allocated memory in the tracker and all its parents. The memory is always
+    /// tracked, the limit of the innermost tracker exceeded is returned as the error.
     #[inline]
-    pub fn alloc_memory(&self, size: i64) {
-        self.memory_usage.fetch_add(size, Ordering::Relaxed);
+    pub fn alloc_memory(&self, size: i64) -> Result<(), OutOfLimit> {
+        let memory_usage = self.memory_usage.fetch_add(size, Ordering::Relaxed) + size;
 
-        if let Some(parent_memory_tracker) = &self.parent_memory_tracker {
-            parent_memory_tracker.alloc_memory(size);
+        let parent_result = match &self.parent_memory_tracker {
+            None => Ok(()),
+            Some(parent_memory_tracker) => parent_memory_tracker.alloc_memory(size),
+        };
+
+        if let Some((limit_name, limit)) = self.limit.get() {
+            if *limit > 0 && memory_usage > *limit {
+                return Err(OutOfLimit {
+                    limit_name,
+                    memory_usage,
+                    limit: *limit,
+                });
+            }
         }
+        parent_result
     }
 
     #[inline]
@@ -154,13 +191,31 @@ impl MemoryTracker {
 
 pub struct RuntimeTracker {
     memory_tracker: Arc<MemoryTracker>,
+    /// The first limit exceeded by the memory tracker or any of its parents.
+    out_of_limit: UnsafeCell<MaybeUninit<OutOfLimit>>,
+    /// The state of `out_of_limit`, one of `OUT_OF_LIMIT_*`.
+    out_of_limit_state: AtomicU8,
 }
 
+const OUT_OF_LIMIT_EMPTY: u8 = 0;
+const OUT_OF_LIMIT_WRITING: u8 = 1;
+const OUT_OF_LIMIT_READY: u8 = 2;
+
+// `out_of_limit` is written once by the thread that moves the state out of empty and is read
+// only after the state is ready.
+unsafe impl Sync for RuntimeTracker {}
+
 impl RuntimeTracker {
     pub fn create() -> Arc<RuntimeTracker> {
         let parent_memory_tracker = MemoryTracker::current();
+        Self::create_with_memory_tracker(MemoryTracker::create(parent_memory_tracker))
+    }
+
+    pub fn create_with_memory_tracker(memory_tracker: Arc<MemoryTracker>) -> Arc<RuntimeTracker> {
         Arc::new(RuntimeTracker {
-            memory_tracker: MemoryTracker::create(parent_memory_tracker),
+            memory_tracker,
+            out_of_limit: UnsafeCell::new(MaybeUninit::uninit()),
+            out_of_limit_state: AtomicU8::new(OUT_OF_LIMIT_EMPTY),
         })
     }
 
@@ -169,6 +224,34 @@ impl RuntimeTracker {
         &self.memory_tracker
     }
 
+    /// The limit exceeded by the threads of the tracker, which are expected to check it
+    /// and abort their work.
+    #[inline]
+    pub fn get_out_of_limit(&self) -> Option<OutOfLimit> {
+        match self.out_of_limit_state.load(Ordering::Acquire) {
+            OUT_OF_LIMIT_READY => Some(unsafe { (*self.out_of_limit.get()).assume_init() }),
+            _ => None,
+        }
+    }
+
+    // Called in the allocator, it must neither allocate nor block.
+    fn on_out_of_limit(&self, out_of_limit: OutOfLimit) {
+        if self
+            .out_of_limit_state
+            .compare_exchange(
+                OUT_OF_LIMIT_EMPTY,
+                OUT_OF_LIMIT_WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+        {
+            unsafe { (*self.out_of_limit.get()).write(out_of_limit) };
+            self.out_of_limit_state
+                .store(OUT_OF_LIMIT_READY, Ordering::Release);
+        }
+    }
+
     pub fn on_stop_thread(self: &Arc<Self>) -> impl Fn() {
         move || unsafe {
             let tracker = std::mem::replace(&mut TRACKER, std::ptr::null_mut());
@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
 use std::thread::Builder;
 use std::thread::JoinHandle;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::runtime_tracker::RuntimeTracker;
 use super::runtime_tracker::ThreadTracker;
 
 pub struct Thread;
@@ -46,7 +48,21 @@ impl<T> ThreadJoinHandle<T> {
 }
 
 impl Thread {
-    pub fn named_spawn<F, T>(mut name: Option<String>, f: F) -> ThreadJoinHandle<T>
+    pub fn named_spawn<F, T>(name: Option<String>, f: F) -> ThreadJoinHandle<T>
+    where
+        F: FnOnce() -> T,
+        F: Send + 'static,
+        T: Send + 'static,
+    {
+        Self::named_spawn_with_tracker(name, ThreadTracker::current_runtime_tracker(), f)
+    }
+
+    /// Spawn a thread tracked by `runtime_tracker` instead of the tracker of the current thread.
+    pub fn named_spawn_with_tracker<F, T>(
+        mut name: Option<String>,
+        runtime_tracker: Option<Arc<RuntimeTracker>>,
+        f: F,
+    ) -> ThreadJoinHandle<T>
     where
         F: FnOnce() -> T,
         F: Send + 'static,
@@ -68,7 +84,7 @@ impl Thread {
             thread_builder = thread_builder.name(named);
         }
 
-        ThreadJoinHandle::create(match ThreadTracker::current_runtime_tracker() {
+        ThreadJoinHandle::create(match runtime_tracker {
             None => thread_builder.spawn(f).unwrap(),
             Some(runtime_tracker) => thread_builder
                 .spawn(move || {
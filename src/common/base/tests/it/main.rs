@@ -18,5 +18,6 @@ mod progress;
 mod range_key_test;
 mod range_map_test;
 mod runtime;
+mod runtime_tracker;
 mod stoppable;
 mod string_func;
@@ -0,0 +1,56 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::*;
+
+#[test]
+fn test_memory_tracker_limit() {
+    let parent = MemoryTracker::create(None);
+    let child = MemoryTracker::create(Some(parent.clone()));
+    parent.set_limit("parent_limit", 100);
+    child.set_limit("child_limit", 50);
+
+    assert!(child.alloc_memory(40).is_ok());
+
+    // The innermost tracker exceeded is reported.
+    let out_of_limit = child.alloc_memory(20).unwrap_err();
+    assert_eq!(out_of_limit.limit_name, "child_limit");
+    assert_eq!(out_of_limit.memory_usage, 60);
+    assert_eq!(out_of_limit.limit, 50);
+
+    // The memory is tracked by the parent even if the child exceeds its limit.
+    assert_eq!(parent.get_memory_usage(), 60);
+
+    let other_child = MemoryTracker::create(Some(parent.clone()));
+    let out_of_limit = other_child.alloc_memory(50).unwrap_err();
+    assert_eq!(out_of_limit.limit_name, "parent_limit");
+    assert_eq!(out_of_limit.memory_usage, 110);
+    assert_eq!(out_of_limit.limit, 100);
+
+    child.dealloc_memory(60);
+    other_child.dealloc_memory(50);
+    assert_eq!(parent.get_memory_usage(), 0);
+    assert!(other_child.alloc_memory(100).is_ok());
+}
+
+#[test]
+fn test_memory_tracker_set_limit_once() {
+    let tracker = MemoryTracker::create(None);
+    tracker.set_limit("first_limit", 10);
+    tracker.set_limit("second_limit", 100);
+
+    let out_of_limit = tracker.alloc_memory(20).unwrap_err();
+    assert_eq!(out_of_limit.limit_name, "first_limit");
+    assert_eq!(out_of_limit.limit, 10);
+}
@@ -100,6 +100,7 @@ build_exceptions! {
     TooManyUserConnections(1041),
     AbortedSession(1042),
     AbortedQuery(1043),
+    MemoryLimitExceeded(1044),
     CannotListenerPort(1045),
     BadBytes(1046),
     InitPrometheusFailure(1047),
@@ -154,6 +155,24 @@ build_exceptions! {
     ///
     /// For example: try to with 3 columns into a table with 4 columns.
     TableSchemaMismatch(1303),
+    /// TableColumnAlreadyExists is used when users try to add or rename to a column
+    /// that already exists in the table.
+    TableColumnAlreadyExists(1304),
+    /// TableColumnAlterInvalid is used when a column can not be altered as requested.
+    ///
+    /// For example: narrow the type of a column, or drop the last column of a table.
+    TableColumnAlterInvalid(1305),
+    /// TableMergeMultipleMatches is used when a row of the target table of MERGE INTO
+    /// is matched by more than one row of the source.
+    TableMergeMultipleMatches(1306),
+
+    // Transaction related errors starts here.
+
+    /// UnsupportedInTransaction is used when users run a statement that can not be
+    /// part of an explicit transaction.
+    ///
+    /// For example: DELETE between BEGIN and COMMIT.
+    UnsupportedInTransaction(1401),
 }
 
 // Metasvr errors [2001, 3000].
@@ -170,6 +189,9 @@ build_exceptions! {
     OCCRetryFailure(2011),
     TableNotWritable(2012),
     TableHistoricalDataNotFound(2013),
+    /// StreamOffsetChanged is used when the offset of a stream, which is consumed by a DML,
+    /// is moved by another statement before the DML commits.
+    StreamOffsetChanged(2014),
 
     // User api error codes.
     UnknownUser(2201),
@@ -200,6 +222,8 @@ build_exceptions! {
     /// - having the same name as a already exist, like `default`
     /// - and without `IF NOT EXISTS`
     CatalogAlreadyExists(2319),
+    IndexAlreadyExists(2320),
+    StreamAlreadyExists(2321),
 
     // Cluster error codes.
     ClusterUnknownNode(2401),
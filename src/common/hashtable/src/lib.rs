@@ -37,6 +37,8 @@ pub use traits::FastHash;
 pub use traits::HashtableLike;
 pub use traits::Keyable as HashtableKeyable;
 pub use traits::UnsizedKeyable as HashtableUnsizedKeyable;
+pub use twolevel_hashtable::bucket_of as twolevel_bucket_of;
+pub use twolevel_hashtable::BUCKETS as TWOLEVEL_BUCKETS;
 
 pub type Hashed<K> = utils::Hashed<K>;
 
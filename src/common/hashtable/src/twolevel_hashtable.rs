@@ -30,9 +30,15 @@ use super::table0::Table0IterMut;
 use super::traits::Keyable;
 use super::utils::ZeroEntry;
 
-const BUCKETS: usize = 256;
+pub const BUCKETS: usize = 256;
 const BUCKETS_LG2: u32 = 8;
 
+/// Returns the index of the bucket which a key with the given hash belongs to.
+#[inline(always)]
+pub fn bucket_of(hash: u64) -> usize {
+    hash as usize >> (64u32 - BUCKETS_LG2)
+}
+
 type Tables<K, V, A> = [Table0<K, V, HeapContainer<Entry<K, V>, A>, A>; BUCKETS];
 
 pub struct TwolevelHashtable<K, V, A = MmapAllocator<GlobalAllocator>>
@@ -107,7 +113,7 @@ where
             }
         }
         let hash = K::hash(key);
-        let index = hash as usize >> (64u32 - BUCKETS_LG2);
+        let index = bucket_of(hash);
         unsafe { self.tables[index].get_with_hash(key, hash) }
     }
     #[inline(always)]
@@ -124,7 +130,7 @@ where
             }
         }
         let hash = K::hash(key);
-        let index = hash as usize >> (64u32 - BUCKETS_LG2);
+        let index = bucket_of(hash);
         unsafe { self.tables[index].get_with_hash_mut(key, hash) }
     }
     #[inline(always)]
@@ -156,7 +162,7 @@ where
             }
         }
         let hash = K::hash(&key);
-        let index = hash as usize >> (64u32 - BUCKETS_LG2);
+        let index = bucket_of(hash);
         if unlikely((self.tables[index].len() + 1) * 2 > self.tables[index].capacity()) {
             if (self.tables[index].entries.len() >> 14) == 0 {
                 self.tables[index].grow(2);
@@ -212,7 +218,7 @@ where
                 let key = entry.key.assume_init();
                 let val = std::ptr::read(entry.val.assume_init_ref());
                 let hash = K::hash(&key);
-                let index = hash as usize >> (64u32 - BUCKETS_LG2);
+                let index = bucket_of(hash);
                 if unlikely((res.tables[index].len() + 1) * 2 > res.tables[index].capacity()) {
                     if (res.tables[index].entries.len() >> 14) == 0 {
                         res.tables[index].grow(2);
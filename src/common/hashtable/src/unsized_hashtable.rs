@@ -120,6 +120,16 @@ where
             + self.table3.capacity()
             + self.table4.capacity()
     }
+    /// Approximate bytes of memory held by the table, including the arena of keys.
+    #[inline(always)]
+    pub fn allocated_bytes(&self) -> usize {
+        self.arena.allocated_bytes()
+            + self.table0.capacity() * std::mem::size_of::<Entry<[u8; 2], V>>()
+            + self.table1.capacity() * std::mem::size_of::<Entry<InlineKey<0>, V>>()
+            + self.table2.capacity() * std::mem::size_of::<Entry<InlineKey<1>, V>>()
+            + self.table3.capacity() * std::mem::size_of::<Entry<InlineKey<2>, V>>()
+            + self.table4.capacity() * std::mem::size_of::<Entry<FallbackKey, V>>()
+    }
     #[inline(always)]
     pub fn entry(&self, key: &K) -> Option<UnsizedHashtableEntryRef<'_, K, V>> {
         let key = key.as_bytes();
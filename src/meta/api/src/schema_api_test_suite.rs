@@ -55,6 +55,7 @@ use common_meta_app::schema::TableStatistics;
 use common_meta_app::schema::TruncateTableReq;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_app::schema::UpsertTableOptionReq;
@@ -2079,6 +2080,75 @@ impl SchemaApiTestSuite {
                 assert_eq!(ErrorCode::TABLE_VERSION_MISMATCHED, err.code());
             }
         }
+
+        info!("--- update multi table meta");
+        {
+            let req = CreateTableReq {
+                if_not_exists: false,
+                name_ident: TableNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                    table_name: "tb3".to_string(),
+                },
+                table_meta: table_meta(Utc::now()),
+            };
+            mt.create_table(req).await?;
+
+            let update_req = |table: &TableInfo, seq: u64, data_bytes: u64| {
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.statistics = TableStatistics {
+                    data_bytes,
+                    ..Default::default()
+                };
+                UpdateTableMetaReq {
+                    table_id: table.ident.table_id,
+                    seq: MatchSeq::Exact(seq),
+                    new_table_meta,
+                }
+            };
+
+            info!("--- update multi table meta, normal case");
+            {
+                let tb2 = mt.get_table((tenant, "db1", "tb2").into()).await?;
+                let tb3 = mt.get_table((tenant, "db1", "tb3").into()).await?;
+
+                mt.update_multi_table_meta(UpdateMultiTableMetaReq {
+                    update_table_metas: vec![
+                        update_req(&tb2, tb2.ident.seq, 2),
+                        update_req(&tb3, tb3.ident.seq, 3),
+                    ],
+                })
+                .await?;
+
+                let tb2 = mt.get_table((tenant, "db1", "tb2").into()).await?;
+                let tb3 = mt.get_table((tenant, "db1", "tb3").into()).await?;
+                assert_eq!(2, tb2.meta.statistics.data_bytes);
+                assert_eq!(3, tb3.meta.statistics.data_bytes);
+            }
+
+            info!("--- update multi table meta: version mismatch, none is updated");
+            {
+                let tb2 = mt.get_table((tenant, "db1", "tb2").into()).await?;
+                let tb3 = mt.get_table((tenant, "db1", "tb3").into()).await?;
+
+                let res = mt
+                    .update_multi_table_meta(UpdateMultiTableMetaReq {
+                        update_table_metas: vec![
+                            update_req(&tb2, tb2.ident.seq, 4),
+                            update_req(&tb3, tb3.ident.seq + 1, 5),
+                        ],
+                    })
+                    .await;
+
+                let err = ErrorCode::from(res.unwrap_err());
+                assert_eq!(ErrorCode::TABLE_VERSION_MISMATCHED, err.code());
+
+                let tb2 = mt.get_table((tenant, "db1", "tb2").into()).await?;
+                let tb3 = mt.get_table((tenant, "db1", "tb3").into()).await?;
+                assert_eq!(2, tb2.meta.statistics.data_bytes);
+                assert_eq!(3, tb3.meta.statistics.data_bytes);
+            }
+        }
         Ok(())
     }
 
@@ -153,11 +153,13 @@ impl FromStr for StageFileFormatType {
             "NDJSON" | "JSONEACHROW" => Ok(StageFileFormatType::NdJson),
             "PARQUET" => Ok(StageFileFormatType::Parquet),
             "XML" => Ok(StageFileFormatType::Xml),
-            "ORC" | "AVRO" | "JSON" => Err(format!(
-                "File format type '{s}' not implemented yet', must be one of ( CSV | TSV | NDJSON | PARQUET | XML)"
+            "AVRO" => Ok(StageFileFormatType::Avro),
+            "ORC" => Ok(StageFileFormatType::Orc),
+            "JSON" => Err(format!(
+                "File format type '{s}' not implemented yet', must be one of ( CSV | TSV | NDJSON | PARQUET | XML | AVRO | ORC)"
             )),
             _ => Err(format!(
-                "Unknown file format type '{s}', must be one of ( CSV | TSV | NDJSON | PARQUET | XML)"
+                "Unknown file format type '{s}', must be one of ( CSV | TSV | NDJSON | PARQUET | XML | AVRO | ORC)"
             )),
         }
     }
@@ -218,6 +220,7 @@ impl FromStr for OnErrorMode {
             "" => Ok(OnErrorMode::None),
             "CONTINUE" => Ok(OnErrorMode::Continue),
             "SKIP_FILE" => Ok(OnErrorMode::SkipFile),
+            "ABORT_STATEMENT" => Ok(OnErrorMode::AbortStatement),
             v => {
                 let num_str = v.replace("SKIP_FILE_", "");
                 let nums = num_str.parse::<u64>();
@@ -21,6 +21,7 @@ use common_exception::Result;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -152,6 +153,8 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// The window specification if it's a window function call, like `SUM(a) OVER (PARTITION BY b)`
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -242,6 +245,52 @@ pub enum MapAccessor<'a> {
     Colon { key: Identifier<'a> },
 }
 
+/// The window of a window function call, `OVER window_name` or `OVER (window_spec)`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Window<'a> {
+    WindowReference(WindowRef<'a>),
+    WindowSpec(WindowSpec<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowRef<'a> {
+    pub window_name: Identifier<'a>,
+}
+
+/// `[existing_window_name] [PARTITION BY ...] [ORDER BY ...] [frame_clause]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpec<'a> {
+    pub existing_window_name: Option<Identifier<'a>>,
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    pub window_frame: Option<WindowFrame<'a>>,
+}
+
+/// `ROWS | RANGE frame_start` or `ROWS | RANGE BETWEEN frame_start AND frame_end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFrame<'a> {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound<'a>,
+    pub end_bound: WindowFrameBound<'a>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+/// The bound of a window frame, `None` offset means `UNBOUNDED`
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowFrameBound<'a> {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<Box<Expr<'a>>>),
+    /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<Box<Expr<'a>>>),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypeName {
     Boolean,
@@ -594,6 +643,73 @@ impl Display for TypeName {
     }
 }
 
+impl<'a> Display for Window<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Window::WindowReference(window_ref) => write!(f, "{}", window_ref.window_name),
+            Window::WindowSpec(window_spec) => write!(f, "({window_spec})"),
+        }
+    }
+}
+
+impl<'a> Display for WindowSpec<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        if let Some(existing_window_name) = &self.existing_window_name {
+            write!(f, "{existing_window_name}")?;
+            first = false;
+        }
+
+        if !self.partition_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+
+        if !self.order_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+
+        if let Some(frame) = &self.window_frame {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for WindowFrame<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.units {
+            WindowFrameUnits::Rows => write!(f, "ROWS")?,
+            WindowFrameUnits::Range => write!(f, "RANGE")?,
+        }
+        write!(f, " BETWEEN {} AND {}", self.start_bound, self.end_bound)
+    }
+}
+
+impl<'a> Display for WindowFrameBound<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(offset)) => write!(f, "{offset} PRECEDING"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(offset)) => write!(f, "{offset} FOLLOWING"),
+        }
+    }
+}
+
 impl Display for TrimWhere {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         f.write_str(match self {
@@ -787,6 +903,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -801,6 +918,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER {window}")?;
+                }
             }
             Expr::Case {
                 operand,
@@ -456,6 +456,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        _window: &'ast Option<Window<'ast>>,
     ) {
         let mut children = Vec::with_capacity(args.len());
         for arg in args.iter() {
@@ -773,6 +774,12 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let validation_mode_node = FormatTreeNode::new(validation_mode_format_ctx);
             children.push(validation_mode_node);
         }
+        if !copy.on_error.is_empty() {
+            let on_error_name = format!("OnError {}", copy.on_error);
+            let on_error_format_ctx = AstFormatContext::new(on_error_name);
+            let on_error_node = FormatTreeNode::new(on_error_format_ctx);
+            children.push(on_error_node);
+        }
         let size_limit_name = format!("SizeLimit {}", copy.size_limit);
         let size_limit_format_ctx = AstFormatContext::new(size_limit_name);
         let size_limit_node = FormatTreeNode::new(size_limit_format_ctx);
@@ -844,6 +851,27 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_begin(&mut self) {
+        let name = "Begin".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_commit(&mut self) {
+        let name = "Commit".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_rollback(&mut self) {
+        let name = "Rollback".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_show_process_list(&mut self) {
         let name = "ShowProcessList".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -1011,6 +1039,64 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_merge_into(&mut self, merge_into: &'ast MergeIntoStmt<'ast>) {
+        let mut children = Vec::new();
+        self.visit_table_reference(&merge_into.target);
+        children.push(self.children.pop().unwrap());
+        self.visit_table_reference(&merge_into.source);
+        children.push(self.children.pop().unwrap());
+        self.visit_expr(&merge_into.join_expr);
+        children.push(self.children.pop().unwrap());
+
+        for clause in merge_into.clauses.iter() {
+            let mut clause_children = Vec::new();
+            let (condition, clause_name) = match clause {
+                MergeClause::Matched {
+                    condition,
+                    action: MatchedAction::Update { update_list },
+                } => {
+                    for update_expr in update_list.iter() {
+                        self.visit_identifier(&update_expr.name);
+                        clause_children.push(self.children.pop().unwrap());
+                        self.visit_expr(&update_expr.expr);
+                        clause_children.push(self.children.pop().unwrap());
+                    }
+                    (condition, "MatchedClause Update".to_string())
+                }
+                MergeClause::Matched {
+                    condition,
+                    action: MatchedAction::Delete,
+                } => (condition, "MatchedClause Delete".to_string()),
+                MergeClause::NotMatched {
+                    condition,
+                    columns,
+                    values,
+                } => {
+                    for column in columns.iter() {
+                        self.visit_identifier(column);
+                        clause_children.push(self.children.pop().unwrap());
+                    }
+                    for value in values.iter() {
+                        self.visit_expr(value);
+                        clause_children.push(self.children.pop().unwrap());
+                    }
+                    (condition, "NotMatchedClause".to_string())
+                }
+            };
+            if let Some(condition) = condition {
+                self.visit_expr(condition);
+                clause_children.push(self.children.pop().unwrap());
+            }
+            let format_ctx = AstFormatContext::with_children(clause_name, clause_children.len());
+            children.push(FormatTreeNode::with_children(format_ctx, clause_children));
+        }
+
+        let name = "MergeInto".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
     fn visit_show_databases(&mut self, stmt: &'ast ShowDatabasesStmt<'ast>) {
         let mut children = Vec::new();
         if let Some(limit) = &stmt.limit {
@@ -1240,6 +1326,32 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let node = FormatTreeNode::with_children(format_ctx, vec![child]);
                 self.children.push(node);
             }
+            CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            } => {
+                let mut children = Vec::with_capacity(2);
+                self.visit_table_ref(catalog, database, table);
+                children.push(self.children.pop().unwrap());
+                if let Some(travel_point) = travel_point {
+                    self.visit_time_travel_point(travel_point);
+                    children.push(self.children.pop().unwrap());
+                }
+                let name = "CloneTable".to_string();
+                let format_ctx = AstFormatContext::with_children(name, children.len());
+                let node = FormatTreeNode::with_children(format_ctx, children);
+                self.children.push(node);
+            }
+            CreateTableSource::Template(query) => {
+                self.visit_query(query);
+                let child = self.children.pop().unwrap();
+                let name = "TemplateQuery".to_string();
+                let format_ctx = AstFormatContext::with_children(name, 1);
+                let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+                self.children.push(node);
+            }
         }
     }
 
@@ -1311,6 +1423,40 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                     AstFormatContext::with_children(action_name, children.len());
                 FormatTreeNode::with_children(action_format_ctx, children)
             }
+            AlterTableAction::AddColumn { column } => {
+                self.visit_column_definition(column);
+                let column_child = self.children.pop().unwrap();
+                let action_name = "Action AddColumn".to_string();
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![column_child])
+            }
+            AlterTableAction::DropColumn { column } => {
+                let action_name = format!("Action DropColumn {}", column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let action_name = format!("Action RenameColumn {} To {}", old_column, new_column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::ModifyColumn { column } => {
+                self.visit_column_definition(column);
+                let column_child = self.children.pop().unwrap();
+                let action_name = "Action ModifyColumn".to_string();
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![column_child])
+            }
+            AlterTableAction::FlashbackTo { point } => {
+                self.visit_time_travel_point(point);
+                let point_child = self.children.pop().unwrap();
+                let action_name = "Action FlashbackTo".to_string();
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![point_child])
+            }
         };
 
         let name = "AlterTable".to_string();
@@ -1357,6 +1503,16 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_analyze_table(&mut self, stmt: &'ast AnalyzeTableStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let child = self.children.pop().unwrap();
+
+        let name = "AnalyzeTable".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_exists_table(&mut self, stmt: &'ast ExistsTableStmt<'ast>) {
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
         let child = self.children.pop().unwrap();
@@ -1367,13 +1523,40 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_index(&mut self, stmt: &'ast CreateIndexStmt<'ast>) {
+        let mut children = Vec::new();
+        self.visit_identifier(&stmt.index_name);
+        children.push(self.children.pop().unwrap());
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        children.push(self.children.pop().unwrap());
+        let mut columns_children = Vec::with_capacity(stmt.columns.len());
+        for column in stmt.columns.iter() {
+            self.visit_identifier(column);
+            columns_children.push(self.children.pop().unwrap());
+        }
+        let columns_name = "Columns".to_string();
+        let columns_format_ctx =
+            AstFormatContext::with_children(columns_name, columns_children.len());
+        let columns_node = FormatTreeNode::with_children(columns_format_ctx, columns_children);
+        children.push(columns_node);
+
+        let name = "CreateIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
     fn visit_create_view(&mut self, stmt: &'ast CreateViewStmt<'ast>) {
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
         let view_child = self.children.pop().unwrap();
         self.visit_query(&stmt.query);
         let query_child = self.children.pop().unwrap();
 
-        let name = "CreateView".to_string();
+        let name = if stmt.materialized {
+            "CreateMaterializedView".to_string()
+        } else {
+            "CreateView".to_string()
+        };
         let format_ctx = AstFormatContext::with_children(name, 2);
         let node = FormatTreeNode::with_children(format_ctx, vec![view_child, query_child]);
         self.children.push(node);
@@ -1401,6 +1584,28 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_stream(&mut self, stmt: &'ast CreateStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let stream_child = self.children.pop().unwrap();
+        self.visit_table_ref(&None, &stmt.table_database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "CreateStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![stream_child, table_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_stream(&mut self, stmt: &'ast DropStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let child = self.children.pop().unwrap();
+
+        let name = "DropStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_show_users(&mut self) {
         let name = "ShowUsers".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -2150,17 +2355,40 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let node = FormatTreeNode::with_children(format_ctx, vec![child]);
                 self.children.push(node);
             }
+            TableReference::Stage {
+                span: _,
+                name,
+                path,
+                options,
+                alias,
+            } => {
+                let stage_name = format!("Stage @{}{}{}", name, path, options);
+                let format_ctx = if let Some(alias) = alias {
+                    AstFormatContext::with_children_alias(stage_name, 0, Some(format!("{}", alias)))
+                } else {
+                    AstFormatContext::new(stage_name)
+                };
+                let node = FormatTreeNode::new(format_ctx);
+                self.children.push(node);
+            }
             TableReference::TableFunction {
                 span: _,
                 name,
                 params,
+                named_params,
                 alias,
             } => {
-                let mut children = Vec::with_capacity(params.len());
+                let mut children = Vec::with_capacity(params.len() + named_params.len());
                 for param in params.iter() {
                     self.visit_expr(param);
                     children.push(self.children.pop().unwrap());
                 }
+                for (name, value) in named_params.iter() {
+                    self.visit_expr(value);
+                    let child = self.children.pop().unwrap();
+                    let format_ctx = AstFormatContext::with_children(format!("{name}=>"), 1);
+                    children.push(FormatTreeNode::with_children(format_ctx, vec![child]));
+                }
                 let func_name = format!("TableFunction {}", name);
                 let format_ctx = if let Some(alias) = alias {
                     AstFormatContext::with_children_alias(
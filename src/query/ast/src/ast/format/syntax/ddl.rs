@@ -130,6 +130,34 @@ fn pretty_table_source(source: CreateTableSource) -> RcDoc {
                 RcDoc::nil()
             })
             .append(RcDoc::text(table.to_string())),
+        CreateTableSource::Clone {
+            catalog,
+            database,
+            table,
+            travel_point,
+        } => RcDoc::space()
+            .append(RcDoc::text("CLONE"))
+            .append(RcDoc::space())
+            .append(if let Some(catalog) = catalog {
+                RcDoc::text(catalog.to_string()).append(RcDoc::text("."))
+            } else {
+                RcDoc::nil()
+            })
+            .append(if let Some(database) = database {
+                RcDoc::text(database.to_string()).append(RcDoc::text("."))
+            } else {
+                RcDoc::nil()
+            })
+            .append(RcDoc::text(table.to_string()))
+            .append(if let Some(travel_point) = travel_point {
+                RcDoc::text(format!(" AT {travel_point}"))
+            } else {
+                RcDoc::nil()
+            }),
+        CreateTableSource::Template(query) => RcDoc::space()
+            .append(RcDoc::text("USING TEMPLATE"))
+            .append(RcDoc::space())
+            .append(parenthenized(pretty_query(*query))),
     }
 }
 
@@ -180,11 +208,37 @@ pub(crate) fn pretty_alter_table_action(action: AlterTableAction) -> RcDoc {
             } else {
                 RcDoc::nil()
             }),
+        AlterTableAction::AddColumn { column } => RcDoc::line()
+            .append(RcDoc::text("ADD COLUMN "))
+            .append(RcDoc::text(column.to_string())),
+        AlterTableAction::DropColumn { column } => RcDoc::line()
+            .append(RcDoc::text("DROP COLUMN "))
+            .append(RcDoc::text(column.to_string())),
+        AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        } => RcDoc::line()
+            .append(RcDoc::text("RENAME COLUMN "))
+            .append(RcDoc::text(old_column.to_string()))
+            .append(RcDoc::text(" TO "))
+            .append(RcDoc::text(new_column.to_string())),
+        AlterTableAction::ModifyColumn { column } => RcDoc::line()
+            .append(RcDoc::text("MODIFY COLUMN "))
+            .append(RcDoc::text(column.to_string())),
+        AlterTableAction::FlashbackTo { point } => RcDoc::line()
+            .append(RcDoc::text("FLASHBACK TO "))
+            .append(RcDoc::text(point.to_string())),
     }
 }
 
 pub(crate) fn pretty_create_view(stmt: CreateViewStmt) -> RcDoc {
-    RcDoc::text("CREATE VIEW")
+    RcDoc::text("CREATE")
+        .append(if stmt.materialized {
+            RcDoc::space().append(RcDoc::text("MATERIALIZED"))
+        } else {
+            RcDoc::nil()
+        })
+        .append(RcDoc::space().append(RcDoc::text("VIEW")))
         .append(if stmt.if_not_exists {
             RcDoc::space().append(RcDoc::text("IF NOT EXISTS"))
         } else {
@@ -220,6 +220,7 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
             name,
             args,
             params,
+            window,
             ..
         } => RcDoc::text(name.to_string())
             .append(if !params.is_empty() {
@@ -240,7 +241,15 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
                 RcDoc::nil()
             })
             .append(inline_comma(args.into_iter().map(pretty_expr)))
-            .append(RcDoc::text(")")),
+            .append(RcDoc::text(")"))
+            .append(if let Some(window) = window {
+                RcDoc::space()
+                    .append(RcDoc::text("OVER"))
+                    .append(RcDoc::space())
+                    .append(RcDoc::text(window.to_string()))
+            } else {
+                RcDoc::nil()
+            }),
         Expr::Case {
             operand,
             conditions,
@@ -30,6 +30,7 @@ use crate::ast::SetExpr;
 use crate::ast::SetOperator;
 use crate::ast::TableReference;
 use crate::ast::TimeTravelPoint;
+use crate::ast::WindowDefinition;
 use crate::ast::With;
 use crate::ast::CTE;
 
@@ -79,7 +80,8 @@ fn pretty_body(body: SetExpr) -> RcDoc {
         .append(pretty_from(select_stmt.from))
         .append(pretty_selection(select_stmt.selection))
         .append(pretty_group_by(select_stmt.group_by))
-        .append(pretty_having(select_stmt.having)),
+        .append(pretty_having(select_stmt.having))
+        .append(pretty_window(select_stmt.window_list)),
         SetExpr::Query(query) => parenthenized(pretty_query(*query)),
         SetExpr::SetOperation(set_operation) => pretty_body(*set_operation.left)
             .append(
@@ -192,6 +194,24 @@ fn pretty_having(having: Option<Expr>) -> RcDoc {
     }
 }
 
+fn pretty_window(window_list: Option<Vec<WindowDefinition>>) -> RcDoc {
+    if let Some(window_list) = window_list {
+        RcDoc::line()
+            .append(RcDoc::text("WINDOW").append(RcDoc::line().nest(NEST_FACTOR)))
+            .append(
+                interweave_comma(
+                    window_list
+                        .into_iter()
+                        .map(|window| RcDoc::text(window.to_string())),
+                )
+                .nest(NEST_FACTOR)
+                .group(),
+            )
+    } else {
+        RcDoc::nil()
+    }
+}
+
 pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
     match table {
         TableReference::Table {
@@ -233,14 +253,30 @@ pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
         } else {
             RcDoc::nil()
         }),
+        TableReference::Stage {
+            span: _,
+            name,
+            path,
+            options,
+            alias,
+        } => RcDoc::text(format!("@{name}{path}{options}")).append(if let Some(alias) = alias {
+            RcDoc::text(format!(" AS {alias}"))
+        } else {
+            RcDoc::nil()
+        }),
         TableReference::TableFunction {
             span: _,
             name,
             params,
+            named_params,
             alias,
         } => RcDoc::text(name.to_string())
             .append(RcDoc::text("("))
-            .append(inline_comma(params.into_iter().map(pretty_expr)))
+            .append(inline_comma(params.into_iter().map(pretty_expr).chain(
+                named_params.into_iter().map(|(name, value)| {
+                    RcDoc::text(format!("{name} => ")).append(pretty_expr(value))
+                }),
+            )))
             .append(RcDoc::text(")"))
             .append(if let Some(alias) = alias {
                 RcDoc::text(format!(" AS {alias}"))
@@ -19,6 +19,7 @@ use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Expr;
 use crate::ast::Identifier;
+use crate::ast::WindowSpec;
 use crate::parser::token::Token;
 
 /// Root node of a query tree
@@ -82,6 +83,15 @@ pub struct SelectStmt<'a> {
     pub group_by: Vec<Expr<'a>>,
     // `HAVING` clause
     pub having: Option<Expr<'a>>,
+    // `WINDOW` clause
+    pub window_list: Option<Vec<WindowDefinition<'a>>>,
+}
+
+/// A named window definition in the `WINDOW` clause, like `WINDOW w AS (PARTITION BY a)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowDefinition<'a> {
+    pub name: Identifier<'a>,
+    pub spec: WindowSpec<'a>,
 }
 
 /// A relational set expression, like `SELECT ... FROM ... {UNION|EXCEPT|INTERSECT} SELECT ... FROM ...`
@@ -143,6 +153,15 @@ pub enum TimeTravelPoint<'a> {
     Timestamp(Box<Expr<'a>>),
 }
 
+impl Display for TimeTravelPoint<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeTravelPoint::Snapshot(sid) => write!(f, "(SNAPSHOT => {sid})"),
+            TimeTravelPoint::Timestamp(ts) => write!(f, "(TIMESTAMP => {ts})"),
+        }
+    }
+}
+
 /// A table name or a parenthesized subquery with an optional alias
 #[derive(Debug, Clone, PartialEq)]
 pub enum TableReference<'a> {
@@ -155,11 +174,20 @@ pub enum TableReference<'a> {
         alias: Option<TableAlias<'a>>,
         travel_point: Option<TimeTravelPoint<'a>>,
     },
-    // `TABLE(expr)[ AS alias ]`
+    // `TABLE(expr, ..., name => expr, ...)[ AS alias ]`
     TableFunction {
         span: &'a [Token<'a>],
         name: Identifier<'a>,
         params: Vec<Expr<'a>>,
+        named_params: Vec<(Identifier<'a>, Expr<'a>)>,
+        alias: Option<TableAlias<'a>>,
+    },
+    // `@stage/path [ (FILE_FORMAT => 'type', PATTERN => 'regex', FILES => ('file', ...)) ][ AS alias ]`
+    Stage {
+        span: &'a [Token<'a>],
+        name: String,
+        path: String,
+        options: SelectStageOptions,
         alias: Option<TableAlias<'a>>,
     },
     // Derived table, which can be a subquery or joined tables or combination of them
@@ -174,6 +202,41 @@ pub enum TableReference<'a> {
     },
 }
 
+/// Options of reading the files in a stage as a table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SelectStageOptions {
+    // Overrides the file format of the stage
+    pub file_format: Option<String>,
+    pub pattern: Option<String>,
+    pub files: Vec<String>,
+}
+
+impl Display for SelectStageOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut options = vec![];
+        if let Some(file_format) = &self.file_format {
+            options.push(format!("FILE_FORMAT => '{file_format}'"));
+        }
+        if let Some(pattern) = &self.pattern {
+            options.push(format!("PATTERN => '{pattern}'"));
+        }
+        if !self.files.is_empty() {
+            let files = self
+                .files
+                .iter()
+                .map(|file| format!("'{file}'"))
+                .collect::<Vec<_>>();
+            options.push(format!("FILES => ({})", files.join(", ")));
+        }
+        if !options.is_empty() {
+            write!(f, " (")?;
+            write_comma_separated_list(f, options)?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TableAlias<'a> {
     pub name: Identifier<'a>,
@@ -276,15 +339,37 @@ impl<'a> Display for TableReference<'a> {
                 span: _,
                 name,
                 params,
+                named_params,
                 alias,
             } => {
                 write!(f, "{name}(")?;
                 write_comma_separated_list(f, params)?;
+                if !params.is_empty() && !named_params.is_empty() {
+                    write!(f, ", ")?;
+                }
+                write_comma_separated_list(
+                    f,
+                    named_params
+                        .iter()
+                        .map(|(name, value)| format!("{name} => {value}")),
+                )?;
                 write!(f, ")")?;
                 if let Some(alias) = alias {
                     write!(f, " AS {alias}")?;
                 }
             }
+            TableReference::Stage {
+                span: _,
+                name,
+                path,
+                options,
+                alias,
+            } => {
+                write!(f, "@{name}{path}{options}")?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {alias}")?;
+                }
+            }
             TableReference::Subquery {
                 span: _,
                 subquery,
@@ -409,10 +494,22 @@ impl<'a> Display for SelectStmt<'a> {
             write!(f, " HAVING {having}")?;
         }
 
+        // WINDOW clause
+        if let Some(windows) = &self.window_list {
+            write!(f, " WINDOW ")?;
+            write_comma_separated_list(f, windows)?;
+        }
+
         Ok(())
     }
 }
 
+impl<'a> Display for WindowDefinition<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} AS ({})", self.name, self.spec)
+    }
+}
+
 impl<'a> Display for SetExpr<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -37,6 +37,7 @@ pub struct CopyStmt<'a> {
     pub file_format: BTreeMap<String, String>,
     /// TODO(xuanwo): parse into validation_mode directly.
     pub validation_mode: String,
+    pub on_error: String,
     pub size_limit: usize,
     pub max_file_size: usize,
     pub split_size: usize,
@@ -52,6 +53,7 @@ impl<'a> CopyStmt<'a> {
             CopyOption::Pattern(v) => self.pattern = v,
             CopyOption::FileFormat(v) => self.file_format = v,
             CopyOption::ValidationMode(v) => self.validation_mode = v,
+            CopyOption::OnError(v) => self.on_error = v,
             CopyOption::SizeLimit(v) => self.size_limit = v,
             CopyOption::MaxFileSize(v) => self.max_file_size = v,
             CopyOption::SplitSize(v) => self.split_size = v,
@@ -87,7 +89,11 @@ impl Display for CopyStmt<'_> {
         }
 
         if !self.validation_mode.is_empty() {
-            write!(f, "VALIDATION_MODE = {}", self.validation_mode)?;
+            write!(f, " VALIDATION_MODE = '{}'", self.validation_mode)?;
+        }
+
+        if !self.on_error.is_empty() {
+            write!(f, " ON_ERROR = {}", self.on_error)?;
         }
 
         if self.size_limit != 0 {
@@ -212,6 +218,7 @@ pub enum CopyOption {
     Pattern(String),
     FileFormat(BTreeMap<String, String>),
     ValidationMode(String),
+    OnError(String),
     SizeLimit(usize),
     MaxFileSize(usize),
     SplitSize(usize),
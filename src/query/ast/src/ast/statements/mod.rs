@@ -17,13 +17,16 @@ mod catalog;
 mod copy;
 mod database;
 mod explain;
+mod index;
 mod insert;
 mod kill;
+mod merge_into;
 mod presign;
 mod share;
 mod show;
 mod stage;
 mod statement;
+mod stream;
 mod table;
 mod update;
 mod user;
@@ -34,13 +37,16 @@ pub use catalog::*;
 pub use copy::*;
 pub use database::*;
 pub use explain::*;
+pub use index::*;
 pub use insert::*;
 pub use kill::*;
+pub use merge_into::*;
 pub use presign::*;
 pub use share::*;
 pub use show::*;
 pub use stage::*;
 pub use statement::*;
+pub use stream::*;
 pub use table::*;
 pub use update::*;
 pub use user::*;
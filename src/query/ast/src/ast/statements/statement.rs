@@ -74,6 +74,13 @@ pub enum Statement<'a> {
 
     Update(UpdateStmt<'a>),
 
+    MergeInto(MergeIntoStmt<'a>),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
+
     // Catalogs
     ShowCatalogs(ShowCatalogsStmt<'a>),
     ShowCreateCatalog(ShowCreateCatalogStmt<'a>),
@@ -103,13 +110,21 @@ pub enum Statement<'a> {
     RenameTable(RenameTableStmt<'a>),
     TruncateTable(TruncateTableStmt<'a>),
     OptimizeTable(OptimizeTableStmt<'a>),
+    AnalyzeTable(AnalyzeTableStmt<'a>),
     ExistsTable(ExistsTableStmt<'a>),
 
+    // Indexes
+    CreateIndex(CreateIndexStmt<'a>),
+
     // Views
     CreateView(CreateViewStmt<'a>),
     AlterView(AlterViewStmt<'a>),
     DropView(DropViewStmt<'a>),
 
+    // Streams
+    CreateStream(CreateStreamStmt<'a>),
+    DropStream(DropStreamStmt<'a>),
+
     // User
     ShowUsers,
     CreateUser(CreateUserStmt),
@@ -221,6 +236,10 @@ impl<'a> Display for Statement<'a> {
                 }
             }
             Statement::Update(update) => write!(f, "{update}")?,
+            Statement::MergeInto(merge_into) => write!(f, "{merge_into}")?,
+            Statement::Begin => write!(f, "BEGIN")?,
+            Statement::Commit => write!(f, "COMMIT")?,
+            Statement::Rollback => write!(f, "ROLLBACK")?,
             Statement::Copy(stmt) => write!(f, "{stmt}")?,
             Statement::ShowSettings { like } => {
                 write!(f, "SHOW SETTINGS")?;
@@ -292,10 +311,14 @@ impl<'a> Display for Statement<'a> {
             Statement::RenameTable(stmt) => write!(f, "{stmt}")?,
             Statement::TruncateTable(stmt) => write!(f, "{stmt}")?,
             Statement::OptimizeTable(stmt) => write!(f, "{stmt}")?,
+            Statement::AnalyzeTable(stmt) => write!(f, "{stmt}")?,
             Statement::ExistsTable(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateIndex(stmt) => write!(f, "{stmt}")?,
             Statement::CreateView(stmt) => write!(f, "{stmt}")?,
             Statement::AlterView(stmt) => write!(f, "{stmt}")?,
             Statement::DropView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateStream(stmt) => write!(f, "{stmt}")?,
+            Statement::DropStream(stmt) => write!(f, "{stmt}")?,
             Statement::ShowUsers => write!(f, "SHOW USERS")?,
             Statement::ShowRoles => write!(f, "SHOW ROLES")?,
             Statement::CreateUser(stmt) => write!(f, "{stmt}")?,
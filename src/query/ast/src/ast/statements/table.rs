@@ -24,6 +24,7 @@ use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Query;
 use crate::ast::TableReference;
+use crate::ast::TimeTravelPoint;
 use crate::ast::TypeName;
 use crate::ast::UriLocation;
 
@@ -162,6 +163,15 @@ pub enum CreateTableSource<'a> {
         database: Option<Identifier<'a>>,
         table: Identifier<'a>,
     },
+    Clone {
+        catalog: Option<Identifier<'a>>,
+        database: Option<Identifier<'a>>,
+        table: Identifier<'a>,
+        travel_point: Option<TimeTravelPoint<'a>>,
+    },
+    // `USING TEMPLATE (query)`, each row of the query describes a column by its name, type and
+    // nullability, like the rows of `INFER_SCHEMA`.
+    Template(Box<Query<'a>>),
 }
 
 impl Display for CreateTableSource<'_> {
@@ -180,6 +190,20 @@ impl Display for CreateTableSource<'_> {
                 write!(f, "LIKE ")?;
                 write_period_separated_list(f, catalog.iter().chain(database).chain(Some(table)))
             }
+            CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            } => {
+                write!(f, "CLONE ")?;
+                write_period_separated_list(f, catalog.iter().chain(database).chain(Some(table)))?;
+                if let Some(travel_point) = travel_point {
+                    write!(f, " AT {travel_point}")?;
+                }
+                Ok(())
+            }
+            CreateTableSource::Template(query) => write!(f, "USING TEMPLATE ({query})"),
         }
     }
 }
@@ -284,6 +308,22 @@ pub enum AlterTableAction<'a> {
         is_final: bool,
         selection: Option<Expr<'a>>,
     },
+    AddColumn {
+        column: ColumnDefinition<'a>,
+    },
+    DropColumn {
+        column: Identifier<'a>,
+    },
+    RenameColumn {
+        old_column: Identifier<'a>,
+        new_column: Identifier<'a>,
+    },
+    ModifyColumn {
+        column: ColumnDefinition<'a>,
+    },
+    FlashbackTo {
+        point: TimeTravelPoint<'a>,
+    },
 }
 
 impl Display for AlterTableAction<'_> {
@@ -312,6 +352,24 @@ impl Display for AlterTableAction<'_> {
                 }
                 Ok(())
             }
+            AlterTableAction::AddColumn { column } => {
+                write!(f, "ADD COLUMN {column}")
+            }
+            AlterTableAction::DropColumn { column } => {
+                write!(f, "DROP COLUMN {column}")
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                write!(f, "RENAME COLUMN {old_column} TO {new_column}")
+            }
+            AlterTableAction::ModifyColumn { column } => {
+                write!(f, "MODIFY COLUMN {column}")
+            }
+            AlterTableAction::FlashbackTo { point } => {
+                write!(f, "FLASHBACK TO {point}")
+            }
         }
     }
 }
@@ -403,6 +461,26 @@ impl Display for OptimizeTableStmt<'_> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzeTableStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+}
+
+impl Display for AnalyzeTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ANALYZE TABLE ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExistsTableStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
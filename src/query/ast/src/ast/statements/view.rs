@@ -22,6 +22,7 @@ use crate::ast::Query;
 #[derive(Debug, Clone, PartialEq)]
 pub struct CreateViewStmt<'a> {
     pub if_not_exists: bool,
+    pub materialized: bool,
     pub catalog: Option<Identifier<'a>>,
     pub database: Option<Identifier<'a>>,
     pub view: Identifier<'a>,
@@ -30,7 +31,11 @@ pub struct CreateViewStmt<'a> {
 
 impl Display for CreateViewStmt<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "CREATE VIEW ")?;
+        write!(f, "CREATE ")?;
+        if self.materialized {
+            write!(f, "MATERIALIZED ")?;
+        }
+        write!(f, "VIEW ")?;
         if self.if_not_exists {
             write!(f, "IF NOT EXISTS ")?;
         }
@@ -16,8 +16,10 @@ use itertools::Itertools;
 use nom::branch::alt;
 use nom::combinator::consumed;
 use nom::combinator::map;
+use nom::combinator::peek;
 use nom::combinator::value;
 use nom::error::context;
+use nom::sequence::terminated;
 use pratt::Affix;
 use pratt::Associativity;
 use pratt::PrattParser;
@@ -241,6 +243,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -416,12 +419,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -732,6 +737,22 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             }
         },
     );
+    let count_all_with_window = map(
+        rule! {
+            COUNT ~ "(" ~ "*" ~ ")" ~ #window_function
+        },
+        |(name, _, _, _, window)| ExprElement::FunctionCall {
+            distinct: false,
+            name: Identifier {
+                name: name.text().to_string(),
+                quote: None,
+                span: name.clone(),
+            },
+            args: vec![],
+            params: vec![],
+            window: Some(window),
+        },
+    );
     let function_call = map(
         rule! {
             #function_name
@@ -739,12 +760,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ #window_function?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window,
         },
     );
     let function_call_with_param = map(
@@ -758,6 +781,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window: None,
         },
     );
     let case = map(
@@ -888,6 +912,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
         ),
         rule!(
             #is_distinct_from: "`... IS [NOT] DISTINCT FROM ...`"
+            | #count_all_with_window : "`COUNT(*) OVER ...`"
             | #count_all : "COUNT(*)"
             | #function_call_with_param : "<function>"
             | #function_call : "<function>"
@@ -904,6 +929,96 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub fn window_function(i: Input) -> IResult<Window> {
+    alt((
+        map(
+            rule! {
+                OVER ~ #ident
+            },
+            |(_, window_name)| Window::WindowReference(WindowRef { window_name }),
+        ),
+        map(
+            rule! {
+                OVER ~ "(" ~ #window_spec ~ ^")"
+            },
+            |(_, _, spec, _)| Window::WindowSpec(spec),
+        ),
+    ))(i)
+}
+
+pub fn window_spec(i: Input) -> IResult<WindowSpec> {
+    // `PARTITION`, `ROWS` and `RANGE` are not reserved, make sure they are not taken as window name
+    let existing_window_name = terminated(
+        ident,
+        peek(rule! { PARTITION | ORDER | ROWS | RANGE | ")" }),
+    );
+    map(
+        rule! {
+            #existing_window_name?
+            ~ ( PARTITION ~ ^BY ~ ^#comma_separated_list1(subexpr(0)) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+            ~ #window_frame?
+        },
+        |(existing_window_name, opt_partition, opt_order, window_frame)| WindowSpec {
+            existing_window_name,
+            partition_by: opt_partition.map(|(_, _, p)| p).unwrap_or_default(),
+            order_by: opt_order.map(|(_, _, o)| o).unwrap_or_default(),
+            window_frame,
+        },
+    )(i)
+}
+
+pub fn window_frame(i: Input) -> IResult<WindowFrame> {
+    let units = map(rule! { ROWS | RANGE }, |token| match token.kind {
+        ROWS => WindowFrameUnits::Rows,
+        RANGE => WindowFrameUnits::Range,
+        _ => unreachable!(),
+    });
+    alt((
+        map(
+            rule! {
+                #units ~ BETWEEN ~ ^#window_frame_bound ~ ^AND ~ ^#window_frame_bound
+            },
+            |(units, _, start_bound, _, end_bound)| WindowFrame {
+                units,
+                start_bound,
+                end_bound,
+            },
+        ),
+        // `ROWS frame_start` is the shorthand of `ROWS BETWEEN frame_start AND CURRENT ROW`
+        map(
+            rule! {
+                #units ~ #window_frame_bound
+            },
+            |(units, start_bound)| WindowFrame {
+                units,
+                start_bound,
+                end_bound: WindowFrameBound::CurrentRow,
+            },
+        ),
+    ))(i)
+}
+
+pub fn window_frame_bound(i: Input) -> IResult<WindowFrameBound> {
+    alt((
+        value(WindowFrameBound::CurrentRow, rule! { CURRENT ~ ROW }),
+        value(
+            WindowFrameBound::Preceding(None),
+            rule! { UNBOUNDED ~ PRECEDING },
+        ),
+        value(
+            WindowFrameBound::Following(None),
+            rule! { UNBOUNDED ~ FOLLOWING },
+        ),
+        map(rule! { #subexpr(0) ~ PRECEDING }, |(expr, _)| {
+            WindowFrameBound::Preceding(Some(Box::new(expr)))
+        }),
+        map(rule! { #subexpr(0) ~ FOLLOWING }, |(expr, _)| {
+            WindowFrameBound::Following(Some(Box::new(expr)))
+        }),
+    ))(i)
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
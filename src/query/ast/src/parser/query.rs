@@ -117,17 +117,21 @@ pub fn select_target(i: Input) -> IResult<SelectTarget> {
 }
 
 pub fn travel_point(i: Input) -> IResult<TimeTravelPoint> {
-    let at_snapshot = map(
-        rule! { AT ~ "(" ~ SNAPSHOT ~ "=>" ~ #literal_string ~ ")" },
-        |(_, _, _, _, s, _)| TimeTravelPoint::Snapshot(s),
+    map(rule! { AT ~ #point_in_time }, |(_, point)| point)(i)
+}
+
+pub fn point_in_time(i: Input) -> IResult<TimeTravelPoint> {
+    let snapshot = map(
+        rule! { "(" ~ SNAPSHOT ~ "=>" ~ #literal_string ~ ")" },
+        |(_, _, _, s, _)| TimeTravelPoint::Snapshot(s),
     );
-    let at_timestamp = map(
-        rule! { AT ~ "(" ~ TIMESTAMP ~ "=>" ~ #expr ~ ")" },
-        |(_, _, _, _, e, _)| TimeTravelPoint::Timestamp(Box::new(e)),
+    let timestamp = map(
+        rule! { "(" ~ TIMESTAMP ~ "=>" ~ #expr ~ ")" },
+        |(_, _, _, e, _)| TimeTravelPoint::Timestamp(Box::new(e)),
     );
 
     rule!(
-        #at_snapshot | #at_timestamp
+        #snapshot | #timestamp
     )(i)
 }
 
@@ -193,6 +197,15 @@ pub fn order_by_expr(i: Input) -> IResult<OrderByExpr> {
     )(i)
 }
 
+pub fn window_definition(i: Input) -> IResult<WindowDefinition> {
+    map(
+        rule! {
+            #ident ~ AS ~ ^"(" ~ #window_spec ~ ^")"
+        },
+        |(name, _, _, spec, _)| WindowDefinition { name, spec },
+    )(i)
+}
+
 pub fn table_reference(i: Input) -> IResult<TableReference> {
     let (rest, table_reference_elements) = rule!(#table_reference_element+)(i)?;
     let iter = &mut table_reference_elements.into_iter();
@@ -212,6 +225,14 @@ pub enum TableReferenceElement<'a> {
     TableFunction {
         name: Identifier<'a>,
         params: Vec<Expr<'a>>,
+        named_params: Vec<(Identifier<'a>, Expr<'a>)>,
+        alias: Option<TableAlias<'a>>,
+    },
+    // `@stage/path [ (options) ][ AS alias ]`
+    Stage {
+        name: String,
+        path: String,
+        options: SelectStageOptions,
         alias: Option<TableAlias<'a>>,
     },
     // Derived table, which can be a subquery or joined tables or combination of them
@@ -244,12 +265,55 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     );
     let table_function = map(
         rule! {
-            #ident ~ "(" ~ #comma_separated_list0(expr) ~ ")" ~ #table_alias?
+            #ident ~ "(" ~ #comma_separated_list0(table_function_param) ~ ")" ~ #table_alias?
         },
-        |(name, _, params, _, alias)| TableReferenceElement::TableFunction {
-            name,
-            params,
-            alias,
+        |(name, _, func_params, _, alias)| {
+            let mut params = vec![];
+            let mut named_params = vec![];
+            for param in func_params {
+                match param {
+                    TableFunctionParam::Positional(expr) => params.push(expr),
+                    TableFunctionParam::Named { name, value } => named_params.push((name, value)),
+                }
+            }
+            TableReferenceElement::TableFunction {
+                name,
+                params,
+                named_params,
+                alias,
+            }
+        },
+    );
+    // Parse input like `@my_stage/path/to/dir (FILE_FORMAT => 'PARQUET')`
+    let stage = map(
+        rule! {
+            #at_string ~ ( "(" ~ #comma_separated_list1(select_stage_option) ~ ^")" )? ~ #table_alias?
+        },
+        |(location, opt_options, alias)| {
+            let parsed = location.splitn(2, '/').collect::<Vec<_>>();
+            let path = match parsed.get(1) {
+                Some(path) => format!("/{}", path),
+                None => "/".to_string(),
+            };
+            let mut options = SelectStageOptions::default();
+            for option in opt_options
+                .map(|(_, options, _)| options)
+                .unwrap_or_default()
+            {
+                match option {
+                    SelectStageOption::FileFormat(file_format) => {
+                        options.file_format = Some(file_format)
+                    }
+                    SelectStageOption::Pattern(pattern) => options.pattern = Some(pattern),
+                    SelectStageOption::Files(files) => options.files = files,
+                }
+            }
+            TableReferenceElement::Stage {
+                name: parsed[0].to_string(),
+                path,
+                options,
+                alias,
+            }
         },
     );
     let subquery = map(
@@ -290,7 +354,8 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     );
 
     let (rest, (span, elem)) = consumed(rule! {
-        #subquery
+        #stage
+        | #subquery
         | #table_function
         | #aliased_table
         | #group
@@ -301,6 +366,47 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub enum SelectStageOption {
+    FileFormat(String),
+    Pattern(String),
+    Files(Vec<String>),
+}
+
+pub fn select_stage_option(i: Input) -> IResult<SelectStageOption> {
+    alt((
+        map(
+            rule! { FILE_FORMAT ~ "=>" ~ #literal_string },
+            |(_, _, file_format)| SelectStageOption::FileFormat(file_format),
+        ),
+        map(
+            rule! { PATTERN ~ "=>" ~ #literal_string },
+            |(_, _, pattern)| SelectStageOption::Pattern(pattern),
+        ),
+        map(
+            rule! { FILES ~ "=>" ~ "(" ~ #comma_separated_list0(literal_string) ~ ")" },
+            |(_, _, _, files, _)| SelectStageOption::Files(files),
+        ),
+    ))(i)
+}
+
+pub enum TableFunctionParam<'a> {
+    Positional(Expr<'a>),
+    // `name => value`
+    Named {
+        name: Identifier<'a>,
+        value: Expr<'a>,
+    },
+}
+
+pub fn table_function_param(i: Input) -> IResult<TableFunctionParam> {
+    alt((
+        map(rule! { #ident ~ "=>" ~ #expr }, |(name, _, value)| {
+            TableFunctionParam::Named { name, value }
+        }),
+        map(expr, TableFunctionParam::Positional),
+    ))(i)
+}
+
 struct TableReferenceParser;
 
 impl<'a, I: Iterator<Item = WithSpan<'a, TableReferenceElement<'a>>>> PrattParser<I>
@@ -339,11 +445,25 @@ impl<'a, I: Iterator<Item = WithSpan<'a, TableReferenceElement<'a>>>> PrattParse
             TableReferenceElement::TableFunction {
                 name,
                 params,
+                named_params,
                 alias,
             } => TableReference::TableFunction {
                 span: input.span.0,
                 name,
                 params,
+                named_params,
+                alias,
+            },
+            TableReferenceElement::Stage {
+                name,
+                path,
+                options,
+                alias,
+            } => TableReference::Stage {
+                span: input.span.0,
+                name,
+                path,
+                options,
                 alias,
             },
             TableReferenceElement::Subquery { subquery, alias } => TableReference::Subquery {
@@ -432,6 +552,7 @@ pub enum SetOperationElement<'a> {
         selection: Box<Option<Expr<'a>>>,
         group_by: Box<Vec<Expr<'a>>>,
         having: Box<Option<Expr<'a>>>,
+        window_list: Box<Option<Vec<WindowDefinition<'a>>>>,
     },
     SetOperation {
         op: SetOperator,
@@ -465,6 +586,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
                 ~ ( WHERE ~ ^#expr )?
                 ~ ( GROUP ~ ^BY ~ ^#comma_separated_list1(expr) )?
                 ~ ( HAVING ~ ^#expr )?
+                ~ ( WINDOW ~ ^#comma_separated_list1(window_definition) )?
         },
         |(
             _select,
@@ -474,6 +596,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
             opt_where_block,
             opt_group_by_block,
             opt_having_block,
+            opt_window_block,
         )| {
             SetOperationElement::SelectStmt {
                 distinct: opt_distinct.is_some(),
@@ -490,6 +613,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
                         .unwrap_or_default(),
                 ),
                 having: Box::new(opt_having_block.map(|(_, having)| having)),
+                window_list: Box::new(opt_window_block.map(|(_, windows)| windows)),
             }
         },
     );
@@ -536,6 +660,7 @@ impl<'a, I: Iterator<Item = WithSpan<'a, SetOperationElement<'a>>>> PrattParser<
                 selection,
                 group_by,
                 having,
+                window_list,
             } => SetExpr::Select(Box::new(SelectStmt {
                 span: input.span.0,
                 distinct,
@@ -544,6 +669,7 @@ impl<'a, I: Iterator<Item = WithSpan<'a, SetOperationElement<'a>>>> PrattParser<
                 selection: *selection,
                 group_by: *group_by,
                 having: *having,
+                window_list: *window_list,
             })),
             _ => unreachable!(),
         };
@@ -128,6 +128,30 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
+    let merge_into = map(
+        rule! {
+            MERGE ~ INTO ~ #merge_target
+            ~ USING ~ ^#merge_source
+            ~ ON ~ ^#expr
+            ~ #merge_clause+
+        },
+        |(_, _, target, _, source, _, join_expr, clauses)| {
+            Statement::MergeInto(MergeIntoStmt {
+                target,
+                source,
+                join_expr,
+                clauses,
+            })
+        },
+    );
+
+    let begin = value(Statement::Begin, rule! {
+        #value((), rule! { BEGIN ~ TRANSACTION? })
+        | #value((), rule! { START ~ TRANSACTION })
+    });
+    let commit = value(Statement::Commit, rule! { COMMIT });
+    let rollback = value(Statement::Rollback, rule! { ROLLBACK });
+
     let show_settings = map(
         rule! {
             SHOW ~ SETTINGS ~ (LIKE ~ #literal_string)?
@@ -504,6 +528,18 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let analyze_table = map(
+        rule! {
+            ANALYZE ~ TABLE ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, (catalog, database, table))| {
+            Statement::AnalyzeTable(AnalyzeTableStmt {
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let exists_table = map(
         rule! {
             EXISTS ~ TABLE ~ #peroid_separated_idents_1_to_3
@@ -516,15 +552,46 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_index = map(
+        rule! {
+            CREATE ~ INDEX ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+            ~ "(" ~ #comma_separated_list1(ident) ~ ")"
+            ~ USING ~ INVERTED
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            index_name,
+            _,
+            (catalog, database, table),
+            _,
+            columns,
+            _,
+            _,
+            _,
+        )| {
+            Statement::CreateIndex(CreateIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index_name,
+                catalog,
+                database,
+                table,
+                columns,
+            })
+        },
+    );
     let create_view = map(
         rule! {
-            CREATE ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
+            CREATE ~ MATERIALIZED? ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
             ~ #peroid_separated_idents_1_to_3
             ~ AS ~ #query
         },
-        |(_, _, opt_if_not_exists, (catalog, database, view), _, query)| {
+        |(_, opt_materialized, _, opt_if_not_exists, (catalog, database, view), _, query)| {
             Statement::CreateView(CreateViewStmt {
                 if_not_exists: opt_if_not_exists.is_some(),
+                materialized: opt_materialized.is_some(),
                 catalog,
                 database,
                 view,
@@ -545,6 +612,36 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_stream = map(
+        rule! {
+            CREATE ~ STREAM ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #peroid_separated_idents_1_to_3
+            ~ ON ~ TABLE ~ #peroid_separated_idents_1_to_2
+        },
+        |(_, _, opt_if_not_exists, (catalog, database, stream), _, _, (table_database, table))| {
+            Statement::CreateStream(CreateStreamStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                stream,
+                table_database,
+                table,
+            })
+        },
+    );
+    let drop_stream = map(
+        rule! {
+            DROP ~ STREAM ~ ( IF ~ EXISTS )? ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, (catalog, database, stream))| {
+            Statement::DropStream(DropStreamStmt {
+                if_exists: opt_if_exists.is_some(),
+                catalog,
+                database,
+                stream,
+            })
+        },
+    );
     let alter_view = map(
         rule! {
             ALTER ~ VIEW
@@ -820,6 +917,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                 pattern: Default::default(),
                 file_format: Default::default(),
                 validation_mode: Default::default(),
+                on_error: Default::default(),
                 size_limit: Default::default(),
                 max_file_size: Default::default(),
                 split_size: Default::default(),
@@ -964,6 +1062,14 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #alter_database : "`ALTER DATABASE [IF EXISTS] <action>`"
             | #use_database : "`USE <database>`"
         ),
+        rule!(
+            #begin : "`BEGIN [TRANSACTION]`"
+            | #commit : "`COMMIT`"
+            | #rollback : "`ROLLBACK`"
+        ),
+        rule!(
+            #merge_into : "`MERGE INTO <table> USING <source> ON <expr> WHEN [NOT] MATCHED [AND <expr>] THEN <action> ...`"
+        ),
         rule!(
             #show_tables : "`SHOW [FULL] TABLES [FROM <database>] [<show_limit>]`"
             | #show_create_table : "`SHOW CREATE TABLE [<database>.]<table>`"
@@ -977,12 +1083,16 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #rename_table : "`RENAME TABLE [<database>.]<table> TO <new_table>`"
             | #truncate_table : "`TRUNCATE TABLE [<database>.]<table> [PURGE]`"
             | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT [SEGMENT])`"
+            | #analyze_table : "`ANALYZE TABLE [<database>.]<table>`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
+            | #create_index : "`CREATE INDEX [IF NOT EXISTS] <index> ON [<database>.]<table> (<column>, ...) USING INVERTED`"
         ),
         rule!(
-            #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
+            #create_view : "`CREATE [MATERIALIZED] VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
             | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
+            | #create_stream : "`CREATE STREAM [IF NOT EXISTS] [<database>.]<stream> ON TABLE [<database>.]<table>`"
+            | #drop_stream : "`DROP STREAM [IF EXISTS] [<database>.]<stream>`"
         ),
         rule!(
             #show_users : "`SHOW USERS`"
@@ -1343,9 +1453,30 @@ pub fn create_table_source(i: Input) -> IResult<CreateTableSource> {
         },
     );
 
+    let clone = map(
+        rule! {
+            CLONE ~ #peroid_separated_idents_1_to_3 ~ #travel_point?
+        },
+        |(_, (catalog, database, table), travel_point)| CreateTableSource::Clone {
+            catalog,
+            database,
+            table,
+            travel_point,
+        },
+    );
+
+    let template = map(
+        rule! {
+            USING ~ TEMPLATE ~ ^"(" ~ ^#query ~ ^")"
+        },
+        |(_, _, _, query, _)| CreateTableSource::Template(Box::new(query)),
+    );
+
     rule!(
         #columns
         | #like
+        | #clone
+        | #template
     )(i)
 }
 
@@ -1394,11 +1525,54 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         },
     );
 
+    let add_column = map(
+        rule! {
+            ADD ~ COLUMN? ~ #column_def
+        },
+        |(_, _, column)| AlterTableAction::AddColumn { column },
+    );
+
+    let drop_column = map(
+        rule! {
+            DROP ~ COLUMN? ~ #ident
+        },
+        |(_, _, column)| AlterTableAction::DropColumn { column },
+    );
+
+    let rename_column = map(
+        rule! {
+            RENAME ~ COLUMN ~ ^#ident ~ ^TO ~ ^#ident
+        },
+        |(_, _, old_column, _, new_column)| AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        },
+    );
+
+    let modify_column = map(
+        rule! {
+            MODIFY ~ COLUMN? ~ #column_def
+        },
+        |(_, _, column)| AlterTableAction::ModifyColumn { column },
+    );
+
+    let flashback_to = map(
+        rule! {
+            FLASHBACK ~ ^TO ~ ^#point_in_time
+        },
+        |(_, _, point)| AlterTableAction::FlashbackTo { point },
+    );
+
     rule!(
         #rename_table
+        | #rename_column
         | #alter_table_cluster_key
         | #drop_table_cluster_key
         | #recluster_table
+        | #add_column
+        | #drop_column
+        | #modify_column
+        | #flashback_to
     )(i)
 }
 
@@ -1688,6 +1862,9 @@ pub fn copy_option(i: Input) -> IResult<CopyOption> {
             rule! { VALIDATION_MODE ~ "=" ~ #literal_string },
             |(_, _, validation_mode)| CopyOption::ValidationMode(validation_mode),
         ),
+        map(rule! { ON_ERROR ~ "=" ~ #ident }, |(_, _, on_error)| {
+            CopyOption::OnError(on_error.to_string())
+        }),
         map(
             rule! { SIZE_LIMIT ~ "=" ~ #literal_u64 },
             |(_, _, size_limit)| CopyOption::SizeLimit(size_limit as usize),
@@ -1784,6 +1961,80 @@ pub fn table_reference_only(i: Input) -> IResult<TableReference> {
     )(i)
 }
 
+pub fn merge_target(i: Input) -> IResult<TableReference> {
+    map(
+        consumed(rule! {
+            #peroid_separated_idents_1_to_3 ~ #table_alias?
+        }),
+        |(input, ((catalog, database, table), alias))| TableReference::Table {
+            span: input.0,
+            catalog,
+            database,
+            table,
+            alias,
+            travel_point: None,
+        },
+    )(i)
+}
+
+pub fn merge_source(i: Input) -> IResult<TableReference> {
+    let subquery = map(
+        consumed(rule! {
+            #parenthesized_query ~ #table_alias?
+        }),
+        |(input, (subquery, alias))| TableReference::Subquery {
+            span: input.0,
+            subquery: Box::new(subquery),
+            alias,
+        },
+    );
+
+    rule!(
+        #subquery
+        | #merge_target
+    )(i)
+}
+
+pub fn merge_clause(i: Input) -> IResult<MergeClause> {
+    let matched_action = alt((
+        map(
+            rule! {
+                UPDATE ~ ^SET ~ ^#comma_separated_list1(update_expr)
+            },
+            |(_, _, update_list)| MatchedAction::Update { update_list },
+        ),
+        value(MatchedAction::Delete, rule! { DELETE }),
+    ));
+    let matched = map(
+        rule! {
+            WHEN ~ MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^#matched_action
+        },
+        |(_, _, opt_condition, _, action)| MergeClause::Matched {
+            condition: opt_condition.map(|(_, condition)| condition),
+            action,
+        },
+    );
+    let not_matched = map(
+        rule! {
+            WHEN ~ NOT ~ ^MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^INSERT
+            ~ ( "(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+            ~ ^VALUES ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, _, opt_condition, _, _, opt_columns, _, _, values, _)| MergeClause::NotMatched {
+            condition: opt_condition.map(|(_, condition)| condition),
+            columns: opt_columns
+                .map(|(_, columns, _)| columns)
+                .unwrap_or_default(),
+            values,
+        },
+    );
+
+    rule!(
+        #matched
+        | #not_matched
+    )(i)
+}
+
 pub fn update_expr(i: Input) -> IResult<UpdateExpr> {
     map(rule! { ( #ident ~ "=" ~ ^#expr ) }, |(name, _, expr)| {
         UpdateExpr { name, expr }
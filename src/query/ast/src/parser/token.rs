@@ -111,6 +111,8 @@ pub enum TokenKind {
     CommentBlock,
 
     #[regex(r#"[_a-zA-Z][_$a-zA-Z0-9]*"#)]
+    // The columns of staged files are referred by position, like `$1`
+    #[regex(r#"\$[0-9]+"#)]
     Ident,
 
     #[regex(r#"`[^`]*`"#)]
@@ -269,6 +271,8 @@ pub enum TokenKind {
     AWS_SECRET_KEY,
     #[token("ANTI", ignore(ascii_case))]
     ANTI,
+    #[token("BEGIN", ignore(ascii_case))]
+    BEGIN,
     #[token("BETWEEN", ignore(ascii_case))]
     BETWEEN,
     #[token("BIGINT", ignore(ascii_case))]
@@ -293,12 +297,18 @@ pub enum TokenKind {
     CATALOGS,
     #[token("CENTURY", ignore(ascii_case))]
     CENTURY,
+    #[token("CLONE", ignore(ascii_case))]
+    CLONE,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
     COMMENTS,
+    #[token("COMMIT", ignore(ascii_case))]
+    COMMIT,
     #[token("COMPACT", ignore(ascii_case))]
     COMPACT,
     #[token("CONNECTION", ignore(ascii_case))]
@@ -321,6 +331,8 @@ pub enum TokenKind {
     CROSS,
     #[token("CSV", ignore(ascii_case))]
     CSV,
+    #[token("CURRENT", ignore(ascii_case))]
+    CURRENT,
     #[token("CURRENT_TIMESTAMP", ignore(ascii_case))]
     CURRENT_TIMESTAMP,
     #[token("DATABASE", ignore(ascii_case))]
@@ -401,12 +413,16 @@ pub enum TokenKind {
     FILES,
     #[token("FINAL", ignore(ascii_case))]
     FINAL,
+    #[token("FLASHBACK", ignore(ascii_case))]
+    FLASHBACK,
     #[token("FLOAT", ignore(ascii_case))]
     FLOAT,
     #[token("FLOAT32", ignore(ascii_case))]
     FLOAT32,
     #[token("FLOAT64", ignore(ascii_case))]
     FLOAT64,
+    #[token("FOLLOWING", ignore(ascii_case))]
+    FOLLOWING,
     #[token("FOR", ignore(ascii_case))]
     FOR,
     #[token("FORCE", ignore(ascii_case))]
@@ -447,6 +463,8 @@ pub enum TokenKind {
     IF,
     #[token("IN", ignore(ascii_case))]
     IN,
+    #[token("INDEX", ignore(ascii_case))]
+    INDEX,
     #[token("INNER", ignore(ascii_case))]
     INNER,
     #[token("INSERT", ignore(ascii_case))]
@@ -467,6 +485,8 @@ pub enum TokenKind {
     INTERVAL,
     #[token("INTO", ignore(ascii_case))]
     INTO,
+    #[token("INVERTED", ignore(ascii_case))]
+    INVERTED,
     #[token("IS", ignore(ascii_case))]
     IS,
     #[token("ISODOW", ignore(ascii_case))]
@@ -503,10 +523,16 @@ pub enum TokenKind {
     MAX_FILE_SIZE,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATCHED", ignore(ascii_case))]
+    MATCHED,
+    #[token("MATERIALIZED", ignore(ascii_case))]
+    MATERIALIZED,
     #[token("MEMO", ignore(ascii_case))]
     MEMO,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
+    #[token("MERGE", ignore(ascii_case))]
+    MERGE,
     #[token("METRICS", ignore(ascii_case))]
     METRICS,
     #[token("MICROSECONDS", ignore(ascii_case))]
@@ -517,6 +543,8 @@ pub enum TokenKind {
     MILLISECONDS,
     #[token("MINUTE", ignore(ascii_case))]
     MINUTE,
+    #[token("MODIFY", ignore(ascii_case))]
+    MODIFY,
     #[token("MONTH", ignore(ascii_case))]
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
@@ -547,10 +575,14 @@ pub enum TokenKind {
     OUTER,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
     #[token("OVERWRITE", ignore(ascii_case))]
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
@@ -581,6 +613,10 @@ pub enum TokenKind {
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
     ROLE,
+    #[token("ROLLBACK", ignore(ascii_case))]
+    ROLLBACK,
+    #[token("PRECEDING", ignore(ascii_case))]
+    PRECEDING,
     #[token("PRESIGN", ignore(ascii_case))]
     PRESIGN,
     #[token("PRIVILEGES", ignore(ascii_case))]
@@ -597,8 +633,14 @@ pub enum TokenKind {
     RIGHT,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("RANGE", ignore(ascii_case))]
+    RANGE,
     #[token("RAW", ignore(ascii_case))]
     RAW,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
     #[token("SCHEMA", ignore(ascii_case))]
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
@@ -635,6 +677,8 @@ pub enum TokenKind {
     SPLIT_SIZE,
     #[token("STAGE", ignore(ascii_case))]
     STAGE,
+    #[token("START", ignore(ascii_case))]
+    START,
     #[token("SYNTAX", ignore(ascii_case))]
     SYNTAX,
     #[token("USAGE", ignore(ascii_case))]
@@ -651,6 +695,8 @@ pub enum TokenKind {
     SUPER,
     #[token("STATUS", ignore(ascii_case))]
     STATUS,
+    #[token("STREAM", ignore(ascii_case))]
+    STREAM,
     #[token("STRING", ignore(ascii_case))]
     STRING,
     #[token("SUBSTRING", ignore(ascii_case))]
@@ -663,6 +709,8 @@ pub enum TokenKind {
     TABLE,
     #[token("TABLES", ignore(ascii_case))]
     TABLES,
+    #[token("TEMPLATE", ignore(ascii_case))]
+    TEMPLATE,
     #[token("TEXT", ignore(ascii_case))]
     TEXT,
     #[token("TENANTSETTING", ignore(ascii_case))]
@@ -687,6 +735,8 @@ pub enum TokenKind {
     TOKEN,
     #[token("TRAILING", ignore(ascii_case))]
     TRAILING,
+    #[token("TRANSACTION", ignore(ascii_case))]
+    TRANSACTION,
     #[token("TRANSIENT", ignore(ascii_case))]
     TRANSIENT,
     #[token("TRIM", ignore(ascii_case))]
@@ -711,6 +761,8 @@ pub enum TokenKind {
     UINT64,
     #[token("UINT8", ignore(ascii_case))]
     UINT8,
+    #[token("UNBOUNDED", ignore(ascii_case))]
+    UNBOUNDED,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
     #[token("UNSIGNED", ignore(ascii_case))]
@@ -741,6 +793,8 @@ pub enum TokenKind {
     WHEN,
     #[token("WHERE", ignore(ascii_case))]
     WHERE,
+    #[token("WINDOW", ignore(ascii_case))]
+    WINDOW,
     #[token("WITH", ignore(ascii_case))]
     WITH,
     #[token("XOR", ignore(ascii_case))]
@@ -957,7 +1011,7 @@ impl TokenKind {
             | TokenKind::TO
             | TokenKind::UNION
             | TokenKind::WHERE
-            // | TokenKind::WINDOW
+            | TokenKind::WINDOW
             | TokenKind::WITH
             | TokenKind::DATE_ADD
             | TokenKind::DATE_SUB
@@ -1074,7 +1128,8 @@ impl TokenKind {
             | TokenKind::TO
             | TokenKind::UNION
             | TokenKind::WHERE
-            // | TokenKind::WINDOW
+            | TokenKind::WINDOW
+            | TokenKind::OVER
             | TokenKind::WITH
             | TokenKind::IGNORE_RESULT
             if !after_as => true,
@@ -24,6 +24,8 @@ use super::walk::walk_select_target;
 use super::walk::walk_set_expr;
 use super::walk::walk_statement;
 use super::walk::walk_table_reference;
+use super::walk::walk_window;
+use super::walk::walk_window_spec;
 use super::walk_time_travel_point;
 use crate::ast::*;
 use crate::parser::token::Token;
@@ -232,10 +234,14 @@ pub trait Visitor<'ast>: Sized {
         _name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<Window<'ast>>,
     ) {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            walk_window(self, window);
+        }
     }
 
     fn visit_case_when(
@@ -351,6 +357,12 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_show_settings(&mut self, _like: &'ast Option<String>) {}
 
+    fn visit_begin(&mut self) {}
+
+    fn visit_commit(&mut self) {}
+
+    fn visit_rollback(&mut self) {}
+
     fn visit_show_process_list(&mut self) {}
 
     fn visit_show_metrics(&mut self) {}
@@ -386,6 +398,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_update(&mut self, _update: &'ast UpdateStmt<'ast>) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &'ast MergeIntoStmt<'ast>) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &'ast ShowCatalogsStmt<'ast>) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &'ast ShowCreateCatalogStmt<'ast>) {}
@@ -434,14 +448,22 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_optimize_table(&mut self, _stmt: &'ast OptimizeTableStmt<'ast>) {}
 
+    fn visit_analyze_table(&mut self, _stmt: &'ast AnalyzeTableStmt<'ast>) {}
+
     fn visit_exists_table(&mut self, _stmt: &'ast ExistsTableStmt<'ast>) {}
 
+    fn visit_create_index(&mut self, _stmt: &'ast CreateIndexStmt<'ast>) {}
+
     fn visit_create_view(&mut self, _stmt: &'ast CreateViewStmt<'ast>) {}
 
     fn visit_alter_view(&mut self, _stmt: &'ast AlterViewStmt<'ast>) {}
 
     fn visit_drop_view(&mut self, _stmt: &'ast DropViewStmt<'ast>) {}
 
+    fn visit_create_stream(&mut self, _stmt: &'ast CreateStreamStmt<'ast>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &'ast DropStreamStmt<'ast>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &'ast CreateUserStmt) {}
@@ -545,6 +567,7 @@ pub trait Visitor<'ast>: Sized {
             selection,
             group_by,
             having,
+            window_list,
             ..
         } = stmt;
 
@@ -567,6 +590,12 @@ pub trait Visitor<'ast>: Sized {
         if let Some(having) = having {
             walk_expr(self, having);
         }
+
+        if let Some(window_list) = window_list {
+            for window_def in window_list.iter() {
+                walk_window_spec(self, &window_def.spec);
+            }
+        }
     }
 
     fn visit_select_target(&mut self, target: &'ast SelectTarget<'ast>) {
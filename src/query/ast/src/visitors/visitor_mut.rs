@@ -24,6 +24,8 @@ use super::walk_mut::walk_select_target_mut;
 use super::walk_mut::walk_set_expr_mut;
 use super::walk_mut::walk_statement_mut;
 use super::walk_mut::walk_table_reference_mut;
+use super::walk_mut::walk_window_mut;
+use super::walk_mut::walk_window_spec_mut;
 use super::walk_time_travel_point_mut;
 use crate::ast::*;
 use crate::parser::token::Token;
@@ -237,10 +239,14 @@ pub trait VisitorMut: Sized {
         _name: &mut Identifier<'_>,
         args: &mut [Expr<'_>],
         _params: &mut [Literal],
+        window: &mut Option<Window<'_>>,
     ) {
         for arg in args.iter_mut() {
             walk_expr_mut(self, arg);
         }
+        if let Some(window) = window {
+            walk_window_mut(self, window);
+        }
     }
 
     fn visit_case_when(
@@ -354,6 +360,12 @@ pub trait VisitorMut: Sized {
 
     fn visit_show_settings(&mut self, _like: &mut Option<String>) {}
 
+    fn visit_begin(&mut self) {}
+
+    fn visit_commit(&mut self) {}
+
+    fn visit_rollback(&mut self) {}
+
     fn visit_show_process_list(&mut self) {}
 
     fn visit_show_metrics(&mut self) {}
@@ -389,6 +401,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_update(&mut self, _update: &mut UpdateStmt<'_>) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &mut MergeIntoStmt<'_>) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &mut ShowCatalogsStmt<'_>) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &mut ShowCreateCatalogStmt<'_>) {}
@@ -437,14 +451,22 @@ pub trait VisitorMut: Sized {
 
     fn visit_optimize_table(&mut self, _stmt: &mut OptimizeTableStmt<'_>) {}
 
+    fn visit_analyze_table(&mut self, _stmt: &mut AnalyzeTableStmt<'_>) {}
+
     fn visit_exists_table(&mut self, _stmt: &mut ExistsTableStmt<'_>) {}
 
+    fn visit_create_index(&mut self, _stmt: &mut CreateIndexStmt<'_>) {}
+
     fn visit_create_view(&mut self, _stmt: &mut CreateViewStmt<'_>) {}
 
     fn visit_alter_view(&mut self, _stmt: &mut AlterViewStmt<'_>) {}
 
     fn visit_drop_view(&mut self, _stmt: &mut DropViewStmt<'_>) {}
 
+    fn visit_create_stream(&mut self, _stmt: &mut CreateStreamStmt<'_>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &mut DropStreamStmt<'_>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &mut CreateUserStmt) {}
@@ -548,6 +570,7 @@ pub trait VisitorMut: Sized {
             selection,
             group_by,
             having,
+            window_list,
             ..
         } = stmt;
 
@@ -570,6 +593,12 @@ pub trait VisitorMut: Sized {
         if let Some(having) = having {
             walk_expr_mut(self, having);
         }
+
+        if let Some(window_list) = window_list {
+            for window_def in window_list.iter_mut() {
+                walk_window_spec_mut(self, &mut window_def.spec);
+            }
+        }
     }
 
     fn visit_select_target(&mut self, target: &mut SelectTarget<'_>) {
@@ -93,7 +93,8 @@ pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, expr: &'a Expr<'a>) {
             name,
             args,
             params,
-        } => visitor.visit_function_call(span, *distinct, name, args, params),
+            window,
+        } => visitor.visit_function_call(span, *distinct, name, args, params, window),
         Expr::Case {
             span,
             operand,
@@ -198,6 +199,43 @@ pub fn walk_select_target<'a, V: Visitor<'a>>(visitor: &mut V, target: &'a Selec
     }
 }
 
+pub fn walk_window<'a, V: Visitor<'a>>(visitor: &mut V, window: &'a Window<'a>) {
+    match window {
+        Window::WindowReference(reference) => {
+            visitor.visit_identifier(&reference.window_name);
+        }
+        Window::WindowSpec(spec) => walk_window_spec(visitor, spec),
+    }
+}
+
+pub fn walk_window_spec<'a, V: Visitor<'a>>(visitor: &mut V, spec: &'a WindowSpec<'a>) {
+    let WindowSpec {
+        existing_window_name,
+        partition_by,
+        order_by,
+        window_frame,
+    } = spec;
+
+    if let Some(name) = existing_window_name {
+        visitor.visit_identifier(name);
+    }
+    for expr in partition_by {
+        visitor.visit_expr(expr);
+    }
+    for order_by in order_by {
+        visitor.visit_order_by(order_by);
+    }
+    if let Some(frame) = window_frame {
+        for bound in [&frame.start_bound, &frame.end_bound] {
+            match bound {
+                WindowFrameBound::Preceding(Some(offset))
+                | WindowFrameBound::Following(Some(offset)) => visitor.visit_expr(offset),
+                _ => {}
+            }
+        }
+    }
+}
+
 pub fn walk_table_reference<'a, V: Visitor<'a>>(
     visitor: &mut V,
     table_ref: &'a TableReference<'a>,
@@ -240,6 +278,7 @@ pub fn walk_table_reference<'a, V: Visitor<'a>>(
         TableReference::TableFunction {
             name,
             params,
+            named_params,
             alias,
             ..
         } => {
@@ -247,10 +286,19 @@ pub fn walk_table_reference<'a, V: Visitor<'a>>(
             for param in params {
                 visitor.visit_expr(param);
             }
+            for (name, value) in named_params {
+                visitor.visit_identifier(name);
+                visitor.visit_expr(value);
+            }
             if let Some(alias) = alias {
                 visitor.visit_identifier(&alias.name);
             }
         }
+        TableReference::Stage { alias, .. } => {
+            if let Some(alias) = alias {
+                visitor.visit_identifier(alias.name);
+            }
+        }
         TableReference::Join { join, .. } => {
             visitor.visit_join(join);
         }
@@ -295,6 +343,10 @@ pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, statement: &'a Statem
             ..
         } => visitor.visit_delete(table_reference, selection),
         Statement::Update(update) => visitor.visit_update(update),
+        Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
+        Statement::Begin => visitor.visit_begin(),
+        Statement::Commit => visitor.visit_commit(),
+        Statement::Rollback => visitor.visit_rollback(),
         Statement::Copy(stmt) => visitor.visit_copy(stmt),
         Statement::ShowSettings { like } => visitor.visit_show_settings(like),
         Statement::ShowProcessList => visitor.visit_show_process_list(),
@@ -336,10 +388,14 @@ pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, statement: &'a Statem
         Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
         Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
         Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
+        Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
         Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
+        Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
         Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
         Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
         Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
+        Statement::CreateStream(stmt) => visitor.visit_create_stream(stmt),
+        Statement::DropStream(stmt) => visitor.visit_drop_stream(stmt),
         Statement::ShowUsers => visitor.visit_show_users(),
         Statement::ShowRoles => visitor.visit_show_roles(),
         Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
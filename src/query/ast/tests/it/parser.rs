@@ -306,6 +306,24 @@ fn test_statement() {
                     skip_header = 1
                 )
                 size_limit=10;"#,
+        r#"COPY INTO mytable
+                FROM 'fs:///path/to/data.csv'
+                FILE_FORMAT = (
+                    type = 'CSV'
+                    field_delimiter = ','
+                    record_delimiter = '\n'
+                    skip_header = 1
+                )
+                on_error=skip_file_3;"#,
+        r#"COPY INTO mytable
+                FROM 'fs:///path/to/data.csv'
+                FILE_FORMAT = (
+                    type = 'CSV'
+                    field_delimiter = ','
+                    record_delimiter = '\n'
+                    skip_header = 1
+                )
+                validation_mode='RETURN_ERRORS';"#,
         // We used to support COPY FROM a quoted at string
         // r#"COPY INTO mytable
         //         FROM '@external_stage/path/to/file.csv'
@@ -342,6 +360,7 @@ fn test_statement() {
         r#"SHOW GRANTS ON DATABASE db;"#,
         r#"SHOW GRANTS OF SHARE t;"#,
         r#"UPDATE db1.tb1 set a = a + 1, b = 2 WHERE c > 3;"#,
+        r#"create table t using template (select * from infer_schema(location => '@s1/'));"#,
     ];
 
     for case in cases {
@@ -449,6 +468,8 @@ fn test_query() {
         r#"select * from t1 union select * from t2 intersect select * from t3"#,
         r#"(select * from t1 union select * from t2) union select * from t3"#,
         r#"select * from t1 union (select * from t2 union select * from t3)"#,
+        r#"select $1, $2 from @s1/data/ (file_format => 'parquet', pattern => '.*[.]parquet') as t"#,
+        r#"select * from infer_schema(location => '@s1/data/', file_format => 'parquet')"#,
     ];
 
     for case in cases {
@@ -34,6 +34,9 @@ pub struct StageTableInfo {
     pub schema: DataSchemaRef,
     pub path: String,
     pub files: Vec<String>,
+    /// The pattern of the files to read if no `StagePushDownInfo` is given, as by SELECT.
+    #[serde(default)]
+    pub pattern: String,
     pub user_stage_info: UserStageInfo,
 }
 
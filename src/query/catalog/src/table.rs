@@ -21,11 +21,13 @@ use chrono::Utc;
 use common_datablocks::BlockCompactThresholds;
 use common_datablocks::DataBlock;
 use common_datavalues::chrono;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MetaId;
 use common_pipeline_core::Pipeline;
 use common_storage::StorageMetrics;
@@ -52,6 +54,16 @@ pub trait Table: Sync + Send {
         self.get_table_info().engine()
     }
 
+    /// The error of the operations the engine of the table does not support.
+    fn unimplemented_for_engine(&self, operation: &str) -> ErrorCode {
+        ErrorCode::Unimplemented(format!(
+            "table {}, of engine type {}, does not support {}",
+            self.name(),
+            self.engine(),
+            operation,
+        ))
+    }
+
     fn schema(&self) -> DataSchemaRef {
         self.get_table_info().schema()
     }
@@ -123,6 +135,19 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        let (_, _) = (ctx, action);
+
+        Err(ErrorCode::UnsupportedEngineParams(format!(
+            "Unsupported altering columns for engine: {}",
+            self.engine()
+        )))
+    }
+
     /// Gather partitions to be scanned according to the push_downs
     async fn read_partitions(
         &self,
@@ -185,6 +210,41 @@ pub trait Table: Sync + Send {
         Ok(())
     }
 
+    /// Writes down the metadata of the insertion buffered by a transaction, and returns the
+    /// request to commit it, which is committed together with the ones of the other tables.
+    async fn prepare_commit_insertion(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        operations: &[DataBlock],
+        overwrite: bool,
+    ) -> Result<UpdateTableMetaReq> {
+        let (_, _, _) = (ctx, operations, overwrite);
+
+        Err(self.unimplemented_for_engine("transaction"))
+    }
+
+    /// Cleans up the metadata written by `prepare_commit_insertion`, if the request is not committed.
+    async fn abort_prepared_insertion(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        req: &UpdateTableMetaReq,
+    ) -> Result<()> {
+        let (_, _) = (ctx, req);
+
+        Ok(())
+    }
+
+    /// Removes the data of the insertion buffered by a transaction, which will never be committed.
+    async fn abort_insertion(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        operations: Vec<DataBlock>,
+    ) -> Result<()> {
+        let (_, _) = (ctx, operations);
+
+        Ok(())
+    }
+
     async fn truncate(&self, ctx: Arc<dyn TableContext>, purge: bool) -> Result<()> {
         let (_, _) = (ctx, purge);
         Ok(())
@@ -204,14 +264,69 @@ pub trait Table: Sync + Send {
         Ok(Box::new(DummyColumnStatisticsProvider))
     }
 
+    /// Recomputes the column statistics used by the optimizer, e.g. the number of distinct values.
+    async fn analyze(&self, ctx: Arc<dyn TableContext>) -> Result<()> {
+        let _ = ctx;
+
+        Err(self.unimplemented_for_engine("ANALYZE"))
+    }
+
     async fn navigate_to(&self, instant: &NavigationPoint) -> Result<Arc<dyn Table>> {
         let _ = instant;
 
-        Err(ErrorCode::Unimplemented(format!(
-            "table {},  of engine type {}, does not support time travel",
-            self.name(),
-            self.get_table_info().engine(),
-        )))
+        Err(self.unimplemented_for_engine("time travel"))
+    }
+
+    /// Makes the data at the given point current again, as a new version of the table.
+    async fn flashback(&self, ctx: Arc<dyn TableContext>, point: &NavigationPoint) -> Result<()> {
+        let (_, _) = (ctx, point);
+
+        Err(self.unimplemented_for_engine("FLASHBACK"))
+    }
+
+    /// Makes the data of `source` the data of this newly created table,
+    /// sharing the data files of `source` instead of copying them.
+    async fn clone_data_from(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        source: Arc<dyn Table>,
+    ) -> Result<()> {
+        let (_, _) = (ctx, source);
+
+        Err(self.unimplemented_for_engine("CLONE"))
+    }
+
+    /// Creates an inverted index named `index_name` on the string `columns`.
+    async fn create_inverted_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        index_name: &str,
+        columns: &[String],
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let (_, _, _, _) = (ctx, index_name, columns, if_not_exists);
+
+        Err(self.unimplemented_for_engine("CREATE INDEX"))
+    }
+
+    /// Returns the tables of the rows inserted and of the rows deleted since the version of
+    /// the table at the snapshot `since_location` ("" for the empty table).
+    async fn changes(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        since_location: &str,
+    ) -> Result<(Arc<dyn Table>, Arc<dyn Table>)> {
+        let (_, _) = (ctx, since_location);
+
+        Err(self.unimplemented_for_engine("STREAM"))
+    }
+
+    /// Infers the schema of the data of the table, for the tables over files which carry the
+    /// schema, like the files in a stage.
+    async fn infer_schema(&self, ctx: Arc<dyn TableContext>) -> Result<DataSchemaRef> {
+        let _ = ctx;
+
+        Err(self.unimplemented_for_engine("schema inference"))
     }
 
     async fn delete(
@@ -222,11 +337,25 @@ pub trait Table: Sync + Send {
     ) -> Result<()> {
         let (_, _, _) = (ctx, projection, selection);
 
-        Err(ErrorCode::Unimplemented(format!(
-            "table {},  of engine type {}, does not support DELETE FROM",
-            self.name(),
-            self.get_table_info().engine(),
-        )))
+        Err(self.unimplemented_for_engine("DELETE FROM"))
+    }
+
+    async fn update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<Expression>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, Expression)>,
+    ) -> Result<()> {
+        let (_, _, _, _) = (ctx, filter, col_indices, update_list);
+
+        Err(self.unimplemented_for_engine("UPDATE"))
+    }
+
+    async fn merge_into(&self, ctx: Arc<dyn TableContext>, merge: MergeIntoInfo) -> Result<()> {
+        let (_, _) = (ctx, merge);
+
+        Err(self.unimplemented_for_engine("MERGE INTO"))
     }
 
     fn get_block_compact_thresholds(&self) -> BlockCompactThresholds {
@@ -250,11 +379,7 @@ pub trait Table: Sync + Send {
     ) -> Result<Option<Box<dyn TableMutator>>> {
         let (_, _, _, _) = (ctx, target, limit, pipeline);
 
-        Err(ErrorCode::Unimplemented(format!(
-            "table {},  of engine type {}, does not support compact",
-            self.name(),
-            self.get_table_info().engine(),
-        )))
+        Err(self.unimplemented_for_engine("compact"))
     }
 
     async fn recluster(
@@ -265,11 +390,7 @@ pub trait Table: Sync + Send {
     ) -> Result<Option<Box<dyn TableMutator>>> {
         let (_, _, _) = (ctx, pipeline, push_downs);
 
-        Err(ErrorCode::Unimplemented(format!(
-            "table {},  of engine type {}, does not support recluster",
-            self.name(),
-            self.get_table_info().engine(),
-        )))
+        Err(self.unimplemented_for_engine("recluster"))
     }
 }
 
@@ -295,7 +416,7 @@ pub trait TableExt: Table {
 
 impl<T: ?Sized> TableExt for T where T: Table {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NavigationPoint {
     SnapshotID(String),
     TimePoint(DateTime<Utc>),
@@ -314,7 +435,19 @@ pub struct ColumnStatistics {
     pub min: DataValue,
     pub max: DataValue,
     pub null_count: u64,
-    pub number_of_distinct_values: u64,
+    // None if the table has not collected it
+    pub number_of_distinct_values: Option<u64>,
+    // equi-height histogram of the non-null values, ordered by the upper bounds
+    pub histogram: Option<Vec<HistogramBucket>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistogramBucket {
+    // the lower bound is the upper bound of the previous bucket (exclusive),
+    // or the min value of the column for the first bucket
+    pub upper_bound: DataValue,
+    pub num_values: u64,
+    pub num_distinct: u64,
 }
 
 pub enum CompactTarget {
@@ -322,6 +455,80 @@ pub enum CompactTarget {
     Segments,
 }
 
+pub enum AlterColumnAction {
+    Add {
+        field: DataField,
+        comment: String,
+        // value of the column for the rows that already exist
+        missing_value: DataValue,
+    },
+    Drop {
+        name: String,
+    },
+    Rename {
+        name: String,
+        new_name: String,
+    },
+    // the new definition of the column, only widening the data type is supported
+    Modify {
+        field: DataField,
+    },
+}
+
+/// The expressions of the matched clauses are evaluated against the rows of the target joined
+/// with the rows of the source, those of the not matched clauses against the rows of the source.
+pub struct MergeIntoInfo {
+    /// Names of the table columns in the joined rows, in the order of the table schema.
+    pub target_columns: Vec<String>,
+    /// The source of the merge, joined with the blocks of the target as they are scanned.
+    pub source: Box<dyn MergeIntoSource>,
+    pub matched: Vec<MergeMatchedClause>,
+    pub not_matched: Vec<MergeNotMatchedClause>,
+}
+
+/// The build side of the join of a MERGE INTO, probed by the blocks of the target.
+///
+/// The join keys and the join filter are applied by the implementation, the names of the
+/// columns of the source rows it returns are the ones used by the expressions of the clauses.
+pub trait MergeIntoSource: Send + Sync {
+    /// The schema of the source rows.
+    fn schema(&self) -> DataSchemaRef;
+
+    /// Returns true if the source has no rows.
+    fn is_empty(&self) -> bool;
+
+    /// Probes the source with a block of the target, in the order of the table schema.
+    fn probe(&self, target: &DataBlock) -> Result<MergeIntoMatches>;
+
+    /// The rows of the source which match none of the target rows probed so far.
+    fn unmatched(&self) -> Result<Vec<DataBlock>>;
+}
+
+/// The pairs of a target row and a source row which match each other.
+pub struct MergeIntoMatches {
+    /// The index of the target row of each pair, in the probed block.
+    pub target_rows: Vec<u32>,
+    /// The source row of each pair.
+    pub source: DataBlock,
+}
+
+pub struct MergeMatchedClause {
+    pub condition: Option<Expression>,
+    pub action: MergeMatchedAction,
+}
+
+pub enum MergeMatchedAction {
+    // (index of the field in the table schema, new value)
+    Update(Vec<(usize, Expression)>),
+    Delete,
+}
+
+pub struct MergeNotMatchedClause {
+    pub condition: Option<Expression>,
+    // value of each field of the table schema
+    pub values: Vec<Expression>,
+}
+
 pub enum AppendMode {
     // From INSERT and RECUSTER operation
     Normal,
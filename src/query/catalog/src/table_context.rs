@@ -24,6 +24,8 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 use common_io::prelude::FormatSettings;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -54,6 +56,14 @@ pub struct ProcessInfo {
     pub created_time: SystemTime,
 }
 
+/// The insertion into a table, buffered by an explicit transaction until it is committed.
+#[derive(Clone)]
+pub struct PendingInsertion {
+    pub table_info: TableInfo,
+    pub operations: Vec<DataBlock>,
+    pub overwrite: bool,
+}
+
 #[async_trait::async_trait]
 pub trait TableContext: Send + Sync {
     /// Build a table instance the plan wants to operate on.
@@ -93,11 +103,37 @@ pub trait TableContext: Send + Sync {
     fn get_data_operator(&self) -> Result<DataOperator>;
     fn push_precommit_block(&self, block: DataBlock);
     fn consume_precommit_blocks(&self) -> Vec<DataBlock>;
+    /// Whether the session is in an explicit transaction, started by `BEGIN`.
+    fn in_txn(&self) -> bool;
+    /// Buffers the insertion into the table in the current transaction.
+    ///
+    /// Returns the operations buffered before, which are superseded by an overwrite.
+    fn buffer_txn_insertion(
+        &self,
+        table_info: &TableInfo,
+        operations: Vec<DataBlock>,
+        overwrite: bool,
+    ) -> Result<Vec<DataBlock>>;
     fn try_get_function_context(&self) -> Result<FunctionContext>;
     fn get_connection_id(&self) -> String;
     fn get_settings(&self) -> Arc<Settings>;
     fn get_cluster(&self) -> Arc<Cluster>;
     async fn get_table(&self, catalog: &str, database: &str, table: &str)
     -> Result<Arc<dyn Table>>;
+    /// Attach a table to the query by the name, see `get_attached_table`.
+    fn attach_table(&self, catalog: &str, database: &str, name: &str, table: Arc<dyn Table>);
+    /// Get the table attached to the query by the name, which takes precedence over the table
+    /// of the name in the catalog.
+    fn get_attached_table(
+        &self,
+        catalog: &str,
+        database: &str,
+        table: &str,
+    ) -> Option<Arc<dyn Table>>;
+    /// Records that the query consumes a stream, `req` moves the stream to the offset read by
+    /// the query. It is committed together with the first table change of the query.
+    fn add_consumed_stream(&self, req: UpdateTableMetaReq);
+    fn get_consumed_streams(&self) -> Vec<UpdateTableMetaReq>;
+    fn clear_consumed_streams(&self);
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
 }
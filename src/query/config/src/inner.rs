@@ -107,6 +107,7 @@ pub struct QueryConfig {
     pub mysql_handler_host: String,
     pub mysql_handler_port: u16,
     pub max_active_sessions: u64,
+    pub max_server_memory_usage: u64,
     pub clickhouse_http_handler_host: String,
     pub clickhouse_http_handler_port: u16,
     pub http_handler_host: String,
@@ -150,6 +151,12 @@ pub struct QueryConfig {
     pub table_cache_bloom_index_meta_count: u64,
     /// Max bytes of cached bloom index
     pub table_cache_bloom_index_data_bytes: u64,
+    /// Interval (in seconds) of purging the data out of the retention period of tables, 0 to disable
+    pub data_retention_purge_interval: u64,
+    /// Interval (in seconds) of compacting and reclustering tables automatically, 0 to disable
+    pub auto_compaction_interval: u64,
+    /// Max number of tables compacted or reclustered automatically at the same time
+    pub auto_compaction_max_concurrency: u64,
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
@@ -170,6 +177,7 @@ impl Default for QueryConfig {
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
             max_active_sessions: 256,
+            max_server_memory_usage: 0,
             clickhouse_http_handler_host: "127.0.0.1".to_string(),
             clickhouse_http_handler_port: 8124,
             http_handler_host: "127.0.0.1".to_string(),
@@ -200,6 +208,9 @@ impl Default for QueryConfig {
             table_cache_segment_count: 10240,
             table_cache_bloom_index_meta_count: 3000,
             table_cache_bloom_index_data_bytes: 1024 * 1024 * 1024,
+            data_retention_purge_interval: 0,
+            auto_compaction_interval: 0,
+            auto_compaction_max_concurrency: 1,
             management_mode: false,
             jwt_key_file: "".to_string(),
             async_insert_max_data_size: 10000,
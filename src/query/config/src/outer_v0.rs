@@ -900,6 +900,9 @@ pub struct QueryConfig {
     #[clap(long, default_value = "256")]
     pub max_active_sessions: u64,
 
+    #[clap(long, default_value = "0")]
+    pub max_server_memory_usage: u64,
+
     #[deprecated(note = "clickhouse tcp support is deprecated")]
     #[clap(long, default_value = "127.0.0.1")]
     pub clickhouse_handler_host: String,
@@ -1015,6 +1018,20 @@ pub struct QueryConfig {
     #[clap(long, default_value = "1073741824")]
     pub table_cache_bloom_index_data_bytes: u64,
 
+    /// Interval in seconds of purging the snapshots, segments and blocks out of the
+    /// `data_retention_period` of tables in background, 0 means disabled
+    #[clap(long, default_value = "0")]
+    pub data_retention_purge_interval: u64,
+
+    /// Interval in seconds of compacting and reclustering the tables in background, 0 means
+    /// disabled. Tables with the option `auto_compaction = false` are skipped
+    #[clap(long, default_value = "0")]
+    pub auto_compaction_interval: u64,
+
+    /// Max number of tables compacted or reclustered in background at the same time
+    #[clap(long, default_value = "1")]
+    pub auto_compaction_max_concurrency: u64,
+
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     #[clap(long)]
     pub management_mode: bool,
@@ -1061,6 +1078,7 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             mysql_handler_host: self.mysql_handler_host,
             mysql_handler_port: self.mysql_handler_port,
             max_active_sessions: self.max_active_sessions,
+            max_server_memory_usage: self.max_server_memory_usage,
             clickhouse_http_handler_host: self.clickhouse_http_handler_host,
             clickhouse_http_handler_port: self.clickhouse_http_handler_port,
             http_handler_host: self.http_handler_host,
@@ -1091,6 +1109,9 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_cache_segment_count: self.table_cache_segment_count,
             table_cache_bloom_index_meta_count: self.table_cache_bloom_index_meta_count,
             table_cache_bloom_index_data_bytes: self.table_cache_bloom_index_data_bytes,
+            data_retention_purge_interval: self.data_retention_purge_interval,
+            auto_compaction_interval: self.auto_compaction_interval,
+            auto_compaction_max_concurrency: self.auto_compaction_max_concurrency,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
             async_insert_max_data_size: self.async_insert_max_data_size,
@@ -1115,6 +1136,7 @@ impl From<InnerQueryConfig> for QueryConfig {
             mysql_handler_host: inner.mysql_handler_host,
             mysql_handler_port: inner.mysql_handler_port,
             max_active_sessions: inner.max_active_sessions,
+            max_server_memory_usage: inner.max_server_memory_usage,
 
             // clickhouse tcp is deprecated
             clickhouse_handler_host: "127.0.0.1".to_string(),
@@ -1151,6 +1173,9 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_cache_segment_count: inner.table_cache_segment_count,
             table_cache_bloom_index_meta_count: inner.table_cache_bloom_index_meta_count,
             table_cache_bloom_index_data_bytes: inner.table_cache_bloom_index_data_bytes,
+            data_retention_purge_interval: inner.data_retention_purge_interval,
+            auto_compaction_interval: inner.auto_compaction_interval,
+            auto_compaction_max_concurrency: inner.auto_compaction_max_concurrency,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
             async_insert_max_data_size: inner.async_insert_max_data_size,
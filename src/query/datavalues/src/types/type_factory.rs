@@ -206,6 +206,9 @@ impl TypeFactory {
                 .insert(name.to_lowercase(), data_type.clone());
             self.conversion_types.insert(name);
         }
+        // the types displayed by DESC and INFER_SCHEMA, like `BIGINT UNSIGNED`, are parsed back
+        self.case_insensitive_types
+            .insert(data_type.sql_name().to_lowercase(), data_type.clone());
     }
 
     pub fn conversion_names(&self) -> Vec<&str> {
@@ -186,11 +186,11 @@ impl FileFormatOptionsExt {
             StageFileFormatType::Json => {
                 unreachable!()
             }
-            StageFileFormatType::Avro => {
-                unreachable!()
-            }
-            StageFileFormatType::Orc => {
-                unreachable!()
+            StageFileFormatType::Avro | StageFileFormatType::Orc => {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "Unloading data to {:?} files is not supported yet",
+                    options.stage.format
+                )));
             }
             StageFileFormatType::Xml => {
                 unreachable!()
@@ -290,12 +290,8 @@ impl FileFormatTypeExt for StageFileFormatType {
                 unreachable!()
             }
             StageFileFormatType::NdJson => format_setting_ndjson(options, tz),
-            StageFileFormatType::Avro => {
-                unreachable!()
-            }
-            StageFileFormatType::Orc => {
-                unreachable!()
-            }
+            StageFileFormatType::Avro => format_setting_parquet(options, tz),
+            StageFileFormatType::Orc => format_setting_parquet(options, tz),
             StageFileFormatType::Parquet => format_setting_parquet(options, tz),
             StageFileFormatType::Xml => format_setting_xml(options, tz),
         };
@@ -307,6 +303,8 @@ impl FileFormatTypeExt for StageFileFormatType {
             StageFileFormatType::Tsv => "text/tab-separated-values; charset=UTF-8",
             StageFileFormatType::Csv => "text/csv; charset=UTF-8",
             StageFileFormatType::Parquet => "application/octet-stream",
+            StageFileFormatType::Avro => "application/avro",
+            StageFileFormatType::Orc => "application/octet-stream",
             StageFileFormatType::NdJson => "application/x-ndjson; charset=UTF-8",
             _ => "text/plain; charset=UTF-8",
         }
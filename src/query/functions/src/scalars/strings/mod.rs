@@ -31,6 +31,7 @@ mod leftright;
 mod length;
 mod locate;
 mod lower;
+mod match_;
 mod new_trim;
 mod oct;
 mod octet_length;
@@ -81,6 +82,8 @@ pub use locate::InstrFunction;
 pub use locate::LocateFunction;
 pub use locate::PositionFunction;
 pub use lower::LowerFunction;
+pub use match_::tokenize;
+pub use match_::MatchFunction;
 pub use new_trim::TrimBothFunction;
 pub use new_trim::TrimLeadingFunction;
 pub use new_trim::TrimTrailingFunction;
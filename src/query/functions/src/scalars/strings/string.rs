@@ -37,6 +37,7 @@ use crate::scalars::LeftFunction;
 use crate::scalars::LeftPadFunction;
 use crate::scalars::LocateFunction;
 use crate::scalars::LowerFunction;
+use crate::scalars::MatchFunction;
 use crate::scalars::OctFunction;
 use crate::scalars::OctetLengthFunction;
 use crate::scalars::OrdFunction;
@@ -119,6 +120,7 @@ impl StringFunction {
         factory.register("concat", ConcatFunction::desc());
         factory.register("replace", ReplaceFunction::desc());
         factory.register("strcmp", StrcmpFunction::desc());
+        factory.register("match", MatchFunction::desc());
         factory.register("locate", LocateFunction::desc());
         factory.register("position", PositionFunction::desc());
         factory.register("instr", InstrFunction::desc());
@@ -0,0 +1,449 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::avro::avro_schema::file::FileMetadata;
+use common_arrow::arrow::io::avro::avro_schema::read::read_metadata;
+use common_arrow::arrow::io::avro::read;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use opendal::io_util::CompressAlgorithm;
+use opendal::Object;
+use opendal::Operator;
+
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::impls::fields_to_schema;
+use crate::processors::sources::input_formats::impls::get_field_indices;
+use crate::processors::sources::input_formats::input_context::CopyIntoPlan;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::input_split::split_by_size;
+use crate::processors::sources::input_formats::input_split::DynData;
+use crate::processors::sources::input_formats::input_split::FileInfo;
+use crate::processors::sources::input_formats::input_split::SplitInfo;
+use crate::processors::sources::input_formats::InputFormat;
+
+const SYNC_MARKER_SIZE: usize = 16;
+const HEADER_READ_SIZE: usize = 64 * 1024;
+const BLOCK_READ_SIZE: usize = 1024 * 1024;
+// Two variable-length longs at most.
+const MAX_BLOCK_HEADER_SIZE: usize = 20;
+
+/// Avro object container files are made of a header followed by data blocks,
+/// each block ends with the 16 bytes sync marker of the file.
+///
+/// A file is split by size, a split owns the blocks starting inside its range,
+/// the start of the first block is found by scanning for the sync marker.
+pub struct InputFormatAvro;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatAvro {
+    fn default_record_delimiter(&self) -> RecordDelimiter {
+        RecordDelimiter::Crlf
+    }
+
+    fn default_field_delimiter(&self) -> u8 {
+        b'_'
+    }
+
+    async fn get_splits(
+        &self,
+        plan: &CopyIntoPlan,
+        op: &Operator,
+        _settings: &Arc<Settings>,
+        schema: &DataSchemaRef,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let split_size = plan.stage_info.copy_options.split_size;
+        let mut infos = vec![];
+        for path in &plan.files {
+            let obj = op.object(path);
+            let size = obj.metadata().await?.content_length() as usize;
+            let (metadata, header_size) = read_header(&obj, path, size).await?;
+            let file_meta = Arc::new(FileMeta::try_create(metadata, schema)?);
+
+            let body_size = size - header_size;
+            let offsets = if split_size > 0 && body_size > split_size {
+                split_by_size(body_size, split_size)
+            } else {
+                vec![(0, body_size)]
+            };
+            let num_file_splits = offsets.len();
+            let file_info = Arc::new(FileInfo {
+                path: path.clone(),
+                size,
+                num_splits: num_file_splits,
+                compress_alg: None,
+            });
+            for (i, (offset, len)) in offsets.into_iter().enumerate() {
+                infos.push(Arc::new(SplitInfo {
+                    file: file_info.clone(),
+                    seq_in_file: i,
+                    offset: header_size + offset,
+                    size: len,
+                    num_file_splits,
+                    format_info: Some(Arc::new(SplitMeta {
+                        file: file_meta.clone(),
+                    })),
+                }));
+            }
+        }
+        Ok(infos)
+    }
+
+    async fn infer_schema(
+        &self,
+        op: &Operator,
+        path: &str,
+        _field_delimiter: u8,
+        _record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+        _compress_alg: Option<CompressAlgorithm>,
+    ) -> Result<DataSchemaRef> {
+        let obj = op.object(path);
+        let size = obj.metadata().await?.content_length() as usize;
+        let (metadata, _) = read_header(&obj, path, size).await?;
+        let infer_schema = read::infer_schema(&metadata.record)?;
+        Ok(fields_to_schema(&infer_schema.fields))
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct AvroFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for AvroFormatPipe {
+    type SplitMeta = SplitMeta;
+    type ReadBatch = Vec<u8>;
+    type RowBatch = RowBatch;
+    type AligningState = AligningState;
+    type BlockBuilder = AvroBlockBuilder;
+
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: &Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let meta = Self::get_split_meta(split_info).expect("must success");
+        let op = ctx.source.get_operator()?;
+        let obj = op.object(&split_info.file.path);
+        let file_size = split_info.file.size;
+        let split_end = split_info.offset + split_info.size;
+        let marker = &meta.file.metadata.marker;
+
+        let start = if split_info.seq_in_file == 0 {
+            Some(split_info.offset)
+        } else {
+            let scan_start = split_info.offset - SYNC_MARKER_SIZE;
+            let data = obj.range_read(scan_start as u64..split_end as u64).await?;
+            data.windows(SYNC_MARKER_SIZE)
+                .position(|w| w == marker)
+                .map(|p| scan_start + p + SYNC_MARKER_SIZE)
+        };
+
+        let mut reader = BlockReader {
+            obj,
+            file_size,
+            start: start.unwrap_or(split_end),
+            data: vec![],
+        };
+        let mut pos = reader.start;
+        let mut rows = 0;
+        while pos < split_end && pos < file_size {
+            reader.fill(pos + MAX_BLOCK_HEADER_SIZE).await?;
+            let (count, size, header_len) = reader.block_header(pos, &split_info.file.path)?;
+            let block_end = pos + header_len + size + SYNC_MARKER_SIZE;
+            reader.fill(block_end).await?;
+            reader.check_marker(block_end, marker, &split_info.file.path)?;
+            rows += count;
+            pos = block_end;
+        }
+        let mut data = reader.data;
+        data.truncate(pos - reader.start);
+        tracing::debug!(
+            "read avro split {}: {} blocks bytes, {} rows",
+            split_info,
+            data.len(),
+            rows
+        );
+        Ok(RowBatch {
+            file: meta.file.clone(),
+            data,
+            rows,
+        })
+    }
+}
+
+pub struct FileMeta {
+    pub metadata: FileMetadata,
+    /// All fields of the file, the avro reader decodes records as a whole.
+    pub fields: Vec<Field>,
+    /// Positions of the schema fields in `fields`.
+    pub field_indices: Vec<usize>,
+}
+
+impl FileMeta {
+    fn try_create(metadata: FileMetadata, schema: &DataSchemaRef) -> Result<Self> {
+        let infer_schema = read::infer_schema(&metadata.record)?;
+        let field_indices = get_field_indices("avro", &infer_schema.fields, schema)?;
+        Ok(FileMeta {
+            metadata,
+            fields: infer_schema.fields,
+            field_indices,
+        })
+    }
+}
+
+pub struct SplitMeta {
+    pub file: Arc<FileMeta>,
+}
+
+impl DynData for SplitMeta {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Consecutive data blocks of an avro file, sync markers included.
+pub struct RowBatch {
+    pub file: Arc<FileMeta>,
+    pub data: Vec<u8>,
+    pub rows: usize,
+}
+
+impl RowBatchTrait for RowBatch {
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+}
+
+impl Debug for RowBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvroRowBatch")
+            .field("size", &self.data.len())
+            .field("rows", &self.rows)
+            .finish()
+    }
+}
+
+pub struct AvroBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for AvroBlockBuilder {
+    type Pipe = AvroFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        AvroBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<RowBatch>) -> Result<Vec<DataBlock>> {
+        let batch = match batch {
+            Some(b) if !b.data.is_empty() => b,
+            _ => return Ok(vec![]),
+        };
+        let file = &batch.file;
+        let reader = read::Reader::new(
+            Cursor::new(batch.data),
+            file.metadata.clone(),
+            file.fields.clone(),
+            None,
+        );
+        let mut blocks = vec![];
+        for chunk in reader {
+            let chunk = chunk?;
+            let arrays = file
+                .field_indices
+                .iter()
+                .map(|i| chunk.arrays()[*i].clone())
+                .collect::<Vec<_>>();
+            let chunk = Chunk::try_new(arrays)?;
+            blocks.push(DataBlock::from_chunk(&self.ctx.schema, &chunk)?);
+        }
+        Ok(blocks)
+    }
+}
+
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = AvroFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<RowBatch>> {
+        if let Some(b) = read_batch {
+            self.buffers.push(b);
+            return Ok(vec![]);
+        }
+        let file_in_memory = self.buffers.concat();
+        let path = &self.split_info.file.path;
+        let mut cursor = Cursor::new(&file_in_memory);
+        let metadata = read_metadata(&mut cursor).map_err(|e| bad_avro(path, e))?;
+        let header_size = cursor.position() as usize;
+        let file = Arc::new(FileMeta::try_create(metadata, &self.ctx.schema)?);
+
+        let mut row_batches = vec![];
+        let mut pos = header_size;
+        while pos < file_in_memory.len() {
+            let (rows, size, header_len) = decode_block_header(&file_in_memory[pos..])
+                .ok_or_else(|| truncated_avro(path, pos))?;
+            let block_end = pos + header_len + size + SYNC_MARKER_SIZE;
+            if block_end > file_in_memory.len() {
+                return Err(truncated_avro(path, pos));
+            }
+            if file_in_memory[block_end - SYNC_MARKER_SIZE..block_end] != file.metadata.marker {
+                return Err(bad_marker(path, block_end));
+            }
+            row_batches.push(RowBatch {
+                file: file.clone(),
+                data: file_in_memory[pos..block_end].to_vec(),
+                rows,
+            });
+            pos = block_end;
+        }
+        tracing::info!(
+            "align avro file {} of {} bytes to {} blocks",
+            path,
+            file_in_memory.len(),
+            row_batches.len()
+        );
+        Ok(row_batches)
+    }
+}
+
+/// Reads the file header, retrying with a larger prefix if the schema in it is large.
+async fn read_header(obj: &Object, path: &str, size: usize) -> Result<(FileMetadata, usize)> {
+    let mut read_size = std::cmp::min(size, HEADER_READ_SIZE);
+    loop {
+        let data = obj.range_read(..read_size as u64).await?;
+        let mut cursor = Cursor::new(&data);
+        match read_metadata(&mut cursor) {
+            Ok(metadata) => return Ok((metadata, cursor.position() as usize)),
+            Err(_) if read_size < size => read_size = std::cmp::min(size, read_size * 4),
+            Err(e) => return Err(bad_avro(path, e)),
+        }
+    }
+}
+
+/// Buffers the bytes of a file from `start`, reading more on demand.
+struct BlockReader {
+    obj: Object,
+    file_size: usize,
+    start: usize,
+    data: Vec<u8>,
+}
+
+impl BlockReader {
+    async fn fill(&mut self, end: usize) -> Result<()> {
+        let end = std::cmp::min(end, self.file_size);
+        let loaded = self.start + self.data.len();
+        if end > loaded {
+            let read_end =
+                std::cmp::min(std::cmp::max(end, loaded + BLOCK_READ_SIZE), self.file_size);
+            let more = self.obj.range_read(loaded as u64..read_end as u64).await?;
+            self.data.extend_from_slice(&more);
+        }
+        Ok(())
+    }
+
+    fn block_header(&self, pos: usize, path: &str) -> Result<(usize, usize, usize)> {
+        decode_block_header(&self.data[pos - self.start..]).ok_or_else(|| truncated_avro(path, pos))
+    }
+
+    fn check_marker(&self, block_end: usize, marker: &[u8], path: &str) -> Result<()> {
+        let end = block_end - self.start;
+        if end > self.data.len() {
+            return Err(truncated_avro(path, block_end));
+        }
+        if &self.data[end - SYNC_MARKER_SIZE..end] != marker {
+            return Err(bad_marker(path, block_end));
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the row count and the byte size of a data block,
+/// returns them with the length of the encoded header.
+fn decode_block_header(buf: &[u8]) -> Option<(usize, usize, usize)> {
+    let (rows, n1) = decode_long(buf)?;
+    let (size, n2) = decode_long(&buf[n1..])?;
+    if rows < 0 || size < 0 {
+        return None;
+    }
+    Some((rows as usize, size as usize, n1 + n2))
+}
+
+/// Decodes a zigzag encoded variable-length long.
+fn decode_long(buf: &[u8]) -> Option<(i64, usize)> {
+    let mut n = 0u64;
+    for (i, b) in buf.iter().take(10).enumerate() {
+        n |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            return Some((((n >> 1) as i64) ^ -((n & 1) as i64), i + 1));
+        }
+    }
+    None
+}
+
+fn bad_avro(path: &str, e: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("fail to read avro file {}: {}", path, e))
+}
+
+fn truncated_avro(path: &str, pos: usize) -> ErrorCode {
+    ErrorCode::BadBytes(format!(
+        "fail to read avro file {}: truncated data block at offset {}",
+        path, pos
+    ))
+}
+
+fn bad_marker(path: &str, pos: usize) -> ErrorCode {
+    ErrorCode::BadBytes(format!(
+        "fail to read avro file {}: sync marker mismatch at offset {}",
+        path, pos
+    ))
+}
@@ -16,7 +16,9 @@ use std::io::Read;
 use std::mem;
 use std::sync::Arc;
 
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::TypeDeserializer;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -25,15 +27,20 @@ use common_io::prelude::BufferReadExt;
 use common_io::prelude::FormatSettings;
 use common_io::prelude::NestedCheckpointReader;
 use common_meta_types::StageFileFormatType;
+use csv_core::ReadFieldResult;
 use csv_core::ReadRecordResult;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
 use crate::processors::sources::input_formats::impls::input_format_tsv::format_column_error;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
+use crate::processors::sources::input_formats::input_format_text::InferredType;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
 use crate::processors::sources::input_formats::input_format_text::RowBatch;
+use crate::processors::sources::input_formats::input_format_text::INFER_SCHEMA_MAX_ROWS;
 use crate::processors::sources::input_formats::InputContext;
+use crate::processors::sources::input_formats::RowError;
 
 pub struct InputFormatCSV {}
 
@@ -46,9 +53,10 @@ impl InputFormatCSV {
         format_settings: &FormatSettings,
         path: &str,
         row_index: usize,
-    ) -> Result<()> {
+    ) -> std::result::Result<(), RowError> {
         let mut field_start = 0;
-        for (c, deserializer) in deserializers.iter_mut().enumerate() {
+        for c in 0..deserializers.len() {
+            let deserializer = &mut deserializers[c];
             let field_end = field_ends[c];
             let col_data = &buf[field_start..field_end];
             let mut reader = NestedCheckpointReader::new(col_data);
@@ -57,12 +65,14 @@ impl InputFormatCSV {
             } else {
                 // todo(youngsofun): do not need escape, already done in csv-core
                 if let Err(e) = deserializer.de_text(&mut reader, format_settings) {
+                    rollback_row(deserializers, c);
                     let err_msg = format_column_error(schema, c, col_data, &e.message());
-                    return Err(csv_error(&err_msg, path, row_index));
+                    return Err(RowError::column(c, csv_error(&err_msg, path, row_index)));
                 };
                 let mut next = [0u8; 1];
-                let readn = reader.read(&mut next[..])?;
+                let readn = reader.read(&mut next[..]).map_err(ErrorCode::from)?;
                 if readn > 0 {
+                    rollback_row(deserializers, c + 1);
                     let remaining = col_data.len() - reader.pos + 1;
                     let err_msg = format!(
                         "bad field end, remain {} bytes, next char is {}",
@@ -71,7 +81,7 @@ impl InputFormatCSV {
                     );
 
                     let err_msg = format_column_error(schema, c, col_data, &err_msg);
-                    return Err(csv_error(&err_msg, path, row_index));
+                    return Err(RowError::column(c, csv_error(&err_msg, path, row_index)));
                 }
             }
             field_start = field_end;
@@ -118,27 +128,127 @@ impl InputFormatTextBase for InputFormatCSV {
         b','
     }
 
-    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+    fn infer_schema(
+        data: &[u8],
+        field_delimiter: u8,
+        record_delimiter: &RecordDelimiter,
+        skip_header: u64,
+    ) -> Result<DataSchemaRef> {
+        let mut reader = csv_core::ReaderBuilder::new()
+            .delimiter(field_delimiter)
+            .terminator(match record_delimiter {
+                RecordDelimiter::Crlf => csv_core::Terminator::CRLF,
+                RecordDelimiter::Any(v) => csv_core::Terminator::Any(*v),
+            })
+            .build();
+        // the unquoted fields are never longer than the input
+        let mut out = vec![0u8; data.len()];
+        let mut input = data;
+        let mut out_pos = 0;
+        let mut field_ends = vec![];
+        let mut rows = 0;
+        let mut names: Vec<String> = vec![];
+        let mut types: Vec<InferredType> = vec![];
+        // an empty input at last tells the end of data
+        loop {
+            let (result, n_in, n_out) = reader.read_field(input, &mut out[out_pos..]);
+            input = &input[n_in..];
+            out_pos += n_out;
+            match result {
+                ReadFieldResult::InputEmpty => {}
+                ReadFieldResult::OutputFull => {
+                    return Err(ErrorCode::Internal("csv field larger than input"));
+                }
+                ReadFieldResult::Field { record_end } => {
+                    field_ends.push(out_pos);
+                    if !record_end {
+                        continue;
+                    }
+                    let fields = field_ends
+                        .iter()
+                        .scan(0, |start, end| {
+                            let field = &out[*start..*end];
+                            *start = *end;
+                            Some(field)
+                        })
+                        .collect::<Vec<_>>();
+                    if rows == 0 && skip_header > 0 {
+                        names = fields
+                            .iter()
+                            .map(|f| String::from_utf8_lossy(f).to_string())
+                            .collect();
+                    } else if rows >= skip_header as usize {
+                        if types.len() < fields.len() {
+                            types.resize(fields.len(), InferredType::Null);
+                        }
+                        for (typ, field) in types.iter_mut().zip(fields) {
+                            *typ = typ
+                                .merge(infer_field_type(field))
+                                .unwrap_or(InferredType::String);
+                        }
+                    }
+                    rows += 1;
+                    out_pos = 0;
+                    field_ends.clear();
+                    if rows >= skip_header as usize + INFER_SCHEMA_MAX_ROWS {
+                        break;
+                    }
+                }
+                ReadFieldResult::End => break,
+            }
+        }
+        if types.is_empty() && names.is_empty() {
+            return Err(ErrorCode::BadBytes(
+                "no row found to infer the schema of csv file",
+            ));
+        }
+        let num_fields = std::cmp::max(names.len(), types.len());
+        let fields = (0..num_fields)
+            .map(|i| {
+                let name = names
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| format!("${}", i + 1));
+                let typ = types.get(i).copied().unwrap_or(InferredType::Null);
+                DataField::new_nullable(&name, typ.to_data_type())
+            })
+            .collect();
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<usize> {
         let columns = &mut builder.mutable_columns;
         let n_column = columns.len();
         let mut start = 0usize;
         let start_row = batch.start_row.expect("must success");
         let mut field_end_idx = 0;
+        let mut num_rows = 0;
+        let mut bad_rows = batch.bad_rows.into_iter().peekable();
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end];
-            Self::read_row(
-                buf,
-                columns,
-                &builder.ctx.schema,
-                &batch.field_ends[field_end_idx..field_end_idx + n_column],
-                &builder.ctx.format_settings,
-                &batch.path,
-                start_row + i,
-            )?;
+            let res = match bad_rows.next_if(|(row, _)| *row == i) {
+                Some((_, e)) => Err(RowError::from(e)),
+                None => Self::read_row(
+                    buf,
+                    columns,
+                    &builder.ctx.schema,
+                    &batch.field_ends[field_end_idx..field_end_idx + n_column],
+                    &builder.ctx.format_settings,
+                    &batch.path,
+                    start_row + i,
+                ),
+            };
+            match res {
+                Ok(()) => num_rows += 1,
+                Err(e) => {
+                    let row = (batch.split_seq, start_row + i);
+                    builder.ctx.on_row_error(&batch.path, row, e)?
+                }
+            }
             start = *end;
             field_end_idx += n_column;
         }
-        Ok(())
+        Ok(num_rows)
     }
 
     fn align(state: &mut AligningState<Self>, buf_in: &[u8]) -> Result<Vec<RowBatch>> {
@@ -207,6 +317,8 @@ impl InputFormatTextBase for InputFormatCSV {
             batch_id: state.batch_id,
             offset: 0,
             start_row: Some(state.rows),
+            split_seq: state.split_info.seq_in_file,
+            bad_rows: vec![],
         };
 
         while !buf.is_empty() {
@@ -234,13 +346,16 @@ impl InputFormatTextBase for InputFormatCSV {
                     ));
                 }
                 ReadRecordResult::Record => {
-                    Self::check_num_field(
+                    // The row is skipped or fails the statement when deserialized, by ON_ERROR.
+                    if let Err(e) = Self::check_num_field(
                         num_fields,
                         endlen,
                         field_ends,
                         &state.path,
                         start_row + row_batch.row_ends.len(),
-                    )?;
+                    ) {
+                        row_batch.bad_rows.push((row_batch.row_ends.len(), e));
+                    }
                     row_batch
                         .field_ends
                         .extend_from_slice(&field_ends[..num_fields]);
@@ -329,7 +444,16 @@ impl InputFormatTextBase for InputFormatCSV {
                 ));
             }
             ReadRecordResult::Record => {
-                Self::check_num_field(num_fields, endlen, field_ends, &state.path, start_row)?;
+                let bad_rows = match Self::check_num_field(
+                    num_fields,
+                    endlen,
+                    field_ends,
+                    &state.path,
+                    start_row,
+                ) {
+                    Ok(()) => vec![],
+                    Err(e) => vec![(0, e)],
+                };
                 let data = mem::take(&mut reader.out);
 
                 let row_batch = RowBatch {
@@ -340,6 +464,8 @@ impl InputFormatTextBase for InputFormatCSV {
                     batch_id: state.batch_id,
                     offset: 0,
                     start_row: Some(state.rows),
+                    split_seq: state.split_info.seq_in_file,
+                    bad_rows,
                 };
                 res.push(row_batch);
 
@@ -412,3 +538,21 @@ fn csv_error(msg: &str, path: &str, row: usize) -> ErrorCode {
 
     ErrorCode::BadBytes(msg)
 }
+
+// The type of a csv field, a field which is empty or `\N` tells nothing.
+fn infer_field_type(field: &[u8]) -> InferredType {
+    if field.is_empty() || field == b"\\N" {
+        return InferredType::Null;
+    }
+    if field.eq_ignore_ascii_case(b"true") || field.eq_ignore_ascii_case(b"false") {
+        return InferredType::Boolean;
+    }
+    match std::str::from_utf8(field) {
+        Ok(v) if v.parse::<i64>().is_ok() => InferredType::Int64,
+        // words like `inf` and `nan` are not taken as numbers
+        Ok(v) if v.bytes().any(|b| b.is_ascii_digit()) && v.parse::<f64>().is_ok() => {
+            InferredType::Float64
+        }
+        _ => InferredType::String,
+    }
+}
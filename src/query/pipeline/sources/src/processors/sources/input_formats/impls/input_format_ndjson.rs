@@ -13,9 +13,12 @@
 //  limitations under the License.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use bstr::ByteSlice;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::TypeDeserializer;
 use common_datavalues::TypeDeserializerImpl;
 use common_exception::ErrorCode;
@@ -23,10 +26,15 @@ use common_exception::Result;
 use common_io::prelude::FormatSettings;
 use common_meta_types::StageFileFormatType;
 
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
+use crate::processors::sources::input_formats::input_format_text::InferredType;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
 use crate::processors::sources::input_formats::input_format_text::RowBatch;
+use crate::processors::sources::input_formats::input_format_text::INFER_SCHEMA_MAX_ROWS;
+use crate::processors::sources::input_formats::RowError;
 
 pub struct InputFormatNDJson {}
 
@@ -36,8 +44,8 @@ impl InputFormatNDJson {
         deserializers: &mut [TypeDeserializerImpl],
         format_settings: &FormatSettings,
         schema: &DataSchemaRef,
-    ) -> Result<()> {
-        let mut json: serde_json::Value = serde_json::from_reader(buf)?;
+    ) -> std::result::Result<(), RowError> {
+        let mut json: serde_json::Value = serde_json::from_reader(buf).map_err(ErrorCode::from)?;
         // if it's not case_sensitive, we convert to lowercase
         if !format_settings.ident_case_sensitive {
             if let serde_json::Value::Object(x) = json {
@@ -46,22 +54,26 @@ impl InputFormatNDJson {
             }
         }
 
-        for (f, deser) in schema.fields().iter().zip(deserializers.iter_mut()) {
+        for (i, f) in schema.fields().iter().enumerate() {
             let value = if format_settings.ident_case_sensitive {
                 &json[f.name().to_owned()]
             } else {
                 &json[f.name().to_lowercase()]
             };
 
-            deser.de_json(value, format_settings).map_err(|e| {
+            if let Err(e) = deserializers[i].de_json(value, format_settings) {
+                rollback_row(deserializers, i);
                 let value_str = format!("{:?}", value);
-                ErrorCode::BadBytes(format!(
-                    "{}. column={} value={}",
-                    e,
-                    f.name(),
-                    maybe_truncated(&value_str, 1024),
-                ))
-            })?;
+                return Err(RowError::column(
+                    i,
+                    ErrorCode::BadBytes(format!(
+                        "{}. column={} value={}",
+                        e,
+                        f.name(),
+                        maybe_truncated(&value_str, 1024),
+                    )),
+                ));
+            }
         }
         Ok(())
     }
@@ -80,10 +92,56 @@ impl InputFormatTextBase for InputFormatNDJson {
         b','
     }
 
-    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+    fn infer_schema(
+        data: &[u8],
+        _field_delimiter: u8,
+        record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+    ) -> Result<DataSchemaRef> {
+        let mut names: Vec<String> = vec![];
+        let mut types: HashMap<String, InferredType> = HashMap::new();
+        for row in data
+            .split(|b| *b == record_delimiter.end())
+            .map(|row| row.trim())
+            .filter(|row| !row.is_empty())
+            .take(INFER_SCHEMA_MAX_ROWS)
+        {
+            let json: serde_json::Value = serde_json::from_slice(row).map_err(ErrorCode::from)?;
+            let object = json.as_object().ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "fail to infer the schema of NDJSON: row is not an object: {}",
+                    maybe_truncated(&json.to_string(), 1024)
+                ))
+            })?;
+            for (key, value) in object {
+                let typ = infer_value_type(value);
+                match types.get_mut(key) {
+                    Some(t) => *t = t.merge(typ).unwrap_or(InferredType::Variant),
+                    None => {
+                        names.push(key.clone());
+                        types.insert(key.clone(), typ);
+                    }
+                }
+            }
+        }
+        if names.is_empty() {
+            return Err(ErrorCode::BadBytes(
+                "no row found to infer the schema of NDJSON file",
+            ));
+        }
+        // a key may be missing in some rows, so all the columns are nullable
+        let fields = names
+            .iter()
+            .map(|name| DataField::new_nullable(name, types[name].to_data_type()))
+            .collect();
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<usize> {
         let columns = &mut builder.mutable_columns;
         let mut start = 0usize;
         let start_row = batch.start_row;
+        let mut num_rows = 0;
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end];
             let buf = buf.trim();
@@ -102,16 +160,25 @@ impl InputFormatTextBase for InputFormatNDJson {
                     let msg = format!(
                         "fail to parse NDJSON: {},  path={}, offset={}, {}",
                         &batch.path,
-                        e,
+                        e.error,
                         batch.offset + start,
                         row_info,
                     );
-                    return Err(ErrorCode::BadBytes(msg));
+                    let row = start_row.unwrap_or_default() + i;
+                    let e = RowError {
+                        column: e.column,
+                        error: ErrorCode::BadBytes(msg),
+                    };
+                    builder
+                        .ctx
+                        .on_row_error(&batch.path, (batch.split_seq, row), e)?;
+                } else {
+                    num_rows += 1;
                 }
             }
             start = *end;
         }
-        Ok(())
+        Ok(num_rows)
     }
 
     fn align(state: &mut AligningState<Self>, buf: &[u8]) -> Result<Vec<RowBatch>> {
@@ -131,3 +198,14 @@ fn maybe_truncated(s: &str, limit: usize) -> Cow<'_, str> {
         Cow::Borrowed(s)
     }
 }
+
+fn infer_value_type(value: &serde_json::Value) -> InferredType {
+    match value {
+        serde_json::Value::Null => InferredType::Null,
+        serde_json::Value::Bool(_) => InferredType::Boolean,
+        serde_json::Value::Number(n) if n.is_i64() => InferredType::Int64,
+        serde_json::Value::Number(_) => InferredType::Float64,
+        serde_json::Value::String(_) => InferredType::String,
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => InferredType::Variant,
+    }
+}
@@ -0,0 +1,853 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::growable::make_growable;
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::BinaryArray;
+use common_arrow::arrow::array::BooleanArray;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::PrimitiveArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::error::Error as ArrowError;
+use common_arrow::arrow::error::Result as ArrowResult;
+use common_arrow::arrow::io::orc::format;
+use common_arrow::arrow::io::orc::format::proto::column_encoding::Kind as EncodingKind;
+use common_arrow::arrow::io::orc::format::proto::stream::Kind as StreamKind;
+use common_arrow::arrow::io::orc::format::proto::r#type::Kind as TypeKind;
+use common_arrow::arrow::io::orc::format::proto::Footer;
+use common_arrow::arrow::io::orc::format::proto::StripeFooter;
+use common_arrow::arrow::io::orc::format::proto::StripeInformation;
+use common_arrow::arrow::io::orc::format::proto::Type;
+use common_arrow::arrow::io::orc::format::read::decode;
+use common_arrow::arrow::io::orc::format::read::Column;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::arrow::io::orc::read;
+use common_arrow::arrow::types::NativeType;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use opendal::io_util::CompressAlgorithm;
+use opendal::Object;
+use opendal::Operator;
+
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::impls::fields_to_schema;
+use crate::processors::sources::input_formats::impls::get_field_indices;
+use crate::processors::sources::input_formats::input_context::CopyIntoPlan;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::input_split::DynData;
+use crate::processors::sources::input_formats::input_split::FileInfo;
+use crate::processors::sources::input_formats::input_split::SplitInfo;
+use crate::processors::sources::input_formats::InputFormat;
+
+const TAIL_READ_SIZE: usize = 64 * 1024;
+
+/// Each stripe of an ORC file is a split.
+///
+/// A struct column is read as a struct array, a list column as a list array, and a map
+/// column as a list array of the (key, value) structs.
+pub struct InputFormatOrc;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatOrc {
+    fn default_record_delimiter(&self) -> RecordDelimiter {
+        RecordDelimiter::Crlf
+    }
+
+    fn default_field_delimiter(&self) -> u8 {
+        b'_'
+    }
+
+    async fn get_splits(
+        &self,
+        plan: &CopyIntoPlan,
+        op: &Operator,
+        _settings: &Arc<Settings>,
+        schema: &DataSchemaRef,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let mut infos = vec![];
+        for path in &plan.files {
+            let obj = op.object(path);
+            let size = obj.metadata().await?.content_length() as usize;
+            let metadata = read_tail(&obj, path, size).await?;
+            let stripes = metadata.footer.stripes.clone();
+            let file_meta = Arc::new(FileMeta::try_create(metadata, schema, path)?);
+
+            let num_file_splits = stripes.len();
+            let file_info = Arc::new(FileInfo {
+                path: path.clone(),
+                size,
+                num_splits: num_file_splits,
+                compress_alg: None,
+            });
+            for (i, stripe) in stripes.iter().enumerate() {
+                infos.push(Arc::new(SplitInfo {
+                    file: file_info.clone(),
+                    seq_in_file: i,
+                    offset: stripe.offset() as usize,
+                    size: stripe_size(stripe),
+                    num_file_splits,
+                    format_info: Some(Arc::new(SplitMeta {
+                        file: file_meta.clone(),
+                        stripe: i,
+                    })),
+                }));
+            }
+        }
+        Ok(infos)
+    }
+
+    async fn infer_schema(
+        &self,
+        op: &Operator,
+        path: &str,
+        _field_delimiter: u8,
+        _record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+        _compress_alg: Option<CompressAlgorithm>,
+    ) -> Result<DataSchemaRef> {
+        let obj = op.object(path);
+        let size = obj.metadata().await?.content_length() as usize;
+        let metadata = read_tail(&obj, path, size).await?;
+        let file_meta = FileMeta::try_create(metadata, &DataSchemaRefExt::create(vec![]), path)?;
+        Ok(fields_to_schema(&file_meta.fields))
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct OrcFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for OrcFormatPipe {
+    type SplitMeta = SplitMeta;
+    type ReadBatch = Vec<u8>;
+    type RowBatch = StripeInMemory;
+    type AligningState = AligningState;
+    type BlockBuilder = OrcBlockBuilder;
+
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: &Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let meta = Self::get_split_meta(split_info).expect("must success");
+        let op = ctx.source.get_operator()?;
+        let obj = op.object(&split_info.file.path);
+        let start = split_info.offset;
+        let data = obj
+            .range_read(start as u64..(start + split_info.size) as u64)
+            .await?;
+        Ok(StripeInMemory {
+            file: meta.file.clone(),
+            stripe: meta.stripe,
+            data: RangeReader::new(start, split_info.file.size, data),
+        })
+    }
+}
+
+pub struct FileMeta {
+    pub path: String,
+    pub metadata: FileMetadata,
+    pub fields: Vec<Field>,
+    /// The ORC columns of `fields`.
+    pub columns: Vec<OrcColumn>,
+    /// Positions of the schema fields in `fields`.
+    pub field_indices: Vec<usize>,
+}
+
+impl FileMeta {
+    fn try_create(metadata: FileMetadata, schema: &DataSchemaRef, path: &str) -> Result<Self> {
+        let (fields, columns) = match metadata.footer.types.first() {
+            Some(root) if root.kind() == TypeKind::Struct => {
+                infer_struct(&metadata.footer, root, false, path)?
+            }
+            _ => return Err(bad_orc(path, "the root type is not a struct")),
+        };
+        let field_indices = get_field_indices("orc", &fields, schema)?;
+        Ok(FileMeta {
+            path: path.to_string(),
+            metadata,
+            fields,
+            columns,
+            field_indices,
+        })
+    }
+}
+
+/// A column of an ORC file with the columns nested in it.
+///
+/// The ids of the columns are assigned in pre-order of the type tree, the root struct takes
+/// the id 0, so a top level column takes its position plus one only if no column before it
+/// is nested.
+pub struct OrcColumn {
+    pub id: u32,
+    pub kind: TypeKind,
+    pub children: Vec<OrcColumn>,
+}
+
+impl OrcColumn {
+    fn is_nested(&self) -> bool {
+        matches!(self.kind, TypeKind::Struct | TypeKind::List | TypeKind::Map)
+    }
+}
+
+/// Infers the fields of a struct type and their columns.
+///
+/// The children of a nullable struct are nullable, since the null structs get null children
+/// in arrow.
+fn infer_struct(
+    footer: &Footer,
+    ty: &Type,
+    nullable: bool,
+    path: &str,
+) -> Result<(Vec<Field>, Vec<OrcColumn>)> {
+    if ty.subtypes.len() != ty.field_names.len() {
+        return Err(bad_orc(
+            path,
+            format!(
+                "struct of {} subtypes and {} field names",
+                ty.subtypes.len(),
+                ty.field_names.len()
+            ),
+        ));
+    }
+    ty.subtypes
+        .iter()
+        .zip(ty.field_names.iter())
+        .map(|(id, name)| infer_column(footer, *id, name, nullable, path))
+        .collect::<Result<Vec<_>>>()
+        .map(|columns| columns.into_iter().unzip())
+}
+
+fn infer_column(
+    footer: &Footer,
+    id: u32,
+    name: &str,
+    parent_nullable: bool,
+    path: &str,
+) -> Result<(Field, OrcColumn)> {
+    let ty = footer
+        .types
+        .get(id as usize)
+        .ok_or_else(|| bad_orc(path, format!("column {} has no type", id)))?;
+    // A column is taken as nullable unless its statistics tell that it has no null.
+    let nullable = parent_nullable
+        || footer
+            .statistics
+            .get(id as usize)
+            .and_then(|s| s.has_null)
+            .unwrap_or(true);
+    let subtype = |i: usize| {
+        ty.subtypes.get(i).cloned().ok_or_else(|| {
+            bad_orc(
+                path,
+                format!("column {} of {:?} has no subtype {}", id, ty.kind(), i),
+            )
+        })
+    };
+    let (data_type, children) = match ty.kind() {
+        TypeKind::Boolean => (DataType::Boolean, vec![]),
+        TypeKind::Byte => (DataType::Int8, vec![]),
+        TypeKind::Short => (DataType::Int16, vec![]),
+        TypeKind::Int => (DataType::Int32, vec![]),
+        TypeKind::Long => (DataType::Int64, vec![]),
+        TypeKind::Float => (DataType::Float32, vec![]),
+        TypeKind::Double => (DataType::Float64, vec![]),
+        TypeKind::String | TypeKind::Varchar | TypeKind::Char => (DataType::Utf8, vec![]),
+        TypeKind::Binary => (DataType::Binary, vec![]),
+        TypeKind::Struct => {
+            let (fields, children) = infer_struct(footer, ty, nullable, path)?;
+            (DataType::Struct(fields), children)
+        }
+        TypeKind::List => {
+            let (item, child) = infer_column(footer, subtype(0)?, "item", false, path)?;
+            (DataType::LargeList(Box::new(item)), vec![child])
+        }
+        TypeKind::Map => {
+            let (key, key_column) = infer_column(footer, subtype(0)?, "key", false, path)?;
+            let (value, value_column) = infer_column(footer, subtype(1)?, "value", false, path)?;
+            let entries = Field::new("entries", DataType::Struct(vec![key, value]), false);
+            (DataType::LargeList(Box::new(entries)), vec![
+                key_column,
+                value_column,
+            ])
+        }
+        kind => {
+            return Err(ErrorCode::Unimplemented(format!(
+                "fail to read orc file {}: column {} is of type {:?}, which is not supported yet",
+                path, name, kind
+            )));
+        }
+    };
+    let column = OrcColumn {
+        id,
+        kind: ty.kind(),
+        children,
+    };
+    Ok((Field::new(name, data_type, nullable), column))
+}
+
+pub struct SplitMeta {
+    pub file: Arc<FileMeta>,
+    pub stripe: usize,
+}
+
+impl DynData for SplitMeta {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct StripeInMemory {
+    pub file: Arc<FileMeta>,
+    pub stripe: usize,
+    pub data: RangeReader,
+}
+
+impl StripeInMemory {
+    fn get_arrow_chunk(&mut self) -> Result<Chunk<Box<dyn Array>>> {
+        let file = self.file.clone();
+        let num_rows = self.rows();
+        let mut scratch = vec![];
+        let footer = format::read::read_stripe_footer(
+            &mut self.data,
+            &file.metadata,
+            self.stripe,
+            &mut scratch,
+        )
+        .map_err(|e| bad_orc(&file.path, e))?;
+        let mut reader = StripeReader {
+            file: &file,
+            stripe: self.stripe,
+            data: &mut self.data,
+            footer: Some(footer),
+            scratch,
+        };
+        let mut arrays = Vec::with_capacity(file.field_indices.len());
+        for i in &file.field_indices {
+            let (field, column) = (&file.fields[*i], &file.columns[*i]);
+            let array = if column.is_nested() {
+                reader.read_array(column, field, num_rows)?
+            } else {
+                reader.read_column(column.id, |c| read::deserialize(field.data_type.clone(), c))?
+            };
+            arrays.push(array);
+        }
+        Ok(Chunk::try_new(arrays)?)
+    }
+}
+
+/// Reads the columns of a stripe one by one, the footer of the stripe is moved into each
+/// column read and taken back after it.
+struct StripeReader<'a> {
+    file: &'a FileMeta,
+    stripe: usize,
+    data: &'a mut RangeReader,
+    footer: Option<StripeFooter>,
+    scratch: Vec<u8>,
+}
+
+impl<'a> StripeReader<'a> {
+    fn read_column<T, E: Debug>(
+        &mut self,
+        id: u32,
+        f: impl FnOnce(&Column) -> std::result::Result<T, E>,
+    ) -> Result<T> {
+        let footer = self
+            .footer
+            .take()
+            .expect("footer is taken back after each column read");
+        let column = format::read::read_stripe_column(
+            self.data,
+            &self.file.metadata,
+            self.stripe,
+            footer,
+            id,
+            std::mem::take(&mut self.scratch),
+        )
+        .map_err(|e| bad_orc(&self.file.path, e))?;
+        let res = f(&column).map_err(|e| bad_orc(&self.file.path, e));
+        let (footer, scratch) = column.into_inner();
+        self.footer = Some(footer);
+        self.scratch = scratch;
+        res
+    }
+
+    /// Reads a column of `num_values` slots.
+    ///
+    /// The slots of a top level column are the rows of the stripe. The slots of a nested column
+    /// are the non-null values of its parent struct, or the items of its parent list, so
+    /// the decoding of arrow2, which takes the rows of the stripe as the slots, does not work
+    /// for them.
+    fn read_array(
+        &mut self,
+        column: &OrcColumn,
+        field: &Field,
+        num_values: usize,
+    ) -> Result<Box<dyn Array>> {
+        let data_type = field.data_type();
+        let (validity, array) = match column.kind {
+            TypeKind::Struct => {
+                let validity = self.read_column(column.id, |c| read_validity(c, num_values))?;
+                let num_valid = count_valid(&validity, num_values);
+                let values = StructArray::get_fields(data_type)
+                    .iter()
+                    .zip(column.children.iter())
+                    .map(|(f, c)| self.read_array(c, f, num_valid))
+                    .collect::<Result<Vec<_>>>()?;
+                let array = StructArray::try_new(data_type.clone(), values, None)?;
+                (validity, Box::new(array) as Box<dyn Array>)
+            }
+            TypeKind::List | TypeKind::Map => {
+                let (validity, lengths) = self.read_column(column.id, |c| {
+                    let validity = read_validity(c, num_values)?;
+                    let num_valid = count_valid(&validity, num_values);
+                    let lengths = read_unsigned(c, StreamKind::Length, num_valid)?;
+                    Ok::<_, ArrowError>((validity, lengths))
+                })?;
+                let mut offsets = Vec::with_capacity(lengths.len() + 1);
+                offsets.push(0i64);
+                for length in lengths {
+                    offsets.push(offsets[offsets.len() - 1] + length as i64);
+                }
+                let num_items = offsets[offsets.len() - 1] as usize;
+                let item = ListArray::<i64>::get_child_field(data_type);
+                let values = if column.kind == TypeKind::Map {
+                    let entries = StructArray::get_fields(item.data_type());
+                    let keys = self.read_array(&column.children[0], &entries[0], num_items)?;
+                    let values = self.read_array(&column.children[1], &entries[1], num_items)?;
+                    let array =
+                        StructArray::try_new(item.data_type().clone(), vec![keys, values], None)?;
+                    Box::new(array) as Box<dyn Array>
+                } else {
+                    self.read_array(&column.children[0], item, num_items)?
+                };
+                let array =
+                    ListArray::<i64>::try_new(data_type.clone(), offsets.into(), values, None)?;
+                (validity, Box::new(array) as Box<dyn Array>)
+            }
+            _ => self.read_column(column.id, |c| {
+                let validity = read_validity(c, num_values)?;
+                let num_valid = count_valid(&validity, num_values);
+                let array = read_primitive(c, data_type, num_valid)?;
+                Ok::<_, ArrowError>((validity, array))
+            })?,
+        };
+        Ok(spread_array(array, &validity))
+    }
+}
+
+impl RowBatchTrait for StripeInMemory {
+    fn size(&self) -> usize {
+        self.data.data.len()
+    }
+
+    fn rows(&self) -> usize {
+        self.file.metadata.footer.stripes[self.stripe].number_of_rows() as usize
+    }
+}
+
+impl Debug for StripeInMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StripeInMemory")
+    }
+}
+
+pub struct OrcBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for OrcBlockBuilder {
+    type Pipe = OrcFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        OrcBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, mut batch: Option<StripeInMemory>) -> Result<Vec<DataBlock>> {
+        if let Some(stripe) = batch.as_mut() {
+            let chunk = stripe.get_arrow_chunk()?;
+            let block = DataBlock::from_chunk(&self.ctx.schema, &chunk)?;
+            Ok(vec![block])
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = OrcFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<StripeInMemory>> {
+        if let Some(b) = read_batch {
+            self.buffers.push(b);
+            return Ok(vec![]);
+        }
+        let file_in_memory = self.buffers.concat();
+        let size = file_in_memory.len();
+        let path = &self.split_info.file.path;
+        let metadata = format::read::read_metadata(&mut Cursor::new(&file_in_memory))
+            .map_err(|e| bad_orc(path, e))?;
+        let file = Arc::new(FileMeta::try_create(metadata, &self.ctx.schema, path)?);
+
+        let stripes = &file.metadata.footer.stripes;
+        let mut row_batches = Vec::with_capacity(stripes.len());
+        for (i, stripe) in stripes.iter().enumerate() {
+            let start = stripe.offset() as usize;
+            let end = start + stripe_size(stripe);
+            if end > size {
+                return Err(ErrorCode::BadBytes(format!(
+                    "fail to read orc file {}: stripe {} out of file range",
+                    path, i
+                )));
+            }
+            row_batches.push(StripeInMemory {
+                file: file.clone(),
+                stripe: i,
+                data: RangeReader::new(start, size, file_in_memory[start..end].to_vec()),
+            });
+        }
+        tracing::info!(
+            "align orc file {} of {} bytes to {} stripes",
+            path,
+            size,
+            row_batches.len()
+        );
+        Ok(row_batches)
+    }
+}
+
+/// A byte range of a file loaded in memory, which can be read as the whole file
+/// as long as the reads do not go out of the range.
+pub struct RangeReader {
+    start: usize,
+    file_size: usize,
+    data: Vec<u8>,
+    pos: usize,
+}
+
+impl RangeReader {
+    fn new(start: usize, file_size: usize, data: Vec<u8>) -> Self {
+        RangeReader {
+            start,
+            file_size,
+            data,
+            pos: start,
+        }
+    }
+}
+
+impl Read for RangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos < self.start {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("read offset {} out of loaded range", self.pos),
+            ));
+        }
+        let offset = std::cmp::min(self.pos - self.start, self.data.len());
+        let n = std::cmp::min(buf.len(), self.data.len() - offset);
+        if n == 0 && !buf.is_empty() && self.pos < self.file_size {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("read offset {} out of loaded range", self.pos),
+            ));
+        }
+        buf[..n].copy_from_slice(&self.data[offset..offset + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for RangeReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => p as i64,
+            SeekFrom::End(p) => self.file_size as i64 + p,
+            SeekFrom::Current(p) => self.pos as i64 + p,
+        };
+        if new_pos < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek to a negative position",
+            ));
+        }
+        self.pos = new_pos as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+/// Reads the postscript and the footer from the tail of the file,
+/// retrying with a larger tail if they do not fit in.
+async fn read_tail(obj: &Object, path: &str, size: usize) -> Result<FileMetadata> {
+    let mut read_size = std::cmp::min(size, TAIL_READ_SIZE);
+    loop {
+        let start = size - read_size;
+        let data = obj.range_read(start as u64..size as u64).await?;
+        let mut reader = RangeReader::new(start, size, data);
+        match format::read::read_metadata(&mut reader) {
+            Ok(metadata) => return Ok(metadata),
+            Err(_) if read_size < size => read_size = std::cmp::min(size, read_size * 4),
+            Err(e) => return Err(bad_orc(path, e)),
+        }
+    }
+}
+
+fn stripe_size(stripe: &StripeInformation) -> usize {
+    (stripe.index_length() + stripe.data_length() + stripe.footer_length()) as usize
+}
+
+/// Reads the present stream of a column of `num_values` slots, a column without null
+/// has no present stream.
+fn read_validity(column: &Column, num_values: usize) -> ArrowResult<Option<Bitmap>> {
+    let stream = match column.get_stream(StreamKind::Present, vec![]) {
+        Ok(stream) => stream,
+        Err(format::error::Error::InvalidKind(_, _)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut validity = MutableBitmap::with_capacity(num_values);
+    for is_valid in decode::BooleanIter::new(stream, num_values) {
+        validity.push(is_valid?);
+    }
+    Ok(validity.into())
+}
+
+fn count_valid(validity: &Option<Bitmap>, num_values: usize) -> usize {
+    validity
+        .as_ref()
+        .map_or(num_values, |v| v.len() - v.unset_bits())
+}
+
+/// Only the non-null values of a column are stored, spreads them to the slots of the column.
+fn spread_array(array: Box<dyn Array>, validity: &Option<Bitmap>) -> Box<dyn Array> {
+    match validity {
+        None => array,
+        Some(validity) => {
+            let mut growable = make_growable(&[array.as_ref()], true, validity.len());
+            let mut next = 0;
+            for is_valid in validity.iter() {
+                if is_valid {
+                    growable.extend(0, next, 1);
+                    next += 1;
+                } else {
+                    growable.extend_validity(1);
+                }
+            }
+            growable.as_box()
+        }
+    }
+}
+
+/// Reads `num_values` non-null values of a column of primitive type.
+fn read_primitive(
+    column: &Column,
+    data_type: &DataType,
+    num_values: usize,
+) -> ArrowResult<Box<dyn Array>> {
+    let array: Box<dyn Array> = match data_type {
+        DataType::Boolean => {
+            let stream = column.get_stream(StreamKind::Data, vec![])?;
+            let values = decode::BooleanIter::new(stream, num_values)
+                .collect::<std::result::Result<Bitmap, _>>()?;
+            Box::new(BooleanArray::try_new(data_type.clone(), values, None)?)
+        }
+        DataType::Int16 => read_int::<i16>(column, data_type, num_values)?,
+        DataType::Int32 => read_int::<i32>(column, data_type, num_values)?,
+        DataType::Int64 => read_int::<i64>(column, data_type, num_values)?,
+        DataType::Float32 => {
+            let stream = column.get_stream(StreamKind::Data, vec![])?;
+            let values = decode::FloatIter::<f32, _>::new(stream, num_values)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let array = PrimitiveArray::<f32>::try_new(data_type.clone(), values.into(), None)?;
+            Box::new(array)
+        }
+        DataType::Float64 => {
+            let stream = column.get_stream(StreamKind::Data, vec![])?;
+            let values = decode::FloatIter::<f64, _>::new(stream, num_values)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let array = PrimitiveArray::<f64>::try_new(data_type.clone(), values.into(), None)?;
+            Box::new(array)
+        }
+        DataType::Utf8 => {
+            let (offsets, values) = read_binary(column, num_values)?;
+            let array =
+                Utf8Array::<i32>::try_new(data_type.clone(), offsets.into(), values.into(), None)?;
+            Box::new(array)
+        }
+        DataType::Binary => {
+            let (offsets, values) = read_binary(column, num_values)?;
+            let array = BinaryArray::<i32>::try_new(
+                data_type.clone(),
+                offsets.into(),
+                values.into(),
+                None,
+            )?;
+            Box::new(array)
+        }
+        dt => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Deserializing nested values of {:?} from ORC",
+                dt
+            )));
+        }
+    };
+    Ok(array)
+}
+
+fn read_int<T: NativeType + TryFrom<i64>>(
+    column: &Column,
+    data_type: &DataType,
+    num_values: usize,
+) -> ArrowResult<Box<dyn Array>> {
+    check_rle_v2(column)?;
+    let stream = column.get_stream(StreamKind::Data, vec![])?;
+    let values = decode::SignedRleV2Iter::new(stream, num_values, vec![])
+        .map(|v| {
+            let v = v?;
+            T::try_from(v).map_err(|_| {
+                ArrowError::ExternalFormat(format!("value {} out of range of {:?}", v, data_type))
+            })
+        })
+        .collect::<ArrowResult<Vec<_>>>()?;
+    let array = PrimitiveArray::<T>::try_new(data_type.clone(), values.into(), None)?;
+    Ok(Box::new(array))
+}
+
+/// Reads `num_values` non-null values of a column of string or binary type, returns their
+/// offsets and bytes.
+fn read_binary(column: &Column, num_values: usize) -> ArrowResult<(Vec<i32>, Vec<u8>)> {
+    let (lengths, bytes) = match column.encoding().kind() {
+        EncodingKind::DirectV2 => {
+            let lengths = read_unsigned(column, StreamKind::Length, num_values)?;
+            let bytes = read_bytes(column, StreamKind::Data, lengths.iter().sum())?;
+            (lengths, bytes)
+        }
+        EncodingKind::DictionaryV2 => {
+            let dict_size = column.dictionary_size().unwrap_or_default();
+            let dict_lengths = read_unsigned(column, StreamKind::Length, dict_size)?;
+            let dict_bytes = read_bytes(
+                column,
+                StreamKind::DictionaryData,
+                dict_lengths.iter().sum(),
+            )?;
+            let mut dict_offsets = Vec::with_capacity(dict_size + 1);
+            dict_offsets.push(0usize);
+            for length in &dict_lengths {
+                dict_offsets.push(dict_offsets[dict_offsets.len() - 1] + *length as usize);
+            }
+            let mut lengths = Vec::with_capacity(num_values);
+            let mut bytes = vec![];
+            for i in read_unsigned(column, StreamKind::Data, num_values)? {
+                let i = i as usize;
+                if i >= dict_size {
+                    return Err(ArrowError::ExternalFormat(format!(
+                        "dictionary index {} out of {} entries",
+                        i, dict_size
+                    )));
+                }
+                lengths.push(dict_lengths[i]);
+                bytes.extend_from_slice(&dict_bytes[dict_offsets[i]..dict_offsets[i + 1]]);
+            }
+            (lengths, bytes)
+        }
+        kind => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Deserializing strings of {:?} encoding from ORC",
+                kind
+            )));
+        }
+    };
+    let mut offsets = Vec::with_capacity(lengths.len() + 1);
+    offsets.push(0i32);
+    let mut end = 0usize;
+    for length in lengths {
+        end += length as usize;
+        offsets.push(i32::try_from(end).map_err(|_| ArrowError::Overflow)?);
+    }
+    Ok((offsets, bytes))
+}
+
+fn read_unsigned(column: &Column, kind: StreamKind, num_values: usize) -> ArrowResult<Vec<u64>> {
+    check_rle_v2(column)?;
+    let stream = column.get_stream(kind, vec![])?;
+    let values = decode::UnsignedRleV2Iter::new(stream, num_values, vec![])
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(values)
+}
+
+fn read_bytes(column: &Column, kind: StreamKind, len: u64) -> ArrowResult<Vec<u8>> {
+    let mut bytes = vec![0; len as usize];
+    if len > 0 {
+        column.get_stream(kind, vec![])?.read_exact(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// The integers and the lengths of the columns written by ORC 0.11 are encoded by RLE v1,
+/// which is not supported.
+fn check_rle_v2(column: &Column) -> ArrowResult<()> {
+    match column.encoding().kind() {
+        EncodingKind::Direct | EncodingKind::Dictionary => Err(ArrowError::NotYetImplemented(
+            "Deserializing integers encoded by RLE v1 from ORC".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn bad_orc(path: &str, e: impl Debug) -> ErrorCode {
+    ErrorCode::BadBytes(format!("fail to read orc file {}: {:?}", path, e))
+}
@@ -36,19 +36,21 @@ use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_arrow::parquet::read::read_metadata;
 use common_arrow::read_columns_async;
 use common_datablocks::DataBlock;
-use common_datavalues::remove_nullable;
-use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_datavalues::IntoColumn;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
 use futures::AsyncRead;
 use futures::AsyncSeek;
+use opendal::io_util::CompressAlgorithm;
 use opendal::Operator;
-use similar_asserts::traits::MakeDiff;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::impls::fields_to_schema;
+use crate::processors::sources::input_formats::impls::get_field_indices;
 use crate::processors::sources::input_formats::input_context::CopyIntoPlan;
 use crate::processors::sources::input_formats::input_context::InputContext;
 use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
@@ -135,6 +137,23 @@ impl InputFormat for InputFormatParquet {
         Ok(infos)
     }
 
+    async fn infer_schema(
+        &self,
+        op: &Operator,
+        path: &str,
+        _field_delimiter: u8,
+        _record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+        _compress_alg: Option<CompressAlgorithm>,
+    ) -> Result<DataSchemaRef> {
+        let obj = op.object(path);
+        let size = obj.metadata().await?.content_length();
+        let mut reader = obj.seekable_reader(..size);
+        let file_meta = read_metadata_async(&mut reader).await?;
+        let infer_schema = read::infer_schema(&file_meta)?;
+        Ok(fields_to_schema(&infer_schema.fields))
+    }
+
     fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
         ParquetFormatPipe::execute_copy_aligned(ctx, pipeline)
     }
@@ -181,6 +200,47 @@ impl DynData for SplitMeta {
     }
 }
 
+/// The statistics of a row group read by a split of a parquet file.
+pub struct RowGroupStatistics {
+    pub num_rows: usize,
+    /// The min value, max value and null count of the columns, by the positions of the columns
+    /// in the schema to read. The columns without statistics are omitted.
+    pub columns: HashMap<usize, (DataValue, DataValue, u64)>,
+}
+
+/// Returns the statistics of the row group read by `split_info`, `None` if the split is not of
+/// a parquet file.
+pub fn row_group_statistics(split_info: &SplitInfo) -> Option<RowGroupStatistics> {
+    let meta = split_info
+        .format_info
+        .as_ref()?
+        .as_any()
+        .downcast_ref::<SplitMeta>()?;
+    let mut columns = HashMap::new();
+    for (i, field) in meta.file.fields.iter().enumerate() {
+        let stats = match read::statistics::deserialize(field, std::slice::from_ref(&meta.meta)) {
+            Ok(stats) => stats,
+            Err(_) => continue,
+        };
+        let min = stats.min_value.into_nullable_column().get(0);
+        let max = stats.max_value.into_nullable_column().get(0);
+        if min.is_null() || max.is_null() {
+            continue;
+        }
+        let null_count = stats
+            .null_count
+            .into_nullable_column()
+            .get(0)
+            .as_u64()
+            .unwrap_or(0);
+        columns.insert(i, (min, max, null_count));
+    }
+    Some(RowGroupStatistics {
+        num_rows: meta.meta.num_rows(),
+        columns,
+    })
+}
+
 pub struct RowGroupInMemory {
     pub meta: RowGroupMetaData,
     pub fields: Arc<Vec<Field>>,
@@ -374,35 +434,11 @@ impl AligningStateTrait for AligningState {
 
 fn get_fields(file_meta: &FileMetaData, schema: &DataSchemaRef) -> Result<Vec<Field>> {
     let infer_schema = read::infer_schema(file_meta)?;
-    let mut read_fields = Vec::with_capacity(schema.num_fields());
-    for f in schema.fields().iter() {
-        if let Some(m) = infer_schema
-            .fields
-            .iter()
-            .filter(|c| c.name.eq_ignore_ascii_case(f.name()))
-            .last()
-        {
-            let tf = DataField::from(m);
-            if remove_nullable(tf.data_type()) != remove_nullable(f.data_type()) {
-                let pair = (f, m);
-                let diff = pair.make_diff("expected_field", "infer_field");
-                // TODO(xuanwo): return a more accurate error code here.
-                return Err(ErrorCode::Internal(format!(
-                    "parquet schema mismatch, differ: {}",
-                    diff
-                )));
-            }
-
-            read_fields.push(m.clone());
-        } else {
-            // TODO(xuanwo): return a more accurate error code here.
-            return Err(ErrorCode::Internal(format!(
-                "schema field size mismatch, expected to find column: {}",
-                f.name()
-            )));
-        }
-    }
-    Ok(read_fields)
+    let indices = get_field_indices("parquet", &infer_schema.fields, schema)?;
+    Ok(indices
+        .into_iter()
+        .map(|i| infer_schema.fields[i].clone())
+        .collect())
 }
 
 pub fn split_column_metas_by_field(
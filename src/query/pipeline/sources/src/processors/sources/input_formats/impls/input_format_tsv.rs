@@ -22,10 +22,14 @@ use common_io::prelude::FormatSettings;
 use common_io::prelude::NestedCheckpointReader;
 use common_meta_types::StageFileFormatType;
 
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::input_format_text::positional_schema;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
 use crate::processors::sources::input_formats::input_format_text::RowBatch;
+use crate::processors::sources::input_formats::RowError;
 
 pub struct InputFormatTSV {}
 
@@ -40,18 +44,21 @@ impl InputFormatTSV {
         batch_id: usize,
         offset: usize,
         row_index: Option<usize>,
-    ) -> Result<()> {
+    ) -> std::result::Result<(), RowError> {
         let num_columns = deserializers.len();
         let mut column_index = 0;
+        let mut num_deserialized = 0;
         let mut field_start = 0;
         let mut pos = 0;
         let mut err_msg = None;
+        let mut err_column = None;
         let buf_len = buf.len();
         while pos <= buf_len {
             if pos == buf_len || buf[pos] == b'\t' {
                 let col_data = &buf[field_start..pos];
                 if col_data.is_empty() {
                     deserializers[column_index].de_default(format_settings);
+                    num_deserialized += 1;
                 } else {
                     let mut reader = NestedCheckpointReader::new(col_data);
                     reader.ignores(|c: u8| c == b' ').expect("must success");
@@ -64,8 +71,10 @@ impl InputFormatTSV {
                             col_data,
                             &e.message(),
                         ));
+                        err_column = Some(column_index);
                         break;
                     };
+                    num_deserialized += 1;
                     reader.ignore_white_spaces().expect("must success");
                     if reader.must_eof().is_err() {
                         err_msg = Some(format_column_error(
@@ -74,6 +83,7 @@ impl InputFormatTSV {
                             col_data,
                             "bad field end",
                         ));
+                        err_column = Some(column_index);
                         break;
                     }
                 }
@@ -95,6 +105,7 @@ impl InputFormatTSV {
         }
 
         if let Some(m) = err_msg {
+            rollback_row(deserializers, num_deserialized);
             let row_info = if let Some(r) = row_index {
                 format!("at row {},", r)
             } else {
@@ -109,7 +120,10 @@ impl InputFormatTSV {
                 m
             );
             verbose_string(buf, &mut msg);
-            Err(ErrorCode::BadBytes(msg))
+            Err(RowError {
+                column: err_column,
+                error: ErrorCode::BadBytes(msg),
+            })
         } else {
             Ok(())
         }
@@ -129,7 +143,30 @@ impl InputFormatTextBase for InputFormatTSV {
         b'\t'
     }
 
-    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+    fn infer_schema(
+        data: &[u8],
+        field_delimiter: u8,
+        record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+    ) -> Result<DataSchemaRef> {
+        let end = data
+            .iter()
+            .position(|b| *b == record_delimiter.end())
+            .unwrap_or(data.len());
+        let mut row = &data[..end];
+        if matches!(record_delimiter, RecordDelimiter::Crlf) && row.last() == Some(&b'\r') {
+            row = &row[..row.len() - 1];
+        }
+        if row.is_empty() {
+            return Err(ErrorCode::BadBytes(
+                "no row found to infer the schema of tsv file",
+            ));
+        }
+        let num_fields = row.iter().filter(|b| **b == field_delimiter).count() + 1;
+        Ok(positional_schema(num_fields))
+    }
+
+    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<usize> {
         tracing::debug!(
             "tsv deserializing row batch {}, id={}, start_row={:?}, offset={}",
             batch.path,
@@ -141,9 +178,10 @@ impl InputFormatTextBase for InputFormatTSV {
         let columns = &mut builder.mutable_columns;
         let mut start = 0usize;
         let start_row = batch.start_row;
+        let mut num_rows = 0;
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end]; // include \n
-            Self::read_row(
+            match Self::read_row(
                 buf,
                 columns,
                 schema,
@@ -152,10 +190,18 @@ impl InputFormatTextBase for InputFormatTSV {
                 batch.batch_id,
                 batch.offset + start,
                 start_row.map(|n| n + i),
-            )?;
+            ) {
+                Ok(()) => num_rows += 1,
+                Err(e) => {
+                    let row = start_row.unwrap_or_default() + i;
+                    builder
+                        .ctx
+                        .on_row_error(&batch.path, (batch.split_seq, row), e)?
+                }
+            }
             start = *end;
         }
-        Ok(())
+        Ok(num_rows)
     }
 
     fn align(state: &mut AligningState<Self>, buf: &[u8]) -> Result<Vec<RowBatch>> {
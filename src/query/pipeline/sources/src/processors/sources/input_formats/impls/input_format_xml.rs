@@ -28,11 +28,13 @@ use xml::reader::XmlEvent;
 use xml::EventReader;
 use xml::ParserConfig;
 
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
 use crate::processors::sources::input_formats::input_format_text::RowBatch;
 use crate::processors::sources::input_formats::InputContext;
+use crate::processors::sources::input_formats::RowError;
 
 pub struct InputFormatXML {}
 
@@ -44,8 +46,9 @@ impl InputFormatXML {
         format_settings: &FormatSettings,
         path: &str,
         row_index: usize,
-    ) -> Result<()> {
-        let mut raw_data: HashMap<String, Vec<u8>> = serde_json::from_reader(buf)?;
+    ) -> std::result::Result<(), RowError> {
+        let mut raw_data: HashMap<String, Vec<u8>> =
+            serde_json::from_reader(buf).map_err(ErrorCode::from)?;
 
         if !format_settings.ident_case_sensitive {
             raw_data = raw_data
@@ -54,7 +57,8 @@ impl InputFormatXML {
                 .collect();
         }
 
-        for (field, deserializer) in schema.fields().iter().zip(deserializers.iter_mut()) {
+        for (c, field) in schema.fields().iter().enumerate() {
+            let deserializer = &mut deserializers[c];
             let value = if format_settings.ident_case_sensitive {
                 raw_data.get(field.name())
             } else {
@@ -67,15 +71,17 @@ impl InputFormatXML {
                     deserializer.de_default(format_settings);
                 } else {
                     if let Err(e) = deserializer.de_text(&mut reader, format_settings) {
+                        rollback_row(deserializers, c);
                         let value_str = format!("{:?}", value);
                         let err_msg = format!("{}. column={} value={}", e, field.name(), value_str);
-                        return Err(xml_error(&err_msg, path, row_index));
+                        return Err(RowError::column(c, xml_error(&err_msg, path, row_index)));
                     };
                     if reader.must_eof().is_err() {
+                        rollback_row(deserializers, c + 1);
                         let value_str = format!("{:?}", value);
                         let err_msg =
                             format!("bad field end. column={} value={}", field.name(), value_str);
-                        return Err(xml_error(&err_msg, path, row_index));
+                        return Err(RowError::column(c, xml_error(&err_msg, path, row_index)));
                     }
                 }
             } else {
@@ -95,7 +101,7 @@ impl InputFormatTextBase for InputFormatXML {
         b','
     }
 
-    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<usize> {
         tracing::debug!(
             "xml deserializing row batch {}, id={}, start_row={:?}, offset={}",
             batch.path,
@@ -107,19 +113,26 @@ impl InputFormatTextBase for InputFormatXML {
 
         let mut start = 0usize;
         let start_row = batch.start_row.expect("must be success");
+        let mut num_rows = 0;
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end];
-            Self::read_row(
+            match Self::read_row(
                 buf,
                 columns,
                 &builder.ctx.schema,
                 &builder.ctx.format_settings,
                 &batch.path,
                 start_row + i,
-            )?;
+            ) {
+                Ok(()) => num_rows += 1,
+                Err(e) => {
+                    let row = (batch.split_seq, start_row + i);
+                    builder.ctx.on_row_error(&batch.path, row, e)?
+                }
+            }
             start = *end;
         }
-        Ok(())
+        Ok(num_rows)
     }
 
     fn align(state: &mut AligningState<Self>, buf: &[u8]) -> Result<Vec<RowBatch>> {
@@ -147,6 +160,8 @@ impl InputFormatTextBase for InputFormatXML {
             batch_id: state.batch_id,
             offset: 0,
             start_row: Some(state.rows),
+            split_seq: state.split_info.seq_in_file,
+            bad_rows: vec![],
         };
 
         let mut cols = HashMap::with_capacity(state.num_fields);
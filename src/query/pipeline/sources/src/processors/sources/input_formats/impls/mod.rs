@@ -12,8 +12,62 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub mod input_format_avro;
 pub mod input_format_csv;
 pub mod input_format_ndjson;
+pub mod input_format_orc;
 pub mod input_format_parquet;
 pub mod input_format_tsv;
 pub mod input_format_xml;
+
+use common_arrow::arrow::datatypes::Field;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use similar_asserts::traits::MakeDiff;
+
+/// Finds the fields of the schema in the fields inferred from a file of the columnar or binary
+/// format, by name case-insensitively, returns their positions in the inferred fields.
+pub fn get_field_indices(
+    format: &str,
+    infer_fields: &[Field],
+    schema: &DataSchemaRef,
+) -> Result<Vec<usize>> {
+    let mut indices = Vec::with_capacity(schema.num_fields());
+    for f in schema.fields().iter() {
+        if let Some((i, m)) = infer_fields
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.name.eq_ignore_ascii_case(f.name()))
+            .last()
+        {
+            let tf = DataField::from(m);
+            if remove_nullable(tf.data_type()) != remove_nullable(f.data_type()) {
+                let pair = (f, m);
+                let diff = pair.make_diff("expected_field", "infer_field");
+                // TODO(xuanwo): return a more accurate error code here.
+                return Err(ErrorCode::Internal(format!(
+                    "{} schema mismatch, differ: {}",
+                    format, diff
+                )));
+            }
+
+            indices.push(i);
+        } else {
+            // TODO(xuanwo): return a more accurate error code here.
+            return Err(ErrorCode::Internal(format!(
+                "schema field size mismatch, expected to find column: {}",
+                f.name()
+            )));
+        }
+    }
+    Ok(indices)
+}
+
+/// Converts the fields inferred from a file of the columnar or binary format to a schema.
+pub fn fields_to_schema(infer_fields: &[Field]) -> DataSchemaRef {
+    DataSchemaRefExt::create(infer_fields.iter().map(DataField::from).collect())
+}
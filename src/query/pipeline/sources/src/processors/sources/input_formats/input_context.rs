@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::mem;
@@ -28,6 +29,7 @@ use common_exception::Result;
 use common_formats::ClickhouseFormatType;
 use common_formats::FileFormatTypeExt;
 use common_io::prelude::FormatSettings;
+use common_meta_types::OnErrorMode;
 use common_meta_types::StageFileCompression;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::UserStageInfo;
@@ -36,8 +38,10 @@ use opendal::io_util::CompressAlgorithm;
 use opendal::Operator;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::impls::input_format_avro::InputFormatAvro;
 use crate::processors::sources::input_formats::impls::input_format_csv::InputFormatCSV;
 use crate::processors::sources::input_formats::impls::input_format_ndjson::InputFormatNDJson;
+use crate::processors::sources::input_formats::impls::input_format_orc::InputFormatOrc;
 use crate::processors::sources::input_formats::impls::input_format_parquet::InputFormatParquet;
 use crate::processors::sources::input_formats::impls::input_format_tsv::InputFormatTSV;
 use crate::processors::sources::input_formats::impls::input_format_xml::InputFormatXML;
@@ -104,6 +108,58 @@ impl InputSource {
     }
 }
 
+/// The error of a row failed to deserialize.
+#[derive(Debug, Clone)]
+pub struct RowError {
+    /// The column failed to deserialize, `None` if the row fails as a whole, e.g. bad JSON.
+    pub column: Option<usize>,
+    pub error: ErrorCode,
+}
+
+impl RowError {
+    pub fn column(column: usize, error: ErrorCode) -> Self {
+        RowError {
+            column: Some(column),
+            error,
+        }
+    }
+}
+
+impl From<ErrorCode> for RowError {
+    fn from(error: ErrorCode) -> Self {
+        RowError {
+            column: None,
+            error,
+        }
+    }
+}
+
+/// The position of a row in a file: the sequence of the split of the file it is in, and the
+/// row number (0-based) in the split, the header rows of the file included.
+pub type RowPosition = (usize, usize);
+
+/// The result of loading a file, reported by COPY with ON_ERROR or VALIDATION_MODE.
+#[derive(Debug, Clone, Default)]
+pub struct FileStatus {
+    pub rows_loaded: usize,
+    pub errors_seen: usize,
+    /// The error of the first failed row, and the row number (1-based) in the file.
+    pub first_error: Option<(RowError, usize)>,
+    /// The errors of all the failed rows, kept only if `InputContext::keep_errors` is set.
+    pub errors: Vec<(RowError, usize)>,
+}
+
+/// The progress of loading a file, whose splits are aligned and deserialized concurrently.
+#[derive(Default)]
+struct FileProgress {
+    rows_loaded: usize,
+    errors_seen: usize,
+    first_error: Option<(RowError, RowPosition)>,
+    errors: Vec<(RowError, RowPosition)>,
+    // the number of rows of each split aligned, by the sequence of the split
+    split_rows: HashMap<usize, usize>,
+}
+
 pub struct InputContext {
     pub plan: InputPlan,
     pub schema: DataSchemaRef,
@@ -124,6 +180,11 @@ pub struct InputContext {
     pub block_compact_thresholds: BlockCompactThresholds,
 
     pub scan_progress: Arc<Progress>,
+
+    pub on_error_mode: OnErrorMode,
+    // keep the errors of all the failed rows, to validate the files by COPY
+    pub keep_errors: bool,
+    file_progress: Mutex<HashMap<String, FileProgress>>,
 }
 
 impl Debug for InputContext {
@@ -137,6 +198,7 @@ impl Debug for InputContext {
             .field("block_compact_thresholds", &self.block_compact_thresholds)
             .field("read_batch_size", &self.read_batch_size)
             .field("num_splits", &self.splits.len())
+            .field("on_error_mode", &self.on_error_mode)
             .finish()
     }
 }
@@ -150,6 +212,8 @@ impl InputContext {
                 Ok(Arc::new(InputFormatText::<InputFormatNDJson>::create()))
             }
             StageFileFormatType::Parquet => Ok(Arc::new(InputFormatParquet {})),
+            StageFileFormatType::Avro => Ok(Arc::new(InputFormatAvro {})),
+            StageFileFormatType::Orc => Ok(Arc::new(InputFormatOrc {})),
             StageFileFormatType::Xml => Ok(Arc::new(InputFormatText::<InputFormatXML>::create())),
             format => Err(ErrorCode::Internal(format!(
                 "Unsupported file format: {:?}",
@@ -170,6 +234,7 @@ impl InputContext {
         if files.is_empty() {
             return Err(ErrorCode::BadArguments("no file to copy"));
         }
+        let on_error_mode = stage_info.copy_options.on_error.clone();
         let plan = Box::new(CopyIntoPlan { stage_info, files });
         let read_batch_size = settings.get_input_read_buffer_size()? as usize;
         let file_format_options = &plan.stage_info.file_format_options;
@@ -215,9 +280,54 @@ impl InputContext {
             source: InputSource::Operator(operator),
             plan: InputPlan::CopyInto(plan),
             block_compact_thresholds,
+            on_error_mode,
+            keep_errors: false,
+            file_progress: Default::default(),
         })
     }
 
+    /// Infers the schema of the file `path` in the stage, by the file format of the stage.
+    pub async fn infer_schema(
+        operator: &Operator,
+        settings: &Arc<Settings>,
+        stage_info: &UserStageInfo,
+        path: &str,
+    ) -> Result<DataSchemaRef> {
+        let file_format_options = &stage_info.file_format_options;
+        let format_typ = file_format_options.format.clone();
+        let file_format_options =
+            StageFileFormatType::get_ext_from_stage(file_format_options.clone(), settings)?;
+        let file_format_options = format_typ.final_file_format_options(&file_format_options)?;
+        let format = Self::get_input_format(&format_typ)?;
+
+        let record_delimiter = {
+            if file_format_options.stage.record_delimiter.is_empty() {
+                format.default_record_delimiter()
+            } else {
+                RecordDelimiter::try_from(file_format_options.stage.record_delimiter.as_str())?
+            }
+        };
+        let field_delimiter = {
+            if file_format_options.stage.field_delimiter.is_empty() {
+                format.default_field_delimiter()
+            } else {
+                file_format_options.stage.field_delimiter.as_bytes()[0]
+            }
+        };
+        let compress_alg =
+            Self::get_compression_alg_copy(stage_info.file_format_options.compression, path)?;
+        format
+            .infer_schema(
+                operator,
+                path,
+                field_delimiter,
+                &record_delimiter,
+                file_format_options.stage.skip_header,
+                compress_alg,
+            )
+            .await
+    }
+
     pub async fn try_create_from_insert(
         format_name: &str,
         stream_receiver: Receiver<Result<StreamingReadBatch>>,
@@ -281,9 +391,136 @@ impl InputContext {
             plan: InputPlan::StreamingLoad(plan),
             splits: vec![],
             block_compact_thresholds,
+            on_error_mode: OnErrorMode::None,
+            keep_errors: false,
+            file_progress: Default::default(),
         })
     }
 
+    /// Handles the error of the row at `row` of the file `path`: returns the error to abort the
+    /// statement, or records it if the row is to be skipped by the ON_ERROR mode.
+    pub fn on_row_error(&self, path: &str, row: RowPosition, e: RowError) -> Result<()> {
+        if matches!(
+            self.on_error_mode,
+            OnErrorMode::None | OnErrorMode::AbortStatement
+        ) {
+            return Err(e.error);
+        }
+
+        let mut file_progress = self.file_progress.lock().expect("must success");
+        let progress = file_progress.entry(path.to_string()).or_default();
+        progress.errors_seen += 1;
+        if self.keep_errors {
+            progress.errors.push((e.clone(), row));
+        }
+        // The rows of a file may be deserialized out of order if the file is split.
+        match &progress.first_error {
+            Some((_, first)) if *first <= row => {}
+            _ => progress.first_error = Some((e, row)),
+        }
+        Ok(())
+    }
+
+    /// Records that the split `split_seq` of the file `path` is aligned to `rows` rows.
+    pub fn on_split_aligned(&self, path: &str, split_seq: usize, rows: usize) {
+        let mut file_progress = self.file_progress.lock().expect("must success");
+        file_progress
+            .entry(path.to_string())
+            .or_default()
+            .split_rows
+            .insert(split_seq, rows);
+    }
+
+    /// Whether the whole file is skipped once a row of it fails, i.e. ON_ERROR is SKIP_FILE or
+    /// SKIP_FILE_<num>.
+    pub fn skips_file_on_error(&self) -> bool {
+        matches!(
+            self.on_error_mode,
+            OnErrorMode::SkipFile | OnErrorMode::SkipFileNum(_)
+        )
+    }
+
+    /// Whether the file `path` is skipped by the errors seen so far.
+    pub fn is_file_skipped(&self, path: &str) -> bool {
+        let file_progress = self.file_progress.lock().expect("must success");
+        file_progress
+            .get(path)
+            .map(|progress| self.is_file_skipped_by(progress))
+            .unwrap_or(false)
+    }
+
+    fn is_file_skipped_by(&self, progress: &FileProgress) -> bool {
+        let max_errors = match self.on_error_mode {
+            OnErrorMode::SkipFile => 1,
+            OnErrorMode::SkipFileNum(n) => std::cmp::max(n, 1) as usize,
+            _ => return false,
+        };
+        progress.errors_seen >= max_errors
+    }
+
+    pub fn add_rows_loaded(&self, path: &str, rows: usize) {
+        if self.on_error_mode == OnErrorMode::None {
+            return;
+        }
+        let mut file_progress = self.file_progress.lock().expect("must success");
+        file_progress
+            .entry(path.to_string())
+            .or_default()
+            .rows_loaded += rows;
+    }
+
+    /// Returns the status of each file to copy, in the order of the files.
+    pub fn get_file_status(&self) -> Vec<(String, FileStatus)> {
+        let files = match &self.plan {
+            InputPlan::CopyInto(p) => p.files.clone(),
+            InputPlan::StreamingLoad(_) => vec![],
+        };
+        let file_progress = self.file_progress.lock().expect("must success");
+        files
+            .into_iter()
+            .map(|path| {
+                let status = match file_progress.get(&path) {
+                    Some(progress) => self.file_status_of(progress),
+                    None => FileStatus::default(),
+                };
+                (path, status)
+            })
+            .collect()
+    }
+
+    fn file_status_of(&self, progress: &FileProgress) -> FileStatus {
+        // The rows of a split are numbered after the rows of the splits before it.
+        let mut split_seqs = progress.split_rows.keys().cloned().collect::<Vec<_>>();
+        split_seqs.sort_unstable();
+        let mut split_starts = HashMap::with_capacity(split_seqs.len());
+        let mut rows = 0;
+        for seq in split_seqs {
+            split_starts.insert(seq, rows);
+            rows += progress.split_rows[&seq];
+        }
+        let line = |(split_seq, row): RowPosition| {
+            split_starts.get(&split_seq).cloned().unwrap_or_default() + row + 1
+        };
+
+        FileStatus {
+            rows_loaded: if self.is_file_skipped_by(progress) {
+                0
+            } else {
+                progress.rows_loaded
+            },
+            errors_seen: progress.errors_seen,
+            first_error: progress
+                .first_error
+                .as_ref()
+                .map(|(e, row)| (e.clone(), line(*row))),
+            errors: progress
+                .errors
+                .iter()
+                .map(|(e, row)| (e.clone(), line(*row)))
+                .collect(),
+        }
+    }
+
     pub fn num_prefetch_splits(&self) -> Result<usize> {
         Ok(self.settings.get_max_threads()? as usize)
     }
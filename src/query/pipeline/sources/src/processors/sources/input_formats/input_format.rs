@@ -18,6 +18,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
+use opendal::io_util::CompressAlgorithm;
 use opendal::Operator;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
@@ -39,6 +40,18 @@ pub trait InputFormat: Send + Sync {
         schema: &DataSchemaRef,
     ) -> Result<Vec<Arc<SplitInfo>>>;
 
+    /// Infers the schema of the file `path`, from its metadata for the columnar and binary
+    /// formats, or by sampling its first rows for the text formats.
+    async fn infer_schema(
+        &self,
+        op: &Operator,
+        path: &str,
+        field_delimiter: u8,
+        record_delimiter: &RecordDelimiter,
+        skip_header: u64,
+        compress_alg: Option<CompressAlgorithm>,
+    ) -> Result<DataSchemaRef>;
+
     fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()>;
 
     fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()>;
@@ -17,14 +17,23 @@ use std::mem;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::BooleanType;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::Float64Type;
+use common_datavalues::Int64Type;
+use common_datavalues::StringType;
 use common_datavalues::TypeDeserializer;
 use common_datavalues::TypeDeserializerImpl;
+use common_datavalues::VariantType;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::StageFileFormatType;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
+use opendal::io_util::CompressAlgorithm;
 use opendal::io_util::DecompressDecoder;
 use opendal::io_util::DecompressState;
 use opendal::Operator;
@@ -57,10 +66,25 @@ pub trait InputFormatTextBase: Sized + Send + Sync + 'static {
 
     fn default_field_delimiter() -> u8;
 
-    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()>;
+    /// Returns the number of rows deserialized, the failed rows skipped by ON_ERROR excluded.
+    fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<usize>;
 
     fn align(state: &mut AligningState<Self>, buf: &[u8]) -> Result<Vec<RowBatch>>;
 
+    /// Infers the schema from the complete rows at the head of a file. The first
+    /// `skip_header` rows are the header, if any.
+    fn infer_schema(
+        _data: &[u8],
+        _field_delimiter: u8,
+        _record_delimiter: &RecordDelimiter,
+        _skip_header: u64,
+    ) -> Result<DataSchemaRef> {
+        Err(ErrorCode::Unimplemented(format!(
+            "schema inference of {:?} files is not supported yet",
+            Self::format_type()
+        )))
+    }
+
     fn align_flush(state: &mut AligningState<Self>) -> Result<Vec<RowBatch>> {
         if state.tail_of_last_batch.is_empty() {
             Ok(vec![])
@@ -76,6 +100,8 @@ pub trait InputFormatTextBase: Sized + Send + Sync + 'static {
                 batch_id: state.batch_id,
                 offset: state.offset,
                 start_row: Some(state.rows),
+                split_seq: state.split_info.seq_in_file,
+                bad_rows: vec![],
             };
             tracing::debug!(
                 "align flush batch {}, bytes = {}, start_row = {}",
@@ -88,6 +114,56 @@ pub trait InputFormatTextBase: Sized + Send + Sync + 'static {
     }
 }
 
+// The bytes read from the head of a file to infer its schema.
+const INFER_SCHEMA_READ_SIZE: u64 = 1024 * 1024;
+// The rows sampled from the head of a file to infer the types of its columns.
+pub const INFER_SCHEMA_MAX_ROWS: usize = 1000;
+
+/// The schema of a text file with `num_fields` fields in a row. The text formats carry no
+/// column names, so the columns are named by position, like `$1`.
+pub fn positional_schema(num_fields: usize) -> DataSchemaRef {
+    DataSchemaRefExt::create(
+        (1..=num_fields)
+            .map(|i| DataField::new_nullable(&format!("${i}"), StringType::new_impl()))
+            .collect(),
+    )
+}
+
+/// The type of a column inferred from the values sampled from a text file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InferredType {
+    // only nulls or empty values are sampled
+    Null,
+    Boolean,
+    Int64,
+    Float64,
+    String,
+    Variant,
+}
+
+impl InferredType {
+    /// The narrowest type which holds the values of both types, if any.
+    pub fn merge(self, other: InferredType) -> Option<InferredType> {
+        match (self, other) {
+            (InferredType::Null, t) | (t, InferredType::Null) => Some(t),
+            (a, b) if a == b => Some(a),
+            (InferredType::Int64, InferredType::Float64)
+            | (InferredType::Float64, InferredType::Int64) => Some(InferredType::Float64),
+            _ => None,
+        }
+    }
+
+    pub fn to_data_type(self) -> DataTypeImpl {
+        match self {
+            InferredType::Null | InferredType::String => StringType::new_impl(),
+            InferredType::Boolean => BooleanType::new_impl(),
+            InferredType::Int64 => Int64Type::new_impl(),
+            InferredType::Float64 => Float64Type::new_impl(),
+            InferredType::Variant => VariantType::new_impl(),
+        }
+    }
+}
+
 pub struct InputFormatText<T: InputFormatTextBase> {
     phantom: PhantomData<T>,
 }
@@ -192,6 +268,35 @@ impl<T: InputFormatTextBase> InputFormat for InputFormatText<T> {
         }
         Ok(infos)
     }
+
+    async fn infer_schema(
+        &self,
+        op: &Operator,
+        path: &str,
+        field_delimiter: u8,
+        record_delimiter: &RecordDelimiter,
+        skip_header: u64,
+        compress_alg: Option<CompressAlgorithm>,
+    ) -> Result<DataSchemaRef> {
+        let obj = op.object(path);
+        let size = obj.metadata().await?.content_length();
+        let data = obj
+            .range_read(..std::cmp::min(size, INFER_SCHEMA_READ_SIZE))
+            .await?;
+        let mut data = match compress_alg {
+            Some(alg) => decompress(&mut DecompressDecoder::new(alg), &data)?,
+            None => data,
+        };
+        // the partial row at the end of the head is dropped
+        if size > INFER_SCHEMA_READ_SIZE {
+            let end = data
+                .iter()
+                .rposition(|b| *b == record_delimiter.end())
+                .map_or(0, |pos| pos + 1);
+            data.truncate(end);
+        }
+        T::infer_schema(&data, field_delimiter, record_delimiter, skip_header)
+    }
 }
 
 #[derive(Default)]
@@ -205,6 +310,10 @@ pub struct RowBatch {
     pub batch_id: usize,
     pub offset: usize,
     pub start_row: Option<usize>,
+    // the sequence of the split in the file, the rows of which `start_row` counts
+    pub split_seq: usize,
+    // rows failed to align, by the index in the batch
+    pub bad_rows: Vec<(usize, ErrorCode)>,
 }
 
 impl RowBatchTrait for RowBatch {
@@ -219,11 +328,12 @@ impl RowBatchTrait for RowBatch {
 
 pub struct AligningState<T> {
     ctx: Arc<InputContext>,
-    split_info: Arc<SplitInfo>,
+    pub split_info: Arc<SplitInfo>,
     pub path: String,
     pub record_delimiter_end: u8,
     pub field_delimiter: u8,
     pub batch_id: usize,
+    // the rows aligned, the header rows of the file included
     pub rows: usize,
     pub offset: usize,
     pub rows_to_skip: usize,
@@ -245,6 +355,11 @@ impl<T: InputFormatTextBase> AligningState<T> {
             for b in buf.iter() {
                 if *b == record_delimiter_end {
                     self.rows_to_skip -= 1;
+                    // the row skipped by the splits after the first one is a part of the row
+                    // at the end of the split before
+                    if self.split_info.seq_in_file == 0 {
+                        self.rows += 1;
+                    }
                     if self.rows_to_skip == 0 {
                         break;
                     }
@@ -280,6 +395,7 @@ impl<T: InputFormatTextBase> AligningState<T> {
             let size = output.data.len();
             output.path = self.path.to_string();
             output.start_row = Some(self.rows);
+            output.split_seq = self.split_info.seq_in_file;
             output.offset = self.offset;
             output.batch_id = self.batch_id;
             self.offset += size;
@@ -357,7 +473,10 @@ impl<T: InputFormatTextBase> AligningStateTrait for AligningState<T> {
                     tracing::warn!("decompressor end with state {:?}", state)
                 }
             }
-            T::align_flush(self)?
+            let row_batches = T::align_flush(self)?;
+            self.ctx
+                .on_split_aligned(&self.path, self.split_info.seq_in_file, self.rows);
+            row_batches
         };
         Ok(row_batches)
     }
@@ -376,6 +495,8 @@ pub struct BlockBuilder<T> {
     pub ctx: Arc<InputContext>,
     pub mutable_columns: Vec<TypeDeserializerImpl>,
     pub num_rows: usize,
+    // the file of the rows, if ON_ERROR skips files
+    file_path: String,
     phantom: PhantomData<T>,
 }
 
@@ -397,6 +518,34 @@ impl<T: InputFormatTextBase> BlockBuilder<T> {
     fn memory_size(&self) -> usize {
         self.mutable_columns.iter().map(|x| x.memory_size()).sum()
     }
+
+    fn is_full(&self) -> bool {
+        let mem = self.memory_size();
+        tracing::debug!(
+            "block builder added new batch: row {} size {}",
+            self.num_rows,
+            mem
+        );
+        self.num_rows >= self.ctx.block_compact_thresholds.min_rows_per_block
+            || mem > self.ctx.block_compact_thresholds.max_bytes_per_block
+    }
+
+    /// Removes the rows of a skipped file.
+    fn clear(&mut self) {
+        self.mutable_columns = self
+            .ctx
+            .schema
+            .create_deserializers(self.ctx.block_compact_thresholds.min_rows_per_block);
+        self.num_rows = 0;
+    }
+}
+
+/// Removes the values of a failed row from the first `num_columns` deserializers, which the row
+/// has been deserialized into before failing.
+pub fn rollback_row(deserializers: &mut [TypeDeserializerImpl], num_columns: usize) {
+    for deserializer in &mut deserializers[..num_columns] {
+        deserializer.pop_data_value().expect("must success");
+    }
 }
 
 impl<T: InputFormatTextBase> BlockBuilderTrait for BlockBuilder<T> {
@@ -410,27 +559,46 @@ impl<T: InputFormatTextBase> BlockBuilderTrait for BlockBuilder<T> {
             ctx,
             mutable_columns: columns,
             num_rows: 0,
+            file_path: String::new(),
             phantom: Default::default(),
         }
     }
 
     fn deserialize(&mut self, batch: Option<RowBatch>) -> Result<Vec<DataBlock>> {
         if let Some(b) = batch {
-            self.num_rows += b.row_ends.len();
-            T::deserialize(self, b)?;
-            let mem = self.memory_size();
-            tracing::debug!(
-                "block builder added new batch: row {} size {}",
-                self.num_rows,
-                mem
-            );
-            if self.num_rows >= self.ctx.block_compact_thresholds.min_rows_per_block
-                || mem > self.ctx.block_compact_thresholds.max_bytes_per_block
-            {
-                self.flush()
-            } else {
-                Ok(vec![])
+            let path = b.path.clone();
+            if !self.ctx.skips_file_on_error() {
+                let num_rows = T::deserialize(self, b)?;
+                self.num_rows += num_rows;
+                self.ctx.add_rows_loaded(&path, num_rows);
+                return if self.is_full() {
+                    self.flush()
+                } else {
+                    Ok(vec![])
+                };
+            }
+
+            // The rows of a file are not built into the blocks of other files, so that the rows
+            // left of a skipped file can be dropped. The blocks flushed before the file is skipped
+            // are loaded, then removed by COPY, which loads such files one by one.
+            let mut blocks = vec![];
+            if path != self.file_path {
+                if self.num_rows > 0 {
+                    blocks = self.flush()?;
+                }
+                self.file_path = path.clone();
+            }
+            if !self.ctx.is_file_skipped(&path) {
+                let num_rows = T::deserialize(self, b)?;
+                self.num_rows += num_rows;
+                self.ctx.add_rows_loaded(&path, num_rows);
+                if self.ctx.is_file_skipped(&path) {
+                    self.clear();
+                } else if self.is_full() {
+                    blocks.extend(self.flush()?);
+                }
             }
+            Ok(blocks)
         } else {
             self.flush()
         }
@@ -24,7 +24,11 @@ mod source_aligner;
 mod source_deserializer;
 mod transform_deserializer;
 
+pub use impls::input_format_parquet::row_group_statistics;
+pub use impls::input_format_parquet::RowGroupStatistics;
+pub use input_context::FileStatus;
 pub use input_context::InputContext;
+pub use input_context::RowError;
 pub use input_format::InputFormat;
 pub use input_pipeline::StreamingReadBatch;
 pub use input_split::split_by_size;
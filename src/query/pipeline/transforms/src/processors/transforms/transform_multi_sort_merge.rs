@@ -78,6 +78,54 @@ pub fn try_add_multi_sort_merge(
     }
 }
 
+/// Create a [`RowConverter`] for the sort columns, returns the converter and the indices of
+/// the sort columns in `schema`.
+pub fn create_row_converter(
+    schema: &DataSchemaRef,
+    sort_columns_descriptions: &[SortColumnDescription],
+) -> Result<(RowConverter, Vec<usize>)> {
+    let mut sort_field_indices = Vec::with_capacity(sort_columns_descriptions.len());
+    let sort_fields = sort_columns_descriptions
+        .iter()
+        .map(|d| {
+            let data_type = match schema
+                .field_with_name(&d.column_name)?
+                .to_arrow()
+                .data_type()
+            {
+                // The actual data type of `Data` and `Timestmap` will be `Int32` and `Int64`.
+                DataType::Date32 | DataType::Time32(_) => DataType::Int32,
+                DataType::Date64 | DataType::Time64(_) | DataType::Timestamp(_, _) => {
+                    DataType::Int64
+                }
+                date_type => date_type.clone(),
+            };
+            sort_field_indices.push(schema.index_of(&d.column_name)?);
+            Ok(SortField::new_with_options(data_type, SortOptions {
+                descending: !d.asc,
+                nulls_first: d.nulls_first,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((RowConverter::new(sort_fields), sort_field_indices))
+}
+
+/// Convert the sort columns of the block to comparable rows.
+pub fn convert_rows(
+    row_converter: &mut RowConverter,
+    sort_field_indices: &[usize],
+    block: &DataBlock,
+) -> Result<Rows> {
+    let columns = sort_field_indices
+        .iter()
+        .map(|i| {
+            let col = block.column(*i);
+            col.as_arrow_array(col.data_type())
+        })
+        .collect::<Vec<_>>();
+    Ok(row_converter.convert_columns(&columns)?)
+}
+
 /// A cursor point to a certain row in a data block.
 struct Cursor {
     pub input_index: usize,
@@ -187,30 +235,8 @@ impl MultiSortMergeProcessor {
         sort_columns_descriptions: Vec<SortColumnDescription>,
     ) -> Result<Self> {
         let input_size = inputs.len();
-        let mut sort_field_indices = Vec::with_capacity(sort_columns_descriptions.len());
-        let sort_fields = sort_columns_descriptions
-            .iter()
-            .map(|d| {
-                let data_type = match output_schema
-                    .field_with_name(&d.column_name)?
-                    .to_arrow()
-                    .data_type()
-                {
-                    // The actual data type of `Data` and `Timestmap` will be `Int32` and `Int64`.
-                    DataType::Date32 | DataType::Time32(_) => DataType::Int32,
-                    DataType::Date64 | DataType::Time64(_) | DataType::Timestamp(_, _) => {
-                        DataType::Int64
-                    }
-                    date_type => date_type.clone(),
-                };
-                sort_field_indices.push(output_schema.index_of(&d.column_name)?);
-                Ok(SortField::new_with_options(data_type, SortOptions {
-                    descending: !d.asc,
-                    nulls_first: d.nulls_first,
-                }))
-            })
-            .collect::<Result<Vec<_>>>()?;
-        let row_converter = RowConverter::new(sort_fields);
+        let (row_converter, sort_field_indices) =
+            create_row_converter(&output_schema, &sort_columns_descriptions)?;
         Ok(Self {
             inputs,
             output,
@@ -512,15 +538,8 @@ impl Processor for MultiSortMergeProcessor {
         match std::mem::replace(&mut self.state, ProcessorState::Consume) {
             ProcessorState::Preserve(blocks) => {
                 for (input_index, block) in blocks.into_iter() {
-                    let columns = self
-                        .sort_field_indices
-                        .iter()
-                        .map(|i| {
-                            let col = block.column(*i);
-                            col.as_arrow_array(col.data_type())
-                        })
-                        .collect::<Vec<_>>();
-                    let rows = self.row_converter.convert_columns(&columns)?;
+                    let rows =
+                        convert_rows(&mut self.row_converter, &self.sort_field_indices, &block)?;
                     if !block.is_empty() {
                         let cursor = Cursor::try_create(input_index, rows);
                         self.heap.push(Reverse(cursor));
@@ -0,0 +1,178 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Semaphore;
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_catalog::table::CompactTarget;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tracing::info;
+use tracing::warn;
+
+use crate::background_task::BackgroundTask;
+use crate::catalogs::Catalog;
+use crate::databases::Database;
+use crate::interpreters::InterpreterClusteringHistory;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::pipelines::Pipeline;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_AUTO_COMPACTION;
+use crate::storages::Table;
+
+/// Compacts and reclusters the Fuse tables periodically.
+///
+/// A table is compacted if the summary of its current snapshot shows too many small blocks or
+/// segments, and reclustered if the average depth of its blocks exceeds the
+/// `row_avg_depth_threshold`, the same way as `ALTER TABLE ... RECLUSTER`.
+pub struct AutoCompactor {
+    max_concurrency: usize,
+    task: BackgroundTask,
+}
+
+impl AutoCompactor {
+    pub fn create(config: &Config) -> AutoCompactor {
+        AutoCompactor {
+            max_concurrency: std::cmp::max(config.query.auto_compaction_max_concurrency, 1)
+                as usize,
+            task: BackgroundTask::create(
+                config,
+                "auto compaction",
+                Duration::from_secs(config.query.auto_compaction_interval),
+            ),
+        }
+    }
+
+    async fn compact_tables(max_concurrency: usize) -> Result<()> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::Dummy)
+            .await?;
+        let ctx = session.create_query_context().await?;
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+
+        let semaphore = Arc::new(Semaphore::new(max_concurrency));
+        let mut handlers = vec![];
+        for database in catalog.list_databases(&tenant).await? {
+            for table in catalog.list_tables(&tenant, database.name()).await? {
+                if table.engine() != "FUSE" {
+                    continue;
+                }
+
+                let permit = semaphore.clone().acquire_owned().await.map_err(|e| {
+                    ErrorCode::TokioError(format!("Cannot acquire auto compaction permit, {}", e))
+                })?;
+                // Each table has its own context, which keeps the partitions and the executor.
+                let ctx = session.create_query_context().await?;
+                let db_name = database.name().to_string();
+                handlers.push(tokio::spawn(async move {
+                    let name = table.name().to_string();
+                    // Errors are ignored, the table will be picked up by the next round.
+                    if let Err(cause) = Self::compact_table(&ctx, &db_name, table).await {
+                        warn!(
+                            "Auto compaction of table {}.{} failure: {:?}",
+                            db_name, name, cause
+                        );
+                    }
+                    drop(permit);
+                }));
+            }
+        }
+
+        for handler in handlers {
+            if let Err(cause) = handler.await {
+                warn!("Auto compaction task failure: {:?}", cause);
+            }
+        }
+        Ok(())
+    }
+
+    async fn compact_table(
+        ctx: &Arc<QueryContext>,
+        database: &str,
+        table: Arc<dyn Table>,
+    ) -> Result<()> {
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+        if fuse_table.transient() || !fuse_table.get_option(FUSE_OPT_KEY_AUTO_COMPACTION, true) {
+            return Ok(());
+        }
+
+        if let Some(target) = fuse_table.auto_compact_target().await? {
+            let (target_name, execute) = match target {
+                CompactTarget::Blocks => ("blocks", true),
+                CompactTarget::Segments => ("segments", false),
+            };
+            let mut pipeline = Pipeline::create();
+            if let Some(mutator) = table
+                .compact(ctx.clone(), target, None, &mut pipeline)
+                .await?
+            {
+                if execute {
+                    Self::execute_pipeline(ctx, pipeline)?;
+                }
+                mutator.try_commit(table.clone()).await?;
+                info!(
+                    "Auto compacted the {} of table {}.{}",
+                    target_name,
+                    database,
+                    table.name()
+                );
+            }
+            // Recluster the compacted table by the next round.
+            return Ok(());
+        }
+
+        if !table.cluster_keys().is_empty() {
+            let start = SystemTime::now();
+            let mut pipeline = Pipeline::create();
+            if let Some(mutator) = table.recluster(ctx.clone(), &mut pipeline, None).await? {
+                Self::execute_pipeline(ctx, pipeline)?;
+                mutator.try_commit(table.clone()).await?;
+                InterpreterClusteringHistory::write_log(ctx, start, database, table.name())?;
+                info!("Auto reclustered table {}.{}", database, table.name());
+            }
+        }
+        Ok(())
+    }
+
+    fn execute_pipeline(ctx: &Arc<QueryContext>, mut pipeline: Pipeline) -> Result<()> {
+        let settings = ctx.get_settings();
+        pipeline.set_max_threads(settings.get_max_threads()? as usize);
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
+        let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+
+        ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+        executor.execute()
+    }
+
+    pub fn start(&mut self) {
+        let max_concurrency = self.max_concurrency;
+        self.task
+            .start(move || Self::compact_tables(max_concurrency));
+    }
+
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.task.shutdown().await
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Add;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_base::base::escape_for_key;
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Notify;
+use common_base::base::tokio::task::JoinHandle;
+use common_base::base::tokio::time::sleep as tokio_async_sleep;
+use common_base::base::GlobalUniqName;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_store::MetaStore;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::Operation;
+use common_meta_types::UpsertKVReq;
+use futures::future::select;
+use futures::future::Either;
+use futures::Future;
+use tracing::warn;
+
+use crate::clusters::ClusterDiscovery;
+
+pub static BACKGROUND_TASK_LEASE_KEY_PREFIX: &str = "__fd_background_task_leases";
+
+/// Runs a task periodically in the background of the query node.
+///
+/// The nodes of a tenant share the tables, so each round of the task runs on one node only:
+/// the node holding the lease of the task in the meta service. The holder renews the lease at
+/// every round, and another node takes it over once it expires after two intervals, e.g. if the
+/// holder is shut down.
+pub struct BackgroundTask {
+    name: &'static str,
+    interval: Duration,
+    config: Config,
+    shutdown: Arc<AtomicBool>,
+    shutdown_notify: Arc<Notify>,
+    shutdown_handler: Option<JoinHandle<()>>,
+}
+
+impl BackgroundTask {
+    pub fn create(config: &Config, name: &'static str, interval: Duration) -> BackgroundTask {
+        BackgroundTask {
+            name,
+            interval,
+            config: config.clone(),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+            shutdown_handler: None,
+        }
+    }
+
+    /// Starts to run `round` every interval, does nothing if the interval is zero.
+    pub fn start<F, Fut>(&mut self, round: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        if self.interval.is_zero() {
+            return;
+        }
+        self.shutdown_handler = Some(tokio::spawn(self.task_loop(round)));
+    }
+
+    fn task_loop<F, Fut>(&self, round: F) -> impl Future<Output = ()> + 'static
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let name = self.name;
+        let interval = self.interval;
+        let config = self.config.clone();
+        let shutdown = self.shutdown.clone();
+        let shutdown_notify = self.shutdown_notify.clone();
+
+        async move {
+            let mut shutdown_notified = Box::pin(shutdown_notify.notified());
+            let mut lease = None;
+
+            while !shutdown.load(Ordering::Relaxed) {
+                let sleep = tokio_async_sleep(interval);
+
+                match select(shutdown_notified, Box::pin(sleep)).await {
+                    Either::Left((_, _)) => {
+                        break;
+                    }
+                    Either::Right((_, new_shutdown_notified)) => {
+                        shutdown_notified = new_shutdown_notified;
+
+                        if lease.is_none() {
+                            match Lease::create(&config, name, interval * 2).await {
+                                Ok(v) => lease = Some(v),
+                                Err(cause) => {
+                                    warn!("Create the lease of {} failure: {:?}", name, cause);
+                                    continue;
+                                }
+                            }
+                        }
+                        match lease.as_ref().unwrap().try_acquire().await {
+                            Ok(true) => {
+                                if let Err(cause) = round().await {
+                                    warn!("{} failure: {:?}", name, cause);
+                                }
+                            }
+                            // Another node runs this round.
+                            Ok(false) => {}
+                            Err(cause) => {
+                                warn!("Acquire the lease of {} failure: {:?}", name, cause)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub async fn shutdown(&mut self) -> Result<()> {
+        if let Some(shutdown_handler) = self.shutdown_handler.take() {
+            self.shutdown.store(true, Ordering::Relaxed);
+            self.shutdown_notify.notify_waiters();
+            if let Err(shutdown_failure) = shutdown_handler.await {
+                return Err(ErrorCode::TokioError(format!(
+                    "Cannot shutdown {}, cause {:?}",
+                    self.name, shutdown_failure
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The lease of a background task of a tenant, stored in the meta service.
+struct Lease {
+    metastore: MetaStore,
+    key: String,
+    // the unique id of this node as a holder of the lease
+    holder: String,
+    lift_time: Duration,
+}
+
+impl Lease {
+    async fn create(config: &Config, name: &str, lift_time: Duration) -> Result<Lease> {
+        let metastore = ClusterDiscovery::create_meta_client(config).await?;
+        Ok(Lease {
+            metastore,
+            key: format!(
+                "{}/{}/{}",
+                BACKGROUND_TASK_LEASE_KEY_PREFIX,
+                escape_for_key(&config.query.tenant_id)?,
+                escape_for_key(name)?
+            ),
+            holder: GlobalUniqName::unique(),
+            lift_time,
+        })
+    }
+
+    /// Returns true if the lease is acquired, or renewed, by this node.
+    async fn try_acquire(&self) -> Result<bool> {
+        let seq = match self.metastore.get_kv(&self.key).await? {
+            // No one holds the lease, or it expired.
+            None => MatchSeq::Exact(0),
+            Some(seq_v) if seq_v.data == self.holder.as_bytes() => MatchSeq::Exact(seq_v.seq),
+            Some(_) => return Ok(false),
+        };
+
+        let expire_at = SystemTime::now()
+            .add(self.lift_time)
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+        let meta = Some(KVMeta {
+            expire_at: Some(expire_at.as_secs()),
+        });
+        let value = Operation::Update(self.holder.as_bytes().to_vec());
+        let reply = self
+            .metastore
+            .upsert_kv(UpsertKVReq::new(&self.key, seq, value, meta))
+            .await?;
+        // Another node acquired the lease in between if nothing is changed.
+        Ok(reply.changed())
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_config::Config;
+use common_exception::Result;
+use tracing::info;
+use tracing::warn;
+
+use crate::background_task::BackgroundTask;
+use crate::catalogs::Catalog;
+use crate::databases::Database;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION_PERIOD;
+use crate::storages::Table;
+
+/// Purges the data out of the `data_retention_period` of the tables periodically.
+///
+/// A query node serves the tenant of its configuration, so only the tables of that tenant are
+/// purged, the nodes of the other tenants purge theirs. The tables are those of the default
+/// catalog, the other catalogs (e.g. Hive) hold no Fuse tables.
+pub struct DataRetentionPurger {
+    task: BackgroundTask,
+}
+
+impl DataRetentionPurger {
+    pub fn create(config: &Config) -> DataRetentionPurger {
+        DataRetentionPurger {
+            task: BackgroundTask::create(
+                config,
+                "data retention purge",
+                Duration::from_secs(config.query.data_retention_purge_interval),
+            ),
+        }
+    }
+
+    async fn purge_tables() -> Result<()> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::Dummy)
+            .await?;
+        let ctx = session.create_query_context().await?;
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+
+        for database in catalog.list_databases(&tenant).await? {
+            for table in catalog.list_tables(&tenant, database.name()).await? {
+                if table.engine() != "FUSE"
+                    || !table
+                        .options()
+                        .contains_key(FUSE_OPT_KEY_DATA_RETENTION_PERIOD)
+                {
+                    continue;
+                }
+
+                let keep_last_snapshot = true;
+                match table.optimize(ctx.clone(), keep_last_snapshot).await {
+                    Ok(_) => info!(
+                        "Purged data out of the retention period of table {}.{}",
+                        database.name(),
+                        table.name()
+                    ),
+                    // Errors are ignored, the table will be picked up by the next round.
+                    Err(cause) => warn!(
+                        "Purge data out of the retention period of table {}.{} failure: {:?}",
+                        database.name(),
+                        table.name(),
+                        cause
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn start(&mut self) {
+        self.task.start(Self::purge_tables);
+    }
+
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.task.shutdown().await
+    }
+}
@@ -111,6 +111,30 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::AlterTableColumn(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::FlashbackTable(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
             Plan::DropTableClusterKey(plan) => {
                 session
                     .validate_privilege(
@@ -148,7 +172,9 @@ impl AccessChecker for PrivilegeAccess {
                     .await?;
             }
             Plan::OptimizeTable(_) => {}
+            Plan::AnalyzeTable(_) => {}
             Plan::ExistsTable(_) => {}
+            Plan::CreateIndex(_) => {}
 
             // Others.
             Plan::Insert(_) => {}
@@ -165,6 +191,21 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::MergeInto(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Update,
+                    )
+                    .await?;
+            }
+            Plan::Begin => {}
+            Plan::Commit => {}
+            Plan::Rollback => {}
             Plan::CreateView(plan) => {
                 session
                     .validate_privilege(
@@ -173,6 +214,18 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::CreateMaterializedView(plan) => {
+                let create_table = &plan.create_table;
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(
+                            create_table.catalog.clone(),
+                            create_table.database.clone(),
+                        ),
+                        UserPrivilegeType::Create,
+                    )
+                    .await?;
+            }
             Plan::AlterView(plan) => {
                 session
                     .validate_privilege(
@@ -189,6 +242,22 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::CreateStream(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Create,
+                    )
+                    .await?;
+            }
+            Plan::DropStream(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Drop,
+                    )
+                    .await?;
+            }
             Plan::AlterUser(_) => {}
             Plan::CreateUser(_) => {}
             Plan::DropUser(_) => {}
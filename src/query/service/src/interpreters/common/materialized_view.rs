@@ -0,0 +1,261 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::PendingInsertion;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_fuse::FuseTable;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_PREFIX;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_QUERY;
+use futures::TryStreamExt;
+use tracing::warn;
+
+use crate::interpreters::abort_insertions;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::QueryContext;
+use crate::sessions::QueryContextShared;
+use crate::sql::MaterializedView;
+use crate::sql::NameResolutionContext;
+use crate::sql::Planner;
+
+/// Refreshes the materialized views of `table` after `operations` are committed to it.
+///
+/// Errors are logged and ignored: a view that fails to refresh is stale, it is not used
+/// to answer queries, and it is fully refreshed on the next append to the table.
+pub async fn refresh_materialized_views(
+    ctx: Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    operations: &[DataBlock],
+    overwrite: bool,
+) {
+    // The insertion is committed at the end of the transaction, the views are refreshed
+    // by the COMMIT then.
+    if ctx.in_txn() {
+        return;
+    }
+
+    let tenant = ctx.get_tenant();
+    let catalog_name = table.get_table_info().catalog().to_string();
+    for (key, view_id) in table.options() {
+        let (database, view) = match key
+            .strip_prefix(OPT_KEY_MATERIALIZED_VIEW_PREFIX)
+            .and_then(|name| name.split_once('.'))
+        {
+            Some(name) => name,
+            None => continue,
+        };
+
+        // The view may have been dropped as a table.
+        let view_table = match ctx.get_catalog(&catalog_name) {
+            Ok(catalog) => match catalog.get_table(&tenant, database, view).await {
+                Ok(view_table) if &view_table.get_id().to_string() == view_id => view_table,
+                _ => continue,
+            },
+            Err(_) => continue,
+        };
+
+        let appended = if overwrite { None } else { Some(operations) };
+        if let Err(cause) =
+            refresh_materialized_view(&ctx, &catalog_name, database, view_table, appended).await
+        {
+            warn!(
+                "refresh materialized view {}.{} failed, it will be refreshed on the next append: {}",
+                database, view, cause
+            );
+        }
+    }
+}
+
+/// Refreshes the materialized view `view_table`.
+///
+/// If the view is fresh before `appended` is committed to the base table, the results of the
+/// query of the view on the appended data are merged into it, otherwise the query is re-run
+/// on the whole base table.
+///
+/// The refreshed data and the base snapshot it is fresh to are committed to the view by one
+/// update of the table meta, which fails if the view is changed by another refresh meanwhile.
+pub async fn refresh_materialized_view(
+    ctx: &Arc<QueryContext>,
+    catalog_name: &str,
+    database: &str,
+    view_table: Arc<dyn Table>,
+    appended: Option<&[DataBlock]>,
+) -> Result<()> {
+    let tenant = ctx.get_tenant();
+    let catalog = ctx.get_catalog(catalog_name)?;
+    let options = view_table.options();
+    let query = options
+        .get(OPT_KEY_MATERIALIZED_VIEW_QUERY)
+        .ok_or_else(|| {
+            ErrorCode::Internal(format!(
+                "{}.{} is not a materialized view",
+                database,
+                view_table.name()
+            ))
+        })?
+        .clone();
+    let settings = ctx.get_settings();
+    let name_resolution_ctx = NameResolutionContext::try_from(settings.as_ref())?;
+    let materialized_view = MaterializedView::parse(&query, &name_resolution_ctx)?;
+    let base_database = materialized_view
+        .database
+        .clone()
+        .unwrap_or_else(|| database.to_string());
+
+    let base_table = catalog
+        .get_table(&tenant, &base_database, &materialized_view.table)
+        .await?;
+    if options.get(OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID)
+        != Some(&base_table.get_id().to_string())
+    {
+        return Err(ErrorCode::UnknownTable(format!(
+            "Base table {}.{} of materialized view {}.{} has been replaced",
+            base_database,
+            materialized_view.table,
+            database,
+            view_table.name()
+        )));
+    }
+    let base_snapshot = base_table
+        .options()
+        .get(OPT_KEY_SNAPSHOT_LOCATION)
+        .cloned()
+        .unwrap_or_default();
+
+    let increment = match (
+        appended,
+        options.get(OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT),
+    ) {
+        (Some(operations), Some(view_snapshot)) => {
+            FuseTable::try_from_table(base_table.as_ref())?
+                .navigate_to_appended(view_snapshot, operations)
+                .await?
+        }
+        _ => None,
+    };
+
+    // The refresh runs as another query, reading the base table at `base_snapshot`, or only
+    // the appended data for the incremental refresh.
+    let shared = QueryContextShared::try_create(
+        ctx.get_config(),
+        ctx.get_current_session(),
+        ctx.get_cluster(),
+    )
+    .await?;
+    let refresh_ctx = QueryContext::create_from_shared(shared);
+    let view = format!("`{}`.`{}`", database, view_table.name());
+    let query = match &increment {
+        Some(increment) => {
+            refresh_ctx.attach_table(
+                &refresh_ctx.get_current_catalog(),
+                &base_database,
+                &materialized_view.table,
+                increment.clone(),
+            );
+            materialized_view.merge_query(&view, &query)
+        }
+        None => {
+            refresh_ctx.attach_table(
+                &refresh_ctx.get_current_catalog(),
+                &base_database,
+                &materialized_view.table,
+                base_table.clone(),
+            );
+            query
+        }
+    };
+
+    // The insertion into the view is buffered by a transaction of the session, and committed
+    // together with the base snapshot below, rather than by the INSERT itself.
+    let session = ctx.get_current_session();
+    if session.in_txn() {
+        return Err(ErrorCode::UnsupportedInTransaction(
+            "Refreshing a materialized view is not supported in a transaction",
+        ));
+    }
+    session.begin_txn();
+    let result = execute_refresh(
+        &refresh_ctx,
+        &format!("INSERT OVERWRITE {} {}", view, query),
+    )
+    .await;
+    let insertions = session.take_txn().unwrap_or_default();
+    if let Some(increment) = &increment {
+        let _ = increment.remove_detached_snapshot().await;
+    }
+
+    let result = match result {
+        Ok(_) => commit_refresh(ctx, database, &view_table, &insertions, base_snapshot).await,
+        Err(cause) => Err(cause),
+    };
+    if result.is_err() {
+        abort_insertions(ctx, insertions).await;
+    }
+    result
+}
+
+async fn commit_refresh(
+    ctx: &Arc<QueryContext>,
+    database: &str,
+    view_table: &Arc<dyn Table>,
+    insertions: &[PendingInsertion],
+    base_snapshot: String,
+) -> Result<()> {
+    let insertion = insertions
+        .iter()
+        .find(|insertion| insertion.table_info.ident.table_id == view_table.get_id())
+        .ok_or_else(|| {
+            ErrorCode::Internal(format!(
+                "The refresh of materialized view {}.{} inserts nothing",
+                database,
+                view_table.name()
+            ))
+        })?;
+
+    // The request is conditional on the version of the view read by the refresh.
+    let catalog = ctx.get_catalog(insertion.table_info.catalog())?;
+    let table = catalog.get_table_by_info(&insertion.table_info)?;
+    let mut req = table
+        .prepare_commit_insertion(ctx.clone(), &insertion.operations, insertion.overwrite)
+        .await?;
+    req.new_table_meta.options.insert(
+        OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT.to_string(),
+        base_snapshot,
+    );
+    if let Err(cause) = catalog
+        .update_table_meta(&ctx.get_tenant(), database, req.clone())
+        .await
+    {
+        let _ = table.abort_prepared_insertion(ctx.clone(), &req).await;
+        return Err(cause);
+    }
+    Ok(())
+}
+
+async fn execute_refresh(ctx: &Arc<QueryContext>, sql: &str) -> Result<()> {
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    let interpreter = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let stream = interpreter.execute(ctx.clone()).await?;
+    stream.try_collect::<Vec<_>>().await?;
+    Ok(())
+}
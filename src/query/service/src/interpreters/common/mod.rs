@@ -13,7 +13,10 @@
 // limitations under the License.
 
 mod grant;
+mod materialized_view;
 mod table;
 
 pub use grant::validate_grant_object_exists;
+pub use materialized_view::refresh_materialized_view;
+pub use materialized_view::refresh_materialized_views;
 pub use table::append2table;
@@ -22,6 +22,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_pipeline_core::Pipeline;
 
+use crate::interpreters::common::refresh_materialized_views;
 use crate::pipelines::processors::TransformAddOn;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -81,7 +82,11 @@ pub fn append2table(
                 let append_entries = ctx.consume_precommit_blocks();
                 // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
                 return GlobalIORuntime::instance().block_on(async move {
-                    table.commit_insertion(ctx, append_entries, overwrite).await
+                    table
+                        .commit_insertion(ctx.clone(), append_entries.clone(), overwrite)
+                        .await?;
+                    refresh_materialized_views(ctx, table, &append_entries, overwrite).await;
+                    Ok(())
                 });
             }
 
@@ -81,7 +81,7 @@ pub trait Interpreter: Sync + Send {
 
         let settings = ctx.get_settings();
         build_res.set_max_threads(settings.get_max_threads()? as usize);
-        let settings = ExecutorSettings::try_create(&settings)?;
+        let settings = ExecutorSettings::try_create(&ctx)?;
 
         if build_res.main_pipeline.is_complete_pipeline()? {
             let mut pipelines = build_res.sources_pipelines;
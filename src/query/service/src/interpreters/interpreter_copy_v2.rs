@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::collections::BTreeMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -23,13 +24,16 @@ use common_catalog::plan::PushDownInfo;
 use common_catalog::plan::StagePushDownInfo;
 use common_catalog::plan::StageTableInfo;
 use common_catalog::table::AppendMode;
+use common_catalog::table::Table;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::GetTableCopiedFileReq;
 use common_meta_app::schema::TableCopiedFileInfo;
 use common_meta_app::schema::UpsertTableCopiedFileReq;
+use common_meta_types::OnErrorMode;
 use common_meta_types::UserStageInfo;
+use common_pipeline_sources::processors::sources::input_formats::FileStatus;
 use common_pipeline_sources::processors::sources::input_formats::InputContext;
 use common_pipeline_transforms::processors::transforms::TransformLimit;
 use common_sql::executor::table_read_plan::ToReadDataSourcePlan;
@@ -41,13 +45,19 @@ use tracing::error;
 use tracing::info;
 
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::refresh_materialized_views;
 use crate::interpreters::Interpreter;
 use crate::interpreters::SelectInterpreterV2;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::pipelines::processors::EmptySink;
+use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::sql::plans::CopyPlanV2;
 use crate::sql::plans::Plan;
+use crate::sql::plans::ValidationMode;
 
 const MAX_QUERY_COPIED_FILES_NUM: usize = 50;
 const TABLE_COPIED_FILE_KEY_EXPIRE_AFTER_DAYS: Option<u64> = Some(7);
@@ -104,6 +114,7 @@ impl CopyInterpreterV2 {
             user_stage_info: stage.clone(),
             path: path.to_string(),
             files: vec![],
+            pattern: "".to_string(),
         };
 
         let mut build_res = select_interpreter.execute2().await?;
@@ -210,6 +221,75 @@ impl CopyInterpreterV2 {
         }
     }
 
+    /// Finishes the copy once the data is loaded:
+    /// 1. commit the data
+    /// 2. purge the copied files
+    /// 3. update the NeedCopy file into to meta
+    #[allow(clippy::too_many_arguments)]
+    async fn commit_copy(
+        ctx: Arc<QueryContext>,
+        to_table: Arc<dyn Table>,
+        stage_info: UserStageInfo,
+        operations: Vec<DataBlock>,
+        all_source_files: Vec<StageFilePartition>,
+        need_copied_files: Vec<StageFilePartition>,
+        catalog_name: String,
+        database_name: String,
+        start: Instant,
+    ) -> Result<()> {
+        let mut copied_files = BTreeMap::new();
+        for file in &need_copied_files {
+            copied_files.insert(file.path.clone(), TableCopiedFileInfo {
+                etag: file.etag.clone(),
+                content_length: file.size,
+                last_modified: Some(file.last_modified),
+            });
+        }
+
+        // 1. Commit datas.
+        info!(
+            "copy: try to commit operations:{}, elapsed:{}",
+            operations.len(),
+            start.elapsed().as_secs()
+        );
+        to_table
+            .commit_insertion(ctx.clone(), operations.clone(), false)
+            .await?;
+        refresh_materialized_views(ctx.clone(), to_table.clone(), &operations, false).await;
+
+        // 2. Try to purge copied files if purge option is true, if error will skip.
+        // If a file is already copied(status with AlreadyCopied) we will try to purge them.
+        if stage_info.copy_options.purge {
+            info!(
+                "copy: try to purge files:{}, elapsed:{}",
+                all_source_files.len(),
+                start.elapsed().as_secs()
+            );
+            CopyInterpreterV2::try_purge_files(ctx.clone(), &stage_info, &all_source_files).await;
+        }
+
+        // 3. Upsert files(status with NeedCopy) info to meta.
+        info!(
+            "copy: try to upsert file infos:{} to meta, elapsed:{}",
+            copied_files.len(),
+            start.elapsed().as_secs()
+        );
+        CopyInterpreterV2::upsert_copied_files_info_to_meta(
+            ctx.get_tenant(),
+            database_name,
+            to_table.get_id(),
+            ctx.get_catalog(&catalog_name)?,
+            copied_files,
+        )
+        .await?;
+
+        info!(
+            "copy: all copy finished, elapsed:{}",
+            start.elapsed().as_secs()
+        );
+        Ok(())
+    }
+
     // Color file if it is copied.
     async fn color_copied_files(
         ctx: &Arc<dyn TableContext>,
@@ -272,6 +352,7 @@ impl CopyInterpreterV2 {
         path: &str,
         pattern: &str,
         force: bool,
+        validation_mode: &ValidationMode,
         stage_table_info: &StageTableInfo,
     ) -> Result<PipelineBuildResult> {
         let start = Instant::now();
@@ -312,7 +393,8 @@ impl CopyInterpreterV2 {
         }
 
         // Color files if copied.
-        if !force {
+        // RETURN_ALL_ERRORS validates all the files, including the copied ones.
+        if !force && *validation_mode != ValidationMode::ReturnAllErrors {
             all_source_file_infos = CopyInterpreterV2::color_copied_files(
                 &table_ctx,
                 catalog_name,
@@ -344,8 +426,38 @@ impl CopyInterpreterV2 {
             .await?;
         stage_table.set_block_compact_thresholds(to_table.get_block_compact_thresholds());
 
+        if *validation_mode != ValidationMode::None {
+            let files = need_copied_file_infos
+                .iter()
+                .map(|v| v.path.clone())
+                .collect::<Vec<_>>();
+            return self
+                .build_validation_pipeline(validation_mode, stage_table_info, &stage_table, files)
+                .await;
+        }
+
+        if matches!(
+            stage_table_info.user_stage_info.copy_options.on_error,
+            OnErrorMode::SkipFile | OnErrorMode::SkipFileNum(_)
+        ) {
+            let file_status = self
+                .copy_files_one_by_one(
+                    catalog_name,
+                    database_name,
+                    stage_table_info,
+                    &stage_table,
+                    to_table,
+                    all_source_file_infos,
+                    need_copied_file_infos,
+                    start,
+                )
+                .await?;
+            return self.build_file_status_result(file_status);
+        }
+
         // Build pipeline.
         let mut build_res = PipelineBuildResult::create();
+        let mut input_ctx_opt = None;
         if !need_copied_file_infos.is_empty() {
             //  Build copy pipeline.
             let files = need_copied_file_infos
@@ -373,6 +485,7 @@ impl CopyInterpreterV2 {
             input_ctx
                 .format
                 .exec_copy(input_ctx.clone(), &mut build_res.main_pipeline)?;
+            input_ctx_opt = Some(input_ctx);
 
             // Build Limit pipeline.
             let limit = stage_table_info.user_stage_info.copy_options.size_limit;
@@ -399,92 +512,277 @@ impl CopyInterpreterV2 {
             )?;
 
             // Pipeline finish.
-            // 1. commit the data
-            // 2. purge the copied files
-            // 3. update the NeedCopy file into to meta
-            let stage_table_info_clone = stage_table_info.clone();
-            let catalog = self.ctx.get_catalog(catalog_name)?;
-            let tenant = self.ctx.get_tenant();
+            let stage_info = stage_table_info.user_stage_info.clone();
+            let catalog_name = catalog_name.to_string();
             let database_name = database_name.to_string();
-            let table_id = to_table.get_id();
             build_res.main_pipeline.set_on_finished(move |may_error| {
                 if may_error.is_none() {
-                    // capture out variable
-                    let ctx = ctx.clone();
-                    let to_table = to_table.clone();
-                    let stage_info = stage_table_info_clone.user_stage_info.clone();
-                    let all_source_files = all_source_file_infos.clone();
-                    let need_copied_files = need_copied_file_infos.clone();
-                    let tenant = tenant.clone();
-                    let database_name = database_name.clone();
-                    let catalog = catalog.clone();
-
-                    let mut copied_files = BTreeMap::new();
-                    for file in &need_copied_files {
-                        copied_files.insert(file.path.clone(), TableCopiedFileInfo {
-                            etag: file.etag.clone(),
-                            content_length: file.size,
-                            last_modified: Some(file.last_modified),
-                        });
-                    }
+                    let operations = ctx.consume_precommit_blocks();
+                    return GlobalIORuntime::instance().block_on(CopyInterpreterV2::commit_copy(
+                        ctx.clone(),
+                        to_table.clone(),
+                        stage_info.clone(),
+                        operations,
+                        all_source_file_infos.clone(),
+                        need_copied_file_infos.clone(),
+                        catalog_name.clone(),
+                        database_name.clone(),
+                        start,
+                    ));
+                }
+                Err(may_error.as_ref().unwrap().clone())
+            });
+        }
 
-                    return GlobalIORuntime::instance().block_on(async move {
-                        // 1. Commit datas.
-                        let operations = ctx.consume_precommit_blocks();
-                        info!(
-                            "copy: try to commit operations:{}, elapsed:{}",
-                            operations.len(),
-                            start.elapsed().as_secs()
-                        );
-                        to_table
-                            .commit_insertion(ctx.clone(), operations, false)
-                            .await?;
-
-                        // 2. Try to purge copied files if purge option is true, if error will skip.
-                        // If a file is already copied(status with AlreadyCopied) we will try to purge them.
-
-                        if stage_info.copy_options.purge {
-                            info!(
-                                "copy: try to purge files:{}, elapsed:{}",
-                                all_source_files.len(),
-                                start.elapsed().as_secs()
-                            );
-                            CopyInterpreterV2::try_purge_files(
-                                ctx.clone(),
-                                &stage_info,
-                                &all_source_files,
-                            )
-                            .await;
-                        }
+        if stage_table_info.user_stage_info.copy_options.on_error == OnErrorMode::None {
+            return Ok(build_res);
+        }
 
-                        // 3. Upsert files(status with NeedCopy) info to meta.
-                        info!(
-                            "copy: try to upsert file infos:{} to meta, elapsed:{}",
-                            copied_files.len(),
-                            start.elapsed().as_secs()
-                        );
-                        CopyInterpreterV2::upsert_copied_files_info_to_meta(
-                            tenant,
-                            database_name,
-                            table_id,
-                            catalog,
-                            copied_files,
-                        )
-                        .await?;
+        // Copy with ON_ERROR returns the load status of each file, so the data is loaded here.
+        let mut file_status = vec![];
+        if let Some(input_ctx) = input_ctx_opt {
+            let settings = self.ctx.get_settings();
+            let mut pipeline = build_res.main_pipeline;
+            pipeline.set_max_threads(settings.get_max_threads()? as usize);
+            let executor_settings = ExecutorSettings::try_create(&self.ctx)?;
+            let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+            self.ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+            executor.execute()?;
+            drop(executor);
+            file_status = input_ctx.get_file_status();
+        }
 
-                        info!(
-                            "copy: all copy finished, elapsed:{}",
-                            start.elapsed().as_secs()
-                        );
+        self.build_file_status_result(file_status)
+    }
 
-                        Ok(())
-                    });
+    /// Loads the files one by one for ON_ERROR=SKIP_FILE and SKIP_FILE_<num>, then commits the
+    /// data of the files not skipped. The blocks of a file are written as the file is loaded, and
+    /// removed if the file turns out to be skipped. Returns the load status of each file.
+    #[allow(clippy::too_many_arguments)]
+    async fn copy_files_one_by_one(
+        &self,
+        catalog_name: &str,
+        database_name: &str,
+        stage_table_info: &StageTableInfo,
+        stage_table: &Arc<dyn Table>,
+        to_table: Arc<dyn Table>,
+        all_source_file_infos: Vec<StageFilePartition>,
+        need_copied_file_infos: Vec<StageFilePartition>,
+        start: Instant,
+    ) -> Result<Vec<(String, FileStatus)>> {
+        if need_copied_file_infos.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ctx = self.ctx.clone();
+        let table_ctx: Arc<dyn TableContext> = ctx.clone();
+        let stage_info = &stage_table_info.user_stage_info;
+        let limit = stage_info.copy_options.size_limit;
+        let mut rows_to_load = limit;
+        let mut file_status = Vec::with_capacity(need_copied_file_infos.len());
+        let mut operations = vec![];
+        let mut loaded_files = HashSet::new();
+        for file in &need_copied_file_infos {
+            let input_ctx = Arc::new(
+                InputContext::try_create_from_copy(
+                    StageTable::get_op(&table_ctx, stage_info)?,
+                    ctx.get_settings(),
+                    stage_table_info.schema.clone(),
+                    stage_info.clone(),
+                    vec![file.path.clone()],
+                    ctx.get_scan_progress(),
+                    stage_table.get_block_compact_thresholds(),
+                )
+                .await?,
+            );
+            let mut pipeline = Pipeline::create();
+            input_ctx
+                .format
+                .exec_copy(input_ctx.clone(), &mut pipeline)?;
+            if limit > 0 {
+                pipeline.resize(1)?;
+                pipeline.add_transform(|transform_input_port, transform_output_port| {
+                    TransformLimit::try_create(
+                        Some(rows_to_load),
+                        0,
+                        transform_input_port,
+                        transform_output_port,
+                    )
+                })?;
+            }
+            to_table.append_data(ctx.clone(), &mut pipeline, AppendMode::Copy, false)?;
+
+            pipeline.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
+            let executor_settings = ExecutorSettings::try_create(&ctx)?;
+            let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+            ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+            executor.execute()?;
+            drop(executor);
+
+            let file_operations = ctx.consume_precommit_blocks();
+            if input_ctx.is_file_skipped(&file.path) {
+                to_table
+                    .abort_insertion(ctx.clone(), file_operations)
+                    .await?;
+            } else {
+                operations.extend(file_operations);
+                loaded_files.insert(file.path.clone());
+            }
+            for (path, status) in input_ctx.get_file_status() {
+                rows_to_load = rows_to_load.saturating_sub(status.rows_loaded);
+                file_status.push((path, status));
+            }
+            if limit > 0 && rows_to_load == 0 {
+                break;
+            }
+        }
+
+        // The files skipped, or not loaded for SIZE_LIMIT, are neither purged nor marked as copied.
+        let all_source_files = all_source_file_infos
+            .into_iter()
+            .filter(|v| v.status != StageFileStatus::NeedCopy || loaded_files.contains(&v.path))
+            .collect::<Vec<_>>();
+        let need_copied_files = need_copied_file_infos
+            .into_iter()
+            .filter(|v| loaded_files.contains(&v.path))
+            .collect::<Vec<_>>();
+        CopyInterpreterV2::commit_copy(
+            ctx,
+            to_table,
+            stage_info.clone(),
+            operations,
+            all_source_files,
+            need_copied_files,
+            catalog_name.to_string(),
+            database_name.to_string(),
+            start,
+        )
+        .await?;
+        Ok(file_status)
+    }
+
+    /// Returns the load status of each file, for COPY with ON_ERROR.
+    fn build_file_status_result(
+        &self,
+        file_status: Vec<(String, FileStatus)>,
+    ) -> Result<PipelineBuildResult> {
+        let mut names = Vec::with_capacity(file_status.len());
+        let mut rows_loaded = Vec::with_capacity(file_status.len());
+        let mut errors_seen = Vec::with_capacity(file_status.len());
+        let mut first_errors = Vec::with_capacity(file_status.len());
+        let mut first_error_lines = Vec::with_capacity(file_status.len());
+        for (name, status) in file_status {
+            names.push(name.into_bytes());
+            rows_loaded.push(status.rows_loaded as u64);
+            errors_seen.push(status.errors_seen as u64);
+            match status.first_error {
+                Some((e, line)) => {
+                    first_errors.push(Some(e.error.message().into_bytes()));
+                    first_error_lines.push(Some(line as u64));
                 }
-                Err(may_error.as_ref().unwrap().clone())
-            });
+                None => {
+                    first_errors.push(None);
+                    first_error_lines.push(None);
+                }
+            }
         }
+        PipelineBuildResult::from_blocks(vec![DataBlock::create(self.plan.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(rows_loaded),
+            Series::from_data(errors_seen),
+            Series::from_data(first_errors),
+            Series::from_data(first_error_lines),
+        ])])
+    }
 
-        Ok(build_res)
+    /// Parses the files to copy without loading them, returns the first n rows of the files for
+    /// RETURN_<n>_ROWS, or the errors of all the failed rows for RETURN_ERRORS and
+    /// RETURN_ALL_ERRORS.
+    async fn build_validation_pipeline(
+        &self,
+        validation_mode: &ValidationMode,
+        stage_table_info: &StageTableInfo,
+        stage_table: &Arc<dyn Table>,
+        files: Vec<String>,
+    ) -> Result<PipelineBuildResult> {
+        let mut build_res = PipelineBuildResult::create();
+        if files.is_empty() {
+            return Ok(build_res);
+        }
+
+        let ctx = self.ctx.clone();
+        let table_ctx: Arc<dyn TableContext> = ctx.clone();
+        let operator = StageTable::get_op(&table_ctx, &stage_table_info.user_stage_info)?;
+        let mut stage_info = stage_table_info.user_stage_info.clone();
+        let limit = match validation_mode {
+            ValidationMode::ReturnNRows(n) => Some(*n as usize),
+            _ => {
+                stage_info.copy_options.on_error = OnErrorMode::Continue;
+                None
+            }
+        };
+        let mut input_ctx = InputContext::try_create_from_copy(
+            operator,
+            ctx.get_settings(),
+            stage_table_info.schema.clone(),
+            stage_info,
+            files,
+            ctx.get_scan_progress(),
+            stage_table.get_block_compact_thresholds(),
+        )
+        .await?;
+        input_ctx.keep_errors = limit.is_none();
+        let input_ctx = Arc::new(input_ctx);
+        input_ctx
+            .format
+            .exec_copy(input_ctx.clone(), &mut build_res.main_pipeline)?;
+
+        if let Some(limit) = limit {
+            build_res.main_pipeline.resize(1)?;
+            build_res.main_pipeline.add_transform(
+                |transform_input_port, transform_output_port| {
+                    TransformLimit::try_create(
+                        Some(limit),
+                        0,
+                        transform_input_port,
+                        transform_output_port,
+                    )
+                },
+            )?;
+            return Ok(build_res);
+        }
+
+        let settings = ctx.get_settings();
+        let mut pipeline = build_res.main_pipeline;
+        pipeline.add_sink(|input| Ok(EmptySink::create(input)))?;
+        pipeline.set_max_threads(settings.get_max_threads()? as usize);
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
+        let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+        ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+        executor.execute()?;
+        drop(executor);
+
+        let fields = stage_table_info.schema.fields();
+        let mut names = vec![];
+        let mut lines = vec![];
+        let mut columns = vec![];
+        let mut errors = vec![];
+        for (name, mut status) in input_ctx.get_file_status() {
+            // The rows of a file may be deserialized out of order if the file is split.
+            status.errors.sort_by_key(|(_, line)| *line);
+            for (e, line) in status.errors {
+                names.push(name.clone().into_bytes());
+                lines.push(line as u64);
+                columns.push(e.column.map(|c| fields[c].name().as_bytes().to_vec()));
+                errors.push(e.error.message().into_bytes());
+            }
+        }
+        PipelineBuildResult::from_blocks(vec![DataBlock::create(self.plan.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(lines),
+            Series::from_data(columns),
+            Series::from_data(errors),
+        ])])
     }
 }
 
@@ -494,6 +792,10 @@ impl Interpreter for CopyInterpreterV2 {
         "CopyInterpreterV2"
     }
 
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
     #[tracing::instrument(level = "debug", name = "copy_interpreter_execute_v2", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         match &self.plan {
@@ -505,6 +807,7 @@ impl Interpreter for CopyInterpreterV2 {
                 pattern,
                 from,
                 force,
+                validation_mode,
                 ..
             } => match &from.source_info {
                 DataSourceInfo::StageSource(table_info) => {
@@ -517,6 +820,7 @@ impl Interpreter for CopyInterpreterV2 {
                         path,
                         pattern,
                         *force,
+                        validation_mode,
                         table_info,
                     )
                     .await
@@ -50,6 +50,10 @@ impl InterpreterFactory {
             e
         })?;
 
+        if ctx.get_current_session().in_txn() {
+            Self::check_txn_supported(plan)?;
+        }
+
         match plan {
             Plan::Query {
                 s_expr,
@@ -145,25 +149,47 @@ impl InterpreterFactory {
             Plan::DropTableClusterKey(drop_table_cluster_key) => Ok(Arc::new(
                 DropTableClusterKeyInterpreter::try_create(ctx, *drop_table_cluster_key.clone())?,
             )),
+            Plan::AlterTableColumn(alter_table_column) => Ok(Arc::new(
+                AlterTableColumnInterpreter::try_create(ctx, *alter_table_column.clone())?,
+            )),
             Plan::ReclusterTable(recluster_table) => Ok(Arc::new(
                 ReclusterTableInterpreter::try_create(ctx, *recluster_table.clone())?,
             )),
             Plan::TruncateTable(truncate_table) => Ok(Arc::new(
                 TruncateTableInterpreter::try_create(ctx, *truncate_table.clone())?,
             )),
+            Plan::FlashbackTable(flashback_table) => Ok(Arc::new(
+                FlashbackTableInterpreter::try_create(ctx, *flashback_table.clone())?,
+            )),
             Plan::OptimizeTable(optimize_table) => Ok(Arc::new(
                 OptimizeTableInterpreter::try_create(ctx, *optimize_table.clone())?,
             )),
+            Plan::AnalyzeTable(analyze_table) => Ok(Arc::new(AnalyzeTableInterpreter::try_create(
+                ctx,
+                *analyze_table.clone(),
+            )?)),
             Plan::ExistsTable(exists_table) => Ok(Arc::new(ExistsTableInterpreter::try_create(
                 ctx,
                 *exists_table.clone(),
             )?)),
 
+            // Indexes
+            Plan::CreateIndex(create_index) => Ok(Arc::new(CreateIndexInterpreter::try_create(
+                ctx,
+                *create_index.clone(),
+            )?)),
+
             // Views
             Plan::CreateView(create_view) => Ok(Arc::new(CreateViewInterpreter::try_create(
                 ctx,
                 *create_view.clone(),
             )?)),
+            Plan::CreateMaterializedView(create_materialized_view) => {
+                Ok(Arc::new(CreateMaterializedViewInterpreter::try_create(
+                    ctx,
+                    *create_materialized_view.clone(),
+                )?))
+            }
             Plan::AlterView(alter_view) => Ok(Arc::new(AlterViewInterpreter::try_create(
                 ctx,
                 *alter_view.clone(),
@@ -173,6 +199,16 @@ impl InterpreterFactory {
                 *drop_view.clone(),
             )?)),
 
+            // Streams
+            Plan::CreateStream(create_stream) => Ok(Arc::new(CreateStreamInterpreter::try_create(
+                ctx,
+                *create_stream.clone(),
+            )?)),
+            Plan::DropStream(drop_stream) => Ok(Arc::new(DropStreamInterpreter::try_create(
+                ctx,
+                *drop_stream.clone(),
+            )?)),
+
             // Users
             Plan::CreateUser(create_user) => Ok(Arc::new(CreateUserInterpreter::try_create(
                 ctx,
@@ -194,9 +230,20 @@ impl InterpreterFactory {
                 *delete.clone(),
             )?)),
 
-            Plan::Update(_update) => Err(ErrorCode::Unimplemented(
-                "Unimplement for update".to_string(),
-            )),
+            Plan::Update(update) => Ok(Arc::new(UpdateInterpreter::try_create(
+                ctx,
+                *update.clone(),
+            )?)),
+
+            Plan::MergeInto(merge_into) => Ok(Arc::new(MergeIntoInterpreter::try_create(
+                ctx,
+                *merge_into.clone(),
+            )?)),
+
+            // Transactions
+            Plan::Begin => Ok(Arc::new(BeginInterpreter::try_create(ctx)?)),
+            Plan::Commit => Ok(Arc::new(CommitInterpreter::try_create(ctx)?)),
+            Plan::Rollback => Ok(Arc::new(RollbackInterpreter::try_create(ctx)?)),
 
             // Roles
             Plan::CreateRole(create_role) => Ok(Arc::new(CreateRoleInterpreter::try_create(
@@ -310,4 +357,28 @@ impl InterpreterFactory {
             )),
         }
     }
+
+    // Only the insertions are buffered by an explicit transaction, the statements which
+    // mutate the existing data of a table can't be committed or rolled back with them.
+    // COPY is excluded as well, since the copied files are recorded out of the transaction,
+    // and so is CREATE MATERIALIZED VIEW, which refreshes the view by its own transaction.
+    fn check_txn_supported(plan: &Plan) -> Result<()> {
+        match plan {
+            Plan::Copy(_)
+            | Plan::CreateMaterializedView(_)
+            | Plan::Delete(_)
+            | Plan::Update(_)
+            | Plan::MergeInto(_)
+            | Plan::TruncateTable(_)
+            | Plan::OptimizeTable(_)
+            | Plan::AnalyzeTable(_)
+            | Plan::ReclusterTable(_)
+            | Plan::AlterTableColumn(_)
+            | Plan::FlashbackTable(_) => Err(ErrorCode::UnsupportedInTransaction(format!(
+                "{} is not supported in a transaction",
+                plan
+            ))),
+            _ => Ok(()),
+        }
+    }
 }
@@ -42,6 +42,7 @@ use parking_lot::RwLock;
 
 use super::plan_schedulers::build_schedule_pipeline;
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::refresh_materialized_views;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::pipelines::PipelineBuildResult;
@@ -221,7 +222,16 @@ impl Interpreter for InsertInterpreterV2 {
                             let append_entries = ctx.consume_precommit_blocks();
                             // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
                             return GlobalIORuntime::instance().block_on(async move {
-                                table.commit_insertion(ctx, append_entries, overwrite).await
+                                table
+                                    .commit_insertion(
+                                        ctx.clone(),
+                                        append_entries.clone(),
+                                        overwrite,
+                                    )
+                                    .await?;
+                                refresh_materialized_views(ctx, table, &append_entries, overwrite)
+                                    .await;
+                                Ok(())
                             });
                         }
 
@@ -0,0 +1,104 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_sql::plans::CreateMaterializedViewPlan;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_PREFIX;
+
+use crate::interpreters::common::refresh_materialized_view;
+use crate::interpreters::CreateTableInterpreterV2;
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct CreateMaterializedViewInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateMaterializedViewPlan,
+}
+
+impl CreateMaterializedViewInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateMaterializedViewPlan) -> Result<Self> {
+        Ok(CreateMaterializedViewInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateMaterializedViewInterpreter {
+    fn name(&self) -> &str {
+        "CreateMaterializedViewInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan.create_table;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        // check whether view has exists
+        if catalog
+            .list_tables(&plan.tenant, &plan.database)
+            .await?
+            .iter()
+            .any(|table| table.name() == plan.table.as_str())
+        {
+            if plan.if_not_exists {
+                return Ok(PipelineBuildResult::create());
+            }
+            return Err(ErrorCode::ViewAlreadyExists(format!(
+                "{}.{} as view Already Exists",
+                plan.database, plan.table
+            )));
+        }
+
+        CreateTableInterpreterV2::try_create(self.ctx.clone(), *plan.clone())?
+            .execute2()
+            .await?;
+        let view_table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+
+        // Register the view on the base table, so that it is refreshed on appends.
+        let base_table = catalog
+            .get_table(
+                &plan.tenant,
+                &self.plan.base_database,
+                &self.plan.base_table,
+            )
+            .await?;
+        let req = UpsertTableOptionReq {
+            table_id: base_table.get_id(),
+            seq: MatchSeq::Any,
+            options: HashMap::from([(
+                format!(
+                    "{}{}.{}",
+                    OPT_KEY_MATERIALIZED_VIEW_PREFIX, plan.database, plan.table
+                ),
+                Some(view_table.get_id().to_string()),
+            )]),
+        };
+        catalog
+            .upsert_table_option(&plan.tenant, &self.plan.base_database, req)
+            .await?;
+
+        refresh_materialized_view(&self.ctx, &plan.catalog, &plan.database, view_table, None)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Expression;
+use common_catalog::table::MergeIntoInfo;
+use common_catalog::table::MergeIntoMatches;
+use common_catalog::table::MergeIntoSource;
+use common_catalog::table::MergeMatchedAction;
+use common_catalog::table::MergeMatchedClause;
+use common_catalog::table::MergeNotMatchedClause;
+use common_datablocks::DataBlock;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_datavalues::UInt32Column;
+use common_datavalues::UInt32Type;
+use common_datavalues::UInt64Column;
+use common_datavalues::UInt64Type;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_sql::executor::format_field_name;
+use common_sql::executor::ExpressionBuilderWithRenaming;
+use common_sql::executor::PhysicalScalarBuilder;
+use common_sql::plans::MergeIntoPlan;
+use common_sql::plans::MergeMatchedAction as PlanMergeMatchedAction;
+use common_sql::plans::Plan;
+use common_sql::plans::Scalar;
+use common_sql::IndexType;
+use parking_lot::Mutex;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::SelectInterpreterV2;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::transforms::hash_join::ProbeState;
+use crate::pipelines::processors::HashJoinDesc;
+use crate::pipelines::processors::HashJoinState;
+use crate::pipelines::processors::JoinHashTable;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::evaluator::Evaluator;
+use crate::sql::executor::PhysicalScalar;
+
+// the row numbers of the source rows, telling which of them are matched
+const MERGE_SOURCE_ROW_COLUMN: &str = "_merge_source_row";
+// the row numbers of the target rows in the probed block
+const MERGE_TARGET_ROW_COLUMN: &str = "_merge_target_row";
+
+/// interprets MergeIntoPlan
+pub struct MergeIntoInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergeIntoPlan,
+}
+
+impl MergeIntoInterpreter {
+    /// Create the MergeIntoInterpreter from MergeIntoPlan
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergeIntoPlan) -> Result<Self> {
+        Ok(MergeIntoInterpreter { ctx, plan })
+    }
+
+    // Columns are named after the expression builder with renaming,
+    // so that the columns of the target and the source never clash.
+    fn column_name(&self, index: IndexType) -> String {
+        let metadata = self.plan.metadata.read();
+        format_field_name(metadata.column(index).name(), index)
+    }
+
+    /// Executes the source query, and builds the hash table of the join with the source rows.
+    async fn build_source(&self, table_schema: &DataSchemaRef) -> Result<MergeIntoJoin> {
+        let plan = &self.plan;
+        let (s_expr, metadata, bind_context) = match plan.source.as_ref() {
+            Plan::Query {
+                s_expr,
+                metadata,
+                bind_context,
+                ..
+            } => (s_expr, metadata, bind_context),
+            _ => {
+                return Err(ErrorCode::Internal(
+                    "the source of MERGE INTO should be a query",
+                ));
+            }
+        };
+        let interpreter = SelectInterpreterV2::try_create(
+            self.ctx.clone(),
+            *bind_context.clone(),
+            *s_expr.clone(),
+            metadata.clone(),
+        )?;
+
+        // the columns of the build side and the probe side are named after their indices,
+        // as the physical scalars expect, followed by the row numbers
+        let source_types = interpreter
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        let mut build_fields = plan
+            .source_columns
+            .iter()
+            .zip(source_types.iter())
+            .map(|(index, data_type)| DataField::new(&index.to_string(), data_type.clone()))
+            .collect::<Vec<_>>();
+        build_fields.push(DataField::new(
+            MERGE_SOURCE_ROW_COLUMN,
+            UInt64Type::new_impl(),
+        ));
+        let build_schema = DataSchemaRefExt::create(build_fields);
+        let mut probe_fields = plan
+            .target_columns
+            .iter()
+            .zip(table_schema.fields())
+            .map(|(index, field)| DataField::new(&index.to_string(), field.data_type().clone()))
+            .collect::<Vec<_>>();
+        probe_fields.push(DataField::new(
+            MERGE_TARGET_ROW_COLUMN,
+            UInt32Type::new_impl(),
+        ));
+        let probe_schema = DataSchemaRefExt::create(probe_fields);
+        let merged_schema = DataSchemaRefExt::create(
+            probe_schema
+                .fields()
+                .iter()
+                .chain(build_schema.fields())
+                .cloned()
+                .collect(),
+        );
+
+        let build_keys = plan
+            .source_keys
+            .iter()
+            .map(|scalar| PhysicalScalarBuilder::new(&build_schema).build(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let probe_keys = plan
+            .target_keys
+            .iter()
+            .map(|scalar| PhysicalScalarBuilder::new(&probe_schema).build(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let non_equi_conditions = plan
+            .join_filter
+            .iter()
+            .map(|scalar| PhysicalScalarBuilder::new(&merged_schema).build(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let join = JoinHashTable::create_join_state(
+            self.ctx.clone(),
+            &build_keys,
+            build_schema.clone(),
+            probe_schema.clone(),
+            HashJoinDesc::create_inner_join(&build_keys, &probe_keys, &non_equi_conditions)?,
+        )?;
+
+        let mut build_res = interpreter.execute2().await?;
+        let settings = self.ctx.get_settings();
+        build_res.set_max_threads(settings.get_max_threads()? as usize);
+        let executor_settings = ExecutorSettings::try_create(&self.ctx)?;
+        let mut executor = PipelinePullingExecutor::from_pipelines(build_res, executor_settings)?;
+        executor.start();
+
+        let mut num_rows = 0;
+        while let Some(block) = executor.pull_data()? {
+            if block.num_rows() == 0 {
+                continue;
+            }
+            let rows = (num_rows..num_rows + block.num_rows() as u64).collect::<Vec<_>>();
+            num_rows += block.num_rows() as u64;
+            let mut columns = block.columns().to_vec();
+            columns.push(Series::from_data(rows));
+            join.build(DataBlock::create(build_schema.clone(), columns))?;
+        }
+        join.finish()?;
+
+        let schema = DataSchemaRefExt::create(
+            plan.source_columns
+                .iter()
+                .zip(source_types.into_iter())
+                .map(|(index, data_type)| DataField::new(&self.column_name(*index), data_type))
+                .collect(),
+        );
+        Ok(MergeIntoJoin {
+            join,
+            schema,
+            probe_schema,
+            matched: Mutex::new(vec![false; num_rows as usize]),
+        })
+    }
+
+    // The default value of a field, for the rows inserted without the value of it.
+    fn default_value(&self, field: &DataField) -> Result<Expression> {
+        let value = match field.default_expr() {
+            Some(default_expr) => {
+                let default_expr: PhysicalScalar = serde_json::from_str(default_expr)?;
+                let func_ctx = self.ctx.try_get_function_context()?;
+                let (value, _) =
+                    Evaluator::eval_physical_scalar(&default_expr)?.try_eval_const(&func_ctx)?;
+                value
+            }
+            None => field.data_type().default_value(),
+        };
+        Ok(Expression::Constant {
+            value,
+            data_type: field.data_type().clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeIntoInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "MergeIntoInterpreter"
+    }
+
+    /// Get the schema of MergeIntoPlan
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "merge_into_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let tbl = self
+            .ctx
+            .get_table(&plan.catalog, &plan.database, &plan.table)
+            .await?;
+        let schema = tbl.schema();
+
+        let builder = ExpressionBuilderWithRenaming::create(plan.metadata.clone());
+        let build_opt =
+            |scalar: &Option<Scalar>| scalar.as_ref().map(|s| builder.build(s)).transpose();
+
+        let matched = plan
+            .matched_clauses
+            .iter()
+            .map(|clause| {
+                let action = match &clause.action {
+                    PlanMergeMatchedAction::Update(update_list) => MergeMatchedAction::Update(
+                        update_list
+                            .iter()
+                            .map(|(idx, scalar)| Ok((*idx, builder.build(scalar)?)))
+                            .collect::<Result<Vec<_>>>()?,
+                    ),
+                    PlanMergeMatchedAction::Delete => MergeMatchedAction::Delete,
+                };
+                Ok(MergeMatchedClause {
+                    condition: build_opt(&clause.condition)?,
+                    action,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let not_matched = plan
+            .not_matched_clauses
+            .iter()
+            .map(|clause| {
+                let values = clause
+                    .values
+                    .iter()
+                    .zip(schema.fields())
+                    .map(|(value, field)| match value {
+                        Some(scalar) => builder.build(scalar),
+                        None => self.default_value(field),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(MergeNotMatchedClause {
+                    condition: build_opt(&clause.condition)?,
+                    values,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let merge = MergeIntoInfo {
+            target_columns: plan
+                .target_columns
+                .iter()
+                .map(|index| self.column_name(*index))
+                .collect(),
+            source: Box::new(self.build_source(&schema).await?),
+            matched,
+            not_matched,
+        };
+
+        tbl.merge_into(self.ctx.clone(), merge).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+/// The inner hash join of MERGE INTO, on the join keys and the join filter.
+///
+/// The source rows are the build side, and the blocks of the target probe them as they are
+/// scanned by the table, so that only the source rows are held in memory.
+struct MergeIntoJoin {
+    join: Arc<JoinHashTable>,
+    // the source columns, named after the names used by the expressions
+    schema: DataSchemaRef,
+    probe_schema: DataSchemaRef,
+    // whether each source row matches a row of the target
+    matched: Mutex<Vec<bool>>,
+}
+
+impl MergeIntoSource for MergeIntoJoin {
+    fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.matched.lock().is_empty()
+    }
+
+    fn probe(&self, target: &DataBlock) -> Result<MergeIntoMatches> {
+        let num_rows = target.num_rows();
+        let mut columns = target.columns().to_vec();
+        columns.push(Series::from_data((0..num_rows as u32).collect::<Vec<_>>()));
+        let input = DataBlock::create(self.probe_schema.clone(), columns);
+
+        // the probed blocks are the target columns followed by the source columns
+        let num_target_columns = self.probe_schema.num_fields();
+        let num_source_columns = self.schema.num_fields();
+        let mut probe_state = ProbeState::with_capacity(num_rows);
+        let mut matched = self.matched.lock();
+        let mut target_rows = vec![];
+        let mut sources = vec![];
+        for block in self.join.probe(&input, &mut probe_state)? {
+            if block.num_rows() == 0 {
+                continue;
+            }
+            let rows: &UInt32Column = Series::check_get(block.column(num_target_columns - 1))?;
+            target_rows.extend_from_slice(rows.values());
+            let source_rows: &UInt64Column =
+                Series::check_get(block.column(num_target_columns + num_source_columns))?;
+            for row in source_rows.iter() {
+                matched[*row as usize] = true;
+            }
+            sources.push(DataBlock::create(
+                self.schema.clone(),
+                block.columns()[num_target_columns..num_target_columns + num_source_columns]
+                    .to_vec(),
+            ));
+        }
+
+        let source = if sources.is_empty() {
+            DataBlock::empty_with_schema(self.schema.clone())
+        } else {
+            DataBlock::concat_blocks(&sources)?
+        };
+        Ok(MergeIntoMatches {
+            target_rows,
+            source,
+        })
+    }
+
+    fn unmatched(&self) -> Result<Vec<DataBlock>> {
+        let num_source_columns = self.schema.num_fields();
+        let matched = self.matched.lock();
+        let mut blocks = vec![];
+        for block in self.join.row_space.datablocks() {
+            let rows: &UInt64Column = Series::check_get(block.column(num_source_columns))?;
+            let unmatched = rows
+                .iter()
+                .map(|row| !matched[*row as usize])
+                .collect::<Vec<_>>();
+            let block = DataBlock::create(
+                self.schema.clone(),
+                block.columns()[..num_source_columns].to_vec(),
+            );
+            blocks.push(DataBlock::filter_block(
+                block,
+                &Series::from_data(unmatched),
+            )?);
+        }
+        Ok(blocks)
+    }
+}
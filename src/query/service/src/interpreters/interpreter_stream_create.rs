@@ -0,0 +1,123 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::StringType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_sql::plans::CreateStreamPlan;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::stream_table::CHANGE_ACTION_COLUMN;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_DATABASE;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_TABLE;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_TABLE_ID;
+use common_storages_view::stream_table::OPT_KEY_STREAM_OFFSET;
+use common_storages_view::stream_table::STREAM_ENGINE;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct CreateStreamInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateStreamPlan,
+}
+
+impl CreateStreamInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateStreamPlan) -> Result<Self> {
+        Ok(CreateStreamInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateStreamInterpreter {
+    fn name(&self) -> &str {
+        "CreateStreamInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        // check whether stream has exists
+        if catalog
+            .list_tables(&plan.tenant, &plan.database)
+            .await?
+            .iter()
+            .any(|table| table.name() == plan.stream.as_str())
+        {
+            if plan.if_not_exists {
+                return Ok(PipelineBuildResult::create());
+            }
+            return Err(ErrorCode::StreamAlreadyExists(format!(
+                "{}.{} as stream Already Exists",
+                plan.database, plan.stream
+            )));
+        }
+
+        // The offset of the new stream is the current version of the base table.
+        let table = catalog
+            .get_table(&plan.tenant, &plan.table_database, &plan.table)
+            .await?;
+        let offset = table
+            .options()
+            .get(OPT_KEY_SNAPSHOT_LOCATION)
+            .cloned()
+            .unwrap_or_default();
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_STREAM_BASE_TABLE_ID.to_string(),
+            table.get_id().to_string(),
+        );
+        options.insert(
+            OPT_KEY_STREAM_BASE_DATABASE.to_string(),
+            plan.table_database.clone(),
+        );
+        options.insert(OPT_KEY_STREAM_BASE_TABLE.to_string(), plan.table.clone());
+        options.insert(OPT_KEY_STREAM_OFFSET.to_string(), offset);
+
+        let mut fields = table.schema().fields().clone();
+        fields.push(DataField::new(
+            CHANGE_ACTION_COLUMN,
+            DataTypeImpl::String(StringType::default()),
+        ));
+
+        let req = CreateTableReq {
+            if_not_exists: plan.if_not_exists,
+            name_ident: TableNameIdent {
+                tenant: plan.tenant.clone(),
+                db_name: plan.database.clone(),
+                table_name: plan.stream.clone(),
+            },
+            table_meta: TableMeta {
+                engine: STREAM_ENGINE.to_string(),
+                schema: DataSchemaRefExt::create(fields),
+                options,
+                ..Default::default()
+            },
+        };
+        catalog.create_table(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
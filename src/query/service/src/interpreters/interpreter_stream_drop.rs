@@ -0,0 +1,77 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::DropTableReq;
+use common_meta_app::schema::TableNameIdent;
+use common_sql::plans::DropStreamPlan;
+use common_storages_view::stream_table::STREAM_ENGINE;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct DropStreamInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: DropStreamPlan,
+}
+
+impl DropStreamInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: DropStreamPlan) -> Result<Self> {
+        Ok(DropStreamInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropStreamInterpreter {
+    fn name(&self) -> &str {
+        "DropStreamInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let tbl = self
+            .ctx
+            .get_table(&self.plan.catalog, &self.plan.database, &self.plan.stream)
+            .await
+            .ok();
+
+        if let Some(table) = &tbl {
+            if table.get_table_info().engine() != STREAM_ENGINE {
+                return Err(ErrorCode::TableEngineNotSupported(format!(
+                    "{}.{} engine is {} that doesn't support DROP STREAM",
+                    &self.plan.database,
+                    &self.plan.stream,
+                    table.get_table_info().engine()
+                )));
+            }
+        };
+
+        let catalog = self.ctx.get_catalog(&self.plan.catalog)?;
+        let plan = DropTableReq {
+            if_exists: self.plan.if_exists,
+            name_ident: TableNameIdent {
+                tenant: self.plan.tenant.clone(),
+                db_name: self.plan.database.clone(),
+                table_name: self.plan.stream.clone(),
+            },
+        };
+        catalog.drop_table(plan).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
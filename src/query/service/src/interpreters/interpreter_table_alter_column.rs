@@ -0,0 +1,137 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::AlterColumnAction;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_sql::plans::AlterTableColumnAction;
+use common_sql::plans::AlterTableColumnPlan;
+use common_sql::plans::Scalar;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::evaluator::Evaluator;
+use crate::sql::executor::PhysicalScalar;
+use crate::sql::executor::PhysicalScalarBuilder;
+
+pub struct AlterTableColumnInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterTableColumnPlan,
+}
+
+impl AlterTableColumnInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AlterTableColumnPlan) -> Result<Self> {
+        Ok(AlterTableColumnInterpreter { ctx, plan })
+    }
+
+    fn build_default_expr(scalar: &Scalar) -> Result<PhysicalScalar> {
+        let input_schema = Arc::new(DataSchema::empty());
+        PhysicalScalarBuilder::new(&input_schema).build(scalar)
+    }
+
+    fn with_default_expr(
+        field: &DataField,
+        default_expr: Option<&PhysicalScalar>,
+    ) -> Result<DataField> {
+        let default_expr = default_expr.map(serde_json::to_string).transpose()?;
+        Ok(field.clone().with_default_expr(default_expr))
+    }
+
+    /// The value of the new column for the rows inserted before the column was added.
+    fn eval_missing_value(
+        &self,
+        field: &DataField,
+        default_expr: Option<&PhysicalScalar>,
+    ) -> Result<DataValue> {
+        match default_expr {
+            None => Ok(field.data_type().default_value()),
+            Some(default_expr) => {
+                let func_ctx = self.ctx.try_get_function_context()?;
+                let (value, _) =
+                    Evaluator::eval_physical_scalar(default_expr)?.try_eval_const(&func_ctx)?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterTableColumnInterpreter {
+    fn name(&self) -> &str {
+        "AlterTableColumnInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let tenant = self.ctx.get_tenant();
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(tenant.as_str(), &plan.database, &plan.table)
+            .await?;
+
+        let action = match &plan.action {
+            AlterTableColumnAction::Add {
+                field,
+                default_expr,
+                comment,
+            } => {
+                let default_expr = default_expr
+                    .as_ref()
+                    .map(Self::build_default_expr)
+                    .transpose()?;
+                let missing_value = self.eval_missing_value(field, default_expr.as_ref())?;
+                let field = Self::with_default_expr(field, default_expr.as_ref())?;
+                AlterColumnAction::Add {
+                    field,
+                    comment: comment.clone(),
+                    missing_value,
+                }
+            }
+            AlterTableColumnAction::Drop { name } => AlterColumnAction::Drop { name: name.clone() },
+            AlterTableColumnAction::Rename { name, new_name } => AlterColumnAction::Rename {
+                name: name.clone(),
+                new_name: new_name.clone(),
+            },
+            AlterTableColumnAction::Modify {
+                field,
+                default_expr,
+            } => {
+                let default_expr = match default_expr {
+                    Some(default_expr) => Some(Self::build_default_expr(default_expr)?),
+                    // Keep the default value of the column, cast to the new type.
+                    None => match table.schema().field_with_name(field.name())?.default_expr() {
+                        Some(old_default_expr) => Some(PhysicalScalar::Cast {
+                            input: Box::new(serde_json::from_str(old_default_expr)?),
+                            target: field.data_type().clone(),
+                        }),
+                        None => None,
+                    },
+                };
+                let field = Self::with_default_expr(field, default_expr.as_ref())?;
+                AlterColumnAction::Modify { field }
+            }
+        };
+
+        table.alter_table_column(self.ctx.clone(), action).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
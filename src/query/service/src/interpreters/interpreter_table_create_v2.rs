@@ -12,20 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use common_datavalues::wrap_nullable;
 use common_datavalues::DataField;
+use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::TypeFactory;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::CreateTableReq;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableNameIdent;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_sql::plans::CloneTableSource;
 use common_sql::plans::CreateTablePlanV2;
 use common_users::UserApiProvider;
+use futures::TryStreamExt;
 
 use crate::interpreters::InsertInterpreterV2;
 use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -33,6 +43,7 @@ use crate::sql::executor::PhysicalScalarBuilder;
 use crate::sql::plans::insert::Insert;
 use crate::sql::plans::insert::InsertInputSource;
 use crate::sql::plans::Plan;
+use crate::storages::fuse::FUSE_OPT_KEY_CLONE_PREFIX;
 use crate::storages::StorageDescription;
 
 pub struct CreateTableInterpreterV2 {
@@ -100,9 +111,15 @@ impl Interpreter for CreateTableInterpreterV2 {
             }
         }
 
-        match &self.plan.as_select {
-            Some(select_plan_node) => self.create_table_as_select(select_plan_node.clone()).await,
-            None => self.create_table().await,
+        match (&self.plan.as_select, &self.plan.clone_source) {
+            (Some(select_plan_node), _) => {
+                self.create_table_as_select(select_plan_node.clone()).await
+            }
+            (None, Some(clone_source)) => self.create_table_as_clone(clone_source).await,
+            (None, None) => match &self.plan.template {
+                Some(template) => self.create_table_from_template(template).await,
+                None => self.create_table().await,
+            },
         }
     }
 }
@@ -151,6 +168,102 @@ impl CreateTableInterpreterV2 {
             .await
     }
 
+    async fn create_table_as_clone(
+        &self,
+        clone_source: &CloneTableSource,
+    ) -> Result<PipelineBuildResult> {
+        let tenant = self.ctx.get_tenant();
+        let source = self
+            .ctx
+            .get_table(
+                &clone_source.catalog,
+                &clone_source.database,
+                &clone_source.table,
+            )
+            .await?;
+        let source = match &clone_source.travel_point {
+            Some(point) => source.navigate_to(point).await?,
+            None => source,
+        };
+
+        let catalog = self.ctx.get_catalog(&self.plan.catalog)?;
+        // The existing table must not be overwritten by the data of the source.
+        if self.plan.if_not_exists
+            && catalog
+                .exists_table(tenant.as_str(), &self.plan.database, &self.plan.table)
+                .await?
+        {
+            return Ok(PipelineBuildResult::create());
+        }
+        catalog.create_table(self.build_request()?).await?;
+        let table = catalog
+            .get_table(tenant.as_str(), &self.plan.database, &self.plan.table)
+            .await?;
+
+        // Register the new table on the source before sharing the files, so that the garbage
+        // collection of the source keeps the files shared with it.
+        let req = UpsertTableOptionReq {
+            table_id: source.get_id(),
+            seq: MatchSeq::Any,
+            options: HashMap::from([(
+                format!("{}{}", FUSE_OPT_KEY_CLONE_PREFIX, table.get_id()),
+                Some(table.get_id().to_string()),
+            )]),
+        };
+        self.ctx
+            .get_catalog(&clone_source.catalog)?
+            .upsert_table_option(tenant.as_str(), &clone_source.database, req)
+            .await?;
+
+        // The data of the source is shared with the new table, instead of being copied.
+        table.clone_data_from(self.ctx.clone(), source).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+
+    /// Creates the table with the columns returned by the template query, each row of which
+    /// is the name, the type and optionally the nullability of a column.
+    async fn create_table_from_template(&self, template: &Plan) -> Result<PipelineBuildResult> {
+        let interpreter = InterpreterFactory::get(self.ctx.clone(), template).await?;
+        let blocks = interpreter
+            .execute(self.ctx.clone())
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut fields = vec![];
+        let mut names = HashSet::new();
+        for block in blocks.iter() {
+            for row in 0..block.num_rows() {
+                let name = String::from_utf8(block.column(0).get(row).as_string()?)?;
+                let type_name = String::from_utf8(block.column(1).get(row).as_string()?)?;
+                let mut data_type = TypeFactory::instance().get(&type_name)?;
+                if block.num_columns() > 2 && block.column(2).get(row).as_bool()? {
+                    data_type = wrap_nullable(&data_type);
+                }
+                if !names.insert(name.clone()) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Duplicated column name: {}",
+                        name
+                    )));
+                }
+                fields.push(DataField::new(&name, data_type));
+            }
+        }
+        if fields.is_empty() {
+            return Err(ErrorCode::BadArguments(
+                "the template query of CREATE TABLE returns no column",
+            ));
+        }
+
+        let mut plan = self.plan.clone();
+        plan.schema = DataSchemaRefExt::create(fields);
+        plan.template = None;
+        CreateTableInterpreterV2::try_create(self.ctx.clone(), plan)?
+            .create_table()
+            .await
+    }
+
     async fn create_table(&self) -> Result<PipelineBuildResult> {
         let catalog = self.ctx.get_catalog(self.plan.catalog.as_str())?;
         catalog.create_table(self.build_request()?).await?;
@@ -175,7 +288,11 @@ impl CreateTableInterpreterV2 {
             };
             fields.push(field)
         }
-        let schema = DataSchemaRefExt::create(fields);
+        // The metadata of the schema, e.g. the column mapping of a cloned table, is kept.
+        let schema = Arc::new(DataSchema::new_from(
+            fields,
+            self.plan.schema.meta().clone(),
+        ));
 
         let mut table_meta = TableMeta {
             schema,
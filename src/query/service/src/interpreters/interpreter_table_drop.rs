@@ -18,6 +18,7 @@ use common_catalog::table::TableExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_sql::plans::DropTablePlan;
+use common_storages_view::stream_table::STREAM_ENGINE;
 use common_storages_view::view_table::VIEW_ENGINE;
 
 use crate::interpreters::Interpreter;
@@ -59,6 +60,12 @@ impl Interpreter for DropTableInterpreter {
                     &self.plan.database, &self.plan.table, &self.plan.database, &self.plan.table
                 )));
             }
+            if table.get_table_info().engine() == STREAM_ENGINE {
+                return Err(ErrorCode::TableEngineNotSupported(format!(
+                    "{}.{} engine is STREAM that doesn't support drop, use `DROP STREAM {}.{}` instead",
+                    &self.plan.database, &self.plan.table, &self.plan.database, &self.plan.table
+                )));
+            }
         };
 
         let catalog = self.ctx.get_catalog(catalog_name)?;
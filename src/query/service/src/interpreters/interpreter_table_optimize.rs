@@ -95,7 +95,7 @@ impl Interpreter for OptimizeTableInterpreter {
             if let Some(mutator) = mutator {
                 let settings = ctx.get_settings();
                 pipeline.set_max_threads(settings.get_max_threads()? as usize);
-                let executor_settings = ExecutorSettings::try_create(&settings)?;
+                let executor_settings = ExecutorSettings::try_create(&ctx)?;
                 let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
 
                 ctx.set_executor(Arc::downgrade(&executor.get_inner()));
@@ -0,0 +1,172 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::TableExt;
+use common_catalog::table_context::PendingInsertion;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
+use tracing::info;
+use tracing::warn;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::common::refresh_materialized_views;
+use crate::interpreters::interpreter_txn_rollback::abort_insertions;
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+const MAX_RETRIES: usize = 10;
+
+/// Commits the insertions buffered by the transaction, as one request to the meta service.
+pub struct CommitInterpreter {
+    ctx: Arc<QueryContext>,
+}
+
+impl CommitInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<Self> {
+        Ok(CommitInterpreter { ctx })
+    }
+
+    async fn commit(&self, insertions: &[PendingInsertion]) -> Result<()> {
+        let catalog_name = insertions[0].table_info.catalog();
+        if insertions
+            .iter()
+            .any(|insertion| insertion.table_info.catalog() != catalog_name)
+        {
+            return Err(ErrorCode::UnsupportedInTransaction(
+                "Cannot commit the insertions into tables of different catalogs in one transaction",
+            ));
+        }
+        let catalog = self.ctx.get_catalog(catalog_name)?;
+
+        let mut retry_times = 0;
+        loop {
+            match self.try_commit(catalog.as_ref(), insertions).await {
+                Err(e) if e.code() == ErrorCode::TABLE_VERSION_MISMATCHED => {
+                    if retry_times >= MAX_RETRIES {
+                        return Err(ErrorCode::OCCRetryFailure(format!(
+                            "can not fulfill the transaction after retries({} times), aborted",
+                            retry_times
+                        )));
+                    }
+                    retry_times += 1;
+                    info!(
+                        "got error TableVersionMismatched, transaction will be retried, retry times {}",
+                        retry_times
+                    );
+                }
+                res => return res,
+            }
+        }
+    }
+
+    // Builds the new snapshots on top of the latest versions of the tables,
+    // and commits them all together.
+    async fn try_commit(
+        &self,
+        catalog: &dyn Catalog,
+        insertions: &[PendingInsertion],
+    ) -> Result<()> {
+        let ctx: Arc<dyn TableContext> = self.ctx.clone();
+
+        let mut prepared = Vec::with_capacity(insertions.len());
+        let result: Result<()> = async {
+            for insertion in insertions {
+                let table = catalog.get_table_by_info(&insertion.table_info)?;
+                let table = table.refresh(ctx.as_ref()).await?;
+                let req = table
+                    .prepare_commit_insertion(
+                        ctx.clone(),
+                        &insertion.operations,
+                        insertion.overwrite,
+                    )
+                    .await?;
+                prepared.push((table, req));
+            }
+
+            let req = UpdateMultiTableMetaReq {
+                update_table_metas: prepared.iter().map(|(_, req)| req.clone()).collect(),
+            };
+            catalog.update_multi_table_meta(req).await?;
+            Ok(())
+        }
+        .await;
+
+        if result.is_err() {
+            for (table, req) in &prepared {
+                let _ = table.abort_prepared_insertion(ctx.clone(), req).await;
+            }
+        }
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CommitInterpreter {
+    fn name(&self) -> &str {
+        "CommitInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        // COMMIT out of a transaction does nothing.
+        let insertions = match self.ctx.get_current_session().take_txn() {
+            Some(insertions) if !insertions.is_empty() => insertions,
+            _ => return Ok(PipelineBuildResult::create()),
+        };
+
+        if let Err(e) = self.commit(&insertions).await {
+            abort_insertions(&self.ctx, insertions).await;
+            return Err(e);
+        }
+
+        refresh_committed_views(&self.ctx, &insertions).await;
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+/// Refreshes the materialized views of the tables the committed insertions went into,
+/// which are skipped by the insertions in the transaction.
+///
+/// Errors are logged and ignored, the transaction is committed already.
+async fn refresh_committed_views(ctx: &Arc<QueryContext>, insertions: &[PendingInsertion]) {
+    for insertion in insertions {
+        let table_info = &insertion.table_info;
+        let table = match ctx
+            .get_catalog(table_info.catalog())
+            .and_then(|catalog| catalog.get_table_by_info(table_info))
+        {
+            Ok(table) => table.refresh(ctx.as_ref()).await,
+            Err(cause) => Err(cause),
+        };
+        match table {
+            Ok(table) => {
+                refresh_materialized_views(
+                    ctx.clone(),
+                    table,
+                    &insertion.operations,
+                    insertion.overwrite,
+                )
+                .await
+            }
+            Err(cause) => warn!(
+                "refresh materialized views of table {} failed, they will be refreshed on the next append: {:?}",
+                table_info.desc, cause
+            ),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::PendingInsertion;
+use common_exception::Result;
+use tracing::warn;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct RollbackInterpreter {
+    ctx: Arc<QueryContext>,
+}
+
+impl RollbackInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<Self> {
+        Ok(RollbackInterpreter { ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for RollbackInterpreter {
+    fn name(&self) -> &str {
+        "RollbackInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        // ROLLBACK out of a transaction does nothing.
+        if let Some(insertions) = self.ctx.get_current_session().take_txn() {
+            abort_insertions(&self.ctx, insertions).await;
+        }
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+/// Removes the data of the insertions, which will never be committed.
+///
+/// Errors are ignored, the data left is not referenced by any snapshot, and will be
+/// collected by the "major GC".
+pub async fn abort_insertions(ctx: &Arc<QueryContext>, insertions: Vec<PendingInsertion>) {
+    for insertion in insertions {
+        let table_info = &insertion.table_info;
+        let table = match ctx
+            .get_catalog(table_info.catalog())
+            .and_then(|catalog| catalog.get_table_by_info(table_info))
+        {
+            Ok(table) => table,
+            Err(cause) => {
+                warn!(
+                    "Abort insertion of table {} failure: {:?}",
+                    table_info.desc, cause
+                );
+                continue;
+            }
+        };
+        if let Err(cause) = table
+            .abort_insertion(ctx.clone(), insertion.operations)
+            .await
+        {
+            warn!(
+                "Abort insertion of table {} failure: {:?}",
+                table_info.desc, cause
+            );
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_sql::executor::ExpressionBuilderWithoutRenaming;
+use common_sql::plans::UpdatePlan;
+use common_sql::ScalarExpr;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// interprets UpdatePlan
+pub struct UpdateInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: UpdatePlan,
+}
+
+impl UpdateInterpreter {
+    /// Create the UpdateInterpreter from UpdatePlan
+    pub fn try_create(ctx: Arc<QueryContext>, plan: UpdatePlan) -> Result<Self> {
+        Ok(UpdateInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for UpdateInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "UpdateInterpreter"
+    }
+
+    /// Get the schema of UpdatePlan
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "update_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let catalog_name = self.plan.catalog.as_str();
+        let db_name = self.plan.database.as_str();
+        let tbl_name = self.plan.table.as_str();
+        let tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+
+        let builder = ExpressionBuilderWithoutRenaming::create(self.plan.metadata.clone());
+        let (filter, col_indices) = if let Some(scalar) = &self.plan.selection {
+            let filter = builder.build(scalar)?;
+            let col_indices = scalar.used_columns().into_iter().collect();
+            (Some(filter), col_indices)
+        } else {
+            (None, vec![])
+        };
+
+        let update_list = self
+            .plan
+            .update_list
+            .iter()
+            .map(|(idx, scalar)| Ok((*idx, builder.build(scalar)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        tbl.update(self.ctx.clone(), filter, col_indices, update_list)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
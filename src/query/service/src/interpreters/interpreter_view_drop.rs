@@ -12,13 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::TableNameIdent;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
 use common_sql::plans::DropViewPlan;
+use common_sql::MaterializedView;
+use common_sql::NameResolutionContext;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_PREFIX;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_QUERY;
 use common_storages_view::view_table::VIEW_ENGINE;
 
 use crate::interpreters::Interpreter;
@@ -53,8 +60,14 @@ impl Interpreter for DropViewInterpreter {
             .await
             .ok();
 
+        let materialized_view_query = tbl.as_ref().and_then(|table| {
+            table
+                .options()
+                .get(OPT_KEY_MATERIALIZED_VIEW_QUERY)
+                .cloned()
+        });
         if let Some(table) = &tbl {
-            if table.get_table_info().engine() != VIEW_ENGINE {
+            if table.get_table_info().engine() != VIEW_ENGINE && materialized_view_query.is_none() {
                 return Err(ErrorCode::Internal(format!(
                     "{}.{} is not VIEW, please use `DROP TABLE {}.{}`",
                     &self.plan.database,
@@ -76,6 +89,37 @@ impl Interpreter for DropViewInterpreter {
         };
         catalog.drop_table(plan).await?;
 
+        // Unregister the materialized view from its base table.
+        if let Some(query) = materialized_view_query {
+            let settings = self.ctx.get_settings();
+            let name_resolution_ctx = NameResolutionContext::try_from(settings.as_ref())?;
+            let materialized_view = MaterializedView::parse(&query, &name_resolution_ctx)?;
+            let base_database = materialized_view
+                .database
+                .unwrap_or_else(|| self.plan.database.clone());
+            if let Ok(base_table) = catalog
+                .get_table(&self.plan.tenant, &base_database, &materialized_view.table)
+                .await
+            {
+                let req = UpsertTableOptionReq {
+                    table_id: base_table.get_id(),
+                    seq: MatchSeq::Any,
+                    options: HashMap::from([(
+                        format!(
+                            "{}{}.{}",
+                            OPT_KEY_MATERIALIZED_VIEW_PREFIX,
+                            self.plan.database,
+                            self.plan.viewname
+                        ),
+                        None,
+                    )]),
+                };
+                catalog
+                    .upsert_table_option(&self.plan.tenant, &base_database, req)
+                    .await?;
+            }
+        }
+
         Ok(PipelineBuildResult::create())
     }
 }
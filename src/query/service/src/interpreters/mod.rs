@@ -31,9 +31,12 @@ mod interpreter_database_undrop;
 mod interpreter_delete;
 mod interpreter_explain_v2;
 mod interpreter_factory;
+mod interpreter_index_create;
 mod interpreter_insert_v2;
 mod interpreter_kill;
 mod interpreter_list;
+mod interpreter_materialized_view_create;
+mod interpreter_merge_into;
 mod interpreter_presign;
 mod interpreter_privilege_grant;
 mod interpreter_privilege_revoke;
@@ -56,16 +59,25 @@ mod interpreter_share_show;
 mod interpreter_share_show_grant_tenants;
 mod interpreter_show_grants;
 mod interpreter_show_object_grant_privileges;
+mod interpreter_stream_create;
+mod interpreter_stream_drop;
+mod interpreter_table_alter_column;
+mod interpreter_table_analyze;
 mod interpreter_table_create_v2;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
 mod interpreter_table_exists;
+mod interpreter_table_flashback;
 mod interpreter_table_optimize;
 mod interpreter_table_recluster;
 mod interpreter_table_rename;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
 mod interpreter_table_undrop;
+mod interpreter_txn_begin;
+mod interpreter_txn_commit;
+mod interpreter_txn_rollback;
+mod interpreter_update;
 mod interpreter_use_database;
 mod interpreter_user_alter;
 mod interpreter_user_create;
@@ -100,9 +112,12 @@ pub use interpreter_database_undrop::UndropDatabaseInterpreter;
 pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_explain_v2::ExplainInterpreterV2;
 pub use interpreter_factory::InterpreterFactory;
+pub use interpreter_index_create::CreateIndexInterpreter;
 pub use interpreter_insert_v2::InsertInterpreterV2;
 pub use interpreter_kill::KillInterpreter;
 pub use interpreter_list::ListInterpreter;
+pub use interpreter_materialized_view_create::CreateMaterializedViewInterpreter;
+pub use interpreter_merge_into::MergeIntoInterpreter;
 pub use interpreter_privilege_grant::GrantPrivilegeInterpreter;
 pub use interpreter_privilege_revoke::RevokePrivilegeInterpreter;
 pub use interpreter_query_log::InterpreterQueryLog;
@@ -122,16 +137,26 @@ pub use interpreter_share_show::ShowSharesInterpreter;
 pub use interpreter_share_show_grant_tenants::ShowGrantTenantsOfShareInterpreter;
 pub use interpreter_show_grants::ShowGrantsInterpreter;
 pub use interpreter_show_object_grant_privileges::ShowObjectGrantPrivilegesInterpreter;
+pub use interpreter_stream_create::CreateStreamInterpreter;
+pub use interpreter_stream_drop::DropStreamInterpreter;
+pub use interpreter_table_alter_column::AlterTableColumnInterpreter;
+pub use interpreter_table_analyze::AnalyzeTableInterpreter;
 pub use interpreter_table_create_v2::CreateTableInterpreterV2;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_table_exists::ExistsTableInterpreter;
+pub use interpreter_table_flashback::FlashbackTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
 pub use interpreter_table_recluster::ReclusterTableInterpreter;
 pub use interpreter_table_rename::RenameTableInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
 pub use interpreter_table_undrop::UndropTableInterpreter;
+pub use interpreter_txn_begin::BeginInterpreter;
+pub use interpreter_txn_commit::CommitInterpreter;
+pub use interpreter_txn_rollback::abort_insertions;
+pub use interpreter_txn_rollback::RollbackInterpreter;
+pub use interpreter_update::UpdateInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
 pub use interpreter_user_alter::AlterUserInterpreter;
 pub use interpreter_user_create::CreateUserInterpreter;
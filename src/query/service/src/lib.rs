@@ -41,8 +41,13 @@ pub mod sessions;
 pub mod stream;
 pub mod table_functions;
 
+mod auto_compactor;
+mod background_task;
+mod data_retention_purger;
 mod global_services;
 
+pub use auto_compactor::AutoCompactor;
 pub use common_sql as sql;
 pub use common_storages_factory as storages;
+pub use data_retention_purger::DataRetentionPurger;
 pub use global_services::GlobalServices;
@@ -12,20 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
 use std::time::Duration;
 
+use common_base::base::RuntimeTracker;
 use common_exception::Result;
-use common_settings::Settings;
+
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
 
 pub struct ExecutorSettings {
     pub max_execute_time: Duration,
+    /// The executor threads are tracked by the memory tracker of the query.
+    pub runtime_tracker: Arc<RuntimeTracker>,
 }
 
 impl ExecutorSettings {
-    pub fn try_create(settings: &Settings) -> Result<ExecutorSettings> {
-        let max_execute_time = settings.get_max_execute_time()?;
+    pub fn try_create(ctx: &QueryContext) -> Result<ExecutorSettings> {
+        let max_execute_time = ctx.get_settings().get_max_execute_time()?;
         Ok(ExecutorSettings {
             max_execute_time: Duration::from_millis(max_execute_time),
+            runtime_tracker: ctx.get_runtime_tracker(),
         })
     }
 }
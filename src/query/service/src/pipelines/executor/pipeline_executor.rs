@@ -25,6 +25,7 @@ use common_base::base::ThreadJoinHandle;
 use common_base::base::TrySpawn;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_io::prelude::convert_byte_size;
 use futures::future::select;
 use futures_util::future::Either;
 use parking_lot::Mutex;
@@ -250,31 +251,36 @@ impl PipelineExecutor {
                 }
             }
 
-            thread_join_handles.push(Thread::named_spawn(name, move || unsafe {
-                let this_clone = this.clone();
-                let try_result = catch_unwind(move || -> Result<()> {
-                    match this_clone.execute_single_thread(thread_num) {
-                        Ok(_) => Ok(()),
-                        Err(cause) => {
-                            if tracing::enabled!(tracing::Level::TRACE) {
-                                Err(cause.add_message_back(format!(
-                                    " (while in processor thread {})",
-                                    thread_num
-                                )))
-                            } else {
-                                Err(cause)
+            let runtime_tracker = Some(self.settings.runtime_tracker.clone());
+            thread_join_handles.push(Thread::named_spawn_with_tracker(
+                name,
+                runtime_tracker,
+                move || unsafe {
+                    let this_clone = this.clone();
+                    let try_result = catch_unwind(move || -> Result<()> {
+                        match this_clone.execute_single_thread(thread_num) {
+                            Ok(_) => Ok(()),
+                            Err(cause) => {
+                                if tracing::enabled!(tracing::Level::TRACE) {
+                                    Err(cause.add_message_back(format!(
+                                        " (while in processor thread {})",
+                                        thread_num
+                                    )))
+                                } else {
+                                    Err(cause)
+                                }
                             }
                         }
-                    }
-                });
+                    });
 
-                // finish the pipeline executor when has error or panic
-                if let Err(cause) = try_result.flatten() {
-                    this.finish(Some(cause));
-                }
+                    // finish the pipeline executor when has error or panic
+                    if let Err(cause) = try_result.flatten() {
+                        this.finish(Some(cause));
+                    }
 
-                Ok(())
-            }));
+                    Ok(())
+                },
+            ));
         }
         thread_join_handles
     }
@@ -298,11 +304,26 @@ impl PipelineExecutor {
                     let schedule_queue = self.graph.schedule_queue(executed_pid)?;
                     schedule_queue.schedule(&self.global_tasks_queue, &mut context);
                 }
+
+                self.check_memory_limit()?;
             }
         }
 
         Ok(())
     }
+
+    // The allocator only records the exceeded limit, the query is aborted here.
+    fn check_memory_limit(&self) -> Result<()> {
+        match self.settings.runtime_tracker.get_out_of_limit() {
+            None => Ok(()),
+            Some(out_of_limit) => Err(ErrorCode::MemoryLimitExceeded(format!(
+                "Memory limit exceeded: {} used, {} is {}",
+                convert_byte_size(out_of_limit.memory_usage as f64),
+                out_of_limit.limit_name,
+                convert_byte_size(out_of_limit.limit as f64),
+            ))),
+        }
+    }
 }
 
 impl Drop for PipelineExecutor {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 use async_channel::Receiver;
@@ -38,7 +39,12 @@ use common_sql::executor::AggregateFunctionDesc;
 use common_sql::executor::PhysicalScalar;
 
 use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::transforms::GraceHashJoinState;
 use crate::pipelines::processors::transforms::HashJoinDesc;
+use crate::pipelines::processors::transforms::RecursiveCteScanSource;
+use crate::pipelines::processors::transforms::RecursiveCteState;
+use crate::pipelines::processors::transforms::RecursiveCteStates;
+use crate::pipelines::processors::transforms::RecursiveUnionSource;
 use crate::pipelines::processors::transforms::RightSemiAntiJoinCompactor;
 use crate::pipelines::processors::transforms::TransformLeftJoin;
 use crate::pipelines::processors::transforms::TransformMarkJoin;
@@ -47,6 +53,7 @@ use crate::pipelines::processors::transforms::TransformRightJoin;
 use crate::pipelines::processors::transforms::TransformRightSemiAntiJoin;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::AggregatorTransformParams;
+use crate::pipelines::processors::HashJoinState;
 use crate::pipelines::processors::JoinHashTable;
 use crate::pipelines::processors::LeftJoinCompactor;
 use crate::pipelines::processors::MarkJoinCompactor;
@@ -61,6 +68,9 @@ use crate::pipelines::processors::TransformHashJoinProbe;
 use crate::pipelines::processors::TransformLimit;
 use crate::pipelines::processors::TransformSortMerge;
 use crate::pipelines::processors::TransformSortPartial;
+use crate::pipelines::processors::TransformSortSpill;
+use crate::pipelines::processors::TransformWindow;
+use crate::pipelines::processors::WindowCompactor;
 use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -78,9 +88,12 @@ use crate::sql::executor::HashJoin;
 use crate::sql::executor::Limit;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::Project;
+use crate::sql::executor::RecursiveCteScan;
+use crate::sql::executor::RecursiveUnion;
 use crate::sql::executor::Sort;
 use crate::sql::executor::TableScan;
 use crate::sql::executor::UnionAll;
+use crate::sql::executor::Window;
 use crate::sql::plans::JoinType;
 use crate::sql::ColumnBinding;
 
@@ -88,6 +101,8 @@ pub struct PipelineBuilder {
     ctx: Arc<QueryContext>,
     main_pipeline: Pipeline,
     pub pipelines: Vec<Pipeline>,
+    // Working tables of the recursive CTEs being evaluated
+    recursive_cte_states: RecursiveCteStates,
 }
 
 impl PipelineBuilder {
@@ -96,9 +111,15 @@ impl PipelineBuilder {
             ctx,
             pipelines: vec![],
             main_pipeline: Pipeline::create(),
+            recursive_cte_states: RecursiveCteStates::new(),
         }
     }
 
+    pub fn with_recursive_cte_states(mut self, states: RecursiveCteStates) -> PipelineBuilder {
+        self.recursive_cte_states = states;
+        self
+    }
+
     pub fn finalize(mut self, plan: &PhysicalPlan) -> Result<PipelineBuildResult> {
         self.build_pipeline(plan)?;
 
@@ -126,10 +147,13 @@ impl PipelineBuilder {
             PhysicalPlan::AggregateFinal(aggregate) => self.build_aggregate_final(aggregate),
             PhysicalPlan::Sort(sort) => self.build_sort(sort),
             PhysicalPlan::Limit(limit) => self.build_limit(limit),
+            PhysicalPlan::Window(window) => self.build_window(window),
             PhysicalPlan::HashJoin(join) => self.build_join(join),
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::RecursiveUnion(union) => self.build_recursive_union(union),
+            PhysicalPlan::RecursiveCteScan(scan) => self.build_recursive_cte_scan(scan),
             PhysicalPlan::DistributedInsertSelect(insert_select) => {
                 self.build_distributed_insert_select(insert_select)
             }
@@ -145,23 +169,34 @@ impl PipelineBuilder {
         self.build_join_probe(join, state)
     }
 
-    fn build_join_state(&mut self, join: &HashJoin) -> Result<Arc<JoinHashTable>> {
-        JoinHashTable::create_join_state(
+    fn build_join_state(&mut self, join: &HashJoin) -> Result<Arc<dyn HashJoinState>> {
+        let settings = self.ctx.get_settings();
+        let spill_bytes_threshold = settings.get_join_spill_bytes_threshold()? as usize;
+        if spill_bytes_threshold > 0 && !join.build_keys.is_empty() {
+            return Ok(GraceHashJoinState::create(
+                self.ctx.clone(),
+                join,
+                spill_bytes_threshold,
+            )?);
+        }
+
+        Ok(JoinHashTable::create_join_state(
             self.ctx.clone(),
             &join.build_keys,
             join.build.output_schema()?,
             join.probe.output_schema()?,
             HashJoinDesc::create(join)?,
-        )
+        )?)
     }
 
     fn expand_build_side_pipeline(
         &mut self,
         build: &PhysicalPlan,
-        join_state: Arc<JoinHashTable>,
+        join_state: Arc<dyn HashJoinState>,
     ) -> Result<()> {
         let build_side_context = QueryContext::create_from(self.ctx.clone());
-        let build_side_builder = PipelineBuilder::create(build_side_context);
+        let build_side_builder = PipelineBuilder::create(build_side_context)
+            .with_recursive_cte_states(self.recursive_cte_states.clone());
         let mut build_res = build_side_builder.finalize(build)?;
 
         assert!(build_res.main_pipeline.is_pulling_pipeline()?);
@@ -433,31 +468,96 @@ impl PipelineBuilder {
             })
             .collect();
 
-        let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
         // Sort
         self.main_pipeline.add_transform(|input, output| {
             TransformSortPartial::try_create(input, output, sort.limit, sort_desc.clone())
         })?;
 
+        self.build_sort_merge(sort.output_schema()?, sort.limit, sort_desc)
+    }
+
+    fn build_sort_merge(
+        &mut self,
+        output_schema: DataSchemaRef,
+        limit: Option<usize>,
+        sort_desc: Vec<SortColumnDescription>,
+    ) -> Result<()> {
+        let settings = self.ctx.get_settings();
+        let block_size = settings.get_max_block_size()? as usize;
+
         // Merge
-        self.main_pipeline.add_transform(|input, output| {
-            TransformSortMerge::try_create(
-                input,
-                output,
-                SortMergeCompactor::new(block_size, sort.limit, sort_desc.clone()),
-            )
-        })?;
+        if settings.get_sort_spill_bytes_threshold()? > 0 {
+            let total_memory_size = Arc::new(AtomicUsize::new(0));
+            self.main_pipeline.add_transform(|input, output| {
+                TransformSortSpill::try_create(
+                    self.ctx.clone(),
+                    input,
+                    output,
+                    output_schema.clone(),
+                    limit,
+                    sort_desc.clone(),
+                    total_memory_size.clone(),
+                )
+            })?;
+        } else {
+            self.main_pipeline.add_transform(|input, output| {
+                TransformSortMerge::try_create(
+                    input,
+                    output,
+                    SortMergeCompactor::new(block_size, limit, sort_desc.clone()),
+                )
+            })?;
+        }
 
         // Concat merge in single thread
         try_add_multi_sort_merge(
             &mut self.main_pipeline,
-            sort.output_schema()?,
+            output_schema,
             block_size,
-            sort.limit,
+            limit,
             sort_desc,
         )
     }
 
+    fn build_window(&mut self, window: &Window) -> Result<()> {
+        self.build_pipeline(&window.input)?;
+        let input_schema = window.input.output_schema()?;
+
+        // Sort the input by partition keys and order keys, so the rows of a
+        // partition are adjacent and ordered.
+        let mut sort_desc: Vec<SortColumnDescription> = window
+            .partition_by
+            .iter()
+            .map(|column_id| SortColumnDescription {
+                column_name: column_id.clone(),
+                asc: true,
+                nulls_first: true,
+            })
+            .collect();
+        sort_desc.extend(window.order_by.iter().map(|desc| SortColumnDescription {
+            column_name: desc.order_by.clone(),
+            asc: desc.asc,
+            nulls_first: desc.nulls_first,
+        }));
+
+        if sort_desc.is_empty() {
+            self.main_pipeline.resize(1)?;
+        } else {
+            self.main_pipeline.add_transform(|input, output| {
+                TransformSortPartial::try_create(input, output, None, sort_desc.clone())
+            })?;
+            self.build_sort_merge(input_schema.clone(), None, sort_desc)?;
+        }
+
+        self.main_pipeline.add_transform(|input, output| {
+            TransformWindow::try_create(
+                input,
+                output,
+                WindowCompactor::try_create(window, input_schema.clone())?,
+            )
+        })
+    }
+
     fn build_limit(&mut self, limit: &Limit) -> Result<()> {
         self.build_pipeline(&limit.input)?;
 
@@ -467,7 +567,7 @@ impl PipelineBuilder {
         })
     }
 
-    fn build_join_probe(&mut self, join: &HashJoin, state: Arc<JoinHashTable>) -> Result<()> {
+    fn build_join_probe(&mut self, join: &HashJoin, state: Arc<dyn HashJoinState>) -> Result<()> {
         self.build_pipeline(&join.probe)?;
 
         self.main_pipeline.add_transform(|input, output| {
@@ -551,7 +651,8 @@ impl PipelineBuilder {
 
     fn expand_union_all(&mut self, plan: &PhysicalPlan) -> Result<Receiver<DataBlock>> {
         let union_ctx = QueryContext::create_from(self.ctx.clone());
-        let pipeline_builder = PipelineBuilder::create(union_ctx);
+        let pipeline_builder = PipelineBuilder::create(union_ctx)
+            .with_recursive_cte_states(self.recursive_cte_states.clone());
         let mut build_res = pipeline_builder.finalize(plan)?;
 
         assert!(build_res.main_pipeline.is_pulling_pipeline()?);
@@ -594,6 +695,44 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_recursive_union(&mut self, union: &RecursiveUnion) -> Result<()> {
+        let ctx = self.ctx.clone();
+        let state = RecursiveCteState::create();
+        let mut cte_states = self.recursive_cte_states.clone();
+        cte_states.insert(union.cte_name.clone(), state.clone());
+
+        self.main_pipeline.add_source(
+            |output| {
+                RecursiveUnionSource::create(
+                    ctx.clone(),
+                    output,
+                    union.clone(),
+                    state.clone(),
+                    cte_states.clone(),
+                )
+            },
+            1,
+        )
+    }
+
+    fn build_recursive_cte_scan(&mut self, scan: &RecursiveCteScan) -> Result<()> {
+        let state = self
+            .recursive_cte_states
+            .get(&scan.cte_name)
+            .cloned()
+            .ok_or_else(|| {
+                ErrorCode::Internal(format!(
+                    "Working table of recursive query {} is not found",
+                    scan.cte_name
+                ))
+            })?;
+        let ctx = self.ctx.clone();
+        self.main_pipeline.add_source(
+            |output| RecursiveCteScanSource::create(ctx.clone(), output, state.clone()),
+            1,
+        )
+    }
+
     pub fn build_distributed_insert_select(
         &mut self,
         insert_select: &DistributedInsertSelect,
@@ -56,3 +56,6 @@ pub use transforms::TransformHashJoinProbe;
 pub use transforms::TransformLimit;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformSortSpill;
+pub use transforms::TransformWindow;
+pub use transforms::WindowCompactor;
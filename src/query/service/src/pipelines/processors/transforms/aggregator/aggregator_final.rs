@@ -25,6 +25,10 @@ use common_datablocks::HashMethodKeysU512;
 use common_datablocks::HashMethodKeysU64;
 use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_datavalues::MutableColumn;
 use common_datavalues::ScalarColumn;
@@ -34,6 +38,8 @@ use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use crate::pipelines::processors::transforms::aggregator::aggregator_spill::scatter_states;
+use crate::pipelines::processors::transforms::aggregator::aggregator_spill::serialize_states;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
@@ -41,6 +47,7 @@ use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntityMutRef;
 use crate::pipelines::processors::transforms::group_by::StateEntityRef;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
+use crate::pipelines::processors::transforms::Spiller;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -72,6 +79,10 @@ pub struct FinalAggregator<
     // used for deserialization only, so we can reuse it during the loop
     temp_place: Option<StateAddr>,
     ctx: Arc<QueryContext>,
+
+    // Spill the states to storage once they take more memory than the threshold, 0 means never.
+    spill_bytes_threshold: usize,
+    spiller: Option<Spiller>,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
@@ -88,6 +99,8 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         } else {
             state.alloc_layout(&params)
         };
+        let spill_bytes_threshold =
+            ctx.get_settings().get_group_by_spill_bytes_threshold()? as usize;
 
         Ok(Self {
             is_generated: false,
@@ -97,8 +110,92 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             params,
             temp_place,
             ctx,
+            spill_bytes_threshold,
+            spiller: None,
         })
     }
+
+    fn try_spill_states(&mut self) -> Result<()> {
+        if self.spill_bytes_threshold == 0
+            || self.state.len() == 0
+            || self.state.allocated_bytes() < self.spill_bytes_threshold
+        {
+            return Ok(());
+        }
+
+        self.spill_states()
+    }
+
+    /// Spill the states to storage, partitioned by the two-level bucket of the group keys,
+    /// and go on with an empty hash table.
+    fn spill_states(&mut self) -> Result<()> {
+        if self.state.len() == 0 {
+            return Ok(());
+        }
+
+        let columns = serialize_states(&self.method, &self.state, &self.params)?;
+        let schema = Self::spill_schema(&self.params, &columns);
+        let block = DataBlock::create(schema.clone(), columns);
+        let partitions = scatter_states(&self.method, &self.state, &block)?;
+
+        self.reset_states();
+
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(&self.ctx, "aggregate_final", schema)?);
+        }
+
+        let spiller = self.spiller.as_mut().unwrap();
+        for (partition, block) in partitions.into_iter().enumerate() {
+            spiller.spill(partition, vec![block])?;
+        }
+
+        Ok(())
+    }
+
+    /// Take the blocks of the next spilled partition, to be merged into an empty hash table.
+    ///
+    /// The states in memory are spilled before restoring the first partition, so all the states
+    /// of a group key are in the same partition.
+    fn restore_next_partition(&mut self) -> Result<Option<Vec<DataBlock>>> {
+        if !self.is_generated {
+            self.is_generated = true;
+            self.spill_states()?;
+        }
+
+        let blocks = match self.spiller.as_mut() {
+            None => return Ok(None),
+            Some(spiller) => match spiller.partitions().first() {
+                None => return Ok(None),
+                Some(partition) => spiller.restore(*partition)?,
+            },
+        };
+
+        self.reset_states();
+        Ok(Some(blocks))
+    }
+
+    fn reset_states(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.temp_place = match self.params.aggregate_functions.is_empty() {
+            true => None,
+            false => self.state.alloc_layout(&self.params),
+        };
+        self.states_dropped = false;
+    }
+
+    fn spill_schema(params: &AggregatorParams, columns: &[ColumnRef]) -> DataSchemaRef {
+        let mut fields = params
+            .aggregate_functions_column_name
+            .iter()
+            .zip(columns.iter())
+            .map(|(name, column)| DataField::new(name, column.data_type()))
+            .collect::<Vec<_>>();
+
+        let keys_column = &columns[columns.len() - 1];
+        fields.push(DataField::new("_group_by_key", keys_column.data_type()));
+        DataSchemaRefExt::create(fields)
+    }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<true, Method> {
@@ -131,14 +228,8 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<
         }
         places
     }
-}
 
-impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
-    for FinalAggregator<true, Method>
-{
-    const NAME: &'static str = "GroupByFinalTransform";
-
-    fn consume(&mut self, block: DataBlock) -> Result<()> {
+    fn merge_states(&mut self, block: &DataBlock) -> Result<()> {
         // 1.1 and 1.2.
         let aggregate_function_len = self.params.aggregate_functions.len();
         let keys_column = block.column(aggregate_function_len);
@@ -180,67 +271,93 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
-            true => {
-                self.drop_states();
-                Ok(None)
-            }
-            false => {
-                self.is_generated = true;
-                let mut group_columns_builder = self
-                    .method
-                    .group_columns_builder(self.state.len(), &self.params);
-
-                let aggregate_functions = &self.params.aggregate_functions;
-                let offsets_aggregate_states = &self.params.offsets_aggregate_states;
-
-                let mut aggregates_column_builder: Vec<Box<dyn MutableColumn>> = {
-                    let mut values = vec![];
-                    for aggregate_function in aggregate_functions {
-                        let builder = aggregate_function.return_type()?.create_mutable(1024);
-                        values.push(builder)
-                    }
-                    values
-                };
+    fn finalize_states(&mut self) -> Result<DataBlock> {
+        let mut group_columns_builder = self
+            .method
+            .group_columns_builder(self.state.len(), &self.params);
 
-                for group_entity in self.state.iter() {
-                    let place: StateAddr = group_entity.get_state_value().into();
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
 
-                    for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
-                        let arg_place = place.next(offsets_aggregate_states[idx]);
-                        let builder: &mut dyn MutableColumn =
-                            aggregates_column_builder[idx].borrow_mut();
-                        aggregate_function.merge_result(arg_place, builder)?;
-                    }
+        let mut aggregates_column_builder: Vec<Box<dyn MutableColumn>> = {
+            let mut values = vec![];
+            for aggregate_function in aggregate_functions {
+                let builder = aggregate_function.return_type()?.create_mutable(1024);
+                values.push(builder)
+            }
+            values
+        };
 
-                    group_columns_builder.append_value(group_entity.get_state_key());
-                }
+        for group_entity in self.state.iter() {
+            let place: StateAddr = group_entity.get_state_value().into();
 
-                // Build final state block.
-                let fields_len = self.params.output_schema.fields().len();
-                let mut columns = Vec::with_capacity(fields_len);
+            for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                let arg_place = place.next(offsets_aggregate_states[idx]);
+                let builder: &mut dyn MutableColumn = aggregates_column_builder[idx].borrow_mut();
+                aggregate_function.merge_result(arg_place, builder)?;
+            }
 
-                for mut array in aggregates_column_builder {
-                    columns.push(array.to_column());
-                }
+            group_columns_builder.append_value(group_entity.get_state_key());
+        }
 
-                columns.extend_from_slice(&group_columns_builder.finish()?);
-                Ok(Some(DataBlock::create(
-                    self.params.output_schema.clone(),
-                    columns,
-                )))
-            }
+        // Build final state block.
+        let fields_len = self.params.output_schema.fields().len();
+        let mut columns = Vec::with_capacity(fields_len);
+
+        for mut array in aggregates_column_builder {
+            columns.push(array.to_column());
         }
+
+        columns.extend_from_slice(&group_columns_builder.finish()?);
+        Ok(DataBlock::create(
+            self.params.output_schema.clone(),
+            columns,
+        ))
     }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
-    for FinalAggregator<false, Method>
+    for FinalAggregator<true, Method>
 {
     const NAME: &'static str = "GroupByFinalTransform";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
+        self.merge_states(&block)?;
+        self.try_spill_states()
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.spiller.is_some() {
+            return match self.restore_next_partition()? {
+                None => {
+                    self.drop_states();
+                    Ok(None)
+                }
+                Some(blocks) => {
+                    for block in blocks.iter() {
+                        self.merge_states(block)?;
+                    }
+
+                    Ok(Some(self.finalize_states()?))
+                }
+            };
+        }
+
+        match self.state.len() == 0 || self.is_generated {
+            true => {
+                self.drop_states();
+                Ok(None)
+            }
+            false => {
+                self.is_generated = true;
+                Ok(Some(self.finalize_states()?))
+            }
+        }
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<false, Method> {
+    fn merge_keys(&mut self, block: &DataBlock) -> Result<()> {
         let key_array = block.column(0);
         let keys_iter = self.method.keys_iter_from_column(key_array)?;
 
@@ -258,23 +375,51 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
+    fn finalize_keys(&mut self) -> Result<DataBlock> {
+        let mut columns_builder = self
+            .method
+            .group_columns_builder(self.state.len(), &self.params);
+        for group_entity in self.state.iter() {
+            columns_builder.append_value(group_entity.get_state_key());
+        }
+
+        let columns = columns_builder.finish()?;
+        Ok(DataBlock::create(
+            self.params.output_schema.clone(),
+            columns,
+        ))
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
+    for FinalAggregator<false, Method>
+{
+    const NAME: &'static str = "GroupByFinalTransform";
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        self.merge_keys(&block)?;
+        self.try_spill_states()
+    }
+
     fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.spiller.is_some() {
+            return match self.restore_next_partition()? {
+                None => Ok(None),
+                Some(blocks) => {
+                    for block in blocks.iter() {
+                        self.merge_keys(block)?;
+                    }
+
+                    Ok(Some(self.finalize_keys()?))
+                }
+            };
+        }
+
         match self.state.len() == 0 || self.is_generated {
             true => Ok(None),
             false => {
                 self.is_generated = true;
-                let mut columns_builder = self
-                    .method
-                    .group_columns_builder(self.state.len(), &self.params);
-                for group_entity in self.state.iter() {
-                    columns_builder.append_value(group_entity.get_state_key());
-                }
-
-                let columns = columns_builder.finish()?;
-                Ok(Some(DataBlock::create(
-                    self.params.output_schema.clone(),
-                    columns,
-                )))
+                Ok(Some(self.finalize_keys()?))
             }
         }
     }
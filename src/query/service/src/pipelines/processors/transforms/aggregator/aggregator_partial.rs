@@ -25,13 +25,11 @@ use common_datablocks::HashMethodKeysU64;
 use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
 use common_datavalues::ColumnRef;
-use common_datavalues::MutableColumn;
-use common_datavalues::MutableStringColumn;
-use common_datavalues::ScalarColumnBuilder;
 use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use crate::pipelines::processors::transforms::aggregator::aggregator_spill::serialize_states;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
@@ -73,21 +71,34 @@ pub struct PartialAggregator<
     state: Method::State,
     params: Arc<AggregatorParams>,
     ctx: Arc<QueryContext>,
+
+    // Flush the states downstream once they take more memory than the threshold, 0 means never.
+    // The final aggregator merges the states of the same group from the flushed blocks.
+    spill_bytes_threshold: usize,
+    flushed_block: Option<DataBlock>,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     PartialAggregator<HAS_AGG, Method>
 {
-    pub fn create(ctx: Arc<QueryContext>, method: Method, params: Arc<AggregatorParams>) -> Self {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        method: Method,
+        params: Arc<AggregatorParams>,
+    ) -> Result<Self> {
         let state = method.aggregate_state();
-        Self {
+        let spill_bytes_threshold =
+            ctx.get_settings().get_group_by_spill_bytes_threshold()? as usize;
+        Ok(Self {
             is_generated: false,
             states_dropped: false,
             state,
             method,
             params,
             ctx,
-        }
+            spill_bytes_threshold,
+            flushed_block: None,
+        })
     }
 
     #[inline(always)]
@@ -191,38 +202,44 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         }
 
         self.is_generated = true;
-        let state_groups_len = self.state.len();
-        let aggregator_params = self.params.as_ref();
-        let funcs = &aggregator_params.aggregate_functions;
-        let aggr_len = funcs.len();
-        let offsets_aggregate_states = &aggregator_params.offsets_aggregate_states;
-
-        // Builders.
-        let mut state_builders: Vec<MutableStringColumn> = (0..aggr_len)
-            .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
-            .collect();
-
-        let mut group_key_builder = self.method.keys_column_builder(state_groups_len);
-        for group_entity in self.state.iter() {
-            let place: StateAddr = group_entity.get_state_value().into();
-
-            for (idx, func) in funcs.iter().enumerate() {
-                let arg_place = place.next(offsets_aggregate_states[idx]);
-                func.serialize(arg_place, state_builders[idx].values_mut())?;
-                state_builders[idx].commit_row();
-            }
+        Ok(Some(self.serialize_block()?))
+    }
 
-            group_key_builder.append_value(group_entity.get_state_key());
-        }
+    /// The states of all groups, or the keys only if there are no aggregate functions.
+    fn serialize_block(&self) -> Result<DataBlock> {
+        let columns = match HAS_AGG {
+            true => serialize_states(&self.method, &self.state, &self.params)?,
+            false => {
+                let mut keys_column_builder = self.method.keys_column_builder(self.state.len());
+                for group_entity in self.state.iter() {
+                    keys_column_builder.append_value(group_entity.get_state_key());
+                }
+                vec![keys_column_builder.finish()]
+            }
+        };
+        Ok(DataBlock::create(
+            self.params.output_schema.clone(),
+            columns,
+        ))
+    }
 
-        let schema = &self.params.output_schema;
-        let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
-        for mut builder in state_builders {
-            columns.push(builder.to_column());
+    /// Flush the states downstream if they exceed the memory threshold, and go on with an
+    /// empty hash table. Spilling the partial states to storage gains nothing over sending
+    /// them to the final aggregator, which spills the merged states if needed.
+    fn try_flush_states(&mut self) -> Result<()> {
+        if self.spill_bytes_threshold == 0
+            || self.state.len() == 0
+            || self.state.allocated_bytes() < self.spill_bytes_threshold
+        {
+            return Ok(());
         }
 
-        columns.push(group_key_builder.finish());
-        Ok(Some(DataBlock::create(schema.clone(), columns)))
+        let block = self.serialize_block()?;
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.states_dropped = false;
+        self.flushed_block = Some(block);
+        Ok(())
     }
 }
 
@@ -247,7 +264,12 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         let places = Self::lookup_state(&self.params, group_keys_iter, &mut self.state);
-        Self::execute(&self.params, &block, &places)
+        Self::execute(&self.params, &block, &places)?;
+        self.try_flush_states()
+    }
+
+    fn take_flushed(&mut self) -> Option<DataBlock> {
+        self.flushed_block.take()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
@@ -276,7 +298,11 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         Self::lookup_key(group_keys_iter, &mut self.state);
-        Ok(())
+        self.try_flush_states()
+    }
+
+    fn take_flushed(&mut self) -> Option<DataBlock> {
+        self.flushed_block.take()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
@@ -285,19 +311,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                 self.drop_states();
                 Ok(None)
             }
-            false => {
-                self.is_generated = true;
-                let mut keys_column_builder = self.method.keys_column_builder(self.state.len());
-                for group_entity in self.state.iter() {
-                    keys_column_builder.append_value(group_entity.get_state_key());
-                }
-
-                let columns = keys_column_builder.finish();
-                Ok(Some(DataBlock::create(
-                    self.params.output_schema.clone(),
-                    vec![columns],
-                )))
-            }
+            false => self.generate_data(),
         }
     }
 }
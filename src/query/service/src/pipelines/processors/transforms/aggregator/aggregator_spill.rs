@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datavalues::ColumnRef;
+use common_datavalues::MutableColumn;
+use common_datavalues::MutableStringColumn;
+use common_datavalues::ScalarColumnBuilder;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_hashtable::twolevel_bucket_of;
+use common_hashtable::TWOLEVEL_BUCKETS;
+
+use crate::pipelines::processors::transforms::group_by::AggregatorState;
+use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
+use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
+use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::processors::transforms::group_by::StateEntityRef;
+use crate::pipelines::processors::AggregatorParams;
+
+/// The spilled aggregation states are split into partitions, every partition covers
+/// a contiguous range of the two-level buckets.
+pub const SPILL_PARTITIONS: usize = 16;
+
+/// Serialize the states of all groups, one binary column per aggregate function followed by
+/// the group keys column. It's the same layout as the output of the partial aggregator.
+pub fn serialize_states<Method>(
+    method: &Method,
+    state: &Method::State,
+    params: &AggregatorParams,
+) -> Result<Vec<ColumnRef>>
+where
+    Method: HashMethod + PolymorphicKeysHelper<Method>,
+{
+    let state_groups_len = state.len();
+    let funcs = &params.aggregate_functions;
+    let offsets_aggregate_states = &params.offsets_aggregate_states;
+
+    // Builders.
+    let mut state_builders: Vec<MutableStringColumn> = (0..funcs.len())
+        .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
+        .collect();
+
+    let mut group_key_builder = method.keys_column_builder(state_groups_len);
+    for group_entity in state.iter() {
+        let place: StateAddr = group_entity.get_state_value().into();
+
+        for (idx, func) in funcs.iter().enumerate() {
+            let arg_place = place.next(offsets_aggregate_states[idx]);
+            func.serialize(arg_place, state_builders[idx].values_mut())?;
+            state_builders[idx].commit_row();
+        }
+
+        group_key_builder.append_value(group_entity.get_state_key());
+    }
+
+    let mut columns: Vec<ColumnRef> = Vec::with_capacity(funcs.len() + 1);
+    for mut builder in state_builders {
+        columns.push(builder.to_column());
+    }
+
+    columns.push(group_key_builder.finish());
+    Ok(columns)
+}
+
+/// Scatter a block of serialized states into `SPILL_PARTITIONS` blocks by the two-level bucket
+/// of the group keys, which are in the last column of the block.
+pub fn scatter_states<Method>(
+    method: &Method,
+    state: &Method::State,
+    block: &DataBlock,
+) -> Result<Vec<DataBlock>>
+where
+    Method: HashMethod + PolymorphicKeysHelper<Method>,
+{
+    let keys_column = block.column(block.num_columns() - 1);
+    let keys_iter = method.keys_iter_from_column(keys_column)?;
+
+    let indices = keys_iter
+        .get_slice()
+        .iter()
+        .map(|key| twolevel_bucket_of(state.hash_key(*key)) * SPILL_PARTITIONS / TWOLEVEL_BUCKETS)
+        .collect::<Vec<_>>();
+
+    DataBlock::scatter_block(block, &indices, SPILL_PARTITIONS)
+}
@@ -16,6 +16,7 @@ mod aggregator_final;
 mod aggregator_params;
 mod aggregator_partial;
 mod aggregator_single_key;
+mod aggregator_spill;
 
 pub use aggregator_final::FinalAggregator;
 pub use aggregator_final::KeysU128FinalAggregator;
@@ -23,6 +23,7 @@ use common_datablocks::HashMethodFixedKeys;
 use common_datablocks::HashMethodSerializer;
 use common_datavalues::prelude::*;
 use common_functions::aggregates::StateAddr;
+use common_hashtable::FastHash;
 use common_hashtable::HashMapKind;
 use common_hashtable::HashMapKindIter;
 use common_hashtable::HashtableEntry;
@@ -92,6 +93,12 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
     }
 
     fn convert_to_twolevel(&mut self) {}
+
+    /// Approximate bytes of memory held by the hash table and the arena of the state.
+    fn allocated_bytes(&self) -> usize;
+
+    /// Hash of the key, the high bits of which decide the two-level bucket of the key.
+    fn hash_key<'a>(&self, key: Self::KeyRef<'a>) -> u64;
 }
 
 /// The fixed length array is used as the data structure to locate the key by subscript
@@ -212,6 +219,17 @@ where
     fn convert_to_twolevel(&mut self) {
         self.two_level_flag = true;
     }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes()
+            + self.max_size * std::mem::size_of::<ShortFixedKeysStateEntity<T>>()
+    }
+
+    #[inline(always)]
+    fn hash_key<'a>(&self, key: Self::KeyRef<'a>) -> u64 {
+        (key.lookup() as u64).fast_hash()
+    }
 }
 
 pub struct LongerFixedKeysAggregatorState<T: HashtableKeyable> {
@@ -301,6 +319,17 @@ where
         self.data.convert_to_twolevel();
         self.two_level_flag = true;
     }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes()
+            + self.data.capacity() * std::mem::size_of::<HashtableEntry<T, usize>>()
+    }
+
+    #[inline(always)]
+    fn hash_key<'a>(&self, key: Self::KeyRef<'a>) -> u64 {
+        <T as HashtableKeyable>::hash(&key)
+    }
 }
 
 pub struct SerializedKeysAggregatorState {
@@ -385,4 +414,14 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
         // self.data_state_map.convert_to_twolevel();
         self.two_level_flag = true;
     }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes() + self.data_state_map.allocated_bytes()
+    }
+
+    #[inline(always)]
+    fn hash_key<'a>(&self, key: Self::KeyRef<'a>) -> u64 {
+        key.fast_hash()
+    }
 }
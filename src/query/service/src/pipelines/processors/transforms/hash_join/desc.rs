@@ -92,6 +92,31 @@ impl HashJoinDesc {
         })
     }
 
+    /// Creates the description of an inner join out of a query plan, e.g. the join of MERGE INTO.
+    pub fn create_inner_join(
+        build_keys: &[PhysicalScalar],
+        probe_keys: &[PhysicalScalar],
+        non_equi_conditions: &[PhysicalScalar],
+    ) -> Result<HashJoinDesc> {
+        let predicate = Self::join_predicate(non_equi_conditions)?;
+
+        Ok(HashJoinDesc {
+            join_type: JoinType::Inner,
+            build_keys: Evaluator::eval_physical_scalars(build_keys)?,
+            probe_keys: Evaluator::eval_physical_scalars(probe_keys)?,
+            other_predicate: predicate
+                .as_ref()
+                .map(Evaluator::eval_physical_scalar)
+                .transpose()?,
+            marker_join_desc: MarkJoinDesc {
+                has_null: RwLock::new(false),
+                marker_index: None,
+            },
+            from_correlated_subquery: false,
+            join_state: JoinState::create()?,
+        })
+    }
+
     fn join_predicate(non_equi_conditions: &[PhysicalScalar]) -> Result<Option<PhysicalScalar>> {
         if non_equi_conditions.is_empty() {
             return Ok(None);
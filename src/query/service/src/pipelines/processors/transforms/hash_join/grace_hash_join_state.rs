@@ -0,0 +1,614 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+
+use common_base::base::tokio::sync::Notify;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::Series;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_hashtable::FastHash;
+
+use super::ProbeState;
+use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
+use crate::pipelines::processors::transforms::Spiller;
+use crate::pipelines::processors::HashJoinState;
+use crate::pipelines::processors::JoinHashTable;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::evaluator::EvalNode;
+use crate::sql::evaluator::Evaluator;
+use crate::sql::executor::HashJoin;
+use crate::sql::plans::JoinType;
+
+/// Both sides of a spilled hash join are split into partitions by the hash of join keys.
+/// A partition whose build side is still larger than the threshold is split again into
+/// `SPILL_PARTITIONS` partitions by the next bits of the hash.
+pub const SPILL_PARTITIONS: usize = 16;
+const SPILL_PARTITION_BITS: usize = 4;
+/// A partition split `MAX_SPILL_LEVEL` times is mostly made of duplicated keys, splitting it
+/// further helps little, so it's joined in memory however large it is.
+const MAX_SPILL_LEVEL: usize = 8;
+
+struct SpillBuffer {
+    blocks: Vec<DataBlock>,
+    memory_size: usize,
+    spiller: Spiller,
+}
+
+impl SpillBuffer {
+    fn create(spiller: Spiller) -> Self {
+        SpillBuffer {
+            blocks: vec![],
+            memory_size: 0,
+            spiller,
+        }
+    }
+
+    fn push(&mut self, block: DataBlock) {
+        self.memory_size += block.memory_size();
+        self.blocks.push(block);
+    }
+
+    fn take_blocks(&mut self) -> Vec<DataBlock> {
+        self.memory_size = 0;
+        std::mem::take(&mut self.blocks)
+    }
+}
+
+/// Partitioned (grace) hash join.
+///
+/// The build side goes to the in-memory `JoinHashTable` until it exceeds
+/// `join_spill_bytes_threshold`, then the blocks are taken back from the hash table, and both
+/// the build side and the probe side are hash partitioned and spilled to storage. After all the
+/// probe processors have finished, the partitions are joined by all of them in parallel, each
+/// with a dedicated `JoinHashTable`. A partition whose build side is still too large is split
+/// into smaller partitions first.
+///
+/// If the build side never exceeds the threshold, everything is delegated to the in-memory
+/// `JoinHashTable`.
+pub struct GraceHashJoinState {
+    ctx: Arc<QueryContext>,
+    join: HashJoin,
+    build_schema: DataSchemaRef,
+    probe_schema: DataSchemaRef,
+    build_keys: Vec<EvalNode>,
+    probe_keys: Vec<EvalNode>,
+    spill_bytes_threshold: usize,
+    in_memory: Arc<JoinHashTable>,
+
+    // Held for read while a block is built into the in-memory hash table, so that no block
+    // is lost when the built blocks are taken back to be spilled.
+    spilled: RwLock<bool>,
+    build_memory_size: AtomicUsize,
+    build_buffer: Mutex<SpillBuffer>,
+    probe_buffer: Mutex<SpillBuffer>,
+    /// Whether there are nulls in the build keys, used by right mark join.
+    build_has_null: AtomicBool,
+    /// Whether there are nulls in the subquery column, used by left mark join.
+    probe_has_null: AtomicBool,
+    /// The partitions to join, with their levels of splitting.
+    pending_partitions: Mutex<VecDeque<(usize, usize)>>,
+    next_partition: AtomicUsize,
+
+    ref_count: Mutex<usize>,
+    probe_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    is_probe_finished: Mutex<bool>,
+    interrupt: Arc<AtomicBool>,
+    finished_notify: Arc<Notify>,
+    probe_finished_notify: Arc<Notify>,
+}
+
+impl GraceHashJoinState {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        join: &HashJoin,
+        spill_bytes_threshold: usize,
+    ) -> Result<Arc<GraceHashJoinState>> {
+        let build_schema = join.build.output_schema()?;
+        let probe_schema = join.probe.output_schema()?;
+        let in_memory = JoinHashTable::create_join_state(
+            ctx.clone(),
+            &join.build_keys,
+            build_schema.clone(),
+            probe_schema.clone(),
+            HashJoinDesc::create(join)?,
+        )?;
+
+        let build_spiller = Spiller::create(&ctx, "join_build", build_schema.clone())?;
+        let probe_spiller = Spiller::create(&ctx, "join_probe", probe_schema.clone())?;
+
+        Ok(Arc::new(GraceHashJoinState {
+            build_keys: Evaluator::eval_physical_scalars(&join.build_keys)?,
+            probe_keys: Evaluator::eval_physical_scalars(&join.probe_keys)?,
+            join: join.clone(),
+            build_schema,
+            probe_schema,
+            spill_bytes_threshold,
+            in_memory,
+            spilled: RwLock::new(false),
+            build_memory_size: AtomicUsize::new(0),
+            build_buffer: Mutex::new(SpillBuffer::create(build_spiller)),
+            probe_buffer: Mutex::new(SpillBuffer::create(probe_spiller)),
+            build_has_null: AtomicBool::new(false),
+            probe_has_null: AtomicBool::new(false),
+            pending_partitions: Mutex::new(VecDeque::new()),
+            next_partition: AtomicUsize::new(SPILL_PARTITIONS),
+            ref_count: Mutex::new(0),
+            probe_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            is_probe_finished: Mutex::new(false),
+            interrupt: Arc::new(AtomicBool::new(false)),
+            finished_notify: Arc::new(Notify::new()),
+            probe_finished_notify: Arc::new(Notify::new()),
+            ctx,
+        }))
+    }
+
+    fn is_spilled(&self) -> bool {
+        *self.spilled.read().unwrap()
+    }
+
+    fn check_interrupt(&self) -> Result<()> {
+        if self.interrupt.load(Ordering::Relaxed) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn eval_keys(&self, keys: &[EvalNode], block: &DataBlock) -> Result<Vec<ColumnRef>> {
+        let func_ctx = self.ctx.try_get_function_context()?;
+        keys.iter()
+            .map(|expr| Ok(expr.eval(&func_ctx, block)?.vector().clone()))
+            .collect::<Result<Vec<ColumnRef>>>()
+    }
+
+    /// Scatter the block into `SPILL_PARTITIONS` blocks by the hash of the join keys, taking
+    /// the 4 bits of the hash of the `level`.
+    ///
+    /// The keys of both sides have been cast to the same type by the binder, but they may
+    /// differ in nullability, so the nullable wrapper is removed before hashing.
+    fn scatter(keys: &[ColumnRef], block: &DataBlock, level: usize) -> Result<Vec<DataBlock>> {
+        let keys = keys
+            .iter()
+            .map(|col| Series::remove_nullable(&col.convert_full_column()))
+            .collect::<Vec<_>>();
+        let keys = keys.iter().collect::<Vec<&ColumnRef>>();
+
+        let shift = 64 - SPILL_PARTITION_BITS * (level + 1);
+        let method = HashMethodSerializer::default();
+        let keys_state = method.build_keys_state(&keys, block.num_rows())?;
+        let indices = method
+            .build_keys_iter(&keys_state)?
+            .map(|key| (key.fast_hash() >> shift) as usize % SPILL_PARTITIONS)
+            .collect::<Vec<_>>();
+
+        DataBlock::scatter_block(block, &indices, SPILL_PARTITIONS)
+    }
+
+    fn check_build_has_null(&self, keys: &[ColumnRef]) {
+        if self.join.join_type == JoinType::RightMark && !keys.is_empty() {
+            if let Some(validity) = keys[0].validity().1 {
+                if validity.unset_bits() > 0 {
+                    self.build_has_null.store(true, Ordering::Release);
+                }
+            }
+        }
+    }
+
+    /// Scatter the blocks of the build side or the probe side into `SPILL_PARTITIONS` groups.
+    fn scatter_blocks(
+        &self,
+        blocks: Vec<DataBlock>,
+        is_build: bool,
+        level: usize,
+    ) -> Result<Vec<Vec<DataBlock>>> {
+        let mut partitions = vec![vec![]; SPILL_PARTITIONS];
+        for block in blocks {
+            self.check_interrupt()?;
+            let keys = match is_build {
+                true => self.eval_keys(&self.build_keys, &block)?,
+                false => self.eval_keys(&self.probe_keys, &block)?,
+            };
+            if is_build {
+                self.check_build_has_null(&keys);
+            }
+            let scattered = Self::scatter(&keys, &block, level)?;
+            for (partition, block) in scattered.into_iter().enumerate() {
+                partitions[partition].push(block);
+            }
+        }
+        Ok(partitions)
+    }
+
+    fn spill_build_blocks(&self, buffer: &mut SpillBuffer) -> Result<()> {
+        let partitions = self.scatter_blocks(buffer.take_blocks(), true, 0)?;
+        for (partition, blocks) in partitions.into_iter().enumerate() {
+            buffer.spiller.spill(partition, blocks)?;
+        }
+        Ok(())
+    }
+
+    fn spill_probe_blocks(&self, buffer: &mut SpillBuffer) -> Result<()> {
+        let partitions = self.scatter_blocks(buffer.take_blocks(), false, 0)?;
+        for (partition, blocks) in partitions.into_iter().enumerate() {
+            buffer.spiller.spill(partition, blocks)?;
+        }
+        Ok(())
+    }
+
+    /// Split a partition whose build side is too large to be joined in memory into
+    /// `SPILL_PARTITIONS` partitions of the next level, both sides are spilled again.
+    fn split_partition(
+        &self,
+        partition: usize,
+        level: usize,
+        build_partitions: Vec<Vec<DataBlock>>,
+    ) -> Result<()> {
+        let first_partition = self
+            .next_partition
+            .fetch_add(SPILL_PARTITIONS, Ordering::AcqRel);
+
+        {
+            let mut buffer = self.build_buffer.lock().unwrap();
+            for (index, blocks) in build_partitions.into_iter().enumerate() {
+                buffer.spiller.spill(first_partition + index, blocks)?;
+            }
+        }
+
+        // The probe side is split file by file, it may be much larger than the build side.
+        loop {
+            let probe_blocks = {
+                let mut buffer = self.probe_buffer.lock().unwrap();
+                buffer.spiller.restore_next(partition)?
+            };
+            let probe_blocks = match probe_blocks {
+                None => break,
+                Some(blocks) => blocks,
+            };
+            let probe_partitions = self.scatter_blocks(probe_blocks, false, level + 1)?;
+            let mut buffer = self.probe_buffer.lock().unwrap();
+            for (index, blocks) in probe_partitions.into_iter().enumerate() {
+                buffer.spiller.spill(first_partition + index, blocks)?;
+            }
+        }
+
+        let mut pending_partitions = self.pending_partitions.lock().unwrap();
+        pending_partitions
+            .extend((first_partition..first_partition + SPILL_PARTITIONS).map(|p| (p, level + 1)));
+        Ok(())
+    }
+
+    /// Join one spilled partition, the result is the same as the output of the join
+    /// compactors in the in-memory pipeline.
+    fn join_partition(&self, partition: usize, level: usize) -> Result<Vec<DataBlock>> {
+        let build_blocks = {
+            let mut buffer = self.build_buffer.lock().unwrap();
+            buffer.spiller.restore(partition)?
+        };
+        let has_probe = {
+            let buffer = self.probe_buffer.lock().unwrap();
+            buffer.spiller.partitions().contains(&partition)
+        };
+
+        let no_result = match self.join.join_type {
+            JoinType::Inner | JoinType::LeftSemi | JoinType::RightSemi => {
+                build_blocks.is_empty() || !has_probe
+            }
+            JoinType::Left | JoinType::Single | JoinType::LeftAnti | JoinType::RightMark => {
+                !has_probe
+            }
+            JoinType::Right | JoinType::RightAnti | JoinType::LeftMark => build_blocks.is_empty(),
+            JoinType::Full | JoinType::Cross => build_blocks.is_empty() && !has_probe,
+        };
+        if no_result {
+            // the files left are removed with the spiller
+            return Ok(vec![]);
+        }
+
+        let build_size = build_blocks
+            .iter()
+            .map(|block| block.memory_size())
+            .sum::<usize>();
+        let build_blocks = if build_size > self.spill_bytes_threshold && level < MAX_SPILL_LEVEL {
+            let build_partitions = self.scatter_blocks(build_blocks, true, level + 1)?;
+            let non_empty = build_partitions
+                .iter()
+                .filter(|blocks| blocks.iter().any(|block| !block.is_empty()))
+                .count();
+            if non_empty > 1 {
+                self.split_partition(partition, level, build_partitions)?;
+                return Ok(vec![]);
+            }
+            // all the keys fall into the same partition, likely duplicated, no use to split
+            build_partitions.into_iter().flatten().collect()
+        } else {
+            build_blocks
+        };
+
+        let hash_table = JoinHashTable::create_join_state(
+            self.ctx.clone(),
+            &self.join.build_keys,
+            self.build_schema.clone(),
+            self.probe_schema.clone(),
+            HashJoinDesc::create(&self.join)?,
+        )?;
+
+        hash_table.attach()?;
+        for block in build_blocks {
+            self.check_interrupt()?;
+            hash_table.build(block)?;
+        }
+        hash_table.detach()?;
+
+        let has_null = match self.join.join_type {
+            JoinType::RightMark => self.build_has_null.load(Ordering::Acquire),
+            _ => false,
+        };
+        if has_null {
+            *hash_table.hash_join_desc.marker_join_desc.has_null.write() = true;
+        }
+
+        let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+        let mut probe_state = ProbeState::with_capacity(max_block_size);
+        let mut blocks = vec![];
+        loop {
+            let probe_blocks = {
+                let mut buffer = self.probe_buffer.lock().unwrap();
+                buffer.spiller.restore_next(partition)?
+            };
+            let probe_blocks = match probe_blocks {
+                None => break,
+                Some(blocks) => blocks,
+            };
+            for block in probe_blocks {
+                self.check_interrupt()?;
+                probe_state.clear();
+                blocks.extend(hash_table.probe(&block, &mut probe_state)?);
+            }
+        }
+
+        let join_type = self.join.join_type.clone();
+        if (join_type == JoinType::Left
+            || join_type == JoinType::Full
+            || join_type == JoinType::Single)
+            && self.join.non_equi_conditions.is_empty()
+        {
+            blocks = hash_table.left_join_blocks(&blocks)?;
+        }
+
+        if join_type == JoinType::LeftMark {
+            // The subquery column may have nulls in other partitions.
+            let probe_has_null = self.probe_has_null.load(Ordering::Acquire);
+            if probe_has_null && !self.join.from_correlated_subquery {
+                *hash_table.hash_join_desc.marker_join_desc.has_null.write() = true;
+            }
+            blocks = hash_table.mark_join_blocks(&blocks)?;
+        }
+
+        if join_type == JoinType::Right || join_type == JoinType::Full {
+            blocks = hash_table.right_join_blocks(&blocks)?;
+        }
+
+        if join_type == JoinType::RightSemi || join_type == JoinType::RightAnti {
+            blocks = hash_table.right_semi_join_blocks(&blocks)?;
+        }
+
+        Ok(blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl HashJoinState for GraceHashJoinState {
+    fn build(&self, input: DataBlock) -> Result<()> {
+        {
+            let spilled = self.spilled.read().unwrap();
+            if !*spilled {
+                let memory_size = input.memory_size();
+                self.in_memory.build(input)?;
+                let total = self
+                    .build_memory_size
+                    .fetch_add(memory_size, Ordering::AcqRel)
+                    + memory_size;
+                if total <= self.spill_bytes_threshold {
+                    return Ok(());
+                }
+            } else {
+                let mut buffer = self.build_buffer.lock().unwrap();
+                buffer.push(input);
+                if buffer.memory_size > self.spill_bytes_threshold {
+                    self.spill_build_blocks(&mut buffer)?;
+                }
+                return Ok(());
+            }
+        }
+
+        // The threshold is exceeded, take the blocks back from the in-memory hash table
+        // and spill them, the following blocks go to the spill buffer.
+        let mut spilled = self.spilled.write().unwrap();
+        if !*spilled {
+            let chunks = std::mem::take(&mut *self.in_memory.row_space.chunks.write().unwrap());
+            let mut buffer = self.build_buffer.lock().unwrap();
+            for chunk in chunks {
+                buffer.push(chunk.data_block);
+            }
+            self.spill_build_blocks(&mut buffer)?;
+            *spilled = true;
+        }
+        Ok(())
+    }
+
+    fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>> {
+        if !self.is_spilled() {
+            return self.in_memory.probe(input, probe_state);
+        }
+
+        if self.join.join_type == JoinType::LeftMark
+            && matches!(input.column(0).validity().1, Some(x) if x.unset_bits() > 0)
+        {
+            self.probe_has_null.store(true, Ordering::Release);
+        }
+
+        let mut buffer = self.probe_buffer.lock().unwrap();
+        buffer.push(input.clone());
+
+        if buffer.memory_size > self.spill_bytes_threshold {
+            self.spill_probe_blocks(&mut buffer)?;
+        }
+        Ok(vec![])
+    }
+
+    fn interrupt(&self) {
+        self.interrupt.store(true, Ordering::Release);
+        self.in_memory.interrupt();
+    }
+
+    fn attach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count += 1;
+        Ok(())
+    }
+
+    fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.finish()?;
+            let mut is_finished = self.is_finished.lock().unwrap();
+            *is_finished = true;
+            self.finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    fn is_finished(&self) -> Result<bool> {
+        Ok(*self.is_finished.lock().unwrap())
+    }
+
+    fn finish(&self) -> Result<()> {
+        if self.is_spilled() {
+            let mut buffer = self.build_buffer.lock().unwrap();
+            return self.spill_build_blocks(&mut buffer);
+        }
+
+        // All the blocks have been built into the in-memory hash table.
+        self.in_memory.attach()?;
+        self.in_memory.detach()
+    }
+
+    async fn wait_finish(&self) -> Result<()> {
+        if !self.is_finished()? {
+            self.finished_notify.notified().await;
+        }
+
+        Ok(())
+    }
+
+    fn mark_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        match self.is_spilled() {
+            true => Ok(blocks.to_vec()),
+            false => self.in_memory.mark_join_blocks(blocks),
+        }
+    }
+
+    fn right_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        match self.is_spilled() {
+            true => Ok(blocks.to_vec()),
+            false => self.in_memory.right_join_blocks(blocks),
+        }
+    }
+
+    fn right_semi_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        match self.is_spilled() {
+            true => Ok(blocks.to_vec()),
+            false => self.in_memory.right_semi_join_blocks(blocks),
+        }
+    }
+
+    fn left_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        match self.is_spilled() {
+            true => Ok(blocks.to_vec()),
+            false => self.in_memory.left_join_blocks(blocks),
+        }
+    }
+
+    fn attach_probe(&self) -> Result<()> {
+        let mut count = self.probe_count.lock().unwrap();
+        *count += 1;
+        Ok(())
+    }
+
+    fn detach_probe(&self) -> Result<bool> {
+        let mut count = self.probe_count.lock().unwrap();
+        *count -= 1;
+        if !self.is_spilled() {
+            return Ok(false);
+        }
+        if *count != 0 {
+            return Ok(true);
+        }
+
+        let mut probe_buffer = self.probe_buffer.lock().unwrap();
+        self.spill_probe_blocks(&mut probe_buffer)?;
+
+        let build_buffer = self.build_buffer.lock().unwrap();
+        let mut partitions = build_buffer.spiller.partitions();
+        partitions.extend(probe_buffer.spiller.partitions());
+        partitions.sort_unstable();
+        partitions.dedup();
+
+        let mut pending_partitions = self.pending_partitions.lock().unwrap();
+        pending_partitions.extend(partitions.into_iter().map(|partition| (partition, 0)));
+
+        let mut is_probe_finished = self.is_probe_finished.lock().unwrap();
+        *is_probe_finished = true;
+        self.probe_finished_notify.notify_waiters();
+        Ok(true)
+    }
+
+    async fn wait_probe_finish(&self) -> Result<()> {
+        let notified = self.probe_finished_notify.notified();
+        if !*self.is_probe_finished.lock().unwrap() {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    fn join_next_partition(&self) -> Result<Option<Vec<DataBlock>>> {
+        let partition = self.pending_partitions.lock().unwrap().pop_front();
+        match partition {
+            None => Ok(None),
+            Some((partition, level)) => Ok(Some(self.join_partition(partition, level)?)),
+        }
+    }
+}
@@ -45,7 +45,7 @@ pub trait HashJoinState: Send + Sync {
     async fn wait_finish(&self) -> Result<()>;
 
     /// Get mark join results
-    fn mark_join_blocks(&self) -> Result<Vec<DataBlock>>;
+    fn mark_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>>;
 
     /// Get right join results
     fn right_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>>;
@@ -55,4 +55,25 @@ pub trait HashJoinState: Send + Sync {
 
     /// Get left join results
     fn left_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>>;
+
+    /// Attach a probe processor to state
+    fn attach_probe(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Detach a probe processor from state, returns true if the spilled partitions need to be
+    /// joined, which is done by all the probe processors once the last one has detached.
+    fn detach_probe(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Wait until all the probe processors have detached and the spilled partitions are ready
+    async fn wait_probe_finish(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Join the next spilled partition, returns None if all partitions have been joined.
+    fn join_next_partition(&self) -> Result<Option<Vec<DataBlock>>> {
+        Ok(None)
+    }
 }
@@ -259,7 +259,7 @@ impl HashJoinState for JoinHashTable {
         Ok(())
     }
 
-    fn mark_join_blocks(&self) -> Result<Vec<DataBlock>> {
+    fn mark_join_blocks(&self, _blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
         let row_ptrs = self.row_ptrs.read();
         let has_null = self.hash_join_desc.marker_join_desc.has_null.read();
 
@@ -14,6 +14,7 @@
 
 mod common;
 mod desc;
+mod grace_hash_join_state;
 mod hash_join_state;
 mod hash_join_state_impl;
 mod join_hash_table;
@@ -24,6 +25,7 @@ pub(crate) mod row;
 mod util;
 
 pub use desc::HashJoinDesc;
+pub use grace_hash_join_state::GraceHashJoinState;
 pub use hash_join_state::HashJoinState;
 pub use join_hash_table::FixedKeyHashTable;
 pub use join_hash_table::HashTable;
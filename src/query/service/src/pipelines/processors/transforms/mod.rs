@@ -24,10 +24,14 @@ mod transform_limit;
 mod transform_mark_join;
 
 pub mod group_by;
+mod spiller;
 mod transform_left_join;
 mod transform_merge_block;
+mod transform_recursive_cte;
 mod transform_right_join;
 mod transform_right_semi_anti_join;
+mod transform_sort_spill;
+mod transform_window;
 
 pub use aggregator::AggregatorParams;
 pub use aggregator::AggregatorTransformParams;
@@ -37,11 +41,13 @@ use common_pipeline_transforms::processors::transforms::transform_compact;
 use common_pipeline_transforms::processors::transforms::transform_sort_merge;
 use common_pipeline_transforms::processors::transforms::transform_sort_partial;
 pub use hash_join::FixedKeyHashTable;
+pub use hash_join::GraceHashJoinState;
 pub use hash_join::HashJoinDesc;
 pub use hash_join::HashJoinState;
 pub use hash_join::HashTable;
 pub use hash_join::JoinHashTable;
 pub use hash_join::SerializerHashTable;
+pub use spiller::Spiller;
 pub use transform_addon::TransformAddOn;
 pub use transform_aggregator::TransformAggregator;
 pub use transform_block_compact::BlockCompactor;
@@ -60,6 +66,10 @@ pub use transform_limit::TransformLimit;
 pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
 pub use transform_merge_block::TransformMergeBlock;
+pub use transform_recursive_cte::RecursiveCteScanSource;
+pub use transform_recursive_cte::RecursiveCteState;
+pub use transform_recursive_cte::RecursiveCteStates;
+pub use transform_recursive_cte::RecursiveUnionSource;
 pub use transform_right_join::RightJoinCompactor;
 pub use transform_right_join::TransformRightJoin;
 pub use transform_right_semi_anti_join::RightSemiAntiJoinCompactor;
@@ -67,3 +77,6 @@ pub use transform_right_semi_anti_join::TransformRightSemiAntiJoin;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_sort_spill::TransformSortSpill;
+pub use transform_window::TransformWindow;
+pub use transform_window::WindowCompactor;
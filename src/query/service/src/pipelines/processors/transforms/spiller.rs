@@ -0,0 +1,187 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_columns;
+use common_arrow::arrow::io::parquet::read::to_deserializer;
+use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::parquet::read::read_metadata;
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
+use common_datablocks::serialize_data_blocks;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// Spills data blocks to the storage operator of the query and restores them later.
+///
+/// The spilled blocks are grouped by partition, so that every partition can be read back
+/// on its own. The files of a partition are removed once the partition is restored, and
+/// the remaining files are removed when the spiller is dropped.
+pub struct Spiller {
+    operator: Operator,
+    schema: DataSchemaRef,
+    location_prefix: String,
+    next_file_id: usize,
+    partitions: BTreeMap<usize, Vec<String>>,
+}
+
+impl Spiller {
+    pub fn create(ctx: &Arc<QueryContext>, kind: &str, schema: DataSchemaRef) -> Result<Spiller> {
+        let operator = ctx.get_data_operator()?.operator();
+        let location_prefix = format!("_spill/{}/{}_{}", ctx.get_id(), kind, Uuid::new_v4());
+
+        Ok(Spiller {
+            operator,
+            schema,
+            location_prefix,
+            next_file_id: 0,
+            partitions: BTreeMap::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+
+    /// Partitions which have spilled data, in ascending order.
+    pub fn partitions(&self) -> Vec<usize> {
+        self.partitions.keys().copied().collect()
+    }
+
+    /// Writes the blocks into a new file of the partition.
+    pub fn spill(&mut self, partition: usize, blocks: Vec<DataBlock>) -> Result<()> {
+        let blocks = blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = Vec::new();
+        serialize_data_blocks(blocks, &self.schema, &mut data)?;
+
+        let location = format!("{}/{}", self.location_prefix, self.next_file_id);
+        self.next_file_id += 1;
+
+        let operator = self.operator.clone();
+        let object_location = location.clone();
+        GlobalIORuntime::instance().block_on(async move {
+            operator.object(&object_location).write(data).await?;
+            Ok::<_, ErrorCode>(())
+        })?;
+
+        self.partitions.entry(partition).or_default().push(location);
+        Ok(())
+    }
+
+    /// Reads back all the blocks of the partition and removes its files.
+    pub fn restore(&mut self, partition: usize) -> Result<Vec<DataBlock>> {
+        let locations = self.partitions.remove(&partition).unwrap_or_default();
+
+        let mut blocks = Vec::new();
+        for location in locations {
+            blocks.extend(self.read_file(location)?);
+        }
+
+        Ok(blocks)
+    }
+
+    /// Reads back the blocks of the earliest spilled file of the partition and removes the file,
+    /// returns None if there is no file left. It's useful to stream a large partition.
+    pub fn restore_next(&mut self, partition: usize) -> Result<Option<Vec<DataBlock>>> {
+        let location = match self.partitions.get_mut(&partition) {
+            None => return Ok(None),
+            Some(locations) => locations.remove(0),
+        };
+
+        if self.partitions[&partition].is_empty() {
+            self.partitions.remove(&partition);
+        }
+
+        Ok(Some(self.read_file(location)?))
+    }
+
+    fn read_file(&self, location: String) -> Result<Vec<DataBlock>> {
+        let operator = self.operator.clone();
+        let data = GlobalIORuntime::instance().block_on(async move {
+            let object = operator.object(&location);
+            let data = object.read().await?;
+            object.delete().await?;
+            Ok::<_, ErrorCode>(data)
+        })?;
+
+        Self::deserialize(&self.schema, data)
+    }
+
+    fn deserialize(schema: &DataSchemaRef, data: Vec<u8>) -> Result<Vec<DataBlock>> {
+        let mut cursor = Cursor::new(data);
+        let file_meta = read_metadata(&mut cursor)?;
+        let arrow_schema = infer_schema(&file_meta)?;
+
+        let mut blocks = Vec::with_capacity(file_meta.row_groups.len());
+        for row_group in file_meta.row_groups.iter() {
+            let mut columns = Vec::with_capacity(arrow_schema.fields.len());
+            for field in arrow_schema.fields.iter() {
+                let column_chunks = read_columns(&mut cursor, row_group.columns(), &field.name)?;
+                columns.push(to_deserializer(
+                    column_chunks,
+                    field.clone(),
+                    row_group.num_rows(),
+                    None,
+                    None,
+                )?);
+            }
+
+            for chunk in RowGroupDeserializer::new(columns, row_group.num_rows(), None) {
+                blocks.push(DataBlock::from_chunk(schema, &chunk?)?);
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl Drop for Spiller {
+    fn drop(&mut self) {
+        let locations = std::mem::take(&mut self.partitions)
+            .into_values()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if locations.is_empty() {
+            return;
+        }
+
+        // Spilled files are only useful for the current query, so it's fine to leak them
+        // if the cleanup fails.
+        let operator = self.operator.clone();
+        let _ = GlobalIORuntime::instance().try_spawn(async move {
+            for location in locations {
+                let _ = operator.object(&location).delete().await;
+            }
+        });
+    }
+}
@@ -157,84 +157,84 @@ impl TransformAggregator {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
             },
             false => match transform_params.method {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
             },
         }
@@ -245,6 +245,12 @@ pub trait Aggregator: Sized + Send {
     const NAME: &'static str;
 
     fn consume(&mut self, data: DataBlock) -> Result<()>;
+
+    /// Take the block flushed by `consume`, which is pushed downstream before consuming more data.
+    fn take_flushed(&mut self) -> Option<DataBlock> {
+        None
+    }
+
     fn generate(&mut self) -> Result<Option<DataBlock>>;
 }
 
@@ -268,6 +274,7 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                 input_port,
                 output_port,
                 input_data_block: None,
+                output_data_block: None,
             },
         ))))
     }
@@ -279,7 +286,7 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                     inner: s.inner,
                     is_finished: false,
                     output_port: s.output_port,
-                    output_data_block: None,
+                    output_data_block: s.output_data_block,
                 }))
             }
             _ => Err(ErrorCode::Internal("")),
@@ -317,6 +324,23 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
     #[inline(always)]
     fn consume_event(&mut self) -> Result<Event> {
         if let AggregatorTransform::ConsumeData(state) = self {
+            if state.output_data_block.is_some() {
+                if state.output_port.is_finished() {
+                    state.input_port.finish();
+                    let mut temp_state = AggregatorTransform::Finished;
+                    std::mem::swap(self, &mut temp_state);
+                    return Ok(Event::Finished);
+                }
+
+                if !state.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                let block = state.output_data_block.take().unwrap();
+                state.output_port.push_data(Ok(block));
+                return Ok(Event::NeedConsume);
+            }
+
             if state.input_data_block.is_some() {
                 return Ok(Event::Sync);
             }
@@ -385,12 +409,14 @@ struct ConsumeState<TAggregator: Aggregator> {
     input_port: Arc<InputPort>,
     output_port: Arc<OutputPort>,
     input_data_block: Option<DataBlock>,
+    output_data_block: Option<DataBlock>,
 }
 
 impl<TAggregator: Aggregator> ConsumeState<TAggregator> {
     pub fn consume(&mut self) -> Result<()> {
         if let Some(input_data) = self.input_data_block.take() {
             self.inner.consume(input_data)?;
+            self.output_data_block = self.inner.take_flushed();
         }
 
         Ok(())
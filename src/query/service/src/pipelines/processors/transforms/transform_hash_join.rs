@@ -61,6 +61,13 @@ impl Sink for SinkBuildHashTable {
 enum HashJoinStep {
     Build,
     Probe,
+    /// All the probe input is consumed, detach from the join state.
+    FinishProbe,
+    /// Wait for the other probe processors to finish before joining the spilled partitions.
+    WaitProbe,
+    /// Join the spilled partitions one at a time, along with the other probe processors.
+    Partition,
+    Finished,
 }
 
 pub struct TransformHashJoinProbe {
@@ -83,6 +90,7 @@ impl TransformHashJoinProbe {
         _output_schema: DataSchemaRef,
     ) -> Result<ProcessorPtr> {
         let default_block_size = ctx.get_settings().get_max_block_size()?;
+        join_state.attach_probe()?;
         Ok(ProcessorPtr::create(Box::new(TransformHashJoinProbe {
             input_data: None,
             output_data_blocks: VecDeque::new(),
@@ -114,11 +122,37 @@ impl Processor for TransformHashJoinProbe {
 
     fn event(&mut self) -> Result<Event> {
         match self.step {
-            HashJoinStep::Build => Ok(Event::Async),
+            HashJoinStep::Build | HashJoinStep::WaitProbe => Ok(Event::Async),
+            HashJoinStep::FinishProbe => Ok(Event::Sync),
+            HashJoinStep::Partition | HashJoinStep::Finished => {
+                if self.output_port.is_finished() {
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if !self.output_data_blocks.is_empty() {
+                    let data = self.output_data_blocks.pop_front().unwrap();
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if let HashJoinStep::Partition = self.step {
+                    return Ok(Event::Sync);
+                }
+
+                self.output_port.finish();
+                Ok(Event::Finished)
+            }
             HashJoinStep::Probe => {
                 if self.output_port.is_finished() {
+                    // still detach from the join state, the other probe processors may be
+                    // waiting for it to join the spilled partitions
                     self.input_port.finish();
-                    return Ok(Event::Finished);
+                    self.step = HashJoinStep::FinishProbe;
+                    return Ok(Event::Sync);
                 }
 
                 if !self.output_port.can_push() {
@@ -143,8 +177,8 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
-                    self.output_port.finish();
-                    return Ok(Event::Finished);
+                    self.step = HashJoinStep::FinishProbe;
+                    return Ok(Event::Sync);
                 }
 
                 self.input_port.set_need_data();
@@ -159,20 +193,41 @@ impl Processor for TransformHashJoinProbe {
 
     fn process(&mut self) -> Result<()> {
         match self.step {
-            HashJoinStep::Build => Ok(()),
+            HashJoinStep::Build | HashJoinStep::WaitProbe | HashJoinStep::Finished => Ok(()),
             HashJoinStep::Probe => {
                 if let Some(data) = self.input_data.take() {
                     self.probe(&data)?;
                 }
                 Ok(())
             }
+            HashJoinStep::FinishProbe => {
+                self.step = match self.join_state.detach_probe()? {
+                    true => HashJoinStep::WaitProbe,
+                    false => HashJoinStep::Finished,
+                };
+                Ok(())
+            }
+            HashJoinStep::Partition => {
+                match self.join_state.join_next_partition()? {
+                    Some(blocks) => self.output_data_blocks.extend(blocks),
+                    None => self.step = HashJoinStep::Finished,
+                }
+                Ok(())
+            }
         }
     }
 
     async fn async_process(&mut self) -> Result<()> {
-        if let HashJoinStep::Build = &self.step {
-            self.join_state.wait_finish().await?;
-            self.step = HashJoinStep::Probe;
+        match &self.step {
+            HashJoinStep::Build => {
+                self.join_state.wait_finish().await?;
+                self.step = HashJoinStep::Probe;
+            }
+            HashJoinStep::WaitProbe => {
+                self.join_state.wait_probe_finish().await?;
+                self.step = HashJoinStep::Partition;
+            }
+            _ => {}
         }
 
         Ok(())
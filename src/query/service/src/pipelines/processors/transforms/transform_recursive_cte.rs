@@ -0,0 +1,259 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::Column;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_datavalues::ScalarColumn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_sql::executor::PhysicalPlan;
+use common_sql::executor::RecursiveUnion;
+use parking_lot::RwLock;
+
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::SyncSource;
+use crate::pipelines::processors::SyncSourcer;
+use crate::pipelines::PipelineBuilder;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// The working table of a recursive CTE, shared between the `RecursiveUnion`
+/// driving the iterations and the `RecursiveCteScan`s of its recursive term.
+pub struct RecursiveCteState {
+    working_table: RwLock<Vec<DataBlock>>,
+}
+
+impl RecursiveCteState {
+    pub fn create() -> Arc<RecursiveCteState> {
+        Arc::new(RecursiveCteState {
+            working_table: RwLock::new(vec![]),
+        })
+    }
+
+    pub fn working_table(&self) -> Vec<DataBlock> {
+        self.working_table.read().clone()
+    }
+
+    fn set_working_table(&self, blocks: Vec<DataBlock>) {
+        *self.working_table.write() = blocks;
+    }
+}
+
+pub type RecursiveCteStates = HashMap<String, Arc<RecursiveCteState>>;
+
+/// Evaluate the anchor once, then the recursive term over the rows produced
+/// by the previous iteration until an iteration produces no rows.
+///
+/// Every iteration runs in its own pipeline, since the recursive term must
+/// only see the working table after the previous iteration is finished.
+pub struct RecursiveUnionSource {
+    ctx: Arc<QueryContext>,
+    plan: RecursiveUnion,
+    state: Arc<RecursiveCteState>,
+    cte_states: RecursiveCteStates,
+    max_depth: u64,
+
+    depth: u64,
+    executor: Option<PipelinePullingExecutor>,
+    next_working_table: Vec<DataBlock>,
+    scan_schema: DataSchemaRef,
+    // Rows produced so far, only for `UNION`
+    seen_rows: Option<HashSet<Vec<DataValue>>>,
+}
+
+impl RecursiveUnionSource {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        plan: RecursiveUnion,
+        state: Arc<RecursiveCteState>,
+        cte_states: RecursiveCteStates,
+    ) -> Result<ProcessorPtr> {
+        let max_depth = ctx.get_settings().get_max_recursion_depth()?;
+        let scan_schema = DataSchemaRefExt::create(
+            plan.scan_columns
+                .iter()
+                .zip(plan.schema.fields())
+                .map(|(name, field)| DataField::new(name, field.data_type().clone()))
+                .collect(),
+        );
+        let seen_rows = if plan.distinct {
+            Some(HashSet::new())
+        } else {
+            None
+        };
+        SyncSourcer::create(ctx.clone(), output, RecursiveUnionSource {
+            ctx,
+            plan,
+            state,
+            cte_states,
+            max_depth,
+            depth: 0,
+            executor: None,
+            next_working_table: vec![],
+            scan_schema,
+            seen_rows,
+        })
+    }
+
+    fn execute(&self, plan: &PhysicalPlan) -> Result<PipelinePullingExecutor> {
+        let ctx = QueryContext::create_from(self.ctx.clone());
+        let builder =
+            PipelineBuilder::create(ctx.clone()).with_recursive_cte_states(self.cte_states.clone());
+        let mut build_res = builder.finalize(plan)?;
+
+        let settings = ctx.get_settings();
+        build_res.set_max_threads(settings.get_max_threads()? as usize);
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
+        let mut executor = PipelinePullingExecutor::from_pipelines(build_res, executor_settings)?;
+        executor.start();
+        Ok(executor)
+    }
+
+    /// Start the next iteration, returns false if a fixpoint is reached.
+    fn next_iteration(&mut self) -> Result<bool> {
+        let plan = if self.depth == 0 {
+            self.plan.anchor.clone()
+        } else {
+            if self.next_working_table.is_empty() {
+                return Ok(false);
+            }
+            if self.depth > self.max_depth {
+                return Err(ErrorCode::Overflow(format!(
+                    "Recursive query \"{}\" exceeded max_recursion_depth {}",
+                    self.plan.cte_name, self.max_depth
+                )));
+            }
+            let working_table = std::mem::take(&mut self.next_working_table);
+            self.state.set_working_table(working_table);
+            self.plan.recursive.clone()
+        };
+        self.executor = Some(self.execute(&plan)?);
+        Ok(true)
+    }
+
+    /// Project the columns of the current iteration and rename them to the anchor columns.
+    fn project(&self, block: &DataBlock) -> Result<DataBlock> {
+        let columns = self
+            .plan
+            .pairs
+            .iter()
+            .map(|(anchor, recursive)| {
+                let name = if self.depth == 0 { anchor } else { recursive };
+                Ok(block.try_column_by_name(name)?.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataBlock::create(self.plan.schema.clone(), columns))
+    }
+
+    /// Remove the rows which have been produced before.
+    fn dedup(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let seen_rows = match &mut self.seen_rows {
+            Some(seen_rows) => seen_rows,
+            None => return Ok(block),
+        };
+        let mut filter = Vec::with_capacity(block.num_rows());
+        for row in 0..block.num_rows() {
+            let values = block
+                .columns()
+                .iter()
+                .map(|column| column.get(row))
+                .collect::<Vec<_>>();
+            filter.push(seen_rows.insert(values));
+        }
+        let filter = BooleanColumn::from_slice(&filter).arc();
+        DataBlock::filter_block(block, &filter)
+    }
+}
+
+impl SyncSource for RecursiveUnionSource {
+    const NAME: &'static str = "RecursiveUnionSource";
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            let executor = match &mut self.executor {
+                Some(executor) => executor,
+                None => {
+                    if !self.next_iteration()? {
+                        return Ok(None);
+                    }
+                    continue;
+                }
+            };
+
+            match executor.pull_data()? {
+                None => {
+                    self.executor = None;
+                    self.depth += 1;
+                }
+                Some(block) => {
+                    let block = self.dedup(self.project(&block)?)?;
+                    if block.is_empty() {
+                        continue;
+                    }
+                    self.next_working_table.push(DataBlock::create(
+                        self.scan_schema.clone(),
+                        block.columns().to_vec(),
+                    ));
+                    return Ok(Some(block));
+                }
+            }
+        }
+    }
+}
+
+/// Read the working table of a recursive CTE.
+pub struct RecursiveCteScanSource {
+    state: Arc<RecursiveCteState>,
+    blocks: Option<Vec<DataBlock>>,
+}
+
+impl RecursiveCteScanSource {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        state: Arc<RecursiveCteState>,
+    ) -> Result<ProcessorPtr> {
+        SyncSourcer::create(ctx, output, RecursiveCteScanSource {
+            state,
+            blocks: None,
+        })
+    }
+}
+
+impl SyncSource for RecursiveCteScanSource {
+    const NAME: &'static str = "RecursiveCteScanSource";
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        let state = &self.state;
+        let blocks = self.blocks.get_or_insert_with(|| {
+            let mut blocks = state.working_table();
+            blocks.reverse();
+            blocks
+        });
+        Ok(blocks.pop())
+    }
+}
@@ -0,0 +1,471 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::sync;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use common_arrow::arrow::compute::sort::row::Row;
+use common_arrow::arrow::compute::sort::row::RowConverter;
+use common_arrow::arrow::compute::sort::row::Rows;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_pipeline_transforms::processors::transforms::convert_rows;
+use common_pipeline_transforms::processors::transforms::create_row_converter;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::transforms::Compactor;
+use crate::pipelines::processors::transforms::SortMergeCompactor;
+use crate::pipelines::processors::transforms::Spiller;
+use crate::pipelines::processors::Processor;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// The max number of runs merged at a time. If there are more runs, they are merged into
+/// longer runs in several passes first, so that the current blocks held in memory by the
+/// final merge are bounded.
+const MAX_MERGE_FAN_IN: usize = 16;
+
+/// A cursor point to a certain row of the current block of a sorted run.
+struct RunCursor {
+    run: usize,
+    row_index: usize,
+    block: DataBlock,
+    rows: Rows,
+}
+
+impl RunCursor {
+    fn is_finished(&self) -> bool {
+        self.row_index == self.rows.len()
+    }
+
+    fn current(&self) -> Row<'_> {
+        self.rows.row_unchecked(self.row_index)
+    }
+}
+
+impl Ord for RunCursor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.current()
+            .cmp(&other.current())
+            .then_with(|| self.run.cmp(&other.run))
+    }
+}
+
+impl PartialEq for RunCursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RunCursor {}
+
+impl PartialOrd for RunCursor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// K-way merge of the sorted runs in the spiller, only the current block of every run
+/// is held in memory.
+struct SortedRunsMerger {
+    row_converter: RowConverter,
+    sort_field_indices: Vec<usize>,
+    heap: BinaryHeap<Reverse<RunCursor>>,
+    block_size: usize,
+    limit: Option<usize>,
+}
+
+impl SortedRunsMerger {
+    fn try_create(
+        spiller: &mut Spiller,
+        runs: &[usize],
+        schema: &DataSchemaRef,
+        sort_columns_descriptions: &[SortColumnDescription],
+        block_size: usize,
+        limit: Option<usize>,
+    ) -> Result<Self> {
+        let (row_converter, sort_field_indices) =
+            create_row_converter(schema, sort_columns_descriptions)?;
+        let mut merger = SortedRunsMerger {
+            row_converter,
+            sort_field_indices,
+            heap: BinaryHeap::with_capacity(runs.len()),
+            block_size,
+            limit,
+        };
+
+        for run in runs {
+            if let Some(cursor) = merger.next_cursor(spiller, *run)? {
+                merger.heap.push(Reverse(cursor));
+            }
+        }
+        Ok(merger)
+    }
+
+    fn next_cursor(&mut self, spiller: &mut Spiller, run: usize) -> Result<Option<RunCursor>> {
+        while let Some(blocks) = spiller.restore_next(run)? {
+            let blocks = blocks
+                .into_iter()
+                .filter(|block| !block.is_empty())
+                .collect::<Vec<_>>();
+            if blocks.is_empty() {
+                continue;
+            }
+
+            let block = DataBlock::concat_blocks(&blocks)?;
+            let rows = convert_rows(&mut self.row_converter, &self.sort_field_indices, &block)?;
+            return Ok(Some(RunCursor {
+                run,
+                row_index: 0,
+                block,
+                rows,
+            }));
+        }
+        Ok(None)
+    }
+
+    fn next_block(&mut self, spiller: &mut Spiller) -> Result<Option<DataBlock>> {
+        let block_size = match self.limit {
+            Some(limit) => std::cmp::min(limit, self.block_size),
+            None => self.block_size,
+        };
+
+        let mut num_rows = 0;
+        let mut slices = vec![];
+        while num_rows < block_size {
+            let mut cursor = match self.heap.pop() {
+                None => break,
+                Some(Reverse(cursor)) => cursor,
+            };
+
+            // Take the rows of the cursor until it's greater than the next cursor.
+            let start = cursor.row_index;
+            let end = std::cmp::min(start + block_size - num_rows, cursor.rows.len());
+            match self.heap.peek() {
+                None => cursor.row_index = end,
+                Some(Reverse(next)) => {
+                    while cursor.row_index < end && cursor.cmp(next) != Ordering::Greater {
+                        cursor.row_index += 1;
+                    }
+                }
+            }
+
+            let len = cursor.row_index - start;
+            slices.push(DataBlock::block_take_by_slices_limit(
+                &cursor.block,
+                (start, len),
+                None,
+            )?);
+            num_rows += len;
+
+            if !cursor.is_finished() {
+                self.heap.push(Reverse(cursor));
+            } else if let Some(cursor) = self.next_cursor(spiller, cursor.run)? {
+                self.heap.push(Reverse(cursor));
+            }
+        }
+
+        if slices.is_empty() {
+            return Ok(None);
+        }
+
+        self.limit = self.limit.map(|limit| limit - num_rows);
+        Ok(Some(DataBlock::concat_blocks(&slices)?))
+    }
+}
+
+enum SortSpillStep {
+    Consume,
+    /// All the input is consumed, sort the buffered blocks or start to merge the spilled runs.
+    Finalize,
+    Merge,
+    Finished,
+}
+
+/// External merge sort.
+///
+/// Like `TransformSortMerge`, it buffers the partially sorted blocks and merges them. But once
+/// the buffered blocks of all the processors of the sort exceed `sort_spill_bytes_threshold`,
+/// the processor sorts its buffered blocks into a run and spills the run to storage. After the
+/// input is finished, the spilled runs are k-way merged into a sorted stream, at most
+/// `MAX_MERGE_FAN_IN` runs at a time.
+pub struct TransformSortSpill {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data_blocks: VecDeque<DataBlock>,
+    step: SortSpillStep,
+
+    schema: DataSchemaRef,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    block_size: usize,
+    limit: Option<usize>,
+    compactor: SortMergeCompactor,
+
+    blocks: Vec<DataBlock>,
+    memory_size: usize,
+    /// The memory bytes of the buffered blocks of all the processors of the sort.
+    total_memory_size: Arc<AtomicUsize>,
+    spill_bytes_threshold: usize,
+    spiller: Spiller,
+    /// The spiller partitions of the sorted runs.
+    runs: Vec<usize>,
+    next_run: usize,
+    merger: Option<SortedRunsMerger>,
+
+    aborting: Arc<AtomicBool>,
+}
+
+impl TransformSortSpill {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        schema: DataSchemaRef,
+        limit: Option<usize>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        total_memory_size: Arc<AtomicUsize>,
+    ) -> Result<ProcessorPtr> {
+        let settings = ctx.get_settings();
+        let block_size = settings.get_max_block_size()? as usize;
+        let spill_bytes_threshold = settings.get_sort_spill_bytes_threshold()? as usize;
+        let spiller = Spiller::create(&ctx, "sort", schema.clone())?;
+
+        Ok(ProcessorPtr::create(Box::new(TransformSortSpill {
+            input,
+            output,
+            input_data: None,
+            output_data_blocks: VecDeque::new(),
+            step: SortSpillStep::Consume,
+            compactor: SortMergeCompactor::new(
+                block_size,
+                limit,
+                sort_columns_descriptions.clone(),
+            ),
+            schema,
+            sort_columns_descriptions,
+            block_size,
+            limit,
+            blocks: vec![],
+            memory_size: 0,
+            total_memory_size,
+            spill_bytes_threshold,
+            spiller,
+            runs: vec![],
+            next_run: 0,
+            merger: None,
+            aborting: Arc::new(AtomicBool::new(false)),
+        })))
+    }
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        let memory_size = block.memory_size();
+        self.memory_size += memory_size;
+        self.blocks.push(block);
+
+        let total_memory_size = self
+            .total_memory_size
+            .fetch_add(memory_size, sync::atomic::Ordering::AcqRel)
+            + memory_size;
+        if total_memory_size > self.spill_bytes_threshold {
+            self.spill_run()?;
+        }
+        Ok(())
+    }
+
+    fn release_memory(&mut self) {
+        self.total_memory_size
+            .fetch_sub(self.memory_size, sync::atomic::Ordering::AcqRel);
+        self.memory_size = 0;
+    }
+
+    /// Sort the buffered blocks into a run, every block of the run is spilled into its own file,
+    /// so that the run can be read back block by block.
+    fn spill_run(&mut self) -> Result<()> {
+        if self.blocks.is_empty() {
+            return Ok(());
+        }
+
+        let run = self.next_run;
+        self.next_run += 1;
+        let blocks = std::mem::take(&mut self.blocks);
+        for block in self.compactor.compact_final(&blocks)? {
+            self.spiller.spill(run, vec![block])?;
+        }
+
+        self.runs.push(run);
+        self.release_memory();
+        Ok(())
+    }
+
+    /// Merges every `MAX_MERGE_FAN_IN` runs into a new run, until there are at most
+    /// `MAX_MERGE_FAN_IN` runs left.
+    fn merge_runs(&mut self) -> Result<()> {
+        while self.runs.len() > MAX_MERGE_FAN_IN {
+            let runs = std::mem::take(&mut self.runs);
+            for group in runs.chunks(MAX_MERGE_FAN_IN) {
+                if self.aborting.load(sync::atomic::Ordering::Relaxed) {
+                    return Err(ErrorCode::AbortedQuery(
+                        "Aborted query, because the server is shutting down or the query was killed.",
+                    ));
+                }
+
+                if group.len() == 1 {
+                    self.runs.push(group[0]);
+                    continue;
+                }
+
+                // the rows beyond the limit of every run are never output, drop them early.
+                let mut merger = SortedRunsMerger::try_create(
+                    &mut self.spiller,
+                    group,
+                    &self.schema,
+                    &self.sort_columns_descriptions,
+                    self.block_size,
+                    self.limit,
+                )?;
+                let run = self.next_run;
+                self.next_run += 1;
+                while let Some(block) = merger.next_block(&mut self.spiller)? {
+                    self.spiller.spill(run, vec![block])?;
+                }
+                self.runs.push(run);
+            }
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        if self.runs.is_empty() {
+            let blocks = std::mem::take(&mut self.blocks);
+            self.output_data_blocks
+                .extend(self.compactor.compact_final(&blocks)?);
+            self.release_memory();
+            self.step = SortSpillStep::Finished;
+            return Ok(());
+        }
+
+        self.spill_run()?;
+        self.merge_runs()?;
+        self.merger = Some(SortedRunsMerger::try_create(
+            &mut self.spiller,
+            &self.runs,
+            &self.schema,
+            &self.sort_columns_descriptions,
+            self.block_size,
+            self.limit,
+        )?);
+        self.step = SortSpillStep::Merge;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSortSpill {
+    fn name(&self) -> String {
+        "SortSpill".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn interrupt(&self) {
+        self.aborting.store(true, sync::atomic::Ordering::Release);
+        self.compactor.interrupt();
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.aborting.load(sync::atomic::Ordering::Relaxed) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data) = self.output_data_blocks.pop_front() {
+            self.output.push_data(Ok(data));
+            return Ok(Event::NeedConsume);
+        }
+
+        match self.step {
+            SortSpillStep::Consume => {
+                if self.input_data.is_some() {
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.has_data() {
+                    self.input_data = Some(self.input.pull_data().unwrap()?);
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.is_finished() {
+                    self.step = SortSpillStep::Finalize;
+                    return Ok(Event::Sync);
+                }
+
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+            SortSpillStep::Finalize | SortSpillStep::Merge => Ok(Event::Sync),
+            SortSpillStep::Finished => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.step {
+            SortSpillStep::Consume => {
+                if let Some(block) = self.input_data.take() {
+                    self.consume(block)?;
+                }
+                Ok(())
+            }
+            SortSpillStep::Finalize => self.finalize(),
+            SortSpillStep::Merge => {
+                let merger = self.merger.as_mut().unwrap();
+                match merger.next_block(&mut self.spiller)? {
+                    Some(block) => self.output_data_blocks.push_back(block),
+                    None => self.step = SortSpillStep::Finished,
+                }
+                Ok(())
+            }
+            SortSpillStep::Finished => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,463 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::BorrowMut;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::MutableColumn;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_sql::executor::LagLeadFunctionDesc;
+use common_sql::executor::NthValueFunctionDesc;
+use common_sql::executor::Window;
+use common_sql::executor::WindowFunction;
+use common_sql::plans::WindowFuncFrame;
+use common_sql::plans::WindowFuncFrameBound;
+use common_sql::plans::WindowFuncFrameUnits;
+
+use crate::pipelines::processors::transforms::Compactor;
+use crate::pipelines::processors::TransformCompact;
+
+enum WindowFunctionImpl {
+    Aggregate {
+        func: AggregateFunctionRef,
+        args: Vec<usize>,
+    },
+    RowNumber,
+    Rank,
+    DenseRank,
+    PercentRank,
+    CumeDist,
+    LagLead(LagLeadFunctionDesc),
+    NthValue(NthValueFunctionDesc),
+}
+
+/// Evaluate a window function over all the input blocks, which have been sorted
+/// by partition keys and order keys. The result column is appended to the input.
+pub struct WindowCompactor {
+    func: WindowFunctionImpl,
+    partition_by: Vec<usize>,
+    order_by: Vec<usize>,
+    // Sort direction of the first order key, used to evaluate `RANGE` frames with offset
+    order_asc: bool,
+    frame: WindowFuncFrame,
+    output_field: DataField,
+}
+
+impl WindowCompactor {
+    pub fn try_create(window: &Window, input_schema: DataSchemaRef) -> Result<Self> {
+        let func = match &window.func {
+            WindowFunction::Aggregate(agg) => WindowFunctionImpl::Aggregate {
+                func: AggregateFunctionFactory::instance().get(
+                    agg.sig.name.as_str(),
+                    agg.sig.params.clone(),
+                    agg.args
+                        .iter()
+                        .map(|&index| input_schema.field(index))
+                        .cloned()
+                        .collect(),
+                )?,
+                args: agg.args.clone(),
+            },
+            WindowFunction::RowNumber => WindowFunctionImpl::RowNumber,
+            WindowFunction::Rank => WindowFunctionImpl::Rank,
+            WindowFunction::DenseRank => WindowFunctionImpl::DenseRank,
+            WindowFunction::PercentRank => WindowFunctionImpl::PercentRank,
+            WindowFunction::CumeDist => WindowFunctionImpl::CumeDist,
+            WindowFunction::LagLead(lag_lead) => WindowFunctionImpl::LagLead(lag_lead.clone()),
+            WindowFunction::NthValue(nth) => WindowFunctionImpl::NthValue(nth.clone()),
+        };
+
+        Ok(WindowCompactor {
+            func,
+            partition_by: window
+                .partition_by
+                .iter()
+                .map(|column_id| input_schema.index_of(column_id))
+                .collect::<Result<_>>()?,
+            order_by: window
+                .order_by
+                .iter()
+                .map(|desc| input_schema.index_of(&desc.order_by))
+                .collect::<Result<_>>()?,
+            order_asc: window.order_by.first().map_or(true, |desc| desc.asc),
+            frame: window.frame.clone(),
+            output_field: DataField::new(&window.column_id, window.data_type.clone()),
+        })
+    }
+
+    fn data_type(&self) -> &DataTypeImpl {
+        self.output_field.data_type()
+    }
+
+    /// Split rows into groups, the adjacent rows with the same values of `columns`
+    /// belong to a group. Returns the start offsets of groups, ended with `num_rows`.
+    fn split_groups(columns: &[ColumnRef], start: usize, end: usize) -> Vec<usize> {
+        let mut offsets = vec![start];
+        for row in start + 1..end {
+            if columns
+                .iter()
+                .any(|column| column.get(row) != column.get(row - 1))
+            {
+                offsets.push(row);
+            }
+        }
+        offsets.push(end);
+        offsets
+    }
+
+    fn eval_partition(
+        &self,
+        columns: &[ColumnRef],
+        start: usize,
+        end: usize,
+        builder: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let order_by_columns = self
+            .order_by
+            .iter()
+            .map(|index| columns[*index].clone())
+            .collect::<Vec<_>>();
+
+        // Rows with the same order keys are peers, `peers[i]` is the peer group of row `i`
+        let peer_offsets = Self::split_groups(&order_by_columns, start, end);
+        let mut peers = Vec::with_capacity(end - start);
+        for (group, window) in peer_offsets.windows(2).enumerate() {
+            for _ in window[0]..window[1] {
+                peers.push((group, window[0], window[1]));
+            }
+        }
+
+        let num_rows = (end - start) as u64;
+        match &self.func {
+            WindowFunctionImpl::RowNumber => {
+                for row in start..end {
+                    builder.append_data_value(DataValue::UInt64((row - start + 1) as u64))?;
+                }
+            }
+            WindowFunctionImpl::Rank => {
+                for (_, peer_start, _) in peers.iter() {
+                    builder
+                        .append_data_value(DataValue::UInt64((peer_start - start + 1) as u64))?;
+                }
+            }
+            WindowFunctionImpl::DenseRank => {
+                for (group, _, _) in peers.iter() {
+                    builder.append_data_value(DataValue::UInt64(*group as u64 + 1))?;
+                }
+            }
+            WindowFunctionImpl::PercentRank => {
+                for (_, peer_start, _) in peers.iter() {
+                    let value = if num_rows == 1 {
+                        0.0
+                    } else {
+                        (peer_start - start) as f64 / (num_rows - 1) as f64
+                    };
+                    builder.append_data_value(DataValue::Float64(value))?;
+                }
+            }
+            WindowFunctionImpl::CumeDist => {
+                for (_, _, peer_end) in peers.iter() {
+                    let value = (peer_end - start) as f64 / num_rows as f64;
+                    builder.append_data_value(DataValue::Float64(value))?;
+                }
+            }
+            WindowFunctionImpl::LagLead(lag_lead) => {
+                let offset = lag_lead.offset as usize;
+                for row in start..end {
+                    let target = if lag_lead.is_lag {
+                        row.checked_sub(offset).filter(|target| *target >= start)
+                    } else {
+                        Some(row + offset).filter(|target| *target < end)
+                    };
+                    let value = match (target, lag_lead.default) {
+                        (Some(target), _) => columns[lag_lead.arg].get(target),
+                        (None, Some(default)) => columns[default].get(row),
+                        (None, None) => DataValue::Null,
+                    };
+                    builder.append_data_value(value)?;
+                }
+            }
+            WindowFunctionImpl::NthValue(nth) => {
+                let frames = self.frames(&order_by_columns, &peers, start, end)?;
+                for (frame_start, frame_end) in frames {
+                    let target = match nth.n {
+                        Some(n) => Some(frame_start + n as usize - 1),
+                        None => frame_end.checked_sub(1),
+                    };
+                    let value = match target {
+                        Some(target) if target >= frame_start && target < frame_end => {
+                            columns[nth.arg].get(target)
+                        }
+                        _ => DataValue::Null,
+                    };
+                    builder.append_data_value(value)?;
+                }
+            }
+            WindowFunctionImpl::Aggregate { func, args } => {
+                let frames = self.frames(&order_by_columns, &peers, start, end)?;
+                let arg_columns = args
+                    .iter()
+                    .map(|index| columns[*index].clone())
+                    .collect::<Vec<_>>();
+                let arena = Bump::new();
+                let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+
+                // The frames growing from the partition start, like the default
+                // `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, are accumulated incrementally.
+                let is_growing = frames.iter().all(|(frame_start, _)| *frame_start == start)
+                    && frames.windows(2).all(|w| w[0].1 <= w[1].1);
+                if is_growing {
+                    func.init_state(place);
+                    let mut accumulated = start;
+                    for (_, frame_end) in frames {
+                        for row in accumulated..frame_end {
+                            func.accumulate_row(place, &arg_columns, row)?;
+                        }
+                        accumulated = frame_end;
+                        func.merge_result(place, builder)?;
+                    }
+                    if func.need_manual_drop_state() {
+                        unsafe { func.drop_state(place) }
+                    }
+                } else {
+                    // Sliding frames are merged from the states of O(log n) segments.
+                    let tree = SegmentTree::try_create(func.clone(), &arg_columns, start, end)?;
+                    for (frame_start, frame_end) in frames {
+                        func.init_state(place);
+                        tree.aggregate(place, frame_start - start, frame_end - start)?;
+                        func.merge_result(place, builder)?;
+                        if func.need_manual_drop_state() {
+                            unsafe { func.drop_state(place) }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute the frame `[frame_start, frame_end)` of each row in the partition.
+    fn frames(
+        &self,
+        order_by_columns: &[ColumnRef],
+        peers: &[(usize, usize, usize)],
+        start: usize,
+        end: usize,
+    ) -> Result<Vec<(usize, usize)>> {
+        let mut frames = Vec::with_capacity(end - start);
+        match self.frame.units {
+            WindowFuncFrameUnits::Rows => {
+                // Returns the offset of the bound, the end bound of frame is exclusive
+                let row_offset =
+                    |bound: &WindowFuncFrameBound, row: usize, is_start: bool| -> Result<usize> {
+                        let row = if is_start { row } else { row + 1 };
+                        Ok(match bound {
+                            WindowFuncFrameBound::Preceding(None) => start,
+                            WindowFuncFrameBound::Following(None) => end,
+                            WindowFuncFrameBound::CurrentRow => row,
+                            WindowFuncFrameBound::Preceding(Some(offset)) => {
+                                row.saturating_sub(offset.as_u64()? as usize).max(start)
+                            }
+                            WindowFuncFrameBound::Following(Some(offset)) => {
+                                (row + offset.as_u64()? as usize).min(end)
+                            }
+                        })
+                    };
+                for row in start..end {
+                    let frame_start = row_offset(&self.frame.start_bound, row, true)?;
+                    let frame_end = row_offset(&self.frame.end_bound, row, false)?;
+                    frames.push((frame_start, frame_end.max(frame_start)));
+                }
+            }
+            WindowFuncFrameUnits::Range => {
+                // Keys are normalized to ascending order, NULLs are grouped together
+                // in either the start or the end of the partition.
+                let keys = if order_by_columns.is_empty() {
+                    vec![None; end - start]
+                } else {
+                    let sign = if self.order_asc { 1.0 } else { -1.0 };
+                    (start..end)
+                        .map(|row| match order_by_columns[0].get(row) {
+                            DataValue::Null => Ok(None),
+                            value => Ok(Some(value.as_f64()? * sign)),
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                };
+                let non_null_start = keys.iter().position(Option::is_some).unwrap_or(0);
+                let non_null_end = keys
+                    .iter()
+                    .rposition(Option::is_some)
+                    .map_or(0, |pos| pos + 1);
+                let non_null_keys = &keys[non_null_start..non_null_end.max(non_null_start)];
+
+                let range_offset =
+                    |bound: &WindowFuncFrameBound, row: usize, is_start: bool| -> Result<usize> {
+                        let (_, peer_start, peer_end) = peers[row - start];
+                        let (key, offset) = match (bound, keys[row - start]) {
+                            (WindowFuncFrameBound::Preceding(None), _) => return Ok(start),
+                            (WindowFuncFrameBound::Following(None), _) => return Ok(end),
+                            (WindowFuncFrameBound::CurrentRow, _) | (_, None) => {
+                                return Ok(if is_start { peer_start } else { peer_end });
+                            }
+                            (WindowFuncFrameBound::Preceding(Some(offset)), Some(key)) => {
+                                (key, -offset.as_f64()?)
+                            }
+                            (WindowFuncFrameBound::Following(Some(offset)), Some(key)) => {
+                                (key, offset.as_f64()?)
+                            }
+                        };
+                        let target = key + offset;
+                        let position = if is_start {
+                            non_null_keys.partition_point(|k| k.unwrap_or_default() < target)
+                        } else {
+                            non_null_keys.partition_point(|k| k.unwrap_or_default() <= target)
+                        };
+                        Ok(start + non_null_start + position)
+                    };
+                for row in start..end {
+                    let frame_start = range_offset(&self.frame.start_bound, row, true)?;
+                    let frame_end = range_offset(&self.frame.end_bound, row, false)?;
+                    frames.push((frame_start, frame_end.max(frame_start)));
+                }
+            }
+        }
+        Ok(frames)
+    }
+}
+
+/// A segment tree of aggregate states over the rows of a partition, the state of any range
+/// of rows is merged from at most O(log n) nodes.
+struct SegmentTree {
+    func: AggregateFunctionRef,
+    // `nodes[n + i]` is the state of row `i`, `nodes[i]` merges `nodes[2i]` and `nodes[2i + 1]`
+    nodes: Vec<StateAddr>,
+    // The states are allocated in the arena, which must outlive them
+    _arena: Bump,
+}
+
+impl SegmentTree {
+    fn try_create(
+        func: AggregateFunctionRef,
+        arg_columns: &[ColumnRef],
+        start: usize,
+        end: usize,
+    ) -> Result<Self> {
+        let num_rows = end - start;
+        let arena = Bump::new();
+        let layout = func.state_layout();
+        let nodes = (0..num_rows * 2)
+            .map(|_| {
+                let place: StateAddr = arena.alloc_layout(layout).into();
+                func.init_state(place);
+                place
+            })
+            .collect::<Vec<_>>();
+        let tree = SegmentTree {
+            func,
+            nodes,
+            _arena: arena,
+        };
+
+        for row in 0..num_rows {
+            tree.func
+                .accumulate_row(tree.nodes[num_rows + row], arg_columns, start + row)?;
+        }
+        for node in (1..num_rows).rev() {
+            tree.func.merge(tree.nodes[node], tree.nodes[node * 2])?;
+            tree.func
+                .merge(tree.nodes[node], tree.nodes[node * 2 + 1])?;
+        }
+        Ok(tree)
+    }
+
+    /// Merge the states of rows `[from, to)` into `place`, in the order of the rows.
+    fn aggregate(&self, place: StateAddr, from: usize, to: usize) -> Result<()> {
+        let num_rows = self.nodes.len() / 2;
+        let (mut left, mut right) = (from + num_rows, to + num_rows);
+        let mut right_nodes = vec![];
+        while left < right {
+            if left & 1 == 1 {
+                self.func.merge(place, self.nodes[left])?;
+                left += 1;
+            }
+            if right & 1 == 1 {
+                right -= 1;
+                right_nodes.push(self.nodes[right]);
+            }
+            left >>= 1;
+            right >>= 1;
+        }
+        for node in right_nodes.into_iter().rev() {
+            self.func.merge(place, node)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SegmentTree {
+    fn drop(&mut self) {
+        if self.func.need_manual_drop_state() {
+            for place in self.nodes.iter() {
+                unsafe { self.func.drop_state(*place) }
+            }
+        }
+    }
+}
+
+impl Compactor for WindowCompactor {
+    fn name() -> &'static str {
+        "Window"
+    }
+
+    // `compact_final` is called when all the blocks are pushed
+    fn compact_final(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let block = DataBlock::concat_blocks(blocks)?;
+        let num_rows = block.num_rows();
+        let columns = block
+            .columns()
+            .iter()
+            .map(|column| column.convert_full_column())
+            .collect::<Vec<_>>();
+        let partition_by_columns = self
+            .partition_by
+            .iter()
+            .map(|index| columns[*index].clone())
+            .collect::<Vec<_>>();
+
+        let mut builder = self.data_type().create_mutable(num_rows);
+        let partitions = Self::split_groups(&partition_by_columns, 0, num_rows);
+        for partition in partitions.windows(2) {
+            self.eval_partition(&columns, partition[0], partition[1], builder.borrow_mut())?;
+        }
+
+        let column = builder.to_column();
+        Ok(vec![block.add_column(column, self.output_field.clone())?])
+    }
+}
+
+pub type TransformWindow = TransformCompact<WindowCompactor>;
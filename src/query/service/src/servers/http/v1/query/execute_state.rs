@@ -374,7 +374,7 @@ impl HttpQueryHandle {
         });
 
         let query_ctx = ctx.clone();
-        let executor_settings = ExecutorSettings::try_create(&ctx.get_settings())?;
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
 
         let run = move || -> Result<()> {
             let mut pipelines = build_res.sources_pipelines;
@@ -184,10 +184,14 @@ impl MySQLFederated {
                     format!("{}-{}", self.mysql_version, self.databend_version.clone()).as_str(),
                 ),
             ),
-            // Txn.
-            ("(?i)^(ROLLBACK(.*))", None),
-            ("(?i)^(COMMIT(.*))", None),
-            ("(?i)^(START(.*))", None),
+            // Txn, `START TRANSACTION` itself starts an explicit transaction.
+            ("(?i)^(START\\s*)$", None),
+            (
+                "(?i)^(START\\s+(SLAVE|REPLICA|GROUP_REPLICATION)(.*))",
+                None,
+            ),
+            // mysqldump --single-transaction.
+            ("(?i)^(START\\s+TRANSACTION\\s+WITH(.*))", None),
             // Set.
             ("(?i)^(SET NAMES(.*))", None),
             ("(?i)^(SET character_set_results(.*))", None),
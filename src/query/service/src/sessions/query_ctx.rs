@@ -26,6 +26,7 @@ use chrono_tz::Tz;
 use common_base::base::tokio::task::JoinHandle;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
+use common_base::base::RuntimeTracker;
 use common_base::base::TrySpawn;
 use common_catalog::plan::DataSourceInfo;
 use common_catalog::plan::DataSourcePlan;
@@ -41,6 +42,7 @@ use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 use common_io::prelude::FormatSettings;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -196,6 +198,10 @@ impl QueryContext {
     pub fn get_created_time(&self) -> SystemTime {
         self.shared.created_time
     }
+
+    pub fn get_runtime_tracker(&self) -> Arc<RuntimeTracker> {
+        self.shared.get_runtime_tracker()
+    }
 }
 
 #[async_trait::async_trait]
@@ -320,6 +326,19 @@ impl TableContext for QueryContext {
     fn consume_precommit_blocks(&self) -> Vec<DataBlock> {
         self.shared.consume_precommit_blocks()
     }
+    fn in_txn(&self) -> bool {
+        self.shared.session.in_txn()
+    }
+    fn buffer_txn_insertion(
+        &self,
+        table_info: &TableInfo,
+        operations: Vec<DataBlock>,
+        overwrite: bool,
+    ) -> Result<Vec<DataBlock>> {
+        self.shared
+            .session
+            .buffer_txn_insertion(table_info, operations, overwrite)
+    }
     fn try_get_function_context(&self) -> Result<FunctionContext> {
         let tz = self.get_settings().get_timezone()?;
         let tz = tz.parse::<Tz>().map_err(|_| {
@@ -354,6 +373,31 @@ impl TableContext for QueryContext {
         self.shared.get_table(catalog, database, table).await
     }
 
+    fn attach_table(&self, catalog: &str, database: &str, name: &str, table: Arc<dyn Table>) {
+        self.shared.attach_table(catalog, database, name, table)
+    }
+
+    fn get_attached_table(
+        &self,
+        catalog: &str,
+        database: &str,
+        table: &str,
+    ) -> Option<Arc<dyn Table>> {
+        self.shared.get_attached_table(catalog, database, table)
+    }
+
+    fn add_consumed_stream(&self, req: UpdateTableMetaReq) {
+        self.shared.add_consumed_stream(req)
+    }
+
+    fn get_consumed_streams(&self) -> Vec<UpdateTableMetaReq> {
+        self.shared.get_consumed_streams()
+    }
+
+    fn clear_consumed_streams(&self) {
+        self.shared.clear_consumed_streams()
+    }
+
     // Get all the processes list info.
     fn get_processes_info(&self) -> Vec<ProcessInfo> {
         SessionManager::instance().processes_info()
@@ -20,12 +20,15 @@ use std::sync::Arc;
 use std::sync::Weak;
 use std::time::SystemTime;
 
+use common_base::base::MemoryTracker;
 use common_base::base::Progress;
 use common_base::base::Runtime;
+use common_base::base::RuntimeTracker;
 use common_config::Config;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -43,6 +46,7 @@ use crate::pipelines::executor::PipelineExecutor;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
 use crate::sessions::Session;
+use crate::sessions::SessionManager;
 use crate::storages::Table;
 
 type DatabaseAndTable = (String, String, String);
@@ -67,6 +71,8 @@ pub struct QueryContextShared {
     pub(in crate::sessions) error: Arc<Mutex<Option<ErrorCode>>>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
+    /// Tracks the memory of the query runtime and the pipeline executor threads.
+    pub(in crate::sessions) runtime_tracker: Arc<RuntimeTracker>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
     pub(in crate::sessions) cluster_cache: Arc<Cluster>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
@@ -74,6 +80,8 @@ pub struct QueryContextShared {
     pub(in crate::sessions) http_query: Arc<RwLock<Option<HttpQueryHandle>>>,
     pub(in crate::sessions) aborting: Arc<AtomicBool>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
+    pub(in crate::sessions) attached_tables: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
+    pub(in crate::sessions) consumed_streams: Arc<Mutex<Vec<UpdateTableMetaReq>>>,
     pub(in crate::sessions) auth_manager: Arc<AuthMgr>,
     pub(in crate::sessions) affect: Arc<Mutex<Option<QueryAffect>>>,
     pub(in crate::sessions) catalog_manager: Arc<CatalogManager>,
@@ -89,6 +97,22 @@ impl QueryContextShared {
         session: Arc<Session>,
         cluster_cache: Arc<Cluster>,
     ) -> Result<Arc<QueryContextShared>> {
+        // The memory tracker of the query is a child of the memory tracker of the user,
+        // so that the running queries of the user are limited by max_memory_usage_for_user.
+        let settings = session.get_settings();
+        let max_memory_usage = settings.get_max_memory_usage()?;
+        let max_memory_usage_for_user = settings.get_max_memory_usage_for_user()?;
+        let parent_memory_tracker = match session.get_current_user() {
+            Ok(user) => Some(SessionManager::instance().get_user_memory_tracker(
+                &session.get_current_tenant(),
+                &user.name,
+                max_memory_usage_for_user,
+            )),
+            Err(_) => MemoryTracker::current(),
+        };
+        let memory_tracker = MemoryTracker::create(parent_memory_tracker);
+        memory_tracker.set_limit("max_memory_usage", max_memory_usage as i64);
+
         Ok(Arc::new(QueryContextShared {
             session,
             cluster_cache,
@@ -106,11 +130,14 @@ impl QueryContextShared {
             http_query: Arc::new(RwLock::new(None)),
             aborting: Arc::new(AtomicBool::new(false)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
+            attached_tables: Arc::new(Mutex::new(HashMap::new())),
+            consumed_streams: Arc::new(Mutex::new(Vec::new())),
             auth_manager: AuthMgr::create(config).await?,
             affect: Arc::new(Mutex::new(None)),
             executor: Arc::new(RwLock::new(Weak::new())),
             precommit_blocks: Arc::new(RwLock::new(vec![])),
             created_time: SystemTime::now(),
+            runtime_tracker: RuntimeTracker::create_with_memory_tracker(memory_tracker),
         }))
     }
 
@@ -207,6 +234,10 @@ impl QueryContextShared {
     ) -> Result<Arc<dyn Table>> {
         // Always get same table metadata in the same query
 
+        if let Some(attached) = self.get_attached_table(catalog, database, table) {
+            return Ok(attached);
+        }
+
         let table_meta_key = (catalog.to_string(), database.to_string(), table.to_string());
 
         let already_in_cache = { self.tables_refs.lock().contains_key(&table_meta_key) };
@@ -221,6 +252,39 @@ impl QueryContextShared {
         }
     }
 
+    /// Attach a table to the query by the name, it takes precedence over the table of the name
+    /// in the catalog for the whole query.
+    pub fn attach_table(&self, catalog: &str, database: &str, name: &str, table: Arc<dyn Table>) {
+        let table_meta_key = (catalog.to_string(), database.to_string(), name.to_string());
+        self.attached_tables.lock().insert(table_meta_key, table);
+    }
+
+    pub fn get_attached_table(
+        &self,
+        catalog: &str,
+        database: &str,
+        table: &str,
+    ) -> Option<Arc<dyn Table>> {
+        let table_meta_key = (catalog.to_string(), database.to_string(), table.to_string());
+        self.attached_tables.lock().get(&table_meta_key).cloned()
+    }
+
+    /// A stream read several times by the query is moved to the offset read first.
+    pub fn add_consumed_stream(&self, req: UpdateTableMetaReq) {
+        let mut consumed_streams = self.consumed_streams.lock();
+        if !consumed_streams.iter().any(|v| v.table_id == req.table_id) {
+            consumed_streams.push(req);
+        }
+    }
+
+    pub fn get_consumed_streams(&self) -> Vec<UpdateTableMetaReq> {
+        self.consumed_streams.lock().clone()
+    }
+
+    pub fn clear_consumed_streams(&self) {
+        self.consumed_streams.lock().clear();
+    }
+
     async fn get_table_to_cache(
         &self,
         catalog: &str,
@@ -248,9 +312,10 @@ impl QueryContextShared {
             Some(query_runtime) => Ok(query_runtime.clone()),
             None => {
                 // To avoid possible deadlock, we should keep at least two threads.
-                let runtime = Arc::new(Runtime::with_worker_threads(
+                let runtime = Arc::new(Runtime::with_worker_threads_and_tracker(
                     2,
                     Some("query-ctx".to_string()),
+                    self.runtime_tracker.clone(),
                 )?);
                 *query_runtime = Some(runtime.clone());
                 Ok(runtime)
@@ -258,6 +323,14 @@ impl QueryContextShared {
         }
     }
 
+    pub fn get_runtime_tracker(&self) -> Arc<RuntimeTracker> {
+        self.runtime_tracker.clone()
+    }
+
+    pub fn get_memory_usage(&self) -> i64 {
+        self.runtime_tracker.get_memory_tracker().get_memory_usage()
+    }
+
     pub fn attach_http_query_handle(&self, handle: HttpQueryHandle) {
         let mut http_query = self.http_query.write();
         *http_query = Some(handle);
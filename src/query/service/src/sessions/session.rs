@@ -16,10 +16,14 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use chrono_tz::Tz;
+use common_base::base::GlobalIORuntime;
+use common_catalog::table_context::PendingInsertion;
 use common_config::Config;
+use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::FormatSettings;
+use common_meta_app::schema::TableInfo;
 use common_meta_types::GrantObject;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
@@ -31,6 +35,7 @@ use futures::channel::*;
 use parking_lot::RwLock;
 
 use crate::clusters::ClusterDiscovery;
+use crate::interpreters::abort_insertions;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::QueryContext;
 use crate::sessions::QueryContextShared;
@@ -168,6 +173,37 @@ impl Session {
         });
     }
 
+    pub fn begin_txn(self: &Arc<Self>) {
+        self.session_ctx.begin_txn()
+    }
+
+    pub fn in_txn(self: &Arc<Self>) -> bool {
+        self.session_ctx.in_txn()
+    }
+
+    pub fn buffer_txn_insertion(
+        self: &Arc<Self>,
+        table_info: &TableInfo,
+        operations: Vec<DataBlock>,
+        overwrite: bool,
+    ) -> Result<Vec<DataBlock>> {
+        self.session_ctx
+            .buffer_txn_insertion(table_info, operations, overwrite)
+    }
+
+    pub fn take_txn(self: &Arc<Self>) -> Option<Vec<PendingInsertion>> {
+        self.session_ctx.take_txn()
+    }
+
+    async fn abort_txn_insertions(insertions: Vec<PendingInsertion>) -> Result<()> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::Dummy)
+            .await?;
+        let ctx = session.create_query_context().await?;
+        abort_insertions(&ctx, insertions).await;
+        Ok(())
+    }
+
     pub fn set_current_database(self: &Arc<Self>, database_name: String) {
         self.session_ctx.set_current_database(database_name);
     }
@@ -344,8 +380,10 @@ impl Session {
     }
 
     pub fn get_memory_usage(self: &Arc<Self>) -> usize {
-        // TODO(winter): use thread memory tracker
-        0
+        match self.session_ctx.get_query_context_shared() {
+            None => 0,
+            Some(shared) => shared.get_memory_usage().max(0) as usize,
+        }
     }
 
     pub fn get_config(&self) -> Config {
@@ -361,5 +399,20 @@ impl Drop for Session {
     fn drop(&mut self) {
         tracing::debug!("Drop session {}", self.id.clone());
         SessionManager::instance().destroy_session(&self.id.clone());
+
+        // The client is gone within an explicit transaction, e.g. the connection is lost,
+        // the insertions buffered by the transaction will never be committed.
+        if let Some(insertions) = self.session_ctx.take_txn() {
+            if !insertions.is_empty() {
+                GlobalIORuntime::instance().spawn(async move {
+                    if let Err(cause) = Session::abort_txn_insertions(insertions).await {
+                        tracing::warn!(
+                            "Abort the insertions of the transaction failure: {:?}",
+                            cause
+                        );
+                    }
+                });
+            }
+        }
     }
 }
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
 
+use common_catalog::table_context::PendingInsertion;
 use common_config::Config;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::TableInfo;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -52,6 +57,9 @@ pub struct SessionContext {
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     query_context_shared: RwLock<Weak<QueryContextShared>>,
+    // The insertions buffered by the explicit transaction, keyed by the table id.
+    // It is None if the session is not in an explicit transaction.
+    txn: RwLock<Option<BTreeMap<u64, PendingInsertion>>>,
 }
 
 impl SessionContext {
@@ -69,6 +77,7 @@ impl SessionContext {
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            txn: Default::default(),
         }))
     }
 
@@ -205,4 +214,56 @@ impl SessionContext {
         let mut lock = self.query_context_shared.write();
         *lock = ctx
     }
+
+    // Start an explicit transaction, it's a no-op if the session is in one already.
+    pub fn begin_txn(&self) {
+        let mut lock = self.txn.write();
+        if lock.is_none() {
+            *lock = Some(BTreeMap::new());
+        }
+    }
+
+    pub fn in_txn(&self) -> bool {
+        let lock = self.txn.read();
+        lock.is_some()
+    }
+
+    // Buffer the insertion into the table, and return the buffered operations superseded by it.
+    pub fn buffer_txn_insertion(
+        &self,
+        table_info: &TableInfo,
+        mut operations: Vec<DataBlock>,
+        overwrite: bool,
+    ) -> Result<Vec<DataBlock>> {
+        let mut lock = self.txn.write();
+        let pending = lock
+            .as_mut()
+            .ok_or_else(|| ErrorCode::Internal("The session is not in a transaction"))?;
+
+        let table_id = table_info.ident.table_id;
+        match pending.get_mut(&table_id) {
+            Some(insertion) if overwrite => {
+                insertion.overwrite = true;
+                Ok(std::mem::replace(&mut insertion.operations, operations))
+            }
+            Some(insertion) => {
+                insertion.operations.append(&mut operations);
+                Ok(vec![])
+            }
+            None => {
+                pending.insert(table_id, PendingInsertion {
+                    table_info: table_info.clone(),
+                    operations,
+                    overwrite,
+                });
+                Ok(vec![])
+            }
+        }
+    }
+
+    // End the explicit transaction, and take the insertions buffered by it.
+    pub fn take_txn(&self) -> Option<Vec<PendingInsertion>> {
+        let mut lock = self.txn.write();
+        lock.take().map(|pending| pending.into_values().collect())
+    }
 }
@@ -30,15 +30,10 @@ impl Session {
     }
 
     fn to_process_info(self: &Arc<Self>, status: &SessionContext) -> ProcessInfo {
-        let mut memory_usage = 0;
-
-        if let Some(shared) = status.get_query_context_shared() {
-            if let Ok(runtime) = shared.try_get_runtime() {
-                let runtime_tracker = runtime.get_tracker();
-                let runtime_memory_tracker = runtime_tracker.get_memory_tracker();
-                memory_usage = runtime_memory_tracker.get_memory_usage();
-            }
-        }
+        let memory_usage = match status.get_query_context_shared() {
+            None => 0,
+            Some(shared) => shared.get_memory_usage(),
+        };
 
         ProcessInfo {
             id: self.id.clone(),
@@ -22,6 +22,7 @@ use std::sync::Weak;
 use std::time::Duration;
 
 use common_base::base::tokio;
+use common_base::base::MemoryTracker;
 use common_base::base::SignalStream;
 use common_base::base::Singleton;
 use common_config::Config;
@@ -52,6 +53,10 @@ pub struct SessionManager {
     // When typ is MySQL, insert into this map, key is id, val is MySQL connection id.
     pub(crate) mysql_conn_map: Arc<RwLock<HashMap<Option<u32>, String>>>,
     pub(in crate::sessions) mysql_basic_conn_id: AtomicU32,
+    /// The memory trackers of the users with running queries on the node, keyed by
+    /// (tenant, user), the memory trackers of the queries are children of them.
+    pub(in crate::sessions) user_memory_trackers:
+        Arc<RwLock<HashMap<(String, String), Weak<MemoryTracker>>>>,
 }
 
 static SESSION_MANAGER: OnceCell<Singleton<Arc<SessionManager>>> = OnceCell::new();
@@ -73,6 +78,7 @@ impl SessionManager {
             status: Arc::new(RwLock::new(SessionManagerStatus::default())),
             mysql_conn_map: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
+            user_memory_trackers: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -232,6 +238,36 @@ impl SessionManager {
         processes_info
     }
 
+    /// Get the memory tracker shared by the running queries of the user, the limit of the
+    /// tracker is the `max_memory_usage_for_user` setting when the first of them starts.
+    pub fn get_user_memory_tracker(
+        &self,
+        tenant: &str,
+        user: &str,
+        max_memory_usage_for_user: u64,
+    ) -> Arc<MemoryTracker> {
+        let key = (tenant.to_string(), user.to_string());
+        let mut user_memory_trackers = self.user_memory_trackers.write();
+
+        let memory_tracker = match user_memory_trackers.get(&key).and_then(Weak::upgrade) {
+            Some(memory_tracker) => memory_tracker,
+            None => {
+                // Clean up the trackers of the users without running queries.
+                user_memory_trackers.retain(|_, tracker| tracker.strong_count() > 0);
+
+                let memory_tracker = MemoryTracker::create(MemoryTracker::current());
+                memory_tracker.set_limit(
+                    "max_memory_usage_for_user",
+                    max_memory_usage_for_user as i64,
+                );
+                user_memory_trackers.insert(key, Arc::downgrade(&memory_tracker));
+                memory_tracker
+            }
+        };
+
+        memory_tracker
+    }
+
     fn destroy_idle_sessions(sessions: &Arc<RwLock<HashMap<String, Weak<Session>>>>) -> bool {
         // Read lock does not support reentrant
         // https://github.com/Amanieu/parking_lot::/blob/lock_api-0.4.4/lock_api/src/rwlock.rs#L422
@@ -0,0 +1,240 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::plan::StageTableInfo;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_types::StageFileFormatType;
+use common_sql::parse_stage_location_v2;
+use common_storages_stage::StageTable;
+
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSource;
+use crate::pipelines::processors::AsyncSourcer;
+use crate::pipelines::Pipe;
+use crate::pipelines::Pipeline;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+use crate::table_functions::table_function_factory::TableArgs;
+use crate::table_functions::TableFunction;
+
+const INFER_SCHEMA: &str = "infer_schema";
+
+/// `INFER_SCHEMA(location => '@stage/path', file_format => 'parquet')` returns the columns of
+/// the files in the location, as inferred by `SELECT FROM @stage`.
+pub struct InferSchemaTable {
+    table_info: TableInfo,
+    location: String,
+    file_format: Option<String>,
+}
+
+impl InferSchemaTable {
+    pub fn create(
+        database_name: &str,
+        table_func_name: &str,
+        table_id: u64,
+        table_args: TableArgs,
+    ) -> Result<Arc<dyn TableFunction>> {
+        let args = table_args
+            .unwrap_or_default()
+            .iter()
+            .map(|arg| {
+                String::from_utf8(arg.as_string()?)
+                    .map_err(|e| ErrorCode::BadArguments(format!("invalid string. {}", e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (location, file_format) = match args.as_slice() {
+            [location] => (location.clone(), None),
+            [location, file_format] => (location.clone(), Some(file_format.clone())),
+            _ => {
+                return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                    "{} expects 1 or 2 arguments, but got {}",
+                    table_func_name,
+                    args.len()
+                )));
+            }
+        };
+
+        let table_info = TableInfo {
+            ident: TableIdent::new(table_id, 0),
+            desc: format!("'{}'.'{}'", database_name, table_func_name),
+            name: table_func_name.to_string(),
+            meta: TableMeta {
+                schema: Self::schema(),
+                engine: INFER_SCHEMA.to_owned(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        Ok(Arc::new(InferSchemaTable {
+            table_info,
+            location,
+            file_format,
+        }))
+    }
+
+    pub fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("column_name", Vu8::to_data_type()),
+            DataField::new("type", Vu8::to_data_type()),
+            DataField::new("nullable", bool::to_data_type()),
+        ])
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for InferSchemaTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read_partitions(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        Ok((PartStatistics::default(), vec![]))
+    }
+
+    fn table_args(&self) -> Option<Vec<DataValue>> {
+        let mut args = vec![DataValue::String(self.location.as_bytes().to_vec())];
+        if let Some(file_format) = &self.file_format {
+            args.push(DataValue::String(file_format.as_bytes().to_vec()));
+        }
+        Some(args)
+    }
+
+    fn read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        _plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let output = OutputPort::create();
+        pipeline.add_pipe(Pipe::SimplePipe {
+            inputs_port: vec![],
+            outputs_port: vec![output.clone()],
+            processors: vec![InferSchemaSource::create(
+                ctx,
+                output,
+                self.location.clone(),
+                self.file_format.clone(),
+            )?],
+        });
+
+        Ok(())
+    }
+}
+
+impl TableFunction for InferSchemaTable {
+    fn function_name(&self) -> &str {
+        self.name()
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
+
+struct InferSchemaSource {
+    finish: bool,
+    ctx: Arc<dyn TableContext>,
+    location: String,
+    file_format: Option<String>,
+}
+
+impl InferSchemaSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        location: String,
+        file_format: Option<String>,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, InferSchemaSource {
+            finish: false,
+            ctx,
+            location,
+            file_format,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSource for InferSchemaSource {
+    const NAME: &'static str = INFER_SCHEMA;
+
+    #[async_trait::unboxed_simple]
+    async fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.finish {
+            return Ok(None);
+        }
+        self.finish = true;
+
+        let location = self.location.strip_prefix('@').ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "{} expects a stage location like '@stage/path', but got '{}'",
+                INFER_SCHEMA, self.location
+            ))
+        })?;
+        let (stage_name, path) = location.split_once('/').unwrap_or((location, ""));
+        let (mut stage_info, path) = parse_stage_location_v2(&self.ctx, stage_name, path).await?;
+        if let Some(file_format) = &self.file_format {
+            stage_info.file_format_options.format =
+                StageFileFormatType::from_str(file_format).map_err(ErrorCode::UnknownFormat)?;
+        }
+
+        let table = StageTable::try_create(StageTableInfo {
+            schema: DataSchemaRefExt::create(vec![]),
+            path,
+            files: vec![],
+            pattern: "".to_string(),
+            user_stage_info: stage_info,
+        })?;
+        let schema = table.infer_schema(self.ctx.clone()).await?;
+
+        let mut names: Vec<String> = Vec::with_capacity(schema.num_fields());
+        let mut types: Vec<String> = Vec::with_capacity(schema.num_fields());
+        let mut nullables: Vec<bool> = Vec::with_capacity(schema.num_fields());
+        for field in schema.fields() {
+            names.push(field.name().to_string());
+            types.push(format_data_type_sql(&remove_nullable(field.data_type())));
+            nullables.push(field.is_nullable());
+        }
+        Ok(Some(DataBlock::create(InferSchemaTable::schema(), vec![
+            Series::from_data(names),
+            Series::from_data(types),
+            Series::from_data(nullables),
+        ])))
+    }
+}
@@ -13,6 +13,7 @@
 //  limitations under the License.
 
 mod async_crash_me;
+mod infer_schema_table;
 mod memory_block_part;
 mod numbers_part;
 mod numbers_table;
@@ -20,6 +21,7 @@ mod sync_crash_me;
 mod table_function;
 mod table_function_factory;
 
+pub use infer_schema_table::InferSchemaTable;
 pub use memory_block_part::generate_numbers_parts;
 pub use numbers_part::NumbersPartInfo;
 pub use numbers_table::NumbersTable;
@@ -29,6 +29,7 @@ use crate::storages::fuse::table_functions::FuseSegmentTable;
 use crate::storages::fuse::table_functions::FuseSnapshotTable;
 use crate::table_functions::async_crash_me::AsyncCrashMeTable;
 use crate::table_functions::sync_crash_me::SyncCrashMeTable;
+use crate::table_functions::InferSchemaTable;
 use crate::table_functions::NumbersTable;
 use crate::table_functions::TableFunction;
 
@@ -126,6 +127,11 @@ impl TableFunctionFactory {
             (next_id(), Arc::new(AsyncCrashMeTable::create)),
         );
 
+        creators.insert(
+            "infer_schema".to_string(),
+            (next_id(), Arc::new(InferSchemaTable::create)),
+        );
+
         TableFunctionFactory {
             creators: RwLock::new(creators),
         }
@@ -35,6 +35,7 @@ num_cpus = 0
 mysql_handler_host = "127.0.0.1"
 mysql_handler_port = 3307
 max_active_sessions = 256
+max_server_memory_usage = 0
 clickhouse_handler_host = "127.0.0.1"
 clickhouse_handler_port = 9000
 clickhouse_http_handler_host = "127.0.0.1"
@@ -68,6 +69,9 @@ table_cache_snapshot_count = 256
 table_cache_segment_count = 10240
 table_cache_bloom_index_meta_count = 3000
 table_cache_bloom_index_data_bytes = 1073741824
+data_retention_purge_interval = 0
+auto_compaction_interval = 0
+auto_compaction_max_concurrency = 1
 management_mode = false
 jwt_key_file = ""
 async_insert_max_data_size = 10000
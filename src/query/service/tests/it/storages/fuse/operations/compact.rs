@@ -0,0 +1,82 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_catalog::table::CompactTarget;
+use common_datablocks::BlockCompactThresholds;
+use common_storages_fuse::operations::auto_compact_target;
+use common_storages_table_meta::meta::Statistics;
+
+fn summary(row_count: u64, block_count: u64, uncompressed_byte_size: u64) -> Statistics {
+    Statistics {
+        row_count,
+        block_count,
+        uncompressed_byte_size,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_auto_compact_target() {
+    // at most 100 rows or 1000 bytes per block, 10 blocks per segment
+    let thresholds = BlockCompactThresholds::new(100, 80, 1000);
+    let target = |summary: &Statistics, segment_count: usize| {
+        auto_compact_target(summary, segment_count, &thresholds, 10)
+    };
+
+    // nothing to compact with a single block
+    assert!(target(&summary(10, 1, 10), 1).is_none());
+    assert!(target(&summary(0, 0, 0), 0).is_none());
+
+    // 1000 rows fit in 10 blocks, 20 blocks are fine, 21 are too many
+    assert!(target(&summary(1000, 20, 1000), 2).is_none());
+    assert!(matches!(
+        target(&summary(1000, 21, 1000), 3),
+        Some(CompactTarget::Blocks)
+    ));
+
+    // the bytes count as well: 10 blocks of 1000 rows but 20000 bytes need 20 blocks
+    assert!(target(&summary(1000, 40, 20000), 4).is_none());
+    assert!(matches!(
+        target(&summary(1000, 41, 20000), 5),
+        Some(CompactTarget::Blocks)
+    ));
+
+    // two small blocks are never too many
+    assert!(target(&summary(2, 2, 2), 1).is_none());
+
+    // 20 blocks fit in 2 segments, 4 segments are fine, 5 are too many
+    assert!(target(&summary(2000, 20, 2000), 4).is_none());
+    assert!(matches!(
+        target(&summary(2000, 20, 2000), 5),
+        Some(CompactTarget::Segments)
+    ));
+
+    // the blocks are compacted before the segments
+    assert!(matches!(
+        target(&summary(1000, 21, 1000), 21),
+        Some(CompactTarget::Blocks)
+    ));
+
+    // the thresholds of 0 are taken as 1
+    let thresholds = BlockCompactThresholds::new(0, 0, 0);
+    assert!(matches!(
+        auto_compact_target(&summary(2, 5, 2), 1, &thresholds, 0),
+        Some(CompactTarget::Blocks)
+    ));
+    assert!(auto_compact_target(&summary(2, 2, 2), 2, &thresholds, 0).is_none());
+    assert!(matches!(
+        auto_compact_target(&summary(2, 2, 2), 5, &thresholds, 0),
+        Some(CompactTarget::Segments)
+    ));
+}
@@ -0,0 +1,151 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionType;
+use databend_query::sessions::TableContext;
+use futures::future::try_join_all;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+// The transactions are per session, concurrent statements run in different sessions.
+async fn create_session_context(fixture: &TestFixture) -> Result<Arc<QueryContext>> {
+    let session = SessionManager::instance()
+        .create_session(SessionType::Dummy)
+        .await?;
+    session
+        .set_authed_user(fixture.ctx().get_current_user()?, None)
+        .await?;
+    session.create_query_context().await
+}
+
+#[tokio::test]
+async fn test_refresh_materialized_view_concurrently() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    execute_command(ctx.clone(), &format!("create table {db}.t(a int, b int)")).await?;
+    execute_command(
+        ctx.clone(),
+        &format!(
+            "create materialized view {db}.mv as select a, count(*) as c, sum(b) as s from {db}.t group by a"
+        ),
+    )
+    .await?;
+
+    // Each insertion refreshes the view, the refreshes of the concurrent insertions race on it.
+    // The refreshes which lose the race fail, leaving the view stale rather than wrong.
+    let mut insertions = vec![];
+    for i in 0..4 {
+        let ctx = create_session_context(&fixture).await?;
+        let sql = format!("insert into {db}.t values (1, {i})");
+        insertions.push(async move { execute_command(ctx, &sql).await });
+    }
+    try_join_all(insertions).await?;
+
+    // A stale view is fully refreshed by the next insertion.
+    execute_command(ctx.clone(), &format!("insert into {db}.t values (1, 10)")).await?;
+
+    let blocks = execute_query(ctx.clone(), &format!("select a, c, s from {db}.mv"))
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let expected = vec![
+        "+---+---+----+", //
+        "| a | c | s  |", //
+        "+---+---+----+", //
+        "| 1 | 5 | 16 |", //
+        "+---+---+----+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    // The view is fresh to the latest snapshot of the base table.
+    let catalog = ctx.get_catalog("default")?;
+    let tenant = ctx.get_tenant();
+    let base_table = catalog.get_table(&tenant, &db, "t").await?;
+    let view_table = catalog.get_table(&tenant, &db, "mv").await?;
+    assert_eq!(
+        base_table.options().get(OPT_KEY_SNAPSHOT_LOCATION),
+        view_table
+            .options()
+            .get(OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh_materialized_view_on_commit() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = create_session_context(&fixture).await?;
+
+    execute_command(ctx.clone(), &format!("create table {db}.t(a int, b int)")).await?;
+    execute_command(
+        ctx.clone(),
+        &format!(
+            "create materialized view {db}.mv as select a, count(*) as c, sum(b) as s from {db}.t group by a"
+        ),
+    )
+    .await?;
+    execute_command(ctx.clone(), &format!("insert into {db}.t values (1, 1)")).await?;
+
+    execute_command(ctx.clone(), "begin").await?;
+    execute_command(ctx.clone(), &format!("insert into {db}.t values (1, 2)")).await?;
+    execute_command(ctx.clone(), &format!("insert into {db}.t values (2, 3)")).await?;
+
+    // The insertions are not committed yet, neither is the refresh of the view.
+    let blocks = execute_query(ctx.clone(), &format!("select a, c, s from {db}.mv"))
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let expected = vec![
+        "+---+---+---+", //
+        "| a | c | s |", //
+        "+---+---+---+", //
+        "| 1 | 1 | 1 |", //
+        "+---+---+---+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    execute_command(ctx.clone(), "commit").await?;
+
+    let blocks = execute_query(ctx.clone(), &format!("select a, c, s from {db}.mv"))
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let expected = vec![
+        "+---+---+---+", //
+        "| a | c | s |", //
+        "+---+---+---+", //
+        "| 1 | 2 | 3 |", //
+        "| 2 | 1 | 3 |", //
+        "+---+---+---+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    Ok(())
+}
@@ -13,6 +13,8 @@
 //  limitations under the License.
 
 mod commit;
+mod compact;
+mod materialized_view;
 mod mutation;
 mod navigate;
 mod optimize;
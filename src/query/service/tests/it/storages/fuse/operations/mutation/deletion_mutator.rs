@@ -99,6 +99,8 @@ async fn test_deletion_mutator_multiple_empty_segments() -> Result<()> {
         location_generator,
         Arc::new(base_snapshot),
         ClusterStatsGenerator::default(),
+        vec![],
+        vec![],
     )?;
 
     // clear half of the segments
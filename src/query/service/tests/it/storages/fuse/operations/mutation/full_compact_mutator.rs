@@ -183,7 +183,7 @@ async fn build_mutator(
     assert!(mutator.is_some());
     let mutator = mutator.unwrap();
     pipeline.set_max_threads(1);
-    let executor_settings = ExecutorSettings::try_create(&settings)?;
+    let executor_settings = ExecutorSettings::try_create(&ctx)?;
     let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
     ctx.set_executor(Arc::downgrade(&executor.get_inner()));
     executor.execute()?;
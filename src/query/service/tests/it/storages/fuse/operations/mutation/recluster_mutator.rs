@@ -119,6 +119,7 @@ async fn test_recluster_mutator_block_select() -> Result<()> {
         &ctx,
         data_accessor.clone(),
         schema,
+        &[],
         &None,
         segments_location,
     )
@@ -197,6 +197,165 @@ async fn test_compact_segment_unresolvable_conflict() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_compact_segment_resolvable_conflict_with_mutation() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // setup
+    let create_tbl_command = "create table t(c int)  block_per_segment=2";
+    execute_command(ctx.clone(), create_tbl_command).await?;
+
+    let catalog = ctx.get_catalog("default")?;
+
+    // the oldest segment contains `c = 2`, the others contain `c = 1`
+    execute_command(ctx.clone(), "insert into t values(2)").await?;
+    let num_inserts = 2;
+    append_rows(ctx.clone(), num_inserts).await?;
+
+    // compact the 2 latest segments only
+    let table = catalog
+        .get_table(ctx.get_tenant().as_str(), "default", "t")
+        .await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let mut pipeline = common_pipeline_core::Pipeline::create();
+    let mutator = fuse_table
+        .compact(ctx.clone(), CompactTarget::Segments, Some(2), &mut pipeline)
+        .await?;
+    assert!(mutator.is_some());
+    let mutator = mutator.unwrap();
+
+    // before commit compact segments, the oldest segment is removed by a deletion
+    execute_command(ctx.clone(), "delete from t where c = 2").await?;
+
+    // segments touched by the compaction and the deletion are disjoint
+    mutator.try_commit(table.clone()).await?;
+
+    // check segment count
+    let count_seg = "select segment_count as count from fuse_snapshot('default', 't') limit 1";
+    let stream = execute_query(fixture.ctx(), count_seg).await?;
+    assert_eq!(1, check_count(stream).await?);
+
+    // check row count
+    let count_qry = "select count(*) from t where c = 1";
+    let stream = execute_query(fixture.ctx(), count_qry).await?;
+    assert_eq!(num_inserts as u64, check_count(stream).await?);
+
+    // check table statistics
+    let latest = table.refresh(ctx.as_ref()).await?;
+    let latest_fuse_table = FuseTable::try_from_table(latest.as_ref())?;
+    let table_statistics = latest_fuse_table.table_statistics()?.unwrap();
+    assert_eq!(table_statistics.num_rows.unwrap() as usize, num_inserts);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compact_segment_unresolvable_conflict_with_mutation() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // setup
+    let create_tbl_command = "create table t(c int)  block_per_segment=2";
+    execute_command(ctx.clone(), create_tbl_command).await?;
+
+    let catalog = ctx.get_catalog("default")?;
+
+    // the oldest segment contains `c = 2`, the others contain `c = 1`
+    execute_command(ctx.clone(), "insert into t values(2)").await?;
+    let num_inserts = 2;
+    append_rows(ctx.clone(), num_inserts).await?;
+
+    // compact the 2 latest segments only
+    let table = catalog
+        .get_table(ctx.get_tenant().as_str(), "default", "t")
+        .await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let mut pipeline = common_pipeline_core::Pipeline::create();
+    let mutator = fuse_table
+        .compact(ctx.clone(), CompactTarget::Segments, Some(2), &mut pipeline)
+        .await?;
+    assert!(mutator.is_some());
+    let mutator = mutator.unwrap();
+
+    // before commit compact segments, the segments being compacted are removed by a deletion
+    execute_command(ctx.clone(), "delete from t where c = 1").await?;
+
+    // the compaction committed latter should fail
+    let r = mutator.try_commit(table.clone()).await;
+    assert!(r.is_err());
+    assert_eq!(r.err().unwrap().code(), ErrorCode::STORAGE_OTHER);
+
+    // the deletion is kept
+    let count_qry = "select count(*) from t";
+    let stream = execute_query(fixture.ctx(), count_qry).await?;
+    assert_eq!(1, check_count(stream).await?);
+
+    let count_seg = "select segment_count as count from fuse_snapshot('default', 't') limit 1";
+    let stream = execute_query(fixture.ctx(), count_seg).await?;
+    assert_eq!(1, check_count(stream).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compact_segment_resolvable_conflict_with_mutation_and_append() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // setup
+    let create_tbl_command = "create table t(c int)  block_per_segment=2";
+    execute_command(ctx.clone(), create_tbl_command).await?;
+
+    let catalog = ctx.get_catalog("default")?;
+
+    // the oldest segment contains `c = 2`, the others contain `c = 1`
+    execute_command(ctx.clone(), "insert into t values(2)").await?;
+    let num_inserts = 2;
+    append_rows(ctx.clone(), num_inserts).await?;
+
+    // compact the 2 latest segments only
+    let table = catalog
+        .get_table(ctx.get_tenant().as_str(), "default", "t")
+        .await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let mut pipeline = common_pipeline_core::Pipeline::create();
+    let mutator = fuse_table
+        .compact(ctx.clone(), CompactTarget::Segments, Some(2), &mut pipeline)
+        .await?;
+    assert!(mutator.is_some());
+    let mutator = mutator.unwrap();
+
+    // before commit compact segments, the oldest segment is removed by a deletion,
+    // and then a new segment is appended
+    execute_command(ctx.clone(), "delete from t where c = 2").await?;
+    execute_command(ctx.clone(), "insert into t values(3)").await?;
+
+    mutator.try_commit(table.clone()).await?;
+
+    // the compacted segment and the appended one are left
+    let count_seg = "select segment_count as count from fuse_snapshot('default', 't') limit 1";
+    let stream = execute_query(fixture.ctx(), count_seg).await?;
+    assert_eq!(2, check_count(stream).await?);
+
+    // check row count
+    let count_qry = "select count(*) from t where c = 1";
+    let stream = execute_query(fixture.ctx(), count_qry).await?;
+    assert_eq!(num_inserts as u64, check_count(stream).await?);
+
+    let count_qry = "select count(*) from t where c = 2";
+    let stream = execute_query(fixture.ctx(), count_qry).await?;
+    assert_eq!(0, check_count(stream).await?);
+
+    // check table statistics
+    let latest = table.refresh(ctx.as_ref()).await?;
+    let latest_fuse_table = FuseTable::try_from_table(latest.as_ref())?;
+    let table_statistics = latest_fuse_table.table_statistics()?.unwrap();
+    assert_eq!(table_statistics.num_rows.unwrap() as usize, num_inserts + 1);
+
+    Ok(())
+}
+
 async fn append_rows(ctx: Arc<QueryContext>, n: usize) -> Result<()> {
     let qry = "insert into t values(1)";
     for _ in 0..n {
@@ -568,6 +727,7 @@ impl CompactSegmentTestFixture {
                 compressed_byte_size: stats_acc.file_size,
                 index_size: stats_acc.index_size,
                 col_stats,
+                col_distributions: Default::default(),
             });
             let location = segment_writer.write_segment_no_cache(&segment_info).await?;
             segments.push((segment_info, location));
@@ -104,7 +104,7 @@ fn test_to_partitions() -> Result<()> {
     let column_leafs = ColumnLeaves { column_leaves };
 
     // CASE I:  no projection
-    let (s, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, None);
+    let (s, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, None, None);
     assert_eq!(parts.len(), num_of_block as usize);
     let expected_block_size: u64 = cols_metas.values().map(|col_meta| col_meta.len).sum();
     assert_eq!(expected_block_size * num_of_block, s.read_bytes as u64);
@@ -134,7 +134,7 @@ fn test_to_partitions() -> Result<()> {
         stage: None,
     });
 
-    let (stats, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, push_down);
+    let (stats, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, None, push_down);
     assert_eq!(parts.len(), num_of_block as usize);
     assert_eq!(expected_block_size * num_of_block, stats.read_bytes as u64);
 
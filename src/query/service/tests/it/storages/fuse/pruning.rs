@@ -52,7 +52,7 @@ async fn apply_block_pruning(
 ) -> Result<Vec<Arc<BlockMeta>>> {
     let ctx: Arc<dyn TableContext> = ctx;
     let segment_locs = table_snapshot.segments.clone();
-    BlockPruner::prune(&ctx, op, schema, push_down, segment_locs)
+    BlockPruner::prune(&ctx, op, schema, &[], push_down, segment_locs)
         .await
         .map(|v| v.into_iter().map(|(_, v)| v).collect())
 }
@@ -91,6 +91,8 @@ async fn test_block_pruner() -> Result<()> {
         field_default_exprs: vec![],
         field_comments: vec![],
         as_select: None,
+        clone_source: None,
+        template: None,
         cluster_key: None,
     };
 
@@ -242,6 +244,8 @@ async fn test_block_pruner_monotonic() -> Result<()> {
         field_default_exprs: vec![],
         field_comments: vec![],
         as_select: None,
+        clone_source: None,
+        template: None,
         cluster_key: None,
     };
 
@@ -147,6 +147,29 @@ fn test_reduce_block_statistics_in_memory_size() -> common_exception::Result<()>
     Ok(())
 }
 
+#[test]
+fn test_reduce_block_statistics_missing_columns() -> common_exception::Result<()> {
+    let stats = |v: i64| ColumnStatistics {
+        min: DataValue::Int64(v),
+        max: DataValue::Int64(v),
+        null_count: 0,
+        in_memory_size: 8,
+    };
+
+    // column 1 is only in the statistics of the latter block, e.g. added by alter table
+    let col_stats_left = HashMap::from([(0, stats(1))]);
+    let col_stats_right = HashMap::from([(0, stats(2)), (1, stats(3))]);
+    let r = reducers::reduce_block_statistics(&[col_stats_left, col_stats_right.clone()])?;
+    assert_eq!(1, r.len());
+    assert_eq!(r.get(&0).unwrap().min, DataValue::Int64(1));
+    assert_eq!(r.get(&0).unwrap().max, DataValue::Int64(2));
+
+    // empty statistics are ignored
+    let r = reducers::reduce_block_statistics(&[HashMap::new(), col_stats_right])?;
+    assert_eq!(2, r.len());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_accumulator() -> common_exception::Result<()> {
     let blocks = TestFixture::gen_sample_blocks(10, 1);
@@ -302,6 +302,8 @@ async fn test_fuse_alter_table_cluster_key() -> Result<()> {
         field_default_exprs: vec![],
         field_comments: vec![],
         as_select: None,
+        clone_source: None,
+        template: None,
         cluster_key: None,
     };
 
@@ -158,6 +158,8 @@ impl TestFixture {
             field_default_exprs: vec![],
             field_comments: vec![],
             as_select: None,
+            clone_source: None,
+            template: None,
             cluster_key: Some("(id)".to_string()),
         }
     }
@@ -181,6 +183,8 @@ impl TestFixture {
             field_default_exprs: vec![],
             field_comments: vec![],
             as_select: None,
+            clone_source: None,
+            template: None,
             cluster_key: None,
         }
     }
@@ -419,7 +423,7 @@ pub async fn execute_query(ctx: Arc<QueryContext>, query: &str) -> Result<Sendab
 }
 
 pub fn execute_pipeline(ctx: Arc<QueryContext>, mut res: PipelineBuildResult) -> Result<()> {
-    let executor_settings = ExecutorSettings::try_create(&ctx.get_settings())?;
+    let executor_settings = ExecutorSettings::try_create(&ctx)?;
     res.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
     let mut pipelines = res.sources_pipelines;
     pipelines.push(res.main_pipeline);
@@ -249,6 +249,30 @@ fn test_build_verifiable_function() -> Result<()> {
                 .and(&col("b", i32::to_data_type()).not_eq(&lit(3))?)?,
             expect: "(((min_a <= 1) and (max_a >= 1)) and ((min_b != 3) or (max_b != 3)))",
         },
+        Test {
+            name: "a in (1, 2)",
+            expr: in_list(col("a", i64::to_data_type()), vec![
+                DataValue::Int64(1),
+                DataValue::Int64(2),
+            ]),
+            expect: "(((min_a <= 1) and (max_a >= 1)) or ((min_a <= 2) and (max_a >= 2)))",
+        },
+        Test {
+            name: "a not in (1, 2)",
+            expr: in_list(col("a", i64::to_data_type()), vec![
+                DataValue::Int64(1),
+                DataValue::Int64(2),
+            ])
+            .unary_op("not")?,
+            expect: "(((min_a != 1) or (max_a != 1)) and ((min_a != 2) or (max_a != 2)))",
+        },
+        Test {
+            name: "not (a = 1 or b = 3)",
+            expr: ExpressionOp::eq(&col("a", i64::to_data_type()), &lit(1))?
+                .or(&ExpressionOp::eq(&col("b", i32::to_data_type()), &lit(3))?)?
+                .unary_op("not")?,
+            expect: "(((min_a != 1) or (max_a != 1)) and ((min_b != 3) or (max_b != 3)))",
+        },
         Test {
             name: "a is null",
             expr: col("a", i64::to_data_type()).unary_op("is_null")?,
@@ -347,6 +371,18 @@ fn test_build_verifiable_function() -> Result<()> {
     Ok(())
 }
 
+fn in_list(expr: Expression, values: Vec<DataValue>) -> Expression {
+    let types = values.iter().map(|v| v.data_type()).collect();
+    Expression::Function {
+        name: "in".to_string(),
+        args: vec![expr, Expression::Constant {
+            value: DataValue::Struct(values),
+            data_type: StructType::new_impl(None, types),
+        }],
+        return_type: bool::to_data_type(),
+    }
+}
+
 #[test]
 fn test_bound_for_like_pattern() -> Result<()> {
     struct Test {
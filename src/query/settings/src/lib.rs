@@ -33,9 +33,11 @@ use common_users::UserApiProvider;
 use dashmap::DashMap;
 use itertools::Itertools;
 
+// The settings shared by all the sessions of the tenant, they can't be overridden in a session.
+static GLOBAL_ONLY_SETTINGS: &[&str] = &["max_memory_usage_for_user"];
+
 #[derive(Clone)]
 enum ScopeLevel {
-    #[allow(dead_code)]
     Global,
     Session,
 }
@@ -96,7 +98,7 @@ impl Settings {
                     tracing::warn!("Ignore deprecated global setting {} = {}", name, val);
                     continue;
                 }
-                settings.set_settings(name.clone(), val, false)?;
+                settings.apply_setting(name.clone(), val, false)?;
                 settings.set_setting_level(&name, true)?;
             }
             settings
@@ -301,6 +303,36 @@ impl Settings {
                 desc: "The threshold of keys to open two-level aggregation, default value: 10000.",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_spill_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The memory bytes of aggregation states to spill to storage, 0 means disabled, default value: 0.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "join_spill_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The memory bytes of hash join build side to spill to storage, 0 means disabled, default value: 0.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "sort_spill_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The memory bytes of sorting blocks to spill to storage, 0 means disabled, default value: 0.",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
                 user_setting: UserSetting::create(
@@ -376,6 +408,36 @@ impl Settings {
                 desc: "The maximum query execution time. it means no limit if the value is zero. default value: 0.",
                 possible_values: None,
             },
+            // max_memory_usage
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create("max_memory_usage", UserSettingValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The maximum memory bytes of a single query, 0 means unlimited, default value: 0.",
+                possible_values: None,
+            },
+            // max_memory_usage_for_user
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "max_memory_usage_for_user",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Global,
+                desc: "The maximum memory bytes of all the running queries of a user, 0 means unlimited, default value: 0.",
+                possible_values: None,
+            },
+            // max_recursion_depth
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1000),
+                user_setting: UserSetting::create(
+                    "max_recursion_depth",
+                    UserSettingValue::UInt64(1000),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The maximum number of iterations of a recursive CTE, default value: 1000.",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::String("binary".to_owned()),
                 user_setting: UserSetting::create(
@@ -463,6 +525,22 @@ impl Settings {
         self.try_set_u64("max_execute_time", val, false)
     }
 
+    // Get max_memory_usage.
+    pub fn get_max_memory_usage(&self) -> Result<u64> {
+        self.try_get_u64("max_memory_usage")
+    }
+
+    // Get max_memory_usage_for_user.
+    pub fn get_max_memory_usage_for_user(&self) -> Result<u64> {
+        self.try_get_u64("max_memory_usage_for_user")
+    }
+
+    // Get max_recursion_depth.
+    pub fn get_max_recursion_depth(&self) -> Result<u64> {
+        let key = "max_recursion_depth";
+        self.try_get_u64(key)
+    }
+
     // Get flight client timeout.
     pub fn get_flight_client_timeout(&self) -> Result<u64> {
         let key = "flight_client_timeout";
@@ -554,6 +632,24 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    // Get group by spilling bytes threshold
+    pub fn get_group_by_spill_bytes_threshold(&self) -> Result<u64> {
+        let key = "group_by_spill_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Get hash join spilling bytes threshold
+    pub fn get_join_spill_bytes_threshold(&self) -> Result<u64> {
+        let key = "join_spill_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Get sort spilling bytes threshold
+    pub fn get_sort_spill_bytes_threshold(&self) -> Result<u64> {
+        let key = "sort_spill_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
     pub fn get_enable_async_insert(&self) -> Result<u64> {
         let key = "enable_async_insert";
         self.try_get_u64(key)
@@ -824,6 +920,16 @@ impl Settings {
     }
 
     pub fn set_settings(&self, key: String, val: String, is_global: bool) -> Result<()> {
+        if !is_global && GLOBAL_ONLY_SETTINGS.contains(&key.as_str()) {
+            return Err(ErrorCode::WrongValueForVariable(format!(
+                "Variable {:?} can only be set globally, use SET GLOBAL instead",
+                key
+            )));
+        }
+        self.apply_setting(key, val, is_global)
+    }
+
+    fn apply_setting(&self, key: String, val: String, is_global: bool) -> Result<()> {
         let setting = self.check_and_get_setting_value(&key)?;
         let val = self.check_possible_values(&setting, val)?;
 
@@ -132,6 +132,9 @@ impl Evaluator {
             Scalar::AggregateFunction(_) => {
                 Err(ErrorCode::Internal("Cannot evaluate aggregate function"))
             }
+            Scalar::WindowFunction(_) => {
+                Err(ErrorCode::Internal("Cannot evaluate window function"))
+            }
         }
     }
 }
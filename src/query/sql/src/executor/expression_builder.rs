@@ -65,7 +65,7 @@ where ExpressionBuilder<T>: FiledNameFormat
                 let metadata = self.metadata.read();
                 let name = metadata.column(column_ref.column.index).name();
                 Ok(Expression::IndexedVariable {
-                    name: name.to_string(),
+                    name: Self::format(name, column_ref.column.index),
                     data_type: (*column_ref.column.data_type).clone(),
                 })
             }
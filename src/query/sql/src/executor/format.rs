@@ -27,9 +27,12 @@ use super::HashJoin;
 use super::Limit;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
 use super::UnionAll;
+use super::Window;
 use crate::executor::FragmentKind;
 use crate::planner::IndexType;
 use crate::planner::MetadataRef;
@@ -51,9 +54,12 @@ fn to_format_tree(plan: &PhysicalPlan, metadata: &MetadataRef) -> Result<FormatT
         PhysicalPlan::AggregateFinal(plan) => aggregate_final_to_format_tree(plan, metadata),
         PhysicalPlan::Sort(plan) => sort_to_format_tree(plan, metadata),
         PhysicalPlan::Limit(plan) => limit_to_format_tree(plan, metadata),
+        PhysicalPlan::Window(plan) => window_to_format_tree(plan, metadata),
         PhysicalPlan::HashJoin(plan) => hash_join_to_format_tree(plan, metadata),
         PhysicalPlan::Exchange(plan) => exchange_to_format_tree(plan, metadata),
         PhysicalPlan::UnionAll(plan) => union_all_to_format_tree(plan, metadata),
+        PhysicalPlan::RecursiveUnion(plan) => recursive_union_to_format_tree(plan, metadata),
+        PhysicalPlan::RecursiveCteScan(plan) => recursive_cte_scan_to_format_tree(plan),
         PhysicalPlan::ExchangeSource(_)
         | PhysicalPlan::ExchangeSink(_)
         | PhysicalPlan::DistributedInsertSelect(_) => {
@@ -267,6 +273,43 @@ fn sort_to_format_tree(plan: &Sort, metadata: &MetadataRef) -> Result<FormatTree
     ]))
 }
 
+fn window_to_format_tree(plan: &Window, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
+    let column_name = |column_id: &String| -> Result<String> {
+        let index = column_id.parse::<IndexType>()?;
+        Ok(metadata.read().column(index).name().to_string())
+    };
+    let partition_by = plan
+        .partition_by
+        .iter()
+        .map(column_name)
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    let order_by = plan
+        .order_by
+        .iter()
+        .map(|sort_key| {
+            Ok(format!(
+                "{} {} {}",
+                column_name(&sort_key.order_by)?,
+                if sort_key.asc { "ASC" } else { "DESC" },
+                if sort_key.nulls_first {
+                    "NULLS FIRST"
+                } else {
+                    "NULLS LAST"
+                }
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    Ok(FormatTreeNode::with_children("Window".to_string(), vec![
+        FormatTreeNode::new(format!("function: {}", plan.func.name())),
+        FormatTreeNode::new(format!("partition by: [{partition_by}]")),
+        FormatTreeNode::new(format!("order by: [{order_by}]")),
+        FormatTreeNode::new(format!("frame: [{}]", plan.frame)),
+        to_format_tree(&plan.input, metadata)?,
+    ]))
+}
+
 fn limit_to_format_tree(plan: &Limit, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
     Ok(FormatTreeNode::with_children("Limit".to_string(), vec![
         FormatTreeNode::new(format!(
@@ -348,3 +391,25 @@ fn union_all_to_format_tree(
         to_format_tree(&plan.right, metadata)?,
     ]))
 }
+
+fn recursive_union_to_format_tree(
+    plan: &RecursiveUnion,
+    metadata: &MetadataRef,
+) -> Result<FormatTreeNode<String>> {
+    Ok(FormatTreeNode::with_children(
+        "RecursiveUnion".to_string(),
+        vec![
+            FormatTreeNode::new(format!("cte: {}", plan.cte_name)),
+            FormatTreeNode::new(format!("distinct: {}", plan.distinct)),
+            to_format_tree(&plan.anchor, metadata)?,
+            to_format_tree(&plan.recursive, metadata)?,
+        ],
+    ))
+}
+
+fn recursive_cte_scan_to_format_tree(plan: &RecursiveCteScan) -> Result<FormatTreeNode<String>> {
+    Ok(FormatTreeNode::with_children(
+        "RecursiveCteScan".to_string(),
+        vec![FormatTreeNode::new(format!("cte: {}", plan.cte_name))],
+    ))
+}
@@ -21,6 +21,7 @@ use common_datavalues::BooleanType;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataTypeImpl;
 use common_datavalues::NullableType;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
@@ -30,8 +31,10 @@ use common_meta_app::schema::TableInfo;
 use super::AggregateFunctionDesc;
 use super::SortDesc;
 use crate::executor::PhysicalScalar;
+use crate::executor::WindowFunction;
 use crate::optimizer::ColumnSet;
 use crate::plans::JoinType;
+use crate::plans::WindowFuncFrame;
 use crate::ColumnBinding;
 use crate::IndexType;
 
@@ -179,6 +182,26 @@ impl Sort {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Window {
+    pub input: Box<PhysicalPlan>,
+    pub func: WindowFunction,
+    pub partition_by: Vec<ColumnID>,
+    pub order_by: Vec<SortDesc>,
+    pub frame: WindowFuncFrame,
+    pub column_id: ColumnID,
+    pub data_type: DataTypeImpl,
+}
+
+impl Window {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let input_schema = self.input.output_schema()?;
+        let mut fields = input_schema.fields().clone();
+        fields.push(DataField::new(&self.column_id, self.data_type.clone()));
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Limit {
     pub input: Box<PhysicalPlan>,
@@ -356,6 +379,39 @@ impl UnionAll {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveUnion {
+    /// Non-recursive term, evaluated once
+    pub anchor: Box<PhysicalPlan>,
+    /// Recursive term, evaluated repeatedly over the working table
+    pub recursive: Box<PhysicalPlan>,
+    pub cte_name: String,
+    // Pairs of unioned columns, (anchor column, recursive term column)
+    pub pairs: Vec<(String, String)>,
+    // Columns of the working table, in the same order as `pairs`
+    pub scan_columns: Vec<String>,
+    pub distinct: bool,
+    pub schema: DataSchemaRef,
+}
+
+impl RecursiveUnion {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveCteScan {
+    pub cte_name: String,
+    pub schema: DataSchemaRef,
+}
+
+impl RecursiveCteScan {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DistributedInsertSelect {
     pub input: Box<PhysicalPlan>,
@@ -386,9 +442,12 @@ pub enum PhysicalPlan {
     AggregateFinal(AggregateFinal),
     Sort(Sort),
     Limit(Limit),
+    Window(Window),
     HashJoin(HashJoin),
     Exchange(Exchange),
     UnionAll(UnionAll),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
 
     /// For insert into ... select ... in cluster
     DistributedInsertSelect(Box<DistributedInsertSelect>),
@@ -417,11 +476,14 @@ impl PhysicalPlan {
             PhysicalPlan::AggregateFinal(plan) => plan.output_schema(),
             PhysicalPlan::Sort(plan) => plan.output_schema(),
             PhysicalPlan::Limit(plan) => plan.output_schema(),
+            PhysicalPlan::Window(plan) => plan.output_schema(),
             PhysicalPlan::HashJoin(plan) => plan.output_schema(),
             PhysicalPlan::Exchange(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSource(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSink(plan) => plan.output_schema(),
             PhysicalPlan::UnionAll(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveUnion(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveCteScan(plan) => plan.output_schema(),
             PhysicalPlan::DistributedInsertSelect(plan) => plan.output_schema(),
         }
     }
@@ -436,6 +498,7 @@ impl PhysicalPlan {
             PhysicalPlan::AggregateFinal(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Sort(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Limit(plan) => Box::new(std::iter::once(plan.input.as_ref())),
+            PhysicalPlan::Window(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::HashJoin(plan) => Box::new(
                 std::iter::once(plan.probe.as_ref()).chain(std::iter::once(plan.build.as_ref())),
             ),
@@ -445,6 +508,11 @@ impl PhysicalPlan {
             PhysicalPlan::UnionAll(plan) => Box::new(
                 std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
             ),
+            PhysicalPlan::RecursiveUnion(plan) => Box::new(
+                std::iter::once(plan.anchor.as_ref())
+                    .chain(std::iter::once(plan.recursive.as_ref())),
+            ),
+            PhysicalPlan::RecursiveCteScan(_) => Box::new(std::iter::empty()),
             PhysicalPlan::DistributedInsertSelect(plan) => {
                 Box::new(std::iter::once(plan.input.as_ref()))
             }
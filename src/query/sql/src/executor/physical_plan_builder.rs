@@ -23,6 +23,7 @@ use common_catalog::plan::PrewhereInfo;
 use common_catalog::plan::Projection;
 use common_catalog::plan::PushDownInfo;
 use common_catalog::table_context::TableContext;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
@@ -37,9 +38,15 @@ use super::AggregatePartial;
 use super::Exchange as PhysicalExchange;
 use super::Filter;
 use super::HashJoin;
+use super::LagLeadFunctionDesc;
 use super::Limit;
+use super::NthValueFunctionDesc;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
+use super::Window;
+use super::WindowFunction;
 use crate::executor::table_read_plan::ToReadDataSourcePlan;
 use crate::executor::util::check_physical;
 use crate::executor::ColumnID;
@@ -58,6 +65,7 @@ use crate::plans::Exchange;
 use crate::plans::PhysicalScan;
 use crate::plans::RelOperator;
 use crate::plans::Scalar;
+use crate::plans::WindowFuncType;
 use crate::IndexType;
 use crate::Metadata;
 use crate::MetadataRef;
@@ -421,6 +429,93 @@ impl PhysicalPlanBuilder {
                     .collect(),
                 limit: sort.limit,
             })),
+            RelOperator::Window(window) => {
+                let input = self.build(s_expr.child(0)?).await?;
+                let input_schema = input.output_schema()?;
+                let column_offset = |arg: &Scalar| -> Result<usize> {
+                    if let Scalar::BoundColumnRef(col) = arg {
+                        input_schema.index_of(&col.column.index.to_string())
+                    } else {
+                        Err(ErrorCode::Internal(
+                            "Window function argument must be a BoundColumnRef".to_string(),
+                        ))
+                    }
+                };
+                let func = match &window.function {
+                    WindowFuncType::Aggregate(agg) => {
+                        WindowFunction::Aggregate(AggregateFunctionDesc {
+                            sig: AggregateFunctionSignature {
+                                name: agg.func_name.clone(),
+                                args: agg.args.iter().map(|s| s.data_type()).collect(),
+                                params: agg.params.clone(),
+                                return_type: *agg.return_type.clone(),
+                            },
+                            column_id: window.index.to_string(),
+                            args: agg.args.iter().map(column_offset).collect::<Result<_>>()?,
+                            arg_indices: agg
+                                .args
+                                .iter()
+                                .map(|arg| {
+                                    if let Scalar::BoundColumnRef(col) = arg {
+                                        Ok(col.column.index)
+                                    } else {
+                                        Err(ErrorCode::Internal(
+                                            "Window function argument must be a BoundColumnRef"
+                                                .to_string(),
+                                        ))
+                                    }
+                                })
+                                .collect::<Result<_>>()?,
+                        })
+                    }
+                    WindowFuncType::RowNumber => WindowFunction::RowNumber,
+                    WindowFuncType::Rank => WindowFunction::Rank,
+                    WindowFuncType::DenseRank => WindowFunction::DenseRank,
+                    WindowFuncType::PercentRank => WindowFunction::PercentRank,
+                    WindowFuncType::CumeDist => WindowFunction::CumeDist,
+                    WindowFuncType::LagLead(lag_lead) => {
+                        WindowFunction::LagLead(LagLeadFunctionDesc {
+                            is_lag: lag_lead.is_lag,
+                            offset: lag_lead.offset,
+                            arg: column_offset(&lag_lead.arg)?,
+                            default: match &lag_lead.default {
+                                Some(default) => Some(column_offset(default)?),
+                                None => None,
+                            },
+                            return_type: *lag_lead.return_type.clone(),
+                        })
+                    }
+                    WindowFuncType::NthValue(nth) => {
+                        WindowFunction::NthValue(NthValueFunctionDesc {
+                            n: nth.n,
+                            arg: column_offset(&nth.arg)?,
+                            return_type: *nth.return_type.clone(),
+                        })
+                    }
+                };
+
+                Ok(PhysicalPlan::Window(Window {
+                    input: Box::new(input),
+                    func,
+                    partition_by: window
+                        .partition_by
+                        .iter()
+                        .map(|index| index.to_string())
+                        .collect(),
+                    order_by: window
+                        .order_by
+                        .iter()
+                        .map(|v| SortDesc {
+                            asc: v.asc,
+                            nulls_first: v.nulls_first,
+                            order_by: v.index.to_string(),
+                        })
+                        .collect(),
+                    frame: window.frame.clone(),
+                    column_id: window.index.to_string(),
+                    data_type: window.function.return_type(),
+                }))
+            }
             RelOperator::Limit(limit) => Ok(PhysicalPlan::Limit(Limit {
                 input: Box::new(self.build(s_expr.child(0)?).await?),
                 limit: limit.limit,
@@ -466,6 +561,45 @@ impl PhysicalPlanBuilder {
                     schema: DataSchemaRefExt::create(fields),
                 }))
             }
+            RelOperator::RecursiveUnion(op) => {
+                let anchor = self.build(s_expr.child(0)?).await?;
+                let anchor_schema = anchor.output_schema()?;
+                let pairs = op
+                    .pairs
+                    .iter()
+                    .map(|(l, r)| (l.to_string(), r.to_string()))
+                    .collect::<Vec<_>>();
+                let fields = pairs
+                    .iter()
+                    .map(|(left, _)| Ok(anchor_schema.field_with_name(left)?.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PhysicalPlan::RecursiveUnion(RecursiveUnion {
+                    anchor: Box::new(anchor),
+                    recursive: Box::new(self.build(s_expr.child(1)?).await?),
+                    cte_name: op.cte_name.clone(),
+                    pairs,
+                    scan_columns: op.scan_columns.iter().map(|i| i.to_string()).collect(),
+                    distinct: op.distinct,
+                    schema: DataSchemaRefExt::create(fields),
+                }))
+            }
+            RelOperator::RecursiveCteScan(scan) => {
+                let metadata = self.metadata.read();
+                let fields = scan
+                    .columns
+                    .iter()
+                    .map(|index| {
+                        DataField::new(
+                            &index.to_string(),
+                            metadata.column(*index).data_type().clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Ok(PhysicalPlan::RecursiveCteScan(RecursiveCteScan {
+                    cte_name: scan.cte_name.clone(),
+                    schema: DataSchemaRefExt::create(fields),
+                }))
+            }
             _ => Err(ErrorCode::Internal(format!(
                 "Unsupported physical plan: {:?}",
                 s_expr.plan()
@@ -29,9 +29,12 @@ use crate::executor::HashJoin;
 use crate::executor::Limit;
 use crate::executor::PhysicalPlan;
 use crate::executor::Project;
+use crate::executor::RecursiveCteScan;
+use crate::executor::RecursiveUnion;
 use crate::executor::Sort;
 use crate::executor::TableScan;
 use crate::executor::UnionAll;
+use crate::executor::Window;
 use crate::plans::JoinType;
 
 impl PhysicalPlan {
@@ -58,11 +61,14 @@ impl<'a> Display for PhysicalPlanIndentFormatDisplay<'a> {
             PhysicalPlan::AggregateFinal(aggregate) => write!(f, "{}", aggregate)?,
             PhysicalPlan::Sort(sort) => write!(f, "{}", sort)?,
             PhysicalPlan::Limit(limit) => write!(f, "{}", limit)?,
+            PhysicalPlan::Window(window) => write!(f, "{}", window)?,
             PhysicalPlan::HashJoin(join) => write!(f, "{}", join)?,
             PhysicalPlan::Exchange(exchange) => write!(f, "{}", exchange)?,
             PhysicalPlan::ExchangeSource(source) => write!(f, "{}", source)?,
             PhysicalPlan::ExchangeSink(sink) => write!(f, "{}", sink)?,
             PhysicalPlan::UnionAll(union_all) => write!(f, "{}", union_all)?,
+            PhysicalPlan::RecursiveUnion(union) => write!(f, "{}", union)?,
+            PhysicalPlan::RecursiveCteScan(scan) => write!(f, "{}", scan)?,
             PhysicalPlan::DistributedInsertSelect(insert_select) => write!(f, "{}", insert_select)?,
         }
 
@@ -129,6 +135,31 @@ impl Display for Sort {
     }
 }
 
+impl Display for Window {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let partition_by = self.partition_by.join(", ");
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|item| {
+                format!(
+                    "{} {}",
+                    item.order_by,
+                    if item.asc { "ASC" } else { "DESC" }
+                )
+            })
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "Window: [{}], Partition by: [{}], Order by: [{}], Frame: [{}]",
+            self.func.name(),
+            partition_by,
+            order_by.join(", "),
+            self.frame
+        )
+    }
+}
+
 impl Display for EvalScalar {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let scalars = self
@@ -293,6 +324,18 @@ impl Display for UnionAll {
     }
 }
 
+impl Display for RecursiveUnion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecursiveUnion: cte: {}", self.cte_name)
+    }
+}
+
+impl Display for RecursiveCteScan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecursiveCteScan: cte: {}", self.cte_name)
+    }
+}
+
 impl Display for DistributedInsertSelect {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "DistributedInsertSelect")
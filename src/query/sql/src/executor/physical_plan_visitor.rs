@@ -26,8 +26,11 @@ use super::HashJoin;
 use super::Limit;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
+use super::Window;
 use crate::executor::UnionAll;
 
 pub trait PhysicalPlanReplacer {
@@ -41,11 +44,14 @@ pub trait PhysicalPlanReplacer {
             PhysicalPlan::AggregateFinal(plan) => self.replace_aggregate_final(plan),
             PhysicalPlan::Sort(plan) => self.replace_sort(plan),
             PhysicalPlan::Limit(plan) => self.replace_limit(plan),
+            PhysicalPlan::Window(plan) => self.replace_window(plan),
             PhysicalPlan::HashJoin(plan) => self.replace_hash_join(plan),
             PhysicalPlan::Exchange(plan) => self.replace_exchange(plan),
             PhysicalPlan::ExchangeSource(plan) => self.replace_exchange_source(plan),
             PhysicalPlan::ExchangeSink(plan) => self.replace_exchange_sink(plan),
             PhysicalPlan::UnionAll(plan) => self.replace_union(plan),
+            PhysicalPlan::RecursiveUnion(plan) => self.replace_recursive_union(plan),
+            PhysicalPlan::RecursiveCteScan(plan) => self.replace_recursive_cte_scan(plan),
             PhysicalPlan::DistributedInsertSelect(plan) => self.replace_insert_select(plan),
         }
     }
@@ -129,6 +135,20 @@ pub trait PhysicalPlanReplacer {
         }))
     }
 
+    fn replace_window(&mut self, plan: &Window) -> Result<PhysicalPlan> {
+        let input = self.replace(&plan.input)?;
+
+        Ok(PhysicalPlan::Window(Window {
+            input: Box::new(input),
+            func: plan.func.clone(),
+            partition_by: plan.partition_by.clone(),
+            order_by: plan.order_by.clone(),
+            frame: plan.frame.clone(),
+            column_id: plan.column_id.clone(),
+            data_type: plan.data_type.clone(),
+        }))
+    }
+
     fn replace_limit(&mut self, plan: &Limit) -> Result<PhysicalPlan> {
         let input = self.replace(&plan.input)?;
 
@@ -178,6 +198,24 @@ pub trait PhysicalPlanReplacer {
         }))
     }
 
+    fn replace_recursive_union(&mut self, plan: &RecursiveUnion) -> Result<PhysicalPlan> {
+        let anchor = self.replace(&plan.anchor)?;
+        let recursive = self.replace(&plan.recursive)?;
+        Ok(PhysicalPlan::RecursiveUnion(RecursiveUnion {
+            anchor: Box::new(anchor),
+            recursive: Box::new(recursive),
+            cte_name: plan.cte_name.clone(),
+            pairs: plan.pairs.clone(),
+            scan_columns: plan.scan_columns.clone(),
+            distinct: plan.distinct,
+            schema: plan.schema.clone(),
+        }))
+    }
+
+    fn replace_recursive_cte_scan(&mut self, plan: &RecursiveCteScan) -> Result<PhysicalPlan> {
+        Ok(PhysicalPlan::RecursiveCteScan(plan.clone()))
+    }
+
     fn replace_insert_select(&mut self, plan: &DistributedInsertSelect) -> Result<PhysicalPlan> {
         let input = self.replace(&plan.input)?;
 
@@ -227,6 +265,9 @@ impl PhysicalPlan {
                 PhysicalPlan::Limit(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::Window(plan) => {
+                    Self::traverse(&plan.input, pre_visit, visit, post_visit);
+                }
                 PhysicalPlan::HashJoin(plan) => {
                     Self::traverse(&plan.build, pre_visit, visit, post_visit);
                     Self::traverse(&plan.probe, pre_visit, visit, post_visit);
@@ -242,6 +283,11 @@ impl PhysicalPlan {
                     Self::traverse(&plan.left, pre_visit, visit, post_visit);
                     Self::traverse(&plan.right, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::RecursiveUnion(plan) => {
+                    Self::traverse(&plan.anchor, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.recursive, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::RecursiveCteScan(_) => {}
                 PhysicalPlan::DistributedInsertSelect(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
@@ -201,3 +201,48 @@ pub struct SortDesc {
     pub nulls_first: bool,
     pub order_by: ColumnID,
 }
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum WindowFunction {
+    Aggregate(AggregateFunctionDesc),
+    RowNumber,
+    Rank,
+    DenseRank,
+    PercentRank,
+    CumeDist,
+    LagLead(LagLeadFunctionDesc),
+    NthValue(NthValueFunctionDesc),
+}
+
+impl WindowFunction {
+    pub fn name(&self) -> String {
+        match self {
+            WindowFunction::Aggregate(agg) => agg.sig.name.clone(),
+            WindowFunction::RowNumber => "row_number".to_string(),
+            WindowFunction::Rank => "rank".to_string(),
+            WindowFunction::DenseRank => "dense_rank".to_string(),
+            WindowFunction::PercentRank => "percent_rank".to_string(),
+            WindowFunction::CumeDist => "cume_dist".to_string(),
+            WindowFunction::LagLead(lag_lead) if lag_lead.is_lag => "lag".to_string(),
+            WindowFunction::LagLead(_) => "lead".to_string(),
+            WindowFunction::NthValue(_) => "nth_value".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LagLeadFunctionDesc {
+    pub is_lag: bool,
+    pub offset: u64,
+    pub arg: usize,
+    pub default: Option<usize>,
+    pub return_type: DataTypeImpl,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NthValueFunctionDesc {
+    /// `None` means the last row of the frame
+    pub n: Option<u64>,
+    pub arg: usize,
+    pub return_type: DataTypeImpl,
+}
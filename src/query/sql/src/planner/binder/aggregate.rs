@@ -137,6 +137,12 @@ impl<'a> AggregateRewriter<'a> {
             Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
 
             Scalar::AggregateFunction(agg_func) => self.replace_aggregate_function(agg_func),
+
+            // The window function itself is not an aggregation, but its inputs may
+            // contain aggregate functions, e.g. `SUM(SUM(a)) OVER ()`.
+            Scalar::WindowFunction(window) => {
+                Ok(window.try_map_inputs(|scalar| self.visit(scalar))?.into())
+            }
         }
     }
 
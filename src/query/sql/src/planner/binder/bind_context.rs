@@ -27,6 +27,7 @@ use common_exception::Result;
 use dashmap::DashMap;
 
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::normalize_identifier;
 use crate::optimizer::SExpr;
 use crate::plans::Scalar;
@@ -95,6 +96,8 @@ pub struct BindContext {
     /// functions, otherwise a grouping error will be raised.
     pub in_grouping: bool,
 
+    pub window_info: WindowInfo,
+
     pub ctes_map: Arc<DashMap<String, CteInfo>>,
 }
 
@@ -112,6 +115,7 @@ impl BindContext {
             columns: Vec::new(),
             aggregate_info: AggregateInfo::default(),
             in_grouping: false,
+            window_info: WindowInfo::default(),
             ctes_map: Arc::new(DashMap::new()),
         }
     }
@@ -122,6 +126,7 @@ impl BindContext {
             columns: vec![],
             aggregate_info: Default::default(),
             in_grouping: false,
+            window_info: Default::default(),
             ctes_map: parent.ctes_map.clone(),
         }
     }
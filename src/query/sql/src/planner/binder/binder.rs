@@ -88,6 +88,14 @@ impl<'a> Binder {
     ) -> Result<Plan> {
         let plan = match stmt {
             Statement::Query(query) => {
+                if let Some(rewritten) = self.rewrite_with_materialized_view(query).await? {
+                    let kind = RewriteKind::MaterializedView;
+                    let mut plan = self.bind_rewrite_to_query(bind_context, &rewritten, kind).await?;
+                    if let Plan::Query { ignore_result, .. } = &mut plan {
+                        *ignore_result = query.ignore_result;
+                    }
+                    return Ok(plan);
+                }
                 let (s_expr, bind_context) = self.bind_query(bind_context, query).await?;
                 Plan::Query {
                     s_expr: Box::new(s_expr),
@@ -161,13 +169,21 @@ impl<'a> Binder {
             Statement::RenameTable(stmt) => self.bind_rename_table(stmt).await?,
             Statement::TruncateTable(stmt) => self.bind_truncate_table(stmt).await?,
             Statement::OptimizeTable(stmt) => self.bind_optimize_table(stmt).await?,
+            Statement::AnalyzeTable(stmt) => self.bind_analyze_table(stmt).await?,
             Statement::ExistsTable(stmt) => self.bind_exists_table(stmt).await?,
 
+            // Indexes
+            Statement::CreateIndex(stmt) => self.bind_create_index(stmt).await?,
+
             // Views
             Statement::CreateView(stmt) => self.bind_create_view(stmt).await?,
             Statement::AlterView(stmt) => self.bind_alter_view(stmt).await?,
             Statement::DropView(stmt) => self.bind_drop_view(stmt).await?,
 
+            // Streams
+            Statement::CreateStream(stmt) => self.bind_create_stream(stmt).await?,
+            Statement::DropStream(stmt) => self.bind_drop_stream(stmt).await?,
+
             // Users
             Statement::CreateUser(stmt) => self.bind_create_user(stmt).await?,
             Statement::DropUser { if_exists, user } => Plan::DropUser(Box::new(DropUserPlan {
@@ -220,6 +236,12 @@ impl<'a> Binder {
                     .await?
             }
             Statement::Update(stmt) => self.bind_update(bind_context, stmt).await?,
+            Statement::MergeInto(stmt) => self.bind_merge_into(bind_context, stmt).await?,
+
+            // Transactions
+            Statement::Begin => Plan::Begin,
+            Statement::Commit => Plan::Commit,
+            Statement::Rollback => Plan::Rollback,
 
             // Permissions
             Statement::Grant(stmt) => self.bind_grant(stmt).await?,
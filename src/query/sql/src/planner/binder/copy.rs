@@ -32,6 +32,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::parse_escape_string;
 use common_meta_types::FileFormatOptions;
+use common_meta_types::OnErrorMode;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::UserStageInfo;
 use common_storage::parse_uri_location;
@@ -235,6 +236,7 @@ impl<'a> Binder {
                 user_stage_info: stage_info,
                 path,
                 files: vec![],
+                pattern: "".to_string(),
             }),
             scan_fields: None,
             parts: vec![],
@@ -294,6 +296,7 @@ impl<'a> Binder {
                 user_stage_info: stage_info,
                 path,
                 files: vec![],
+                pattern: "".to_string(),
             }),
             scan_fields: None,
             parts: vec![],
@@ -489,12 +492,11 @@ impl<'a> Binder {
 
         // Copy options.
         {
-            // TODO(xuanwo): COPY should handle on error.
             // on_error.
-            // if !stmt.on_error.is_empty() {
-            //     stage_info.copy_options.on_error =
-            //         OnErrorMode::from_str(&self.on_error).map_err(ErrorCode::SyntaxException)?;
-            // }
+            if !stmt.on_error.is_empty() {
+                stage.copy_options.on_error =
+                    OnErrorMode::from_str(&stmt.on_error).map_err(ErrorCode::SyntaxException)?;
+            }
 
             // size_limit.
             if stmt.size_limit != 0 {
@@ -0,0 +1,62 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateIndexStmt;
+use common_exception::Result;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateIndexPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_index(
+        &mut self,
+        stmt: &CreateIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateIndexStmt {
+            if_not_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+            columns,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index_name = normalize_identifier(index_name, &self.name_resolution_ctx).name;
+        let columns = columns
+            .iter()
+            .map(|column| normalize_identifier(column, &self.name_resolution_ctx).name)
+            .collect();
+
+        let plan = CreateIndexPlan {
+            if_not_exists: *if_not_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+            columns,
+        };
+        Ok(Plan::CreateIndex(Box::new(plan)))
+    }
+}
@@ -15,8 +15,10 @@
 mod account;
 mod catalog;
 mod database;
+mod index;
 mod role;
 mod share;
 mod stage;
+mod stream;
 mod table;
 mod view;
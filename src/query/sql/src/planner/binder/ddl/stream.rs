@@ -0,0 +1,112 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateStreamStmt;
+use common_ast::ast::DropStreamStmt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateStreamPlan;
+use crate::plans::DropStreamPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_stream(
+        &mut self,
+        stmt: &CreateStreamStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateStreamStmt {
+            if_not_exists,
+            catalog,
+            database,
+            stream,
+            table_database,
+            table,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let stream = normalize_identifier(stream, &self.name_resolution_ctx).name;
+        let table_database = table_database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        let base_table = self
+            .ctx
+            .get_table(&catalog, &table_database, &table)
+            .await?;
+        if base_table.engine() != "FUSE" {
+            return Err(ErrorCode::SemanticError(format!(
+                "Stream is only supported on FUSE tables, but {}.{} is {}",
+                table_database,
+                table,
+                base_table.engine()
+            )));
+        }
+
+        let plan = CreateStreamPlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            stream,
+            table_database,
+            table,
+        };
+        Ok(Plan::CreateStream(Box::new(plan)))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_stream(
+        &mut self,
+        stmt: &DropStreamStmt<'a>,
+    ) -> Result<Plan> {
+        let DropStreamStmt {
+            if_exists,
+            catalog,
+            database,
+            stream,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let stream = normalize_identifier(stream, &self.name_resolution_ctx).name;
+
+        let plan = DropStreamPlan {
+            if_exists: *if_exists,
+            tenant,
+            catalog,
+            database,
+            stream,
+        };
+        Ok(Plan::DropStream(Box::new(plan)))
+    }
+}
@@ -19,6 +19,8 @@ use std::sync::Arc;
 
 use common_ast::ast::AlterTableAction;
 use common_ast::ast::AlterTableStmt;
+use common_ast::ast::AnalyzeTableStmt;
+use common_ast::ast::ColumnDefinition;
 use common_ast::ast::CompactTarget;
 use common_ast::ast::CreateTableSource;
 use common_ast::ast::CreateTableStmt;
@@ -27,9 +29,11 @@ use common_ast::ast::DropTableStmt;
 use common_ast::ast::Engine;
 use common_ast::ast::ExistsTableStmt;
 use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
 use common_ast::ast::Literal;
 use common_ast::ast::OptimizeTableAction as AstOptimizeTableAction;
 use common_ast::ast::OptimizeTableStmt;
+use common_ast::ast::Query;
 use common_ast::ast::RenameTableStmt;
 use common_ast::ast::ShowCreateTableStmt;
 use common_ast::ast::ShowLimit;
@@ -37,6 +41,7 @@ use common_ast::ast::ShowTablesStatusStmt;
 use common_ast::ast::ShowTablesStmt;
 use common_ast::ast::Statement;
 use common_ast::ast::TableReference;
+use common_ast::ast::TimeTravelPoint;
 use common_ast::ast::TruncateTableStmt;
 use common_ast::ast::UndropTableStmt;
 use common_ast::parser::parse_sql;
@@ -58,6 +63,7 @@ use common_storage::DataOperator;
 use common_storage::UriLocation;
 use common_storages_table_meta::table::is_reserved_opt_key;
 use common_storages_table_meta::table::OPT_KEY_DATABASE_ID;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
 use tracing::debug;
 
 use crate::binder::scalar::ScalarBinder;
@@ -69,12 +75,17 @@ use crate::optimizer::OptimizerContext;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::IdentifierNormalizer;
 use crate::plans::AlterTableClusterKeyPlan;
+use crate::plans::AlterTableColumnAction;
+use crate::plans::AlterTableColumnPlan;
+use crate::plans::AnalyzeTablePlan;
 use crate::plans::CastExpr;
+use crate::plans::CloneTableSource;
 use crate::plans::CreateTablePlanV2;
 use crate::plans::DescribeTablePlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTablePlan;
 use crate::plans::ExistsTablePlan;
+use crate::plans::FlashbackTablePlan;
 use crate::plans::OptimizeTableAction;
 use crate::plans::OptimizeTablePlan;
 use crate::plans::Plan;
@@ -379,7 +390,7 @@ impl<'a> Binder {
             )?;
         }
 
-        let storage_params = match uri_location {
+        let mut storage_params = match uri_location {
             Some(uri) => {
                 let uri = UriLocation {
                     protocol: uri.protocol.clone(),
@@ -402,6 +413,50 @@ impl<'a> Binder {
             options.insert("TRANSIENT".to_owned(), "T".to_owned());
         }
 
+        let clone_source = match source {
+            Some(CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            }) => {
+                if as_query.is_some() {
+                    return Err(ErrorCode::BadArguments(
+                        "Incorrect CREATE query: CLONE can not be used together with AS SELECT",
+                    ));
+                }
+                Some(
+                    self.analyze_clone_source(catalog, database, table, travel_point)
+                        .await?,
+                )
+            }
+            _ => None,
+        };
+
+        let template = match source {
+            Some(CreateTableSource::Template(query)) => {
+                if as_query.is_some() {
+                    return Err(ErrorCode::BadArguments(
+                        "Incorrect CREATE query: USING TEMPLATE can not be used together with AS SELECT",
+                    ));
+                }
+                if !cluster_by.is_empty() {
+                    return Err(ErrorCode::BadArguments(
+                        "Incorrect CREATE query: USING TEMPLATE can not be used together with CLUSTER BY",
+                    ));
+                }
+                let template_plan = self.bind_create_table_query(query).await?;
+                let num_fields = template_plan.schema().num_fields();
+                if !(2..=3).contains(&num_fields) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "USING TEMPLATE expects a query of the column name, the type and the optional nullability, but got {num_fields} columns"
+                    )));
+                }
+                Some(Box::new(template_plan))
+            }
+            _ => None,
+        };
+
         // Build table schema
         let (schema, field_default_exprs, field_comments) = match (&source, &as_query) {
             (Some(source), None) => {
@@ -470,7 +525,7 @@ impl<'a> Binder {
             options.insert(OPT_KEY_DATABASE_ID.to_owned(), db_id.to_string());
         }
 
-        let cluster_key = {
+        let mut cluster_key = {
             let keys = self
                 .analyze_cluster_keys(cluster_by, schema.clone())
                 .await?;
@@ -481,6 +536,32 @@ impl<'a> Binder {
             }
         };
 
+        // The clone inherits the cluster key, the storage and the options of the cloned table,
+        // unless they are specified in the statement.
+        if let Some(clone_source) = &clone_source {
+            let source_table = self
+                .ctx
+                .get_table(
+                    &clone_source.catalog,
+                    &clone_source.database,
+                    &clone_source.table,
+                )
+                .await?;
+            let source_meta = &source_table.get_table_info().meta;
+            if cluster_key.is_none() {
+                cluster_key = source_meta.default_cluster_key.clone();
+            }
+            if storage_params.is_none() {
+                storage_params = source_meta.storage_params.clone();
+            }
+            for (key, value) in source_meta.options.iter() {
+                if is_reserved_opt_key(key) || key == OPT_KEY_SNAPSHOT_LOCATION {
+                    continue;
+                }
+                options.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+
         let plan = CreateTablePlanV2 {
             if_not_exists: *if_not_exists,
             tenant: self.ctx.get_tenant(),
@@ -495,20 +576,25 @@ impl<'a> Binder {
             field_comments,
             cluster_key,
             as_select: if let Some(query) = as_query {
-                let bind_context = BindContext::new();
-                let stmt = Statement::Query(Box::new(*query.clone()));
-                let select_plan = self.bind_statement(&bind_context, &stmt).await?;
-                // Don't enable distributed optimization for `CREATE TABLE ... AS SELECT ...` for now
-                let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig::default()));
-                let optimized_plan = optimize(self.ctx.clone(), opt_ctx, select_plan)?;
-                Some(Box::new(optimized_plan))
+                Some(Box::new(self.bind_create_table_query(query).await?))
             } else {
                 None
             },
+            clone_source,
+            template,
         };
         Ok(Plan::CreateTable(Box::new(plan)))
     }
 
+    async fn bind_create_table_query(&mut self, query: &Query<'a>) -> Result<Plan> {
+        let bind_context = BindContext::new();
+        let stmt = Statement::Query(Box::new(query.clone()));
+        let select_plan = self.bind_statement(&bind_context, &stmt).await?;
+        // Don't enable distributed optimization for `CREATE TABLE ... AS SELECT ...` for now
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig::default()));
+        optimize(self.ctx.clone(), opt_ctx, select_plan)
+    }
+
     pub(in crate::planner::binder) async fn bind_drop_table(
         &mut self,
         stmt: &DropTableStmt<'a>,
@@ -683,6 +769,70 @@ impl<'a> Binder {
                     push_downs,
                 })))
             }
+            AlterTableAction::AddColumn { column } => {
+                let (field, default_expr, comment) = self.analyze_column_definition(column).await?;
+
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action: AlterTableColumnAction::Add {
+                        field,
+                        default_expr,
+                        comment,
+                    },
+                })))
+            }
+            AlterTableAction::DropColumn { column } => {
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action: AlterTableColumnAction::Drop {
+                        name: normalize_identifier(column, &self.name_resolution_ctx).name,
+                    },
+                })))
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                tenant,
+                catalog,
+                database,
+                table,
+                action: AlterTableColumnAction::Rename {
+                    name: normalize_identifier(old_column, &self.name_resolution_ctx).name,
+                    new_name: normalize_identifier(new_column, &self.name_resolution_ctx).name,
+                },
+            }))),
+            AlterTableAction::ModifyColumn { column } => {
+                let (field, default_expr, _) = self.analyze_column_definition(column).await?;
+
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action: AlterTableColumnAction::Modify {
+                        field,
+                        default_expr,
+                    },
+                })))
+            }
+            AlterTableAction::FlashbackTo { point } => {
+                let point = self.resolve_data_travel_point(bind_context, point).await?;
+
+                Ok(Plan::FlashbackTable(Box::new(FlashbackTablePlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    point,
+                })))
+            }
         }
     }
 
@@ -823,6 +973,33 @@ impl<'a> Binder {
         })))
     }
 
+    pub(in crate::planner::binder) async fn bind_analyze_table(
+        &mut self,
+        stmt: &AnalyzeTableStmt<'a>,
+    ) -> Result<Plan> {
+        let AnalyzeTableStmt {
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        Ok(Plan::AnalyzeTable(Box::new(AnalyzeTablePlan {
+            catalog,
+            database,
+            table,
+        })))
+    }
+
     pub(in crate::planner::binder) async fn bind_exists_table(
         &mut self,
         stmt: &ExistsTableStmt<'a>,
@@ -854,43 +1031,17 @@ impl<'a> Binder {
         &self,
         source: &CreateTableSource<'a>,
     ) -> Result<(DataSchemaRef, Vec<Option<Scalar>>, Vec<String>)> {
-        let bind_context = BindContext::new();
         match source {
             CreateTableSource::Columns(columns) => {
-                let mut scalar_binder = ScalarBinder::new(
-                    &bind_context,
-                    self.ctx.clone(),
-                    &self.name_resolution_ctx,
-                    self.metadata.clone(),
-                    &[],
-                );
                 let mut fields = Vec::with_capacity(columns.len());
                 let mut fields_default_expr = Vec::with_capacity(columns.len());
                 let mut fields_comments = Vec::with_capacity(columns.len());
                 for column in columns.iter() {
-                    let name = normalize_identifier(&column.name, &self.name_resolution_ctx).name;
-                    let data_type = TypeFactory::instance().get(column.data_type.to_string())?;
-
-                    fields.push(DataField::new(&name, data_type.clone()));
-                    fields_default_expr.push({
-                        if let Some(default_expr) = &column.default_expr {
-                            let (mut expr, expr_type) = scalar_binder.bind(default_expr).await?;
-                            if compare_coercion(&data_type, &expr_type).is_err() {
-                                return Err(ErrorCode::SemanticError(format!("column {name} is of type {} but default expression is of type {}", data_type, expr_type)));
-                            }
-                            if !expr_type.eq(&data_type) {
-                                expr = Scalar::CastExpr(CastExpr {
-                                    argument: Box::new(expr),
-                                    from_type: Box::new(expr_type),
-                                    target_type: Box::new(data_type),
-                                })
-                            }
-                            Some(expr)
-                        } else {
-                            None
-                        }
-                    });
-                    fields_comments.push(column.comment.clone().unwrap_or_default());
+                    let (field, default_expr, comment) =
+                        self.analyze_column_definition(column).await?;
+                    fields.push(field);
+                    fields_default_expr.push(default_expr);
+                    fields_comments.push(comment);
                 }
                 let schema = DataSchemaRefExt::create(fields);
                 Self::validate_create_table_schema(&schema)?;
@@ -913,11 +1064,108 @@ impl<'a> Binder {
                 let table = self.ctx.get_table(&catalog, &database, &table_name).await?;
                 Ok((table.schema(), vec![], table.field_comments().clone()))
             }
+            CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            } => {
+                let source = self
+                    .analyze_clone_source(catalog, database, table, travel_point)
+                    .await?;
+                let mut table = self
+                    .ctx
+                    .get_table(&source.catalog, &source.database, &source.table)
+                    .await?;
+                if let Some(point) = &source.travel_point {
+                    table = table.navigate_to(point).await?;
+                }
+                Ok((table.schema(), vec![], table.field_comments().clone()))
+            }
+            // The columns are the rows of the template query, which are known when it is executed
+            CreateTableSource::Template(_) => {
+                Ok((DataSchemaRefExt::create(vec![]), vec![], vec![]))
+            }
         }
     }
 
+    async fn analyze_clone_source(
+        &self,
+        catalog: &Option<Identifier<'a>>,
+        database: &Option<Identifier<'a>>,
+        table: &Identifier<'a>,
+        travel_point: &Option<TimeTravelPoint<'a>>,
+    ) -> Result<CloneTableSource> {
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database.as_ref().map_or_else(
+            || self.ctx.get_current_database(),
+            |ident| normalize_identifier(ident, &self.name_resolution_ctx).name,
+        );
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let travel_point = match travel_point {
+            Some(point) => Some(
+                self.resolve_data_travel_point(&BindContext::new(), point)
+                    .await?,
+            ),
+            None => None,
+        };
+        Ok(CloneTableSource {
+            catalog,
+            database,
+            table,
+            travel_point,
+        })
+    }
+
+    async fn analyze_column_definition(
+        &self,
+        column: &ColumnDefinition<'a>,
+    ) -> Result<(DataField, Option<Scalar>, String)> {
+        let bind_context = BindContext::new();
+        let mut scalar_binder = ScalarBinder::new(
+            &bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let name = normalize_identifier(&column.name, &self.name_resolution_ctx).name;
+        let data_type = TypeFactory::instance().get(column.data_type.to_string())?;
+
+        let default_expr = if let Some(default_expr) = &column.default_expr {
+            let (mut expr, expr_type) = scalar_binder.bind(default_expr).await?;
+            if compare_coercion(&data_type, &expr_type).is_err() {
+                return Err(ErrorCode::SemanticError(format!(
+                    "column {name} is of type {} but default expression is of type {}",
+                    data_type, expr_type
+                )));
+            }
+            if !expr_type.eq(&data_type) {
+                expr = Scalar::CastExpr(CastExpr {
+                    argument: Box::new(expr),
+                    from_type: Box::new(expr_type),
+                    target_type: Box::new(data_type.clone()),
+                })
+            }
+            Some(expr)
+        } else {
+            None
+        };
+
+        Ok((
+            DataField::new(&name, data_type),
+            default_expr,
+            column.comment.clone().unwrap_or_default(),
+        ))
+    }
+
     /// Validate the schema of the table to be created.
-    fn validate_create_table_schema(schema: &DataSchemaRef) -> Result<()> {
+    pub(in crate::planner::binder) fn validate_create_table_schema(
+        schema: &DataSchemaRef,
+    ) -> Result<()> {
         // Check if there are duplicated column names
         let mut name_set = HashSet::new();
         for field in schema.fields() {
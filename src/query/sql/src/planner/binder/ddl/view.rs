@@ -12,29 +12,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use common_ast::ast::AlterViewStmt;
 use common_ast::ast::CreateViewStmt;
 use common_ast::ast::DropViewStmt;
+use common_ast::ast::Engine;
+use common_ast::ast::Identifier;
+use common_ast::ast::Query;
+use common_ast::ast::SetExpr;
+use common_ast::ast::TableReference;
+use common_ast::parser::token::Token;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_storages_table_meta::table::OPT_KEY_DATABASE_ID;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_PREFIX;
+use common_storages_view::materialized_view::OPT_KEY_MATERIALIZED_VIEW_QUERY;
 
 use crate::binder::Binder;
 use crate::planner::semantic::normalize_identifier;
 use crate::plans::AlterViewPlan;
+use crate::plans::CreateMaterializedViewPlan;
+use crate::plans::CreateTablePlanV2;
 use crate::plans::CreateViewPlan;
 use crate::plans::DropViewPlan;
 use crate::plans::Plan;
+use crate::BindContext;
+use crate::MaterializedView;
 
 impl<'a> Binder {
     pub(in crate::planner::binder) async fn bind_create_view(
         &mut self,
         stmt: &CreateViewStmt<'a>,
     ) -> Result<Plan> {
+        if stmt.materialized {
+            return self.bind_create_materialized_view(stmt).await;
+        }
+
         let CreateViewStmt {
             if_not_exists,
             catalog,
             database,
             view,
             query,
+            ..
         } = stmt;
 
         let tenant = self.ctx.get_tenant();
@@ -60,6 +86,202 @@ impl<'a> Binder {
         Ok(Plan::CreateView(Box::new(plan)))
     }
 
+    async fn bind_create_materialized_view(&mut self, stmt: &CreateViewStmt<'a>) -> Result<Plan> {
+        let CreateViewStmt {
+            if_not_exists,
+            catalog,
+            database,
+            view,
+            query,
+            ..
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+
+        let materialized_view = MaterializedView::try_from_query(query, &self.name_resolution_ctx)?;
+        let base_database = materialized_view
+            .database
+            .clone()
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let base_table = self
+            .ctx
+            .get_table(&catalog, &base_database, &materialized_view.table)
+            .await?;
+        if base_table.engine() != "FUSE" {
+            return Err(ErrorCode::SemanticError(format!(
+                "Materialized view is only supported on FUSE tables, but {}.{} is {}",
+                base_database,
+                base_table.name(),
+                base_table.engine()
+            )));
+        }
+
+        let init_bind_context = BindContext::new();
+        let (_s_expr, bind_context) = self.bind_query(&init_bind_context, query).await?;
+        let fields = bind_context
+            .columns
+            .iter()
+            .map(|column_binding| {
+                DataField::new(
+                    &column_binding.column_name,
+                    *column_binding.data_type.clone(),
+                )
+            })
+            .collect();
+        let schema = DataSchemaRefExt::create(fields);
+        Self::validate_create_table_schema(&schema)?;
+
+        // The view is refreshed in other sessions, the base table must not depend on the
+        // current database.
+        let mut query = query.clone();
+        if let SetExpr::Select(select) = &mut query.body {
+            if let Some(TableReference::Table { database, .. }) = select.from.first_mut() {
+                *database = Some(Identifier {
+                    name: base_database.clone(),
+                    quote: Some('`'),
+                    span: Token::new_eoi(""),
+                });
+            }
+        }
+
+        let db = self
+            .ctx
+            .get_catalog(&catalog)?
+            .get_database(&tenant, &database)
+            .await?;
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_DATABASE_ID.to_owned(),
+            db.get_db_info().ident.db_id.to_string(),
+        );
+        options.insert(
+            OPT_KEY_MATERIALIZED_VIEW_QUERY.to_owned(),
+            query.to_string(),
+        );
+        options.insert(
+            OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID.to_owned(),
+            base_table.get_id().to_string(),
+        );
+
+        let create_table = CreateTablePlanV2 {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table: viewname,
+            schema,
+            engine: Engine::Fuse,
+            storage_params: None,
+            options,
+            field_default_exprs: vec![],
+            field_comments: vec![],
+            cluster_key: None,
+            as_select: None,
+            clone_source: None,
+            template: None,
+        };
+        let plan = CreateMaterializedViewPlan {
+            create_table: Box::new(create_table),
+            base_database,
+            base_table: base_table.name().to_string(),
+        };
+        Ok(Plan::CreateMaterializedView(Box::new(plan)))
+    }
+
+    /// Rewrites the aggregate query on a single table to read a materialized view of the table
+    /// instead, if there is a fresh one which can answer the query.
+    pub(in crate::planner::binder) async fn rewrite_with_materialized_view(
+        &mut self,
+        query: &Query<'a>,
+    ) -> Result<Option<String>> {
+        let (catalog, database, table) = match &query.body {
+            SetExpr::Select(select) if !select.group_by.is_empty() => {
+                match select.from.as_slice() {
+                    [
+                        TableReference::Table {
+                            catalog,
+                            database,
+                            table,
+                            travel_point: None,
+                            ..
+                        },
+                    ] => (catalog, database, table),
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        // Errors are left to the binding of the original query.
+        let table = match self
+            .resolve_data_source(&tenant, &catalog, &database, &table, &None)
+            .await
+        {
+            Ok(table) => table,
+            Err(_) => return Ok(None),
+        };
+
+        let base_table_id = table.get_id().to_string();
+        let base_snapshot = table
+            .options()
+            .get(OPT_KEY_SNAPSHOT_LOCATION)
+            .cloned()
+            .unwrap_or_default();
+        let views = table
+            .options()
+            .iter()
+            .filter_map(|(key, view_id)| {
+                let (database, view) = key
+                    .strip_prefix(OPT_KEY_MATERIALIZED_VIEW_PREFIX)?
+                    .split_once('.')?;
+                Some((database.to_string(), view.to_string(), view_id.clone()))
+            })
+            .collect::<Vec<_>>();
+        let catalog = self.catalogs.get_catalog(&catalog)?;
+        for (database, view, view_id) in views {
+            // The view may have been dropped, and it is used only if it is fresh.
+            let view_table = match catalog.get_table(&tenant, &database, &view).await {
+                Ok(view_table) if view_table.get_id().to_string() == view_id => view_table,
+                _ => continue,
+            };
+            let options = view_table.options();
+            if options.get(OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID) != Some(&base_table_id)
+                || options.get(OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT) != Some(&base_snapshot)
+            {
+                continue;
+            }
+            let materialized_view = match options.get(OPT_KEY_MATERIALIZED_VIEW_QUERY) {
+                Some(sql) => MaterializedView::parse(sql, &self.name_resolution_ctx)?,
+                None => continue,
+            };
+            if let Some(rewritten) =
+                materialized_view.rewrite_query(query, &self.name_resolution_ctx, &database, &view)
+            {
+                return Ok(Some(rewritten));
+            }
+        }
+        Ok(None)
+    }
+
     pub(in crate::planner::binder) async fn bind_alter_view(
         &mut self,
         stmt: &AlterViewStmt<'a>,
@@ -20,6 +20,7 @@ use crate::binder::Binder;
 use crate::binder::ColumnBinding;
 use crate::optimizer::SExpr;
 use crate::planner::semantic::GroupingChecker;
+use crate::planner::semantic::WindowChecker;
 use crate::plans::Aggregate;
 use crate::plans::AggregateMode;
 use crate::plans::BoundColumnRef;
@@ -40,16 +41,19 @@ impl Binder {
         let scalar_items: Vec<ScalarItem> = scalar_items
             .drain()
             .map(|(_, item)| {
+                let mut scalar = item.scalar;
                 if bind_context.in_grouping {
                     let mut group_checker = GroupingChecker::new(bind_context);
-                    let scalar = group_checker.resolve(&item.scalar, None)?;
-                    Ok(ScalarItem {
-                        scalar,
-                        index: item.index,
-                    })
-                } else {
-                    Ok(item)
+                    scalar = group_checker.resolve(&scalar, None)?;
                 }
+                if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    scalar = window_checker.resolve(&scalar)?;
+                }
+                Ok(ScalarItem {
+                    scalar,
+                    index: item.index,
+                })
             })
             .collect::<Result<_>>()?;
 
@@ -14,10 +14,13 @@
 
 use common_ast::ast::Expr;
 use common_ast::parser::token::Token;
+use common_ast::DisplayError;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::select::SelectList;
 use crate::binder::aggregate::AggregateRewriter;
+use crate::binder::find_window_functions;
 use crate::binder::split_conjunctions;
 use crate::binder::ScalarBinder;
 use crate::optimizer::SExpr;
@@ -49,6 +52,11 @@ impl<'a> Binder {
             &aliases,
         );
         let (scalar, _) = scalar_binder.bind(having).await?;
+        if !find_window_functions(&scalar)?.is_empty() {
+            return Err(ErrorCode::SemanticError(having.span().display_error(
+                "window functions are not allowed in HAVING clause".to_string(),
+            )));
+        }
         let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
         Ok((rewriter.visit(&scalar)?, having.span()))
     }
@@ -351,7 +351,7 @@ pub fn check_duplicate_join_tables(
     Ok(())
 }
 
-struct JoinConditionResolver<'a> {
+pub(super) struct JoinConditionResolver<'a> {
     ctx: Arc<dyn TableContext>,
     name_resolution_ctx: &'a NameResolutionContext,
     metadata: MetadataRef,
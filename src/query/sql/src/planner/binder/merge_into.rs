@@ -0,0 +1,321 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::JoinCondition;
+use common_ast::ast::JoinOperator;
+use common_ast::ast::MatchedAction;
+use common_ast::ast::MergeClause;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::TableReference;
+use common_ast::ast::UpdateExpr;
+use common_datavalues::wrap_nullable;
+use common_datavalues::BooleanType;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::join::JoinConditionResolver;
+use crate::binder::wrap_cast_if_needed;
+use crate::binder::Binder;
+use crate::binder::ScalarBinder;
+use crate::normalize_identifier;
+use crate::optimizer::optimize;
+use crate::optimizer::OptimizerConfig;
+use crate::optimizer::OptimizerContext;
+use crate::plans::AndExpr;
+use crate::plans::MergeIntoPlan;
+use crate::plans::MergeMatchedAction;
+use crate::plans::MergeMatchedClause;
+use crate::plans::MergeNotMatchedClause;
+use crate::plans::Plan;
+use crate::plans::Scalar;
+use crate::BindContext;
+use crate::ScalarExpr;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_merge_into(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &MergeIntoStmt<'a>,
+    ) -> Result<Plan> {
+        let MergeIntoStmt {
+            target,
+            source,
+            join_expr,
+            clauses,
+        } = stmt;
+
+        let (catalog_name, database_name, table_name) = if let TableReference::Table {
+            catalog,
+            database,
+            table,
+            ..
+        } = target
+        {
+            (
+                catalog
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_catalog(), |i| i.name.clone()),
+                database
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_database(), |i| i.name.clone()),
+                table.name.clone(),
+            )
+        } else {
+            return Err(ErrorCode::Internal(
+                "should not happen, parser should have report error already",
+            ));
+        };
+
+        let table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let table_id = table.get_id();
+        let schema = table.schema();
+
+        let (_, target_context) = self.bind_table_reference(bind_context, target).await?;
+        let (source_expr, source_context) = self.bind_table_reference(bind_context, source).await?;
+
+        // Columns of the target come first, followed by the columns of the source.
+        let mut join_context = bind_context.replace();
+        for column in target_context.all_column_bindings() {
+            join_context.add_column_binding(column.clone());
+        }
+        for column in source_context.all_column_bindings() {
+            join_context.add_column_binding(column.clone());
+        }
+
+        let mut target_keys = vec![];
+        let mut source_keys = vec![];
+        let mut non_equi_conditions = vec![];
+        let mut other_conditions = vec![];
+        let join_condition = JoinCondition::On(Box::new(join_expr.clone()));
+        let mut join_condition_resolver = JoinConditionResolver::new(
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            JoinOperator::Inner,
+            &target_context,
+            &source_context,
+            &mut join_context,
+            &join_condition,
+        );
+        join_condition_resolver
+            .resolve(
+                &mut target_keys,
+                &mut source_keys,
+                &mut non_equi_conditions,
+                &mut other_conditions,
+                &JoinOperator::Inner,
+            )
+            .await?;
+        if target_keys.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "the ON clause of MERGE INTO must contain at least one equality between the target and the source",
+            ));
+        }
+        non_equi_conditions.extend(other_conditions);
+        let join_filter = non_equi_conditions.into_iter().reduce(and_scalar);
+
+        let mut matched_clauses = vec![];
+        let mut not_matched_clauses = vec![];
+        for clause in clauses {
+            match clause {
+                MergeClause::Matched { condition, action } => {
+                    let condition = self.bind_merge_condition(&join_context, condition).await?;
+                    let action = match action {
+                        MatchedAction::Update { update_list } => MergeMatchedAction::Update(
+                            self.bind_merge_update_list(&join_context, &schema, update_list)
+                                .await?,
+                        ),
+                        MatchedAction::Delete => MergeMatchedAction::Delete,
+                    };
+                    matched_clauses.push(MergeMatchedClause { condition, action });
+                }
+                MergeClause::NotMatched {
+                    condition,
+                    columns,
+                    values,
+                } => {
+                    // Only the columns of the source are available for the rows that are not matched.
+                    let condition = self
+                        .bind_merge_condition(&source_context, condition)
+                        .await?;
+                    let values = self
+                        .bind_merge_insert_values(&source_context, &schema, columns, values)
+                        .await?;
+                    not_matched_clauses.push(MergeNotMatchedClause { condition, values });
+                }
+            }
+        }
+
+        let source_plan = Plan::Query {
+            s_expr: Box::new(source_expr),
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(source_context.clone()),
+            rewrite_kind: None,
+            ignore_result: false,
+        };
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+            enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+        }));
+        let source_plan = optimize(self.ctx.clone(), opt_ctx, source_plan)?;
+
+        let plan = MergeIntoPlan {
+            catalog: catalog_name,
+            database: database_name,
+            table: table_name,
+            table_id,
+            metadata: self.metadata.clone(),
+            source: Box::new(source_plan),
+            source_columns: source_context
+                .columns
+                .iter()
+                .map(|column| column.index)
+                .collect(),
+            target_columns: target_context
+                .columns
+                .iter()
+                .map(|column| column.index)
+                .collect(),
+            target_keys,
+            source_keys,
+            join_filter,
+            matched_clauses,
+            not_matched_clauses,
+        };
+        Ok(Plan::MergeInto(Box::new(plan)))
+    }
+
+    async fn bind_merge_condition(
+        &mut self,
+        bind_context: &BindContext,
+        condition: &Option<Expr<'a>>,
+    ) -> Result<Option<Scalar>> {
+        match condition {
+            Some(condition) => {
+                let mut scalar_binder = ScalarBinder::new(
+                    bind_context,
+                    self.ctx.clone(),
+                    &self.name_resolution_ctx,
+                    self.metadata.clone(),
+                    &[],
+                );
+                let (scalar, _) = scalar_binder.bind(condition).await?;
+                Ok(Some(scalar))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn bind_merge_update_list(
+        &mut self,
+        bind_context: &BindContext,
+        schema: &DataSchemaRef,
+        update_list: &[UpdateExpr<'a>],
+    ) -> Result<Vec<(usize, Scalar)>> {
+        let mut scalar_binder = ScalarBinder::new(
+            bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let mut updated = HashSet::with_capacity(update_list.len());
+        let mut update_columns = Vec::with_capacity(update_list.len());
+        for update_expr in update_list {
+            let col_name = normalize_identifier(&update_expr.name, &self.name_resolution_ctx).name;
+            let index = schema.index_of(&col_name)?;
+            if !updated.insert(index) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Multiple assignments in the single statement to column `{}`",
+                    col_name
+                )));
+            }
+
+            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+            let scalar = wrap_cast_if_needed(scalar, schema.field(index).data_type());
+            update_columns.push((index, scalar));
+        }
+        Ok(update_columns)
+    }
+
+    async fn bind_merge_insert_values(
+        &mut self,
+        bind_context: &BindContext,
+        schema: &DataSchemaRef,
+        columns: &[Identifier<'a>],
+        values: &[Expr<'a>],
+    ) -> Result<Vec<Option<Scalar>>> {
+        let indices = if columns.is_empty() {
+            (0..schema.num_fields()).collect::<Vec<_>>()
+        } else {
+            let mut indices = Vec::with_capacity(columns.len());
+            for column in columns {
+                let col_name = normalize_identifier(column, &self.name_resolution_ctx).name;
+                let index = schema.index_of(&col_name)?;
+                if indices.contains(&index) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column `{}` is specified more than once in the INSERT clause",
+                        col_name
+                    )));
+                }
+                indices.push(index);
+            }
+            indices
+        };
+        if indices.len() != values.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "INSERT clause has {} columns but {} values",
+                indices.len(),
+                values.len()
+            )));
+        }
+
+        let mut scalar_binder = ScalarBinder::new(
+            bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let mut insert_values = vec![None; schema.num_fields()];
+        for (index, value) in indices.into_iter().zip(values) {
+            let (scalar, _) = scalar_binder.bind(value).await?;
+            insert_values[index] =
+                Some(wrap_cast_if_needed(scalar, schema.field(index).data_type()));
+        }
+        Ok(insert_values)
+    }
+}
+
+fn and_scalar(left: Scalar, right: Scalar) -> Scalar {
+    let return_type = if left.data_type().is_nullable() || right.data_type().is_nullable() {
+        wrap_nullable(&BooleanType::new_impl())
+    } else {
+        BooleanType::new_impl()
+    };
+    Scalar::AndExpr(AndExpr {
+        left: Box::new(left),
+        right: Box::new(right),
+        return_type: Box::new(return_type),
+    })
+}
@@ -25,8 +25,10 @@ mod insert;
 mod join;
 mod kill;
 mod limit;
+mod merge_into;
 mod presign;
 mod project;
+mod recursive_cte;
 mod scalar;
 mod scalar_common;
 mod scalar_visitor;
@@ -36,9 +38,12 @@ mod show;
 mod sort;
 mod table;
 mod update;
+mod window;
 
 pub use aggregate::AggregateInfo;
 pub use bind_context::*;
 pub use binder::Binder;
+pub use copy::parse_stage_location_v2;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use window::WindowInfo;
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 
 use common_ast::ast::Indirection;
 use common_ast::ast::SelectTarget;
+use common_ast::ast::WindowDefinition;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -30,6 +31,7 @@ use crate::planner::binder::Binder;
 use crate::planner::binder::ColumnBinding;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::GroupingChecker;
+use crate::planner::semantic::WindowChecker;
 use crate::plans::BoundColumnRef;
 use crate::plans::EvalScalar;
 use crate::plans::Scalar;
@@ -105,16 +107,19 @@ impl<'a> Binder {
         let scalars = scalars
             .iter()
             .map(|(_, item)| {
+                let mut scalar = item.scalar.clone();
                 if bind_context.in_grouping {
                     let mut grouping_checker = GroupingChecker::new(bind_context);
-                    let scalar = grouping_checker.resolve(&item.scalar, None)?;
-                    Ok(ScalarItem {
-                        scalar,
-                        index: item.index,
-                    })
-                } else {
-                    Ok(item.clone())
+                    scalar = grouping_checker.resolve(&scalar, None)?;
+                }
+                if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    scalar = window_checker.resolve(&scalar)?;
                 }
+                Ok(ScalarItem {
+                    scalar,
+                    index: item.index,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
         let eval_scalar = EvalScalar { items: scalars };
@@ -147,6 +152,7 @@ impl<'a> Binder {
         &mut self,
         input_context: &BindContext,
         select_list: &'a [SelectTarget<'a>],
+        windows: &'a [WindowDefinition<'a>],
     ) -> Result<SelectList<'a>> {
         let mut output = SelectList::<'a>::default();
         for select_target in select_list {
@@ -206,7 +212,8 @@ impl<'a> Binder {
                         &self.name_resolution_ctx,
                         self.metadata.clone(),
                         &[],
-                    );
+                    )
+                    .with_windows(windows);
                     let (bound_expr, _) = scalar_binder.bind(expr).await?;
 
                     // If alias is not specified, we will generate a name for the scalar expression.
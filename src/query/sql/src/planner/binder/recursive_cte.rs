@@ -0,0 +1,230 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
+use common_ast::ast::TableReference;
+use common_ast::ast::CTE;
+use common_ast::walk_table_reference;
+use common_ast::Visitor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::CteInfo;
+use crate::binder::Visibility;
+use crate::normalize_identifier;
+use crate::optimizer::SExpr;
+use crate::planner::binder::BindContext;
+use crate::planner::binder::Binder;
+use crate::plans::BoundColumnRef;
+use crate::plans::CastExpr;
+use crate::plans::EvalScalar;
+use crate::plans::RecursiveCteScan;
+use crate::plans::RecursiveUnion;
+use crate::plans::ScalarItem;
+use crate::ColumnBinding;
+use crate::NameResolutionContext;
+
+impl Binder {
+    /// Bind a common table expression of a `WITH RECURSIVE` clause.
+    ///
+    /// A recursive CTE must be of the form `anchor UNION [ALL] recursive_term`,
+    /// where only `recursive_term` references the CTE itself. Each reference is
+    /// bound to a `RecursiveCteScan` over the working table, whose columns take
+    /// the types of the anchor. CTEs that never reference themselves are bound
+    /// as ordinary CTEs.
+    pub(super) async fn bind_recursive_cte(
+        &mut self,
+        bind_context: &BindContext,
+        cte: &CTE<'_>,
+    ) -> Result<(SExpr, BindContext)> {
+        let cte_name = cte.alias.name.name.clone();
+        let set_operation = match &cte.query.body {
+            SetExpr::SetOperation(set_operation)
+                if set_operation.op == SetOperator::Union
+                    && self.is_cte_referenced(&cte_name, &set_operation.right) =>
+            {
+                set_operation
+            }
+            _ => {
+                if self.is_cte_referenced(&cte_name, &cte.query.body) {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "recursive query \"{cte_name}\" must be of the form non-recursive-term UNION [ALL] recursive-term"
+                    )));
+                }
+                return self.bind_query(bind_context, &cte.query).await;
+            }
+        };
+        if self.is_cte_referenced(&cte_name, &set_operation.left) {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive reference to query \"{cte_name}\" must not appear within its non-recursive term"
+            )));
+        }
+        if !cte.query.order_by.is_empty()
+            || !cte.query.limit.is_empty()
+            || cte.query.offset.is_some()
+        {
+            return Err(ErrorCode::SemanticError(format!(
+                "ORDER BY, LIMIT and OFFSET are not allowed in recursive query \"{cte_name}\""
+            )));
+        }
+
+        let (anchor_expr, anchor_context) = self
+            .bind_set_expr(bind_context, &set_operation.left, &[])
+            .await?;
+
+        let columns_alias: Vec<String> = cte.alias.columns.iter().map(|c| c.name.clone()).collect();
+        if columns_alias.len() > anchor_context.columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "table has {} columns available but {} columns specified",
+                anchor_context.columns.len(),
+                columns_alias.len()
+            )));
+        }
+
+        // Columns of the working table, which are read by the recursive term
+        let mut scan_context = BindContext::new();
+        for (idx, anchor_column) in anchor_context.columns.iter().enumerate() {
+            let column_name = columns_alias
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| anchor_column.column_name.clone());
+            let index = self.metadata.write().add_column(
+                column_name.clone(),
+                *anchor_column.data_type.clone(),
+                None,
+                None,
+            );
+            scan_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: Some(cte_name.clone()),
+                column_name,
+                index,
+                data_type: anchor_column.data_type.clone(),
+                visibility: Visibility::Visible,
+            });
+        }
+        let scan_columns: Vec<_> = scan_context.columns.iter().map(|c| c.index).collect();
+        let scan_plan = RecursiveCteScan {
+            cte_name: cte_name.clone(),
+            columns: scan_columns.clone(),
+        };
+        bind_context.ctes_map.insert(cte_name.clone(), CteInfo {
+            columns_alias: columns_alias.clone(),
+            s_expr: SExpr::create_leaf(scan_plan.into()),
+            bind_context: scan_context,
+        });
+
+        let (mut recursive_expr, recursive_context) = self
+            .bind_set_expr(bind_context, &set_operation.right, &[])
+            .await?;
+        if anchor_context.columns.len() != recursive_context.columns.len() {
+            return Err(ErrorCode::SemanticError(
+                "SetOperation must have the same number of columns",
+            ));
+        }
+
+        // The rows of the recursive term are fed back into the working table,
+        // so they are cast to the types of the anchor.
+        let mut scalar_items = vec![];
+        let mut new_bind_context = BindContext::new();
+        let mut pairs = Vec::with_capacity(anchor_context.columns.len());
+        for (anchor_column, recursive_column) in anchor_context
+            .columns
+            .iter()
+            .zip(recursive_context.columns.iter())
+        {
+            let recursive_index = if recursive_column.data_type != anchor_column.data_type {
+                let new_column_index = self.metadata.write().add_column(
+                    recursive_column.column_name.clone(),
+                    *anchor_column.data_type.clone(),
+                    None,
+                    None,
+                );
+                scalar_items.push(ScalarItem {
+                    scalar: CastExpr {
+                        argument: Box::new(
+                            BoundColumnRef {
+                                column: recursive_column.clone(),
+                            }
+                            .into(),
+                        ),
+                        from_type: recursive_column.data_type.clone(),
+                        target_type: anchor_column.data_type.clone(),
+                    }
+                    .into(),
+                    index: new_column_index,
+                });
+                new_column_index
+            } else {
+                recursive_column.index
+            };
+            new_bind_context.add_column_binding(anchor_column.clone());
+            pairs.push((anchor_column.index, recursive_index));
+        }
+        if !scalar_items.is_empty() {
+            recursive_expr = SExpr::create_unary(
+                EvalScalar {
+                    items: scalar_items,
+                }
+                .into(),
+                recursive_expr,
+            );
+        }
+
+        let union_plan = RecursiveUnion {
+            cte_name,
+            pairs,
+            scan_columns,
+            distinct: !set_operation.all,
+        };
+        Ok((
+            SExpr::create_binary(union_plan.into(), anchor_expr, recursive_expr),
+            new_bind_context,
+        ))
+    }
+
+    fn is_cte_referenced(&self, cte_name: &str, set_expr: &SetExpr<'_>) -> bool {
+        let mut finder = CteReferenceFinder {
+            cte_name,
+            name_resolution_ctx: &self.name_resolution_ctx,
+            found: false,
+        };
+        finder.visit_set_expr(set_expr);
+        finder.found
+    }
+}
+
+/// Find table references to a CTE by name.
+struct CteReferenceFinder<'b> {
+    cte_name: &'b str,
+    name_resolution_ctx: &'b NameResolutionContext,
+    found: bool,
+}
+
+impl<'ast, 'b> Visitor<'ast> for CteReferenceFinder<'b> {
+    fn visit_table_reference(&mut self, table_ref: &'ast TableReference<'ast>) {
+        if let TableReference::Table {
+            database: None,
+            table,
+            ..
+        } = table_ref
+        {
+            if normalize_identifier(table, self.name_resolution_ctx).name == self.cte_name {
+                self.found = true;
+            }
+        }
+        walk_table_reference(self, table_ref);
+    }
+}
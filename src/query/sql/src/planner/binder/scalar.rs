@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use common_ast::ast::Expr;
+use common_ast::ast::WindowDefinition;
 use common_catalog::table_context::TableContext;
 use common_datavalues::DataTypeImpl;
 use common_exception::Result;
@@ -32,6 +33,7 @@ pub struct ScalarBinder<'a> {
     name_resolution_ctx: &'a NameResolutionContext,
     metadata: MetadataRef,
     aliases: &'a [(String, Scalar)],
+    windows: &'a [WindowDefinition<'a>],
 }
 
 impl<'a> ScalarBinder<'a> {
@@ -48,9 +50,16 @@ impl<'a> ScalarBinder<'a> {
             name_resolution_ctx,
             metadata,
             aliases,
+            windows: &[],
         }
     }
 
+    /// Set the named windows that can be referenced with `OVER window_name`.
+    pub fn with_windows(mut self, windows: &'a [WindowDefinition<'a>]) -> Self {
+        self.windows = windows;
+        self
+    }
+
     pub async fn bind(&mut self, expr: &Expr<'a>) -> Result<(Scalar, DataTypeImpl)> {
         let mut type_checker = TypeChecker::new(
             self.bind_context,
@@ -58,7 +67,8 @@ impl<'a> ScalarBinder<'a> {
             self.name_resolution_ctx,
             self.metadata.clone(),
             self.aliases,
-        );
+        )
+        .with_windows(self.windows);
         Ok(*type_checker.resolve(expr, None).await?)
     }
 }
@@ -40,7 +40,6 @@ impl<'a, F> Finder<'a, F>
 where F: Fn(&Scalar) -> bool
 {
     /// Create a new finder with the `test_fn`
-    #[allow(dead_code)]
     fn new(find_fn: &'a F) -> Self {
         Self {
             find_fn,
@@ -65,6 +64,13 @@ where F: Fn(&Scalar) -> bool
     }
 }
 
+/// Find all the window functions in the scalar expression.
+pub fn find_window_functions(scalar: &Scalar) -> Result<Vec<Scalar>> {
+    let find_fn = |scalar: &Scalar| matches!(scalar, Scalar::WindowFunction(_));
+    let finder = scalar.accept(Finder::new(&find_fn))?;
+    Ok(finder.scalars)
+}
+
 pub fn split_conjunctions(scalar: &Scalar) -> Vec<Scalar> {
     match scalar {
         Scalar::AndExpr(AndExpr { left, right, .. }) => {
@@ -79,6 +79,11 @@ pub trait ScalarVisitor: Sized {
                                     stack.push(RecursionProcessing::Call(argument))
                                 }
                                 Scalar::SubqueryExpr(_) => {}
+                                Scalar::WindowFunction(window) => {
+                                    for scalar in window.input_scalars() {
+                                        stack.push(RecursionProcessing::Call(scalar));
+                                    }
+                                }
                             }
 
                             visitor
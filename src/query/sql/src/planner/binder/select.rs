@@ -26,12 +26,17 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::DisplayError;
+use common_datavalues::remove_nullable;
 use common_datavalues::type_coercion::compare_coercion;
+use common_datavalues::BooleanType;
+use common_datavalues::DataType;
 use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::binder::join::JoinConditions;
+use crate::binder::scalar_common::find_window_functions;
 use crate::binder::scalar_common::split_conjunctions;
 use crate::binder::CteInfo;
 use crate::binder::Visibility;
@@ -41,12 +46,19 @@ use crate::planner::binder::BindContext;
 use crate::planner::binder::Binder;
 use crate::plans::BoundColumnRef;
 use crate::plans::CastExpr;
+use crate::plans::ConstantExpr;
 use crate::plans::EvalScalar;
 use crate::plans::Filter;
+use crate::plans::FunctionCall;
 use crate::plans::JoinType;
 use crate::plans::Scalar;
 use crate::plans::ScalarItem;
 use crate::plans::UnionAll;
+use crate::plans::Window;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncFrameBound;
+use crate::plans::WindowFuncFrameUnits;
+use crate::plans::WindowFuncType;
 use crate::ColumnBinding;
 use crate::IndexType;
 
@@ -97,7 +109,11 @@ impl<'a> Binder {
 
         // Generate a analyzed select list with from context
         let mut select_list = self
-            .normalize_select_list(&from_context, &stmt.select_list)
+            .normalize_select_list(
+                &from_context,
+                &stmt.select_list,
+                stmt.window_list.as_deref().unwrap_or(&[]),
+            )
             .await?;
 
         let (mut scalar_items, projections) = self.analyze_projection(&select_list)?;
@@ -108,6 +124,10 @@ impl<'a> Binder {
 
         self.analyze_aggregate_select(&mut from_context, &mut select_list)?;
 
+        // Window functions are rewritten after aggregates, since the inputs of
+        // window functions may contain aggregate functions.
+        self.analyze_window_select(&mut from_context, &mut select_list)?;
+
         let having = if let Some(having) = &stmt.having {
             Some(
                 self.analyze_aggregate_having(&mut from_context, &select_list, having)
@@ -138,6 +158,10 @@ impl<'a> Binder {
                 .await?;
         }
 
+        if !from_context.window_info.window_functions.is_empty() {
+            s_expr = self.bind_window(&from_context, s_expr)?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -201,7 +225,11 @@ impl<'a> Binder {
                         "duplicate cte {table_name}"
                     )));
                 }
-                let (s_expr, cte_bind_context) = self.bind_query(bind_context, &cte.query).await?;
+                let (s_expr, cte_bind_context) = if with.recursive {
+                    self.bind_recursive_cte(bind_context, cte).await?
+                } else {
+                    self.bind_query(bind_context, &cte.query).await?
+                };
                 let cte_info = CteInfo {
                     columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
                     s_expr,
@@ -265,6 +293,11 @@ impl<'a> Binder {
             &[],
         );
         let (scalar, _) = scalar_binder.bind(expr).await?;
+        if !find_window_functions(&scalar)?.is_empty() {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in WHERE clause".to_string(),
+            )));
+        }
         let filter_plan = Filter {
             predicates: split_conjunctions(&scalar),
             is_having: false,
@@ -312,6 +345,20 @@ impl<'a> Binder {
                 // Transfer Except to Anti join
                 self.bind_except(left_bind_context, right_bind_context, left_expr, right_expr)
             }
+            (SetOperator::Intersect, true) => self.bind_intersect_or_except_all(
+                left_bind_context,
+                right_bind_context,
+                left_expr,
+                right_expr,
+                JoinType::LeftSemi,
+            ),
+            (SetOperator::Except, true) => self.bind_intersect_or_except_all(
+                left_bind_context,
+                right_bind_context,
+                left_expr,
+                right_expr,
+                JoinType::LeftAnti,
+            ),
             (SetOperator::Union, true) => self.bind_union(
                 left_bind_context,
                 right_bind_context,
@@ -328,9 +375,6 @@ impl<'a> Binder {
                 right_expr,
                 true,
             ),
-            _ => Err(ErrorCode::Unimplemented(
-                "Unsupported query type, currently, databend only support intersect distinct and except distinct",
-            )),
         }
     }
 
@@ -410,37 +454,76 @@ impl<'a> Binder {
             &mut HashMap::new(),
             left_expr,
         )?;
-        let mut left_conditions = Vec::with_capacity(left_context.columns.len());
-        let mut right_conditions = Vec::with_capacity(right_context.columns.len());
-        assert_eq!(left_context.columns.len(), right_context.columns.len());
-        for (left_column, right_column) in left_context
-            .columns
-            .iter()
-            .zip(right_context.columns.iter())
-        {
-            left_conditions.push(
-                BoundColumnRef {
-                    column: left_column.clone(),
-                }
-                .into(),
-            );
-            right_conditions.push(
-                BoundColumnRef {
-                    column: right_column.clone(),
-                }
-                .into(),
-            );
-        }
-        let join_conditions = JoinConditions {
-            left_conditions,
-            right_conditions,
-            non_equi_conditions: vec![],
-            other_conditions: vec![],
-        };
+        let join_conditions =
+            set_operation_join_conditions(&left_context.columns, &right_context.columns)?;
+        let s_expr = self.bind_join_with_type(join_type, join_conditions, left_expr, right_expr)?;
+        Ok((s_expr, left_context))
+    }
+
+    /// `INTERSECT ALL` and `EXCEPT ALL` keep duplicated rows, so the n-th occurrence
+    /// of a row on the left side should only match the n-th occurrence of the same
+    /// row on the right side. Number the duplicated rows of both sides with
+    /// `row_number()`, then transfer them to Semi/Anti join with the row number as
+    /// an additional join key.
+    fn bind_intersect_or_except_all(
+        &mut self,
+        left_context: BindContext,
+        right_context: BindContext,
+        left_expr: SExpr,
+        right_expr: SExpr,
+        join_type: JoinType,
+    ) -> Result<(SExpr, BindContext)> {
+        let (left_expr, left_row_number) = self.bind_duplicate_row_number(&left_context, left_expr);
+        let (right_expr, right_row_number) =
+            self.bind_duplicate_row_number(&right_context, right_expr);
+
+        let mut left_columns = left_context.columns.clone();
+        left_columns.push(left_row_number);
+        let mut right_columns = right_context.columns.clone();
+        right_columns.push(right_row_number);
+        let join_conditions = set_operation_join_conditions(&left_columns, &right_columns)?;
         let s_expr = self.bind_join_with_type(join_type, join_conditions, left_expr, right_expr)?;
         Ok((s_expr, left_context))
     }
 
+    /// Append `row_number() OVER (PARTITION BY <all columns>)` to the input.
+    fn bind_duplicate_row_number(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> (SExpr, ColumnBinding) {
+        let data_type = WindowFuncType::RowNumber.return_type();
+        let index = self.metadata.write().add_column(
+            "row_number".to_string(),
+            data_type.clone(),
+            None,
+            None,
+        );
+        let window_plan = Window {
+            index,
+            function: WindowFuncType::RowNumber,
+            partition_by: bind_context.columns.iter().map(|c| c.index).collect(),
+            order_by: vec![],
+            frame: WindowFuncFrame {
+                units: WindowFuncFrameUnits::Rows,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::Following(None),
+            },
+        };
+        let column_binding = ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name: "row_number".to_string(),
+            index,
+            data_type: Box::new(data_type),
+            visibility: Visibility::InVisible,
+        };
+        (
+            SExpr::create_unary(window_plan.into(), child),
+            column_binding,
+        )
+    }
+
     #[allow(clippy::type_complexity)]
     fn coercion_union_type(
         &self,
@@ -543,3 +626,82 @@ impl<'a> Binder {
         Ok((new_bind_context, pairs, left_expr, right_expr))
     }
 }
+
+/// Builds the join conditions of the Semi/Anti join transferred from a set operation.
+///
+/// Set operations take NULLs as equal, but the hash join never matches NULL keys. So a
+/// nullable column is joined by a pair of non-null keys instead: `is_not_null(c)`, and
+/// `c` with NULL replaced by the default value of its type.
+fn set_operation_join_conditions(
+    left_columns: &[ColumnBinding],
+    right_columns: &[ColumnBinding],
+) -> Result<JoinConditions> {
+    if left_columns.len() != right_columns.len() {
+        return Err(ErrorCode::SemanticError(
+            "SetOperation must have the same number of columns",
+        ));
+    }
+    let mut left_conditions = Vec::with_capacity(left_columns.len());
+    let mut right_conditions = Vec::with_capacity(right_columns.len());
+    for (left_column, right_column) in left_columns.iter().zip(right_columns.iter()) {
+        if left_column.data_type.is_nullable() || right_column.data_type.is_nullable() {
+            left_conditions.extend(null_safe_join_keys(left_column));
+            right_conditions.extend(null_safe_join_keys(right_column));
+        } else {
+            left_conditions.push(
+                BoundColumnRef {
+                    column: left_column.clone(),
+                }
+                .into(),
+            );
+            right_conditions.push(
+                BoundColumnRef {
+                    column: right_column.clone(),
+                }
+                .into(),
+            );
+        }
+    }
+    Ok(JoinConditions {
+        left_conditions,
+        right_conditions,
+        non_equi_conditions: vec![],
+        other_conditions: vec![],
+    })
+}
+
+fn null_safe_join_keys(column: &ColumnBinding) -> [Scalar; 2] {
+    let column_ref: Scalar = BoundColumnRef {
+        column: column.clone(),
+    }
+    .into();
+    let data_type = *column.data_type.clone();
+    let inner_type = remove_nullable(&data_type);
+    let is_not_null = Scalar::FunctionCall(FunctionCall {
+        arguments: vec![column_ref.clone()],
+        func_name: "is_not_null".to_string(),
+        arg_types: vec![data_type.clone()],
+        return_type: Box::new(BooleanType::new_impl()),
+    });
+    let not_null = Scalar::FunctionCall(FunctionCall {
+        arguments: vec![column_ref],
+        func_name: "assume_not_null".to_string(),
+        arg_types: vec![data_type],
+        return_type: Box::new(inner_type.clone()),
+    });
+    let default = Scalar::ConstantExpr(ConstantExpr {
+        value: inner_type.default_value(),
+        data_type: Box::new(inner_type.clone()),
+    });
+    let value = Scalar::FunctionCall(FunctionCall {
+        arguments: vec![is_not_null.clone(), not_null, default],
+        func_name: "if".to_string(),
+        arg_types: vec![
+            BooleanType::new_impl(),
+            inner_type.clone(),
+            inner_type.clone(),
+        ],
+        return_type: Box::new(inner_type),
+    });
+    [is_not_null, value]
+}
@@ -30,6 +30,7 @@ use crate::binder::ColumnBinding;
 use crate::normalize_identifier;
 use crate::optimizer::SExpr;
 use crate::planner::semantic::GroupingChecker;
+use crate::planner::semantic::WindowChecker;
 use crate::plans::AggregateFunction;
 use crate::plans::AndExpr;
 use crate::plans::BoundColumnRef;
@@ -256,6 +257,10 @@ impl<'a> Binder {
                         let mut group_checker = GroupingChecker::new(from_context);
                         scalar = group_checker.resolve(&scalar, None)?;
                     }
+                    if !from_context.window_info.window_functions.is_empty() {
+                        let mut window_checker = WindowChecker::new(from_context);
+                        scalar = window_checker.resolve(&scalar)?;
+                    }
                     scalars.push(ScalarItem { scalar, index });
                 }
             }
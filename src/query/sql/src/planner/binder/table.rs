@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
+use common_ast::ast::Expr;
 use common_ast::ast::Indirection;
+use common_ast::ast::Literal;
+use common_ast::ast::SelectStageOptions;
 use common_ast::ast::SelectStmt;
 use common_ast::ast::SelectTarget;
 use common_ast::ast::Statement;
@@ -27,19 +31,35 @@ use common_ast::Backtrace;
 use common_ast::Dialect;
 use common_ast::DisplayError;
 use common_catalog::catalog::CATALOG_DEFAULT;
+use common_catalog::plan::DataSourceInfo;
+use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::StageTableInfo;
 use common_catalog::table::NavigationPoint;
 use common_catalog::table::Table;
 use common_catalog::table_function::TableFunction;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_types::MatchSeq;
+use common_meta_types::StageFileFormatType;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::stream_table::CHANGE_ACTION_COLUMN;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_DATABASE;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_TABLE;
+use common_storages_view::stream_table::OPT_KEY_STREAM_BASE_TABLE_ID;
+use common_storages_view::stream_table::OPT_KEY_STREAM_OFFSET;
+use common_storages_view::stream_table::STREAM_ENGINE;
 use common_storages_view::view_table::QUERY;
 
+use crate::binder::copy::parse_stage_location_v2;
 use crate::binder::scalar::ScalarBinder;
 use crate::binder::Binder;
 use crate::binder::ColumnBinding;
 use crate::binder::CteInfo;
 use crate::binder::Visibility;
+use crate::optimizer::ColumnStat;
+use crate::optimizer::ColumnStatSet;
 use crate::optimizer::SExpr;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::TypeChecker;
@@ -47,6 +67,7 @@ use crate::plans::ConstantExpr;
 use crate::plans::LogicalGet;
 use crate::plans::Scalar;
 use crate::BindContext;
+use crate::ColumnEntry;
 use crate::IndexType;
 
 impl<'a> Binder {
@@ -79,6 +100,7 @@ impl<'a> Binder {
         );
 
         self.bind_base_table(bind_context, database, table_index)
+            .await
     }
 
     pub(super) async fn bind_table_reference(
@@ -130,11 +152,18 @@ impl<'a> Binder {
                     )
                     .await?;
                 match table_meta.engine() {
-                    "VIEW" => {
-                        let query = table_meta
-                            .options()
-                            .get(QUERY)
-                            .ok_or_else(|| ErrorCode::Internal("Invalid VIEW object"))?;
+                    engine @ ("VIEW" | STREAM_ENGINE) => {
+                        let query = if engine == STREAM_ENGINE {
+                            // A stream is read as the query of the changes of its base table
+                            self.resolve_stream_changes(&catalog, &database, table_meta.as_ref())
+                                .await?
+                        } else {
+                            table_meta
+                                .options()
+                                .get(QUERY)
+                                .cloned()
+                                .ok_or_else(|| ErrorCode::Internal("Invalid VIEW object"))?
+                        };
                         let tokens = tokenize_sql(query.as_str())?;
                         let backtrace = Backtrace::new();
                         let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
@@ -157,7 +186,8 @@ impl<'a> Binder {
                             Ok((s_expr, bind_context))
                         } else {
                             Err(ErrorCode::Internal(format!(
-                                "Invalid VIEW object: {}",
+                                "Invalid {} object: {}",
+                                engine,
                                 table_meta.name()
                             )))
                         }
@@ -168,8 +198,9 @@ impl<'a> Binder {
                                 .write()
                                 .add_table(catalog, database.clone(), table_meta);
 
-                        let (s_expr, mut bind_context) =
-                            self.bind_base_table(bind_context, database.as_str(), table_index)?;
+                        let (s_expr, mut bind_context) = self
+                            .bind_base_table(bind_context, database.as_str(), table_index)
+                            .await?;
                         if let Some(alias) = alias {
                             bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
                         }
@@ -181,8 +212,15 @@ impl<'a> Binder {
                 span: _,
                 name,
                 params,
+                named_params,
                 alias,
             } => {
+                let func_name = normalize_identifier(name, &self.name_resolution_ctx).name;
+                // Reads the parquet files in a stage, as a shorthand of `SELECT FROM @stage`
+                if func_name.eq_ignore_ascii_case("read_parquet") && named_params.is_empty() {
+                    return self.bind_read_parquet(bind_context, params, alias).await;
+                }
+
                 let mut scalar_binder = ScalarBinder::new(
                     bind_context,
                     self.ctx.clone(),
@@ -194,17 +232,30 @@ impl<'a> Binder {
                 for arg in params.iter() {
                     args.push(scalar_binder.bind(arg).await?);
                 }
+                let mut named_args = Vec::with_capacity(named_params.len());
+                for (name, arg) in named_params.iter() {
+                    let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+                    named_args.push((name, scalar_binder.bind(arg).await?));
+                }
 
-                let expressions = args
+                let to_constant = |scalar: Scalar| match scalar {
+                    Scalar::ConstantExpr(ConstantExpr { value, .. }) => Ok(value),
+                    _ => Err(ErrorCode::Unimplemented(format!(
+                        "Unsupported table argument type: {:?}",
+                        scalar
+                    ))),
+                };
+                let mut expressions = args
                     .into_iter()
-                    .map(|(scalar, _)| match scalar {
-                        Scalar::ConstantExpr(ConstantExpr { value, .. }) => Ok(value),
-                        _ => Err(ErrorCode::Unimplemented(format!(
-                            "Unsupported table argument type: {:?}",
-                            scalar
-                        ))),
-                    })
+                    .map(|(scalar, _)| to_constant(scalar))
                     .collect::<Result<Vec<DataValue>>>()?;
+                if !named_args.is_empty() {
+                    let named_args = named_args
+                        .into_iter()
+                        .map(|(name, (scalar, _))| Ok((name, to_constant(scalar)?)))
+                        .collect::<Result<Vec<_>>>()?;
+                    expressions = Self::position_table_args(&func_name, expressions, named_args)?;
+                }
 
                 let table_args = Some(expressions);
 
@@ -212,10 +263,7 @@ impl<'a> Binder {
                 let table_meta: Arc<dyn TableFunction> = self
                     .catalogs
                     .get_catalog(CATALOG_DEFAULT)?
-                    .get_table_function(
-                        &normalize_identifier(name, &self.name_resolution_ctx).name,
-                        table_args,
-                    )?;
+                    .get_table_function(&func_name, table_args)?;
                 let table = table_meta.as_table();
 
                 let table_index = self.metadata.write().add_table(
@@ -224,13 +272,24 @@ impl<'a> Binder {
                     table.clone(),
                 );
 
-                let (s_expr, mut bind_context) =
-                    self.bind_base_table(bind_context, "system", table_index)?;
+                let (s_expr, mut bind_context) = self
+                    .bind_base_table(bind_context, "system", table_index)
+                    .await?;
                 if let Some(alias) = alias {
                     bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
                 }
                 Ok((s_expr, bind_context))
             }
+            TableReference::Stage {
+                span: _,
+                name,
+                path,
+                options,
+                alias,
+            } => {
+                self.bind_stage_table(bind_context, name, path, options, alias)
+                    .await
+            }
             TableReference::Join { span: _, join } => self.bind_join(bind_context, join).await,
             TableReference::Subquery {
                 span: _,
@@ -246,6 +305,160 @@ impl<'a> Binder {
         }
     }
 
+    /// Binds the files in the location `@stage_name/path` as a table, the schema of which is
+    /// inferred from the first file.
+    async fn bind_stage_table(
+        &mut self,
+        bind_context: &BindContext,
+        stage_name: &str,
+        path: &str,
+        options: &SelectStageOptions,
+        alias: &Option<TableAlias<'a>>,
+    ) -> Result<(SExpr, BindContext)> {
+        let (mut stage_info, path) = parse_stage_location_v2(&self.ctx, stage_name, path).await?;
+        if let Some(file_format) = &options.file_format {
+            stage_info.file_format_options.format =
+                StageFileFormatType::from_str(file_format).map_err(ErrorCode::UnknownFormat)?;
+        }
+
+        let mut table_info = StageTableInfo {
+            schema: DataSchemaRefExt::create(vec![]),
+            path,
+            files: options.files.clone(),
+            pattern: options.pattern.clone().unwrap_or_default(),
+            user_stage_info: stage_info,
+        };
+        let mut plan = DataSourcePlan {
+            catalog: CATALOG_DEFAULT.to_string(),
+            source_info: DataSourceInfo::StageSource(table_info.clone()),
+            scan_fields: None,
+            parts: vec![],
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        };
+        let table = self.ctx.build_table_from_source_plan(&plan)?;
+        table_info.schema = table.infer_schema(self.ctx.clone()).await?;
+        plan.source_info = DataSourceInfo::StageSource(table_info);
+        let table = self.ctx.build_table_from_source_plan(&plan)?;
+
+        let table_index = self.metadata.write().add_table(
+            CATALOG_DEFAULT.to_string(),
+            "system".to_string(),
+            table,
+        );
+        let (s_expr, mut bind_context) = self
+            .bind_base_table(bind_context, "system", table_index)
+            .await?;
+        if let Some(alias) = alias {
+            bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
+        }
+        Ok((s_expr, bind_context))
+    }
+
+    /// Puts the named arguments of the table function at the positions of the parameters.
+    fn position_table_args(
+        func_name: &str,
+        args: Vec<DataValue>,
+        named_args: Vec<(String, DataValue)>,
+    ) -> Result<Vec<DataValue>> {
+        let param_names: &[&str] = match func_name.to_lowercase().as_str() {
+            "infer_schema" => &["location", "file_format"],
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "table function {func_name} does not support named arguments"
+                )));
+            }
+        };
+        if args.len() > param_names.len() {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "table function {func_name} expects at most {} arguments, but got {}",
+                param_names.len(),
+                args.len() + named_args.len()
+            )));
+        }
+        let mut values = args.into_iter().map(Some).collect::<Vec<_>>();
+        for (name, value) in named_args {
+            let pos = param_names
+                .iter()
+                .position(|param| param.eq_ignore_ascii_case(&name))
+                .ok_or_else(|| {
+                    ErrorCode::BadArguments(format!(
+                        "unknown argument {name} of table function {func_name}"
+                    ))
+                })?;
+            if values.len() <= pos {
+                values.resize(pos + 1, None);
+            }
+            if values[pos].is_some() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "argument {name} of table function {func_name} is given more than once"
+                )));
+            }
+            values[pos] = Some(value);
+        }
+        values
+            .into_iter()
+            .zip(param_names)
+            .map(|(value, name)| {
+                value.ok_or_else(|| {
+                    ErrorCode::BadArguments(format!(
+                        "argument {name} of table function {func_name} is missing"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Binds `read_parquet('@stage_name/path' [, 'pattern'])`.
+    async fn bind_read_parquet(
+        &mut self,
+        bind_context: &BindContext,
+        params: &[Expr<'a>],
+        alias: &Option<TableAlias<'a>>,
+    ) -> Result<(SExpr, BindContext)> {
+        let args = params
+            .iter()
+            .map(|param| match param {
+                Expr::Literal {
+                    lit: Literal::String(arg),
+                    ..
+                } => Ok(arg.clone()),
+                _ => Err(ErrorCode::BadArguments(format!(
+                    "read_parquet expects string arguments, but got {param}"
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (location, pattern) = match args.as_slice() {
+            [location] => (location, None),
+            [location, pattern] => (location, Some(pattern.clone())),
+            _ => {
+                return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                    "read_parquet expects 1 or 2 arguments, but got {}",
+                    args.len()
+                )));
+            }
+        };
+        let location = location.strip_prefix('@').ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "read_parquet expects a stage location like '@stage/path', but got '{location}'"
+            ))
+        })?;
+        let (stage_name, path) = match location.split_once('/') {
+            Some((stage_name, path)) => (stage_name, format!("/{path}")),
+            None => (location, "/".to_string()),
+        };
+
+        let options = SelectStageOptions {
+            file_format: Some("parquet".to_string()),
+            pattern,
+            files: vec![],
+        };
+        self.bind_stage_table(bind_context, stage_name, &path, &options, alias)
+            .await
+    }
+
     fn bind_cte(
         &mut self,
         bind_context: &BindContext,
@@ -287,7 +500,7 @@ impl<'a> Binder {
         Ok((cte_info.s_expr.clone(), new_bind_context))
     }
 
-    fn bind_base_table(
+    async fn bind_base_table(
         &mut self,
         bind_context: &BindContext,
         database_name: &str,
@@ -312,6 +525,7 @@ impl<'a> Binder {
             bind_context.add_column_binding(column_binding);
         }
         let stat = table.table().table_statistics()?;
+        let column_stats = bind_column_stats(table.table(), &columns).await?;
         Ok((
             SExpr::create_leaf(
                 LogicalGet {
@@ -321,6 +535,7 @@ impl<'a> Binder {
                     limit: None,
                     order_by: None,
                     statistics: stat,
+                    column_stats,
                     prewhere: None,
                 }
                 .into(),
@@ -329,7 +544,7 @@ impl<'a> Binder {
         ))
     }
 
-    async fn resolve_data_source(
+    pub(in crate::planner::binder) async fn resolve_data_source(
         &self,
         tenant: &str,
         catalog_name: &str,
@@ -337,9 +552,17 @@ impl<'a> Binder {
         table_name: &str,
         travel_point: &Option<NavigationPoint>,
     ) -> Result<Arc<dyn Table>> {
-        // Resolve table with catalog
-        let catalog = self.catalogs.get_catalog(catalog_name)?;
-        let mut table_meta = catalog.get_table(tenant, database_name, table_name).await?;
+        // Resolve table with catalog, unless a table of the name is attached to the query
+        let attached = self
+            .ctx
+            .get_attached_table(catalog_name, database_name, table_name);
+        let mut table_meta = match attached {
+            Some(table) => table,
+            None => {
+                let catalog = self.catalogs.get_catalog(catalog_name)?;
+                catalog.get_table(tenant, database_name, table_name).await?
+            }
+        };
 
         if let Some(tp) = travel_point {
             table_meta = table_meta.navigate_to(tp).await?;
@@ -347,7 +570,82 @@ impl<'a> Binder {
         Ok(table_meta)
     }
 
-    async fn resolve_data_travel_point(
+    /// Returns the query of the rows inserted into and deleted from the base table of
+    /// `stream` since the offset of it, which reads the changes as tables attached to the
+    /// query.
+    ///
+    /// The stream is recorded as consumed up to the current version of the base table, it
+    /// is moved there if the query commits a change to a table.
+    async fn resolve_stream_changes(
+        &self,
+        catalog: &str,
+        database: &str,
+        stream: &dyn Table,
+    ) -> Result<String> {
+        let options = stream.options();
+        let option = |key: &str| {
+            options.get(key).ok_or_else(|| {
+                ErrorCode::Internal(format!(
+                    "Invalid STREAM object: {}, option {} not found",
+                    stream.name(),
+                    key
+                ))
+            })
+        };
+        let base_database = option(OPT_KEY_STREAM_BASE_DATABASE)?;
+        let base_table_name = option(OPT_KEY_STREAM_BASE_TABLE)?;
+        let offset = option(OPT_KEY_STREAM_OFFSET)?;
+
+        let tenant = self.ctx.get_tenant();
+        let base_table = self
+            .resolve_data_source(&tenant, catalog, base_database, base_table_name, &None)
+            .await?;
+        if options.get(OPT_KEY_STREAM_BASE_TABLE_ID) != Some(&base_table.get_id().to_string()) {
+            return Err(ErrorCode::UnknownTable(format!(
+                "Base table {}.{} of stream {}.{} has been dropped or replaced",
+                base_database,
+                base_table_name,
+                database,
+                stream.name()
+            )));
+        }
+
+        let (inserted, deleted) = base_table.changes(self.ctx.clone(), offset).await?;
+        let inserted_name = format!("{}$inserted", stream.name());
+        let deleted_name = format!("{}$deleted", stream.name());
+        self.ctx
+            .attach_table(catalog, database, &inserted_name, inserted);
+        self.ctx
+            .attach_table(catalog, database, &deleted_name, deleted);
+
+        let mut new_table_meta = stream.get_table_info().meta.clone();
+        new_table_meta.options.insert(
+            OPT_KEY_STREAM_OFFSET.to_string(),
+            base_table
+                .options()
+                .get(OPT_KEY_SNAPSHOT_LOCATION)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        self.ctx.add_consumed_stream(UpdateTableMetaReq {
+            table_id: stream.get_id(),
+            seq: MatchSeq::Exact(stream.get_table_info().ident.seq),
+            new_table_meta,
+        });
+
+        // The rows of the blocks rewritten by a mutation are both deleted and inserted, the
+        // rows left unchanged are cancelled out by their values, counting the duplicates.
+        // EXCEPT ALL takes NULLs as equal, so the rows with NULLs are cancelled out too.
+        Ok(format!(
+            "SELECT *, 'INSERT' AS `{action}` FROM (SELECT * FROM `{database}`.`{inserted_name}` \
+             EXCEPT ALL SELECT * FROM `{database}`.`{deleted_name}`) AS `{inserted_name}` \
+             UNION ALL SELECT *, 'DELETE' AS `{action}` FROM (SELECT * FROM `{database}`.`{deleted_name}` \
+             EXCEPT ALL SELECT * FROM `{database}`.`{inserted_name}`) AS `{deleted_name}`",
+            action = CHANGE_ACTION_COLUMN,
+        ))
+    }
+
+    pub(in crate::planner::binder) async fn resolve_data_travel_point(
         &self,
         bind_context: &BindContext,
         travel_point: &TimeTravelPoint<'a>,
@@ -380,3 +678,42 @@ impl<'a> Binder {
         }
     }
 }
+
+// Collects the statistics of the columns of a table for the optimizer. Only the columns
+// whose number of distinct values is collected by the table are taken into account.
+async fn bind_column_stats(
+    table: &Arc<dyn Table>,
+    columns: &[ColumnEntry],
+) -> Result<ColumnStatSet> {
+    let provider = table.column_statistics_provider().await?;
+    let schema = table.schema();
+
+    // The statistics are keyed by the positions of the leaf columns, and the top level
+    // columns are the first ones of `columns`, in the order of the schema.
+    let mut column_stats = ColumnStatSet::new();
+    let mut leaf_position = 0;
+    for (field, column) in schema.fields().iter().zip(columns.iter()) {
+        if field.data_type().data_type_id() != TypeID::Struct {
+            if let Some(stat) = provider.column_statistics(leaf_position as u32) {
+                if let Some(distinct_count) = stat.number_of_distinct_values {
+                    column_stats.insert(column.index(), ColumnStat {
+                        distinct_count,
+                        null_count: stat.null_count,
+                        min: stat.min,
+                        max: stat.max,
+                        histogram: stat.histogram,
+                    });
+                }
+            }
+        }
+        leaf_position += num_leaf_columns(field.data_type());
+    }
+    Ok(column_stats)
+}
+
+fn num_leaf_columns(data_type: &DataTypeImpl) -> usize {
+    match data_type {
+        DataTypeImpl::Struct(struct_type) => struct_type.types().iter().map(num_leaf_columns).sum(),
+        _ => 1,
+    }
+}
@@ -106,6 +106,7 @@ impl<'a> Binder {
             table_id,
             update_list: update_columns,
             selection: push_downs,
+            metadata: self.metadata.clone(),
         };
         Ok(Plan::Update(Box::new(plan)))
     }
@@ -0,0 +1,282 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::select::SelectList;
+use crate::binder::Binder;
+use crate::binder::ColumnBinding;
+use crate::binder::Visibility;
+use crate::optimizer::SExpr;
+use crate::planner::semantic::GroupingChecker;
+use crate::plans::AndExpr;
+use crate::plans::BoundColumnRef;
+use crate::plans::CastExpr;
+use crate::plans::ComparisonExpr;
+use crate::plans::EvalScalar;
+use crate::plans::FunctionCall;
+use crate::plans::OrExpr;
+use crate::plans::Scalar;
+use crate::plans::ScalarExpr;
+use crate::plans::ScalarItem;
+use crate::plans::SortItem;
+use crate::plans::Window;
+use crate::plans::WindowFunc;
+use crate::BindContext;
+use crate::IndexType;
+use crate::MetadataRef;
+
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct WindowInfo {
+    /// Window functions, the inputs of which have been replaced with `BoundColumnRef`s
+    pub window_functions: Vec<ScalarItem>,
+
+    /// Inputs of window functions, including arguments, partition keys and order keys
+    pub window_arguments: Vec<ScalarItem>,
+
+    /// Mapping: (window function display name) -> (index of window func in `window_functions`)
+    /// This is used to find a window function in current context.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+pub(super) struct WindowRewriter<'a> {
+    pub bind_context: &'a mut BindContext,
+    pub metadata: MetadataRef,
+}
+
+impl<'a> WindowRewriter<'a> {
+    pub fn new(bind_context: &'a mut BindContext, metadata: MetadataRef) -> Self {
+        Self {
+            bind_context,
+            metadata,
+        }
+    }
+
+    pub fn visit(&mut self, scalar: &Scalar) -> Result<Scalar> {
+        match scalar {
+            Scalar::BoundColumnRef(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::AndExpr(scalar) => Ok(AndExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::OrExpr(scalar) => Ok(OrExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
+                op: scalar.op.clone(),
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::FunctionCall(func) => {
+                let new_args = func
+                    .arguments
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(FunctionCall {
+                    arguments: new_args,
+                    func_name: func.func_name.clone(),
+                    arg_types: func.arg_types.clone(),
+                    return_type: func.return_type.clone(),
+                }
+                .into())
+            }
+            Scalar::CastExpr(cast) => Ok(CastExpr {
+                argument: Box::new(self.visit(&cast.argument)?),
+                from_type: cast.from_type.clone(),
+                target_type: cast.target_type.clone(),
+            }
+            .into()),
+
+            // Window functions can not be nested in aggregate functions or subqueries
+            // of current context, which has been checked by `TypeChecker`.
+            Scalar::SubqueryExpr(_) | Scalar::AggregateFunction(_) => Ok(scalar.clone()),
+
+            Scalar::WindowFunction(window) => self.replace_window_function(window),
+        }
+    }
+
+    /// Replace the inputs of window function with a BoundColumnRef, and
+    /// add the replaced window function and the inputs into `WindowInfo`.
+    fn replace_window_function(&mut self, window: &WindowFunc) -> Result<Scalar> {
+        let window_info = &mut self.bind_context.window_info;
+        if let Some(index) = window_info.window_functions_map.get(&window.display_name) {
+            return Ok(window_info.window_functions[*index].scalar.clone());
+        }
+
+        let metadata = &self.metadata;
+        let replaced_window = window.try_map_inputs(|arg| {
+            if let Scalar::BoundColumnRef(column_ref) = arg {
+                window_info.window_arguments.push(ScalarItem {
+                    index: column_ref.column.index,
+                    scalar: arg.clone(),
+                });
+                return Ok(arg.clone());
+            }
+
+            let name = format!(
+                "{}_arg_{}",
+                window.func.func_name(),
+                window_info.window_arguments.len()
+            );
+            let index = metadata
+                .write()
+                .add_column(name.clone(), arg.data_type(), None, None);
+            window_info.window_arguments.push(ScalarItem {
+                index,
+                scalar: arg.clone(),
+            });
+
+            // Window arguments can not be referenced, the name is only for debug
+            Ok(BoundColumnRef {
+                column: ColumnBinding {
+                    database_name: None,
+                    table_name: None,
+                    column_name: name,
+                    index,
+                    data_type: Box::new(arg.data_type()),
+                    visibility: Visibility::Visible,
+                },
+            }
+            .into())
+        })?;
+
+        let index = self.metadata.write().add_column(
+            window.display_name.clone(),
+            window.data_type(),
+            None,
+            None,
+        );
+
+        window_info.window_functions.push(ScalarItem {
+            scalar: replaced_window.clone().into(),
+            index,
+        });
+        window_info.window_functions_map.insert(
+            replaced_window.display_name.clone(),
+            window_info.window_functions.len() - 1,
+        );
+
+        Ok(replaced_window.into())
+    }
+}
+
+impl<'a> Binder {
+    /// Analyze window functions in select clause, this will rewrite window functions.
+    /// See `WindowRewriter` for more details.
+    pub(crate) fn analyze_window_select(
+        &mut self,
+        bind_context: &mut BindContext,
+        select_list: &mut SelectList<'a>,
+    ) -> Result<()> {
+        for item in select_list.items.iter_mut() {
+            let mut rewriter = WindowRewriter::new(bind_context, self.metadata.clone());
+            let new_scalar = rewriter.visit(&item.scalar)?;
+            item.scalar = new_scalar;
+        }
+
+        Ok(())
+    }
+
+    /// Build an `EvalScalar` to produce the inputs of window functions, followed
+    /// by a `Window` operator for each window function.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        let window_info = &bind_context.window_info;
+        let scalar_items = window_info
+            .window_arguments
+            .iter()
+            .map(|item| {
+                if bind_context.in_grouping {
+                    // Window functions are evaluated after aggregation, so the inputs
+                    // of them should pass the grouping check.
+                    let mut grouping_checker = GroupingChecker::new(bind_context);
+                    let scalar = grouping_checker.resolve(&item.scalar, None)?;
+                    Ok(ScalarItem {
+                        scalar,
+                        index: item.index,
+                    })
+                } else {
+                    Ok(item.clone())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut new_expr = child;
+        if !scalar_items.is_empty() {
+            let eval_scalar = EvalScalar {
+                items: scalar_items,
+            };
+            new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
+        }
+
+        let default_nulls_first = !self.ctx.get_settings().get_sql_dialect()?.is_null_biggest();
+        let column_index = |scalar: &Scalar| -> Result<IndexType> {
+            if let Scalar::BoundColumnRef(column_ref) = scalar {
+                Ok(column_ref.column.index)
+            } else {
+                Err(ErrorCode::Internal(
+                    "Window function input must be a BoundColumnRef",
+                ))
+            }
+        };
+
+        for item in window_info.window_functions.iter() {
+            let window = if let Scalar::WindowFunction(window) = &item.scalar {
+                window
+            } else {
+                return Err(ErrorCode::Internal("Expected window function"));
+            };
+
+            let window_plan = Window {
+                index: item.index,
+                function: window.func.clone(),
+                partition_by: window
+                    .partition_by
+                    .iter()
+                    .map(column_index)
+                    .collect::<Result<_>>()?,
+                order_by: window
+                    .order_by
+                    .iter()
+                    .map(|order| {
+                        Ok(SortItem {
+                            index: column_index(&order.expr)?,
+                            asc: order.asc.unwrap_or(true),
+                            nulls_first: order.nulls_first.unwrap_or(default_nulls_first),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+                frame: window.frame.clone(),
+            };
+            new_expr = SExpr::create_unary(window_plan.into(), new_expr);
+        }
+
+        Ok(new_expr)
+    }
+}
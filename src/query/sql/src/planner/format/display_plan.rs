@@ -62,20 +62,39 @@ impl Plan {
             Plan::DropTableClusterKey(drop_table_cluster_key) => {
                 Ok(format!("{:?}", drop_table_cluster_key))
             }
+            Plan::AlterTableColumn(alter_table_column) => Ok(format!("{:?}", alter_table_column)),
+            Plan::FlashbackTable(flashback_table) => Ok(format!("{:?}", flashback_table)),
             Plan::ReclusterTable(recluster_table) => Ok(format!("{:?}", recluster_table)),
             Plan::TruncateTable(truncate_table) => Ok(format!("{:?}", truncate_table)),
             Plan::OptimizeTable(optimize_table) => Ok(format!("{:?}", optimize_table)),
+            Plan::AnalyzeTable(analyze_table) => Ok(format!("{:?}", analyze_table)),
             Plan::ExistsTable(exists_table) => Ok(format!("{:?}", exists_table)),
 
+            // Indexes
+            Plan::CreateIndex(create_index) => Ok(format!("{:?}", create_index)),
+
             // Views
             Plan::CreateView(create_view) => Ok(format!("{:?}", create_view)),
+            Plan::CreateMaterializedView(create_materialized_view) => {
+                Ok(format!("{:?}", create_materialized_view))
+            }
             Plan::AlterView(alter_view) => Ok(format!("{:?}", alter_view)),
             Plan::DropView(drop_view) => Ok(format!("{:?}", drop_view)),
 
+            // Streams
+            Plan::CreateStream(create_stream) => Ok(format!("{:?}", create_stream)),
+            Plan::DropStream(drop_stream) => Ok(format!("{:?}", drop_stream)),
+
             // Insert
             Plan::Insert(insert) => Ok(format!("{:?}", insert)),
             Plan::Delete(delete) => Ok(format!("{:?}", delete)),
             Plan::Update(update) => Ok(format!("{:?}", update)),
+            Plan::MergeInto(merge_into) => Ok(format!("{:?}", merge_into)),
+
+            // Transactions
+            Plan::Begin => Ok("Begin".to_string()),
+            Plan::Commit => Ok("Commit".to_string()),
+            Plan::Rollback => Ok("Rollback".to_string()),
 
             // Stages
             Plan::ListStage(s) => Ok(format!("{:?}", s)),
@@ -37,6 +37,7 @@ use crate::plans::PhysicalScan;
 use crate::plans::RelOperator;
 use crate::plans::Scalar;
 use crate::plans::Sort;
+use crate::plans::Window;
 use crate::MetadataRef;
 use crate::ScalarExpr;
 
@@ -81,6 +82,9 @@ impl Display for FormatContext {
                 RelOperator::UnionAll(_) => write!(f, "Union"),
                 RelOperator::Pattern(_) => write!(f, "Pattern"),
                 RelOperator::DummyTableScan(_) => write!(f, "DummyTableScan"),
+                RelOperator::Window(_) => write!(f, "Window"),
+                RelOperator::RecursiveUnion(_) => write!(f, "RecursiveUnion"),
+                RelOperator::RecursiveCteScan(_) => write!(f, "RecursiveCteScan"),
             },
             Self::Text(text) => write!(f, "{}", text),
         }
@@ -139,6 +143,7 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &Scalar) -> String {
             )
         }
         Scalar::SubqueryExpr(_) => "SUBQUERY".to_string(),
+        Scalar::WindowFunction(window) => window.display_name.clone(),
     }
 }
 
@@ -217,6 +222,7 @@ fn to_format_tree(
         RelOperator::Sort(op) => sort_to_format_tree(op, metadata, children),
         RelOperator::Limit(op) => limit_to_format_tree(op, metadata, children),
         RelOperator::Exchange(op) => exchange_to_format_tree(op, metadata, children),
+        RelOperator::Window(op) => window_to_format_tree(op, metadata, children),
 
         _ => FormatTreeNode::with_children(
             FormatContext::RelOp {
@@ -586,6 +592,67 @@ fn sort_to_format_tree(
     )
 }
 
+fn window_to_format_tree(
+    op: &Window,
+    metadata: MetadataRef,
+    children: Vec<FormatTreeNode<FormatContext>>,
+) -> FormatTreeNode<FormatContext> {
+    let partition_by = op
+        .partition_by
+        .iter()
+        .map(|index| {
+            let metadata = metadata.read();
+            format!("{} (#{})", metadata.column(*index).name(), index)
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let order_by = op
+        .order_by
+        .iter()
+        .map(|item| {
+            let metadata = metadata.read();
+            format!(
+                "{} (#{}) {}",
+                metadata.column(item.index).name(),
+                item.index,
+                if item.asc { "ASC" } else { "DESC" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let function = format!(
+        "{}({}) (#{})",
+        op.function.func_name(),
+        op.function
+            .arguments()
+            .into_iter()
+            .map(|arg| format_scalar(&metadata, arg))
+            .collect::<Vec<String>>()
+            .join(", "),
+        op.index
+    );
+
+    FormatTreeNode::with_children(
+        FormatContext::RelOp {
+            metadata,
+            rel_operator: Box::new(op.clone().into()),
+        },
+        vec![
+            vec![
+                FormatTreeNode::new(FormatContext::Text(format!("function: {}", function))),
+                FormatTreeNode::new(FormatContext::Text(format!(
+                    "partition by: [{}]",
+                    partition_by
+                ))),
+                FormatTreeNode::new(FormatContext::Text(format!("order by: [{}]", order_by))),
+                FormatTreeNode::new(FormatContext::Text(format!("frame: [{}]", op.frame))),
+            ],
+            children,
+        ]
+        .concat(),
+    )
+}
+
 fn limit_to_format_tree(
     op: &Limit,
     metadata: MetadataRef,
@@ -0,0 +1,494 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::Literal;
+use common_ast::ast::Query;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::ast::SubqueryModifier;
+use common_ast::ast::TableReference;
+use common_ast::ast::Window;
+use common_ast::parser::parse_sql;
+use common_ast::parser::token::Token;
+use common_ast::parser::tokenize_sql;
+use common_ast::walk_expr_mut;
+use common_ast::walk_query_mut;
+use common_ast::Backtrace;
+use common_ast::Dialect;
+use common_ast::VisitorMut;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+
+use crate::planner::semantic::IdentifierNormalizer;
+use crate::NameResolutionContext;
+
+/// The aggregates whose partial results can be merged, and the functions merging them.
+const MERGEABLE_AGGREGATES: [(&str, &str); 4] = [
+    ("count", "sum"),
+    ("sum", "sum"),
+    ("min", "min"),
+    ("max", "max"),
+];
+
+/// The definition of a materialized view, which is an aggregate query on a single table:
+///
+/// `SELECT <keys>, <aggregates> FROM [<database>.]<table> [WHERE <predicate>] GROUP BY <keys>`
+///
+/// Only the aggregates whose partial results can be merged, i.e. `COUNT`, `SUM`, `MIN` and `MAX`,
+/// are supported, so that the view can be refreshed incrementally by merging the results of the
+/// query on the appended data into it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaterializedView {
+    pub database: Option<String>,
+    pub table: String,
+    /// The formatted `WHERE` clause.
+    pub predicate: Option<String>,
+    pub columns: Vec<MaterializedViewColumn>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaterializedViewColumn {
+    /// The name of the column in the view.
+    pub name: String,
+    /// The formatted select expression, which is the column name for a group key.
+    pub expr: String,
+    /// The function merging the partial results of the aggregate, `None` for a group key.
+    pub merge_func: Option<&'static str>,
+}
+
+impl MaterializedView {
+    pub fn parse(sql: &str, name_resolution_ctx: &NameResolutionContext) -> Result<Self> {
+        let tokens = tokenize_sql(sql)?;
+        let backtrace = Backtrace::new();
+        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
+        match stmt {
+            Statement::Query(query) => Self::try_from_query(&query, name_resolution_ctx),
+            _ => Err(ErrorCode::SemanticError(format!(
+                "Invalid query of materialized view: {sql}"
+            ))),
+        }
+    }
+
+    pub fn try_from_query(
+        query: &Query<'_>,
+        name_resolution_ctx: &NameResolutionContext,
+    ) -> Result<Self> {
+        let mut query = query.clone();
+        walk_query_mut(
+            &mut IdentifierNormalizer {
+                ctx: name_resolution_ctx,
+            },
+            &mut query,
+        );
+
+        if query.with.is_some()
+            || !query.order_by.is_empty()
+            || !query.limit.is_empty()
+            || query.offset.is_some()
+        {
+            return Err(unsupported(
+                "WITH, ORDER BY, LIMIT and OFFSET are not allowed",
+            ));
+        }
+        let select = match &query.body {
+            SetExpr::Select(select) => select,
+            _ => return Err(unsupported("only a single SELECT is allowed")),
+        };
+        if select.distinct || select.having.is_some() || select.window_list.is_some() {
+            return Err(unsupported("DISTINCT, HAVING and WINDOW are not allowed"));
+        }
+
+        let (database, table) = match select.from.as_slice() {
+            [
+                TableReference::Table {
+                    database,
+                    table,
+                    travel_point: None,
+                    ..
+                },
+            ] => (
+                database.as_ref().map(|ident| ident.name.clone()),
+                table.name.clone(),
+            ),
+            _ => return Err(unsupported("it must select from a single table")),
+        };
+
+        if select.group_by.is_empty() {
+            return Err(unsupported("GROUP BY is required"));
+        }
+        let mut keys = Vec::with_capacity(select.group_by.len());
+        for expr in select.group_by.iter() {
+            match expr {
+                Expr::ColumnRef { column, .. } => keys.push(column.name.clone()),
+                _ => return Err(unsupported("only columns are allowed in GROUP BY")),
+            }
+        }
+
+        let mut columns: Vec<MaterializedViewColumn> = Vec::with_capacity(select.select_list.len());
+        for target in select.select_list.iter() {
+            let (expr, alias) = match target {
+                SelectTarget::AliasedExpr { expr, alias } => (expr, alias),
+                SelectTarget::QualifiedName(_) => {
+                    return Err(unsupported("wildcard is not allowed"));
+                }
+            };
+            let (formatted, merge_func) = match expr.as_ref() {
+                Expr::ColumnRef { column, .. } if keys.contains(&column.name) => {
+                    (column.name.clone(), None)
+                }
+                Expr::CountAll { .. } => (format!("{:#}", expr), Some("sum")),
+                Expr::FunctionCall {
+                    distinct: false,
+                    name,
+                    args,
+                    params,
+                    window: None,
+                    ..
+                } if params.is_empty() => match merge_func_of(&name.name) {
+                    Some(merge_func) => (
+                        format_aggregate(false, name, args, params),
+                        Some(merge_func),
+                    ),
+                    None => return Err(unsupported_select_target()),
+                },
+                _ => return Err(unsupported_select_target()),
+            };
+            let name = match alias {
+                Some(alias) => alias.name.clone(),
+                None => format!("{:#}", expr).to_lowercase(),
+            };
+            if columns.iter().any(|column| column.name == name) {
+                return Err(unsupported("the names of the columns must be unique"));
+            }
+            columns.push(MaterializedViewColumn {
+                name,
+                expr: formatted,
+                merge_func,
+            });
+        }
+        // The rows of the view are merged by the group keys.
+        if keys.iter().any(|key| {
+            !columns
+                .iter()
+                .any(|column| column.merge_func.is_none() && &column.expr == key)
+        }) {
+            return Err(unsupported("all the group keys must be selected"));
+        }
+
+        Ok(MaterializedView {
+            database,
+            table,
+            predicate: select.selection.as_ref().map(|expr| format!("{:#}", expr)),
+            columns,
+        })
+    }
+
+    /// Builds the query which merges the partial results of `query`, the query of the view
+    /// on the newly appended data, into the rows of the view `view`.
+    pub fn merge_query(&self, view: &str, query: &str) -> String {
+        let select_list = self
+            .columns
+            .iter()
+            .map(|column| match column.merge_func {
+                Some(merge_func) => format!("{merge_func}(`{0}`) AS `{0}`", column.name),
+                None => format!("`{}`", column.name),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let group_by = self
+            .columns
+            .iter()
+            .filter(|column| column.merge_func.is_none())
+            .map(|column| format!("`{}`", column.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "SELECT {select_list} FROM (SELECT * FROM {view} UNION ALL {query}) AS merged GROUP BY {group_by}"
+        )
+    }
+
+    /// Rewrites `query` on the table of the view to read the view `database`.`view` instead,
+    /// returns `None` if the query can not be answered by the view. It can be answered if:
+    ///
+    /// - it is an aggregate query with the same `WHERE` clause as the view,
+    /// - its group keys are all group keys of the view,
+    /// - its aggregates are all materialized by the view.
+    ///
+    /// The rows of the view are aggregated again by the merge functions, since the query may
+    /// group by fewer keys.
+    pub fn rewrite_query(
+        &self,
+        query: &Query<'_>,
+        name_resolution_ctx: &NameResolutionContext,
+        database: &str,
+        view: &str,
+    ) -> Option<String> {
+        let mut query = query.clone();
+        walk_query_mut(
+            &mut IdentifierNormalizer {
+                ctx: name_resolution_ctx,
+            },
+            &mut query,
+        );
+        if query.with.is_some() {
+            return None;
+        }
+
+        let mut rewriter = MaterializedViewRewriter {
+            view: self,
+            aliases: vec![],
+            failed: false,
+        };
+        {
+            let select = match &mut query.body {
+                SetExpr::Select(select) => select,
+                _ => return None,
+            };
+            if select.distinct
+                || select.window_list.is_some()
+                || select.group_by.is_empty()
+                || !matches!(select.from.as_slice(), [TableReference::Table {
+                    travel_point: None,
+                    ..
+                }])
+                || select.selection.as_ref().map(|expr| format!("{:#}", expr)) != self.predicate
+            {
+                return None;
+            }
+
+            for target in select.select_list.iter_mut() {
+                match target {
+                    SelectTarget::AliasedExpr { expr, alias } => {
+                        // Keep the names of the columns as the original query.
+                        let alias = alias.get_or_insert_with(|| {
+                            quoted_identifier(&format!("{:#}", expr).to_lowercase())
+                        });
+                        rewriter.aliases.push(alias.name.clone());
+                        rewriter.visit_expr(expr);
+                    }
+                    SelectTarget::QualifiedName(_) => return None,
+                }
+            }
+            for expr in select.group_by.iter_mut() {
+                rewriter.visit_expr(expr);
+            }
+            if let Some(having) = &mut select.having {
+                rewriter.visit_expr(having);
+            }
+
+            select.selection = None;
+            select.from = vec![TableReference::Table {
+                span: &[],
+                catalog: None,
+                database: Some(quoted_identifier(database)),
+                table: quoted_identifier(view),
+                alias: None,
+                travel_point: None,
+            }];
+        }
+        for order_by in query.order_by.iter_mut() {
+            rewriter.visit_expr(&mut order_by.expr);
+        }
+
+        if rewriter.failed {
+            None
+        } else {
+            Some(query.to_string())
+        }
+    }
+
+    fn group_key(&self, column: &str) -> Option<&MaterializedViewColumn> {
+        self.columns
+            .iter()
+            .find(|c| c.merge_func.is_none() && c.expr == column)
+    }
+
+    fn aggregate(&self, expr: &str) -> Option<&MaterializedViewColumn> {
+        self.columns
+            .iter()
+            .find(|c| c.merge_func.is_some() && c.expr == expr)
+    }
+}
+
+fn merge_func_of(aggregate: &str) -> Option<&'static str> {
+    MERGEABLE_AGGREGATES
+        .iter()
+        .find(|(func, _)| aggregate.eq_ignore_ascii_case(func))
+        .map(|(_, merge_func)| *merge_func)
+}
+
+// The names of functions are case-insensitive.
+fn format_aggregate(
+    distinct: bool,
+    name: &Identifier<'_>,
+    args: &[Expr<'_>],
+    params: &[Literal],
+) -> String {
+    let expr = Expr::FunctionCall {
+        span: &[],
+        distinct,
+        name: Identifier {
+            name: name.name.to_lowercase(),
+            quote: name.quote,
+            span: name.span.clone(),
+        },
+        args: args.to_vec(),
+        params: params.to_vec(),
+        window: None,
+    };
+    format!("{:#}", expr)
+}
+
+fn unsupported(reason: &str) -> ErrorCode {
+    ErrorCode::SemanticError(format!("Unsupported query of materialized view, {reason}"))
+}
+
+fn unsupported_select_target() -> ErrorCode {
+    unsupported("only group keys and aggregates COUNT, SUM, MIN and MAX are allowed to select")
+}
+
+fn quoted_identifier<'a>(name: &str) -> Identifier<'a> {
+    Identifier {
+        name: name.to_string(),
+        quote: Some('`'),
+        span: Token::new_eoi(""),
+    }
+}
+
+fn merge_call<'a>(column: &MaterializedViewColumn) -> (Identifier<'a>, Expr<'a>) {
+    let name = Identifier {
+        name: column.merge_func.unwrap_or_default().to_string(),
+        quote: None,
+        span: Token::new_eoi(""),
+    };
+    let arg = Expr::ColumnRef {
+        span: &[],
+        database: None,
+        table: None,
+        column: quoted_identifier(&column.name),
+    };
+    (name, arg)
+}
+
+/// Replaces the aggregates materialized by the view with the merge functions on the columns of
+/// the view, and the group keys with the columns of the view.
+struct MaterializedViewRewriter<'a> {
+    view: &'a MaterializedView,
+    // The aliases of the select list, which can be referred by `HAVING` and `ORDER BY`.
+    aliases: Vec<String>,
+    failed: bool,
+}
+
+impl<'a> VisitorMut for MaterializedViewRewriter<'a> {
+    fn visit_expr(&mut self, expr: &mut Expr<'_>) {
+        if let Expr::CountAll { .. } = expr {
+            match self.view.aggregate(&format!("{:#}", expr)) {
+                Some(column) => {
+                    let (name, arg) = merge_call(column);
+                    *expr = Expr::FunctionCall {
+                        span: &[],
+                        distinct: false,
+                        name,
+                        args: vec![arg],
+                        params: vec![],
+                        window: None,
+                    };
+                }
+                None => self.failed = true,
+            }
+            return;
+        }
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_column_ref(
+        &mut self,
+        _span: &mut &[Token<'_>],
+        database: &mut Option<Identifier<'_>>,
+        table: &mut Option<Identifier<'_>>,
+        column: &mut Identifier<'_>,
+    ) {
+        if let Some(key) = self.view.group_key(&column.name) {
+            *database = None;
+            *table = None;
+            *column = quoted_identifier(&key.name);
+        } else if database.is_some() || table.is_some() || !self.aliases.contains(&column.name) {
+            self.failed = true;
+        }
+    }
+
+    fn visit_count_all(&mut self, _span: &mut &[Token<'_>]) {
+        // `COUNT(*)` nested in other expressions can not be replaced in place.
+        self.failed = true;
+    }
+
+    fn visit_function_call(
+        &mut self,
+        _span: &mut &[Token<'_>],
+        distinct: bool,
+        name: &mut Identifier<'_>,
+        args: &mut [Expr<'_>],
+        params: &mut [Literal],
+        window: &mut Option<Window<'_>>,
+    ) {
+        if window.is_some() {
+            self.failed = true;
+            return;
+        }
+        if !AggregateFunctionFactory::instance().check(&name.name) {
+            for arg in args.iter_mut() {
+                walk_expr_mut(self, arg);
+            }
+            return;
+        }
+
+        let formatted = format_aggregate(distinct, name, args, params);
+        match self.view.aggregate(&formatted) {
+            Some(column) if args.len() == 1 && params.is_empty() => {
+                let (merge_name, arg) = merge_call(column);
+                *name = merge_name;
+                args[0] = arg;
+            }
+            _ => self.failed = true,
+        }
+    }
+
+    fn visit_in_subquery(
+        &mut self,
+        _span: &mut &[Token<'_>],
+        _expr: &mut Expr<'_>,
+        _subquery: &mut Query<'_>,
+        _not: bool,
+    ) {
+        self.failed = true;
+    }
+
+    fn visit_exists(&mut self, _span: &mut &[Token<'_>], _not: bool, _subquery: &mut Query<'_>) {
+        self.failed = true;
+    }
+
+    fn visit_subquery(
+        &mut self,
+        _span: &mut &[Token<'_>],
+        _modifier: &mut Option<SubqueryModifier>,
+        _subquery: &mut Query<'_>,
+    ) {
+        self.failed = true;
+    }
+}
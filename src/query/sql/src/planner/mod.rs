@@ -14,6 +14,7 @@
 
 mod expression_parser;
 mod format;
+mod materialized_view;
 mod metadata;
 #[allow(clippy::module_inception)]
 mod planner;
@@ -23,12 +24,15 @@ pub(crate) mod binder;
 pub mod optimizer;
 pub mod plans;
 
+pub use binder::parse_stage_location_v2;
 pub use binder::BindContext;
 pub use binder::Binder;
 pub use binder::ColumnBinding;
 pub use binder::ScalarBinder;
 pub use binder::Visibility;
 pub use expression_parser::ExpressionParser;
+pub use materialized_view::MaterializedView;
+pub use materialized_view::MaterializedViewColumn;
 pub use metadata::*;
 pub use planner::Planner;
 pub use plans::ScalarExpr;
@@ -40,12 +40,16 @@ fn compute_cost_impl(memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
         RelOperator::PhysicalScan(plan) => compute_cost_physical_scan(memo, m_expr, plan),
         RelOperator::DummyTableScan(_) => Ok(Cost(0.0)),
         RelOperator::PhysicalHashJoin(plan) => compute_cost_hash_join(memo, m_expr, plan),
-        RelOperator::UnionAll(_) => compute_cost_union_all(memo, m_expr),
+        RelOperator::UnionAll(_) | RelOperator::RecursiveUnion(_) => {
+            compute_cost_union_all(memo, m_expr)
+        }
+        RelOperator::RecursiveCteScan(_) => Ok(Cost(0.0)),
 
         RelOperator::EvalScalar(_)
         | RelOperator::Filter(_)
         | RelOperator::Aggregate(_)
         | RelOperator::Sort(_)
+        | RelOperator::Window(_)
         | RelOperator::Limit(_) => compute_cost_unary_common_operator(memo, m_expr),
 
         _ => Err(ErrorCode::Internal("Cannot compute cost from logical plan")),
@@ -406,6 +406,7 @@ impl SubqueryRewriter {
                     limit: None,
                     order_by: None,
                     statistics: None,
+                    column_stats: Default::default(),
                     prewhere: None,
                 }
                 .into(),
@@ -665,6 +666,9 @@ impl SubqueryRewriter {
             | RelOperator::LogicalGet(_)
             | RelOperator::PhysicalScan(_)
             | RelOperator::DummyTableScan(_)
+            | RelOperator::Window(_)
+            | RelOperator::RecursiveUnion(_)
+            | RelOperator::RecursiveCteScan(_)
             | RelOperator::PhysicalHashJoin(_) => Err(ErrorCode::Internal(
                 "Invalid plan type for flattening subquery",
             )),
@@ -22,6 +22,7 @@ use crate::optimizer::SExpr;
 use crate::plans::Aggregate;
 use crate::plans::EvalScalar;
 use crate::plans::LogicalGet;
+use crate::plans::LogicalOperator;
 use crate::plans::RelOperator;
 use crate::MetadataRef;
 use crate::ScalarExpr;
@@ -97,6 +98,7 @@ impl UnusedColumnPruner {
                     limit: p.limit,
                     order_by: p.order_by.clone(),
                     statistics: p.statistics,
+                    column_stats: p.column_stats.clone(),
                     prewhere,
                 })))
             }
@@ -217,12 +219,26 @@ impl UnusedColumnPruner {
                     self.keep_required_columns(expr.child(0)?, required)?,
                 ))
             }
+            RelOperator::Window(p) => {
+                if !required.contains(&p.index) {
+                    // Eliminate unused window function
+                    return self.keep_required_columns(expr.child(0)?, required);
+                }
+                required.extend(p.used_columns()?);
+                Ok(SExpr::create_unary(
+                    RelOperator::Window(p.clone()),
+                    self.keep_required_columns(expr.child(0)?, required)?,
+                ))
+            }
             RelOperator::Limit(p) => Ok(SExpr::create_unary(
                 RelOperator::Limit(p.clone()),
                 self.keep_required_columns(expr.child(0)?, required)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::UnionAll(_) => Ok(expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveUnion(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(expr.clone()),
 
             _ => Err(ErrorCode::Internal(
                 "Attempting to prune columns of a physical plan is not allowed",
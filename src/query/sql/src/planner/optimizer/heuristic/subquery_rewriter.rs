@@ -118,20 +118,21 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.into(), input))
             }
 
-            RelOperator::LogicalInnerJoin(_) | RelOperator::UnionAll(_) => {
-                Ok(SExpr::create_binary(
-                    s_expr.plan().clone(),
-                    self.rewrite(s_expr.child(0)?)?,
-                    self.rewrite(s_expr.child(1)?)?,
-                ))
-            }
-
-            RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(SExpr::create_unary(
+            RelOperator::LogicalInnerJoin(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveUnion(_) => Ok(SExpr::create_binary(
                 s_expr.plan().clone(),
                 self.rewrite(s_expr.child(0)?)?,
+                self.rewrite(s_expr.child(1)?)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
+            RelOperator::Limit(_) | RelOperator::Sort(_) | RelOperator::Window(_) => Ok(
+                SExpr::create_unary(s_expr.plan().clone(), self.rewrite(s_expr.child(0)?)?),
+            ),
+
+            RelOperator::DummyTableScan(_)
+            | RelOperator::LogicalGet(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(s_expr.clone()),
 
             RelOperator::PhysicalHashJoin(_)
             | RelOperator::Pattern(_)
@@ -198,7 +199,9 @@ impl SubqueryRewriter {
                 ))
             }
 
-            Scalar::AggregateFunction(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::AggregateFunction(_) | Scalar::WindowFunction(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::FunctionCall(func) => {
                 let mut args = vec![];
@@ -14,6 +14,9 @@
 
 use std::collections::HashMap;
 
+use common_catalog::table::HistogramBucket;
+use common_datavalues::DataValue;
+
 use crate::IndexType;
 
 pub type ColumnStatSet = HashMap<IndexType, ColumnStat>;
@@ -25,4 +28,9 @@ pub struct ColumnStat {
     pub distinct_count: u64,
     // Count of null values
     pub null_count: u64,
+    // Min and max of the non-null values
+    pub min: DataValue,
+    pub max: DataValue,
+    // Equi-height histogram of the non-null values, if collected by the table
+    pub histogram: Option<Vec<HistogramBucket>>,
 }
@@ -11,3 +11,159 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+use common_datavalues::DataValue;
+
+use super::column_stat::ColumnStat;
+use super::column_stat::ColumnStatSet;
+use crate::plans::ComparisonOp;
+use crate::plans::Scalar;
+
+/// Selectivity of the range predicates, which can not be estimated with the min/max or histogram.
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// Estimates the fraction of the rows satisfying a predicate, with the statistics of the columns.
+///
+/// Predicates which can not be estimated, e.g. those on columns without statistics,
+/// are assumed to keep all the rows, i.e. the selectivity is 1.
+pub struct SelectivityEstimator<'a> {
+    column_stats: &'a ColumnStatSet,
+}
+
+impl<'a> SelectivityEstimator<'a> {
+    pub fn new(column_stats: &'a ColumnStatSet) -> Self {
+        SelectivityEstimator { column_stats }
+    }
+
+    pub fn compute_selectivity(&self, predicate: &Scalar) -> f64 {
+        let selectivity = match predicate {
+            Scalar::AndExpr(and) => {
+                self.compute_selectivity(&and.left) * self.compute_selectivity(&and.right)
+            }
+            Scalar::OrExpr(or) => {
+                let left = self.compute_selectivity(&or.left);
+                let right = self.compute_selectivity(&or.right);
+                left + right - left * right
+            }
+            Scalar::ComparisonExpr(comparison) => {
+                match (strip_cast(&comparison.left), strip_cast(&comparison.right)) {
+                    (Scalar::BoundColumnRef(column), Scalar::ConstantExpr(constant)) => self
+                        .compute_comparison(column.column.index, &comparison.op, &constant.value),
+                    (Scalar::ConstantExpr(constant), Scalar::BoundColumnRef(column)) => self
+                        .compute_comparison(
+                            column.column.index,
+                            &comparison.op.reverse(),
+                            &constant.value,
+                        ),
+                    _ => 1.0,
+                }
+            }
+            _ => 1.0,
+        };
+        selectivity.clamp(0.0, 1.0)
+    }
+
+    fn compute_comparison(&self, column: usize, op: &ComparisonOp, value: &DataValue) -> f64 {
+        let stat = match self.column_stats.get(&column) {
+            Some(stat) if is_comparable(&stat.min, value) && is_comparable(&stat.max, value) => {
+                stat
+            }
+            _ => return 1.0,
+        };
+
+        match op {
+            ComparisonOp::Equal => equal_selectivity(stat, value),
+            ComparisonOp::NotEqual => 1.0 - equal_selectivity(stat, value),
+            ComparisonOp::LT => less_than_selectivity(stat, value),
+            ComparisonOp::LTE => {
+                less_than_selectivity(stat, value) + equal_selectivity(stat, value)
+            }
+            ComparisonOp::GT => {
+                1.0 - less_than_selectivity(stat, value) - equal_selectivity(stat, value)
+            }
+            ComparisonOp::GTE => 1.0 - less_than_selectivity(stat, value),
+        }
+    }
+}
+
+// Numeric casts keep the order of the values, so they are looked through.
+fn strip_cast(scalar: &Scalar) -> &Scalar {
+    match scalar {
+        Scalar::CastExpr(cast) => strip_cast(&cast.argument),
+        _ => scalar,
+    }
+}
+
+fn is_comparable(l: &DataValue, r: &DataValue) -> bool {
+    !l.is_null()
+        && !r.is_null()
+        && (l.value_type() == r.value_type() || (l.is_numeric() && r.is_numeric()))
+}
+
+fn equal_selectivity(stat: &ColumnStat, value: &DataValue) -> f64 {
+    if value < &stat.min || value > &stat.max {
+        return 0.0;
+    }
+
+    if let Some(histogram) = &stat.histogram {
+        let total = histogram.iter().map(|b| b.num_values).sum::<u64>();
+        if total > 0 {
+            return match histogram.iter().find(|b| value <= &b.upper_bound) {
+                Some(bucket) if bucket.num_distinct > 0 => {
+                    bucket.num_values as f64 / bucket.num_distinct as f64 / total as f64
+                }
+                _ => 0.0,
+            };
+        }
+    }
+
+    if stat.distinct_count == 0 {
+        return 1.0;
+    }
+    1.0 / stat.distinct_count as f64
+}
+
+// Fraction of the non-null values which are less than `value`.
+fn less_than_selectivity(stat: &ColumnStat, value: &DataValue) -> f64 {
+    if value <= &stat.min {
+        return 0.0;
+    }
+    if value > &stat.max {
+        return 1.0;
+    }
+
+    if let Some(histogram) = &stat.histogram {
+        let total = histogram.iter().map(|b| b.num_values).sum::<u64>();
+        if total > 0 {
+            let mut num_values = 0.0;
+            let mut lower_bound = &stat.min;
+            for bucket in histogram {
+                if value > &bucket.upper_bound {
+                    num_values += bucket.num_values as f64;
+                    lower_bound = &bucket.upper_bound;
+                    continue;
+                }
+                // the values are assumed to be uniformly distributed within a bucket
+                let fraction = interpolate(lower_bound, &bucket.upper_bound, value).unwrap_or(0.5);
+                num_values += bucket.num_values as f64 * fraction;
+                break;
+            }
+            return num_values / total as f64;
+        }
+    }
+
+    interpolate(&stat.min, &stat.max, value).unwrap_or(DEFAULT_RANGE_SELECTIVITY)
+}
+
+// Position of `value` in the range `[lower, upper]`, for the numeric values.
+fn interpolate(lower: &DataValue, upper: &DataValue, value: &DataValue) -> Option<f64> {
+    let (lower, upper, value) = (
+        lower.as_f64().ok()?,
+        upper.as_f64().ok()?,
+        value.as_f64().ok()?,
+    );
+    if upper <= lower {
+        return None;
+    }
+    Some(((value - lower) / (upper - lower)).clamp(0.0, 1.0))
+}
@@ -49,6 +49,8 @@ pub fn validate_distributed_query(s_expr: &SExpr) -> bool {
                 | JoinType::RightMark
                 | JoinType::Single => false,
             },
+            // The recursive term is evaluated repeatedly on a single node
+            RelOperator::RecursiveUnion(_) => false,
             _ => true,
         }
 }
@@ -15,10 +15,13 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::str::FromStr;
+use std::sync::Arc;
 
+use common_catalog::plan::DataSourceInfo;
 use common_catalog::plan::DataSourcePlan;
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 use common_meta_types::MetaId;
+use common_meta_types::OnErrorMode;
 use common_meta_types::UserStageInfo;
 
 use crate::plans::Plan;
@@ -76,6 +79,56 @@ pub enum CopyPlanV2 {
     },
 }
 
+impl CopyPlanV2 {
+    /// The ON_ERROR mode of copying into table, `OnErrorMode::None` if not specified.
+    pub fn on_error_mode(&self) -> OnErrorMode {
+        match self {
+            CopyPlanV2::IntoTable { from, .. } => match &from.source_info {
+                DataSourceInfo::StageSource(info) => {
+                    info.user_stage_info.copy_options.on_error.clone()
+                }
+                _ => OnErrorMode::None,
+            },
+            CopyPlanV2::IntoStage { .. } => OnErrorMode::None,
+        }
+    }
+
+    /// Copying into table with VALIDATION_MODE returns the rows or the errors of the files, and
+    /// with ON_ERROR returns the load status of each file.
+    pub fn schema(&self) -> DataSchemaRef {
+        if let CopyPlanV2::IntoTable {
+            schema,
+            validation_mode,
+            ..
+        } = self
+        {
+            match validation_mode {
+                ValidationMode::None => {}
+                ValidationMode::ReturnNRows(_) => return schema.clone(),
+                ValidationMode::ReturnErrors | ValidationMode::ReturnAllErrors => {
+                    return Arc::new(DataSchema::new(vec![
+                        DataField::new("file", Vu8::to_data_type()),
+                        DataField::new("line", u64::to_data_type()),
+                        DataField::new_nullable("column", Vu8::to_data_type()),
+                        DataField::new("error", Vu8::to_data_type()),
+                    ]));
+                }
+            }
+        }
+
+        if self.on_error_mode() == OnErrorMode::None {
+            return Arc::new(DataSchema::empty());
+        }
+        Arc::new(DataSchema::new(vec![
+            DataField::new("file", Vu8::to_data_type()),
+            DataField::new("rows_loaded", u64::to_data_type()),
+            DataField::new("errors_seen", u64::to_data_type()),
+            DataField::new_nullable("first_error", Vu8::to_data_type()),
+            DataField::new_nullable("first_error_line", u64::to_data_type()),
+        ]))
+    }
+}
+
 impl Debug for CopyPlanV2 {
     // Ignore the schema.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -16,6 +16,7 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use common_ast::ast::Engine;
+use common_catalog::table::NavigationPoint;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
@@ -46,6 +47,9 @@ pub struct CreateTablePlanV2 {
     pub field_comments: Vec<String>,
     pub cluster_key: Option<String>,
     pub as_select: Option<Box<Plan>>,
+    pub clone_source: Option<CloneTableSource>,
+    /// The query of `USING TEMPLATE`, the rows of which are the columns of the table.
+    pub template: Option<Box<Plan>>,
 }
 
 impl CreateTablePlanV2 {
@@ -54,6 +58,15 @@ impl CreateTablePlanV2 {
     }
 }
 
+/// The table to be cloned by `CREATE TABLE ... CLONE`, at the given point of time if any.
+#[derive(Clone, Debug)]
+pub struct CloneTableSource {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub travel_point: Option<NavigationPoint>,
+}
+
 /// Desc.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DescribeTablePlan {
@@ -125,6 +138,20 @@ pub enum OptimizeTableAction {
     CompactSegments(Option<usize>),
 }
 
+/// Analyze.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnalyzeTablePlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+}
+
+impl AnalyzeTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
 /// Rename.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RenameTablePlan {
@@ -245,6 +272,58 @@ impl AlterTableClusterKeyPlan {
     }
 }
 
+/// Column.
+#[derive(Clone, Debug)]
+pub enum AlterTableColumnAction {
+    Add {
+        field: DataField,
+        default_expr: Option<Scalar>,
+        comment: String,
+    },
+    Drop {
+        name: String,
+    },
+    Rename {
+        name: String,
+        new_name: String,
+    },
+    Modify {
+        field: DataField,
+        default_expr: Option<Scalar>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct AlterTableColumnPlan {
+    pub tenant: String,
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub action: AlterTableColumnAction,
+}
+
+impl AlterTableColumnPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
+/// Flashback.
+#[derive(Clone, Debug)]
+pub struct FlashbackTablePlan {
+    pub tenant: String,
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub point: NavigationPoint,
+}
+
+impl FlashbackTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DropTableClusterKeyPlan {
     pub tenant: String,
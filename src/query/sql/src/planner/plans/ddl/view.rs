@@ -16,6 +16,8 @@ use std::sync::Arc;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 
+use crate::plans::CreateTablePlanV2;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CreateViewPlan {
     pub if_not_exists: bool,
@@ -32,6 +34,20 @@ impl CreateViewPlan {
     }
 }
 
+/// The materialized view is a FUSE table, which is refreshed on appends to the base table.
+#[derive(Clone, Debug)]
+pub struct CreateMaterializedViewPlan {
+    pub create_table: Box<CreateTablePlanV2>,
+    pub base_database: String,
+    pub base_table: String,
+}
+
+impl CreateMaterializedViewPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AlterViewPlan {
     pub tenant: String,
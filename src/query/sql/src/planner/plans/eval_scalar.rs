@@ -113,7 +113,7 @@ impl LogicalOperator for EvalScalar {
             cardinality,
             precise_cardinality,
 
-            column_stats: Default::default(),
+            column_stats: input_prop.column_stats,
         })
     }
 
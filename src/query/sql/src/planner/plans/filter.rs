@@ -19,6 +19,7 @@ use crate::optimizer::PhysicalProperty;
 use crate::optimizer::RelExpr;
 use crate::optimizer::RelationalProperty;
 use crate::optimizer::RequiredProperty;
+use crate::optimizer::SelectivityEstimator;
 use crate::plans::LogicalOperator;
 use crate::plans::Operator;
 use crate::plans::PhysicalOperator;
@@ -87,9 +88,20 @@ impl LogicalOperator for Filter {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
-        // Derive cardinality. We can not estimate the cardinality of the filter until we have
-        // NDV(Number of Distinct Values), so we pass it through.
-        let cardinality = input_prop.cardinality;
+        // Derive cardinality with the selectivity of the predicates, which are assumed to be independent.
+        let estimator = SelectivityEstimator::new(&input_prop.column_stats);
+        let selectivity = self
+            .predicates
+            .iter()
+            .map(|pred| estimator.compute_selectivity(pred))
+            .product::<f64>();
+        let cardinality = input_prop.cardinality * selectivity;
+
+        // A column has no more distinct values than rows.
+        let mut column_stats = input_prop.column_stats;
+        for stat in column_stats.values_mut() {
+            stat.distinct_count = stat.distinct_count.min(cardinality.ceil() as u64);
+        }
 
         // Derive used columns
         let mut used_columns = self.used_columns()?;
@@ -104,7 +116,7 @@ impl LogicalOperator for Filter {
             // precise cardinality
             precise_cardinality: None,
 
-            column_stats: Default::default(),
+            column_stats,
         })
     }
 
@@ -84,7 +84,7 @@ impl LogicalOperator for Limit {
             },
             precise_cardinality: None,
 
-            column_stats: Default::default(),
+            column_stats: input_prop.column_stats,
         })
     }
 
@@ -17,6 +17,7 @@ use common_exception::Result;
 use itertools::Itertools;
 
 use crate::optimizer::ColumnSet;
+use crate::optimizer::ColumnStatSet;
 use crate::optimizer::RelExpr;
 use crate::optimizer::RelationalProperty;
 use crate::plans::LogicalOperator;
@@ -49,6 +50,7 @@ pub struct LogicalGet {
 
     // statistics will be ignored in comparison and hashing
     pub statistics: Option<TableStatistics>,
+    pub column_stats: ColumnStatSet,
 }
 
 impl PartialEq for LogicalGet {
@@ -115,7 +117,12 @@ impl LogicalOperator for LogicalGet {
                 .map_or(0.0, |stat| stat.num_rows.map_or(0.0, |num| num as f64)),
             precise_cardinality: self.statistics.as_ref().and_then(|stat| stat.num_rows),
 
-            column_stats: Default::default(),
+            column_stats: self
+                .column_stats
+                .iter()
+                .filter(|(index, _)| self.columns.contains(index))
+                .map(|(index, stat)| (*index, stat.clone()))
+                .collect(),
         })
     }
 
@@ -161,10 +161,54 @@ impl Operator for LogicalInnerJoin {
     }
 }
 
+impl LogicalInnerJoin {
+    // |L| * |R| / max(NDV(left_key), NDV(right_key)) for each pair of the equi-join keys,
+    // which are assumed to be independent.
+    fn inner_join_cardinality(
+        &self,
+        left_prop: &RelationalProperty,
+        right_prop: &RelationalProperty,
+    ) -> f64 {
+        let mut cardinality = left_prop.cardinality * right_prop.cardinality;
+        let mut estimated = false;
+        for (left, right) in self
+            .left_conditions
+            .iter()
+            .zip(self.right_conditions.iter())
+        {
+            let left_ndv = join_key_distinct_count(left, left_prop);
+            let right_ndv = join_key_distinct_count(right, right_prop);
+            if let (Some(left_ndv), Some(right_ndv)) = (left_ndv, right_ndv) {
+                let ndv = u64::max(left_ndv, right_ndv).max(1);
+                cardinality /= ndv as f64;
+                estimated = true;
+            }
+        }
+
+        if estimated {
+            cardinality
+        } else {
+            f64::max(left_prop.cardinality, right_prop.cardinality)
+        }
+    }
+}
+
+fn join_key_distinct_count(key: &Scalar, prop: &RelationalProperty) -> Option<u64> {
+    match key {
+        Scalar::BoundColumnRef(column) => prop
+            .column_stats
+            .get(&column.column.index)
+            .map(|stat| stat.distinct_count),
+        Scalar::CastExpr(cast) => join_key_distinct_count(&cast.argument, prop),
+        _ => None,
+    }
+}
+
 impl LogicalOperator for LogicalInnerJoin {
     fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
         let left_prop = rel_expr.derive_relational_prop_child(0)?;
         let right_prop = rel_expr.derive_relational_prop_child(1)?;
+        let inner_cardinality = self.inner_join_cardinality(&left_prop, &right_prop);
 
         // Derive output columns
         let mut output_columns = left_prop.output_columns;
@@ -193,13 +237,16 @@ impl LogicalOperator for LogicalInnerJoin {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
-        // Derive cardinality. We can not estimate the cardinality of inner join until we have
-        // distribution information of join keys, so we set it to the maximum value.
+        // Derive cardinality. The cardinality of inner join is estimated with the number of
+        // distinct values of the join keys, or set to the maximum value without them.
         let cardinality = match self.join_type {
-            // Todo(xudong): after `distinct_count` of `ColumnStat` is ready, we can estimate more precisely
-            JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
-                f64::max(left_prop.cardinality, right_prop.cardinality)
-            }
+            JoinType::Inner => inner_cardinality,
+            JoinType::Left => f64::max(inner_cardinality, left_prop.cardinality),
+            JoinType::Right => f64::max(inner_cardinality, right_prop.cardinality),
+            JoinType::Full => f64::max(
+                inner_cardinality,
+                f64::max(left_prop.cardinality, right_prop.cardinality),
+            ),
             JoinType::Cross => left_prop.cardinality * right_prop.cardinality,
 
             JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark | JoinType::Single => {
@@ -216,6 +263,13 @@ impl LogicalOperator for LogicalInnerJoin {
         used_columns.extend(left_prop.used_columns);
         used_columns.extend(right_prop.used_columns);
 
+        // Derive column statistics, a column has no more distinct values than rows.
+        let mut column_stats = left_prop.column_stats;
+        column_stats.extend(right_prop.column_stats);
+        for stat in column_stats.values_mut() {
+            stat.distinct_count = stat.distinct_count.min(cardinality.ceil() as u64);
+        }
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
@@ -223,7 +277,7 @@ impl LogicalOperator for LogicalInnerJoin {
             cardinality,
             precise_cardinality: None,
 
-            column_stats: Default::default(),
+            column_stats,
         })
     }
 
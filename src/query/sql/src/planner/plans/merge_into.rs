@@ -0,0 +1,71 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_meta_types::MetaId;
+
+use crate::plans::Plan;
+use crate::plans::Scalar;
+use crate::IndexType;
+use crate::MetadataRef;
+
+#[derive(Clone, Debug)]
+pub struct MergeIntoPlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub table_id: MetaId,
+    pub metadata: MetadataRef,
+    /// The optimized query producing the source rows.
+    pub source: Box<Plan>,
+    /// Columns of the source, in the order of the source output.
+    pub source_columns: Vec<IndexType>,
+    /// Columns of the target table, in the order of the table schema.
+    pub target_columns: Vec<IndexType>,
+    pub target_keys: Vec<Scalar>,
+    pub source_keys: Vec<Scalar>,
+    /// Non-equi conditions of the ON clause.
+    pub join_filter: Option<Scalar>,
+    pub matched_clauses: Vec<MergeMatchedClause>,
+    pub not_matched_clauses: Vec<MergeNotMatchedClause>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MergeMatchedClause {
+    pub condition: Option<Scalar>,
+    pub action: MergeMatchedAction,
+}
+
+#[derive(Clone, Debug)]
+pub enum MergeMatchedAction {
+    /// Pairs of the index of the updated field in the table schema and the new value.
+    Update(Vec<(usize, Scalar)>),
+    Delete,
+}
+
+#[derive(Clone, Debug)]
+pub struct MergeNotMatchedClause {
+    pub condition: Option<Scalar>,
+    /// The value of each field of the table schema, `None` for the default value.
+    pub values: Vec<Option<Scalar>>,
+}
+
+impl MergeIntoPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
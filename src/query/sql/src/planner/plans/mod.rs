@@ -28,18 +28,22 @@ mod limit;
 mod list;
 mod logical_get;
 mod logical_join;
+mod merge_into;
 mod operator;
 mod pattern;
 mod physical_scan;
 mod plan;
 mod presign;
 mod recluster_table;
+mod recursive_cte_scan;
+mod recursive_union;
 mod scalar;
 mod setting;
 pub mod share;
 mod sort;
 mod union_all;
 mod update;
+mod window;
 
 pub use aggregate::*;
 pub use call::CallPlan;
@@ -58,6 +62,7 @@ pub use limit::*;
 pub use list::ListPlan;
 pub use logical_get::*;
 pub use logical_join::*;
+pub use merge_into::*;
 pub use operator::*;
 pub use pattern::PatternPlan;
 pub use physical_scan::PhysicalScan;
@@ -66,9 +71,12 @@ pub use plan::RewriteKind::*;
 pub use plan::*;
 pub use presign::*;
 pub use recluster_table::ReclusterTablePlan;
+pub use recursive_cte_scan::RecursiveCteScan;
+pub use recursive_union::RecursiveUnion;
 pub use scalar::*;
 pub use setting::*;
 pub use share::*;
 pub use sort::*;
 pub use union_all::UnionAll;
 pub use update::UpdatePlan;
+pub use window::Window;
@@ -25,8 +25,11 @@ use super::logical_get::LogicalGet;
 use super::logical_join::LogicalInnerJoin;
 use super::pattern::PatternPlan;
 use super::physical_scan::PhysicalScan;
+use super::recursive_cte_scan::RecursiveCteScan;
+use super::recursive_union::RecursiveUnion;
 use super::sort::Sort;
 use super::union_all::UnionAll;
+use super::window::Window;
 use crate::optimizer::ColumnSet;
 use crate::optimizer::PhysicalProperty;
 use crate::optimizer::RelExpr;
@@ -86,6 +89,9 @@ pub enum RelOp {
     Exchange,
     UnionAll,
     DummyTableScan,
+    Window,
+    RecursiveUnion,
+    RecursiveCteScan,
 
     // Pattern
     Pattern,
@@ -108,6 +114,9 @@ pub enum RelOperator {
     Exchange(Exchange),
     UnionAll(UnionAll),
     DummyTableScan(DummyTableScan),
+    Window(Window),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
 
     Pattern(PatternPlan),
 }
@@ -128,6 +137,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.rel_op(),
             RelOperator::UnionAll(rel_op) => rel_op.rel_op(),
             RelOperator::DummyTableScan(rel_op) => rel_op.rel_op(),
+            RelOperator::Window(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.rel_op(),
         }
     }
 
@@ -146,6 +158,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.is_physical(),
             RelOperator::UnionAll(rel_op) => rel_op.is_physical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.is_physical(),
+            RelOperator::Window(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.is_physical(),
         }
     }
 
@@ -164,6 +179,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.is_logical(),
             RelOperator::UnionAll(rel_op) => rel_op.is_logical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.is_logical(),
+            RelOperator::Window(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.is_logical(),
         }
     }
 
@@ -182,6 +200,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.as_logical(),
             RelOperator::UnionAll(rel_op) => rel_op.as_logical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.as_logical(),
+            RelOperator::Window(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.as_logical(),
         }
     }
 
@@ -200,6 +221,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.as_physical(),
             RelOperator::UnionAll(rel_op) => rel_op.as_physical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.as_physical(),
+            RelOperator::Window(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.as_physical(),
         }
     }
 }
@@ -445,3 +469,58 @@ impl TryFrom<RelOperator> for DummyTableScan {
         }
     }
 }
+
+impl From<Window> for RelOperator {
+    fn from(v: Window) -> Self {
+        Self::Window(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Window {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Window(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal("Cannot downcast RelOperator to Window"))
+        }
+    }
+}
+
+impl From<RecursiveUnion> for RelOperator {
+    fn from(v: RecursiveUnion) -> Self {
+        Self::RecursiveUnion(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveUnion {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveUnion(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal(
+                "Cannot downcast RelOperator to RecursiveUnion",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveCteScan> for RelOperator {
+    fn from(v: RecursiveCteScan) -> Self {
+        Self::RecursiveCteScan(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveCteScan {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveCteScan(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal(
+                "Cannot downcast RelOperator to RecursiveCteScan",
+            ))
+        }
+    }
+}
@@ -37,14 +37,19 @@ use crate::plans::share::ShowGrantTenantsOfSharePlan;
 use crate::plans::share::ShowObjectGrantPrivilegesPlan;
 use crate::plans::share::ShowSharesPlan;
 use crate::plans::AlterTableClusterKeyPlan;
+use crate::plans::AlterTableColumnPlan;
 use crate::plans::AlterUDFPlan;
 use crate::plans::AlterUserPlan;
 use crate::plans::AlterViewPlan;
+use crate::plans::AnalyzeTablePlan;
 use crate::plans::CallPlan;
 use crate::plans::CreateCatalogPlan;
 use crate::plans::CreateDatabasePlan;
+use crate::plans::CreateIndexPlan;
+use crate::plans::CreateMaterializedViewPlan;
 use crate::plans::CreateRolePlan;
 use crate::plans::CreateStagePlan;
+use crate::plans::CreateStreamPlan;
 use crate::plans::CreateTablePlanV2;
 use crate::plans::CreateUDFPlan;
 use crate::plans::CreateUserPlan;
@@ -55,16 +60,19 @@ use crate::plans::DropCatalogPlan;
 use crate::plans::DropDatabasePlan;
 use crate::plans::DropRolePlan;
 use crate::plans::DropStagePlan;
+use crate::plans::DropStreamPlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTablePlan;
 use crate::plans::DropUDFPlan;
 use crate::plans::DropUserPlan;
 use crate::plans::DropViewPlan;
 use crate::plans::ExistsTablePlan;
+use crate::plans::FlashbackTablePlan;
 use crate::plans::GrantPrivilegePlan;
 use crate::plans::GrantRolePlan;
 use crate::plans::KillPlan;
 use crate::plans::ListPlan;
+use crate::plans::MergeIntoPlan;
 use crate::plans::OptimizeTablePlan;
 use crate::plans::RemoveStagePlan;
 use crate::plans::RenameDatabasePlan;
@@ -136,21 +144,38 @@ pub enum Plan {
     RenameTable(Box<RenameTablePlan>),
     AlterTableClusterKey(Box<AlterTableClusterKeyPlan>),
     DropTableClusterKey(Box<DropTableClusterKeyPlan>),
+    AlterTableColumn(Box<AlterTableColumnPlan>),
+    FlashbackTable(Box<FlashbackTablePlan>),
     ReclusterTable(Box<ReclusterTablePlan>),
     TruncateTable(Box<TruncateTablePlan>),
     OptimizeTable(Box<OptimizeTablePlan>),
+    AnalyzeTable(Box<AnalyzeTablePlan>),
     ExistsTable(Box<ExistsTablePlan>),
 
+    // Indexes
+    CreateIndex(Box<CreateIndexPlan>),
+
     // Insert
     Insert(Box<Insert>),
     Delete(Box<DeletePlan>),
     Update(Box<UpdatePlan>),
+    MergeInto(Box<MergeIntoPlan>),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
 
     // Views
     CreateView(Box<CreateViewPlan>),
+    CreateMaterializedView(Box<CreateMaterializedViewPlan>),
     AlterView(Box<AlterViewPlan>),
     DropView(Box<DropViewPlan>),
 
+    // Streams
+    CreateStream(Box<CreateStreamPlan>),
+    DropStream(Box<DropStreamPlan>),
+
     // Account
     AlterUser(Box<AlterUserPlan>),
     CreateUser(Box<CreateUserPlan>),
@@ -215,6 +240,8 @@ pub enum RewriteKind {
     ShowStages,
     DescribeStage,
     ShowRoles,
+
+    MaterializedView,
 }
 
 impl Display for Plan {
@@ -240,13 +267,20 @@ impl Display for Plan {
             Plan::RenameTable(_) => write!(f, "RenameTable"),
             Plan::AlterTableClusterKey(_) => write!(f, "AlterTableClusterKey"),
             Plan::DropTableClusterKey(_) => write!(f, "DropTableClusterKey"),
+            Plan::AlterTableColumn(_) => write!(f, "AlterTableColumn"),
+            Plan::FlashbackTable(_) => write!(f, "FlashbackTable"),
             Plan::ReclusterTable(_) => write!(f, "ReclusterTable"),
             Plan::TruncateTable(_) => write!(f, "TruncateTable"),
             Plan::OptimizeTable(_) => write!(f, "OptimizeTable"),
+            Plan::AnalyzeTable(_) => write!(f, "AnalyzeTable"),
             Plan::ExistsTable(_) => write!(f, "ExistsTable"),
+            Plan::CreateIndex(_) => write!(f, "CreateIndex"),
             Plan::CreateView(_) => write!(f, "CreateView"),
+            Plan::CreateMaterializedView(_) => write!(f, "CreateMaterializedView"),
             Plan::AlterView(_) => write!(f, "AlterView"),
             Plan::DropView(_) => write!(f, "DropView"),
+            Plan::CreateStream(_) => write!(f, "CreateStream"),
+            Plan::DropStream(_) => write!(f, "DropStream"),
             Plan::AlterUser(_) => write!(f, "AlterUser"),
             Plan::CreateUser(_) => write!(f, "CreateUser"),
             Plan::DropUser(_) => write!(f, "DropUser"),
@@ -268,6 +302,10 @@ impl Display for Plan {
             Plan::Insert(_) => write!(f, "Insert"),
             Plan::Delete(_) => write!(f, "Delete"),
             Plan::Update(_) => write!(f, "Update"),
+            Plan::MergeInto(_) => write!(f, "MergeInto"),
+            Plan::Begin => write!(f, "Begin"),
+            Plan::Commit => write!(f, "Commit"),
+            Plan::Rollback => write!(f, "Rollback"),
             Plan::Call(_) => write!(f, "Call"),
             Plan::Presign(_) => write!(f, "Presign"),
             Plan::SetVariable(_) => write!(f, "SetVariable"),
@@ -301,7 +339,7 @@ impl Plan {
             Plan::Explain { .. } | Plan::ExplainAst { .. } | Plan::ExplainSyntax { .. } => {
                 DataSchemaRefExt::create(vec![DataField::new("explain", StringType::new_impl())])
             }
-            Plan::Copy(_) => Arc::new(DataSchema::empty()),
+            Plan::Copy(plan) => plan.schema(),
             Plan::ShowCreateCatalog(plan) => plan.schema(),
             Plan::CreateCatalog(plan) => plan.schema(),
             Plan::DropCatalog(plan) => plan.schema(),
@@ -319,13 +357,20 @@ impl Plan {
             Plan::RenameTable(plan) => plan.schema(),
             Plan::AlterTableClusterKey(plan) => plan.schema(),
             Plan::DropTableClusterKey(plan) => plan.schema(),
+            Plan::AlterTableColumn(plan) => plan.schema(),
+            Plan::FlashbackTable(plan) => plan.schema(),
             Plan::ReclusterTable(plan) => plan.schema(),
             Plan::TruncateTable(plan) => plan.schema(),
             Plan::OptimizeTable(plan) => plan.schema(),
+            Plan::AnalyzeTable(plan) => plan.schema(),
             Plan::ExistsTable(plan) => plan.schema(),
+            Plan::CreateIndex(plan) => plan.schema(),
             Plan::CreateView(plan) => plan.schema(),
+            Plan::CreateMaterializedView(plan) => plan.schema(),
             Plan::AlterView(plan) => plan.schema(),
             Plan::DropView(plan) => plan.schema(),
+            Plan::CreateStream(plan) => plan.schema(),
+            Plan::DropStream(plan) => plan.schema(),
             Plan::AlterUser(plan) => plan.schema(),
             Plan::CreateUser(plan) => plan.schema(),
             Plan::DropUser(plan) => plan.schema(),
@@ -347,6 +392,10 @@ impl Plan {
             Plan::Insert(plan) => plan.schema(),
             Plan::Delete(_) => Arc::new(DataSchema::empty()),
             Plan::Update(_) => Arc::new(DataSchema::empty()),
+            Plan::MergeInto(plan) => plan.schema(),
+            Plan::Begin => Arc::new(DataSchema::empty()),
+            Plan::Commit => Arc::new(DataSchema::empty()),
+            Plan::Rollback => Arc::new(DataSchema::empty()),
             Plan::Call(_) => Arc::new(DataSchema::empty()),
             Plan::Presign(plan) => plan.schema(),
             Plan::SetVariable(plan) => plan.schema(),
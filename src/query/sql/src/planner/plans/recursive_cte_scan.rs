@@ -0,0 +1,92 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::optimizer::ColumnSet;
+use crate::optimizer::Distribution;
+use crate::optimizer::PhysicalProperty;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
+use crate::optimizer::RequiredProperty;
+use crate::plans::LogicalOperator;
+use crate::plans::Operator;
+use crate::plans::PhysicalOperator;
+use crate::plans::RelOp;
+use crate::IndexType;
+
+/// Scan of the working table of a recursive CTE, i.e. the rows produced
+/// by the previous iteration of the enclosing `RecursiveUnion`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveCteScan {
+    pub cte_name: String,
+    pub columns: Vec<IndexType>,
+}
+
+impl Operator for RecursiveCteScan {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveCteScan
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl LogicalOperator for RecursiveCteScan {
+    fn derive_relational_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        Ok(RelationalProperty {
+            output_columns: self.columns.iter().cloned().collect(),
+            outer_columns: ColumnSet::new(),
+            used_columns: self.used_columns()?,
+            cardinality: 0.0,
+            precise_cardinality: None,
+
+            column_stats: Default::default(),
+        })
+    }
+
+    fn used_columns<'a>(&self) -> Result<ColumnSet> {
+        Ok(self.columns.iter().cloned().collect())
+    }
+}
+
+impl PhysicalOperator for RecursiveCteScan {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone())
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::optimizer::ColumnSet;
+use crate::optimizer::Distribution;
+use crate::optimizer::PhysicalProperty;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
+use crate::optimizer::RequiredProperty;
+use crate::plans::LogicalOperator;
+use crate::plans::Operator;
+use crate::plans::PhysicalOperator;
+use crate::plans::RelOp;
+use crate::IndexType;
+
+/// Evaluation of a `WITH RECURSIVE` CTE.
+///
+/// The left child is the non-recursive anchor, the right child is the
+/// recursive term which reads the rows of the previous iteration through
+/// a `RecursiveCteScan` over `scan_columns`. The recursive term is
+/// re-evaluated until it produces no new rows.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveUnion {
+    pub cte_name: String,
+    // Pairs of unioned columns, (anchor column, recursive term column)
+    pub pairs: Vec<(IndexType, IndexType)>,
+    // Columns of the working table read by `RecursiveCteScan`
+    pub scan_columns: Vec<IndexType>,
+    // Remove duplicated rows across iterations (`UNION` instead of `UNION ALL`)
+    pub distinct: bool,
+}
+
+impl Operator for RecursiveUnion {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveUnion
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl LogicalOperator for RecursiveUnion {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let left_prop = rel_expr.derive_relational_prop_child(0)?;
+        let right_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        // Only the anchor columns are visible to the parent
+        let output_columns = self.pairs.iter().map(|(left, _)| *left).collect();
+
+        // Derive outer columns
+        let mut outer_columns = left_prop.outer_columns;
+        outer_columns = outer_columns
+            .union(&right_prop.outer_columns)
+            .cloned()
+            .collect();
+
+        // Derive used columns
+        let mut used_columns = self.used_columns()?;
+        used_columns.extend(left_prop.used_columns);
+        used_columns.extend(right_prop.used_columns);
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+            used_columns,
+            cardinality: left_prop.cardinality + right_prop.cardinality,
+            precise_cardinality: None,
+
+            column_stats: Default::default(),
+        })
+    }
+
+    fn used_columns<'a>(&self) -> Result<ColumnSet> {
+        let mut used_columns = ColumnSet::new();
+        for (left, right) in &self.pairs {
+            used_columns.insert(*left);
+            used_columns.insert(*right);
+        }
+        used_columns.extend(self.scan_columns.iter().cloned());
+        Ok(used_columns)
+    }
+}
+
+impl PhysicalOperator for RecursiveUnion {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone())
+    }
+}
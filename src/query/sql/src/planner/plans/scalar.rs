@@ -12,13 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Display;
 use std::hash::Hash;
 
 use common_ast::ast::BinaryOperator;
 use common_datavalues::BooleanType;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
+use common_datavalues::Float64Type;
 use common_datavalues::NullableType;
+use common_datavalues::UInt64Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionFactory;
@@ -57,6 +60,7 @@ pub enum Scalar {
     // after making functions static typed?
     CastExpr(CastExpr),
     SubqueryExpr(SubqueryExpr),
+    WindowFunction(WindowFunc),
 }
 
 impl ScalarExpr for Scalar {
@@ -71,6 +75,7 @@ impl ScalarExpr for Scalar {
             Scalar::FunctionCall(scalar) => scalar.data_type(),
             Scalar::CastExpr(scalar) => scalar.data_type(),
             Scalar::SubqueryExpr(scalar) => scalar.data_type(),
+            Scalar::WindowFunction(scalar) => scalar.data_type(),
         }
     }
 
@@ -85,6 +90,7 @@ impl ScalarExpr for Scalar {
             Scalar::FunctionCall(scalar) => scalar.used_columns(),
             Scalar::CastExpr(scalar) => scalar.used_columns(),
             Scalar::SubqueryExpr(scalar) => scalar.used_columns(),
+            Scalar::WindowFunction(scalar) => scalar.used_columns(),
         }
     }
 
@@ -99,6 +105,7 @@ impl ScalarExpr for Scalar {
             Scalar::FunctionCall(scalar) => scalar.is_deterministic(),
             Scalar::CastExpr(scalar) => scalar.is_deterministic(),
             Scalar::SubqueryExpr(scalar) => scalar.is_deterministic(),
+            Scalar::WindowFunction(scalar) => scalar.is_deterministic(),
         }
     }
 }
@@ -268,6 +275,23 @@ impl TryFrom<Scalar> for SubqueryExpr {
     }
 }
 
+impl From<WindowFunc> for Scalar {
+    fn from(v: WindowFunc) -> Self {
+        Self::WindowFunction(v)
+    }
+}
+
+impl TryFrom<Scalar> for WindowFunc {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::WindowFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal("Cannot downcast Scalar to WindowFunc"))
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BoundColumnRef {
     pub column: ColumnBinding,
@@ -395,6 +419,17 @@ impl ComparisonOp {
         }
         .to_string()
     }
+
+    /// The operator with the operands swapped, e.g. `a < b` is `b > a`.
+    pub fn reverse(&self) -> Self {
+        match self {
+            ComparisonOp::GT => ComparisonOp::LT,
+            ComparisonOp::LT => ComparisonOp::GT,
+            ComparisonOp::GTE => ComparisonOp::LTE,
+            ComparisonOp::LTE => ComparisonOp::GTE,
+            op => op.clone(),
+        }
+    }
 }
 
 impl<'a> TryFrom<&'a BinaryOperator> for ComparisonOp {
@@ -573,3 +608,239 @@ impl ScalarExpr for SubqueryExpr {
         false
     }
 }
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowFunc {
+    pub display_name: String,
+    pub func: WindowFuncType,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub frame: WindowFuncFrame,
+}
+
+impl WindowFunc {
+    /// All the scalar expressions that must be evaluated before the window function,
+    /// including arguments, partition keys and order keys.
+    pub fn input_scalars(&self) -> Vec<&Scalar> {
+        let mut scalars = self.func.arguments();
+        scalars.extend(self.partition_by.iter());
+        scalars.extend(self.order_by.iter().map(|order| &order.expr));
+        scalars
+    }
+
+    /// Rebuild the window function with each of its input scalars rewritten by `f`.
+    pub fn try_map_inputs<F>(&self, mut f: F) -> Result<WindowFunc>
+    where F: FnMut(&Scalar) -> Result<Scalar> {
+        let func = match &self.func {
+            WindowFuncType::Aggregate(agg) => WindowFuncType::Aggregate(AggregateFunction {
+                args: agg.args.iter().map(&mut f).collect::<Result<Vec<_>>>()?,
+                ..agg.clone()
+            }),
+            WindowFuncType::LagLead(lag_lead) => WindowFuncType::LagLead(LagLeadFunction {
+                is_lag: lag_lead.is_lag,
+                arg: Box::new(f(&lag_lead.arg)?),
+                offset: lag_lead.offset,
+                default: match &lag_lead.default {
+                    Some(default) => Some(Box::new(f(default)?)),
+                    None => None,
+                },
+                return_type: lag_lead.return_type.clone(),
+            }),
+            WindowFuncType::NthValue(nth) => WindowFuncType::NthValue(NthValueFunction {
+                n: nth.n,
+                arg: Box::new(f(&nth.arg)?),
+                return_type: nth.return_type.clone(),
+            }),
+            func => func.clone(),
+        };
+        let partition_by = self
+            .partition_by
+            .iter()
+            .map(&mut f)
+            .collect::<Result<Vec<_>>>()?;
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|order| {
+                Ok(WindowOrderBy {
+                    expr: f(&order.expr)?,
+                    asc: order.asc,
+                    nulls_first: order.nulls_first,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(WindowFunc {
+            display_name: self.display_name.clone(),
+            func,
+            partition_by,
+            order_by,
+            frame: self.frame.clone(),
+        })
+    }
+}
+
+impl ScalarExpr for WindowFunc {
+    fn data_type(&self) -> DataTypeImpl {
+        self.func.return_type()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for scalar in self.input_scalars() {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        result
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    pub asc: Option<bool>,
+    pub nulls_first: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WindowFuncType {
+    Aggregate(AggregateFunction),
+    RowNumber,
+    Rank,
+    DenseRank,
+    PercentRank,
+    CumeDist,
+    LagLead(LagLeadFunction),
+    NthValue(NthValueFunction),
+}
+
+impl WindowFuncType {
+    pub fn func_name(&self) -> String {
+        match self {
+            WindowFuncType::Aggregate(agg) => agg.func_name.clone(),
+            WindowFuncType::RowNumber => "row_number".to_string(),
+            WindowFuncType::Rank => "rank".to_string(),
+            WindowFuncType::DenseRank => "dense_rank".to_string(),
+            WindowFuncType::PercentRank => "percent_rank".to_string(),
+            WindowFuncType::CumeDist => "cume_dist".to_string(),
+            WindowFuncType::LagLead(lag_lead) if lag_lead.is_lag => "lag".to_string(),
+            WindowFuncType::LagLead(_) => "lead".to_string(),
+            WindowFuncType::NthValue(nth) => match nth.n {
+                Some(1) => "first_value".to_string(),
+                Some(_) => "nth_value".to_string(),
+                None => "last_value".to_string(),
+            },
+        }
+    }
+
+    pub fn arguments(&self) -> Vec<&Scalar> {
+        match self {
+            WindowFuncType::Aggregate(agg) => agg.args.iter().collect(),
+            WindowFuncType::LagLead(lag_lead) => {
+                let mut args = vec![lag_lead.arg.as_ref()];
+                if let Some(default) = &lag_lead.default {
+                    args.push(default.as_ref());
+                }
+                args
+            }
+            WindowFuncType::NthValue(nth) => vec![nth.arg.as_ref()],
+            _ => vec![],
+        }
+    }
+
+    pub fn return_type(&self) -> DataTypeImpl {
+        match self {
+            WindowFuncType::Aggregate(agg) => *agg.return_type.clone(),
+            WindowFuncType::RowNumber | WindowFuncType::Rank | WindowFuncType::DenseRank => {
+                UInt64Type::new_impl()
+            }
+            WindowFuncType::PercentRank | WindowFuncType::CumeDist => Float64Type::new_impl(),
+            WindowFuncType::LagLead(lag_lead) => *lag_lead.return_type.clone(),
+            WindowFuncType::NthValue(nth) => *nth.return_type.clone(),
+        }
+    }
+
+    /// Ranking and offset functions always work on the whole partition, the
+    /// window frame only makes sense for aggregate and value functions.
+    pub fn support_frame(&self) -> bool {
+        matches!(
+            self,
+            WindowFuncType::Aggregate(_) | WindowFuncType::NthValue(_)
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LagLeadFunction {
+    /// True if the function is `LAG`, otherwise it's `LEAD`
+    pub is_lag: bool,
+    pub arg: Box<Scalar>,
+    pub offset: u64,
+    pub default: Option<Box<Scalar>>,
+    pub return_type: Box<DataTypeImpl>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NthValueFunction {
+    /// The n-th row of the frame, `None` means the last row of the frame.
+    pub n: Option<u64>,
+    pub arg: Box<Scalar>,
+    pub return_type: Box<DataTypeImpl>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WindowFuncFrame {
+    pub units: WindowFuncFrameUnits,
+    pub start_bound: WindowFuncFrameBound,
+    pub end_bound: WindowFuncFrameBound,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameBound {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<DataValue>),
+    /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<DataValue>),
+}
+
+impl Display for WindowFuncFrameUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFuncFrameUnits::Rows => write!(f, "ROWS"),
+            WindowFuncFrameUnits::Range => write!(f, "RANGE"),
+        }
+    }
+}
+
+impl Display for WindowFuncFrameBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFuncFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFuncFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFuncFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFuncFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFuncFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
+impl Display for WindowFuncFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
 use common_meta_types::MetaId;
 
 use crate::plans::Scalar;
+use crate::MetadataRef;
 
 #[derive(Clone, Debug)]
 pub struct UpdatePlan {
@@ -26,4 +30,11 @@ pub struct UpdatePlan {
     pub table_id: MetaId,
     pub update_list: HashMap<usize, Scalar>,
     pub selection: Option<Scalar>,
+    pub metadata: MetadataRef,
+}
+
+impl UpdatePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
 }
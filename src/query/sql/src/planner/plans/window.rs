@@ -0,0 +1,122 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::Result;
+
+use crate::optimizer::ColumnSet;
+use crate::optimizer::Distribution;
+use crate::optimizer::PhysicalProperty;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
+use crate::optimizer::RequiredProperty;
+use crate::plans::LogicalOperator;
+use crate::plans::Operator;
+use crate::plans::PhysicalOperator;
+use crate::plans::RelOp;
+use crate::plans::ScalarExpr;
+use crate::plans::SortItem;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncType;
+use crate::IndexType;
+
+/// Evaluate a window function over the input, the result of the
+/// window function will be appended to the input as a new column.
+///
+/// All the inputs of the window function(arguments, partition keys
+/// and order keys) should have been evaluated by a child `EvalScalar`,
+/// so they are all `BoundColumnRef`s here.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Window {
+    /// Index of the output column
+    pub index: IndexType,
+    pub function: WindowFuncType,
+    pub partition_by: Vec<IndexType>,
+    pub order_by: Vec<SortItem>,
+    pub frame: WindowFuncFrame,
+}
+
+impl Operator for Window {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Window
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl PhysicalOperator for Window {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Rows of a partition must be processed together, so we just
+        // gather all the data into a single node for now.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+}
+
+impl LogicalOperator for Window {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let input_prop = rel_expr.derive_relational_prop_child(0)?;
+
+        // Derive output columns
+        let mut output_columns = input_prop.output_columns;
+        output_columns.insert(self.index);
+
+        // Derive used columns
+        let mut used_columns = self.used_columns()?;
+        used_columns.extend(input_prop.used_columns);
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns: input_prop.outer_columns,
+            used_columns,
+            cardinality: input_prop.cardinality,
+            precise_cardinality: input_prop.precise_cardinality,
+
+            column_stats: input_prop.column_stats,
+        })
+    }
+
+    fn used_columns<'a>(&self) -> Result<ColumnSet> {
+        let mut used_columns = ColumnSet::new();
+        used_columns.insert(self.index);
+        for arg in self.function.arguments() {
+            used_columns.extend(arg.used_columns());
+        }
+        used_columns.extend(self.partition_by.iter());
+        used_columns.extend(self.order_by.iter().map(|item| item.index));
+        Ok(used_columns)
+    }
+}
@@ -118,6 +118,10 @@ impl<'a> GroupingChecker<'a> {
                 Ok(scalar.clone())
             }
 
+            // Window functions are evaluated after aggregation, the inputs of them
+            // will be checked while binding the window.
+            Scalar::WindowFunction(_) => Ok(scalar.clone()),
+
             Scalar::AggregateFunction(agg) => {
                 if let Some(column) = self
                     .bind_context
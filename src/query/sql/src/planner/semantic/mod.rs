@@ -16,6 +16,7 @@ mod grouping_check;
 mod name_resolution;
 mod sync_type_checker;
 mod type_check;
+mod window_check;
 
 pub use grouping_check::GroupingChecker;
 pub use name_resolution::normalize_identifier;
@@ -24,3 +25,4 @@ pub use name_resolution::NameResolutionContext;
 pub use sync_type_checker::SyncTypeChecker;
 pub use type_check::validate_function_arg;
 pub use type_check::TypeChecker;
+pub use window_check::WindowChecker;
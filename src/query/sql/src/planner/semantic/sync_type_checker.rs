@@ -183,6 +183,7 @@ impl<'a> SyncTypeChecker<'a> {
                             },
                             args: vec![(args[0]).clone()],
                             params: vec![],
+                            window: None,
                         }],
                         None,
                     )?
@@ -283,6 +284,7 @@ impl<'a> SyncTypeChecker<'a> {
                                 },
                                 args: args.iter().copied().cloned().collect(),
                                 params: vec![],
+                                window: None,
                             }],
                             None,
                         )?
@@ -457,6 +459,7 @@ impl<'a> SyncTypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -510,9 +513,16 @@ impl<'a> SyncTypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.as_str();
+                if window.is_some() {
+                    return Err(ErrorCode::SemanticError(
+                        expr.span()
+                            .display_error("not support window functions".to_string()),
+                    ));
+                }
+
                 if !is_builtin_function(func_name)
                     && !Self::is_rewritable_scalar_function(func_name)
                 {
@@ -26,6 +26,13 @@ use common_ast::ast::Query;
 use common_ast::ast::SubqueryModifier;
 use common_ast::ast::TrimWhere;
 use common_ast::ast::UnaryOperator;
+use common_ast::ast::Window;
+use common_ast::ast::WindowDefinition;
+use common_ast::ast::WindowFrame;
+use common_ast::ast::WindowFrameBound;
+use common_ast::ast::WindowFrameUnits;
+use common_ast::ast::WindowRef;
+use common_ast::ast::WindowSpec;
 use common_ast::parser::parse_expr;
 use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
@@ -35,6 +42,7 @@ use common_catalog::catalog::CatalogManager;
 use common_catalog::table_context::TableContext;
 use common_datavalues::remove_nullable;
 use common_datavalues::type_coercion::merge_types;
+use common_datavalues::wrap_nullable;
 use common_datavalues::ArrayType;
 use common_datavalues::DataField;
 use common_datavalues::DataType;
@@ -73,10 +81,18 @@ use crate::plans::ComparisonExpr;
 use crate::plans::ComparisonOp;
 use crate::plans::ConstantExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
+use crate::plans::NthValueFunction;
 use crate::plans::OrExpr;
 use crate::plans::Scalar;
 use crate::plans::SubqueryExpr;
 use crate::plans::SubqueryType;
+use crate::plans::WindowFunc;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncFrameBound;
+use crate::plans::WindowFuncFrameUnits;
+use crate::plans::WindowFuncType;
+use crate::plans::WindowOrderBy;
 use crate::BindContext;
 use crate::MetadataRef;
 use crate::ScalarExpr;
@@ -98,9 +114,16 @@ pub struct TypeChecker<'a> {
 
     aliases: &'a [(String, Scalar)],
 
+    // Named windows defined in `WINDOW` clause.
+    windows: &'a [WindowDefinition<'a>],
+
     // true if current expr is inside an aggregate function.
     // This is used to check if there is nested aggregate function.
     in_aggregate_function: bool,
+
+    // true if current expr is inside a window function.
+    // This is used to check if there is nested window function.
+    in_window_function: bool,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -117,10 +140,18 @@ impl<'a> TypeChecker<'a> {
             name_resolution_ctx,
             metadata,
             aliases,
+            windows: &[],
             in_aggregate_function: false,
+            in_window_function: false,
         }
     }
 
+    /// Set the named windows that can be referenced with `OVER window_name`.
+    pub fn with_windows(mut self, windows: &'a [WindowDefinition<'a>]) -> Self {
+        self.windows = windows;
+        self
+    }
+
     fn post_resolve(
         &mut self,
         scalar: &Scalar,
@@ -211,6 +242,7 @@ impl<'a> TypeChecker<'a> {
                             },
                             args: vec![(args[0]).clone()],
                             params: vec![],
+                            window: None,
                         }],
                         None,
                     )
@@ -315,6 +347,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: args.iter().copied().cloned().collect(),
                                 params: vec![],
+                                window: None,
                             }],
                             None,
                         )
@@ -515,6 +548,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -570,9 +604,17 @@ impl<'a> TypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.as_str();
+                if let Some(window) = window {
+                    return self
+                        .resolve_window_function(
+                            span, expr, func_name, *distinct, args, params, window,
+                        )
+                        .await;
+                }
+
                 if !is_builtin_function(func_name)
                     && !Self::is_rewritable_scalar_function(func_name)
                 {
@@ -606,48 +648,17 @@ impl<'a> TypeChecker<'a> {
                     }
                     self.in_aggregate_function = false;
 
-                    let data_fields = arguments
-                        .iter()
-                        .map(|box (_, data_type)| DataField::new("", data_type.clone()))
-                        .collect();
-
-                    // Rewrite `xxx(distinct)` to `xxx_distinct(...)`
-                    let (func_name, distinct) =
-                        if func_name.eq_ignore_ascii_case("count") && *distinct {
-                            ("count_distinct", false)
-                        } else {
-                            (func_name, *distinct)
-                        };
-
-                    let func_name = if distinct {
-                        format!("{}_distinct", func_name)
-                    } else {
-                        func_name.to_string()
-                    };
-
-                    let agg_func = AggregateFunctionFactory::instance()
-                        .get(&func_name, params.clone(), data_fields)
-                        .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
-
-                    let args = if optimize_remove_count_args(&func_name, distinct, args.as_slice())
-                    {
-                        vec![]
-                    } else {
-                        arguments.into_iter().map(|box (arg, _)| arg).collect()
-                    };
-
-                    Box::new((
-                        AggregateFunction {
-                            display_name: format!("{:#}", expr),
-                            func_name,
-                            distinct: false,
-                            params,
-                            args,
-                            return_type: Box::new(agg_func.return_type()?),
-                        }
-                        .into(),
-                        agg_func.return_type()?,
-                    ))
+                    let agg_func = Self::build_aggregate_function(
+                        span,
+                        format!("{:#}", expr),
+                        func_name,
+                        *distinct,
+                        params,
+                        arguments,
+                        &args,
+                    )?;
+                    let data_type = agg_func.data_type();
+                    Box::new((agg_func.into(), data_type))
                 } else {
                     // Scalar function
                     self.resolve_function(span, func_name, &args, required_type)
@@ -898,6 +909,428 @@ impl<'a> TypeChecker<'a> {
         Ok(Box::new(self.post_resolve(&scalar, &data_type)?))
     }
 
+    /// Build an `AggregateFunction` with resolved arguments.
+    fn build_aggregate_function(
+        span: &[Token<'_>],
+        display_name: String,
+        func_name: &str,
+        distinct: bool,
+        params: Vec<DataValue>,
+        arguments: Vec<Box<(Scalar, DataTypeImpl)>>,
+        raw_args: &[&Expr<'_>],
+    ) -> Result<AggregateFunction> {
+        let data_fields = arguments
+            .iter()
+            .map(|box (_, data_type)| DataField::new("", data_type.clone()))
+            .collect();
+
+        // Rewrite `xxx(distinct)` to `xxx_distinct(...)`
+        let (func_name, distinct) = if func_name.eq_ignore_ascii_case("count") && distinct {
+            ("count_distinct", false)
+        } else {
+            (func_name, distinct)
+        };
+
+        let func_name = if distinct {
+            format!("{}_distinct", func_name)
+        } else {
+            func_name.to_string()
+        };
+
+        let agg_func = AggregateFunctionFactory::instance()
+            .get(&func_name, params.clone(), data_fields)
+            .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+
+        let args = if optimize_remove_count_args(&func_name, distinct, raw_args) {
+            vec![]
+        } else {
+            arguments.into_iter().map(|box (arg, _)| arg).collect()
+        };
+
+        Ok(AggregateFunction {
+            display_name,
+            func_name,
+            distinct: false,
+            params,
+            args,
+            return_type: Box::new(agg_func.return_type()?),
+        })
+    }
+
+    /// Resolve window function call, e.g. `RANK() OVER (PARTITION BY a ORDER BY b)`.
+    ///
+    /// Named windows should have been replaced with their definitions by binder.
+    #[allow(clippy::too_many_arguments)]
+    #[async_recursion::async_recursion]
+    async fn resolve_window_function(
+        &mut self,
+        span: &[Token<'_>],
+        expr: &Expr<'_>,
+        func_name: &str,
+        distinct: bool,
+        args: &[Expr<'_>],
+        params: &[Literal],
+        window: &Window<'_>,
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        if self.in_aggregate_function || self.in_window_function {
+            // Reset the state
+            self.in_aggregate_function = false;
+            self.in_window_function = false;
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested".to_string(),
+            )));
+        }
+
+        let spec = match window {
+            Window::WindowSpec(spec) => self.merge_window_spec(span, spec, self.windows.len())?,
+            Window::WindowReference(WindowRef { window_name }) => {
+                self.resolve_named_window(span, window_name, self.windows.len())?
+            }
+        };
+
+        self.in_window_function = true;
+        let mut partition_by = Vec::with_capacity(spec.partition_by.len());
+        for partition in spec.partition_by.iter() {
+            let box (scalar, _) = self.resolve(partition, None).await?;
+            partition_by.push(scalar);
+        }
+
+        let mut order_by = Vec::with_capacity(spec.order_by.len());
+        for order in spec.order_by.iter() {
+            let box (scalar, _) = self.resolve(&order.expr, None).await?;
+            order_by.push(WindowOrderBy {
+                expr: scalar,
+                asc: order.asc,
+                nulls_first: order.nulls_first,
+            });
+        }
+
+        let func = self
+            .resolve_window_func_type(span, func_name, distinct, args, params)
+            .await?;
+        self.in_window_function = false;
+
+        let frame = match &spec.window_frame {
+            Some(frame) if func.support_frame() => {
+                self.resolve_window_frame(span, &order_by, frame).await?
+            }
+            // Ranking and offset functions always ignore the frame clause.
+            _ if order_by.is_empty() || !func.support_frame() => WindowFuncFrame {
+                units: WindowFuncFrameUnits::Rows,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::Following(None),
+            },
+            // Default frame is `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`
+            // if there is `ORDER BY` in window specification.
+            _ => WindowFuncFrame {
+                units: WindowFuncFrameUnits::Range,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::CurrentRow,
+            },
+        };
+
+        let window_func = WindowFunc {
+            display_name: format!("{:#}", expr),
+            func,
+            partition_by,
+            order_by,
+            frame,
+        };
+        let data_type = window_func.data_type();
+        Ok(Box::new((window_func.into(), data_type)))
+    }
+
+    /// Find the window named `window_name` in the first `end` window definitions,
+    /// a window definition can only reference the windows defined before it.
+    fn resolve_named_window(
+        &self,
+        span: &[Token<'_>],
+        window_name: &Identifier<'_>,
+        end: usize,
+    ) -> Result<WindowSpec<'a>> {
+        let name = normalize_identifier(window_name, self.name_resolution_ctx).name;
+        match self.windows[..end].iter().position(|window| {
+            normalize_identifier(&window.name, self.name_resolution_ctx).name == name
+        }) {
+            Some(position) => self.merge_window_spec(span, &self.windows[position].spec, position),
+            None => Err(ErrorCode::SemanticError(span.display_error(format!(
+                "window \"{}\" does not exist",
+                window_name
+            )))),
+        }
+    }
+
+    /// Merge a window specification with the window it is based on, e.g.
+    /// `OVER (w ORDER BY a)`. The result doesn't contain `existing_window_name`.
+    fn merge_window_spec<'b>(
+        &self,
+        span: &[Token<'_>],
+        spec: &WindowSpec<'b>,
+        end: usize,
+    ) -> Result<WindowSpec<'b>>
+    where
+        'a: 'b,
+    {
+        let window_name = match &spec.existing_window_name {
+            Some(window_name) => window_name,
+            None => return Ok(spec.clone()),
+        };
+        let base = self.resolve_named_window(span, window_name, end)?;
+        if !spec.partition_by.is_empty() {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "cannot override PARTITION BY clause of window \"{}\"",
+                window_name
+            ))));
+        }
+        if !spec.order_by.is_empty() && !base.order_by.is_empty() {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "cannot override ORDER BY clause of window \"{}\"",
+                window_name
+            ))));
+        }
+        if base.window_frame.is_some() {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "cannot copy window \"{}\" because it has a frame clause",
+                window_name
+            ))));
+        }
+
+        Ok(WindowSpec {
+            existing_window_name: None,
+            partition_by: base.partition_by,
+            order_by: if spec.order_by.is_empty() {
+                base.order_by
+            } else {
+                spec.order_by.clone()
+            },
+            window_frame: spec.window_frame.clone(),
+        })
+    }
+
+    async fn resolve_window_func_type(
+        &mut self,
+        span: &[Token<'_>],
+        func_name: &str,
+        distinct: bool,
+        args: &[Expr<'_>],
+        params: &[Literal],
+    ) -> Result<WindowFuncType> {
+        let args: Vec<&Expr> = args.iter().collect();
+
+        if AggregateFunctionFactory::instance().check(func_name) {
+            let params = params
+                .iter()
+                .map(|literal| {
+                    self.resolve_literal(literal, None)
+                        .map(|box (value, _)| value)
+                })
+                .collect::<Result<Vec<DataValue>>>()?;
+
+            let mut arguments = vec![];
+            for arg in args.iter() {
+                arguments.push(self.resolve(arg, None).await?);
+            }
+
+            let display_name = format!(
+                "{}({}{})",
+                func_name,
+                if distinct { "DISTINCT " } else { "" },
+                args.iter()
+                    .map(|arg| format!("{:#}", arg))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let agg_func = Self::build_aggregate_function(
+                span,
+                display_name,
+                func_name,
+                distinct,
+                params,
+                arguments,
+                &args,
+            )?;
+            return Ok(WindowFuncType::Aggregate(agg_func));
+        }
+
+        if distinct {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "DISTINCT is not supported for window function {func_name}"
+            ))));
+        }
+
+        let func_name = func_name.to_lowercase();
+        let (min_args, max_args) = match func_name.as_str() {
+            "row_number" | "rank" | "dense_rank" | "percent_rank" | "cume_dist" => (0, 0),
+            "lag" | "lead" => (1, 3),
+            "first_value" | "last_value" => (1, 1),
+            "nth_value" => (2, 2),
+            _ => {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "function {func_name} is not a window function or an aggregate function"
+                ))));
+            }
+        };
+        if args.len() < min_args || args.len() > max_args {
+            return Err(ErrorCode::NumberArgumentsNotMatch(span.display_error(format!(
+                "Function {func_name} expect to have [{min_args}, {max_args}] arguments, but got {}",
+                args.len()
+            ))));
+        }
+
+        match func_name.as_str() {
+            "row_number" => Ok(WindowFuncType::RowNumber),
+            "rank" => Ok(WindowFuncType::Rank),
+            "dense_rank" => Ok(WindowFuncType::DenseRank),
+            "percent_rank" => Ok(WindowFuncType::PercentRank),
+            "cume_dist" => Ok(WindowFuncType::CumeDist),
+            "lag" | "lead" => {
+                let box (arg, arg_type) = self.resolve(args[0], None).await?;
+                let offset = if args.len() > 1 {
+                    self.resolve_window_constant_u64(args[1]).await?
+                } else {
+                    1
+                };
+                let return_type = wrap_nullable(&arg_type);
+                let default = if args.len() > 2 {
+                    let box (default, _) = self.resolve(args[2], None).await?;
+                    Some(Box::new(wrap_cast_if_needed(default, &return_type)))
+                } else {
+                    None
+                };
+                Ok(WindowFuncType::LagLead(LagLeadFunction {
+                    is_lag: func_name == "lag",
+                    arg: Box::new(arg),
+                    offset,
+                    default,
+                    return_type: Box::new(return_type),
+                }))
+            }
+            _ => {
+                let box (arg, arg_type) = self.resolve(args[0], None).await?;
+                let n = match func_name.as_str() {
+                    "first_value" => Some(1),
+                    "last_value" => None,
+                    _ => {
+                        let n = self.resolve_window_constant_u64(args[1]).await?;
+                        if n == 0 {
+                            return Err(ErrorCode::SemanticError(args[1].span().display_error(
+                                "argument of nth_value must be greater than zero".to_string(),
+                            )));
+                        }
+                        Some(n)
+                    }
+                };
+                Ok(WindowFuncType::NthValue(NthValueFunction {
+                    n,
+                    arg: Box::new(arg),
+                    return_type: Box::new(wrap_nullable(&arg_type)),
+                }))
+            }
+        }
+    }
+
+    async fn resolve_window_frame(
+        &mut self,
+        span: &[Token<'_>],
+        order_by: &[WindowOrderBy],
+        frame: &WindowFrame<'_>,
+    ) -> Result<WindowFuncFrame> {
+        let units = match frame.units {
+            WindowFrameUnits::Rows => WindowFuncFrameUnits::Rows,
+            WindowFrameUnits::Range => WindowFuncFrameUnits::Range,
+        };
+
+        let mut bounds = Vec::with_capacity(2);
+        for bound in [&frame.start_bound, &frame.end_bound] {
+            let bound = match bound {
+                WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
+                WindowFrameBound::Preceding(None) => WindowFuncFrameBound::Preceding(None),
+                WindowFrameBound::Following(None) => WindowFuncFrameBound::Following(None),
+                WindowFrameBound::Preceding(Some(offset)) => WindowFuncFrameBound::Preceding(Some(
+                    self.resolve_window_frame_offset(&units, order_by, offset)
+                        .await?,
+                )),
+                WindowFrameBound::Following(Some(offset)) => WindowFuncFrameBound::Following(Some(
+                    self.resolve_window_frame_offset(&units, order_by, offset)
+                        .await?,
+                )),
+            };
+            bounds.push(bound);
+        }
+        let end_bound = bounds.pop().unwrap();
+        let start_bound = bounds.pop().unwrap();
+
+        if start_bound == WindowFuncFrameBound::Following(None) {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "frame start cannot be UNBOUNDED FOLLOWING".to_string(),
+            )));
+        }
+        if end_bound == WindowFuncFrameBound::Preceding(None) {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "frame end cannot be UNBOUNDED PRECEDING".to_string(),
+            )));
+        }
+
+        Ok(WindowFuncFrame {
+            units,
+            start_bound,
+            end_bound,
+        })
+    }
+
+    async fn resolve_window_frame_offset(
+        &mut self,
+        units: &WindowFuncFrameUnits,
+        order_by: &[WindowOrderBy],
+        offset: &Expr<'_>,
+    ) -> Result<DataValue> {
+        match units {
+            WindowFuncFrameUnits::Rows => Ok(DataValue::UInt64(
+                self.resolve_window_constant_u64(offset).await?,
+            )),
+            WindowFuncFrameUnits::Range => {
+                if order_by.len() != 1
+                    || !remove_nullable(&order_by[0].expr.data_type())
+                        .data_type_id()
+                        .is_numeric()
+                {
+                    return Err(ErrorCode::SemanticError(offset.span().display_error(
+                        "RANGE with offset PRECEDING/FOLLOWING requires exactly one numeric ORDER BY column"
+                            .to_string(),
+                    )));
+                }
+                let box (scalar, _) = self.resolve(offset, None).await?;
+                match scalar {
+                    Scalar::ConstantExpr(ConstantExpr { value, .. })
+                        if value.is_numeric() && value.as_f64()? >= 0.0 =>
+                    {
+                        Ok(value)
+                    }
+                    _ => Err(ErrorCode::SemanticError(offset.span().display_error(
+                        "frame offset must be a non-negative numeric constant".to_string(),
+                    ))),
+                }
+            }
+        }
+    }
+
+    async fn resolve_window_constant_u64(&mut self, expr: &Expr<'_>) -> Result<u64> {
+        let box (scalar, _) = self.resolve(expr, None).await?;
+        match scalar {
+            Scalar::ConstantExpr(ConstantExpr { value, .. }) if value.is_integer() => {
+                value.as_u64().map_err(|_| {
+                    ErrorCode::SemanticError(
+                        expr.span()
+                            .display_error("expect a non-negative integer constant".to_string()),
+                    )
+                })
+            }
+            _ => Err(ErrorCode::SemanticError(expr.span().display_error(
+                "expect a non-negative integer constant".to_string(),
+            ))),
+        }
+    }
+
     fn rewrite_substring(args: &mut [Scalar]) {
         if let Scalar::ConstantExpr(expr) = &args[1] {
             if let Ok(0) = expr.value.as_u64() {
@@ -1637,6 +2070,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![(*arg).clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     new_args.push(is_not_null_expr);
@@ -2120,6 +2554,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -2129,6 +2564,7 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: window.clone(),
                 }),
                 Expr::Case {
                     span,
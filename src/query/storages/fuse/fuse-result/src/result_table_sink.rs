@@ -129,6 +129,7 @@ impl ResultTableSink {
             compressed_byte_size: acc.file_size,
             col_stats,
             index_size: 0,
+            col_distributions: Default::default(),
         });
 
         let meta = ResultTableMeta {
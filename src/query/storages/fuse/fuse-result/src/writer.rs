@@ -82,6 +82,7 @@ impl ResultTableWriter {
             compressed_byte_size: acc.file_size,
             col_stats,
             index_size: 0,
+            col_distributions: Default::default(),
         });
 
         let meta = ResultTableMeta {
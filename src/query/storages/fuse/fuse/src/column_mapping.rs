@@ -0,0 +1,259 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_catalog::plan::Projection;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_table_meta::meta::ColumnId;
+
+use crate::fuse_part::ColumnLeaves;
+use crate::FUSE_SCHEMA_META_KEY_COLUMN_MAPPING;
+
+/// The columns that store (or used to store) the data of a field.
+///
+/// Leaf columns of nested types take consecutive ids, starting from `column_id`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct FieldColumns {
+    pub column_id: ColumnId,
+    /// The value of the field for blocks written before the field was added.
+    pub missing_value: DataValue,
+    /// Columns of the previous versions (renamed or retyped) of the field, latest first.
+    pub history: Vec<(ColumnId, DataTypeImpl)>,
+}
+
+/// Where the data of a field could be found in a specific block.
+pub enum ColumnSource<'a> {
+    /// Stored with the current data type, starting at the column id.
+    Stored(ColumnId),
+    /// Stored with a previous data type, should be cast after being read.
+    Cast(ColumnId, &'a DataTypeImpl),
+    /// Not stored at all, the block was written before the field was added.
+    Missing(&'a DataValue),
+}
+
+impl FieldColumns {
+    pub fn resolve(
+        &self,
+        data_type: &DataTypeImpl,
+        contains: impl Fn(ColumnId) -> bool,
+    ) -> ColumnSource {
+        if contains(self.column_id) {
+            return ColumnSource::Stored(self.column_id);
+        }
+        for (column_id, old_type) in &self.history {
+            if contains(*column_id) {
+                return if old_type == data_type {
+                    ColumnSource::Stored(*column_id)
+                } else {
+                    ColumnSource::Cast(*column_id, old_type)
+                };
+            }
+        }
+        ColumnSource::Missing(&self.missing_value)
+    }
+}
+
+/// Maps the fields of a table schema to stable column ids.
+///
+/// Column metas and statistics of blocks are keyed by column id, so that blocks written
+/// before a column is added, dropped, renamed or retyped can still be read under the new schema.
+/// The mapping is kept in the metadata of the table schema, tables that have never been
+/// altered have no mapping, and use the position of leaf columns as column id.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ColumnMapping {
+    pub next_column_id: ColumnId,
+    pub fields: BTreeMap<String, FieldColumns>,
+}
+
+impl ColumnMapping {
+    pub fn try_from_schema(schema: &DataSchema) -> Result<Option<Self>> {
+        match schema.meta().get(FUSE_SCHEMA_META_KEY_COLUMN_MAPPING) {
+            None => Ok(None),
+            Some(v) => Ok(Some(serde_json::from_str(v)?)),
+        }
+    }
+
+    pub fn from_schema(schema: &DataSchema) -> Result<Self> {
+        if let Some(mapping) = Self::try_from_schema(schema)? {
+            return Ok(mapping);
+        }
+
+        let mut next_column_id = 0;
+        let mut fields = BTreeMap::new();
+        for field in schema.fields() {
+            fields.insert(field.name().clone(), FieldColumns {
+                column_id: next_column_id,
+                missing_value: DataValue::Null,
+                history: vec![],
+            });
+            next_column_id += Self::num_leaves(field.data_type()) as ColumnId;
+        }
+        Ok(Self {
+            next_column_id,
+            fields,
+        })
+    }
+
+    pub fn to_schema_meta(&self, meta: &mut BTreeMap<String, String>) -> Result<()> {
+        meta.insert(
+            FUSE_SCHEMA_META_KEY_COLUMN_MAPPING.to_owned(),
+            serde_json::to_string(self)?,
+        );
+        Ok(())
+    }
+
+    pub fn field(&self, name: &str) -> Result<&FieldColumns> {
+        self.fields.get(name).ok_or_else(|| {
+            ErrorCode::Internal(format!("column {} not found in column mapping", name))
+        })
+    }
+
+    pub fn add_field(&mut self, field: &DataField, missing_value: DataValue) {
+        let column_id = self.alloc(field.data_type());
+        self.fields.insert(field.name().clone(), FieldColumns {
+            column_id,
+            missing_value,
+            history: vec![],
+        });
+    }
+
+    pub fn drop_field(&mut self, name: &str) {
+        self.fields.remove(name);
+    }
+
+    /// Renames or retypes a field. Column ids are never reused, the new version of the
+    /// field takes new ids, so that the statistics and bloom filters of blocks written with
+    /// the old version will not be applied to the new one by accident.
+    pub fn alter_field(&mut self, old_field: &DataField, new_field: &DataField) -> Result<()> {
+        let mut columns = self.field(old_field.name())?.clone();
+        columns
+            .history
+            .insert(0, (columns.column_id, old_field.data_type().clone()));
+        columns.column_id = self.alloc(new_field.data_type());
+        self.fields.remove(old_field.name());
+        self.fields.insert(new_field.name().clone(), columns);
+        Ok(())
+    }
+
+    /// Column ids of the leaf columns of the schema, in the order of leaf columns.
+    pub fn leaf_column_ids(&self, schema: &DataSchema) -> Result<Vec<ColumnId>> {
+        let mut column_ids = vec![];
+        for field in schema.fields() {
+            let column_id = self.field(field.name())?.column_id;
+            let num_leaves = Self::num_leaves(field.data_type()) as ColumnId;
+            column_ids.extend(column_id..column_id + num_leaves);
+        }
+        Ok(column_ids)
+    }
+
+    /// For each leaf column of the schema, all the column ids under which its data
+    /// could be stored with the current data type.
+    pub fn leaf_column_candidates(&self, schema: &DataSchema) -> Result<Vec<Vec<ColumnId>>> {
+        let mut candidates = vec![];
+        for field in schema.fields() {
+            let columns = self.field(field.name())?;
+            let column_ids = std::iter::once(columns.column_id)
+                .chain(
+                    columns
+                        .history
+                        .iter()
+                        .filter(|(_, old_type)| old_type == field.data_type())
+                        .map(|(column_id, _)| *column_id),
+                )
+                .collect::<Vec<_>>();
+            for offset in 0..Self::num_leaves(field.data_type()) as ColumnId {
+                candidates.push(column_ids.iter().map(|id| id + offset).collect());
+            }
+        }
+        Ok(candidates)
+    }
+
+    /// All the column ids, of current and previous versions, of the projected fields.
+    pub fn projection_column_ids(
+        &self,
+        column_leaves: &ColumnLeaves,
+        projection: &Projection,
+    ) -> Vec<ColumnId> {
+        let roots = match projection {
+            Projection::Columns(indices) => indices.clone(),
+            Projection::InnerColumns(path_indices) => {
+                path_indices.values().map(|path| path[0]).collect()
+            }
+        };
+
+        let mut column_ids = vec![];
+        for root in roots {
+            let column_leaf = &column_leaves.column_leaves[root];
+            if let Some(columns) = self.fields.get(&column_leaf.field.name) {
+                let num_leaves = column_leaf.leaf_ids.len() as ColumnId;
+                column_ids.extend(columns.column_id..columns.column_id + num_leaves);
+                for (column_id, old_type) in &columns.history {
+                    let num_leaves = Self::num_leaves(old_type) as ColumnId;
+                    column_ids.extend(*column_id..*column_id + num_leaves);
+                }
+            }
+        }
+        column_ids.sort_unstable();
+        column_ids.dedup();
+        column_ids
+    }
+
+    /// Re-keys the column metas or statistics of a block, from the position of leaf columns to column ids.
+    pub fn remap_to_column_ids<V>(
+        values: HashMap<ColumnId, V>,
+        column_ids: &[ColumnId],
+    ) -> HashMap<ColumnId, V> {
+        values
+            .into_iter()
+            .map(|(idx, v)| (column_ids.get(idx as usize).cloned().unwrap_or(idx), v))
+            .collect()
+    }
+
+    /// Re-keys the statistics of a block, from column ids to the position of leaf columns.
+    pub fn remap_to_leaf_positions<V: Clone>(
+        stats: &HashMap<ColumnId, V>,
+        candidates: &[Vec<ColumnId>],
+    ) -> HashMap<ColumnId, V> {
+        candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, column_ids)| {
+                column_ids
+                    .iter()
+                    .find_map(|column_id| stats.get(column_id))
+                    .map(|stat| (idx as ColumnId, stat.clone()))
+            })
+            .collect()
+    }
+
+    pub fn num_leaves(data_type: &DataTypeImpl) -> usize {
+        let schema = DataSchema::new(vec![DataField::new("_", data_type.clone())]);
+        ColumnLeaves::new_from_schema(&schema.to_arrow()).column_leaves[0]
+            .leaf_ids
+            .len()
+    }
+
+    fn alloc(&mut self, data_type: &DataTypeImpl) -> ColumnId {
+        let column_id = self.next_column_id;
+        self.next_column_id += Self::num_leaves(data_type) as ColumnId;
+        column_id
+    }
+}
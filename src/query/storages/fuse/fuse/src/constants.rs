@@ -16,14 +16,24 @@ pub const FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD: &str = "block_size_threshold
 pub const FUSE_OPT_KEY_BLOCK_PER_SEGMENT: &str = "block_per_segment";
 pub const FUSE_OPT_KEY_ROW_PER_BLOCK: &str = "row_per_block";
 pub const FUSE_OPT_KEY_ROW_AVG_DEPTH_THRESHOLD: &str = "row_avg_depth_threshold";
+pub const FUSE_OPT_KEY_DATA_RETENTION_PERIOD: &str = "data_retention_period";
+pub const FUSE_OPT_KEY_AUTO_COMPACTION: &str = "auto_compaction";
+// options of the inverted indexes are named as "inverted_index.<index_name>"
+pub const FUSE_OPT_KEY_INVERTED_INDEX_PREFIX: &str = "inverted_index.";
+// the tables cloned from a table are registered on it as "clone.<table_id>",
+// the files shared with them are never purged by it
+pub const FUSE_OPT_KEY_CLONE_PREFIX: &str = "clone.";
 
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 pub const FUSE_TBL_BLOCK_INDEX_PREFIX: &str = "_i";
 pub const FUSE_TBL_XOR_BLOOM_INDEX_PREFIX: &str = "_i_b_v2";
+pub const FUSE_TBL_INVERTED_INDEX_PREFIX: &str = "_i_inv";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
 pub const FUSE_TBL_LAST_SNAPSHOT_HINT: &str = "last_snapshot_location_hint";
 
+pub const FUSE_SCHEMA_META_KEY_COLUMN_MAPPING: &str = "column_mapping";
+
 pub const DEFAULT_BLOCK_PER_SEGMENT: usize = 1000;
 pub const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
 pub const DEFAULT_ROW_PER_BLOCK: usize = 1000 * 1000;
@@ -25,11 +25,14 @@ use common_catalog::plan::PartStatistics;
 use common_catalog::plan::Partitions;
 use common_catalog::plan::Projection;
 use common_catalog::plan::PushDownInfo;
+use common_catalog::table::AlterColumnAction;
 use common_catalog::table::AppendMode;
 use common_catalog::table::ColumnId;
 use common_catalog::table::ColumnStatistics;
 use common_catalog::table::ColumnStatisticsProvider;
 use common_catalog::table::CompactTarget;
+use common_catalog::table::HistogramBucket;
+use common_catalog::table::MergeIntoInfo;
 use common_catalog::table_context::TableContext;
 use common_catalog::table_mutator::TableMutator;
 use common_datablocks::BlockCompactThresholds;
@@ -37,6 +40,7 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_sharing::create_share_table_operator;
 use common_sql::ExpressionParser;
 use common_storage::init_operator;
@@ -46,6 +50,7 @@ use common_storage::StorageMetrics;
 use common_storage::StorageMetricsLayer;
 use common_storages_table_meta::meta::ClusterKey;
 use common_storages_table_meta::meta::ColumnStatistics as FuseColumnStatistics;
+use common_storages_table_meta::meta::DistributionOfColumns;
 use common_storages_table_meta::meta::Statistics as FuseStatistics;
 use common_storages_table_meta::meta::TableSnapshot;
 use common_storages_table_meta::meta::Versioned;
@@ -59,13 +64,16 @@ use uuid::Uuid;
 use crate::io::MetaReaders;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::AppendOperationLogEntry;
+use crate::operations::TableChanges;
 use crate::pipelines::Pipeline;
+use crate::ColumnMapping;
 use crate::NavigationPoint;
 use crate::Table;
 use crate::TableStatistics;
 use crate::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
 use crate::DEFAULT_ROW_PER_BLOCK;
 use crate::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
+use crate::FUSE_OPT_KEY_INVERTED_INDEX_PREFIX;
 use crate::FUSE_OPT_KEY_ROW_PER_BLOCK;
 use crate::FUSE_TBL_LAST_SNAPSHOT_HINT;
 
@@ -79,6 +87,9 @@ pub struct FuseTable {
 
     pub(crate) operator: Operator,
     pub(crate) data_metrics: Arc<StorageMetrics>,
+
+    /// If set, the table only reads the blocks of the rows changed since a snapshot.
+    pub(crate) changes: Option<Arc<TableChanges>>,
 }
 
 impl FuseTable {
@@ -119,6 +130,7 @@ impl FuseTable {
             read_only,
             operator,
             data_metrics,
+            changes: None,
         }))
     }
 
@@ -213,6 +225,27 @@ impl FuseTable {
     pub fn transient(&self) -> bool {
         self.table_info.meta.options.contains_key("TRANSIENT")
     }
+
+    /// Ids of the leaf columns, which the column metas and statistics of new blocks are keyed by.
+    pub fn leaf_column_ids(&self) -> Result<Vec<ColumnId>> {
+        let schema = self.table_info.schema();
+        ColumnMapping::from_schema(&schema)?.leaf_column_ids(&schema)
+    }
+
+    /// Names of the string columns, which are indexed by any of the inverted indexes.
+    pub fn inverted_index_columns(&self) -> Vec<String> {
+        let mut columns = vec![];
+        for (key, value) in &self.table_info.meta.options {
+            if key.starts_with(FUSE_OPT_KEY_INVERTED_INDEX_PREFIX) {
+                for column in value.split(',') {
+                    if !columns.iter().any(|c| c == column) {
+                        columns.push(column.to_string());
+                    }
+                }
+            }
+        }
+        columns
+    }
 }
 
 #[async_trait::async_trait]
@@ -341,6 +374,14 @@ impl Table for FuseTable {
         .await
     }
 
+    async fn alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        self.do_alter_column(ctx, action).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_read_partitions", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn read_partitions(
         &self,
@@ -380,6 +421,10 @@ impl Table for FuseTable {
         overwrite: bool,
     ) -> Result<()> {
         self.check_mutable()?;
+        if ctx.in_txn() {
+            return self.do_buffer_insertion(ctx, operations, overwrite).await;
+        }
+
         // only append operation supported currently
         let append_log_entries = operations
             .iter()
@@ -388,6 +433,34 @@ impl Table for FuseTable {
         self.do_commit(ctx, append_log_entries, overwrite).await
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_prepare_commit_insertion", skip(self, ctx, operations), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn prepare_commit_insertion(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        operations: &[DataBlock],
+        overwrite: bool,
+    ) -> Result<UpdateTableMetaReq> {
+        self.check_mutable()?;
+        self.do_prepare_commit_insertion(operations, overwrite)
+            .await
+    }
+
+    async fn abort_prepared_insertion(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        req: &UpdateTableMetaReq,
+    ) -> Result<()> {
+        self.do_abort_prepared_insertion(req).await
+    }
+
+    async fn abort_insertion(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        operations: Vec<DataBlock>,
+    ) -> Result<()> {
+        self.do_abort_insertion(operations).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_truncate", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn truncate(&self, ctx: Arc<dyn TableContext>, purge: bool) -> Result<()> {
         self.check_mutable()?;
@@ -411,18 +484,43 @@ impl Table for FuseTable {
     }
 
     async fn column_statistics_provider(&self) -> Result<Box<dyn ColumnStatisticsProvider>> {
+        // The summary of the snapshot does not describe the changed blocks only.
+        if self.changes.is_some() {
+            return Ok(Box::new(FuseColumnStatisticsProvider::default()));
+        }
+
         let provider = if let Some(snapshot) = self.read_table_snapshot().await? {
-            let stats = &snapshot.summary.col_stats;
-            FakedColumnStatisticsProvider {
-                column_stats: stats.clone(),
-                faked_ndv: snapshot.summary.row_count,
+            let schema = self.table_info.schema();
+            let summary = &snapshot.summary;
+            let (stats, distributions) = match ColumnMapping::try_from_schema(&schema)? {
+                None => (summary.col_stats.clone(), summary.col_distributions.clone()),
+                Some(mapping) => {
+                    let candidates = mapping.leaf_column_candidates(&schema)?;
+                    (
+                        ColumnMapping::remap_to_leaf_positions(&summary.col_stats, &candidates),
+                        ColumnMapping::remap_to_leaf_positions(
+                            &summary.col_distributions,
+                            &candidates,
+                        ),
+                    )
+                }
+            };
+            FuseColumnStatisticsProvider {
+                column_stats: stats,
+                column_distributions: distributions,
             }
         } else {
-            FakedColumnStatisticsProvider::default()
+            FuseColumnStatisticsProvider::default()
         };
         Ok(Box::new(provider))
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_analyze", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<dyn TableContext>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_analyze(&ctx).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_navigate_to", skip_all)]
     async fn navigate_to(&self, point: &NavigationPoint) -> Result<Arc<dyn Table>> {
         match point {
@@ -435,6 +533,46 @@ impl Table for FuseTable {
         }
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_flashback", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn flashback(&self, ctx: Arc<dyn TableContext>, point: &NavigationPoint) -> Result<()> {
+        self.check_mutable()?;
+        self.do_flashback(ctx, point).await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_clone_data_from", skip(self, ctx, source), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn clone_data_from(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        source: Arc<dyn Table>,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        let source = FuseTable::try_from_table(source.as_ref())?;
+        self.do_clone_data_from(ctx, source).await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_create_inverted_index", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn create_inverted_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        index_name: &str,
+        columns: &[String],
+        if_not_exists: bool,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_create_inverted_index(ctx, index_name, columns, if_not_exists)
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_changes", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn changes(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        since_location: &str,
+    ) -> Result<(Arc<dyn Table>, Arc<dyn Table>)> {
+        let (inserted, deleted) = self.do_changes(ctx, since_location).await?;
+        Ok((inserted, deleted))
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_delete", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn delete(
         &self,
@@ -445,6 +583,22 @@ impl Table for FuseTable {
         self.do_delete(ctx, projection, selection).await
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_update", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<Expression>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, Expression)>,
+    ) -> Result<()> {
+        self.do_update(ctx, filter, col_indices, update_list).await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_merge_into", skip(self, ctx, merge), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn merge_into(&self, ctx: Arc<dyn TableContext>, merge: MergeIntoInfo) -> Result<()> {
+        self.do_merge_into(ctx, merge).await
+    }
+
     async fn compact(
         &self,
         ctx: Arc<dyn TableContext>,
@@ -476,20 +630,33 @@ impl Table for FuseTable {
 }
 
 #[derive(Default)]
-struct FakedColumnStatisticsProvider {
+struct FuseColumnStatisticsProvider {
     column_stats: HashMap<ColumnId, FuseColumnStatistics>,
-    // faked value, just the row number
-    faked_ndv: u64,
+    // collected by appending and analyzing, may be absent after mutations
+    column_distributions: DistributionOfColumns,
 }
 
-impl ColumnStatisticsProvider for FakedColumnStatisticsProvider {
+impl ColumnStatisticsProvider for FuseColumnStatisticsProvider {
     fn column_statistics(&self, column_id: ColumnId) -> Option<ColumnStatistics> {
         let col_stats = &self.column_stats.get(&column_id);
+        let distribution = self.column_distributions.get(&column_id);
         col_stats.map(|s| ColumnStatistics {
             min: s.min.clone(),
             max: s.max.clone(),
             null_count: s.null_count,
-            number_of_distinct_values: self.faked_ndv,
+            number_of_distinct_values: distribution.map(|d| d.distinct_sketch.estimate()),
+            histogram: distribution.and_then(|d| {
+                d.histogram.as_ref().map(|h| {
+                    h.buckets
+                        .iter()
+                        .map(|b| HistogramBucket {
+                            upper_bound: b.upper_bound.clone(),
+                            num_values: b.num_values,
+                            num_distinct: b.num_distinct,
+                        })
+                        .collect()
+                })
+            }),
         })
     }
 }
@@ -26,6 +26,7 @@ use uuid::Uuid;
 use crate::constants::FUSE_TBL_BLOCK_PREFIX;
 use crate::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::FUSE_TBL_INVERTED_INDEX_PREFIX;
 use crate::FUSE_TBL_LAST_SNAPSHOT_HINT;
 use crate::FUSE_TBL_XOR_BLOOM_INDEX_PREFIX;
 
@@ -76,6 +77,20 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    /// The inverted index is kept in the same format as the bloom filter index.
+    pub fn block_inverted_index_location(&self, block_id: &Uuid) -> Location {
+        (
+            format!(
+                "{}/{}/{}_v{}.parquet",
+                &self.prefix,
+                FUSE_TBL_INVERTED_INDEX_PREFIX,
+                block_id.as_simple(),
+                BlockFilter::VERSION,
+            ),
+            BlockFilter::VERSION,
+        )
+    }
+
     pub fn gen_segment_info_location(&self) -> String {
         let segment_uuid = Uuid::new_v4().simple().to_string();
         format!(
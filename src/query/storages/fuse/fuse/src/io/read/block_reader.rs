@@ -20,7 +20,6 @@ use std::sync::Arc;
 use common_arrow::arrow::datatypes::Field;
 use common_arrow::arrow::io::parquet::read::column_iter_to_arrays;
 use common_arrow::arrow::io::parquet::read::ArrayIter;
-use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
 use common_arrow::arrow::io::parquet::write::to_parquet_schema;
 use common_arrow::parquet::compression::Compression as ParquetCompression;
 use common_arrow::parquet::metadata::ColumnDescriptor;
@@ -31,10 +30,12 @@ use common_arrow::parquet::read::PageReader;
 use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::Projection;
 use common_datablocks::DataBlock;
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::default_column_cast;
 use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Compression;
 use futures::AsyncReadExt;
 use futures::StreamExt;
@@ -44,10 +45,13 @@ use opendal::Operator;
 use tracing::debug_span;
 use tracing::Instrument;
 
-use crate::fuse_part::ColumnLeaf;
 use crate::fuse_part::ColumnLeaves;
 use crate::fuse_part::ColumnMeta;
 use crate::fuse_part::FusePartInfo;
+use crate::ColumnMapping;
+use crate::ColumnSource;
+use crate::FieldColumns;
+use crate::FuseTable;
 
 #[derive(Clone)]
 pub struct BlockReader {
@@ -56,6 +60,29 @@ pub struct BlockReader {
     projected_schema: DataSchemaRef,
     column_leaves: ColumnLeaves,
     parquet_schema_descriptor: SchemaDescriptor,
+    // the top level fields of the projected columns, if the table schema has been altered.
+    projected_fields: Option<Vec<ProjectedField>>,
+}
+
+#[derive(Clone)]
+struct ProjectedField {
+    field: DataField,
+    columns: FieldColumns,
+    // position of the first leaf column of the field
+    first_leaf: usize,
+    // is the projected column the top level field itself, or an inner column of it
+    is_root: bool,
+}
+
+enum ColumnReadPlan {
+    Read {
+        column_ids: Vec<usize>,
+        descriptors: Vec<ColumnDescriptor>,
+        field: Field,
+        nullable: bool,
+        cast_to: Option<DataTypeImpl>,
+    },
+    Constant(DataTypeImpl, DataValue),
 }
 
 impl BlockReader {
@@ -75,12 +102,40 @@ impl BlockReader {
         let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
         let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
 
+        let projected_fields = match ColumnMapping::try_from_schema(&schema)? {
+            None => None,
+            Some(mapping) => {
+                let paths = match &projection {
+                    Projection::Columns(indices) => indices.iter().map(|i| vec![*i]).collect(),
+                    Projection::InnerColumns(path_indices) => {
+                        path_indices.values().cloned().collect::<Vec<_>>()
+                    }
+                };
+                let projected_fields = paths
+                    .iter()
+                    .map(|path| {
+                        let field = schema.field(path[0]).clone();
+                        let columns = mapping.field(field.name())?.clone();
+                        let leaf_ids = &column_leaves.column_leaves[path[0]].leaf_ids;
+                        Ok(ProjectedField {
+                            field,
+                            columns,
+                            first_leaf: leaf_ids.first().cloned().unwrap_or_default(),
+                            is_root: path.len() == 1,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Some(projected_fields)
+            }
+        };
+
         Ok(Arc::new(BlockReader {
             operator,
             projection,
             projected_schema,
             parquet_schema_descriptor,
             column_leaves,
+            projected_fields,
         }))
     }
 
@@ -131,151 +186,83 @@ impl BlockReader {
         )?)
     }
 
-    // TODO refine these
-
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read_with_block_meta(&self, meta: &BlockMeta) -> Result<DataBlock> {
-        let (num_rows, columns_array_iter) = self.read_columns_with_block_meta(meta).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        let part = FuseTable::all_columns_part(meta);
+        self.read(part).await
     }
-    // TODO refine these
-
-    pub async fn read_columns_with_block_meta(
-        &self,
-        meta: &BlockMeta,
-    ) -> Result<(usize, Vec<ArrayIter<'static>>)> {
-        let num_rows = meta.row_count as usize;
-        let num_cols = self.projection.len();
-        let mut column_chunk_futs = Vec::with_capacity(num_cols);
-        let mut columns_meta: HashMap<usize, ColumnMeta> =
-            HashMap::with_capacity(meta.col_metas.len());
-
-        let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let indices = Self::build_projection_indices(&columns);
-        for index in indices {
-            let column_meta = &meta.col_metas[&(index as u32)];
-            let column_reader = self.operator.object(&meta.location.0);
-            let fut = async move {
-                let column_chunk = column_reader
-                    .range_read(column_meta.offset..column_meta.offset + column_meta.len)
-                    .await?;
-                Ok::<_, ErrorCode>((index, column_chunk))
-            }
-            .instrument(debug_span!("read_col_chunk"));
-            column_chunk_futs.push(fut);
-
-            columns_meta.insert(
-                index,
-                ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
-            );
-        }
-
-        let num_cols = columns_meta.len();
-        let chunks = futures::stream::iter(column_chunk_futs)
-            .buffered(std::cmp::min(10, num_cols))
-            .try_collect::<Vec<_>>()
-            .await?;
-
-        let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
-        let mut cnt_map = Self::build_projection_count_map(&columns);
-        let mut columns_array_iter = Vec::with_capacity(num_cols);
-        for column in &columns {
-            let field = column.field.clone();
-            let indices = &column.leaf_ids;
-            let mut column_metas = Vec::with_capacity(indices.len());
-            let mut column_chunks = Vec::with_capacity(indices.len());
-            let mut column_descriptors = Vec::with_capacity(indices.len());
-            for index in indices {
-                let column_meta = &columns_meta[index];
-                let cnt = cnt_map.get_mut(index).unwrap();
-                *cnt -= 1;
-                let column_chunk = if cnt > &mut 0 {
-                    chunk_map.get(index).unwrap().clone()
-                } else {
-                    chunk_map.remove(index).unwrap()
-                };
-                let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
-                column_metas.push(column_meta);
-                column_chunks.push(column_chunk);
-                column_descriptors.push(column_descriptor);
-            }
-            columns_array_iter.push(Self::to_array_iter(
-                column_metas,
-                column_chunks,
-                num_rows,
-                column_descriptors,
-                field,
-                &meta.compression(),
-            )?);
-        }
-
-        Ok((num_rows, columns_array_iter))
-    }
-
-    async fn read_columns(&self, part: PartInfoPtr) -> Result<(usize, Vec<ArrayIter<'static>>)> {
-        let part = FusePartInfo::from_part(&part)?;
-
-        // TODO: add prefetch column data.
-        let num_rows = part.nums_rows;
-        let num_cols = self.projection.len();
-        let mut column_chunk_futs = Vec::with_capacity(num_cols);
 
+    // Decide where to read each of the projected columns from, for the given part.
+    //
+    // The table schema might have been altered since the block was written, in which case
+    // columns may be stored under the ids of a previous version, or not stored at all.
+    fn read_plans(&self, part: &FusePartInfo) -> Result<Vec<ColumnReadPlan>> {
         let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let indices = Self::build_projection_indices(&columns);
-        for index in indices {
-            let column_meta = &part.columns_meta[&index];
-            let column_reader = self.operator.object(&part.location);
-            let fut = async move {
-                let (idx, column_chunk) =
-                    Self::read_column(column_reader, index, column_meta.offset, column_meta.length)
-                        .await?;
-                Ok::<_, ErrorCode>((idx, column_chunk))
-            }
-            .instrument(debug_span!("read_col_chunk"));
-            column_chunk_futs.push(fut);
-        }
-
-        let num_cols = column_chunk_futs.len();
-        let chunks = futures::stream::iter(column_chunk_futs)
-            .buffered(std::cmp::min(10, num_cols))
-            .try_collect::<Vec<_>>()
-            .await?;
-
-        let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
-        let mut cnt_map = Self::build_projection_count_map(&columns);
-        let mut columns_array_iter = Vec::with_capacity(num_cols);
-        for column in &columns {
-            let field = column.field.clone();
-            let indices = &column.leaf_ids;
-            let mut column_metas = Vec::with_capacity(indices.len());
-            let mut column_chunks = Vec::with_capacity(indices.len());
-            let mut column_descriptors = Vec::with_capacity(indices.len());
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
-                let cnt = cnt_map.get_mut(index).unwrap();
-                *cnt -= 1;
-                let column_chunk = if cnt > &mut 0 {
-                    chunk_map.get(index).unwrap().clone()
-                } else {
-                    chunk_map.remove(index).unwrap()
-                };
-                let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
-                column_metas.push(column_meta);
-                column_chunks.push(column_chunk);
-                column_descriptors.push(column_descriptor);
-            }
-            columns_array_iter.push(Self::to_array_iter(
-                column_metas,
-                column_chunks,
-                num_rows,
-                column_descriptors,
-                field,
-                &part.compression,
-            )?);
+        let descriptors = self.parquet_schema_descriptor.columns();
+        let mut plans = Vec::with_capacity(columns.len());
+        for (i, column) in columns.iter().enumerate() {
+            let projected_field = self.projected_schema.field(i);
+            let stored = |column_id: usize, first_leaf: usize| ColumnReadPlan::Read {
+                column_ids: column
+                    .leaf_ids
+                    .iter()
+                    .map(|index| column_id + index - first_leaf)
+                    .collect(),
+                descriptors: column
+                    .leaf_ids
+                    .iter()
+                    .map(|index| descriptors[*index].clone())
+                    .collect(),
+                field: column.field.clone(),
+                nullable: projected_field.is_nullable(),
+                cast_to: None,
+            };
+
+            let plan = match &self.projected_fields {
+                None => stored(0, 0),
+                Some(projected_fields) => {
+                    let projected = &projected_fields[i];
+                    let contains = |id: ColumnId| part.columns_meta.contains_key(&(id as usize));
+                    match projected
+                        .columns
+                        .resolve(projected.field.data_type(), contains)
+                    {
+                        ColumnSource::Stored(column_id) => {
+                            stored(column_id as usize, projected.first_leaf)
+                        }
+                        ColumnSource::Cast(column_id, old_type) => {
+                            let old_field =
+                                DataField::new(projected.field.name(), old_type.clone());
+                            let old_schema = DataSchema::new(vec![old_field.clone()]);
+                            let old_descriptor = to_parquet_schema(&old_schema.to_arrow())?;
+                            let num_leaves = old_descriptor.columns().len();
+                            ColumnReadPlan::Read {
+                                column_ids: (0..num_leaves)
+                                    .map(|i| column_id as usize + i)
+                                    .collect(),
+                                descriptors: old_descriptor.columns().to_vec(),
+                                field: old_field.to_arrow(),
+                                nullable: old_field.is_nullable(),
+                                cast_to: Some(projected.field.data_type().clone()),
+                            }
+                        }
+                        ColumnSource::Missing(value) => {
+                            let data_type = projected_field.data_type().clone();
+                            let value = if projected.is_root {
+                                value.clone()
+                            } else if projected_field.is_nullable() {
+                                DataValue::Null
+                            } else {
+                                data_type.default_value()
+                            };
+                            ColumnReadPlan::Constant(data_type, value)
+                        }
+                    }
+                }
+            };
+            plans.push(plan);
         }
-
-        Ok((num_rows, columns_array_iter))
+        Ok(plans)
     }
 
     pub fn deserialize(
@@ -284,51 +271,73 @@ impl BlockReader {
         chunks: Vec<(usize, Vec<u8>)>,
     ) -> Result<DataBlock> {
         let part = FusePartInfo::from_part(&part)?;
+        let plans = self.read_plans(part)?;
         let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
-        let mut columns_array_iter = Vec::with_capacity(self.projection.len());
+        let mut cnt_map = Self::build_projection_count_map(&plans);
 
         let num_rows = part.nums_rows;
-        let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let mut cnt_map = Self::build_projection_count_map(&columns);
-        for column in &columns {
-            let field = column.field.clone();
-            let indices = &column.leaf_ids;
-            let mut column_metas = Vec::with_capacity(indices.len());
-            let mut column_chunks = Vec::with_capacity(indices.len());
-            let mut column_descriptors = Vec::with_capacity(indices.len());
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
-                let cnt = cnt_map.get_mut(index).unwrap();
-                *cnt -= 1;
-                let column_chunk = if cnt > &mut 0 {
-                    chunk_map.get(index).unwrap().clone()
-                } else {
-                    chunk_map.remove(index).unwrap()
-                };
-                let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
-                column_metas.push(column_meta);
-                column_chunks.push(column_chunk);
-                column_descriptors.push(column_descriptor);
-            }
-            columns_array_iter.push(Self::to_array_iter(
-                column_metas,
-                column_chunks,
-                num_rows,
-                column_descriptors,
-                field,
-                &part.compression,
-            )?);
+        let mut columns = Vec::with_capacity(plans.len());
+        for plan in plans {
+            let column = match plan {
+                ColumnReadPlan::Read {
+                    column_ids,
+                    descriptors,
+                    field,
+                    nullable,
+                    cast_to,
+                } => {
+                    let mut column_metas = Vec::with_capacity(column_ids.len());
+                    let mut column_chunks = Vec::with_capacity(column_ids.len());
+                    for column_id in &column_ids {
+                        let column_meta = &part.columns_meta[column_id];
+                        let cnt = cnt_map.get_mut(column_id).unwrap();
+                        *cnt -= 1;
+                        let column_chunk = if cnt > &mut 0 {
+                            chunk_map.get(column_id).unwrap().clone()
+                        } else {
+                            chunk_map.remove(column_id).unwrap()
+                        };
+                        column_metas.push(column_meta);
+                        column_chunks.push(column_chunk);
+                    }
+                    let mut array_iter = Self::to_array_iter(
+                        column_metas,
+                        column_chunks,
+                        num_rows,
+                        descriptors.iter().collect(),
+                        field,
+                        &part.compression,
+                    )?;
+                    let array = match array_iter.next() {
+                        None => Err(ErrorCode::Internal(
+                            "deserializer from row group: fail to get a chunk",
+                        )),
+                        Some(Err(cause)) => Err(ErrorCode::from(cause)),
+                        Some(Ok(array)) => Ok(array),
+                    }?;
+                    let column = match nullable {
+                        true => array.into_nullable_column(),
+                        false => array.into_column(),
+                    };
+                    match cast_to {
+                        None => column,
+                        Some(data_type) => default_column_cast(&column, &data_type)?,
+                    }
+                }
+                ColumnReadPlan::Constant(data_type, value) => data_type
+                    .create_constant_column(&value, num_rows)?
+                    .convert_full_column(),
+            };
+            columns.push(column);
         }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-
-        self.try_next_block(&mut deserializer)
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
     }
 
     pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<(usize, Vec<u8>)>> {
         let part = FusePartInfo::from_part(&part)?;
-        let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let indices = Self::build_projection_indices(&columns);
+        let plans = self.read_plans(part)?;
+        let indices = Self::build_projection_indices(&plans);
         let mut join_handlers = Vec::with_capacity(indices.len());
 
         for index in indices {
@@ -351,8 +360,8 @@ impl BlockReader {
     pub fn sync_read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<(usize, Vec<u8>)>> {
         let part = FusePartInfo::from_part(&part)?;
 
-        let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let indices = Self::build_projection_indices(&columns);
+        let plans = self.read_plans(part)?;
+        let indices = Self::build_projection_indices(&plans);
         let mut results = Vec::with_capacity(indices.len());
 
         for index in indices {
@@ -396,19 +405,35 @@ impl BlockReader {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
-        let (num_rows, columns_array_iter) = self.read_columns(part).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        let chunks = self.read_columns_data_buffered(&part).await?;
+        self.deserialize(part, chunks)
     }
 
-    fn try_next_block(&self, deserializer: &mut RowGroupDeserializer) -> Result<DataBlock> {
-        match deserializer.next() {
-            None => Err(ErrorCode::Internal(
-                "deserializer from row group: fail to get a chunk",
-            )),
-            Some(Err(cause)) => Err(ErrorCode::from(cause)),
-            Some(Ok(chunk)) => DataBlock::from_chunk(&self.projected_schema, &chunk),
+    // Read the column chunks of the part concurrently, at most 10 chunks at a time.
+    async fn read_columns_data_buffered(
+        &self,
+        part: &PartInfoPtr,
+    ) -> Result<Vec<(usize, Vec<u8>)>> {
+        let part = FusePartInfo::from_part(part)?;
+        let plans = self.read_plans(part)?;
+        let indices = Self::build_projection_indices(&plans);
+        let mut column_chunk_futs = Vec::with_capacity(indices.len());
+
+        for index in indices {
+            let column_meta = &part.columns_meta[&index];
+            let column_reader = self.operator.object(&part.location);
+            let fut =
+                Self::read_column(column_reader, index, column_meta.offset, column_meta.length)
+                    .instrument(debug_span!("read_col_chunk"));
+            column_chunk_futs.push(fut);
         }
+
+        // all the projected columns may be missing in the block, which are filled by constants.
+        let num_cols = column_chunk_futs.len();
+        futures::stream::iter(column_chunk_futs)
+            .buffered(std::cmp::min(10, num_cols.max(1)))
+            .try_collect::<Vec<_>>()
+            .await
     }
 
     fn to_parquet_compression(meta_compression: &Compression) -> Result<ParquetCompression> {
@@ -436,27 +461,31 @@ impl BlockReader {
         }
     }
 
-    // Build non duplicate leaf_ids to avoid repeated read column from parquet
-    fn build_projection_indices(columns: &Vec<&ColumnLeaf>) -> HashSet<usize> {
-        let mut indices = HashSet::with_capacity(columns.len());
-        for column in columns {
-            for index in &column.leaf_ids {
-                indices.insert(*index);
+    // Build non duplicate column ids to avoid repeated read column from parquet
+    fn build_projection_indices(plans: &[ColumnReadPlan]) -> HashSet<usize> {
+        let mut indices = HashSet::with_capacity(plans.len());
+        for plan in plans {
+            if let ColumnReadPlan::Read { column_ids, .. } = plan {
+                for index in column_ids {
+                    indices.insert(*index);
+                }
             }
         }
         indices
     }
 
-    // Build a map to record the count number of each leaf_id
-    fn build_projection_count_map(columns: &Vec<&ColumnLeaf>) -> HashMap<usize, usize> {
-        let mut cnt_map = HashMap::with_capacity(columns.len());
-        for column in columns {
-            for index in &column.leaf_ids {
-                if let Entry::Vacant(e) = cnt_map.entry(*index) {
-                    e.insert(1);
-                } else {
-                    let cnt = cnt_map.get_mut(index).unwrap();
-                    *cnt += 1;
+    // Build a map to record the count number of each column id
+    fn build_projection_count_map(plans: &[ColumnReadPlan]) -> HashMap<usize, usize> {
+        let mut cnt_map = HashMap::with_capacity(plans.len());
+        for plan in plans {
+            if let ColumnReadPlan::Read { column_ids, .. } = plan {
+                for index in column_ids {
+                    if let Entry::Vacant(e) = cnt_map.entry(*index) {
+                        e.insert(1);
+                    } else {
+                        let cnt = cnt_map.get_mut(index).unwrap();
+                        *cnt += 1;
+                    }
                 }
             }
         }
@@ -24,15 +24,18 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_storages_table_meta::meta::BlockMeta;
 use common_storages_table_meta::meta::ClusterStatistics;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Location;
 use opendal::Operator;
 use tracing::warn;
 use uuid::Uuid;
 
 use crate::index::BlockFilter;
+use crate::index::BlockInvertedIndex;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::util;
 use crate::statistics::gen_columns_statistics;
+use crate::ColumnMapping;
 
 const DEFAULT_BLOOM_INDEX_WRITE_BUFFER_SIZE: usize = 300 * 1024;
 const DEFAULT_BLOCK_WRITE_BUFFER_SIZE: usize = 100 * 1024 * 1024;
@@ -40,6 +43,11 @@ const DEFAULT_BLOCK_WRITE_BUFFER_SIZE: usize = 100 * 1024 * 1024;
 pub struct BlockWriter<'a> {
     location_generator: &'a TableMetaLocationGenerator,
     data_accessor: &'a Operator,
+    // ids of the leaf columns, which column metas and statistics are keyed by.
+    // leave it empty to key them by the position of leaf columns.
+    column_ids: &'a [ColumnId],
+    // string columns, which the inverted indexes are built for.
+    inverted_index_columns: &'a [String],
 }
 
 impl<'a> BlockWriter<'a> {
@@ -50,9 +58,21 @@ impl<'a> BlockWriter<'a> {
         Self {
             location_generator,
             data_accessor,
+            column_ids: &[],
+            inverted_index_columns: &[],
         }
     }
 
+    pub fn with_column_ids(mut self, column_ids: &'a [ColumnId]) -> Self {
+        self.column_ids = column_ids;
+        self
+    }
+
+    pub fn with_inverted_index_columns(mut self, inverted_index_columns: &'a [String]) -> Self {
+        self.inverted_index_columns = inverted_index_columns;
+        self
+    }
+
     pub async fn write_with_location(
         &self,
         block: DataBlock,
@@ -63,13 +83,20 @@ impl<'a> BlockWriter<'a> {
         let data_accessor = &self.data_accessor;
         let row_count = block.num_rows() as u64;
         let block_size = block.memory_size() as u64;
-        let col_stats = gen_columns_statistics(&block)?;
+        let col_stats =
+            ColumnMapping::remap_to_column_ids(gen_columns_statistics(&block)?, self.column_ids);
         let (bloom_filter_index_size, bloom_filter_index_location) = self
             .build_block_index(data_accessor, &block, block_id)
             .await?;
+        let inverted_index = self
+            .build_inverted_index(data_accessor, &block, block_id)
+            .await?;
         let (file_size, file_meta_data) = write_block(block, data_accessor, &location.0).await?;
-        let col_metas = util::column_metas(&file_meta_data)?;
-        let block_meta = BlockMeta::new(
+        let col_metas = ColumnMapping::remap_to_column_ids(
+            util::column_metas(&file_meta_data)?,
+            self.column_ids,
+        );
+        let mut block_meta = BlockMeta::new(
             row_count,
             block_size,
             file_size,
@@ -80,6 +107,10 @@ impl<'a> BlockWriter<'a> {
             Some(bloom_filter_index_location),
             bloom_filter_index_size,
         );
+        if let Some((inverted_index_size, inverted_index_location)) = inverted_index {
+            block_meta.inverted_index_location = Some(inverted_index_location);
+            block_meta.inverted_index_size = inverted_index_size;
+        }
         Ok(block_meta)
     }
 
@@ -115,6 +146,31 @@ impl<'a> BlockWriter<'a> {
         write_data(&data, data_accessor, &location.0).await?;
         Ok((size, location))
     }
+
+    pub async fn build_inverted_index(
+        &self,
+        data_accessor: &Operator,
+        block: &DataBlock,
+        block_id: Uuid,
+    ) -> Result<Option<(u64, Location)>> {
+        let inverted_index =
+            match BlockInvertedIndex::try_create(block, self.inverted_index_columns)? {
+                Some(inverted_index) => inverted_index,
+                None => return Ok(None),
+            };
+        let location = self
+            .location_generator
+            .block_inverted_index_location(&block_id);
+        let mut data = Vec::with_capacity(DEFAULT_BLOOM_INDEX_WRITE_BUFFER_SIZE);
+        let (size, _) = serialize_data_blocks_with_compression(
+            vec![inverted_index.filter_block],
+            &inverted_index.filter_schema,
+            &mut data,
+            CompressionOptions::Uncompressed,
+        )?;
+        write_data(&data, data_accessor, &location.0).await?;
+        Ok(Some((size, location)))
+    }
 }
 
 pub async fn write_block(
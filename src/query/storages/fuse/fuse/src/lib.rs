@@ -17,6 +17,7 @@
 #![deny(unused_crate_dependencies)]
 #![feature(once_cell)]
 
+mod column_mapping;
 mod constants;
 mod fuse_lazy_part;
 mod fuse_part;
@@ -34,6 +35,9 @@ use common_catalog::table::Table;
 use common_catalog::table::TableStatistics;
 pub use common_catalog::table_context::TableContext;
 use common_catalog::table_mutator::TableMutator;
+pub use column_mapping::ColumnMapping;
+pub use column_mapping::ColumnSource;
+pub use column_mapping::FieldColumns;
 pub use constants::*;
 pub use fuse_part::ColumnLeaf;
 pub use fuse_part::ColumnLeaves;
@@ -0,0 +1,215 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::table::AlterColumnAction;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::TypeID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_table_meta::meta::Statistics;
+use common_storages_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use super::update::collect_column_names;
+use crate::ColumnMapping;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Alters a column of the table, by committing a new snapshot of the new schema.
+    ///
+    /// Blocks are not rewritten, the column mapping of the new schema tells the readers
+    /// how to read the blocks written under the previous schemas.
+    pub async fn do_alter_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        let schema = self.table_info.schema();
+        let mut mapping = ColumnMapping::from_schema(&schema)?;
+        let mut fields = schema.fields().clone();
+        let mut new_table_meta = self.table_info.meta.clone();
+        // tables created by old versions may have no field comments
+        let has_comments = new_table_meta.field_comments.len() == fields.len();
+
+        match action {
+            AlterColumnAction::Add {
+                field,
+                comment,
+                missing_value,
+            } => {
+                self.check_column_not_exists(&schema, field.name())?;
+                mapping.add_field(&field, missing_value);
+                fields.push(field);
+                if has_comments {
+                    new_table_meta.field_comments.push(comment);
+                }
+            }
+            AlterColumnAction::Drop { name } => {
+                let idx = self.column_index(&schema, &name)?;
+                if fields.len() == 1 {
+                    return Err(ErrorCode::TableColumnAlterInvalid(format!(
+                        "can not drop column {}, the table must have at least one column",
+                        name
+                    )));
+                }
+                self.check_not_cluster_key(&name)?;
+                mapping.drop_field(&name);
+                fields.remove(idx);
+                if has_comments {
+                    new_table_meta.field_comments.remove(idx);
+                }
+            }
+            AlterColumnAction::Rename { name, new_name } => {
+                let idx = self.column_index(&schema, &name)?;
+                self.check_column_not_exists(&schema, &new_name)?;
+                self.check_not_cluster_key(&name)?;
+                let old_field = &fields[idx];
+                let new_field = DataField::new(&new_name, old_field.data_type().clone())
+                    .with_default_expr(old_field.default_expr().cloned());
+                mapping.alter_field(old_field, &new_field)?;
+                fields[idx] = new_field;
+            }
+            AlterColumnAction::Modify { field } => {
+                let idx = self.column_index(&schema, field.name())?;
+                let old_field = &fields[idx];
+                if old_field.data_type() == field.data_type() {
+                    return Ok(());
+                }
+                if !is_widening(old_field.data_type(), field.data_type()) {
+                    return Err(ErrorCode::TableColumnAlterInvalid(format!(
+                        "can not modify column {} from {} to {}, only widening the data type is supported",
+                        field.name(),
+                        old_field.data_type().name(),
+                        field.data_type().name()
+                    )));
+                }
+                self.check_not_cluster_key(field.name())?;
+                mapping.alter_field(old_field, &field)?;
+                fields[idx] = field;
+            }
+        }
+
+        let mut metadata = schema.meta().clone();
+        mapping.to_schema_meta(&mut metadata)?;
+        let new_schema = DataSchema::new_from(fields, metadata);
+        new_table_meta.schema = Arc::new(new_schema.clone());
+
+        let prev = self.read_table_snapshot().await?;
+        let prev_version = self.snapshot_format_version().await?;
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+        let (summary, segments) = if let Some(v) = prev {
+            (v.summary.clone(), v.segments.clone())
+        } else {
+            (Statistics::default(), vec![])
+        };
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            new_schema,
+            summary,
+            segments,
+            self.cluster_key_meta.clone(),
+        );
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta = new_table_meta;
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            &self.operator,
+        )
+        .await
+    }
+
+    fn column_index(&self, schema: &DataSchema, name: &str) -> Result<usize> {
+        schema
+            .column_with_name(name)
+            .map(|(idx, _)| idx)
+            .ok_or_else(|| {
+                ErrorCode::UnknownColumn(format!(
+                    "column {} does not exist in table {}",
+                    name,
+                    self.name()
+                ))
+            })
+    }
+
+    fn check_column_not_exists(&self, schema: &DataSchema, name: &str) -> Result<()> {
+        if schema.has_field(name) {
+            return Err(ErrorCode::TableColumnAlreadyExists(format!(
+                "column {} already exists in table {}",
+                name,
+                self.name()
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_not_cluster_key(&self, name: &str) -> Result<()> {
+        let mut names = HashSet::new();
+        for expr in self.cluster_keys() {
+            collect_column_names(&expr, &mut names);
+        }
+        if names.contains(name) {
+            return Err(ErrorCode::TableColumnAlterInvalid(format!(
+                "can not alter column {}, which is used by the cluster key of table {}",
+                name,
+                self.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Values of the old type can always be cast to the new type without loss.
+fn is_widening(from: &DataTypeImpl, to: &DataTypeImpl) -> bool {
+    if from.is_nullable() && !to.is_nullable() {
+        return false;
+    }
+
+    let (from, to) = (remove_nullable(from), remove_nullable(to));
+    if from == to {
+        return true;
+    }
+
+    let (from, to) = (from.data_type_id(), to.data_type_id());
+    let (from_size, to_size) = match (from.numeric_byte_size(), to.numeric_byte_size()) {
+        (Ok(from_size), Ok(to_size)) => (from_size, to_size),
+        _ => return false,
+    };
+    if from.is_signed_integer() {
+        (to.is_signed_integer() && to_size > from_size) || (to == TypeID::Float64 && from_size <= 4)
+    } else if from.is_unsigned_integer() {
+        (to.is_unsigned_integer() && to_size > from_size)
+            || (to.is_signed_integer() && to_size > from_size)
+            || (to == TypeID::Float64 && from_size <= 4)
+    } else {
+        from == TypeID::Float32 && to == TypeID::Float64
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Projection;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_storages_table_meta::caches::CacheManager;
+use common_storages_table_meta::meta::SegmentInfo;
+use common_storages_table_meta::meta::Statistics;
+
+use crate::io::SegmentWriter;
+use crate::io::SegmentsIO;
+use crate::operations::mutation::block_filter::all_the_columns_ids;
+use crate::operations::mutation::AbortOperation;
+use crate::statistics::gen_columns_distributions;
+use crate::statistics::reduce_block_distributions;
+use crate::statistics::reducers::merge_statistics_mut;
+use crate::ColumnMapping;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Recomputes the distributions of the columns (distinct sketches and histograms) from the
+    /// data of all the blocks, and commits them by the summaries of new segments and snapshot.
+    ///
+    /// The blocks are kept untouched, the new segments refer to the same blocks as the old ones.
+    pub async fn do_analyze(&self, ctx: &Arc<dyn TableContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) if !snapshot.segments.is_empty() => snapshot,
+            // Nothing to analyze.
+            _ => return Ok(()),
+        };
+
+        let segments_io = SegmentsIO::create(ctx.clone(), self.operator.clone());
+        let segments = segments_io
+            .read_segments(&snapshot.segments)
+            .await?
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        let reader = self.create_block_reader(Projection::Columns(all_the_columns_ids(self)))?;
+        let column_ids = self.leaf_column_ids()?;
+        let segment_info_cache = CacheManager::instance().get_table_segment_cache();
+        let segment_writer = SegmentWriter::new(
+            &self.operator,
+            &self.meta_location_generator,
+            &segment_info_cache,
+        );
+
+        let mut abort_operation = AbortOperation::default();
+        let mut new_segments = Vec::with_capacity(segments.len());
+        let mut new_summary = Statistics::default();
+        for segment in segments {
+            let mut distributions = Vec::with_capacity(segment.blocks.len());
+            for block_meta in &segment.blocks {
+                let block = reader.read_with_block_meta(block_meta).await?;
+                distributions.push(ColumnMapping::remap_to_column_ids(
+                    gen_columns_distributions(&block)?,
+                    &column_ids,
+                ));
+            }
+
+            let mut summary = segment.summary.clone();
+            summary.col_distributions = reduce_block_distributions(&distributions);
+            merge_statistics_mut(&mut new_summary, &summary)?;
+
+            let new_segment = SegmentInfo::new(segment.blocks.clone(), summary);
+            let location = segment_writer.write_segment(new_segment).await?;
+            abort_operation = abort_operation.add_segment(location.0.clone());
+            new_segments.push(location);
+        }
+
+        self.commit_mutation(ctx, snapshot, new_segments, new_summary, abort_operation)
+            .await
+    }
+}
@@ -99,6 +99,8 @@ impl FuseTable {
             })?;
         }
 
+        let column_ids = self.leaf_column_ids()?;
+        let inverted_index_columns = self.inverted_index_columns();
         if need_output {
             pipeline.add_transform(|transform_input_port, transform_output_port| {
                 FuseTableSink::try_create(
@@ -108,6 +110,8 @@ impl FuseTable {
                     self.operator.clone(),
                     self.meta_location_generator().clone(),
                     cluster_stats_gen.clone(),
+                    column_ids.clone(),
+                    inverted_index_columns.clone(),
                     Some(transform_output_port),
                 )
             })?;
@@ -120,6 +124,8 @@ impl FuseTable {
                     self.operator.clone(),
                     self.meta_location_generator().clone(),
                     cluster_stats_gen.clone(),
+                    column_ids.clone(),
+                    inverted_index_columns.clone(),
                     None,
                 )
             })?;
@@ -0,0 +1,175 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableStatistics;
+use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::Location;
+
+use crate::io::MetaReaders;
+use crate::io::SegmentsIO;
+use crate::io::TableMetaLocationGenerator;
+use crate::pruning::BlockPruner;
+use crate::FuseTable;
+
+/// The blocks a table of changes is restricted to.
+pub struct TableChanges {
+    /// The segments containing the changed blocks.
+    segments: Vec<Location>,
+    /// The locations of the changed blocks.
+    blocks: HashSet<String>,
+}
+
+impl FuseTable {
+    /// Returns the tables of the rows inserted and of the rows deleted since the snapshot at
+    /// `since_location` ("" for an empty table), up to the current snapshot.
+    ///
+    /// Changes are tracked by blocks: the rows of a block rewritten by a mutation (DELETE,
+    /// UPDATE, compaction, ...) are both deleted and inserted, the readers of the changes
+    /// cancel out the rows left unchanged.
+    pub async fn do_changes(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        since_location: &str,
+    ) -> Result<(Arc<FuseTable>, Arc<FuseTable>)> {
+        let current_segments = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot.segments.clone(),
+            None => vec![],
+        };
+        let since_segments = if since_location.is_empty() {
+            vec![]
+        } else {
+            let reader = MetaReaders::table_snapshot_reader(self.get_operator());
+            let ver = TableMetaLocationGenerator::snapshot_version(since_location);
+            let snapshot = reader.read(since_location, None, ver).await.map_err(|e| {
+                ErrorCode::TableHistoricalDataNotFound(format!(
+                    "Snapshot {} of table {} is not available, it may have been purged: {}",
+                    since_location,
+                    self.table_info.name,
+                    e.message()
+                ))
+            })?;
+            snapshot.segments.clone()
+        };
+
+        let added_segments = Self::segments_difference(&current_segments, &since_segments);
+        let removed_segments = Self::segments_difference(&since_segments, &current_segments);
+
+        // A block may move from a removed segment to an added one, e.g. by segment compaction.
+        let segments_io = SegmentsIO::create(ctx, self.operator.clone());
+        let mut added_blocks = Self::segments_blocks(&segments_io, &added_segments).await?;
+        let mut removed_blocks = Self::segments_blocks(&segments_io, &removed_segments).await?;
+        let moved_blocks = added_blocks
+            .keys()
+            .filter(|location| removed_blocks.contains_key(*location))
+            .cloned()
+            .collect::<Vec<_>>();
+        for location in &moved_blocks {
+            added_blocks.remove(location);
+            removed_blocks.remove(location);
+        }
+
+        let inserted = self.with_changes(added_segments, added_blocks)?;
+        let deleted = self.with_changes(removed_segments, removed_blocks)?;
+        Ok((inserted, deleted))
+    }
+
+    pub(crate) async fn read_changes_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+        changes: &TableChanges,
+    ) -> Result<(PartStatistics, Partitions)> {
+        // The limit applies to the changed blocks, not to all the blocks of the segments.
+        let prune_push_downs = push_downs.clone().map(|push_downs| PushDownInfo {
+            limit: None,
+            order_by: vec![],
+            ..push_downs
+        });
+        let block_metas = BlockPruner::prune(
+            &ctx,
+            self.operator.clone(),
+            self.table_info.schema(),
+            &self.inverted_index_columns(),
+            &prune_push_downs,
+            changes.segments.clone(),
+        )
+        .await?
+        .into_iter()
+        .map(|(_, v)| v)
+        .filter(|block_meta| changes.blocks.contains(&block_meta.location.0))
+        .collect::<Vec<_>>();
+
+        self.read_partitions_with_metas(
+            ctx,
+            self.table_info.schema(),
+            push_downs,
+            block_metas,
+            changes.blocks.len(),
+        )
+    }
+
+    fn with_changes(
+        &self,
+        segments: Vec<Location>,
+        blocks: HashMap<String, Arc<BlockMeta>>,
+    ) -> Result<Arc<FuseTable>> {
+        let mut table_info = self.table_info.clone();
+        table_info.meta.statistics = TableStatistics {
+            number_of_rows: blocks.values().map(|b| b.row_count).sum(),
+            data_bytes: blocks.values().map(|b| b.block_size).sum(),
+            compressed_data_bytes: blocks.values().map(|b| b.file_size).sum(),
+            index_data_bytes: 0,
+        };
+
+        let read_only = true;
+        let mut fuse_tbl = FuseTable::do_create(table_info, read_only)?;
+        fuse_tbl.changes = Some(Arc::new(TableChanges {
+            segments,
+            blocks: blocks.into_keys().collect(),
+        }));
+        Ok(fuse_tbl.into())
+    }
+
+    fn segments_difference(segments: &[Location], others: &[Location]) -> Vec<Location> {
+        let others = others.iter().collect::<HashSet<_>>();
+        segments
+            .iter()
+            .filter(|segment| !others.contains(segment))
+            .cloned()
+            .collect()
+    }
+
+    async fn segments_blocks(
+        segments_io: &SegmentsIO,
+        segments: &[Location],
+    ) -> Result<HashMap<String, Arc<BlockMeta>>> {
+        let mut blocks = HashMap::new();
+        for segment in segments_io.read_segments(segments).await? {
+            for block_meta in &segment?.blocks {
+                blocks.insert(block_meta.location.0.clone(), block_meta.clone());
+            }
+        }
+        Ok(blocks)
+    }
+}
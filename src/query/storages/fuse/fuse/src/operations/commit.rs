@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::ops::Range;
 use std::sync::Arc;
@@ -23,16 +24,20 @@ use backoff::ExponentialBackoffBuilder;
 use backon::Retryable;
 use common_base::base::ProgressValues;
 use common_cache::Cache;
+use common_catalog::catalog::Catalog;
 use common_catalog::table::Table;
 use common_catalog::table::TableExt;
 use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableStatistics;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
 use common_storages_table_meta::caches::CacheManager;
 use common_storages_table_meta::meta::ClusterKey;
 use common_storages_table_meta::meta::Location;
@@ -64,7 +69,7 @@ use crate::FuseTable;
 const OCC_DEFAULT_BACKOFF_INIT_DELAY_MS: Duration = Duration::from_millis(5);
 const OCC_DEFAULT_BACKOFF_MAX_DELAY_MS: Duration = Duration::from_millis(20 * 1000);
 const OCC_DEFAULT_BACKOFF_MAX_ELAPSED_MS: Duration = Duration::from_millis(120 * 1000);
-const MAX_RETRIES: u64 = 10;
+pub(crate) const MAX_RETRIES: u64 = 10;
 
 impl FuseTable {
     pub async fn do_commit(
@@ -172,10 +177,6 @@ impl FuseTable {
         operation_log: &'a TableOperationLog,
         overwrite: bool,
     ) -> Result<()> {
-        let prev = self.read_table_snapshot().await?;
-        let prev_version = self.snapshot_format_version().await?;
-        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
-        let schema = self.table_info.meta.schema.as_ref().clone();
         let (segments, summary) = Self::merge_append_operations(operation_log)?;
 
         let progress_values = ProgressValues {
@@ -184,6 +185,33 @@ impl FuseTable {
         };
         ctx.get_write_progress().incr(&progress_values);
 
+        let new_snapshot = self
+            .build_append_snapshot(segments, summary, overwrite)
+            .await?;
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            &self.operator,
+        )
+        .await
+    }
+
+    /// Builds the snapshot which appends the `segments` to the current snapshot,
+    /// or replaces it if `overwrite` is true.
+    async fn build_append_snapshot(
+        &self,
+        segments: Vec<String>,
+        summary: Statistics,
+        overwrite: bool,
+    ) -> Result<TableSnapshot> {
+        let prev = self.read_table_snapshot().await?;
+        let prev_version = self.snapshot_format_version().await?;
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let schema = self.table_info.meta.schema.as_ref().clone();
+
         let segments = segments
             .into_iter()
             .map(|loc| (loc, SegmentInfo::VERSION))
@@ -209,24 +237,7 @@ impl FuseTable {
                 self.cluster_key_meta.clone(),
             )?
         };
-
-        let mut new_table_meta = self.get_table_info().meta.clone();
-        // update statistics
-        new_table_meta.statistics = TableStatistics {
-            number_of_rows: new_snapshot.summary.row_count,
-            data_bytes: new_snapshot.summary.uncompressed_byte_size,
-            compressed_data_bytes: new_snapshot.summary.compressed_byte_size,
-            index_data_bytes: new_snapshot.summary.index_size,
-        };
-
-        FuseTable::commit_to_meta_server(
-            ctx.as_ref(),
-            &self.table_info,
-            &self.meta_location_generator,
-            new_snapshot,
-            &self.operator,
-        )
-        .await
+        Ok(new_snapshot)
     }
 
     fn merge_table_operations(
@@ -272,11 +283,91 @@ impl FuseTable {
         snapshot: TableSnapshot,
         operator: &Operator,
     ) -> Result<()> {
+        let (req, snapshot_location) = Self::prepare_commit_to_meta_server(
+            table_info,
+            location_generator,
+            &snapshot,
+            operator,
+        )
+        .await?;
+        let catalog = ctx.get_catalog(&table_info.meta.catalog)?;
+
+        // 4. let's roll
+        let tenant = ctx.get_tenant();
+        let db_name = ctx.get_current_database();
+        // The streams consumed by the query are moved to their new offsets by the same commit.
+        let consumed_streams = ctx.get_consumed_streams();
+        let reply = if consumed_streams.is_empty() {
+            catalog
+                .update_table_meta(&tenant, &db_name, req)
+                .await
+                .map(|_| ())
+        } else {
+            Self::commit_with_consumed_streams(catalog.as_ref(), req, consumed_streams).await
+        };
+        match reply {
+            Ok(_) => {
+                ctx.clear_consumed_streams();
+                if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
+                    let cache = &mut snapshot_cache.write();
+                    cache.put(snapshot_location.clone(), Arc::new(snapshot));
+                }
+                // try keep a hit file of last snapshot
+                Self::write_last_snapshot_hint(operator, location_generator, snapshot_location)
+                    .await;
+                Ok(())
+            }
+            Err(e) => {
+                // commit snapshot to meta server failed, try to delete it.
+                // "major GC" will collect this, if deletion failure (even after DAL retried)
+                let _ = operator.object(&snapshot_location).delete().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn commit_with_consumed_streams(
+        catalog: &dyn Catalog,
+        req: UpdateTableMetaReq,
+        consumed_streams: Vec<UpdateTableMetaReq>,
+    ) -> Result<()> {
+        let mut update_table_metas = Vec::with_capacity(consumed_streams.len() + 1);
+        update_table_metas.push(req);
+        update_table_metas.extend(consumed_streams.iter().cloned());
+        let reply = catalog
+            .update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+            .await;
+
+        if let Err(e) = &reply {
+            if e.code() == ErrorCode::TABLE_VERSION_MISMATCHED {
+                // Retrying is hopeless once a stream has been moved by another statement.
+                for stream in &consumed_streams {
+                    let (ident, _) = catalog.get_table_meta_by_id(stream.table_id).await?;
+                    if stream.seq.match_seq(ident.seq).is_err() {
+                        return Err(ErrorCode::StreamOffsetChanged(format!(
+                            "The offset of stream (table id {}) has been changed by another statement since it was read",
+                            stream.table_id
+                        )));
+                    }
+                }
+            }
+        }
+        reply.map(|_| ())
+    }
+
+    /// Writes down the snapshot, and returns the request which makes it the current
+    /// snapshot of the table, together with the location of it.
+    pub async fn prepare_commit_to_meta_server(
+        table_info: &TableInfo,
+        location_generator: &TableMetaLocationGenerator,
+        snapshot: &TableSnapshot,
+        operator: &Operator,
+    ) -> Result<(UpdateTableMetaReq, String)> {
         let snapshot_location = location_generator
             .snapshot_location_from_uuid(&snapshot.snapshot_id, snapshot.format_version())?;
 
         // 1. write down snapshot
-        write_meta(operator, &snapshot_location, &snapshot).await?;
+        write_meta(operator, &snapshot_location, snapshot).await?;
 
         // 2. prepare table meta
         let mut new_table_meta = table_info.meta.clone();
@@ -299,7 +390,6 @@ impl FuseTable {
         };
 
         // 3. prepare the request
-        let catalog = ctx.get_catalog(&table_info.meta.catalog)?;
         let table_id = table_info.ident.table_id;
         let table_version = table_info.ident.seq;
 
@@ -308,29 +398,80 @@ impl FuseTable {
             seq: MatchSeq::Exact(table_version),
             new_table_meta,
         };
+        Ok((req, snapshot_location))
+    }
 
-        // 3. let's roll
-        let tenant = ctx.get_tenant();
-        let db_name = ctx.get_current_database();
-        let reply = catalog.update_table_meta(&tenant, &db_name, req).await;
-        match reply {
-            Ok(_) => {
-                if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
-                    let cache = &mut snapshot_cache.write();
-                    cache.put(snapshot_location.clone(), Arc::new(snapshot));
-                }
-                // try keep a hit file of last snapshot
-                Self::write_last_snapshot_hint(operator, location_generator, snapshot_location)
-                    .await;
-                Ok(())
-            }
-            Err(e) => {
-                // commit snapshot to meta server failed, try to delete it.
-                // "major GC" will collect this, if deletion failure (even after DAL retried)
-                let _ = operator.object(&snapshot_location).delete().await;
-                Err(e)
-            }
+    /// Buffers the insertion in the transaction of the session, which commits it on `COMMIT`.
+    pub async fn do_buffer_insertion(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        operations: Vec<DataBlock>,
+        overwrite: bool,
+    ) -> Result<()> {
+        if !ctx.get_consumed_streams().is_empty() {
+            return Err(ErrorCode::UnsupportedInTransaction(
+                "Consuming a stream is not supported in an explicit transaction",
+            ));
         }
+
+        let append_log_entries = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
+        let (_, summary) = Self::merge_append_operations(&append_log_entries)?;
+
+        let progress_values = ProgressValues {
+            rows: summary.row_count as usize,
+            bytes: summary.uncompressed_byte_size as usize,
+        };
+        ctx.get_write_progress().incr(&progress_values);
+
+        // The insertions overwritten in the same transaction are never committed.
+        let superseded = ctx.buffer_txn_insertion(&self.table_info, operations, overwrite)?;
+        if !superseded.is_empty() {
+            self.do_abort_insertion(superseded).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn do_prepare_commit_insertion(
+        &self,
+        operations: &[DataBlock],
+        overwrite: bool,
+    ) -> Result<UpdateTableMetaReq> {
+        let append_log_entries = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
+        let (segments, summary) = Self::merge_append_operations(&append_log_entries)?;
+        let new_snapshot = self
+            .build_append_snapshot(segments, summary, overwrite)
+            .await?;
+
+        let (req, _) = Self::prepare_commit_to_meta_server(
+            &self.table_info,
+            &self.meta_location_generator,
+            &new_snapshot,
+            &self.operator,
+        )
+        .await?;
+        Ok(req)
+    }
+
+    pub async fn do_abort_prepared_insertion(&self, req: &UpdateTableMetaReq) -> Result<()> {
+        // The snapshot written by `do_prepare_commit_insertion` is never referenced.
+        if let Some(snapshot_location) = req.new_table_meta.options.get(OPT_KEY_SNAPSHOT_LOCATION) {
+            let _ = self.operator.object(snapshot_location).delete().await;
+        }
+        Ok(())
+    }
+
+    pub async fn do_abort_insertion(&self, operations: Vec<DataBlock>) -> Result<()> {
+        let append_log_entries = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
+        utils::abort_operations(self.get_operator(), append_log_entries).await
     }
 
     pub fn merge_append_operations(
@@ -427,8 +568,9 @@ impl FuseTable {
         // holding the reference of latest table during retries
         let mut latest_table_ref: Arc<dyn Table>;
 
-        // potentially concurrently appended segments, init it to empty
-        let mut concurrently_appended_segment_locations: &[Location] = &[];
+        // potentially concurrently appended and removed segments, init them to empty
+        let mut concurrently_appended_segment_locations: Vec<Location> = vec![];
+        let mut concurrently_removed_segment_locations: Vec<Location> = vec![];
 
         while retries < MAX_RETRIES {
             let mut snapshot_tobe_committed =
@@ -439,7 +581,8 @@ impl FuseTable {
                 self.operator.clone(),
                 &base_segments,
                 &base_summary,
-                concurrently_appended_segment_locations,
+                &concurrently_appended_segment_locations,
+                &concurrently_removed_segment_locations,
             )
             .await?;
             snapshot_tobe_committed.segments = segments_tobe_committed;
@@ -468,10 +611,12 @@ impl FuseTable {
                             })?;
                     latest_table_info = &latest_fuse_table.table_info;
 
-                    // Check if there is only insertion during the operation.
+                    // Check if the concurrent operations are insertions, or mutations of
+                    // the segments that are not touched by this mutation.
                     match MutatorConflictDetector::detect_conflicts(
                         base_snapshot.as_ref(),
                         latest_snapshot.as_ref(),
+                        &base_segments,
                     ) {
                         Conflict::Unresolvable => {
                             abort_operation
@@ -486,7 +631,17 @@ impl FuseTable {
                             info!("resolvable conflicts detected");
                             metrics_inc_commit_mutation_resolvable_conflict();
                             concurrently_appended_segment_locations =
-                                &latest_snapshot.segments[range_of_newly_append];
+                                latest_snapshot.segments[range_of_newly_append].to_vec();
+                            concurrently_removed_segment_locations = vec![];
+                        }
+                        Conflict::ResolvableMutation {
+                            appended_segments,
+                            removed_segments,
+                        } => {
+                            info!("resolvable conflicts with concurrent mutation detected");
+                            metrics_inc_commit_mutation_resolvable_conflict();
+                            concurrently_appended_segment_locations = appended_segments;
+                            concurrently_removed_segment_locations = removed_segments;
                         }
                     }
 
@@ -518,9 +673,33 @@ impl FuseTable {
         base_segments: &[Location],
         base_summary: &Statistics,
         concurrently_appended_segment_locations: &[Location],
+        concurrently_removed_segment_locations: &[Location],
     ) -> Result<(Vec<Location>, Statistics)> {
-        if concurrently_appended_segment_locations.is_empty() {
+        if concurrently_appended_segment_locations.is_empty()
+            && concurrently_removed_segment_locations.is_empty()
+        {
             Ok((base_segments.to_owned(), base_summary.clone()))
+        } else if !concurrently_removed_segment_locations.is_empty() {
+            // place the segments written by the concurrent mutations at the head of segment list,
+            // and take away the segments removed (or replaced) by them
+            let removed = concurrently_removed_segment_locations
+                .iter()
+                .collect::<HashSet<_>>();
+            let new_segments = concurrently_appended_segment_locations
+                .iter()
+                .chain(base_segments.iter().filter(|loc| !removed.contains(loc)))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            // the statistics of the removed segments can not be subtracted, re-calculate them
+            let fuse_segment_io = SegmentsIO::create(ctx, operator);
+            let segment_infos = fuse_segment_io.read_segments(&new_segments).await?;
+            let mut new_statistics = Statistics::default();
+            for result in segment_infos.into_iter() {
+                let segment = result?;
+                new_statistics = merge_statistics(&new_statistics, &segment.summary)?;
+            }
+            Ok((new_segments, new_statistics))
         } else {
             // place the concurrently appended segments at the head of segment list
             let new_segments = concurrently_appended_segment_locations
@@ -550,6 +729,12 @@ pub enum Conflict {
     // resolvable conflicts with append only operation
     // the range embedded is the range of segments that are appended in the latest snapshot
     ResolvableAppend(Range<usize>),
+    // resolvable conflicts with mutations that touched other segments than the mutator did
+    // the segments embedded are the segments appended to and removed from the base snapshot
+    ResolvableMutation {
+        appended_segments: Vec<Location>,
+        removed_segments: Vec<Location>,
+    },
 }
 
 // wraps a namespace, to clarify the who is detecting conflict
@@ -557,7 +742,14 @@ pub struct MutatorConflictDetector;
 
 impl MutatorConflictDetector {
     // detects conflicts, as a mutator, working on the base snapshot, with latest snapshot
-    pub fn detect_conflicts(base: &TableSnapshot, latest: &TableSnapshot) -> Conflict {
+    //
+    // `mutated_segments` are the segments of the base snapshot after the mutation, the segments
+    // of base snapshot that are missing from it are the ones replaced or removed by the mutator.
+    pub fn detect_conflicts(
+        base: &TableSnapshot,
+        latest: &TableSnapshot,
+        mutated_segments: &[Location],
+    ) -> Conflict {
         let base_segments = &base.segments;
         let latest_segments = &latest.segments;
 
@@ -568,9 +760,33 @@ impl MutatorConflictDetector {
             && base_segments[0..base_segments_len]
                 == latest_segments[(latest_segments_len - base_segments_len)..latest_segments_len]
         {
-            Conflict::ResolvableAppend(0..(latest_segments_len - base_segments_len))
-        } else {
-            Conflict::Unresolvable
+            return Conflict::ResolvableAppend(0..(latest_segments_len - base_segments_len));
+        }
+
+        let base_segment_set = base_segments.iter().collect::<HashSet<_>>();
+        let latest_segment_set = latest_segments.iter().collect::<HashSet<_>>();
+        let mutated_segment_set = mutated_segments.iter().collect::<HashSet<_>>();
+
+        // A block belongs to only one segment of a snapshot, if the segments touched by the
+        // mutator and by the concurrent operations are disjoint, so are the blocks.
+        let touched_by_both = base_segments
+            .iter()
+            .any(|loc| !mutated_segment_set.contains(loc) && !latest_segment_set.contains(loc));
+        if touched_by_both {
+            return Conflict::Unresolvable;
+        }
+
+        Conflict::ResolvableMutation {
+            appended_segments: latest_segments
+                .iter()
+                .filter(|loc| !base_segment_set.contains(loc))
+                .cloned()
+                .collect(),
+            removed_segments: base_segments
+                .iter()
+                .filter(|loc| !latest_segment_set.contains(loc))
+                .cloned()
+                .collect(),
         }
     }
 }
@@ -598,6 +814,9 @@ mod utils {
                 if let Some(index) = &block.bloom_filter_index_location {
                     let _ = operator.object(&index.0).delete().await;
                 }
+                if let Some(index) = &block.inverted_index_location {
+                    let _ = operator.object(&index.0).delete().await;
+                }
             }
             let _ = operator.object(&entry.segment_location).delete().await;
         }
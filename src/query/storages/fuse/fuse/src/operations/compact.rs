@@ -17,10 +17,12 @@ use std::sync::Arc;
 use common_catalog::plan::DataSourceInfo;
 use common_catalog::plan::DataSourcePlan;
 use common_catalog::table::CompactTarget;
+use common_datablocks::BlockCompactThresholds;
 use common_exception::Result;
 use common_pipeline_core::Pipeline;
 use common_pipeline_transforms::processors::transforms::BlockCompactor;
 use common_pipeline_transforms::processors::transforms::TransformCompact;
+use common_storages_table_meta::meta::Statistics;
 use common_storages_table_meta::meta::TableSnapshot;
 
 use super::FuseTableSink;
@@ -76,6 +78,23 @@ impl FuseTable {
         }
     }
 
+    /// Returns the compaction worth running on the table automatically, judged by the summary
+    /// of the current snapshot, or None if the table is compact enough.
+    pub async fn auto_compact_target(&self) -> Result<Option<CompactTarget>> {
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+        let block_per_seg =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT) as u64;
+        Ok(auto_compact_target(
+            &snapshot.summary,
+            snapshot.segments.len(),
+            &self.get_block_compact_thresholds(),
+            block_per_seg,
+        ))
+    }
+
     async fn compact_segments(
         &self,
         ctx: Arc<dyn TableContext>,
@@ -153,6 +172,8 @@ impl FuseTable {
             )
         })?;
 
+        let column_ids = self.leaf_column_ids()?;
+        let inverted_index_columns = self.inverted_index_columns();
         pipeline.add_sink(|input| {
             FuseTableSink::try_create(
                 input,
@@ -161,6 +182,8 @@ impl FuseTable {
                 mutator.get_storage_operator(),
                 self.meta_location_generator().clone(),
                 ClusterStatsGenerator::default(),
+                column_ids.clone(),
+                inverted_index_columns.clone(),
                 None,
             )
         })?;
@@ -168,3 +191,39 @@ impl FuseTable {
         Ok(Some(Box::new(mutator)))
     }
 }
+
+/// Returns the compaction worth running on a table with the summary and the number of segments
+/// of its snapshot, or None if the table is compact enough.
+pub fn auto_compact_target(
+    summary: &Statistics,
+    segment_count: usize,
+    thresholds: &BlockCompactThresholds,
+    block_per_seg: u64,
+) -> Option<CompactTarget> {
+    if summary.block_count <= 1 {
+        return None;
+    }
+
+    // The blocks are too small if there are more than twice as many blocks as the table
+    // would have with blocks of the max size. The thresholds come from the table options,
+    // which may be set to 0.
+    let max_rows = (thresholds.max_rows_per_block as u64).max(1);
+    let max_bytes = (thresholds.max_bytes_per_block as u64).max(1);
+    let ideal_block_count = std::cmp::max(
+        (summary.row_count + max_rows - 1) / max_rows,
+        (summary.uncompressed_byte_size + max_bytes - 1) / max_bytes,
+    )
+    .max(1);
+    if summary.block_count > ideal_block_count * 2 {
+        return Some(CompactTarget::Blocks);
+    }
+
+    // Likewise for the segments.
+    let block_per_seg = block_per_seg.max(1);
+    let ideal_segment_count = (summary.block_count + block_per_seg - 1) / block_per_seg;
+    if segment_count as u64 > ideal_segment_count * 2 {
+        return Some(CompactTarget::Segments);
+    }
+
+    None
+}
@@ -86,6 +86,8 @@ impl FuseTable {
             self.meta_location_generator.clone(),
             snapshot.clone(),
             cluster_stats_gen,
+            self.leaf_column_ids()?,
+            self.inverted_index_columns(),
         )?;
         let schema = self.table_info.schema();
         // TODO refine pruner
@@ -103,6 +105,7 @@ impl FuseTable {
             &ctx,
             self.operator.clone(),
             schema,
+            &self.inverted_index_columns(),
             &push_downs,
             segments_location,
         )
@@ -152,7 +155,7 @@ impl FuseTable {
         .await
     }
 
-    fn cluster_stats_gen(&self) -> Result<ClusterStatsGenerator> {
+    pub(crate) fn cluster_stats_gen(&self) -> Result<ClusterStatsGenerator> {
         if self.cluster_key_meta.is_none() {
             return Ok(ClusterStatsGenerator::default());
         }
@@ -0,0 +1,113 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::NavigationPoint;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Commits a new snapshot, which has the same schema and data as the snapshot at `point`.
+    ///
+    /// The snapshots in between are kept, so the table can still travel back to them.
+    pub async fn do_flashback(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        point: &NavigationPoint,
+    ) -> Result<()> {
+        let historical_table = self.navigate_to(point).await?;
+        let historical_table = FuseTable::try_from_table(historical_table.as_ref())?;
+        let target = historical_table
+            .read_table_snapshot()
+            .await?
+            .ok_or_else(|| ErrorCode::TableHistoricalDataNotFound("No historical data found"))?;
+
+        let prev = self.read_table_snapshot().await?;
+        let prev_version = self.snapshot_format_version().await?;
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            target.schema.clone(),
+            target.summary.clone(),
+            target.segments.clone(),
+            self.cluster_key_meta.clone(),
+        );
+
+        // The schema may have been altered since then, restore it as well.
+        let mut table_info = self.table_info.clone();
+        let num_fields = target.schema.num_fields();
+        table_info.meta.schema = Arc::new(target.schema.clone());
+        if table_info.meta.field_comments.len() != num_fields {
+            table_info.meta.field_comments = vec!["".to_string(); num_fields];
+        }
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            &self.operator,
+        )
+        .await
+    }
+
+    /// Commits the first snapshot of this table, which shares the segments of the
+    /// current snapshot of `source`.
+    ///
+    /// The data files of `source` are NOT copied, and they are never purged by the
+    /// garbage collection of this table, since they are out of the storage prefix of it.
+    /// Neither are they purged by the garbage collection of `source`, on which this table
+    /// is registered by an option prefixed with `FUSE_OPT_KEY_CLONE_PREFIX`.
+    pub async fn do_clone_data_from(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        source: &FuseTable,
+    ) -> Result<()> {
+        let source_snapshot = match source.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            // Nothing to clone.
+            None => return Ok(()),
+        };
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &None,
+            None,
+            self.table_info.schema().as_ref().clone(),
+            source_snapshot.summary.clone(),
+            source_snapshot.segments.clone(),
+            self.cluster_key_meta.clone(),
+        );
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            &self.operator,
+        )
+        .await
+    }
+}
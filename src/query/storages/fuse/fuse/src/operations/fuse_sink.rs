@@ -28,11 +28,13 @@ use common_exception::Result;
 use common_pipeline_core::processors::port::OutputPort;
 use common_storages_index::*;
 use common_storages_table_meta::caches::CacheManager;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::SegmentInfo;
 use common_storages_table_meta::meta::Statistics;
 use opendal::Operator;
 
+use super::column_metas;
 use super::AppendOperationLogEntry;
 use crate::io;
 use crate::io::TableMetaLocationGenerator;
@@ -43,8 +45,10 @@ use crate::pipelines::processors::Processor;
 use crate::statistics::BlockStatistics;
 use crate::statistics::ClusterStatsGenerator;
 use crate::statistics::StatisticsAccumulator;
+use crate::ColumnMapping;
 
-struct BloomIndexState {
+// serialized index, bloom filter index or inverted index, of a block
+struct IndexState {
     data: Vec<u8>,
     size: u64,
     location: Location,
@@ -58,7 +62,8 @@ enum State {
         size: u64,
         meta_data: Box<ThriftFileMetaData>,
         block_statistics: BlockStatistics,
-        bloom_index_state: BloomIndexState,
+        bloom_index_state: IndexState,
+        inverted_index_state: Option<IndexState>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -82,12 +87,17 @@ pub struct FuseTableSink {
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
     cluster_stats_gen: ClusterStatsGenerator,
+    // ids of the leaf columns, which column metas and statistics are keyed by.
+    column_ids: Vec<ColumnId>,
+    // string columns, which the inverted indexes are built for.
+    inverted_index_columns: Vec<String>,
 
     // A dummy output port for distributed insert select to connect Exchange Sink.
     output: Option<Arc<OutputPort>>,
 }
 
 impl FuseTableSink {
+    #[allow(clippy::too_many_arguments)]
     pub fn try_create(
         input: Arc<InputPort>,
         ctx: Arc<dyn TableContext>,
@@ -95,6 +105,8 @@ impl FuseTableSink {
         data_accessor: Operator,
         meta_locations: TableMetaLocationGenerator,
         cluster_stats_gen: ClusterStatsGenerator,
+        column_ids: Vec<ColumnId>,
+        inverted_index_columns: Vec<String>,
         output: Option<Arc<OutputPort>>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
@@ -106,6 +118,8 @@ impl FuseTableSink {
             accumulator: Default::default(),
             num_block_threshold: num_block_threshold as u64,
             cluster_stats_gen,
+            column_ids,
+            inverted_index_columns,
             output,
         })))
     }
@@ -178,15 +192,44 @@ impl Processor for FuseTableSink {
                         &mut data,
                         CompressionOptions::Uncompressed,
                     )?;
-                    BloomIndexState {
+                    IndexState {
                         data,
                         size,
                         location,
                     }
                 };
 
-                let block_statistics =
+                let inverted_index_state =
+                    match BlockInvertedIndex::try_create(&block, &self.inverted_index_columns)? {
+                        Some(inverted_index) => {
+                            let location =
+                                self.meta_locations.block_inverted_index_location(&block_id);
+                            let mut data = Vec::with_capacity(100 * 1024);
+                            let (size, _) = serialize_data_blocks_with_compression(
+                                vec![inverted_index.filter_block],
+                                &inverted_index.filter_schema,
+                                &mut data,
+                                CompressionOptions::Uncompressed,
+                            )?;
+                            Some(IndexState {
+                                data,
+                                size,
+                                location,
+                            })
+                        }
+                        None => None,
+                    };
+
+                let mut block_statistics =
                     BlockStatistics::from(&block, block_location.0, cluster_stats)?;
+                block_statistics.block_column_statistics = ColumnMapping::remap_to_column_ids(
+                    block_statistics.block_column_statistics,
+                    &self.column_ids,
+                );
+                block_statistics.block_column_distributions = ColumnMapping::remap_to_column_ids(
+                    block_statistics.block_column_distributions,
+                    &self.column_ids,
+                );
                 // we need a configuration of block size threshold here
                 let mut data = Vec::with_capacity(100 * 1024 * 1024);
                 let schema = block.schema().clone();
@@ -198,11 +241,13 @@ impl Processor for FuseTableSink {
                     block_statistics,
                     meta_data: Box::new(meta_data),
                     bloom_index_state,
+                    inverted_index_state,
                 };
             }
             State::GenerateSegment => {
                 let acc = std::mem::take(&mut self.accumulator);
                 let col_stats = acc.summary()?;
+                let col_distributions = acc.summary_distributions();
 
                 let segment_info = SegmentInfo::new(acc.blocks_metas, Statistics {
                     row_count: acc.summary_row_count,
@@ -211,6 +256,7 @@ impl Processor for FuseTableSink {
                     compressed_byte_size: acc.file_size,
                     index_size: acc.index_size,
                     col_stats,
+                    col_distributions,
                 });
 
                 self.state = State::SerializedSegment {
@@ -246,6 +292,7 @@ impl Processor for FuseTableSink {
                 meta_data,
                 block_statistics,
                 bloom_index_state,
+                inverted_index_state,
             } => {
                 // write data block
                 io::write_data(
@@ -263,13 +310,26 @@ impl Processor for FuseTableSink {
                 )
                 .await?;
 
+                // write inverted index
+                let (inverted_index_location, inverted_index_size) = match inverted_index_state {
+                    Some(state) => {
+                        io::write_data(&state.data, &self.data_accessor, &state.location.0).await?;
+                        (Some(state.location), state.size)
+                    }
+                    None => (None, 0),
+                };
+
                 let bloom_filter_index_size = bloom_index_state.size;
-                self.accumulator.add_block(
+                let col_metas =
+                    ColumnMapping::remap_to_column_ids(column_metas(&meta_data)?, &self.column_ids);
+                self.accumulator.add(
                     size,
-                    *meta_data,
+                    col_metas,
                     block_statistics,
                     Some(bloom_index_state.location),
                     bloom_filter_index_size,
+                    inverted_index_location,
+                    inverted_index_size,
                 )?;
 
                 if self.accumulator.summary_block_count >= self.num_block_threshold {
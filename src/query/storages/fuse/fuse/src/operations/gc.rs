@@ -12,14 +12,19 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
 use common_cache::Cache;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::TableInfo;
 use common_storages_table_meta::caches::CacheManager;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::SnapshotId;
@@ -30,6 +35,8 @@ use crate::io::Files;
 use crate::io::SegmentsIO;
 use crate::io::SnapshotsIO;
 use crate::FuseTable;
+use crate::FUSE_OPT_KEY_CLONE_PREFIX;
+use crate::FUSE_OPT_KEY_DATA_RETENTION_PERIOD;
 
 #[derive(Default)]
 struct LocationTuple {
@@ -96,21 +103,65 @@ impl FuseTable {
         let mut segments_to_be_purged = HashSet::new();
 
         // 3.1 Find all the snapshots need to be deleted.
+        // The root snapshot is retained if the keep_last_snapshot is true, and so are the
+        // snapshots inside the data retention period, including the one that was current
+        // at the beginning of the period, to keep the time travel of the period working.
+        let retention_boundary = if keep_last_snapshot {
+            self.data_retention_boundary()
+        } else {
+            None
+        };
+        let mut snapshots_to_be_retained = vec![];
         {
+            // The snapshots are chained from the root to the oldest.
+            let mut boundary_covered = retention_boundary.is_none();
             for snapshot in &all_snapshot_lites {
-                // Skip the root snapshot if the keep_last_snapshot is true.
-                if keep_last_snapshot && snapshot.snapshot_id == root_snapshot_id {
+                let is_root = snapshot.snapshot_id == root_snapshot_id;
+                if keep_last_snapshot && (is_root || !boundary_covered) {
+                    if let Some(boundary) = retention_boundary {
+                        boundary_covered = snapshot.timestamp.map_or(true, |ts| ts <= boundary);
+                    }
+                    if !is_root {
+                        snapshots_to_be_retained
+                            .push((snapshot.snapshot_id, snapshot.format_version));
+                    }
                     continue;
                 }
                 snapshots_to_be_purged.insert((snapshot.snapshot_id, snapshot.format_version));
             }
         }
 
-        // 3.2 Find all the segments need to be deleted.
+        // 3.2 Find the segments, blocks and bloom indexes referenced by the retained snapshots.
+        let mut segments_to_be_retained = segments_referenced_by_root;
+        let mut locations_to_be_retained = locations_referenced_by_root;
+        if !snapshots_to_be_retained.is_empty() {
+            let segments = self
+                .get_snapshot_segments(ctx.clone(), &snapshots_to_be_retained)
+                .await?
+                .into_iter()
+                .filter(|segment| !segments_to_be_retained.contains(segment))
+                .collect::<Vec<_>>();
+            let locations = self.get_block_locations(ctx.clone(), &segments).await?;
+            locations_to_be_retained
+                .block_location
+                .extend(locations.block_location);
+            locations_to_be_retained
+                .bloom_location
+                .extend(locations.bloom_location);
+            segments_to_be_retained.extend(segments);
+        }
+
+        // 3.3 Find the files shared with the tables cloned from this table, they are never purged.
+        let (segments_shared, locations_shared) = self.get_cloned_locations(ctx).await?;
+
+        // 3.4 Find all the segments need to be deleted.
         {
             for segment in &all_segment_locations {
-                // Skip the root snapshot segments if the keep_last_snapshot is true.
-                if keep_last_snapshot && segments_referenced_by_root.contains(segment) {
+                // Skip the segments of the retained snapshots.
+                if keep_last_snapshot && segments_to_be_retained.contains(segment) {
+                    continue;
+                }
+                if segments_shared.contains(segment) {
                     continue;
                 }
                 segments_to_be_purged.insert(segment.clone());
@@ -135,10 +186,13 @@ impl FuseTable {
                     let mut block_locations_to_be_pruged = HashSet::new();
                     for loc in &locations.block_location {
                         if keep_last_snapshot
-                            && locations_referenced_by_root.block_location.contains(loc)
+                            && locations_to_be_retained.block_location.contains(loc)
                         {
                             continue;
                         }
+                        if locations_shared.block_location.contains(loc) {
+                            continue;
+                        }
                         block_locations_to_be_pruged.insert(loc.to_string());
                     }
                     status_block_to_be_purged_count += block_locations_to_be_pruged.len();
@@ -151,10 +205,13 @@ impl FuseTable {
                     let mut bloom_locations_to_be_pruged = HashSet::new();
                     for loc in &locations.bloom_location {
                         if keep_last_snapshot
-                            && locations_referenced_by_root.bloom_location.contains(loc)
+                            && locations_to_be_retained.bloom_location.contains(loc)
                         {
                             continue;
                         }
+                        if locations_shared.bloom_location.contains(loc) {
+                            continue;
+                        }
                         bloom_locations_to_be_pruged.insert(loc.to_string());
                     }
                     status_bloom_to_be_purged_count += bloom_locations_to_be_pruged.len();
@@ -229,6 +286,122 @@ impl FuseTable {
         Ok(())
     }
 
+    /// The snapshots committed after the returned time are inside the data retention period,
+    /// `None` if the `data_retention_period` option (in seconds) of the table is not set.
+    pub fn data_retention_boundary(&self) -> Option<DateTime<Utc>> {
+        let period = self.get_option(FUSE_OPT_KEY_DATA_RETENTION_PERIOD, 0u64);
+        if period == 0 {
+            return None;
+        }
+        let boundary = Duration::from_std(std::time::Duration::from_secs(period))
+            .ok()
+            .and_then(|period| Utc::now().checked_sub_signed(period));
+        // The period is longer than the time could be, retain all the snapshots.
+        Some(boundary.unwrap_or(DateTime::<Utc>::MIN_UTC))
+    }
+
+    // Get the segments referenced by the snapshots.
+    async fn get_snapshot_segments(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        snapshots: &[(SnapshotId, u64)],
+    ) -> Result<HashSet<Location>> {
+        let location_gen = self.meta_location_generator();
+        let snapshot_locations = snapshots
+            .iter()
+            .map(|(id, ver)| location_gen.snapshot_location_from_uuid(id, *ver))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut segments = HashSet::new();
+        let snapshots_io = SnapshotsIO::create(
+            ctx.clone(),
+            self.operator.clone(),
+            self.snapshot_format_version().await?,
+        );
+        let results = snapshots_io.read_snapshots(&snapshot_locations).await?;
+        for (idx, snapshot) in results.into_iter().enumerate() {
+            let snapshot = match snapshot {
+                Err(e) if e.code() == ErrorCode::STORAGE_NOT_FOUND => {
+                    // concurrent gc: someone else has already collected this snapshot, ignore it
+                    warn!(
+                        "concurrent gc: snapshot of location {} already collected. table: {}, ident {}",
+                        snapshot_locations[idx], self.table_info.desc, self.table_info.ident,
+                    );
+                    continue;
+                }
+                Err(e) => return Err(e),
+                Ok(v) => v,
+            };
+            segments.extend(snapshot.segments.iter().cloned());
+        }
+        Ok(segments)
+    }
+
+    // Get the segments, blocks and bloom indexes referenced by the snapshots of the tables cloned
+    // from this table, and of the tables cloned from them in turn. The clones are found by the
+    // table options, those vacuumed after being dropped are skipped.
+    async fn get_cloned_locations(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+    ) -> Result<(HashSet<Location>, LocationTuple)> {
+        let mut segments = HashSet::new();
+        let mut locations = LocationTuple::default();
+
+        let catalog = ctx.get_catalog(self.table_info.catalog())?;
+        let mut visited = HashSet::new();
+        let mut clone_ids = cloned_table_ids(&self.table_info.meta.options);
+        while let Some(table_id) = clone_ids.pop() {
+            if !visited.insert(table_id) {
+                continue;
+            }
+            let (ident, meta) = match catalog.get_table_meta_by_id(table_id).await {
+                Ok(v) => v,
+                Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE_ID => continue,
+                Err(e) => return Err(e),
+            };
+            clone_ids.extend(cloned_table_ids(&meta.options));
+
+            let table_info = TableInfo {
+                ident,
+                desc: format!("clone of {}", self.table_info.desc),
+                name: String::new(),
+                meta: meta.as_ref().clone(),
+                ..Default::default()
+            };
+            let table = catalog.get_table_by_info(&table_info)?;
+            let table = FuseTable::try_from_table(table.as_ref())?;
+            let root_snapshot_location = match table.snapshot_loc().await? {
+                Some(location) => location,
+                None => continue,
+            };
+
+            // All the snapshots of the clone, the time travel of it reaches the shared files too.
+            let snapshots_io = SnapshotsIO::create(
+                ctx.clone(),
+                table.operator.clone(),
+                table.snapshot_format_version().await?,
+            );
+            let (_, clone_segments) = snapshots_io
+                .read_snapshot_lites(root_snapshot_location, None, true, None, |_| {})
+                .await?;
+            let clone_segments = clone_segments
+                .into_iter()
+                .filter(|segment| !segments.contains(segment))
+                .collect::<Vec<_>>();
+            let clone_locations = table
+                .get_block_locations(ctx.clone(), &clone_segments)
+                .await?;
+            locations
+                .block_location
+                .extend(clone_locations.block_location);
+            locations
+                .bloom_location
+                .extend(clone_locations.bloom_location);
+            segments.extend(clone_segments);
+        }
+        Ok((segments, locations))
+    }
+
     // Purge file by location chunks.
     async fn try_purge_location_files(
         &self,
@@ -236,7 +409,13 @@ impl FuseTable {
         locations_to_be_purged: HashSet<String>,
     ) -> Result<()> {
         let fuse_file = Files::create(ctx.clone(), self.operator.clone());
-        let locations = Vec::from_iter(locations_to_be_purged);
+        // The files out of the prefix of the table, e.g. shared from the table it was cloned from,
+        // are not owned by this table.
+        let prefix = format!("{}/", self.meta_location_generator.prefix());
+        let locations = locations_to_be_purged
+            .into_iter()
+            .filter(|loc| loc.starts_with(&prefix))
+            .collect::<Vec<_>>();
         self.clean_cache(&locations);
         fuse_file.remove_file_in_batch(&locations).await
     }
@@ -275,6 +454,10 @@ impl FuseTable {
                         .unwrap_or_default()
                         .0,
                 );
+                // inverted indexes are purged along with the bloom filter indexes
+                if let Some(index) = &block_meta.inverted_index_location {
+                    blooms.insert(index.0.clone());
+                }
             }
         }
 
@@ -293,3 +476,11 @@ impl FuseTable {
         }
     }
 }
+
+fn cloned_table_ids(options: &BTreeMap<String, String>) -> Vec<u64> {
+    options
+        .iter()
+        .filter(|(key, _)| key.starts_with(FUSE_OPT_KEY_CLONE_PREFIX))
+        .filter_map(|(_, value)| value.parse::<u64>().ok())
+        .collect()
+}
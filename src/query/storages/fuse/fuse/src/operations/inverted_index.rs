@@ -0,0 +1,148 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table::TableExt;
+use common_catalog::table_context::TableContext;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_index::BlockInvertedIndex;
+use common_storages_table_meta::meta::Statistics;
+use common_storages_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use crate::operations::commit::MAX_RETRIES;
+use crate::FuseTable;
+use crate::FUSE_OPT_KEY_INVERTED_INDEX_PREFIX;
+
+impl FuseTable {
+    /// Records the inverted index in the options of the table, the columns of it are
+    /// indexed for the blocks written afterwards.
+    ///
+    /// The existing blocks are not indexed, so the index can only be created on a table
+    /// without blocks. The commit is retried if the table is changed concurrently.
+    pub async fn do_create_inverted_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        index_name: &str,
+        columns: &[String],
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let mut retries = 0;
+        let mut latest_table = self;
+
+        // holding the reference of latest table during retries
+        let mut latest_table_ref: Arc<dyn Table>;
+
+        loop {
+            match latest_table
+                .try_create_inverted_index(ctx.clone(), index_name, columns, if_not_exists)
+                .await
+            {
+                Err(e) if e.code() == ErrorCode::TABLE_VERSION_MISMATCHED => {
+                    if retries >= MAX_RETRIES {
+                        return Err(e.add_message_back(format!(
+                            " (creating inverted index '{}' failed after {} retries)",
+                            index_name, retries
+                        )));
+                    }
+                    latest_table_ref = latest_table.refresh(ctx.as_ref()).await?;
+                    latest_table = FuseTable::try_from_table(latest_table_ref.as_ref())?;
+                    retries += 1;
+                }
+                r => return r,
+            }
+        }
+    }
+
+    async fn try_create_inverted_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        index_name: &str,
+        columns: &[String],
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let option_key = format!("{}{}", FUSE_OPT_KEY_INVERTED_INDEX_PREFIX, index_name);
+        if self.table_info.meta.options.contains_key(&option_key) {
+            return if if_not_exists {
+                Ok(())
+            } else {
+                Err(ErrorCode::IndexAlreadyExists(format!(
+                    "Index '{}' already exists on table {}",
+                    index_name,
+                    self.name()
+                )))
+            };
+        }
+
+        let schema = self.schema();
+        for column in columns {
+            let field = schema.field_with_name(column)?;
+            if !BlockInvertedIndex::is_supported_type(field.data_type()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Inverted index only supports string columns, but column '{}' is of type {}",
+                    column,
+                    field.data_type().name()
+                )));
+            }
+        }
+
+        let mut table_info = self.table_info.clone();
+        table_info
+            .meta
+            .options
+            .insert(option_key, columns.join(","));
+
+        let prev = self.read_table_snapshot().await?;
+        if matches!(&prev, Some(snapshot) if snapshot.summary.block_count > 0) {
+            return Err(ErrorCode::Unimplemented(format!(
+                "Can not create inverted index '{}' on table {}, which is not empty: \
+                 the existing blocks can not be indexed",
+                index_name,
+                self.name()
+            )));
+        }
+
+        let prev_version = self.snapshot_format_version().await?;
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+        let (summary, segments) = if let Some(v) = prev {
+            (v.summary.clone(), v.segments.clone())
+        } else {
+            (Statistics::default(), vec![])
+        };
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            schema.as_ref().clone(),
+            summary,
+            segments,
+            self.cluster_key_meta.clone(),
+        );
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            &self.operator,
+        )
+        .await
+    }
+}
@@ -0,0 +1,493 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_catalog::plan::Expression;
+use common_catalog::plan::Projection;
+use common_catalog::table::MergeIntoInfo;
+use common_catalog::table::MergeIntoSource;
+use common_catalog::table::MergeMatchedAction;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use common_sql::evaluator::EvalNode;
+use common_sql::evaluator::Evaluator;
+use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::Statistics;
+use common_storages_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use super::update::collect_column_names;
+use crate::io::BlockReader;
+use crate::operations::mutation::block_filter::all_the_columns_ids;
+use crate::operations::mutation::block_filter::scatter_updated_column;
+use crate::operations::mutation::MergeIntoMutator;
+use crate::pruning::BlockPruner;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// MERGE INTO table USING source ON target_keys = source_keys [AND join_filter]
+    ///   WHEN MATCHED [AND condition] THEN UPDATE SET col = expr, ... | DELETE
+    ///   WHEN NOT MATCHED [AND condition] THEN INSERT VALUES (expr, ...)
+    ///
+    /// The source is the build side of the join, probed by the blocks of the target as they are scanned.
+    /// Each matched row of the target is assigned to the first matched clause that its condition holds,
+    /// and so is each row of the source that matches no rows of the target to the not matched clauses.
+    /// The blocks having rows updated or deleted are rewritten, the inserted rows are written to a
+    /// new segment, and all of them are committed in a single snapshot.
+    pub async fn do_merge_into(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        merge: MergeIntoInfo,
+    ) -> Result<()> {
+        if merge.source.is_empty() {
+            return Ok(());
+        }
+
+        let prev = self.read_table_snapshot().await?;
+        let snapshot = match &prev {
+            Some(snapshot) => snapshot.clone(),
+            None => Arc::new(TableSnapshot::new(
+                Uuid::new_v4(),
+                &None,
+                None,
+                self.table_info.schema().as_ref().clone(),
+                Statistics::default(),
+                vec![],
+                self.cluster_key_meta.clone(),
+            )),
+        };
+
+        let cluster_stats_gen = self.cluster_stats_gen()?;
+        let mut mutator = MergeIntoMutator::try_create(
+            ctx.clone(),
+            self.get_operator(),
+            self.meta_location_generator.clone(),
+            snapshot.clone(),
+            cluster_stats_gen,
+            self.leaf_column_ids()?,
+            self.inverted_index_columns(),
+        )?;
+
+        let func_ctx = ctx.try_get_function_context()?;
+        let merger = Merger::try_create(self, merge)?;
+
+        let block_metas = BlockPruner::prune(
+            &ctx,
+            self.operator.clone(),
+            self.table_info.schema(),
+            &self.inverted_index_columns(),
+            &None,
+            snapshot.segments.clone(),
+        )
+        .await?;
+        let reader = self.create_block_reader(Projection::Columns(all_the_columns_ids(self)))?;
+        for (seg_idx, block_meta) in block_metas {
+            self.merge_block(
+                &func_ctx,
+                &reader,
+                &merger,
+                &mut mutator,
+                seg_idx,
+                &block_meta,
+            )
+            .await?;
+        }
+
+        if let Some(block) = merger.not_matched_rows(&func_ctx)? {
+            let block = self.with_cluster_key_columns(&func_ctx, block)?;
+            let max_rows = self.get_block_compact_thresholds().max_rows_per_block;
+            for block in DataBlock::split_block_by_size(&block, max_rows)? {
+                mutator.append(block).await?;
+            }
+        }
+
+        if prev.is_none() {
+            // the table has no snapshot yet, all the source rows are inserted, commit them as an append
+            let operation_log = mutator
+                .write_appended_segment()
+                .await?
+                .into_iter()
+                .collect();
+            return self.do_commit(ctx, operation_log, false).await;
+        }
+
+        let (segments, summary, abort_operation) = mutator.generate_segments().await?;
+        self.commit_mutation(
+            &ctx,
+            mutator.base_snapshot(),
+            segments,
+            summary,
+            abort_operation,
+        )
+        .await
+    }
+
+    async fn merge_block(
+        &self,
+        func_ctx: &FunctionContext,
+        reader: &Arc<BlockReader>,
+        merger: &Merger,
+        mutator: &mut MergeIntoMutator,
+        seg_idx: usize,
+        block_meta: &BlockMeta,
+    ) -> Result<()> {
+        let data_block = reader.read_with_block_meta(block_meta).await?;
+        if let Some(new_block) = merger.merge_block(func_ctx, data_block)? {
+            let origin_stats = if merger.keep_cluster_stats {
+                block_meta.cluster_stats.clone()
+            } else {
+                None
+            };
+            mutator
+                .replace_with(
+                    seg_idx,
+                    block_meta.location.clone(),
+                    origin_stats,
+                    new_block,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Appends the columns of the cluster keys which are not columns of the table,
+    // as the cluster statistics generator expects.
+    fn with_cluster_key_columns(
+        &self,
+        func_ctx: &FunctionContext,
+        block: DataBlock,
+    ) -> Result<DataBlock> {
+        let schema = self.table_info.schema();
+        let mut block = block;
+        for expr in self.cluster_keys() {
+            let name = expr.column_name();
+            if block.schema().has_field(&name) {
+                continue;
+            }
+            let column = Evaluator::eval_expression(&expr, schema.as_ref())?
+                .eval(func_ctx, &block)?
+                .vector;
+            block = block.add_column(column, DataField::new(&name, expr.data_type()))?;
+        }
+        Ok(block)
+    }
+}
+
+/// Holds the state of a merge: the source joined with the blocks of the target,
+/// and the evaluation nodes of the clauses.
+struct Merger {
+    table_schema: DataSchemaRef,
+    // the target columns renamed to the names used by the expressions
+    target_schema: DataSchemaRef,
+    source: Box<dyn MergeIntoSource>,
+    // evaluated against the target rows joined with the matched source rows
+    matched: Vec<MatchedClause>,
+    // evaluated against the source rows
+    not_matched: Vec<(Option<EvalNode>, Vec<EvalNode>)>,
+    keep_cluster_stats: bool,
+}
+
+struct MatchedClause {
+    condition: Option<EvalNode>,
+    // (index of the field in the table schema, new value), empty for the DELETE clauses
+    update_nodes: Vec<(usize, EvalNode)>,
+    delete: bool,
+}
+
+impl Merger {
+    fn try_create(table: &FuseTable, merge: MergeIntoInfo) -> Result<Self> {
+        let table_schema = table.table_info.schema();
+        if merge.target_columns.len() != table_schema.num_fields() {
+            return Err(ErrorCode::Internal(format!(
+                "the number of target columns {} does not match the table schema",
+                merge.target_columns.len()
+            )));
+        }
+        let target_schema = DataSchemaRefExt::create(
+            table_schema
+                .fields()
+                .iter()
+                .zip(merge.target_columns.iter())
+                .map(|(field, name)| DataField::new(name, field.data_type().clone()))
+                .collect(),
+        );
+        let source_schema = merge.source.schema();
+
+        // the target rows joined with the source rows
+        let mut joined_fields = target_schema.fields().clone();
+        joined_fields.extend(source_schema.fields().iter().cloned());
+        let joined_schema = DataSchema::new(joined_fields);
+        let matched = merge
+            .matched
+            .iter()
+            .map(|clause| {
+                let condition = clause
+                    .condition
+                    .as_ref()
+                    .map(|expr| Evaluator::eval_expression(expr, &joined_schema))
+                    .transpose()?;
+                let (update_nodes, delete) = match &clause.action {
+                    MergeMatchedAction::Update(update_list) => {
+                        let update_nodes = update_list
+                            .iter()
+                            .map(|(idx, expr)| {
+                                let expr = cast_if_needed(expr.clone(), table_schema.field(*idx));
+                                Ok((*idx, Evaluator::eval_expression(&expr, &joined_schema)?))
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        (update_nodes, false)
+                    }
+                    MergeMatchedAction::Delete => (vec![], true),
+                };
+                Ok(MatchedClause {
+                    condition,
+                    update_nodes,
+                    delete,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // if the cluster keys are touched, the blocks are no longer sorted by the cluster keys,
+        // the origin cluster statistics could not be kept.
+        let keep_cluster_stats = {
+            let mut cluster_key_columns = HashSet::new();
+            for expr in table.cluster_keys().iter() {
+                collect_column_names(expr, &mut cluster_key_columns);
+            }
+            matched
+                .iter()
+                .flat_map(|clause| clause.update_nodes.iter())
+                .all(|(idx, _)| !cluster_key_columns.contains(table_schema.field(*idx).name()))
+        };
+
+        let not_matched = merge
+            .not_matched
+            .iter()
+            .map(|clause| {
+                let condition = clause
+                    .condition
+                    .as_ref()
+                    .map(|expr| Evaluator::eval_expression(expr, source_schema.as_ref()))
+                    .transpose()?;
+                let values = clause
+                    .values
+                    .iter()
+                    .zip(table_schema.fields())
+                    .map(|(expr, field)| {
+                        let expr = cast_if_needed(expr.clone(), field);
+                        Evaluator::eval_expression(&expr, source_schema.as_ref())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((condition, values))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            table_schema,
+            target_schema,
+            source: merge.source,
+            matched,
+            not_matched,
+            keep_cluster_stats,
+        })
+    }
+
+    /// Applies the matched clauses to a block of the target.
+    ///
+    /// Returns `None` if none of the rows of the block are updated or deleted.
+    fn merge_block(
+        &self,
+        func_ctx: &FunctionContext,
+        data_block: DataBlock,
+    ) -> Result<Option<DataBlock>> {
+        let matches = self.source.probe(&data_block)?;
+        if matches.target_rows.is_empty() {
+            return Ok(None);
+        }
+
+        // the pair of each matched target row, a target row matches one source row at most
+        let num_rows = data_block.num_rows();
+        let mut pairs = vec![None; num_rows];
+        for (pair, target_row) in matches.target_rows.iter().enumerate() {
+            let slot = &mut pairs[*target_row as usize];
+            if slot.is_some() {
+                return Err(ErrorCode::TableMergeMultipleMatches(
+                    "a row of the target table is matched by more than one row of the source",
+                ));
+            }
+            *slot = Some(pair as u32);
+        }
+        let (target_indices, source_indices): (Vec<u32>, Vec<u32>) = pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(target_row, pair)| pair.map(|pair| (target_row as u32, pair)))
+            .unzip();
+        let target = DataBlock::create(self.target_schema.clone(), data_block.columns().to_vec());
+        let joined = join(&target, &target_indices, &matches.source, &source_indices)?;
+
+        // assign each matched row to the first clause that its condition holds
+        let mut remaining = vec![true; joined.num_rows()];
+        let mut columns = data_block.columns().to_vec();
+        let mut kept = vec![true; num_rows];
+        let mut any_assigned = false;
+        let mut any_deleted = false;
+        for clause in &self.matched {
+            let mut assigned = match &clause.condition {
+                Some(condition) => eval_predicate(condition, func_ctx, &joined)?,
+                None => vec![true; joined.num_rows()],
+            };
+            for (assigned, remaining) in assigned.iter_mut().zip(remaining.iter_mut()) {
+                *assigned = *assigned && *remaining;
+                *remaining = *remaining && !*assigned;
+            }
+            if !assigned.iter().any(|v| *v) {
+                continue;
+            }
+            any_assigned = true;
+
+            // the target rows assigned to the clause
+            let mut selection = vec![false; num_rows];
+            for (assigned, target_row) in assigned.iter().zip(target_indices.iter()) {
+                selection[*target_row as usize] = *assigned;
+            }
+
+            if clause.delete {
+                for (kept, selected) in kept.iter_mut().zip(selection.iter()) {
+                    *kept = *kept && !*selected;
+                }
+                any_deleted = true;
+                continue;
+            }
+
+            // evaluate the new values on the rows assigned to the clause only, so that the rows
+            // assigned to the other clauses never fail the evaluation
+            let rows = DataBlock::filter_block(joined.clone(), &Series::from_data(assigned))?;
+            let selection = Bitmap::from_iter(selection);
+            for (idx, node) in &clause.update_nodes {
+                let updated = node.eval(func_ctx, &rows)?.vector.convert_full_column();
+                columns[*idx] = scatter_updated_column(&columns[*idx], &updated, &selection)?;
+            }
+        }
+
+        if !any_assigned {
+            return Ok(None);
+        }
+
+        let new_block = DataBlock::create(self.table_schema.clone(), columns);
+        if !any_deleted {
+            return Ok(Some(new_block));
+        }
+        let new_block = DataBlock::filter_block(new_block, &Series::from_data(kept))?;
+        Ok(Some(new_block))
+    }
+
+    /// Applies the not matched clauses to the source rows that match no rows of the target.
+    fn not_matched_rows(&self, func_ctx: &FunctionContext) -> Result<Option<DataBlock>> {
+        if self.not_matched.is_empty() {
+            return Ok(None);
+        }
+        let unmatched = self.source.unmatched()?;
+        if unmatched.is_empty() {
+            return Ok(None);
+        }
+        let source = DataBlock::concat_blocks(&unmatched)?;
+        if source.num_rows() == 0 {
+            return Ok(None);
+        }
+
+        let mut remaining = vec![true; source.num_rows()];
+        let mut inserted = vec![];
+        for (condition, values) in &self.not_matched {
+            let mut assigned = match condition {
+                Some(condition) => eval_predicate(condition, func_ctx, &source)?,
+                None => vec![true; source.num_rows()],
+            };
+            for (assigned, remaining) in assigned.iter_mut().zip(remaining.iter_mut()) {
+                *assigned = *assigned && *remaining;
+                *remaining = *remaining && !*assigned;
+            }
+            if !assigned.iter().any(|v| *v) {
+                continue;
+            }
+
+            let rows = DataBlock::filter_block(source.clone(), &Series::from_data(assigned))?;
+            let columns = values
+                .iter()
+                .map(|node| Ok(node.eval(func_ctx, &rows)?.vector.convert_full_column()))
+                .collect::<Result<Vec<_>>>()?;
+            inserted.push(DataBlock::create(self.table_schema.clone(), columns));
+        }
+
+        if inserted.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(DataBlock::concat_blocks(&inserted)?))
+    }
+}
+
+// Takes the target rows and the source rows by the indices, and puts them side by side.
+fn join(
+    target: &DataBlock,
+    target_indices: &[u32],
+    source: &DataBlock,
+    source_indices: &[u32],
+) -> Result<DataBlock> {
+    let target = DataBlock::block_take_by_indices(target, target_indices)?;
+    let source = DataBlock::block_take_by_indices(source, source_indices)?;
+
+    let mut fields = target.schema().fields().clone();
+    fields.extend(source.schema().fields().iter().cloned());
+    let mut columns = target.columns().to_vec();
+    columns.extend(source.columns().iter().cloned());
+    Ok(DataBlock::create(DataSchemaRefExt::create(fields), columns))
+}
+
+fn cast_if_needed(expr: Expression, field: &DataField) -> Expression {
+    if expr.data_type() != *field.data_type() {
+        Expression::Cast {
+            input: Box::new(expr),
+            target: field.data_type().clone(),
+        }
+    } else {
+        expr
+    }
+}
+
+fn eval_predicate(
+    node: &EvalNode,
+    func_ctx: &FunctionContext,
+    block: &DataBlock,
+) -> Result<Vec<bool>> {
+    let result = node.eval(func_ctx, block)?.vector;
+    let predicates = DataBlock::cast_to_nonull_boolean(&result)?;
+    if let Some(const_bool) = DataBlock::try_as_const_bool(&predicates)? {
+        return Ok(vec![const_bool; block.num_rows()]);
+    }
+    let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+    Ok(boolean_col.values().iter().collect())
+}
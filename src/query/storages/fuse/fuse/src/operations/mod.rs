@@ -12,12 +12,18 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter_column;
+mod analyze;
 mod append;
+mod changes;
 mod commit;
 mod compact;
 mod delete;
+mod flashback;
 mod fuse_sink;
 mod gc;
+mod inverted_index;
+mod merge_into;
 mod mutation;
 mod navigate;
 mod operation_log;
@@ -25,10 +31,13 @@ mod read_data;
 mod read_partitions;
 mod recluster;
 mod truncate;
+mod update;
 
 mod fuse_source;
 pub mod util;
 
+pub(crate) use changes::TableChanges;
+pub use compact::auto_compact_target;
 pub(crate) use compact::CompactOptions;
 pub use fuse_sink::FuseTableSink;
 pub use fuse_source::FuseTableSource;
@@ -36,10 +45,12 @@ pub use fuse_source::State;
 pub use mutation::delete_from_block;
 pub use mutation::DeletionMutator;
 pub use mutation::FullCompactMutator;
+pub use mutation::MergeIntoMutator;
 pub use mutation::ReclusterMutator;
 pub use mutation::SegmentCompactMutator;
 pub use mutation::SegmentCompactionState;
 pub use mutation::SegmentCompactor;
+pub use mutation::UpdateMutator;
 pub use operation_log::AppendOperationLogEntry;
 pub use operation_log::TableOperationLog;
 pub use util::column_metas;
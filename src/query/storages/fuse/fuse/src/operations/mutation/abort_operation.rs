@@ -26,6 +26,7 @@ pub struct AbortOperation {
     pub segments: Vec<String>,
     pub blocks: Vec<String>,
     pub bloom_filter_indexes: Vec<String>,
+    pub inverted_indexes: Vec<String>,
 }
 
 impl AbortOperation {
@@ -35,6 +36,9 @@ impl AbortOperation {
         if let Some(index) = block.bloom_filter_index_location.clone() {
             self.bloom_filter_indexes.push(index.0);
         }
+        if let Some(index) = block.inverted_index_location.clone() {
+            self.inverted_indexes.push(index.0);
+        }
         self
     }
 
@@ -49,6 +53,7 @@ impl AbortOperation {
             .blocks
             .into_iter()
             .chain(self.bloom_filter_indexes.into_iter())
+            .chain(self.inverted_indexes.into_iter())
             .chain(self.segments.into_iter());
         fuse_file.remove_file_in_batch(locations).await
     }
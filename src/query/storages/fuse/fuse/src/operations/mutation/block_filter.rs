@@ -15,11 +15,14 @@
 use std::ops::Not;
 use std::sync::Arc;
 
+use common_arrow::arrow::bitmap::Bitmap;
 use common_catalog::plan::Expression;
 use common_catalog::plan::Projection;
 use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
 use common_datavalues::BooleanColumn;
+use common_datavalues::ChunkRowIndex;
+use common_datavalues::ColumnRef;
 use common_datavalues::Series;
 use common_exception::Result;
 use common_sql::evaluator::Evaluator;
@@ -112,3 +115,37 @@ pub fn all_the_columns_ids(table: &FuseTable) -> Vec<usize> {
         .into_iter()
         .collect::<Vec<usize>>()
 }
+
+/// Builds the new column of a block in which only the rows selected by `selection` are updated.
+///
+/// `updated` holds the new values of the selected rows only, in the order of the rows, the other
+/// rows keep their values in `origin`.
+pub fn scatter_updated_column(
+    origin: &ColumnRef,
+    updated: &ColumnRef,
+    selection: &Bitmap,
+) -> Result<ColumnRef> {
+    debug_assert_eq!(origin.len(), selection.len());
+    debug_assert_eq!(updated.len(), selection.len() - selection.unset_bits());
+
+    // the runs of (chunk, row, size), chunk 0 is the origin column and chunk 1 the updated one
+    let mut indices: Vec<ChunkRowIndex> = Vec::new();
+    let mut updated_row = 0;
+    for (row, selected) in selection.iter().enumerate() {
+        let (chunk, chunk_row) = if selected {
+            updated_row += 1;
+            (1, updated_row - 1)
+        } else {
+            (0, row)
+        };
+        match indices.last_mut() {
+            Some((last_chunk, last_row, size))
+                if *last_chunk == chunk && *last_row + *size == chunk_row =>
+            {
+                *size += 1
+            }
+            _ => indices.push((chunk, chunk_row, 1)),
+        }
+    }
+    Series::take_chunk_indices(&[origin.clone(), updated.clone()], &indices)
+}
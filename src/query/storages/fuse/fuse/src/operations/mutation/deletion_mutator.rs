@@ -18,6 +18,7 @@ use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_storages_table_meta::meta::ClusterStatistics;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::Statistics;
 use common_storages_table_meta::meta::TableSnapshot;
@@ -37,6 +38,8 @@ pub enum Deletion {
 pub struct DeletionMutator {
     base_mutator: BaseMutator,
     cluster_stats_gen: ClusterStatsGenerator,
+    column_ids: Vec<ColumnId>,
+    inverted_index_columns: Vec<String>,
 }
 
 impl DeletionMutator {
@@ -46,11 +49,15 @@ impl DeletionMutator {
         location_generator: TableMetaLocationGenerator,
         base_snapshot: Arc<TableSnapshot>,
         cluster_stats_gen: ClusterStatsGenerator,
+        column_ids: Vec<ColumnId>,
+        inverted_index_columns: Vec<String>,
     ) -> Result<Self> {
         let base_mutator = BaseMutator::try_create(ctx, op, location_generator, base_snapshot)?;
         Ok(Self {
             base_mutator,
             cluster_stats_gen,
+            column_ids,
+            inverted_index_columns,
         })
     }
 
@@ -78,7 +85,9 @@ impl DeletionMutator {
             let block_writer = BlockWriter::new(
                 &self.base_mutator.data_accessor,
                 &self.base_mutator.location_generator,
-            );
+            )
+            .with_column_ids(&self.column_ids)
+            .with_inverted_index_columns(&self.inverted_index_columns);
             let cluster_stats = self
                 .cluster_stats_gen
                 .gen_with_origin_stats(&replace_with, origin_stats)?;
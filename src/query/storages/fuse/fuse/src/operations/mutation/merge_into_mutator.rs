@@ -0,0 +1,154 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_storages_table_meta::caches::CacheManager;
+use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::ClusterStatistics;
+use common_storages_table_meta::meta::ColumnId;
+use common_storages_table_meta::meta::Location;
+use common_storages_table_meta::meta::SegmentInfo;
+use common_storages_table_meta::meta::Statistics;
+use common_storages_table_meta::meta::TableSnapshot;
+use opendal::Operator;
+
+use super::AbortOperation;
+use crate::io::BlockWriter;
+use crate::io::SegmentWriter;
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::BaseMutator;
+use crate::operations::AppendOperationLogEntry;
+use crate::statistics::merge_statistics;
+use crate::statistics::reducers::reduce_block_metas;
+use crate::statistics::ClusterStatsGenerator;
+
+pub struct MergeIntoMutator {
+    base_mutator: BaseMutator,
+    cluster_stats_gen: ClusterStatsGenerator,
+    column_ids: Vec<ColumnId>,
+    inverted_index_columns: Vec<String>,
+    // blocks of the rows inserted by the not matched clauses
+    appended_blocks: Vec<Arc<BlockMeta>>,
+}
+
+impl MergeIntoMutator {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        op: Operator,
+        location_generator: TableMetaLocationGenerator,
+        base_snapshot: Arc<TableSnapshot>,
+        cluster_stats_gen: ClusterStatsGenerator,
+        column_ids: Vec<ColumnId>,
+        inverted_index_columns: Vec<String>,
+    ) -> Result<Self> {
+        let base_mutator = BaseMutator::try_create(ctx, op, location_generator, base_snapshot)?;
+        Ok(Self {
+            base_mutator,
+            cluster_stats_gen,
+            column_ids,
+            inverted_index_columns,
+            appended_blocks: vec![],
+        })
+    }
+
+    pub fn base_snapshot(self) -> Arc<TableSnapshot> {
+        self.base_mutator.base_snapshot
+    }
+
+    /// Records the replacements:
+    ///  the block located at `block_location` of segment indexed by `seg_idx` with the merged block,
+    ///  the block is removed if all of its rows are deleted
+    pub async fn replace_with(
+        &mut self,
+        seg_idx: usize,
+        location_of_block_to_be_replaced: Location,
+        origin_stats: Option<ClusterStatistics>,
+        replace_with: DataBlock,
+    ) -> Result<()> {
+        let new_block_meta = if replace_with.num_rows() == 0 {
+            None
+        } else {
+            let cluster_stats = self
+                .cluster_stats_gen
+                .gen_with_origin_stats(&replace_with, origin_stats)?;
+            Some(
+                self.block_writer()
+                    .write(replace_with, cluster_stats)
+                    .await?,
+            )
+        };
+        self.base_mutator
+            .add_mutation(seg_idx, location_of_block_to_be_replaced, new_block_meta);
+        Ok(())
+    }
+
+    /// Writes a block of newly inserted rows, the block should contain the extra columns
+    /// of the cluster keys, if any.
+    pub async fn append(&mut self, block: DataBlock) -> Result<()> {
+        let (cluster_stats, block) = self.cluster_stats_gen.gen_stats_for_append(&block)?;
+        let block_meta = self.block_writer().write(block, cluster_stats).await?;
+        self.appended_blocks.push(Arc::new(block_meta));
+        Ok(())
+    }
+
+    /// Generates the segments of the mutated blocks, and a new segment of the appended blocks.
+    pub async fn generate_segments(&self) -> Result<(Vec<Location>, Statistics, AbortOperation)> {
+        let (mut segments, mut summary, mut abort_operation) =
+            self.base_mutator.generate_segments().await?;
+        if let Some(entry) = self.write_appended_segment().await? {
+            for block_meta in &self.appended_blocks {
+                abort_operation = abort_operation.add_block(block_meta);
+            }
+            abort_operation = abort_operation.add_segment(entry.segment_location.clone());
+            // like appending, the new segment is placed at the head of segment list
+            segments.insert(0, (entry.segment_location, SegmentInfo::VERSION));
+            summary = merge_statistics(&summary, &entry.segment_info.summary)?;
+        }
+        Ok((segments, summary, abort_operation))
+    }
+
+    /// Writes the appended blocks to a new segment, if any.
+    pub async fn write_appended_segment(&self) -> Result<Option<AppendOperationLogEntry>> {
+        if self.appended_blocks.is_empty() {
+            return Ok(None);
+        }
+
+        let segment_info_cache = CacheManager::instance().get_table_segment_cache();
+        let seg_writer = SegmentWriter::new(
+            &self.base_mutator.data_accessor,
+            &self.base_mutator.location_generator,
+            &segment_info_cache,
+        );
+        let summary = reduce_block_metas(&self.appended_blocks)?;
+        let segment_info = SegmentInfo::new(self.appended_blocks.clone(), summary);
+        let (location, _) = seg_writer.write_segment_no_cache(&segment_info).await?;
+        Ok(Some(AppendOperationLogEntry::new(
+            location,
+            Arc::new(segment_info),
+        )))
+    }
+
+    fn block_writer(&self) -> BlockWriter<'_> {
+        BlockWriter::new(
+            &self.base_mutator.data_accessor,
+            &self.base_mutator.location_generator,
+        )
+        .with_column_ids(&self.column_ids)
+        .with_inverted_index_columns(&self.inverted_index_columns)
+    }
+}
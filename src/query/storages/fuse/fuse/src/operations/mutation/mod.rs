@@ -17,7 +17,9 @@ pub mod base_mutator;
 pub mod block_filter;
 mod compact_mutator;
 pub mod deletion_mutator;
+pub mod merge_into_mutator;
 pub mod recluster_mutator;
+pub mod update_mutator;
 
 pub use abort_operation::AbortOperation;
 pub use base_mutator::BaseMutator;
@@ -27,4 +29,6 @@ pub use compact_mutator::SegmentCompactMutator;
 pub use compact_mutator::SegmentCompactionState;
 pub use compact_mutator::SegmentCompactor;
 pub use deletion_mutator::DeletionMutator;
+pub use merge_into_mutator::MergeIntoMutator;
 pub use recluster_mutator::ReclusterMutator;
+pub use update_mutator::UpdateMutator;
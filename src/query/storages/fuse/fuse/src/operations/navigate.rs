@@ -16,15 +16,20 @@ use std::sync::Arc;
 
 use chrono::DateTime;
 use chrono::Utc;
+use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableStatistics;
+use common_storages_table_meta::meta::Statistics;
 use common_storages_table_meta::meta::TableSnapshot;
 use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
 use futures::TryStreamExt;
+use uuid::Uuid;
 
+use crate::io::write_meta;
 use crate::io::MetaReaders;
 use crate::io::SnapshotHistoryReader;
+use crate::operations::AppendOperationLogEntry;
 use crate::FuseTable;
 
 impl FuseTable {
@@ -115,13 +120,7 @@ impl FuseTable {
                 .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), loc);
 
             // 3. The statistics
-            let summary = &snapshot.summary;
-            table_info.meta.statistics = TableStatistics {
-                number_of_rows: summary.row_count,
-                data_bytes: summary.uncompressed_byte_size,
-                compressed_data_bytes: summary.compressed_byte_size,
-                index_data_bytes: summary.index_size,
-            };
+            table_info.meta.statistics = Self::table_statistics(&snapshot.summary);
 
             // let's instantiate it
             let read_only = true;
@@ -133,4 +132,96 @@ impl FuseTable {
             ))
         }
     }
+
+    /// Navigates to the data appended by `operations`, which is the latest commit on top of
+    /// the snapshot at `prev_location` ("" for an empty table).
+    ///
+    /// A detached snapshot, which only contains the appended segments, is written for the
+    /// returned read-only table, and it should be removed by `remove_detached_snapshot` once
+    /// the table is no longer used. Returns `None` if the latest commit is not the append.
+    pub async fn navigate_to_appended(
+        &self,
+        prev_location: &str,
+        operations: &[DataBlock],
+    ) -> Result<Option<Arc<FuseTable>>> {
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        let location = match snapshot.prev_snapshot_id {
+            Some((id, ver)) => self
+                .meta_location_generator
+                .snapshot_location_from_uuid(&id, ver)?,
+            None => "".to_string(),
+        };
+        if location != prev_location {
+            return Ok(None);
+        }
+
+        let append_log_entries = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
+        let (segments, summary) = FuseTable::merge_append_operations(&append_log_entries)?;
+        let appended = segments.iter().all(|segment| {
+            snapshot
+                .segments
+                .iter()
+                .any(|(location, _)| location == segment)
+        });
+        if !appended {
+            return Ok(None);
+        }
+
+        let segments = snapshot
+            .segments
+            .iter()
+            .filter(|(location, _)| segments.contains(location))
+            .cloned()
+            .collect();
+        let detached = TableSnapshot::new(
+            Uuid::new_v4(),
+            &snapshot.timestamp,
+            None,
+            snapshot.schema.clone(),
+            summary,
+            segments,
+            snapshot.cluster_key_meta.clone(),
+        );
+        let detached_location = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&detached.snapshot_id, detached.format_version())?;
+        let table_statistics = Self::table_statistics(&detached.summary);
+        write_meta(&self.operator, &detached_location, &detached).await?;
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = Arc::new(detached.schema.clone());
+        table_info
+            .meta
+            .options
+            .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), detached_location);
+        table_info.meta.statistics = table_statistics;
+
+        let read_only = true;
+        let fuse_tbl = FuseTable::do_create(table_info, read_only)?;
+        Ok(Some(fuse_tbl.into()))
+    }
+
+    /// Removes the detached snapshot written by `navigate_to_appended`.
+    pub async fn remove_detached_snapshot(&self) -> Result<()> {
+        if let Some(location) = self.snapshot_loc().await? {
+            self.operator.object(&location).delete().await?;
+        }
+        Ok(())
+    }
+
+    fn table_statistics(summary: &Statistics) -> TableStatistics {
+        TableStatistics {
+            number_of_rows: summary.row_count,
+            data_bytes: summary.uncompressed_byte_size,
+            compressed_data_bytes: summary.compressed_byte_size,
+            index_data_bytes: summary.index_size,
+        }
+    }
 }
@@ -19,13 +19,13 @@ use std::time::Instant;
 use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::PartStatistics;
 use common_catalog::plan::Partitions;
-use common_catalog::plan::Projection;
 use common_catalog::plan::PushDownInfo;
 use common_catalog::table_context::TableContext;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
 use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::TableSnapshot;
 use opendal::Operator;
@@ -37,6 +37,7 @@ use crate::fuse_part::ColumnLeaves;
 use crate::fuse_part::ColumnMeta;
 use crate::fuse_part::FusePartInfo;
 use crate::pruning::BlockPruner;
+use crate::ColumnMapping;
 use crate::FuseTable;
 
 impl FuseTable {
@@ -48,6 +49,10 @@ impl FuseTable {
     ) -> Result<(PartStatistics, Partitions)> {
         debug!("fuse table do read partitions, push downs:{:?}", push_downs);
 
+        if let Some(changes) = &self.changes {
+            return self.read_changes_partitions(ctx, push_downs, changes).await;
+        }
+
         let snapshot = self.read_table_snapshot().await?;
         match snapshot {
             Some(snapshot) => {
@@ -112,6 +117,7 @@ impl FuseTable {
             &ctx,
             dal,
             table_info.schema(),
+            &self.inverted_index_columns(),
             &push_downs,
             segments_location,
         )
@@ -139,10 +145,16 @@ impl FuseTable {
     ) -> Result<(PartStatistics, Partitions)> {
         let arrow_schema = schema.to_arrow();
         let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
+        let column_mapping = ColumnMapping::try_from_schema(&schema)?;
 
         let partitions_scanned = block_metas.len();
 
-        let (mut statistics, parts) = Self::to_partitions(&block_metas, &column_leaves, push_downs);
+        let (mut statistics, parts) = Self::to_partitions(
+            &block_metas,
+            &column_leaves,
+            column_mapping.as_ref(),
+            push_downs,
+        );
 
         // Update planner statistics.
         statistics.partitions_total = partitions_total;
@@ -160,6 +172,7 @@ impl FuseTable {
     pub fn to_partitions(
         blocks_metas: &[Arc<BlockMeta>],
         column_leaves: &ColumnLeaves,
+        column_mapping: Option<&ColumnMapping>,
         push_down: Option<PushDownInfo>,
     ) -> (PartStatistics, Partitions) {
         let limit = push_down
@@ -173,7 +186,18 @@ impl FuseTable {
             Some(extras) => match &extras.projection {
                 None => Self::all_columns_partitions(blocks_metas, limit),
                 Some(projection) => {
-                    Self::projection_partitions(blocks_metas, column_leaves, projection, limit)
+                    // columns of blocks are keyed by column id, which is the position of the
+                    // leaf column, unless the table schema has been altered.
+                    let column_ids = match column_mapping {
+                        None => column_leaves
+                            .get_by_projection(projection)
+                            .unwrap()
+                            .iter()
+                            .flat_map(|column| column.leaf_ids.iter().map(|id| *id as ColumnId))
+                            .collect(),
+                        Some(mapping) => mapping.projection_column_ids(column_leaves, projection),
+                    };
+                    Self::projection_partitions(blocks_metas, &column_ids, limit)
                 }
             },
         };
@@ -224,8 +248,7 @@ impl FuseTable {
 
     fn projection_partitions(
         metas: &[Arc<BlockMeta>],
-        column_leaves: &ColumnLeaves,
-        projection: &Projection,
+        column_ids: &[ColumnId],
         limit: usize,
     ) -> (PartStatistics, Partitions) {
         let mut statistics = PartStatistics::default_exact();
@@ -238,15 +261,12 @@ impl FuseTable {
         let mut remaining = limit;
 
         for block_meta in metas {
-            partitions.push(Self::projection_part(block_meta, column_leaves, projection));
+            partitions.push(Self::projection_part(block_meta, column_ids));
             let rows = block_meta.row_count as usize;
 
             statistics.read_rows += rows;
-            let columns = column_leaves.get_by_projection(projection).unwrap();
-            for column in &columns {
-                let indices = &column.leaf_ids;
-                for index in indices {
-                    let col_metas = &block_meta.col_metas[&(*index as u32)];
+            for column_id in column_ids {
+                if let Some(col_metas) = block_meta.col_metas.get(column_id) {
                     statistics.read_bytes += col_metas.len as usize;
                 }
             }
@@ -286,21 +306,14 @@ impl FuseTable {
         )
     }
 
-    fn projection_part(
-        meta: &BlockMeta,
-        column_leaves: &ColumnLeaves,
-        projection: &Projection,
-    ) -> PartInfoPtr {
-        let mut columns_meta = HashMap::with_capacity(projection.len());
-
-        let columns = column_leaves.get_by_projection(projection).unwrap();
-        for column in &columns {
-            let indices = &column.leaf_ids;
-            for index in indices {
-                let column_meta = &meta.col_metas[&(*index as u32)];
+    fn projection_part(meta: &BlockMeta, column_ids: &[ColumnId]) -> PartInfoPtr {
+        let mut columns_meta = HashMap::with_capacity(column_ids.len());
 
+        // columns added after the block was written are not stored in it
+        for column_id in column_ids {
+            if let Some(column_meta) = meta.col_metas.get(column_id) {
                 columns_meta.insert(
-                    *index,
+                    *column_id as usize,
                     ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
                 );
             }
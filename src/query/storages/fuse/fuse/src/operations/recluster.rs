@@ -68,6 +68,7 @@ impl FuseTable {
             &ctx,
             self.operator.clone(),
             schema,
+            &self.inverted_index_columns(),
             &push_downs,
             segments_locations,
         )
@@ -202,6 +203,8 @@ impl FuseTable {
             )
         })?;
 
+        let column_ids = self.leaf_column_ids()?;
+        let inverted_index_columns = self.inverted_index_columns();
         pipeline.add_sink(|input| {
             FuseTableSink::try_create(
                 input,
@@ -210,6 +213,8 @@ impl FuseTable {
                 self.operator.clone(),
                 self.meta_location_generator().clone(),
                 cluster_stats_gen.clone(),
+                column_ids.clone(),
+                inverted_index_columns.clone(),
                 None,
             )
         })?;
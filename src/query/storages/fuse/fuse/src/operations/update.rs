@@ -0,0 +1,269 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::plan::Expression;
+use common_catalog::plan::Projection;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::Column;
+use common_datavalues::Series;
+use common_exception::Result;
+use common_sql::evaluator::EvalNode;
+use common_sql::evaluator::Evaluator;
+use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::TableSnapshot;
+
+use crate::io::BlockReader;
+use crate::operations::mutation::block_filter::all_the_columns_ids;
+use crate::operations::mutation::block_filter::scatter_updated_column;
+use crate::operations::mutation::UpdateMutator;
+use crate::pruning::BlockPruner;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// UPDATE table SET col = expr, ... [WHERE filter]
+    ///
+    /// `update_list` contains the index of the column being updated in the table schema,
+    /// together with the expression evaluating to its new value.
+    /// `col_indices` are the indices of the columns referenced by the `filter`.
+    pub async fn do_update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<Expression>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, Expression)>,
+    ) -> Result<()> {
+        let snapshot_opt = self.read_table_snapshot().await?;
+
+        // check if table is empty
+        let snapshot = if let Some(val) = snapshot_opt {
+            val
+        } else {
+            // no snapshot, no update
+            return Ok(());
+        };
+
+        if snapshot.summary.row_count == 0 || update_list.is_empty() {
+            // empty snapshot, or nothing to assign, no update
+            return Ok(());
+        }
+
+        self.update_rows(ctx, &snapshot, filter, col_indices, update_list)
+            .await
+    }
+
+    async fn update_rows(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        snapshot: &Arc<TableSnapshot>,
+        filter: Option<Expression>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, Expression)>,
+    ) -> Result<()> {
+        let cluster_stats_gen = self.cluster_stats_gen()?;
+        let mut update_mutator = UpdateMutator::try_create(
+            ctx.clone(),
+            self.get_operator(),
+            self.meta_location_generator.clone(),
+            snapshot.clone(),
+            cluster_stats_gen,
+            self.leaf_column_ids()?,
+            self.inverted_index_columns(),
+        )?;
+
+        let schema = self.table_info.schema();
+        let push_downs = filter.as_ref().map(|filter| PushDownInfo {
+            projection: Some(Projection::Columns(col_indices)),
+            filters: vec![filter.clone()],
+            prewhere: None,
+            limit: None,
+            order_by: vec![],
+            stage: None,
+        });
+        let segments_location = snapshot.segments.clone();
+        let block_metas = BlockPruner::prune(
+            &ctx,
+            self.operator.clone(),
+            schema.clone(),
+            &self.inverted_index_columns(),
+            &push_downs,
+            segments_location,
+        )
+        .await?;
+
+        // if the cluster keys are touched, the blocks are no longer sorted by the cluster keys,
+        // the origin cluster statistics could not be kept.
+        let keep_cluster_stats = {
+            let mut cluster_key_columns = HashSet::new();
+            for expr in self.cluster_keys().iter() {
+                collect_column_names(expr, &mut cluster_key_columns);
+            }
+            update_list
+                .iter()
+                .all(|(idx, _)| !cluster_key_columns.contains(schema.field(*idx).name()))
+        };
+
+        let filter_node = filter
+            .as_ref()
+            .map(|expr| Evaluator::eval_expression(expr, schema.as_ref()))
+            .transpose()?;
+        let update_nodes = self.build_update_nodes(&update_list)?;
+
+        let reader = self.create_block_reader(Projection::Columns(all_the_columns_ids(self)))?;
+
+        // update block one by one.
+        for (seg_idx, block_meta) in block_metas {
+            if let Some(new_block) = update_block(
+                &ctx,
+                &reader,
+                &block_meta,
+                filter_node.as_ref(),
+                &update_nodes,
+            )
+            .await?
+            {
+                let origin_stats = if keep_cluster_stats {
+                    block_meta.cluster_stats.clone()
+                } else {
+                    None
+                };
+                update_mutator
+                    .replace_with(
+                        seg_idx,
+                        block_meta.location.clone(),
+                        origin_stats,
+                        new_block,
+                    )
+                    .await?;
+            }
+        }
+
+        let (segments, summary, abort_operation) = update_mutator.generate_segments().await?;
+        self.commit_mutation(
+            &ctx,
+            update_mutator.base_snapshot(),
+            segments,
+            summary,
+            abort_operation,
+        )
+        .await
+    }
+
+    // For each of the updated columns, build an expression of `cast(expr as col_type)`.
+    // The expressions are evaluated on the rows matching the filter only.
+    fn build_update_nodes(
+        &self,
+        update_list: &[(usize, Expression)],
+    ) -> Result<Vec<(usize, EvalNode)>> {
+        let schema = self.table_info.schema();
+        let mut nodes = Vec::with_capacity(update_list.len());
+        for (idx, expr) in update_list {
+            let target_type = schema.field(*idx).data_type().clone();
+            let value = if expr.data_type() != target_type {
+                Expression::Cast {
+                    input: Box::new(expr.clone()),
+                    target: target_type,
+                }
+            } else {
+                expr.clone()
+            };
+            nodes.push((*idx, Evaluator::eval_expression(&value, schema.as_ref())?));
+        }
+        Ok(nodes)
+    }
+}
+
+/// Applies the update to the block located by `block_meta`.
+///
+/// Returns `None` if none of the rows match the filter (false positive of the pruner).
+async fn update_block(
+    ctx: &Arc<dyn TableContext>,
+    reader: &Arc<BlockReader>,
+    block_meta: &BlockMeta,
+    filter: Option<&EvalNode>,
+    update_nodes: &[(usize, EvalNode)],
+) -> Result<Option<DataBlock>> {
+    let func_ctx = ctx.try_get_function_context()?;
+    let data_block = reader.read_with_block_meta(block_meta).await?;
+
+    // the rows to update, `None` if all the rows of the block are updated
+    let mut selection = None;
+    if let Some(filter) = filter {
+        let filter_result = filter.eval(&func_ctx, &data_block)?.vector;
+        let predicates = DataBlock::cast_to_nonull_boolean(&filter_result)?;
+
+        // shortcut, if predicates is const boolean (or can be cast to boolean)
+        if let Some(const_bool) = DataBlock::try_as_const_bool(&predicates)? {
+            if !const_bool {
+                // none of the rows should be updated
+                return Ok(None);
+            }
+        } else {
+            let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+            let unset_bits = boolean_col.values().unset_bits();
+            if unset_bits == boolean_col.len() {
+                // false positive, nothing to update indeed
+                return Ok(None);
+            }
+            if unset_bits != 0 {
+                selection = Some(boolean_col.values().clone());
+            }
+        }
+    }
+
+    // evaluate the new values on the matched rows only, so that the rows out of the
+    // filter never fail the evaluation, e.g. `SET a = CAST(s AS INT) WHERE s REGEXP '^[0-9]+$'`
+    let matched_block = match &selection {
+        Some(selection) => DataBlock::filter_block_with_bool_column(
+            data_block.clone(),
+            &BooleanColumn::from_arrow_data(selection.clone()),
+        )?,
+        None => data_block.clone(),
+    };
+
+    let mut columns = data_block.columns().to_vec();
+    for (idx, node) in update_nodes {
+        let updated = node.eval(&func_ctx, &matched_block)?.vector;
+        columns[*idx] = match &selection {
+            Some(selection) => scatter_updated_column(&columns[*idx], &updated, selection)?,
+            None => updated.convert_full_column(),
+        };
+    }
+
+    Ok(Some(DataBlock::create(
+        data_block.schema().clone(),
+        columns,
+    )))
+}
+
+pub(crate) fn collect_column_names(expr: &Expression, names: &mut HashSet<String>) {
+    match expr {
+        Expression::IndexedVariable { name, .. } => {
+            names.insert(name.clone());
+        }
+        Expression::Function { args, .. } => {
+            for arg in args {
+                collect_column_names(arg, names);
+            }
+        }
+        Expression::Cast { input, .. } => collect_column_names(input, names),
+        Expression::Constant { .. } => {}
+    }
+}
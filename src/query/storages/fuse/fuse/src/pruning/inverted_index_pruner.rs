@@ -0,0 +1,111 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Expression;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_sql::executor::ExpressionOp;
+use common_storages_index::BlockInvertedIndex;
+use common_storages_index::FilterEvalResult;
+use common_storages_table_meta::meta::BlockMeta;
+use opendal::Operator;
+
+use crate::io::BlockFilterReader;
+use crate::pruning::pruner::Pruner;
+
+/// Prunes the blocks that do not contain the tokens of `MATCH` and `LIKE`,
+/// by the inverted indexes of the string columns.
+struct InvertedIndexPruner {
+    ctx: Arc<dyn TableContext>,
+
+    /// columns that should be loaded from the inverted index
+    index_columns: Vec<String>,
+
+    /// the expression that would be evaluate
+    filter_expression: Expression,
+
+    /// the data accessor
+    dal: Operator,
+}
+
+#[async_trait::async_trait]
+impl Pruner for InvertedIndexPruner {
+    async fn should_keep(&self, block_meta: &BlockMeta) -> bool {
+        let index_location = match &block_meta.inverted_index_location {
+            Some(loc) => loc,
+            // blocks written before the index was created
+            None => return true,
+        };
+
+        let maybe_index = index_location
+            .read_filter(
+                self.ctx.clone(),
+                self.dal.clone(),
+                &self.index_columns,
+                block_meta.inverted_index_size,
+            )
+            .await
+            .and_then(|filter| {
+                BlockInvertedIndex::from_filter_block(filter.into_data())
+                    .eval(&self.filter_expression)
+            });
+
+        match maybe_index {
+            Ok(result) => result != FilterEvalResult::False,
+            Err(e) => {
+                // the index of the block may not have all the columns, if it was written
+                // before some of the indexes (or the filters of n-grams) were created.
+                // keep the block in that case.
+                tracing::debug!("failed to apply inverted index, returning true. {}", e);
+                true
+            }
+        }
+    }
+}
+
+/// Try to build a pruner of the inverted indexes.
+///
+/// None will be returned, if there are no `MATCH` or `LIKE` conditions on the indexed columns.
+pub fn new_inverted_index_pruner(
+    ctx: &Arc<dyn TableContext>,
+    filter_exprs: Option<&[Expression]>,
+    inverted_index_columns: &[String],
+    dal: Operator,
+) -> Result<Option<Arc<dyn Pruner + Send + Sync>>> {
+    let exprs = match filter_exprs {
+        Some(exprs) if !exprs.is_empty() && !inverted_index_columns.is_empty() => exprs,
+        _ => return Ok(None),
+    };
+
+    let mut expr = exprs[0].clone();
+    for item in &exprs[1..] {
+        expr = expr.and(item)?;
+    }
+
+    // the conditions on the columns which are not indexed are not applicable
+    let index_columns = BlockInvertedIndex::query_filter_columns(&expr, inverted_index_columns);
+    if index_columns.is_empty() {
+        tracing::debug!("no token queries on the indexed columns found");
+        return Ok(None);
+    }
+
+    Ok(Some(Arc::new(InvertedIndexPruner {
+        ctx: ctx.clone(),
+        index_columns,
+        filter_expression: expr,
+        dal,
+    })))
+}
@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod inverted_index_pruner;
 mod limiter;
 mod pruner;
 mod pruning_executor;
@@ -21,15 +21,18 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_sql::executor::ExpressionOp;
 use common_storages_index::BlockFilter;
+use common_storages_table_meta::meta::BlockMeta;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::Location;
 use opendal::Operator;
 
 use crate::io::BlockFilterReader;
+use crate::ColumnMapping;
 
 #[async_trait::async_trait]
 pub trait Pruner {
-    // returns ture, if target should NOT be pruned (false positive allowed)
-    async fn should_keep(&self, index_location: &Option<Location>, index_length: u64) -> bool;
+    // returns ture, if the block should NOT be pruned (false positive allowed)
+    async fn should_keep(&self, block_meta: &BlockMeta) -> bool;
 }
 
 struct FilterPruner {
@@ -46,6 +49,9 @@ struct FilterPruner {
 
     /// the schema of data being indexed
     data_schema: DataSchemaRef,
+
+    /// ids of the indexed columns, if the table schema has been altered
+    index_column_ids: Option<Vec<ColumnId>>,
 }
 
 impl FilterPruner {
@@ -55,6 +61,7 @@ impl FilterPruner {
         filter_expression: Expression,
         dal: Operator,
         data_schema: DataSchemaRef,
+        index_column_ids: Option<Vec<ColumnId>>,
     ) -> Self {
         Self {
             ctx,
@@ -62,6 +69,19 @@ impl FilterPruner {
             filter_expression,
             dal,
             data_schema,
+            index_column_ids,
+        }
+    }
+
+    // returns false, if the filter index of the block should not be used
+    fn is_applicable(&self, block_meta: &BlockMeta) -> bool {
+        // filter index is keyed by column name, blocks written before the indexed
+        // columns were renamed or re-added have the index of other columns.
+        match &self.index_column_ids {
+            None => true,
+            Some(column_ids) => column_ids
+                .iter()
+                .all(|column_id| block_meta.col_metas.contains_key(column_id)),
         }
     }
 }
@@ -69,8 +89,11 @@ impl FilterPruner {
 use self::util::*;
 #[async_trait::async_trait]
 impl Pruner for FilterPruner {
-    async fn should_keep(&self, index_location: &Option<Location>, index_length: u64) -> bool {
-        if let Some(loc) = index_location {
+    async fn should_keep(&self, block_meta: &BlockMeta) -> bool {
+        if !self.is_applicable(block_meta) {
+            return true;
+        }
+        if let Some(loc) = &block_meta.bloom_filter_index_location {
             // load filter, and try pruning according to filter expression
             match should_keep_by_filter(
                 self.ctx.clone(),
@@ -79,7 +102,7 @@ impl Pruner for FilterPruner {
                 &self.filter_expression,
                 &self.index_columns,
                 loc,
-                index_length,
+                block_meta.bloom_filter_index_size,
             )
             .await
             {
@@ -128,12 +151,23 @@ pub fn new_filter_pruner(
                 .map(|n| BlockFilter::build_filter_column_name(n))
                 .collect();
 
+            let index_column_ids = match ColumnMapping::try_from_schema(schema)? {
+                None => None,
+                Some(mapping) => Some(
+                    point_query_cols
+                        .iter()
+                        .map(|n| Ok(mapping.field(n)?.column_id))
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+            };
+
             return Ok(Some(Arc::new(FilterPruner::new(
                 ctx.clone(),
                 filter_block_cols,
                 expr,
                 dal,
                 schema.clone(),
+                index_column_ids,
             ))));
         } else {
             tracing::debug!("no point filters found, using NonPruner");
@@ -184,21 +218,15 @@ mod util {
 
     impl ExpressionVisitor for PointQueryVisitor {
         fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
-            // 1. only binary op "=" is considered, which is NOT enough
-            // 2. should combine this logic with Filter
+            // keep the same logic with `BlockFilter::eval`, the point queries under `NOT`
+            // can not be evaluated by the filter.
+            if let Some(column) = BlockFilter::point_query_column(expr) {
+                self.columns.insert(column.to_string());
+                return Ok(Recursion::Stop(self));
+            }
             match expr {
-                Expression::Function { name, args, .. }
-                    if name.as_str() == "=" && args.len() == 2 =>
-                {
-                    match (&args[0], &args[1]) {
-                        (Expression::IndexedVariable { name, .. }, Expression::Constant { .. })
-                        | (Expression::Constant { .. }, Expression::IndexedVariable { name, .. }) =>
-                        {
-                            self.columns.insert(name.clone());
-                            Ok(Recursion::Stop(self))
-                        }
-                        _ => Ok(Recursion::Continue(self)),
-                    }
+                Expression::Function { name, .. } if name.to_lowercase() == "not" => {
+                    Ok(Recursion::Stop(self))
                 }
                 _ => Ok(Recursion::Continue(self)),
             }
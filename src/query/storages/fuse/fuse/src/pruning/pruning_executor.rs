@@ -33,6 +33,7 @@ use opendal::Operator;
 use tracing::warn;
 use tracing::Instrument;
 
+use super::inverted_index_pruner;
 use super::pruner;
 use crate::io::MetaReaders;
 use crate::pruning::limiter;
@@ -48,7 +49,7 @@ type SegmentPruningJoinHandles = Vec<JoinHandle<Result<Vec<(SegmentIndex, Arc<Bl
 struct PruningContext {
     limiter: LimiterPruner,
     range_pruner: Arc<dyn RangePruner + Send + Sync>,
-    filter_pruner: Option<Arc<dyn Pruner + Send + Sync>>,
+    filter_pruners: Vec<Arc<dyn Pruner + Send + Sync>>,
     rt: Arc<Runtime>,
     semaphore: Arc<Semaphore>,
 }
@@ -61,6 +62,7 @@ impl BlockPruner {
         ctx: &Arc<dyn TableContext>,
         dal: Operator,
         schema: DataSchemaRef,
+        inverted_index_columns: &[String],
         push_down: &Option<PushDownInfo>,
         segment_locs: Vec<Location>,
     ) -> Result<Vec<(SegmentIndex, Arc<BlockMeta>)>> {
@@ -90,6 +92,19 @@ impl BlockPruner {
         let filter_pruner =
             pruner::new_filter_pruner(ctx, filter_expressions, &schema, dal.clone())?;
 
+        // prepare the inverted index.
+        // None will be returned, if there are no token queries on the indexed columns.
+        let inverted_index_pruner = inverted_index_pruner::new_inverted_index_pruner(
+            ctx,
+            filter_expressions,
+            inverted_index_columns,
+            dal.clone(),
+        )?;
+        let filter_pruners = filter_pruner
+            .into_iter()
+            .chain(inverted_index_pruner)
+            .collect();
+
         // 2. constraint the degree of parallelism
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         let max_concurrency = {
@@ -116,7 +131,7 @@ impl BlockPruner {
         let pruning_ctx = Arc::new(PruningContext {
             limiter: limiter.clone(),
             range_pruner: range_pruner.clone(),
-            filter_pruner,
+            filter_pruners,
             rt: pruning_runtime.clone(),
             semaphore: semaphore.clone(),
         });
@@ -186,8 +201,8 @@ impl BlockPruner {
             &segment_info.summary.col_stats,
             segment_info.summary.row_count,
         ) {
-            if let Some(filter_pruner) = &pruning_ctx.filter_pruner {
-                Self::prune_blocks(&pruning_ctx, filter_pruner, segment_idx, &segment_info).await?
+            if !pruning_ctx.filter_pruners.is_empty() {
+                Self::prune_blocks(&pruning_ctx, segment_idx, &segment_info).await?
             } else {
                 // if no available filter pruners, just prune the blocks by
                 // using zone map index, and do not spawn async tasks
@@ -202,7 +217,6 @@ impl BlockPruner {
     #[tracing::instrument(level = "debug", skip_all)]
     async fn prune_blocks(
         pruning_ctx: &Arc<PruningContext>,
-        filter_pruner: &Arc<dyn Pruner + Send + Sync>,
         segment_idx: SegmentIndex,
         segment_info: &SegmentInfo,
     ) -> Result<Vec<(SegmentIndex, Arc<BlockMeta>)>> {
@@ -226,14 +240,18 @@ impl BlockPruner {
                 {
                     // not pruned by block zone map index,
                     let ctx = pruning_ctx.clone();
-                    let filter_pruner = filter_pruner.clone();
-                    let index_location = block_meta.bloom_filter_index_location.clone();
-                    let index_size = block_meta.bloom_filter_index_size;
+                    let block_meta = block_meta.clone();
                     let v: BlockPruningFuture = Box::new(move |permit: OwnedSemaphorePermit| {
                         Box::pin(async move {
                             let _permit = permit;
-                            let keep = filter_pruner.should_keep(&index_location, index_size).await
-                                && ctx.limiter.within_limit(row_count);
+                            let mut keep = true;
+                            for filter_pruner in &ctx.filter_pruners {
+                                if !filter_pruner.should_keep(&block_meta).await {
+                                    keep = false;
+                                    break;
+                                }
+                            }
+                            let keep = keep && ctx.limiter.within_limit(row_count);
                             (block_idx, keep)
                         })
                     });
@@ -19,8 +19,11 @@ use common_catalog::table_context::TableContext;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_storages_index::RangeFilter;
+use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::StatisticsOfColumns;
 
+use crate::ColumnMapping;
+
 pub trait RangePruner {
     // returns ture, if target should NOT be pruned (false positive allowed)
     fn should_keep(&self, input: &StatisticsOfColumns, row_count: u64) -> bool;
@@ -42,6 +45,20 @@ impl RangePruner for KeepFalse {
     }
 }
 
+// Statistics of blocks are keyed by column id, once the table schema has been altered,
+// they should be re-keyed by the position of leaf columns, which the range filter expects.
+struct ColumnIdRangePruner {
+    inner: RangeFilter,
+    candidates: Vec<Vec<ColumnId>>,
+}
+
+impl RangePruner for ColumnIdRangePruner {
+    fn should_keep(&self, stats: &StatisticsOfColumns, row_count: u64) -> bool {
+        let stats = ColumnMapping::remap_to_leaf_positions(stats, &self.candidates);
+        self.inner.should_keep(&stats, row_count)
+    }
+}
+
 impl RangePruner for RangeFilter {
     fn should_keep(&self, stats: &StatisticsOfColumns, row_count: u64) -> bool {
         match self.eval(stats, row_count) {
@@ -71,7 +88,13 @@ pub fn new_range_pruner<'a>(
                         Arc::new(KeepFalse)
                     }
                 }
-                Err(_) => Arc::new(range_filter),
+                Err(_) => match ColumnMapping::try_from_schema(schema)? {
+                    None => Arc::new(range_filter),
+                    Some(mapping) => Arc::new(ColumnIdRangePruner {
+                        inner: range_filter,
+                        candidates: mapping.leaf_column_candidates(schema)?,
+                    }),
+                },
             }
         }
         _ => Arc::new(KeepTrue),
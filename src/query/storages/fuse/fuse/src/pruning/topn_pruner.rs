@@ -16,10 +16,10 @@ use std::sync::Arc;
 
 use common_catalog::plan::Expression;
 use common_datavalues::DataSchemaRef;
-use common_exception::ErrorCode;
 use common_exception::Result;
 use common_storages_table_meta::meta::BlockMeta;
-use common_storages_table_meta::meta::ColumnStatistics;
+
+use crate::ColumnMapping;
 
 pub(crate) struct TopNPrunner {
     schema: DataSchemaRef,
@@ -72,18 +72,24 @@ impl TopNPrunner {
             return Ok(metas);
         };
 
-        let mut id_stats = metas
-            .iter()
-            .map(|(id, meta)| {
-                let stat = meta.col_stats.get(&sort_idx).ok_or_else(|| {
-                    ErrorCode::UnknownException(format!(
-                        "Unable to get the colStats by ColumnId: {}",
-                        sort_idx
-                    ))
-                })?;
-                Ok((*id, stat.clone(), meta.clone()))
-            })
-            .collect::<Result<Vec<(usize, ColumnStatistics, Arc<BlockMeta>)>>>()?;
+        // stats of blocks are keyed by column id, once the table schema has been altered
+        let column_ids = match ColumnMapping::try_from_schema(&self.schema)? {
+            None => vec![sort_idx],
+            Some(mapping) => mapping
+                .leaf_column_candidates(&self.schema)?
+                .get(sort_idx as usize)
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        let mut id_stats = Vec::with_capacity(metas.len());
+        for (id, meta) in &metas {
+            match column_ids.iter().find_map(|id| meta.col_stats.get(id)) {
+                Some(stat) => id_stats.push((*id, stat.clone(), meta.clone())),
+                // blocks written before the column was added have no stats of it
+                None => return Ok(metas),
+            }
+        }
 
         id_stats.sort_by(|a, b| {
             if a.1.null_count + b.1.null_count != 0 && *nulls_first {
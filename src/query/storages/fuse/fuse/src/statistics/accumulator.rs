@@ -20,6 +20,7 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_storages_table_meta::meta::BlockMeta;
 use common_storages_table_meta::meta::ColumnMeta;
+use common_storages_table_meta::meta::DistributionOfColumns;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::StatisticsOfColumns;
 use common_storages_table_meta::meta::Versioned;
@@ -31,6 +32,7 @@ use crate::statistics::block_statistics::BlockStatistics;
 pub struct StatisticsAccumulator {
     pub blocks_metas: Vec<Arc<BlockMeta>>,
     pub blocks_statistics: Vec<StatisticsOfColumns>,
+    pub blocks_distributions: Vec<DistributionOfColumns>,
     pub summary_row_count: u64,
     pub summary_block_count: u64,
     pub in_memory_size: u64,
@@ -58,6 +60,8 @@ impl StatisticsAccumulator {
             block_statistics,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            None,
+            0,
         )
     }
 
@@ -68,6 +72,8 @@ impl StatisticsAccumulator {
     ) -> Result<()> {
         let bloom_filter_index_location = block_meta.bloom_filter_index_location;
         let bloom_filter_index_size = block_meta.bloom_filter_index_size;
+        let inverted_index_location = block_meta.inverted_index_location;
+        let inverted_index_size = block_meta.inverted_index_size;
         let file_size = block_meta.file_size;
         let col_metas = block_meta.col_metas;
 
@@ -77,6 +83,8 @@ impl StatisticsAccumulator {
             block_statistics,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            inverted_index_location,
+            inverted_index_size,
         )
     }
 
@@ -84,21 +92,30 @@ impl StatisticsAccumulator {
         super::reduce_block_statistics(&self.blocks_statistics)
     }
 
-    fn add(
+    pub fn summary_distributions(&self) -> DistributionOfColumns {
+        super::reduce_block_distributions(&self.blocks_distributions)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add(
         &mut self,
         file_size: u64,
         column_meta: HashMap<u32, ColumnMeta>,
         block_statistics: BlockStatistics,
         bloom_filter_index_location: Option<Location>,
         bloom_filter_index_size: u64,
+        inverted_index_location: Option<Location>,
+        inverted_index_size: u64,
     ) -> Result<()> {
         self.file_size += file_size;
-        self.index_size += bloom_filter_index_size;
+        self.index_size += bloom_filter_index_size + inverted_index_size;
         self.summary_block_count += 1;
         self.in_memory_size += block_statistics.block_bytes_size;
         self.summary_row_count += block_statistics.block_rows_size;
         self.blocks_statistics
             .push(block_statistics.block_column_statistics.clone());
+        self.blocks_distributions
+            .push(block_statistics.block_column_distributions);
 
         let row_count = block_statistics.block_rows_size;
         let block_size = block_statistics.block_bytes_size;
@@ -106,7 +123,7 @@ impl StatisticsAccumulator {
         let data_location = (block_statistics.block_file_location, DataBlock::VERSION);
         let cluster_stats = block_statistics.block_cluster_statistics;
 
-        self.blocks_metas.push(Arc::new(BlockMeta::new(
+        let mut block_meta = BlockMeta::new(
             row_count,
             block_size,
             file_size,
@@ -116,7 +133,10 @@ impl StatisticsAccumulator {
             data_location,
             bloom_filter_index_location,
             bloom_filter_index_size,
-        )));
+        );
+        block_meta.inverted_index_location = inverted_index_location;
+        block_meta.inverted_index_size = inverted_index_size;
+        self.blocks_metas.push(Arc::new(block_meta));
 
         Ok(())
     }
@@ -18,7 +18,9 @@ use common_datablocks::DataBlock;
 use common_storages_table_meta::meta::ClusterStatistics;
 use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::ColumnStatistics;
+use common_storages_table_meta::meta::DistributionOfColumns;
 
+use crate::statistics::column_distribution;
 use crate::statistics::column_statistic;
 
 pub struct BlockStatistics {
@@ -27,6 +29,7 @@ pub struct BlockStatistics {
     pub block_file_location: String,
     pub block_column_statistics: HashMap<ColumnId, ColumnStatistics>,
     pub block_cluster_statistics: Option<ClusterStatistics>,
+    pub block_column_distributions: DistributionOfColumns,
 }
 
 impl BlockStatistics {
@@ -41,6 +44,7 @@ impl BlockStatistics {
             block_bytes_size: data_block.memory_size() as u64,
             block_column_statistics: column_statistic::gen_columns_statistics(data_block)?,
             block_cluster_statistics: cluster_stats,
+            block_column_distributions: column_distribution::gen_columns_distributions(data_block)?,
         })
     }
 }
@@ -0,0 +1,88 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_storages_index::MinMaxIndex;
+use common_storages_index::SupportedType;
+use common_storages_table_meta::meta::ColumnDistribution;
+use common_storages_table_meta::meta::DistinctSketch;
+use common_storages_table_meta::meta::DistributionOfColumns;
+use common_storages_table_meta::meta::Histogram;
+use siphasher::sip::SipHasher24;
+
+use crate::statistics::traverse;
+
+// At most this number of values of a block are sampled to build the histogram.
+const HISTOGRAM_MAX_SAMPLES: usize = 4096;
+
+pub fn gen_columns_distributions(data_block: &DataBlock) -> Result<DistributionOfColumns> {
+    let mut distributions = DistributionOfColumns::new();
+
+    let leaves = traverse::traverse_columns_dfs(data_block.columns())?;
+
+    for (idx, col) in leaves.iter().enumerate() {
+        let col_data_type = col.data_type();
+        if !MinMaxIndex::is_supported_type(&col_data_type) {
+            continue;
+        }
+
+        // histograms are only useful for the range predicates of numbers and date/times
+        let type_id = remove_nullable(&col_data_type).data_type_id();
+        let with_histogram = type_id.is_numeric() || type_id.is_date_or_date_time();
+        let sample_step = ((col.len() + HISTOGRAM_MAX_SAMPLES - 1) / HISTOGRAM_MAX_SAMPLES).max(1);
+
+        let mut distinct_sketch = DistinctSketch::new();
+        let mut samples = vec![];
+        let mut num_non_null = 0;
+        for row in 0..col.len() {
+            let value = col.get(row);
+            if value.is_null() {
+                continue;
+            }
+
+            let mut hasher = SipHasher24::new();
+            value.hash(&mut hasher);
+            distinct_sketch.add_hash(hasher.finish());
+
+            if with_histogram && num_non_null % sample_step == 0 {
+                samples.push(value);
+            }
+            num_non_null += 1;
+        }
+
+        let histogram = if with_histogram {
+            samples.sort();
+            let weight = if samples.is_empty() {
+                1.0
+            } else {
+                num_non_null as f64 / samples.len() as f64
+            };
+            Some(Histogram::from_sorted_values(&samples, weight))
+        } else {
+            None
+        };
+
+        distributions.insert(idx as u32, ColumnDistribution {
+            distinct_sketch,
+            histogram,
+        });
+    }
+    Ok(distributions)
+}
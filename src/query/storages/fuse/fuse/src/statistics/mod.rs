@@ -15,16 +15,19 @@
 pub mod accumulator;
 mod block_statistics;
 mod cluster_statistics;
+mod column_distribution;
 mod column_statistic;
 pub mod reducers;
 
 pub use accumulator::StatisticsAccumulator;
 pub use block_statistics::BlockStatistics;
 pub use cluster_statistics::ClusterStatsGenerator;
+pub use column_distribution::gen_columns_distributions;
 pub use column_statistic::gen_columns_statistics;
 pub use column_statistic::traverse;
 pub use column_statistic::Trim;
 pub use column_statistic::STATS_REPLACEMENT_CHAR;
 pub use column_statistic::STATS_STRING_PREFIX_LEN;
 pub use reducers::merge_statistics;
+pub use reducers::reduce_block_distributions;
 pub use reducers::reduce_block_statistics;
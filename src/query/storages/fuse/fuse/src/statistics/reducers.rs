@@ -20,6 +20,7 @@ use common_exception::Result;
 use common_storages_table_meta::meta::BlockMeta;
 use common_storages_table_meta::meta::ColumnId;
 use common_storages_table_meta::meta::ColumnStatistics;
+use common_storages_table_meta::meta::DistributionOfColumns;
 use common_storages_table_meta::meta::Statistics;
 use common_storages_table_meta::meta::StatisticsOfColumns;
 
@@ -42,9 +43,18 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
     // Reduce the `Vec<&ColumnStatistics` into ColumnStatistics`, i.e.:
     // from : `HashMap<ColumnId, Vec<&ColumnStatistics>)>`
     // to   : `type BlockStatistics = HashMap<ColumnId, ColumnStatistics>`
+    //
+    // Columns missing from some of the (non-empty) statistics are dropped, the reduced
+    // statistics would not cover the blocks that have no statistics of them, e.g. blocks
+    // written before the column was added by altering the table.
     let len = stats_of_columns.len();
+    let num_non_empty = stats_of_columns
+        .iter()
+        .filter(|item| !item.borrow().is_empty())
+        .count();
     col_to_stats_lit
         .iter()
+        .filter(|(_, stats)| stats.len() == num_non_empty)
         .try_fold(HashMap::with_capacity(len), |mut acc, (id, stats)| {
             let mut min_stats = Vec::with_capacity(stats.len());
             let mut max_stats = Vec::with_capacity(stats.len());
@@ -89,6 +99,51 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
         })
 }
 
+/// Reduces the distributions of the columns of the blocks.
+///
+/// Like `reduce_block_statistics`, columns missing from some of the (non-empty)
+/// distributions are dropped.
+pub fn reduce_block_distributions<T: Borrow<DistributionOfColumns>>(
+    distributions: &[T],
+) -> DistributionOfColumns {
+    let mut non_empty = distributions
+        .iter()
+        .map(|item| item.borrow())
+        .filter(|item| !item.is_empty());
+    let mut reduced = match non_empty.next() {
+        None => return DistributionOfColumns::new(),
+        Some(first) => first.clone(),
+    };
+    for item in non_empty {
+        reduced.retain(|id, _| item.contains_key(id));
+        for (id, distribution) in reduced.iter_mut() {
+            distribution.merge(&item[id]);
+        }
+    }
+    reduced
+}
+
+// The distributions are unknown if either side has rows without distributions,
+// e.g. the segments rewritten by mutations, until the table is analyzed.
+fn merge_column_distributions(l: &Statistics, r: &Statistics) -> DistributionOfColumns {
+    if l.row_count == 0 {
+        r.col_distributions.clone()
+    } else if r.row_count == 0 {
+        l.col_distributions.clone()
+    } else {
+        l.col_distributions
+            .iter()
+            .filter_map(|(id, distribution)| {
+                r.col_distributions.get(id).map(|other| {
+                    let mut distribution = distribution.clone();
+                    distribution.merge(other);
+                    (*id, distribution)
+                })
+            })
+            .collect()
+    }
+}
+
 pub fn merge_statistics(l: &Statistics, r: &Statistics) -> Result<Statistics> {
     let s = Statistics {
         row_count: l.row_count + r.row_count,
@@ -97,11 +152,13 @@ pub fn merge_statistics(l: &Statistics, r: &Statistics) -> Result<Statistics> {
         compressed_byte_size: l.compressed_byte_size + r.compressed_byte_size,
         index_size: l.index_size + r.index_size,
         col_stats: reduce_block_statistics(&[&l.col_stats, &r.col_stats])?,
+        col_distributions: merge_column_distributions(l, r),
     };
     Ok(s)
 }
 
 pub fn merge_statistics_mut(l: &mut Statistics, r: &Statistics) -> Result<()> {
+    l.col_distributions = merge_column_distributions(l, r);
     l.row_count += r.row_count;
     l.block_count += r.block_count;
     l.uncompressed_byte_size += r.uncompressed_byte_size;
@@ -132,7 +189,7 @@ pub fn reduce_block_metas<T: Borrow<BlockMeta>>(block_metas: &[T]) -> Result<Sta
         block_count += 1;
         uncompressed_byte_size += b.block_size;
         compressed_byte_size += b.file_size;
-        index_size += b.bloom_filter_index_size;
+        index_size += b.bloom_filter_index_size + b.inverted_index_size;
     });
 
     let stats = block_metas
@@ -148,5 +205,7 @@ pub fn reduce_block_metas<T: Borrow<BlockMeta>>(block_metas: &[T]) -> Result<Sta
         compressed_byte_size,
         index_size,
         col_stats: merged_col_stats,
+        // block metas carry no distributions
+        col_distributions: DistributionOfColumns::new(),
     })
 }
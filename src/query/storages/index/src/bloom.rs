@@ -19,6 +19,8 @@ use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::check_pattern_type;
+use common_functions::scalars::PatternType;
 
 use crate::filters::Filter;
 use crate::filters::FilterBuilder;
@@ -26,6 +28,9 @@ use crate::filters::Xor8Builder;
 use crate::filters::Xor8Filter;
 use crate::SupportedType;
 
+/// The max number of the strings probed for a point query on the lower case of a column.
+const MAX_LOWER_PREIMAGES: usize = 1 << 16;
+
 /// BlockFilter represents multiple per-column filters(bloom filter or xor filter etc) for data block.
 ///
 /// By default we create a filter per column for a parquet data file. For columns whose data_type
@@ -176,6 +181,31 @@ impl BlockFilter {
         }
     }
 
+    /// Like `find`, but the result is False only if none of the targets shows up in the filter.
+    /// The filter is decoded once for all the targets.
+    pub fn find_any(
+        &self,
+        column_name: &str,
+        targets: impl IntoIterator<Item = DataValue>,
+        typ: &DataTypeImpl,
+    ) -> Result<FilterEvalResult> {
+        let filter_column = Self::build_filter_column_name(column_name);
+        if !self.filter_block.schema().has_field(&filter_column)
+            || !Xor8Filter::is_supported_type(typ)
+        {
+            return Ok(FilterEvalResult::NotApplicable);
+        }
+
+        let filter_bytes = self.filter_block.first(&filter_column)?.as_string()?;
+        let (filter, _size) = Xor8Filter::from_bytes(&filter_bytes)?;
+        for target in targets {
+            if !target.is_null() && filter.contains(&target) {
+                return Ok(FilterEvalResult::Maybe);
+            }
+        }
+        Ok(FilterEvalResult::False)
+    }
+
     /// Returns false when the expression must be false, otherwise true.
     /// The 'true' doesn't really mean the expression is true, but 'maybe true'.
     /// That is to say, you still need the load all data and run the execution.
@@ -188,13 +218,21 @@ impl BlockFilter {
     /// This happens when the data doesn't show up in the filter.
     ///
     /// Otherwise return either Maybe or NotApplicable.
+    ///
+    /// The filters only hold the values of the columns, so the operand of a point query must be
+    /// a column, or an expression of a column whose inputs giving the value are known, like
+    /// an injective cast of it, or "lower(name) = 'alice'" whose inputs are the case variants
+    /// of 'alice'. Point queries on other expressions of a column are NotApplicable.
     #[tracing::instrument(level = "debug", name = "block_filter_index_eval", skip_all)]
     pub fn eval(&self, expr: &Expression) -> Result<FilterEvalResult> {
-        // TODO: support multiple columns and other ops like 'in' ...
+        // `NOT IN` can not be evaluated by the filter, which only tells the absence of values,
+        // it is left to the range filter.
         match expr {
             Expression::Function { name, args, .. } if args.len() == 2 => {
                 match name.to_lowercase().as_str() {
                     "=" => self.eval_equivalent_expression(&args[0], &args[1]),
+                    "in" => self.eval_in_expression(&args[0], &args[1]),
+                    "like" => self.eval_like_expression(&args[0], &args[1]),
                     "and" => self.eval_logical_and(&args[0], &args[1]),
                     "or" => self.eval_logical_or(&args[0], &args[1]),
                     _ => Ok(FilterEvalResult::NotApplicable),
@@ -204,32 +242,178 @@ impl BlockFilter {
         }
     }
 
+    /// Returns the name of the column, if the expression is a point query which could
+    /// be evaluated by the filter, like "name = 'Alice'" or "age IN (20, 30)".
+    pub fn point_query_column(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match (name.to_lowercase().as_str(), &args[0], &args[1]) {
+                    ("=", operand, Expression::Constant { .. })
+                    | ("=", Expression::Constant { .. }, operand)
+                    | (
+                        "in",
+                        operand,
+                        Expression::Constant {
+                            value: DataValue::Struct(_),
+                            ..
+                        },
+                    ) => Self::column_of_point_operand(operand),
+                    ("like", operand, Expression::Constant { value, .. })
+                        if Self::like_pattern_as_string(value).is_some() =>
+                    {
+                        Self::column_of_point_operand(operand)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // The operand of a point query is either a column, or an injective cast of a column,
+    // e.g. "CAST(age AS BIGINT) = 20", which is introduced by the type coercion, or the lower
+    // case of a column, e.g. "LOWER(name) = 'alice'".
+    fn column_of_point_operand(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::IndexedVariable { name, .. } => Some(name.as_str()),
+            Expression::Cast { input, target } if is_injective_cast(&input.data_type(), target) => {
+                Self::column_of_point_operand(input)
+            }
+            Expression::Function { name, args, .. } => match Self::lower_of_column(name, args) {
+                Some((column_name, _)) => Some(column_name),
+                None => None,
+            },
+            _ => None,
+        }
+    }
+
+    // The column and its type of the expression "LOWER(column)" on a string column.
+    fn lower_of_column<'a>(
+        name: &str,
+        args: &'a [Expression],
+    ) -> Option<(&'a str, &'a DataTypeImpl)> {
+        match (name.to_lowercase().as_str(), args) {
+            ("lower" | "lcase", [Expression::IndexedVariable { name, data_type }])
+                if remove_nullable(data_type).data_type_id() == TypeID::String =>
+            {
+                Some((name.as_str(), data_type))
+            }
+            _ => None,
+        }
+    }
+
+    // The string that a LIKE pattern without wildcards and escapes matches exactly.
+    fn like_pattern_as_string(pattern: &DataValue) -> Option<DataValue> {
+        match pattern {
+            DataValue::String(bytes)
+                if !bytes.contains(&b'\\')
+                    && check_pattern_type(bytes, false) == PatternType::OrdinalStr =>
+            {
+                Some(pattern.clone())
+            }
+            _ => None,
+        }
+    }
+
     // Evaluate the equivalent expression like "name='Alice'"
     fn eval_equivalent_expression(
         &self,
         left: &Expression,
         right: &Expression,
     ) -> Result<FilterEvalResult> {
-        let schema: &DataSchemaRef = &self.source_schema;
-
-        // For now only support single column like "name = 'Alice'"
         match (left, right) {
             // match the expression of 'column_name = literal constant'
-            (Expression::IndexedVariable { name, .. }, Expression::Constant { value, .. })
-            | (Expression::Constant { value, .. }, Expression::IndexedVariable { name, .. }) => {
+            (operand, Expression::Constant { value, .. })
+            | (Expression::Constant { value, .. }, operand) => self.find_point(operand, value),
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    // Evaluate the in list expression like "age IN (20, 30)",
+    // which must be false if none of the values shows up in the filter.
+    fn eval_in_expression(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<FilterEvalResult> {
+        let values = match right {
+            Expression::Constant {
+                value: DataValue::Struct(values),
+                ..
+            } => values,
+            _ => return Ok(FilterEvalResult::NotApplicable),
+        };
+
+        let mut result = FilterEvalResult::False;
+        for value in values {
+            // NULL never equals to any value
+            if value.is_null() {
+                continue;
+            }
+            match self.find_point(left, value)? {
+                FilterEvalResult::False => {}
+                FilterEvalResult::Maybe => result = FilterEvalResult::Maybe,
+                FilterEvalResult::NotApplicable => return Ok(FilterEvalResult::NotApplicable),
+            }
+        }
+        Ok(result)
+    }
+
+    // Evaluate the like expression without wildcards, like "name LIKE 'Alice'"
+    fn eval_like_expression(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<FilterEvalResult> {
+        match right {
+            Expression::Constant { value, .. } => match Self::like_pattern_as_string(value) {
+                Some(value) => self.find_point(left, &value),
+                None => Ok(FilterEvalResult::NotApplicable),
+            },
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    // Probe the filter with the value of the operand of a point query.
+    fn find_point(&self, operand: &Expression, value: &DataValue) -> Result<FilterEvalResult> {
+        match operand {
+            Expression::IndexedVariable { name, .. } => {
                 // find the corresponding column from source table
-                let data_field = schema.field_with_name(name)?;
+                let data_field = self.source_schema.field_with_name(name)?;
                 let data_type = data_field.data_type();
-
-                // check if cast needed
-                let value = if &value.data_type() != data_type {
-                    let col = value.as_const_column(data_type, 1)?;
-                    col.get_checked(0)?
-                } else {
-                    value.clone()
-                };
+                let value = cast_value(value, data_type)?;
                 self.find(name, value, data_type)
             }
+            Expression::Cast { input, target } if is_injective_cast(&input.data_type(), target) => {
+                // "CAST(x AS T) = v" holds only if "x = v'", where v' is the value casting v back.
+                // If v can not be cast back losslessly, give up rather than prune wrongly.
+                let value = cast_value(value, target)?;
+                let origin = match cast_value(&value, &input.data_type()) {
+                    Ok(origin) if cast_value(&origin, target).ok().as_ref() == Some(&value) => {
+                        origin
+                    }
+                    _ => return Ok(FilterEvalResult::NotApplicable),
+                };
+                self.find_point(input, &origin)
+            }
+            Expression::Function { name, args, .. } => match Self::lower_of_column(name, args) {
+                // "LOWER(x) = v" holds only if x is one of the strings lowering to v.
+                Some((column_name, _)) => match value {
+                    DataValue::String(value) => match lower_preimages(value) {
+                        Some(preimages) => {
+                            let data_field = self.source_schema.field_with_name(column_name)?;
+                            self.find_any(
+                                column_name,
+                                preimages.into_iter().map(DataValue::String),
+                                data_field.data_type(),
+                            )
+                        }
+                        None => Ok(FilterEvalResult::NotApplicable),
+                    },
+                    _ => Ok(FilterEvalResult::NotApplicable),
+                },
+                None => Ok(FilterEvalResult::NotApplicable),
+            },
             _ => Ok(FilterEvalResult::NotApplicable),
         }
     }
@@ -268,3 +452,75 @@ impl BlockFilter {
         }
     }
 }
+
+// check if cast needed
+fn cast_value(value: &DataValue, data_type: &DataTypeImpl) -> Result<DataValue> {
+    if &value.data_type() != data_type {
+        let col = value.as_const_column(data_type, 1)?;
+        col.get_checked(0)
+    } else {
+        Ok(value.clone())
+    }
+}
+
+// Different values stay different after the cast, so that the filter of the
+// input could be probed with the value casting back.
+// Only the widening casts of integers are considered for now. Other casts, e.g. from
+// strings or floats, map different values of the column to the same one, like the
+// functions of the column do, so they can not be evaluated by the filter of the column.
+fn is_injective_cast(from: &DataTypeImpl, to: &DataTypeImpl) -> bool {
+    let from = remove_nullable(from).data_type_id();
+    let to = remove_nullable(to).data_type_id();
+    if !from.is_integer() || !to.is_integer() {
+        return false;
+    }
+    match (from.numeric_byte_size(), to.numeric_byte_size()) {
+        (Ok(from_size), Ok(to_size)) => {
+            to_size > from_size
+                || (to_size == from_size && from.is_signed_integer() == to.is_signed_integer())
+        }
+        _ => false,
+    }
+}
+
+// The strings lowering to the value, if the value is ASCII and they are not too many.
+//
+// Besides the ASCII letters of both cases, only the KELVIN SIGN (U+212A) lowers to an ASCII
+// letter, 'k', the other characters lower to non-ASCII ones. So the strings lowering to an
+// ASCII value are the case variants of it, and none if it has upper case letters.
+fn lower_preimages(value: &[u8]) -> Option<Vec<Vec<u8>>> {
+    if !value.is_ascii() {
+        return None;
+    }
+
+    let mut candidates_of_bytes = Vec::with_capacity(value.len());
+    let mut num_preimages = 1usize;
+    for byte in value {
+        let candidates = match byte {
+            b'A'..=b'Z' => return Some(vec![]),
+            b'k' => vec![vec![b'k'], vec![b'K'], '\u{212A}'.to_string().into_bytes()],
+            b'a'..=b'z' => vec![vec![*byte], vec![byte.to_ascii_uppercase()]],
+            _ => vec![vec![*byte]],
+        };
+        num_preimages = num_preimages.saturating_mul(candidates.len());
+        if num_preimages > MAX_LOWER_PREIMAGES {
+            return None;
+        }
+        candidates_of_bytes.push(candidates);
+    }
+
+    let mut preimages = vec![Vec::with_capacity(value.len())];
+    for candidates in candidates_of_bytes {
+        preimages = preimages
+            .into_iter()
+            .flat_map(|preimage| {
+                candidates.iter().map(move |candidate| {
+                    let mut preimage = preimage.clone();
+                    preimage.extend_from_slice(candidate);
+                    preimage
+                })
+            })
+            .collect();
+    }
+    Some(preimages)
+}
@@ -0,0 +1,345 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::tokenize;
+
+use crate::filters::Filter;
+use crate::filters::FilterBuilder;
+use crate::filters::Xor8Builder;
+use crate::filters::Xor8Filter;
+use crate::FilterEvalResult;
+
+/// BlockInvertedIndex keeps the tokens of the indexed string columns of a data block,
+/// so that the blocks that do not contain the tokens of `MATCH` or `LIKE` can be skipped.
+///
+/// The tokens of a column are kept in a xor filter, with field name 'Inverted(column_name)'.
+/// Tokens are split by [tokenize], the same as the `MATCH` function.
+///
+/// The n-grams of the tokens are kept in another xor filter, with field name 'Ngram(column_name)',
+/// for the words of `LIKE` patterns which are adjoined to the wildcards, e.g. 'ogi' and 'gin'
+/// for '%login%'.
+///
+/// For example, for the source data block as follows:
+/// ```
+///         +---message----------+
+///         | "GET /index.html"  |
+///         | "POST /login"      |
+///         +--------------------+
+/// ```
+/// The filter of 'Inverted(message)' contains "get", "index", "html", "post" and "login",
+/// and the filter of 'Ngram(message)' contains "get", "ind", "nde", "dex", "htm", "tml", ...
+pub struct BlockInvertedIndex {
+    /// The schema of the index block.
+    pub filter_schema: DataSchemaRef,
+
+    /// Data block of the filters of tokens.
+    pub filter_block: DataBlock,
+}
+
+/// Number of the characters of the n-grams.
+const NGRAM_SIZE: usize = 3;
+
+impl BlockInvertedIndex {
+    pub fn build_filter_column_name(column_name: &str) -> String {
+        format!("Inverted({})", column_name)
+    }
+
+    pub fn build_ngram_column_name(column_name: &str) -> String {
+        format!("Ngram({})", column_name)
+    }
+
+    pub fn is_supported_type(data_type: &DataTypeImpl) -> bool {
+        remove_nullable(data_type).data_type_id().is_string()
+    }
+
+    /// Load the index from the index block.
+    pub fn from_filter_block(filter_block: DataBlock) -> Self {
+        Self {
+            filter_schema: filter_block.schema().clone(),
+            filter_block,
+        }
+    }
+
+    /// Create the index of the given columns from the data block.
+    ///
+    /// Returns None if there are no columns to index.
+    pub fn try_create(block: &DataBlock, columns: &[String]) -> Result<Option<Self>> {
+        if columns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut filter_fields = Vec::with_capacity(columns.len() * 2);
+        let mut filter_columns = Vec::with_capacity(columns.len() * 2);
+        for column_name in columns {
+            let column = block.try_column_by_name(column_name)?;
+            let mut tokens = HashSet::new();
+            let mut ngrams = HashSet::new();
+            for value in column.to_values() {
+                if let DataValue::String(text) = value {
+                    tokens.extend(tokenize(&text));
+                    for word in String::from_utf8_lossy(&text).split(|c: char| !c.is_alphanumeric())
+                    {
+                        ngrams.extend(word_ngrams(&word.chars().collect::<Vec<_>>()));
+                    }
+                }
+            }
+
+            for (filter_column_name, keys) in [
+                (Self::build_filter_column_name(column_name), tokens),
+                (Self::build_ngram_column_name(column_name), ngrams),
+            ] {
+                let filter_value = DataValue::String(Self::build_filter(keys)?);
+                filter_columns.push(filter_value.as_const_column(&Vu8::to_data_type(), 1)?);
+                filter_fields.push(DataField::new(&filter_column_name, Vu8::to_data_type()));
+            }
+        }
+
+        let filter_schema = Arc::new(DataSchema::new(filter_fields));
+        let filter_block = DataBlock::create(filter_schema.clone(), filter_columns);
+        Ok(Some(Self {
+            filter_schema,
+            filter_block,
+        }))
+    }
+
+    // an empty filter can not be built, nothing is kept for a column without keys
+    fn build_filter(keys: HashSet<String>) -> Result<Vec<u8>> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut filter_builder = Xor8Builder::create();
+        filter_builder.add_keys(&keys.into_iter().collect::<Vec<_>>());
+        filter_builder.build()?.to_bytes()
+    }
+
+    /// Returns the names of the filter columns required by the expression,
+    /// only the conditions on the `indexed_columns` are taken into account.
+    pub fn query_filter_columns(expr: &Expression, indexed_columns: &[String]) -> Vec<String> {
+        let mut filter_columns = HashSet::new();
+        Self::collect_query_filter_columns(expr, indexed_columns, &mut filter_columns);
+        filter_columns.into_iter().collect()
+    }
+
+    fn collect_query_filter_columns(
+        expr: &Expression,
+        indexed_columns: &[String],
+        filter_columns: &mut HashSet<String>,
+    ) {
+        if let Some(query) = Self::token_query(expr) {
+            if indexed_columns.iter().any(|column| column == query.column) {
+                if !query.tokens.is_empty() {
+                    filter_columns.insert(Self::build_filter_column_name(query.column));
+                }
+                if !query.ngrams.is_empty() {
+                    filter_columns.insert(Self::build_ngram_column_name(query.column));
+                }
+            }
+            return;
+        }
+        if let Expression::Function { name, args, .. } = expr {
+            if matches!(name.to_lowercase().as_str(), "and" | "or") {
+                for arg in args {
+                    Self::collect_query_filter_columns(arg, indexed_columns, filter_columns);
+                }
+            }
+        }
+    }
+
+    // Returns the column and the tokens (and n-grams) that the column must contain,
+    // if the expression is like "MATCH(message, 'error')" or "message LIKE '%an error%'".
+    fn token_query(expr: &Expression) -> Option<TokenQuery> {
+        match expr {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match (name.to_lowercase().as_str(), &args[0], &args[1]) {
+                    (
+                        "match",
+                        Expression::IndexedVariable { name, .. },
+                        Expression::Constant {
+                            value: DataValue::String(query),
+                            ..
+                        },
+                    ) => Some(TokenQuery {
+                        column: name.as_str(),
+                        tokens: tokenize(query),
+                        ngrams: vec![],
+                    }),
+                    (
+                        "like",
+                        Expression::IndexedVariable { name, .. },
+                        Expression::Constant {
+                            value: DataValue::String(pattern),
+                            ..
+                        },
+                    ) => Some(TokenQuery {
+                        column: name.as_str(),
+                        tokens: like_pattern_tokens(pattern),
+                        ngrams: like_pattern_ngrams(pattern),
+                    }),
+                    _ => None,
+                }
+                .filter(|query| !query.tokens.is_empty() || !query.ngrams.is_empty())
+            }
+            _ => None,
+        }
+    }
+
+    /// Apply the expression, returns False if the block must not contain the tokens.
+    #[tracing::instrument(level = "debug", name = "block_inverted_index_eval", skip_all)]
+    pub fn eval(&self, expr: &Expression) -> Result<FilterEvalResult> {
+        if let Some(query) = Self::token_query(expr) {
+            return self.find_tokens(&query);
+        }
+
+        match expr {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match name.to_lowercase().as_str() {
+                    "and" => {
+                        let left = self.eval(&args[0])?;
+                        let right = self.eval(&args[1])?;
+                        Ok(match (left, right) {
+                            (FilterEvalResult::False, _) | (_, FilterEvalResult::False) => {
+                                FilterEvalResult::False
+                            }
+                            (FilterEvalResult::Maybe, FilterEvalResult::Maybe) => {
+                                FilterEvalResult::Maybe
+                            }
+                            _ => FilterEvalResult::NotApplicable,
+                        })
+                    }
+                    "or" => {
+                        let left = self.eval(&args[0])?;
+                        let right = self.eval(&args[1])?;
+                        Ok(match (left, right) {
+                            (FilterEvalResult::False, other) | (other, FilterEvalResult::False) => {
+                                other
+                            }
+                            (FilterEvalResult::Maybe, FilterEvalResult::Maybe) => {
+                                FilterEvalResult::Maybe
+                            }
+                            _ => FilterEvalResult::NotApplicable,
+                        })
+                    }
+                    _ => Ok(FilterEvalResult::NotApplicable),
+                }
+            }
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    fn find_tokens(&self, query: &TokenQuery) -> Result<FilterEvalResult> {
+        let mut result = FilterEvalResult::NotApplicable;
+        for (filter_column, keys) in [
+            (Self::build_filter_column_name(query.column), &query.tokens),
+            (Self::build_ngram_column_name(query.column), &query.ngrams),
+        ] {
+            // the filter of n-grams is missing in the indexes written before it's introduced
+            if keys.is_empty() || !self.filter_block.schema().has_field(&filter_column) {
+                continue;
+            }
+            if !self.contains_keys(&filter_column, keys)? {
+                return Ok(FilterEvalResult::False);
+            }
+            result = FilterEvalResult::Maybe;
+        }
+        Ok(result)
+    }
+
+    fn contains_keys(&self, filter_column: &str, keys: &[String]) -> Result<bool> {
+        let filter_bytes = self.filter_block.first(filter_column)?.as_string()?;
+        if filter_bytes.is_empty() {
+            // the column has no keys at all
+            return Ok(false);
+        }
+        let (filter, _size) = Xor8Filter::from_bytes(&filter_bytes)?;
+        Ok(keys.iter().all(|key| filter.contains(key)))
+    }
+}
+
+struct TokenQuery<'a> {
+    column: &'a str,
+    // the complete tokens
+    tokens: Vec<String>,
+    // the n-grams of the partial tokens
+    ngrams: Vec<String>,
+}
+
+/// Returns the tokens that the strings matching the LIKE pattern must contain.
+///
+/// Only the complete tokens are returned, e.g. "error" for '%an error occurred%',
+/// since the words adjoined to the wildcards may be parts of longer tokens.
+pub fn like_pattern_tokens(pattern: &[u8]) -> Vec<String> {
+    like_pattern_words(pattern)
+        .into_iter()
+        .filter(|(_, complete)| *complete)
+        .map(|(word, _)| word.iter().collect::<String>().to_lowercase())
+        .collect()
+}
+
+/// Returns the n-grams that the strings matching the LIKE pattern must contain.
+///
+/// They are the n-grams of the words adjoined to the wildcards, e.g. "ccu", "cur" and "urr"
+/// for '%an error occur%', which are parts of the tokens of the strings.
+pub fn like_pattern_ngrams(pattern: &[u8]) -> Vec<String> {
+    like_pattern_words(pattern)
+        .into_iter()
+        .filter(|(_, complete)| !*complete)
+        .flat_map(|(word, _)| word_ngrams(&word))
+        .collect()
+}
+
+// Splits the LIKE pattern into the words of alphanumeric characters, together with
+// whether the word is complete, i.e. not adjoined to a wildcard.
+fn like_pattern_words(pattern: &[u8]) -> Vec<(Vec<char>, bool)> {
+    let pattern = match std::str::from_utf8(pattern) {
+        // escaped wildcards are not taken into account for simplicity
+        Ok(pattern) if !pattern.contains('\\') => pattern.chars().collect::<Vec<_>>(),
+        _ => return vec![],
+    };
+    let is_wildcard = |c: char| c == '%' || c == '_';
+
+    let mut words = vec![];
+    let mut start = 0;
+    for end in 0..=pattern.len() {
+        if end < pattern.len() && pattern[end].is_alphanumeric() {
+            continue;
+        }
+        if end > start {
+            let complete = (start == 0 || !is_wildcard(pattern[start - 1]))
+                && (end == pattern.len() || !is_wildcard(pattern[end]));
+            words.push((pattern[start..end].to_vec(), complete));
+        }
+        start = end + 1;
+    }
+    words
+}
+
+// The n-grams of the lowercase characters of a word. The characters are lowercased one by one,
+// so that a part of a word always has the n-grams of the word.
+fn word_ngrams(word: &[char]) -> Vec<String> {
+    let chars = word
+        .iter()
+        .flat_map(|c| c.to_lowercase())
+        .collect::<Vec<_>>();
+    chars
+        .windows(NGRAM_SIZE)
+        .map(|ngram| ngram.iter().collect())
+        .collect()
+}
@@ -20,11 +20,15 @@ use common_datavalues::DataTypeImpl;
 mod bloom;
 pub mod filters;
 pub mod index_min_max;
+mod inverted_index;
 pub mod range_filter;
 
 pub use bloom::BlockFilter;
 pub use bloom::FilterEvalResult;
 pub use index_min_max::*;
+pub use inverted_index::like_pattern_ngrams;
+pub use inverted_index::like_pattern_tokens;
+pub use inverted_index::BlockInvertedIndex;
 pub use range_filter::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
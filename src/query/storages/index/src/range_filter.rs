@@ -156,9 +156,21 @@ pub fn build_verifiable_expr(
                         let right = build_verifiable_expr(right, schema, stat_columns);
                         return left.or(&right).unwrap();
                     }
+                    "in" => match try_expand_in_list(left, right, false) {
+                        // `a in (1, 2)` is verified as `a = 1 or a = 2`
+                        Some(expanded) => {
+                            return build_verifiable_expr(&expanded, schema, stat_columns);
+                        }
+                        None => return unhandled,
+                    },
                     _ => (vec![left.clone(), right.clone()], name.clone()),
                 }
             } else {
+                if name.to_lowercase() == "not" && args.len() == 1 {
+                    if let Some(negated) = try_negate(&args[0]) {
+                        return build_verifiable_expr(&negated, schema, stat_columns);
+                    }
+                }
                 try_convert_is_null(name.to_lowercase().as_str(), args.clone())
             }
         }
@@ -183,6 +195,60 @@ fn inverse_operator(op: &str) -> Result<&str> {
     }
 }
 
+/// The max number of values of `IN` and `NOT IN`, which will be expanded to
+/// the comparisons with each of the values.
+const IN_LIST_MAX_VALUES: usize = 16;
+
+/// Try to expand `a in (1, 2)` to `a = 1 or a = 2`, or to `a != 1 and a != 2` if negated.
+fn try_expand_in_list(left: &Expression, right: &Expression, negated: bool) -> Option<Expression> {
+    let values = match right {
+        Expression::Constant {
+            value: DataValue::Struct(values),
+            ..
+        } if !values.is_empty() && values.len() <= IN_LIST_MAX_VALUES => values,
+        _ => return None,
+    };
+    values
+        .iter()
+        .map(|value| {
+            let value = Expression::Constant {
+                value: value.clone(),
+                data_type: value.data_type(),
+            };
+            if negated {
+                left.not_eq(&value)
+            } else {
+                left.eq(&value)
+            }
+        })
+        .reduce(|acc, item| {
+            if negated {
+                acc?.and(&item?)
+            } else {
+                acc?.or(&item?)
+            }
+        })
+        .and_then(|expr| expr.ok())
+}
+
+/// Try to push `not` down to the comparisons, so that `NOT IN` could be verified.
+///
+/// e.g. `not(a in (1, 2))` or `not(a = 1 or a = 2)` is rewritten to `a != 1 and a != 2`,
+/// which must be false if all the values of `a` in the block are the same one of the list.
+fn try_negate(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::Function { name, args, .. } if args.len() == 2 => {
+            match name.to_lowercase().as_str() {
+                "=" => args[0].not_eq(&args[1]).ok(),
+                "or" => try_negate(&args[0])?.and(&try_negate(&args[1])?).ok(),
+                "in" => try_expand_in_list(&args[0], &args[1], true),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Try to convert `not(is_not_null)` to `is_null`.
 fn try_convert_is_null(name: &str, args: Vec<Expression>) -> (Vec<Expression>, String) {
     // `is null` will be converted to `not(is not null)` in the parser.
@@ -434,7 +500,6 @@ impl<'a> VerifiableExprBuilder<'a> {
     }
 
     fn build(&mut self) -> Result<Expression> {
-        // TODO: support in/not in.
         match self.op {
             "is_null" => {
                 // should_keep: col.null_count > 0
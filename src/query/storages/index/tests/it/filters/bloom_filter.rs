@@ -15,13 +15,17 @@
 
 use std::collections::HashSet;
 
+use common_catalog::plan::Expression;
 use common_datablocks::DataBlock;
 use common_datavalues::BooleanType;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
 use common_datavalues::StringType;
+use common_datavalues::StructType;
 use common_datavalues::ToDataType;
 use common_exception::Result;
 use common_storages_index::BlockFilter;
@@ -106,3 +110,178 @@ fn test_column_type_support() -> Result<()> {
     });
     Ok(())
 }
+
+#[test]
+fn test_point_query_eval() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("i", i32::to_data_type()),
+        DataField::new("s", StringType::new_impl()),
+    ]);
+    let block = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1i32, 2, 3]),
+        Series::from_data(vec!["a", "Bob", "c"]),
+    ]);
+    let index = BlockFilter::try_create(&[&block])?;
+
+    let col_i = Expression::IndexedVariable {
+        name: "i".to_string(),
+        data_type: i32::to_data_type(),
+    };
+    let col_s = Expression::IndexedVariable {
+        name: "s".to_string(),
+        data_type: StringType::new_impl(),
+    };
+    let function = |name: &str, args: Vec<Expression>| Expression::Function {
+        name: name.to_string(),
+        args,
+        return_type: BooleanType::new_impl(),
+    };
+    let constant = |value: DataValue| Expression::Constant {
+        data_type: value.data_type(),
+        value,
+    };
+    let in_list = |expr: Expression, values: Vec<DataValue>| {
+        let types = values.iter().map(|v| v.data_type()).collect();
+        function("in", vec![expr, Expression::Constant {
+            value: DataValue::Struct(values),
+            data_type: StructType::new_impl(None, types),
+        }])
+    };
+    let cast_i = Expression::Cast {
+        input: Box::new(col_i.clone()),
+        target: i64::to_data_type(),
+    };
+
+    let lower_s = Expression::Function {
+        name: "lower".to_string(),
+        args: vec![col_s.clone()],
+        return_type: StringType::new_impl(),
+    };
+
+    let tests = vec![
+        (
+            "i in (4, 5)",
+            in_list(col_i.clone(), vec![
+                DataValue::Int64(4),
+                DataValue::Int64(5),
+            ]),
+            FilterEvalResult::False,
+        ),
+        (
+            "i in (3, 4)",
+            in_list(col_i.clone(), vec![
+                DataValue::Int64(3),
+                DataValue::Int64(4),
+            ]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "s like 'd'",
+            function("like", vec![
+                col_s.clone(),
+                constant(DataValue::String(b"d".to_vec())),
+            ]),
+            FilterEvalResult::False,
+        ),
+        (
+            "s like 'd%'",
+            function("like", vec![
+                col_s.clone(),
+                constant(DataValue::String(b"d%".to_vec())),
+            ]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "cast(i as bigint) = 4",
+            function("=", vec![cast_i.clone(), constant(DataValue::Int64(4))]),
+            FilterEvalResult::False,
+        ),
+        (
+            "cast(i as bigint) = 1",
+            function("=", vec![cast_i, constant(DataValue::Int64(1))]),
+            FilterEvalResult::Maybe,
+        ),
+        // the filter of the column is probed with the strings lowering to the value
+        (
+            "lower(s) = 'd'",
+            function("=", vec![
+                lower_s.clone(),
+                constant(DataValue::String(b"d".to_vec())),
+            ]),
+            FilterEvalResult::False,
+        ),
+        (
+            "lower(s) = 'bob'",
+            function("=", vec![
+                lower_s.clone(),
+                constant(DataValue::String(b"bob".to_vec())),
+            ]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "lower(s) = 'Bob'",
+            function("=", vec![
+                lower_s.clone(),
+                constant(DataValue::String(b"Bob".to_vec())),
+            ]),
+            FilterEvalResult::False,
+        ),
+        (
+            "lower(s) in ('d', 'e')",
+            in_list(lower_s.clone(), vec![
+                DataValue::String(b"d".to_vec()),
+                DataValue::String(b"e".to_vec()),
+            ]),
+            FilterEvalResult::False,
+        ),
+        (
+            "lower(s) in ('d', 'c')",
+            in_list(lower_s.clone(), vec![
+                DataValue::String(b"d".to_vec()),
+                DataValue::String(b"c".to_vec()),
+            ]),
+            FilterEvalResult::Maybe,
+        ),
+        // the strings lowering to a non-ASCII value are not enumerated
+        (
+            "lower(s) = 'é'",
+            function("=", vec![
+                lower_s.clone(),
+                constant(DataValue::String("é".as_bytes().to_vec())),
+            ]),
+            FilterEvalResult::NotApplicable,
+        ),
+        // the values of other expressions of a column are not indexed
+        (
+            "upper(s) = 'D'",
+            function("=", vec![
+                Expression::Function {
+                    name: "upper".to_string(),
+                    args: vec![col_s.clone()],
+                    return_type: StringType::new_impl(),
+                },
+                constant(DataValue::String(b"D".to_vec())),
+            ]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "i not in (4, 5)",
+            function("not", vec![in_list(col_i, vec![
+                DataValue::Int64(4),
+                DataValue::Int64(5),
+            ])]),
+            FilterEvalResult::NotApplicable,
+        ),
+    ];
+
+    for (name, expr, expected) in tests {
+        assert_eq!(expected, index.eval(&expr)?, "{}", name);
+    }
+
+    let lower_s_eq = function("=", vec![
+        lower_s,
+        constant(DataValue::String(b"a".to_vec())),
+    ]);
+    assert_eq!(BlockFilter::point_query_column(&lower_s_eq), Some("s"));
+    Ok(())
+}
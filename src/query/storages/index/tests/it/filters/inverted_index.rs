@@ -0,0 +1,176 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanType;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_datavalues::StringType;
+use common_datavalues::ToDataType;
+use common_exception::Result;
+use common_storages_index::like_pattern_ngrams;
+use common_storages_index::like_pattern_tokens;
+use common_storages_index::BlockInvertedIndex;
+use common_storages_index::FilterEvalResult;
+
+#[test]
+fn test_like_pattern_tokens() {
+    let tests = vec![
+        ("%an error occurred%", vec!["error"]),
+        ("error: disk full", vec!["error", "disk", "full"]),
+        ("%Disk Full", vec!["full"]),
+        ("_disk full_", vec![]),
+        ("%disk%", vec![]),
+        ("%disk\\_full%", vec![]),
+    ];
+
+    for (pattern, expected) in tests {
+        assert_eq!(
+            expected,
+            like_pattern_tokens(pattern.as_bytes()),
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn test_like_pattern_ngrams() {
+    let tests = vec![
+        ("%an error occur%", vec!["occ", "ccu", "cur"]),
+        ("error: disk full", vec![]),
+        ("%Disk Full", vec!["dis", "isk"]),
+        ("_disk full_", vec!["dis", "isk", "ful", "ull"]),
+        ("%ogi%", vec!["ogi"]),
+        ("%og%", vec![]),
+        ("%disk\\_full%", vec![]),
+    ];
+
+    for (pattern, expected) in tests {
+        assert_eq!(
+            expected,
+            like_pattern_ngrams(pattern.as_bytes()),
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn test_inverted_index_eval() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("i", i32::to_data_type()),
+        DataField::new("s", StringType::new_impl()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec![1i32, 2]),
+        Series::from_data(vec!["GET /index.html", "POST /login failed"]),
+    ]);
+
+    assert!(BlockInvertedIndex::try_create(&block, &[])?.is_none());
+    let index = BlockInvertedIndex::try_create(&block, &["s".to_string()])?.unwrap();
+
+    let col_s = Expression::IndexedVariable {
+        name: "s".to_string(),
+        data_type: StringType::new_impl(),
+    };
+    let function = |name: &str, args: Vec<Expression>| Expression::Function {
+        name: name.to_string(),
+        args,
+        return_type: BooleanType::new_impl(),
+    };
+    let string = |value: &str| Expression::Constant {
+        value: DataValue::String(value.as_bytes().to_vec()),
+        data_type: StringType::new_impl(),
+    };
+
+    let tests = vec![
+        (
+            "match(s, 'login failed')",
+            function("match", vec![col_s.clone(), string("login failed")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "match(s, 'login succeeded')",
+            function("match", vec![col_s.clone(), string("login succeeded")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "s like '%/Login failed%'",
+            function("like", vec![col_s.clone(), string("%/Login failed%")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "s like '%Login timeout at%'",
+            function("like", vec![col_s.clone(), string("%Login timeout at%")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "s like '%ogi%'",
+            function("like", vec![col_s.clone(), string("%ogi%")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "s like '%ogin fail%'",
+            function("like", vec![col_s.clone(), string("%ogin fail%")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "s like '%ogout%'",
+            function("like", vec![col_s.clone(), string("%ogout%")]),
+            FilterEvalResult::False,
+        ),
+        // the n-grams never span over the tokens
+        (
+            "s like '%in fa%'",
+            function("like", vec![col_s.clone(), string("%in fa%")]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "s like '%ogin_fail%'",
+            function("like", vec![col_s.clone(), string("%ogin_fail%")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "s like '%og%'",
+            function("like", vec![col_s.clone(), string("%og%")]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "match(s, 'put') or match(s, 'get')",
+            function("or", vec![
+                function("match", vec![col_s.clone(), string("put")]),
+                function("match", vec![col_s.clone(), string("get")]),
+            ]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "match(s, 'get') and match(s, 'put')",
+            function("and", vec![
+                function("match", vec![col_s.clone(), string("get")]),
+                function("match", vec![col_s, string("put")]),
+            ]),
+            FilterEvalResult::False,
+        ),
+    ];
+
+    for (name, expr, expected) in tests {
+        assert_eq!(expected, index.eval(&expr)?, "{}", name);
+    }
+    Ok(())
+}
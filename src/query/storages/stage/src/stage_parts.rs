@@ -17,6 +17,9 @@ use std::any::Any;
 use chrono::DateTime;
 use chrono::Utc;
 use common_catalog::plan::PartInfo;
+use common_catalog::plan::PartInfoPtr;
+use common_exception::ErrorCode;
+use common_exception::Result;
 use common_meta_types::UserIdentity;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -49,3 +52,14 @@ impl PartInfo for StageFilePartition {
         }
     }
 }
+
+impl StageFilePartition {
+    pub fn from_part(info: &PartInfoPtr) -> Result<&StageFilePartition> {
+        match info.as_any().downcast_ref::<StageFilePartition>() {
+            Some(part_ref) => Ok(part_ref),
+            None => Err(ErrorCode::Internal(
+                "Cannot downcast from PartInfo to StageFilePartition.",
+            )),
+        }
+    }
+}
@@ -18,24 +18,38 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 use common_base::base::uuid;
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
 use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::Expression;
 use common_catalog::plan::PartInfo;
 use common_catalog::plan::PartStatistics;
 use common_catalog::plan::Partitions;
 use common_catalog::plan::PushDownInfo;
+use common_catalog::plan::StagePushDownInfo;
 use common_catalog::plan::StageTableInfo;
 use common_catalog::table::AppendMode;
 use common_catalog::table::Table;
 use common_catalog::table_context::TableContext;
 use common_datablocks::BlockCompactThresholds;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_types::StageFileFormatType;
 use common_meta_types::StageType;
 use common_meta_types::UserStageInfo;
 use common_pipeline_core::Pipeline;
+use common_pipeline_sources::processors::sources::input_formats::row_group_statistics;
+use common_pipeline_sources::processors::sources::input_formats::InputContext;
+use common_pipeline_sources::processors::sources::EmptySource;
+use common_pipeline_transforms::processors::transforms::Transform;
+use common_pipeline_transforms::processors::transforms::Transformer;
 use common_storage::init_operator;
+use common_storages_index::RangeFilter;
+use common_storages_table_meta::meta::ColumnStatistics;
+use common_storages_table_meta::meta::StatisticsOfColumns;
 use opendal::layers::SubdirLayer;
 use opendal::Operator;
 use parking_lot::Mutex;
@@ -44,6 +58,11 @@ use regex::Regex;
 use crate::list_file;
 use crate::stage_table_sink::StageTableSink;
 use crate::stat_file;
+use crate::StageFilePartition;
+
+// The same as the defaults of fuse tables.
+const DEFAULT_ROW_PER_BLOCK: usize = 1000 * 1000;
+const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
 
 /// TODO: we need to track the data metrics in stage table.
 pub struct StageTable {
@@ -57,7 +76,8 @@ pub struct StageTable {
 
 impl StageTable {
     pub fn try_create(table_info: StageTableInfo) -> Result<Arc<dyn Table>> {
-        let table_info_placeholder = TableInfo::default().set_schema(table_info.schema());
+        let mut table_info_placeholder = TableInfo::default().set_schema(table_info.schema());
+        table_info_placeholder.name = table_info.user_stage_info.stage_name.clone();
 
         Ok(Arc::new(Self {
             table_info,
@@ -75,33 +95,14 @@ impl StageTable {
             Ok(pop.layer(SubdirLayer::new(&stage.stage_prefix())))
         }
     }
-}
-
-#[async_trait::async_trait]
-impl Table for StageTable {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    // External stage has no table info yet.
-    fn get_table_info(&self) -> &TableInfo {
-        &self.table_info_placeholder
-    }
-
-    async fn read_partitions(
-        &self,
-        ctx: Arc<dyn TableContext>,
-        push_downs: Option<PushDownInfo>,
-    ) -> Result<(PartStatistics, Partitions)> {
-        let pushdown = push_downs.ok_or_else(|| {
-            ErrorCode::Internal("stage_table: pushdown cannot be None(It's a bug)")
-        })?;
-        let stage_info = pushdown.stage.ok_or_else(|| {
-            ErrorCode::Internal("stage_table: pushdown.stage cannot be None(It's a bug)")
-        })?;
 
+    /// Lists the files to read, by the files or the pattern set by the user.
+    async fn list_files(
+        ctx: &Arc<dyn TableContext>,
+        stage_info: &StagePushDownInfo,
+    ) -> Result<Vec<StageFilePartition>> {
         // User set the files.
-        let files = stage_info.files;
+        let files = &stage_info.files;
 
         // 1. List all files.
         let path = &stage_info.path;
@@ -131,6 +132,100 @@ impl Table for StageTable {
                 all_files.retain(|v| regex.is_match(&v.path));
             }
         }
+        Ok(all_files)
+    }
+
+    /// The files to read if the table is queried, rather than read by COPY.
+    fn stage_push_down_info(&self) -> StagePushDownInfo {
+        StagePushDownInfo {
+            files: self.table_info.files.clone(),
+            path: self.table_info.path.clone(),
+            pattern: self.table_info.pattern.clone(),
+            user_stage_info: self.table_info.user_stage_info.clone(),
+        }
+    }
+
+    fn block_compact_thresholds(&self) -> BlockCompactThresholds {
+        let guard = self.block_compact_threshold.lock();
+        (*guard).unwrap_or_else(|| {
+            BlockCompactThresholds::new(
+                DEFAULT_ROW_PER_BLOCK,
+                DEFAULT_ROW_PER_BLOCK * 4 / 5,
+                DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+            )
+        })
+    }
+
+    /// Removes the row groups of parquet files which are known to have no rows matching the
+    /// filters by the statistics.
+    fn prune_row_groups(
+        ctx: &Arc<dyn TableContext>,
+        input_ctx: &mut InputContext,
+        filters: &[Expression],
+    ) {
+        if filters.is_empty() {
+            return;
+        }
+        let range_filter =
+            match RangeFilter::try_create(ctx.clone(), filters, input_ctx.schema.clone()) {
+                Ok(range_filter) => range_filter,
+                // e.g. the filters refer to the columns not read
+                Err(_) => return,
+            };
+        let num_splits = input_ctx.splits.len();
+        input_ctx.splits.retain(|split| {
+            let stats = match row_group_statistics(split) {
+                Some(stats) => stats,
+                None => return true,
+            };
+            let columns = stats
+                .columns
+                .into_iter()
+                .map(|(i, (min, max, null_count))| {
+                    let stats = ColumnStatistics {
+                        min,
+                        max,
+                        null_count,
+                        in_memory_size: 0,
+                    };
+                    (i as u32, stats)
+                })
+                .collect::<StatisticsOfColumns>();
+            // keep the row group if a column has no statistics
+            range_filter
+                .eval(&columns, stats.num_rows as u64)
+                .unwrap_or(true)
+        });
+        tracing::debug!(
+            "stage table: {} of {} parquet row groups pruned",
+            num_splits - input_ctx.splits.len(),
+            num_splits
+        );
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StageTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    // External stage has no table info yet.
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info_placeholder
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        // The files are given by COPY, or by the table itself if it is queried.
+        let stage_info = match push_downs.and_then(|push_downs| push_downs.stage) {
+            Some(stage_info) => stage_info,
+            None => self.stage_push_down_info(),
+        };
+        let all_files = StageTable::list_files(&ctx, &stage_info).await?;
 
         let partitions = all_files
             .iter()
@@ -142,13 +237,87 @@ impl Table for StageTable {
         Ok((PartStatistics::default(), partitions))
     }
 
+    async fn infer_schema(&self, ctx: Arc<dyn TableContext>) -> Result<DataSchemaRef> {
+        let files = StageTable::list_files(&ctx, &self.stage_push_down_info()).await?;
+        let file = files.first().ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "no file found in stage {} to infer the schema",
+                self.table_info.desc()
+            ))
+        })?;
+        let user_stage_info = &self.table_info.user_stage_info;
+        let op = StageTable::get_op(&ctx, user_stage_info)?;
+        InputContext::infer_schema(&op, &ctx.get_settings(), user_stage_info, &file.path).await
+    }
+
     fn read_data(
         &self,
         ctx: Arc<dyn TableContext>,
         plan: &DataSourcePlan,
         pipeline: &mut Pipeline,
     ) -> Result<()> {
-        let (_, _, _) = (ctx, plan, pipeline);
+        let files = plan
+            .parts
+            .iter()
+            .map(|part| StageFilePartition::from_part(part).map(|file| file.path.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        if files.is_empty() {
+            return pipeline.add_source(EmptySource::create, 1);
+        }
+
+        let user_stage_info = self.table_info.user_stage_info.clone();
+        // The fields of a row of CSV and TSV are read by position, all the fields are read and
+        // projected then. The other formats read only the fields to scan.
+        let is_positional = matches!(
+            user_stage_info.file_format_options.format,
+            StageFileFormatType::Csv | StageFileFormatType::Tsv
+        );
+        let schema = if is_positional {
+            self.table_info.schema()
+        } else {
+            plan.schema()
+        };
+        let filters = plan
+            .push_downs
+            .as_ref()
+            .map(|push_downs| push_downs.filters.clone())
+            .unwrap_or_default();
+
+        // `read_data` is not async, the splits of the files are got in the IO runtime.
+        let op = StageTable::get_op(&ctx, &user_stage_info)?;
+        let settings = ctx.get_settings();
+        let scan_progress = ctx.get_scan_progress();
+        let block_compact_thresholds = self.block_compact_thresholds();
+        let handle = GlobalIORuntime::instance().spawn(async move {
+            InputContext::try_create_from_copy(
+                op,
+                settings,
+                schema,
+                user_stage_info,
+                files,
+                scan_progress,
+                block_compact_thresholds,
+            )
+            .await
+        });
+        let mut input_ctx = futures::executor::block_on(handle)
+            .map_err(|e| ErrorCode::Internal(format!("stage table: fail to read files, {e}")))??;
+        StageTable::prune_row_groups(&ctx, &mut input_ctx, &filters);
+        if input_ctx.splits.is_empty() {
+            return pipeline.add_source(EmptySource::create, 1);
+        }
+
+        let input_ctx = Arc::new(input_ctx);
+        input_ctx.format.exec_copy(input_ctx.clone(), pipeline)?;
+
+        if is_positional {
+            let schema = plan.schema();
+            pipeline.add_transform(|input, output| {
+                Ok(Transformer::create(input, output, ProjectionTransform {
+                    schema: schema.clone(),
+                }))
+            })?;
+        }
         Ok(())
     }
 
@@ -226,3 +395,16 @@ impl Table for StageTable {
         (*guard) = Some(thresholds)
     }
 }
+
+/// Projects the blocks read from the files of the positional formats to the fields to scan.
+struct ProjectionTransform {
+    schema: DataSchemaRef,
+}
+
+impl Transform for ProjectionTransform {
+    const NAME: &'static str = "StageProjectionTransform";
+
+    fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
+        data.resort(self.schema.clone())
+    }
+}
@@ -19,6 +19,8 @@ use common_datavalues::DataValue;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::meta::DistributionOfColumns;
+
 pub type ColumnId = u32;
 pub type FormatVersion = u64;
 pub type SnapshotId = Uuid;
@@ -69,6 +71,9 @@ pub struct Statistics {
     pub index_size: u64,
 
     pub col_stats: HashMap<ColumnId, ColumnStatistics>,
+    // Kept in the summaries of segments and snapshots only, not in the block metas.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub col_distributions: DistributionOfColumns,
 }
 
 /// Thing has a u64 version nubmer
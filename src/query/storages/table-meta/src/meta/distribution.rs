@@ -0,0 +1,323 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+
+use crate::meta::ColumnId;
+
+pub type DistributionOfColumns = HashMap<ColumnId, ColumnDistribution>;
+
+/// Number of the bits of the hash, which are used to pick the register of [`DistinctSketch`].
+const SKETCH_PRECISION: u32 = 8;
+const SKETCH_NUM_REGISTERS: usize = 1 << SKETCH_PRECISION;
+
+/// Number of buckets of the histograms, the histograms built by merging are re-bucketed to it.
+pub const HISTOGRAM_NUM_BUCKETS: usize = 32;
+
+/// The distribution of the values of a column, which is used by the optimizer
+/// to estimate the selectivity of the predicates and the cardinality of the joins.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDistribution {
+    pub distinct_sketch: DistinctSketch,
+    // Only built for the numeric and date/time columns.
+    #[serde(default)]
+    pub histogram: Option<Histogram>,
+}
+
+impl ColumnDistribution {
+    pub fn merge(&mut self, other: &ColumnDistribution) {
+        self.distinct_sketch.merge(&other.distinct_sketch);
+        self.histogram = match (&self.histogram, &other.histogram) {
+            (Some(l), Some(r)) => Some(l.merge(r)),
+            _ => None,
+        };
+    }
+}
+
+/// A HyperLogLog sketch of the hashes of the non-null values, for estimating
+/// the number of distinct values.
+///
+/// Sketches are merged by taking the maximum of each register, so the number of
+/// distinct values of a segment (or snapshot) can be estimated without reading the blocks.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistinctSketch {
+    registers: Vec<u8>,
+}
+
+impl Default for DistinctSketch {
+    fn default() -> Self {
+        DistinctSketch {
+            registers: vec![0; SKETCH_NUM_REGISTERS],
+        }
+    }
+}
+
+impl DistinctSketch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - SKETCH_PRECISION)) as usize;
+        // position of the first set bit of the remaining bits
+        let rank = ((hash << SKETCH_PRECISION).leading_zeros()).min(64 - SKETCH_PRECISION) + 1;
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank as u8);
+    }
+
+    pub fn merge(&mut self, other: &DistinctSketch) {
+        // sketches of other precisions are not expected, just be defensive here.
+        if self.registers.len() != other.registers.len() {
+            return;
+        }
+        for (l, r) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *l = (*l).max(*r);
+        }
+    }
+
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        if m == 0.0 {
+            return 0;
+        }
+
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum();
+        let raw = alpha * m * m / sum;
+
+        // small range correction, i.e. linear counting
+        let zeros = self.registers.iter().filter(|r| **r == 0).count() as f64;
+        let estimate = if raw <= 2.5 * m && zeros > 0.0 {
+            m * (m / zeros).ln()
+        } else {
+            raw
+        };
+        estimate.round() as u64
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HistogramBucket {
+    // The lower bound of a bucket is the upper bound of the previous one (exclusive),
+    // or the min value of the column for the first bucket.
+    pub upper_bound: DataValue,
+    pub num_values: u64,
+    pub num_distinct: u64,
+}
+
+/// An equi-height histogram of the non-null values.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Histogram {
+    pub buckets: Vec<HistogramBucket>,
+}
+
+impl Histogram {
+    /// Builds the histogram from the sorted values, each of them stands for `weight`
+    /// values of the column, which is greater than 1 if the values are sampled.
+    pub fn from_sorted_values(sorted_values: &[DataValue], weight: f64) -> Histogram {
+        let len = sorted_values.len();
+        let values_per_bucket = ((len + HISTOGRAM_NUM_BUCKETS - 1) / HISTOGRAM_NUM_BUCKETS).max(1);
+
+        let mut buckets = Vec::with_capacity(HISTOGRAM_NUM_BUCKETS);
+        let mut start = 0;
+        while start < len {
+            let mut end = (start + values_per_bucket).min(len);
+            // a value never spans over buckets
+            while end < len && sorted_values[end] == sorted_values[end - 1] {
+                end += 1;
+            }
+            let num_distinct = 1
+                + (start + 1..end)
+                    .filter(|i| sorted_values[*i] != sorted_values[*i - 1])
+                    .count();
+            buckets.push(HistogramBucket {
+                upper_bound: sorted_values[end - 1].clone(),
+                num_values: ((end - start) as f64 * weight).round() as u64,
+                num_distinct: num_distinct as u64,
+            });
+            start = end;
+        }
+        Histogram { buckets }
+    }
+
+    pub fn num_values(&self) -> u64 {
+        self.buckets.iter().map(|b| b.num_values).sum()
+    }
+
+    /// Merges the buckets of the two histograms, and re-buckets them by the accumulated number of values.
+    ///
+    /// The buckets of the two histograms may overlap, so the result is an approximation.
+    pub fn merge(&self, other: &Histogram) -> Histogram {
+        let mut buckets = self
+            .buckets
+            .iter()
+            .chain(other.buckets.iter())
+            .collect::<Vec<_>>();
+        buckets.sort_by(|l, r| l.upper_bound.cmp(&r.upper_bound));
+
+        let total = buckets.iter().map(|b| b.num_values).sum::<u64>();
+        let values_per_bucket = (total / HISTOGRAM_NUM_BUCKETS as u64).max(1);
+
+        let mut merged = Vec::with_capacity(HISTOGRAM_NUM_BUCKETS);
+        let mut num_values = 0;
+        let mut num_distinct = 0;
+        for (i, bucket) in buckets.iter().enumerate() {
+            num_values += bucket.num_values;
+            num_distinct += bucket.num_distinct;
+
+            let next = buckets.get(i + 1);
+            let is_full = num_values >= values_per_bucket;
+            let same_bound_follows = next.map_or(false, |n| n.upper_bound == bucket.upper_bound);
+            if next.is_none() || (is_full && !same_bound_follows) {
+                merged.push(HistogramBucket {
+                    upper_bound: bucket.upper_bound.clone(),
+                    num_values,
+                    num_distinct: num_distinct.min(num_values),
+                });
+                num_values = 0;
+                num_distinct = 0;
+            }
+        }
+        Histogram { buckets: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_datavalues::DataValue;
+
+    use super::DistinctSketch;
+    use super::Histogram;
+    use super::HISTOGRAM_NUM_BUCKETS;
+
+    // splitmix64, spreads the sequential integers over the whole range of the hash.
+    fn hash(v: u64) -> u64 {
+        let mut z = v.wrapping_add(0x9E3779B97F4A7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn sketch_of(values: std::ops::Range<u64>) -> DistinctSketch {
+        let mut sketch = DistinctSketch::new();
+        for v in values {
+            sketch.add_hash(hash(v));
+        }
+        sketch
+    }
+
+    fn assert_estimate(sketch: &DistinctSketch, expected: u64) {
+        let estimate = sketch.estimate();
+        let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+        assert!(
+            error < 0.15,
+            "estimate {} is too far from {}",
+            estimate,
+            expected
+        );
+    }
+
+    fn int_values(values: std::ops::Range<i64>) -> Vec<DataValue> {
+        values.map(DataValue::Int64).collect()
+    }
+
+    #[test]
+    fn test_distinct_sketch_estimate() {
+        assert_eq!(DistinctSketch::new().estimate(), 0);
+
+        for n in [100, 1000, 10000] {
+            assert_estimate(&sketch_of(0..n), n);
+        }
+
+        // duplicated values are counted once
+        let mut sketch = sketch_of(0..1000);
+        for v in 0..1000 {
+            sketch.add_hash(hash(v));
+        }
+        assert_eq!(sketch.estimate(), sketch_of(0..1000).estimate());
+    }
+
+    #[test]
+    fn test_distinct_sketch_merge() {
+        let mut merged = sketch_of(0..5000);
+        merged.merge(&sketch_of(5000..10000));
+        assert_eq!(merged, sketch_of(0..10000));
+        assert_estimate(&merged, 10000);
+
+        // merging the overlapped values
+        let mut merged = sketch_of(0..5000);
+        merged.merge(&sketch_of(2500..7500));
+        assert_estimate(&merged, 7500);
+    }
+
+    #[test]
+    fn test_histogram_from_sorted_values() {
+        assert!(Histogram::from_sorted_values(&[], 1.0).buckets.is_empty());
+
+        // 2 values per bucket
+        let histogram = Histogram::from_sorted_values(&int_values(0..64), 1.0);
+        assert_eq!(histogram.buckets.len(), HISTOGRAM_NUM_BUCKETS);
+        for (i, bucket) in histogram.buckets.iter().enumerate() {
+            assert_eq!(bucket.upper_bound, DataValue::Int64(i as i64 * 2 + 1));
+            assert_eq!(bucket.num_values, 2);
+            assert_eq!(bucket.num_distinct, 2);
+        }
+        assert_eq!(histogram.num_values(), 64);
+
+        // the sampled values are weighted
+        let histogram = Histogram::from_sorted_values(&int_values(0..64), 2.5);
+        assert_eq!(histogram.buckets[0].num_values, 5);
+        assert_eq!(histogram.num_values(), 160);
+
+        // a value never spans over buckets
+        let values = [1, 1, 1, 2]
+            .into_iter()
+            .map(DataValue::Int64)
+            .collect::<Vec<_>>();
+        let histogram = Histogram::from_sorted_values(&values, 1.0);
+        assert_eq!(histogram.buckets.len(), 2);
+        assert_eq!(histogram.buckets[0].upper_bound, DataValue::Int64(1));
+        assert_eq!(histogram.buckets[0].num_values, 3);
+        assert_eq!(histogram.buckets[0].num_distinct, 1);
+        assert_eq!(histogram.buckets[1].upper_bound, DataValue::Int64(2));
+        assert_eq!(histogram.buckets[1].num_values, 1);
+    }
+
+    #[test]
+    fn test_histogram_merge() {
+        let l = Histogram::from_sorted_values(&int_values(0..64), 1.0);
+        let r = Histogram::from_sorted_values(&int_values(64..128), 1.0);
+
+        // re-bucketed to 4 values per bucket
+        let merged = l.merge(&r);
+        assert_eq!(merged.buckets.len(), HISTOGRAM_NUM_BUCKETS);
+        for (i, bucket) in merged.buckets.iter().enumerate() {
+            assert_eq!(bucket.upper_bound, DataValue::Int64(i as i64 * 4 + 3));
+            assert_eq!(bucket.num_values, 4);
+            assert_eq!(bucket.num_distinct, 4);
+        }
+        assert_eq!(merged, r.merge(&l));
+
+        // the buckets of the same upper bound are merged into one
+        let merged = l.merge(&l);
+        assert_eq!(merged.buckets.len(), HISTOGRAM_NUM_BUCKETS);
+        for (i, bucket) in merged.buckets.iter().enumerate() {
+            assert_eq!(bucket.upper_bound, DataValue::Int64(i as i64 * 2 + 1));
+            assert_eq!(bucket.num_values, 4);
+        }
+        assert_eq!(merged.num_values(), 128);
+    }
+}
@@ -16,6 +16,7 @@ mod common;
 
 /// Re-exports meta data structures of current version, i.e. v1
 mod current;
+mod distribution;
 mod v0;
 mod v1;
 mod versions;
@@ -31,6 +32,12 @@ pub use common::Statistics;
 pub use common::StatisticsOfColumns;
 pub use common::Versioned;
 pub use current::*;
+pub use distribution::ColumnDistribution;
+pub use distribution::DistinctSketch;
+pub use distribution::DistributionOfColumns;
+pub use distribution::Histogram;
+pub use distribution::HistogramBucket;
+pub use distribution::HISTOGRAM_NUM_BUCKETS;
 pub use versions::BlockBloomFilterIndexVersion;
 pub use versions::SegmentInfoVersion;
 pub use versions::SnapshotVersion;
@@ -58,6 +58,13 @@ pub struct BlockMeta {
     #[serde(default)]
     pub bloom_filter_index_size: u64,
 
+    /// location of inverted index, only for the tables with inverted indexes
+    #[serde(default)]
+    pub inverted_index_location: Option<Location>,
+
+    #[serde(default)]
+    pub inverted_index_size: u64,
+
     /// Compression algo used to compress the columns of blocks
     ///
     /// If not specified, the legacy algo `Lz4` will be used.
@@ -90,6 +97,8 @@ impl BlockMeta {
             location,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            inverted_index_location: None,
+            inverted_index_size: 0,
             compression: Compression::Lz4Raw,
         }
     }
@@ -141,6 +150,8 @@ impl From<v0::BlockMeta> for BlockMeta {
             location: (s.location.path, DataBlock::VERSION),
             bloom_filter_index_location: None,
             bloom_filter_index_size: 0,
+            inverted_index_location: None,
+            inverted_index_size: 0,
             compression: Compression::Lz4,
         }
     }
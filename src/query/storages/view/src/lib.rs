@@ -12,4 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod materialized_view;
+pub mod stream_table;
 pub mod view_table;
@@ -0,0 +1,27 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A materialized view is a FUSE table, which keeps the results of an aggregate query on
+//! another FUSE table, the base table. It is refreshed after each append to the base table.
+
+/// Option of the view, the query defining it, with the base table qualified by its database.
+pub const OPT_KEY_MATERIALIZED_VIEW_QUERY: &str = "materialized_view_query";
+/// Option of the view, the id of the base table.
+pub const OPT_KEY_MATERIALIZED_VIEW_BASE_TABLE_ID: &str = "materialized_view_base_table_id";
+/// Option of the view, the snapshot location of the base table that the view is refreshed to.
+/// The view is stale if it is not the current snapshot location of the base table.
+pub const OPT_KEY_MATERIALIZED_VIEW_BASE_SNAPSHOT: &str = "materialized_view_base_snapshot";
+/// Options of the base table are named as "materialized_view.<database>.<view>", one for each
+/// of the views on it, the values are the table ids of the views.
+pub const OPT_KEY_MATERIALIZED_VIEW_PREFIX: &str = "materialized_view.";
@@ -0,0 +1,74 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stream records an offset, a version of a FUSE table (the base table). Selecting from the
+//! stream returns the rows inserted into and deleted from the base table since the offset,
+//! and a DML statement reading the stream moves the offset to the version it has read.
+
+use std::any::Any;
+
+use common_catalog::catalog::StorageDescription;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+
+pub const STREAM_ENGINE: &str = "STREAM";
+
+/// Option of the stream, the id of the base table.
+pub const OPT_KEY_STREAM_BASE_TABLE_ID: &str = "base_table_id";
+/// Option of the stream, the database of the base table.
+pub const OPT_KEY_STREAM_BASE_DATABASE: &str = "base_database";
+/// Option of the stream, the name of the base table.
+pub const OPT_KEY_STREAM_BASE_TABLE: &str = "base_table";
+/// Option of the stream, the snapshot location of the base table at the offset, "" if the
+/// base table was empty.
+pub const OPT_KEY_STREAM_OFFSET: &str = "offset";
+
+/// The metadata column of a stream, the kind of the change: 'INSERT' or 'DELETE'.
+pub const CHANGE_ACTION_COLUMN: &str = "change$action";
+
+pub struct StreamTable {
+    table_info: TableInfo,
+}
+
+impl StreamTable {
+    pub fn try_create(table_info: TableInfo) -> Result<Box<dyn Table>> {
+        if !table_info.options().contains_key(OPT_KEY_STREAM_OFFSET) {
+            return Err(ErrorCode::Internal(
+                "Need `offset` when creating StreamTable",
+            ));
+        }
+        Ok(Box::new(StreamTable { table_info }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: STREAM_ENGINE.to_string(),
+            comment: "STREAM STORAGE (CHANGES OF A TABLE)".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StreamTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+}
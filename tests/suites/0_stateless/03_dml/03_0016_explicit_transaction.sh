@@ -0,0 +1,54 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists t03_0016_a" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists t03_0016_b" | $MYSQL_CLIENT_CONNECT
+echo "create table t03_0016_a(c int)" | $MYSQL_CLIENT_CONNECT
+echo "create table t03_0016_b(c int)" | $MYSQL_CLIENT_CONNECT
+
+echo "insertions are invisible before commit"
+cat <<SQL | $MYSQL_CLIENT_CONNECT
+begin;
+insert into t03_0016_a values(1),(2);
+insert into t03_0016_b values(3);
+insert into t03_0016_a values(4);
+select count(*) from t03_0016_a;
+select count(*) from t03_0016_b;
+commit;
+SQL
+
+echo "all the insertions are committed together"
+echo "select sum(c) from t03_0016_a" | $MYSQL_CLIENT_CONNECT
+echo "select sum(c) from t03_0016_b" | $MYSQL_CLIENT_CONNECT
+
+echo "rollback discards the insertions"
+cat <<SQL | $MYSQL_CLIENT_CONNECT
+start transaction;
+insert into t03_0016_a values(5);
+insert overwrite t03_0016_b values(6);
+rollback;
+SQL
+echo "select sum(c) from t03_0016_a" | $MYSQL_CLIENT_CONNECT
+echo "select sum(c) from t03_0016_b" | $MYSQL_CLIENT_CONNECT
+
+echo "insert overwrite in a transaction"
+cat <<SQL | $MYSQL_CLIENT_CONNECT
+begin transaction;
+insert into t03_0016_b values(7);
+insert overwrite t03_0016_b values(8);
+commit;
+SQL
+echo "select sum(c) from t03_0016_b" | $MYSQL_CLIENT_CONNECT
+
+echo "mutations are not supported in a transaction"
+cat <<SQL | $MYSQL_CLIENT_CONNECT 2>&1 | grep -o "Code: 1401"
+begin;
+delete from t03_0016_a where c = 1;
+rollback;
+SQL
+echo "select count(*) from t03_0016_a" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table t03_0016_a" | $MYSQL_CLIENT_CONNECT
+echo "drop table t03_0016_b" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+
+## Create table t12_0005
+echo "create table t12_0005(c int)" | $MYSQL_CLIENT_CONNECT
+echo "two insertions"
+echo "insert into t12_0005 values(1),(2)" | $MYSQL_CLIENT_CONNECT
+
+echo "insert into t12_0005 values(3)" | $MYSQL_CLIENT_CONNECT
+echo "latest snapshot should contain 3 rows"
+echo "select count(*)  from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+## Get the snapshot id of the first insertion
+SNAPSHOT_ID=$(echo "select snapshot_id from fuse_snapshot('default','t12_0005') where row_count=2 " | $MYSQL_CLIENT_CONNECT)
+
+echo "clone the data set of first insertion, which should contain 2 rows"
+echo "create table t12_0005_clone clone t12_0005 at (snapshot => '$SNAPSHOT_ID')" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005_clone" | $MYSQL_CLIENT_CONNECT
+
+echo "the clone and the source are independent of each other"
+echo "insert into t12_0005_clone values(4)" | $MYSQL_CLIENT_CONNECT
+echo "select sum(c) from t12_0005_clone" | $MYSQL_CLIENT_CONNECT
+echo "select sum(c) from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+echo "purging the history of the clone keeps the shared data"
+echo "optimize table t12_0005_clone purge" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005 at (snapshot => '$SNAPSHOT_ID')" | $MYSQL_CLIENT_CONNECT
+
+echo "flashback to the first insertion, which should contain 2 rows"
+echo "alter table t12_0005 flashback to (snapshot => '$SNAPSHOT_ID')" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+echo "the flashback is a new snapshot, the history is kept"
+echo "select count(*) from fuse_snapshot('default','t12_0005')" | $MYSQL_CLIENT_CONNECT
+
+## Drop table.
+echo "drop table t12_0005_clone" | $MYSQL_CLIENT_CONNECT
+echo "drop table t12_0005" | $MYSQL_CLIENT_CONNECT
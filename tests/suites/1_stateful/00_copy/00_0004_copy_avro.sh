@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists sample_avro;" | $MYSQL_CLIENT_CONNECT
+
+## Create table
+echo "create table sample_avro(id int, name string, score double, tags array(string), info tuple(a int, b string));" | $MYSQL_CLIENT_CONNECT
+
+# Should be <root>/tests/data/
+DATADIR=$(realpath $CURDIR/../../../data/)
+
+copy_avro_cases=(
+  # copy avro
+  "copy into sample_avro from 'fs://${DATADIR}/sample.avro' FILE_FORMAT = (type = 'AVRO')"
+  # copy avro with split size, a split may own no block
+  "copy into sample_avro from 'fs://${DATADIR}/sample.avro' FILE_FORMAT = (type = 'AVRO') split_size = 200"
+)
+
+for i in "${copy_avro_cases[@]}"; do
+  echo "$i" | $MYSQL_CLIENT_CONNECT
+  echo "select count(1), sum(id), sum(score) from sample_avro" | $MYSQL_CLIENT_CONNECT
+  echo "select count(1) from sample_avro where name = 'name_7'" | $MYSQL_CLIENT_CONNECT
+  echo "select id, tags, info from sample_avro order by id limit 3" | $MYSQL_CLIENT_CONNECT
+  echo "truncate table sample_avro" | $MYSQL_CLIENT_CONNECT
+done
+
+## Drop table
+echo "drop table if exists sample_avro;" | $MYSQL_CLIENT_CONNECT
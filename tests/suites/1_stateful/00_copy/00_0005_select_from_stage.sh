@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+aws --endpoint-url ${STORAGE_S3_ENDPOINT_URL} s3 cp s3://testbucket/admin/data/ontime_200.csv s3://testbucket/admin/stage/user/root/select_stage/ontime_200.csv >/dev/null 2>&1
+aws --endpoint-url ${STORAGE_S3_ENDPOINT_URL} s3 cp s3://testbucket/admin/data/ontime_200.parquet s3://testbucket/admin/stage/user/root/select_stage/ontime_200.parquet >/dev/null 2>&1
+
+select_from_stage_cases=(
+  # select parquet
+  "select count(1), avg(\"Year\"), sum(\"DayOfWeek\") from @~/select_stage/ (file_format => 'parquet', pattern => '.*parquet$')"
+  # select parquet by read_parquet
+  "select count(1), avg(\"Year\"), sum(\"DayOfWeek\") from read_parquet('@~/select_stage/', '.*parquet$')"
+  # row groups pruned by the filter
+  "select count(1) from @~/select_stage/ (file_format => 'parquet', pattern => '.*parquet$') where \"Year\" > 2020"
+  # select csv, the header is read as a row
+  "select count(\$1) from @~/select_stage/ (file_format => 'csv', files => ('ontime_200.csv')) as t"
+)
+
+for i in "${select_from_stage_cases[@]}"; do
+  echo "$i" | $MYSQL_CLIENT_CONNECT
+done
+
+aws --endpoint-url ${STORAGE_S3_ENDPOINT_URL} s3 rm --recursive s3://testbucket/admin/stage/user/root/select_stage/ >/dev/null 2>&1
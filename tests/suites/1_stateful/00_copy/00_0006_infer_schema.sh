@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists infer_t;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists infer_stage;" | $MYSQL_CLIENT_CONNECT
+
+printf 'id,name,score,ok\n1,a,1.5,true\n2,b,,false\n' | aws --endpoint-url ${STORAGE_S3_ENDPOINT_URL} s3 cp - s3://testbucket/admin/stage/internal/infer_stage/t.csv >/dev/null 2>&1
+printf '{"id":1,"name":"a","tags":[1,2]}\n{"id":2,"score":2.5}\n' | aws --endpoint-url ${STORAGE_S3_ENDPOINT_URL} s3 cp - s3://testbucket/admin/stage/internal/infer_stage/t.ndjson >/dev/null 2>&1
+
+echo "CREATE STAGE infer_stage FILE_FORMAT = (TYPE = CSV SKIP_HEADER = 1);" | $MYSQL_CLIENT_CONNECT
+
+infer_schema_cases=(
+  # csv, the column names are read from the header
+  "select * from infer_schema(location => '@infer_stage/t.csv')"
+  # ndjson
+  "select * from infer_schema(location => '@infer_stage/t.ndjson', file_format => 'ndjson')"
+  # positional arguments
+  "select column_name from infer_schema('@infer_stage/t.ndjson', 'ndjson') where nullable"
+  # create a table from the inferred columns and load the file into it
+  "create table infer_t using template (select * from infer_schema(location => '@infer_stage/t.ndjson', file_format => 'ndjson'))"
+  "copy into infer_t from @infer_stage files = ('t.ndjson') file_format = (type = 'ndjson')"
+  "select * from infer_t order by id"
+)
+
+for i in "${infer_schema_cases[@]}"; do
+  echo "$i" | $MYSQL_CLIENT_CONNECT
+done
+
+echo "drop table if exists infer_t;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists infer_stage;" | $MYSQL_CLIENT_CONNECT
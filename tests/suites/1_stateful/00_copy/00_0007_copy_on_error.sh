@@ -0,0 +1,39 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_on_error;" | $MYSQL_CLIENT_CONNECT
+
+## Create table
+echo "create table test_on_error(id int, name string);" | $MYSQL_CLIENT_CONNECT
+
+# Should be <root>/tests/data/
+DATADIR=$(realpath $CURDIR/../../../data/on_error)
+
+copy_on_error_cases=(
+  # the failed rows are skipped
+  "copy into test_on_error from 'fs://${DATADIR}/' PATTERN = '.*csv$' FILE_FORMAT = (type = 'CSV') ON_ERROR = CONTINUE"
+  # the files with a failed row are skipped
+  "copy into test_on_error from 'fs://${DATADIR}/' PATTERN = '.*csv$' FILE_FORMAT = (type = 'CSV') ON_ERROR = SKIP_FILE"
+  # the files with 2 failed rows are skipped
+  "copy into test_on_error from 'fs://${DATADIR}/' PATTERN = '.*csv$' FILE_FORMAT = (type = 'CSV') ON_ERROR = SKIP_FILE_2"
+  # the rows of the splits of a file are numbered in the file
+  "copy into test_on_error from 'fs://${DATADIR}/bad.ndjson' FILE_FORMAT = (type = 'NDJSON') ON_ERROR = CONTINUE split_size = 16"
+  # a file is skipped whichever split the failed row is in
+  "copy into test_on_error from 'fs://${DATADIR}/bad.ndjson' FILE_FORMAT = (type = 'NDJSON') ON_ERROR = SKIP_FILE split_size = 16"
+)
+
+for i in "${copy_on_error_cases[@]}"; do
+  # file, rows_loaded, errors_seen and first_error_line of each file
+  echo "$i" | $MYSQL_CLIENT_CONNECT | awk -F '\t' -v OFS='\t' '{ n = split($1, p, "/"); print p[n], $2, $3, $5 }' | sort
+  echo "select count(1), sum(id) from test_on_error" | $MYSQL_CLIENT_CONNECT
+  echo "truncate table test_on_error" | $MYSQL_CLIENT_CONNECT
+done
+
+# file, line and column of each failed row of the files validated
+echo "copy into test_on_error from 'fs://${DATADIR}/bad.ndjson' FILE_FORMAT = (type = 'NDJSON') VALIDATION_MODE = 'RETURN_ERRORS' split_size = 16" | $MYSQL_CLIENT_CONNECT | awk -F '\t' -v OFS='\t' '{ n = split($1, p, "/"); print p[n], $2, $3 }'
+echo "select count(1) from test_on_error" | $MYSQL_CLIENT_CONNECT
+
+## Drop table
+echo "drop table if exists test_on_error;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists sample_orc;" | $MYSQL_CLIENT_CONNECT
+
+## Create table
+echo "create table sample_orc(id int, name string, tags array(string), info tuple(a int, b string), attrs array(tuple(key string, value int)), score double);" | $MYSQL_CLIENT_CONNECT
+
+# Should be <root>/tests/data/
+DATADIR=$(realpath $CURDIR/../../../data/)
+
+# copy orc of two stripes, the ids of the columns after the nested ones are shifted by their children
+echo "copy into sample_orc from 'fs://${DATADIR}/sample_nested.orc' FILE_FORMAT = (type = 'ORC')" | $MYSQL_CLIENT_CONNECT
+echo "select count(1), sum(id), sum(score) from sample_orc" | $MYSQL_CLIENT_CONNECT
+echo "select id, name, tags, info, attrs from sample_orc order by id limit 3" | $MYSQL_CLIENT_CONNECT
+echo "select id, tags, info, attrs, score from sample_orc where id >= 5 order by id limit 2" | $MYSQL_CLIENT_CONNECT
+
+## Drop table
+echo "drop table if exists sample_orc;" | $MYSQL_CLIENT_CONNECT